@@ -0,0 +1,7 @@
+-- Add full-text search support to simple vector documents for hybrid (lexical + vector) retrieval
+-- The 'simple' configuration keeps identifiers, error codes and product names unstemmed
+ALTER TABLE simple_vector_documents
+    ADD COLUMN content_tsv TSVECTOR GENERATED ALWAYS AS (to_tsvector('simple', content)) STORED;
+
+-- Index on the partitioned parent is propagated to existing and future instance partitions
+CREATE INDEX idx_simple_vector_documents_content_tsv ON simple_vector_documents USING GIN(content_tsv);
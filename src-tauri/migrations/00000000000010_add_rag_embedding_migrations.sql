@@ -0,0 +1,32 @@
+-- Re-embed RAG instances in the background when their embedding model changes
+-- New vectors are staged in embedding_next and swapped into embedding once every chunk is done,
+-- so queries keep using the previous model's index until the migration completes
+ALTER TABLE simple_vector_documents ADD COLUMN embedding_next HALFVEC;
+
+CREATE TABLE rag_embedding_migrations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    rag_instance_id UUID NOT NULL REFERENCES rag_instances(id) ON DELETE CASCADE,
+    source_model_id UUID REFERENCES models(id) ON DELETE SET NULL,
+    target_model_id UUID NOT NULL REFERENCES models(id) ON DELETE CASCADE,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'running', 'completed', 'cancelled', 'failed')),
+    total_documents BIGINT NOT NULL DEFAULT 0,
+    processed_documents BIGINT NOT NULL DEFAULT 0,
+    error_message TEXT,
+    started_at TIMESTAMP WITH TIME ZONE,
+    completed_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW() NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW() NOT NULL
+);
+
+CREATE INDEX idx_rag_embedding_migrations_instance ON rag_embedding_migrations(rag_instance_id);
+
+-- At most one pending or running migration per instance
+CREATE UNIQUE INDEX idx_rag_embedding_migrations_active
+    ON rag_embedding_migrations(rag_instance_id)
+    WHERE status IN ('pending', 'running');
+
+CREATE TRIGGER update_rag_embedding_migrations_updated_at
+    BEFORE UPDATE ON rag_embedding_migrations
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,24 @@
+-- Model groups: a named logical model served by an ordered list of concrete models
+-- Requests fall through to the next member when a provider is rate limited, fails or times out
+CREATE TABLE model_groups (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(255) NOT NULL UNIQUE,
+    description TEXT,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW() NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW() NOT NULL
+);
+
+CREATE TABLE model_group_members (
+    model_group_id UUID NOT NULL REFERENCES model_groups(id) ON DELETE CASCADE,
+    model_id UUID NOT NULL REFERENCES models(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    PRIMARY KEY (model_group_id, model_id)
+);
+
+CREATE INDEX idx_model_group_members_order ON model_group_members(model_group_id, position);
+
+CREATE TRIGGER update_model_groups_updated_at
+    BEFORE UPDATE ON model_groups
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,13 @@
+-- Add the Ollama provider type
+-- Ollama serves models through its native /api/chat, /api/embed and /api/tags endpoints
+
+-- Recreate the provider type check with 'ollama' allowed
+ALTER TABLE providers DROP CONSTRAINT IF EXISTS providers_provider_type_check;
+
+ALTER TABLE providers
+    ADD CONSTRAINT providers_provider_type_check
+        CHECK (provider_type IN ('local', 'openai', 'anthropic', 'groq', 'gemini', 'mistral', 'deepseek', 'huggingface', 'ollama', 'custom'));
+
+-- Built-in provider pointing at the default local Ollama server
+INSERT INTO providers (name, provider_type, enabled, built_in, base_url) VALUES
+    ('Ollama', 'ollama', false, true, 'http://localhost:11434');
@@ -0,0 +1,31 @@
+-- Cache MCP prompts and resources discovered from servers, next to mcp_tools_cache
+
+ALTER TABLE mcp_servers
+    ADD COLUMN prompts_discovered_at TIMESTAMP WITH TIME ZONE,
+    ADD COLUMN resources_discovered_at TIMESTAMP WITH TIME ZONE;
+
+-- Create mcp_prompts_cache table
+CREATE TABLE mcp_prompts_cache (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    server_id UUID NOT NULL REFERENCES mcp_servers(id) ON DELETE CASCADE,
+    prompt_name VARCHAR NOT NULL,
+    prompt_description TEXT,
+    arguments JSONB NOT NULL DEFAULT '[]',
+    discovered_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    UNIQUE(server_id, prompt_name)
+);
+
+-- Create mcp_resources_cache table
+CREATE TABLE mcp_resources_cache (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    server_id UUID NOT NULL REFERENCES mcp_servers(id) ON DELETE CASCADE,
+    uri TEXT NOT NULL,
+    resource_name VARCHAR NOT NULL,
+    resource_description TEXT,
+    mime_type VARCHAR,
+    discovered_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    UNIQUE(server_id, uri)
+);
+
+CREATE INDEX idx_mcp_prompts_cache_server_id ON mcp_prompts_cache(server_id);
+CREATE INDEX idx_mcp_resources_cache_server_id ON mcp_resources_cache(server_id);
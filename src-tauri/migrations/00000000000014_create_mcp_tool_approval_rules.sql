@@ -0,0 +1,23 @@
+-- Admin-defined approval rules for MCP tool calls, evaluated before user approvals
+-- A rule matches on server, tool name pattern, user group and conditions over the call arguments
+CREATE TABLE mcp_tool_approval_rules (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(255) NOT NULL,
+    description TEXT,
+    server_id UUID REFERENCES mcp_servers(id) ON DELETE CASCADE,
+    tool_name_pattern VARCHAR NOT NULL DEFAULT '*',
+    group_id UUID REFERENCES user_groups(id) ON DELETE CASCADE,
+    conditions JSONB NOT NULL DEFAULT '[]',
+    action VARCHAR(16) NOT NULL CHECK (action IN ('approve', 'deny')),
+    priority INTEGER NOT NULL DEFAULT 0,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW() NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW() NOT NULL
+);
+
+CREATE INDEX idx_mcp_tool_approval_rules_order ON mcp_tool_approval_rules(priority DESC, created_at);
+
+CREATE TRIGGER update_mcp_tool_approval_rules_updated_at
+    BEFORE UPDATE ON mcp_tool_approval_rules
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,46 @@
+-- OAuth 2.1 authorization for remote (HTTP/SSE) MCP servers
+-- Clients are registered dynamically with the server's authorization server, once per redirect URI
+CREATE TABLE mcp_oauth_clients (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    server_id UUID NOT NULL REFERENCES mcp_servers(id) ON DELETE CASCADE,
+    issuer TEXT NOT NULL,
+    authorization_endpoint TEXT NOT NULL,
+    token_endpoint TEXT NOT NULL,
+    registration_endpoint TEXT,
+    client_id TEXT NOT NULL,
+    client_secret TEXT,
+    redirect_uri TEXT NOT NULL,
+    resource TEXT NOT NULL,
+    scope TEXT,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW() NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW() NOT NULL,
+    UNIQUE (server_id, redirect_uri)
+);
+
+-- Tokens obtained by each user for a server
+CREATE TABLE mcp_oauth_tokens (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    server_id UUID NOT NULL REFERENCES mcp_servers(id) ON DELETE CASCADE,
+    oauth_client_id UUID NOT NULL REFERENCES mcp_oauth_clients(id) ON DELETE CASCADE,
+    access_token TEXT NOT NULL,
+    refresh_token TEXT,
+    token_type VARCHAR(50) NOT NULL DEFAULT 'Bearer',
+    scope TEXT,
+    expires_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW() NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW() NOT NULL,
+    UNIQUE (user_id, server_id)
+);
+
+CREATE INDEX idx_mcp_oauth_tokens_server_id ON mcp_oauth_tokens(server_id);
+
+CREATE TRIGGER update_mcp_oauth_clients_updated_at
+    BEFORE UPDATE ON mcp_oauth_clients
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TRIGGER update_mcp_oauth_tokens_updated_at
+    BEFORE UPDATE ON mcp_oauth_tokens
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,4 @@
+-- Optional sandbox profile of stdio MCP servers, see MCPSandboxProfile
+
+ALTER TABLE mcp_servers
+    ADD COLUMN sandbox JSONB NOT NULL DEFAULT '{}';
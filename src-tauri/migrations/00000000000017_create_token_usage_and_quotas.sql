@@ -0,0 +1,48 @@
+-- Token usage of every model call, from chat responses and API proxy requests
+-- Proxy requests have no user; chat rows keep the assistant message they produced
+CREATE TABLE token_usage (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    source VARCHAR(16) NOT NULL CHECK (source IN ('chat', 'api_proxy')),
+    user_id UUID REFERENCES users(id) ON DELETE CASCADE,
+    message_id UUID REFERENCES messages(id) ON DELETE SET NULL,
+    model_id UUID REFERENCES models(id) ON DELETE SET NULL,
+    provider_id UUID REFERENCES providers(id) ON DELETE SET NULL,
+    prompt_tokens INTEGER NOT NULL DEFAULT 0,
+    completion_tokens INTEGER NOT NULL DEFAULT 0,
+    total_tokens INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW() NOT NULL
+);
+
+CREATE INDEX idx_token_usage_user_created ON token_usage(user_id, created_at);
+CREATE INDEX idx_token_usage_source_created ON token_usage(source, created_at);
+CREATE INDEX idx_token_usage_message_id ON token_usage(message_id);
+
+-- Daily or monthly limits on tokens and/or requests
+-- A user quota limits one user, a group quota the combined usage of its members and the
+-- api_proxy quota all requests to the API proxy server
+CREATE TABLE usage_quotas (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    scope VARCHAR(16) NOT NULL CHECK (scope IN ('user', 'group', 'api_proxy')),
+    user_id UUID REFERENCES users(id) ON DELETE CASCADE,
+    group_id UUID REFERENCES user_groups(id) ON DELETE CASCADE,
+    period VARCHAR(16) NOT NULL CHECK (period IN ('daily', 'monthly')),
+    max_tokens BIGINT CHECK (max_tokens >= 0),
+    max_requests INTEGER CHECK (max_requests >= 0),
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW() NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW() NOT NULL,
+    CHECK (max_tokens IS NOT NULL OR max_requests IS NOT NULL),
+    CHECK (
+        (scope = 'user' AND user_id IS NOT NULL AND group_id IS NULL)
+        OR (scope = 'group' AND group_id IS NOT NULL AND user_id IS NULL)
+        OR (scope = 'api_proxy' AND user_id IS NULL AND group_id IS NULL)
+    )
+);
+
+CREATE INDEX idx_usage_quotas_user_id ON usage_quotas(user_id);
+CREATE INDEX idx_usage_quotas_group_id ON usage_quotas(group_id);
+
+CREATE TRIGGER update_usage_quotas_updated_at
+    BEFORE UPDATE ON usage_quotas
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,19 @@
+-- Which file contributed each mention of a graph entity or relationship
+-- Entity importance, relationship weight and merged descriptions are recomputed from the
+-- remaining rows when a file is re-indexed or removed. Graphs built before this table existed
+-- have no contribution rows; re-index their files to make removals exact.
+CREATE TABLE simple_graph_contributions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    rag_instance_id UUID NOT NULL REFERENCES rag_instances(id) ON DELETE CASCADE,
+    file_id UUID NOT NULL REFERENCES files(id) ON DELETE CASCADE,
+    entity_id UUID REFERENCES simple_graph_entities(id) ON DELETE CASCADE,
+    relationship_id UUID REFERENCES simple_graph_relationships(id) ON DELETE CASCADE,
+    description TEXT,
+    weight FLOAT NOT NULL DEFAULT 1.0,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    CHECK ((entity_id IS NULL) <> (relationship_id IS NULL))
+);
+
+CREATE INDEX idx_simple_graph_contributions_instance_file ON simple_graph_contributions(rag_instance_id, file_id);
+CREATE INDEX idx_simple_graph_contributions_entity ON simple_graph_contributions(entity_id);
+CREATE INDEX idx_simple_graph_contributions_relationship ON simple_graph_contributions(relationship_id);
@@ -0,0 +1,8 @@
+-- Chunk content hashes switched from Rust's DefaultHasher, which is not stable across builds,
+-- to hex SHA-256 of the chunk text. Recompute the stored hashes so incremental re-indexing and
+-- embedding reuse match chunks indexed before the switch instead of re-embedding everything.
+UPDATE simple_vector_documents
+SET content_hash = encode(sha256(convert_to(content, 'UTF8')), 'hex');
+
+UPDATE simple_graph_chunks
+SET content_hash = encode(sha256(convert_to(content, 'UTF8')), 'hex');
@@ -0,0 +1,28 @@
+-- File filter shared by the RAG search queries (similarity, full-text and graph chunk lookup).
+-- NULL criteria match every file; tags must all be present in the file's rag_metadata.tags.
+CREATE OR REPLACE FUNCTION rag_file_matches_filter(
+    instance_id_param UUID,
+    file_id_param UUID,
+    file_ids_param UUID[],
+    file_types_param TEXT[],
+    uploaded_after_param TIMESTAMPTZ,
+    uploaded_before_param TIMESTAMPTZ,
+    tags_param TEXT[]
+)
+RETURNS BOOLEAN AS $$
+    SELECT EXISTS (
+        SELECT 1
+        FROM rag_instance_files rif
+        JOIN files f ON f.id = rif.file_id
+        WHERE rif.rag_instance_id = instance_id_param
+          AND rif.file_id = file_id_param
+          AND (file_ids_param IS NULL OR rif.file_id = ANY(file_ids_param))
+          AND (file_types_param IS NULL
+               OR lower(f.mime_type) = ANY(file_types_param)
+               OR lower(substring(f.filename from '\.([^.]+)$')) = ANY(file_types_param))
+          AND (uploaded_after_param IS NULL OR f.created_at >= uploaded_after_param)
+          AND (uploaded_before_param IS NULL OR f.created_at < uploaded_before_param)
+          AND (tags_param IS NULL
+               OR COALESCE(rif.rag_metadata -> 'tags', '[]'::jsonb) ?& tags_param)
+    )
+$$ LANGUAGE sql STABLE;
@@ -0,0 +1,547 @@
+//! Anthropic Messages API on top of the proxy's models
+//!
+//! `POST {prefix}/messages` takes requests shaped like Anthropic's Messages API, runs them on
+//! the resolved model through the same `AIModel` layer as the chat UI and answers with an
+//! Anthropic message, or its SSE events when `stream` is set. Any enabled model, local ones
+//! included, can then be used by Anthropic SDK clients.
+//!
+//! Text, tool use and tool result blocks are translated. Thinking blocks of earlier turns are
+//! dropped and other blocks (images, documents) are rejected. `usage` holds the counts the
+//! provider reports at the end of the stream, so it is zero in `message_start`.
+
+use axum::http::StatusCode;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use super::ProxyError;
+use crate::ai::core::providers::{
+    ChatMessage, ContentPart, MessageContent, StreamingChunk, ToolDefinition, Usage,
+};
+use crate::ai::SimplifiedChatRequest;
+use crate::database::models::model::ModelParameters;
+
+#[derive(Debug, Deserialize)]
+pub struct MessagesRequest {
+    pub messages: Vec<Message>,
+    pub system: Option<TextOrBlocks>,
+    pub max_tokens: Option<i32>,
+    pub temperature: Option<f32>,
+    pub top_p: Option<f32>,
+    pub top_k: Option<i32>,
+    pub stop_sequences: Option<Vec<String>>,
+    #[serde(default)]
+    pub stream: bool,
+    pub tools: Option<Vec<Tool>>,
+    pub tool_choice: Option<ToolChoice>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Message {
+    pub role: String,
+    pub content: TextOrBlocks,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum TextOrBlocks {
+    Text(String),
+    Blocks(Vec<ContentBlock>),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentBlock {
+    Text {
+        text: String,
+    },
+    ToolUse {
+        id: String,
+        name: String,
+        input: Value,
+    },
+    ToolResult {
+        tool_use_id: String,
+        content: Option<TextOrBlocks>,
+        #[serde(default)]
+        is_error: bool,
+    },
+    Thinking {},
+    RedactedThinking {},
+    #[serde(other)]
+    Unsupported,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Tool {
+    pub name: String,
+    pub description: Option<String>,
+    pub input_schema: Option<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ToolChoice {
+    #[serde(rename = "type")]
+    pub choice_type: String,
+}
+
+impl TextOrBlocks {
+    /// The text of the blocks, joined by newlines
+    fn to_text(&self) -> Result<String, ProxyError> {
+        match self {
+            TextOrBlocks::Text(text) => Ok(text.clone()),
+            TextOrBlocks::Blocks(blocks) => {
+                let mut texts = Vec::new();
+                for block in blocks {
+                    match block {
+                        ContentBlock::Text { text } => texts.push(text.as_str()),
+                        _ => return Err(unsupported_block()),
+                    }
+                }
+                Ok(texts.join("\n"))
+            }
+        }
+    }
+}
+
+fn unsupported_block() -> ProxyError {
+    ProxyError::InvalidRequest(
+        "only text, tool_use and tool_result content blocks are supported".to_string(),
+    )
+}
+
+impl MessagesRequest {
+    /// The chat request running this request on a model
+    pub fn to_chat_request(&self) -> Result<SimplifiedChatRequest, ProxyError> {
+        let mut messages = Vec::new();
+
+        if let Some(system) = &self.system {
+            messages.push(ChatMessage::text("system", &system.to_text()?));
+        }
+
+        for message in &self.messages {
+            if message.role != "user" && message.role != "assistant" {
+                return Err(ProxyError::InvalidRequest(format!(
+                    "unknown message role '{}'",
+                    message.role
+                )));
+            }
+            translate_message(message, &mut messages)?;
+        }
+
+        // "none" forbids tool use, which is the same as offering no tools
+        let tools = match (&self.tools, &self.tool_choice) {
+            (_, Some(choice)) if choice.choice_type == "none" => None,
+            (Some(tools), _) if !tools.is_empty() => Some(
+                tools
+                    .iter()
+                    .map(|tool| {
+                        let input_schema = tool.input_schema.clone().ok_or_else(|| {
+                            ProxyError::InvalidRequest(format!(
+                                "tool '{}' has no input_schema; server tools are not supported",
+                                tool.name
+                            ))
+                        })?;
+                        Ok(ToolDefinition {
+                            name: tool.name.clone(),
+                            description: tool.description.clone(),
+                            input_schema,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, ProxyError>>()?,
+            ),
+            _ => None,
+        };
+
+        Ok(SimplifiedChatRequest {
+            messages,
+            stream: true,
+            tools,
+            parameters: Some(ModelParameters {
+                max_tokens: self.max_tokens,
+                temperature: self.temperature,
+                top_k: self.top_k,
+                top_p: self.top_p,
+                stop: self.stop_sequences.clone(),
+                ..Default::default()
+            }),
+        })
+    }
+}
+
+/// Add the chat messages of an Anthropic message. Tool results of a user turn become a
+/// `tool` message, as in conversations, followed by the rest of the turn.
+fn translate_message(
+    message: &Message,
+    messages: &mut Vec<ChatMessage>,
+) -> Result<(), ProxyError> {
+    let blocks = match &message.content {
+        TextOrBlocks::Text(text) => {
+            messages.push(ChatMessage::text(&message.role, text));
+            return Ok(());
+        }
+        TextOrBlocks::Blocks(blocks) => blocks,
+    };
+
+    let mut tool_results = Vec::new();
+    let mut parts = Vec::new();
+    for block in blocks {
+        match block {
+            ContentBlock::Text { text } => parts.push(ContentPart::Text(text.clone())),
+            ContentBlock::ToolUse { id, name, input } => parts.push(ContentPart::ToolUse {
+                id: id.clone(),
+                name: name.clone(),
+                input: input.clone(),
+            }),
+            ContentBlock::ToolResult {
+                tool_use_id,
+                content,
+                is_error,
+            } => {
+                let output = match content {
+                    Some(content) => content.to_text()?,
+                    None => String::new(),
+                };
+                tool_results.push(ContentPart::ToolResult {
+                    call_id: tool_use_id.clone(),
+                    output: if *is_error {
+                        format!("Error: {}", output)
+                    } else {
+                        output
+                    },
+                });
+            }
+            ContentBlock::Thinking {} | ContentBlock::RedactedThinking {} => {}
+            ContentBlock::Unsupported => return Err(unsupported_block()),
+        }
+    }
+
+    if !tool_results.is_empty() {
+        messages.push(ChatMessage {
+            role: "tool".to_string(),
+            content: MessageContent::Multimodal(tool_results),
+        });
+    }
+
+    match parts.as_slice() {
+        [] => {}
+        [ContentPart::Text(text)] => messages.push(ChatMessage::text(&message.role, text)),
+        _ => messages.push(ChatMessage {
+            role: message.role.clone(),
+            content: MessageContent::Multimodal(parts),
+        }),
+    }
+
+    Ok(())
+}
+
+/// Anthropic stop reason of a provider finish reason
+fn stop_reason(finish_reason: Option<&str>, used_tools: bool) -> &'static str {
+    if used_tools {
+        return "tool_use";
+    }
+    match finish_reason.map(|reason| reason.to_lowercase()).as_deref() {
+        Some("length") | Some("max_tokens") => "max_tokens",
+        Some("stop_sequence") => "stop_sequence",
+        _ => "end_turn",
+    }
+}
+
+fn usage_json(usage: Option<&Usage>) -> Value {
+    json!({
+        "input_tokens": usage.and_then(|u| u.prompt_tokens).unwrap_or(0),
+        "output_tokens": usage.and_then(|u| u.completion_tokens).unwrap_or(0),
+    })
+}
+
+/// Anthropic error type of a response status
+pub fn error_type(status: StatusCode) -> &'static str {
+    match status {
+        StatusCode::BAD_REQUEST => "invalid_request_error",
+        StatusCode::UNAUTHORIZED => "authentication_error",
+        StatusCode::FORBIDDEN => "permission_error",
+        StatusCode::NOT_FOUND => "not_found_error",
+        StatusCode::TOO_MANY_REQUESTS => "rate_limit_error",
+        StatusCode::SERVICE_UNAVAILABLE => "overloaded_error",
+        _ => "api_error",
+    }
+}
+
+/// Body of an Anthropic error response or `error` event
+pub fn error_body(error_type: &str, message: &str) -> Value {
+    json!({
+        "type": "error",
+        "error": { "type": error_type, "message": message }
+    })
+}
+
+/// Builds the Anthropic answer from the chunks of a streamed chat, either as SSE events while
+/// the chunks come in or as a single message at the end
+pub struct MessageEncoder {
+    id: String,
+    model: String,
+    content: Vec<Value>,
+    text_block_open: bool,
+    finish_reason: Option<String>,
+    usage: Option<Usage>,
+}
+
+impl MessageEncoder {
+    pub fn new(model: String) -> Self {
+        Self {
+            id: format!("msg_{}", uuid::Uuid::new_v4().simple()),
+            model,
+            content: Vec::new(),
+            text_block_open: false,
+            finish_reason: None,
+            usage: None,
+        }
+    }
+
+    /// Token usage reported by the provider so far
+    pub fn usage(&self) -> Option<&Usage> {
+        self.usage.as_ref()
+    }
+
+    /// The `message_start` event
+    pub fn start(&self) -> Vec<(&'static str, Value)> {
+        vec![(
+            "message_start",
+            json!({
+                "type": "message_start",
+                "message": {
+                    "id": self.id,
+                    "type": "message",
+                    "role": "assistant",
+                    "model": self.model,
+                    "content": [],
+                    "stop_reason": null,
+                    "stop_sequence": null,
+                    "usage": usage_json(None),
+                }
+            }),
+        )]
+    }
+
+    /// Add a chunk, returning the events it produces
+    pub fn push(&mut self, chunk: StreamingChunk) -> Vec<(&'static str, Value)> {
+        let mut events = Vec::new();
+
+        if let Some(text) = chunk.content.filter(|text| !text.is_empty()) {
+            if !self.text_block_open {
+                self.text_block_open = true;
+                self.content.push(json!({ "type": "text", "text": "" }));
+                events.push((
+                    "content_block_start",
+                    json!({
+                        "type": "content_block_start",
+                        "index": self.content.len() - 1,
+                        "content_block": { "type": "text", "text": "" }
+                    }),
+                ));
+            }
+
+            let index = self.content.len() - 1;
+            if let Some(Value::String(block_text)) = self.content[index].get_mut("text") {
+                block_text.push_str(&text);
+            }
+            events.push((
+                "content_block_delta",
+                json!({
+                    "type": "content_block_delta",
+                    "index": index,
+                    "delta": { "type": "text_delta", "text": text }
+                }),
+            ));
+        }
+
+        // Providers hand over tool calls whole, so each one is a complete block
+        if let Some(tool_use) = chunk.tool_use {
+            events.extend(self.close_text_block());
+
+            let index = self.content.len();
+            self.content.push(json!({
+                "type": "tool_use",
+                "id": tool_use.id,
+                "name": tool_use.name,
+                "input": tool_use.input,
+            }));
+            events.push((
+                "content_block_start",
+                json!({
+                    "type": "content_block_start",
+                    "index": index,
+                    "content_block": {
+                        "type": "tool_use",
+                        "id": tool_use.id,
+                        "name": tool_use.name,
+                        "input": {}
+                    }
+                }),
+            ));
+            events.push((
+                "content_block_delta",
+                json!({
+                    "type": "content_block_delta",
+                    "index": index,
+                    "delta": {
+                        "type": "input_json_delta",
+                        "partial_json": tool_use.input.to_string()
+                    }
+                }),
+            ));
+            events.push((
+                "content_block_stop",
+                json!({ "type": "content_block_stop", "index": index }),
+            ));
+        }
+
+        if chunk.finish_reason.is_some() {
+            self.finish_reason = chunk.finish_reason;
+        }
+        if chunk.usage.is_some() {
+            self.usage = chunk.usage;
+        }
+
+        events
+    }
+
+    fn close_text_block(&mut self) -> Vec<(&'static str, Value)> {
+        if !self.text_block_open {
+            return Vec::new();
+        }
+        self.text_block_open = false;
+        vec![(
+            "content_block_stop",
+            json!({ "type": "content_block_stop", "index": self.content.len() - 1 }),
+        )]
+    }
+
+    fn stop_reason(&self) -> &'static str {
+        let used_tools = self
+            .content
+            .iter()
+            .any(|block| block["type"] == "tool_use");
+        stop_reason(self.finish_reason.as_deref(), used_tools)
+    }
+
+    /// The events closing the message
+    pub fn finish(&mut self) -> Vec<(&'static str, Value)> {
+        let mut events = self.close_text_block();
+        events.push((
+            "message_delta",
+            json!({
+                "type": "message_delta",
+                "delta": { "stop_reason": self.stop_reason(), "stop_sequence": null },
+                "usage": usage_json(self.usage.as_ref())
+            }),
+        ));
+        events.push(("message_stop", json!({ "type": "message_stop" })));
+        events
+    }
+
+    /// The whole message, for requests that are not streamed
+    pub fn into_message(self) -> Value {
+        let stop_reason = self.stop_reason();
+        json!({
+            "id": self.id,
+            "type": "message",
+            "role": "assistant",
+            "model": self.model,
+            "content": self.content,
+            "stop_reason": stop_reason,
+            "stop_sequence": null,
+            "usage": usage_json(self.usage.as_ref()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::core::providers::ToolUse;
+
+    #[test]
+    fn translates_tool_turns() {
+        let request: MessagesRequest = serde_json::from_value(json!({
+            "model": "local",
+            "max_tokens": 512,
+            "system": [{ "type": "text", "text": "Be brief." }],
+            "messages": [
+                { "role": "user", "content": "Weather in Paris?" },
+                { "role": "assistant", "content": [
+                    { "type": "thinking", "thinking": "...", "signature": "x" },
+                    { "type": "tool_use", "id": "toolu_1", "name": "weather", "input": { "city": "Paris" } }
+                ]},
+                { "role": "user", "content": [
+                    { "type": "tool_result", "tool_use_id": "toolu_1", "content": "18C" },
+                    { "type": "text", "text": "Thanks" }
+                ]}
+            ],
+            "tools": [{ "name": "weather", "input_schema": { "type": "object" } }]
+        }))
+        .unwrap();
+
+        let chat = request.to_chat_request().unwrap();
+        let roles: Vec<&str> = chat.messages.iter().map(|m| m.role.as_str()).collect();
+        assert_eq!(roles, ["system", "user", "assistant", "tool", "user"]);
+        assert!(matches!(
+            &chat.messages[3].content,
+            MessageContent::Multimodal(parts)
+                if matches!(&parts[0], ContentPart::ToolResult { call_id, output } if call_id == "toolu_1" && output == "18C")
+        ));
+        assert_eq!(chat.tools.unwrap().len(), 1);
+        assert_eq!(chat.parameters.unwrap().max_tokens, Some(512));
+    }
+
+    #[test]
+    fn rejects_image_blocks() {
+        let request: MessagesRequest = serde_json::from_value(json!({
+            "messages": [{ "role": "user", "content": [
+                { "type": "image", "source": { "type": "base64", "media_type": "image/png", "data": "" } }
+            ]}]
+        }))
+        .unwrap();
+
+        assert!(request.to_chat_request().is_err());
+    }
+
+    #[test]
+    fn encodes_text_then_tool_use() {
+        let mut encoder = MessageEncoder::new("local".to_string());
+        encoder.push(StreamingChunk {
+            content: Some("Checking".to_string()),
+            finish_reason: None,
+            tool_use: None,
+            usage: None,
+            thinking: None,
+            thinking_signature: None,
+        });
+        let events = encoder.push(StreamingChunk {
+            content: None,
+            finish_reason: Some("tool_calls".to_string()),
+            tool_use: Some(ToolUse {
+                id: "call_1".to_string(),
+                name: "weather".to_string(),
+                input: json!({ "city": "Paris" }),
+            }),
+            usage: Some(Usage {
+                prompt_tokens: Some(12),
+                completion_tokens: Some(7),
+                total_tokens: Some(19),
+            }),
+            thinking: None,
+            thinking_signature: None,
+        });
+        let names: Vec<&str> = events.iter().map(|(name, _)| *name).collect();
+        assert_eq!(
+            names,
+            ["content_block_stop", "content_block_start", "content_block_delta", "content_block_stop"]
+        );
+
+        let message = encoder.into_message();
+        assert_eq!(message["stop_reason"], "tool_use");
+        assert_eq!(message["content"][0]["text"], "Checking");
+        assert_eq!(message["content"][1]["input"]["city"], "Paris");
+        assert_eq!(message["usage"]["output_tokens"], 7);
+    }
+}
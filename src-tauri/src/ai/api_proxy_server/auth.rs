@@ -13,10 +13,12 @@ pub async fn validate_api_key(headers: &HeaderMap, expected_key: &str) -> Result
         return Ok(()); // No API key required
     }
 
+    // OpenAI clients send a bearer token, Anthropic clients an `x-api-key` header
     let auth_header = headers
         .get("Authorization")
         .and_then(|h| h.to_str().ok())
-        .and_then(|h| h.strip_prefix("Bearer "));
+        .and_then(|h| h.strip_prefix("Bearer "))
+        .or_else(|| headers.get("x-api-key").and_then(|h| h.to_str().ok()));
 
     match auth_header {
         Some(key) if key == expected_key => Ok(()),
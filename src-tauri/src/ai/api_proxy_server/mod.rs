@@ -1,9 +1,11 @@
+pub mod anthropic;
 pub mod auth;
 pub mod logging;
 pub mod registry;
 pub mod router;
 pub mod security;
 pub mod server;
+pub mod usage;
 
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -288,6 +290,9 @@ pub enum ProxyError {
     #[error("Proxy server not enabled")]
     ProxyDisabled,
 
+    #[error("{0}")]
+    QuotaExceeded(String),
+
     #[error("Internal error")]
     InternalError,
 }
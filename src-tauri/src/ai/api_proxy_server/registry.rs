@@ -3,12 +3,14 @@ use uuid::Uuid;
 
 use super::ProxyError;
 use crate::database::models::api_proxy_server_model::ModelServerEntry;
-use crate::database::queries::api_proxy_server_models;
+use crate::database::queries::{api_proxy_server_models, model_groups};
 
 #[derive(Debug)]
 pub struct ModelRegistry {
     enabled_models: HashMap<Uuid, ModelServerEntry>,
     alias_map: HashMap<String, Uuid>,
+    /// Model group name -> members enabled in the proxy, in failover order
+    group_map: HashMap<String, Vec<Uuid>>,
     default_model: Option<Uuid>,
 }
 
@@ -17,6 +19,7 @@ impl ModelRegistry {
         let mut registry = Self {
             enabled_models: HashMap::new(),
             alias_map: HashMap::new(),
+            group_map: HashMap::new(),
             default_model: None,
         };
 
@@ -32,6 +35,7 @@ impl ModelRegistry {
 
         self.enabled_models.clear();
         self.alias_map.clear();
+        self.group_map.clear();
         self.default_model = None;
 
         for proxy_model in enabled_models {
@@ -55,22 +59,45 @@ impl ModelRegistry {
             }
         }
 
+        // Model groups only route to members that are enabled in the proxy
+        for group in model_groups::list_enabled_model_groups().await? {
+            let members: Vec<Uuid> = group
+                .model_ids
+                .into_iter()
+                .filter(|model_id| self.enabled_models.contains_key(model_id))
+                .collect();
+            if !members.is_empty() {
+                self.group_map.insert(group.name, members);
+            }
+        }
+
         tracing::info!(
-            "Loaded {} enabled models for API proxy",
-            self.enabled_models.len()
+            "Loaded {} enabled models and {} model groups for API proxy",
+            self.enabled_models.len(),
+            self.group_map.len()
         );
         Ok(())
     }
 
-    pub async fn resolve_model_identifier(&self, identifier: &str) -> Result<Uuid, ProxyError> {
+    /// Resolve a model identifier (UUID, alias or model group name) to the models that may
+    /// serve the request, in failover order
+    pub async fn resolve_model_identifier(
+        &self,
+        identifier: &str,
+    ) -> Result<Vec<Uuid>, ProxyError> {
         // Try to parse as UUID first
         if let Ok(uuid) = Uuid::parse_str(identifier) {
-            return Ok(uuid);
+            return Ok(vec![uuid]);
         }
 
         // Try to resolve as alias
         if let Some(&model_id) = self.alias_map.get(identifier) {
-            return Ok(model_id);
+            return Ok(vec![model_id]);
+        }
+
+        // Try to resolve as model group
+        if let Some(members) = self.group_map.get(identifier) {
+            return Ok(members.clone());
         }
 
         Err(ProxyError::ModelNotFound(format!(
@@ -91,6 +118,10 @@ impl ModelRegistry {
         self.enabled_models.values().cloned().collect()
     }
 
+    pub fn list_model_group_names(&self) -> Vec<String> {
+        self.group_map.keys().cloned().collect()
+    }
+
     pub async fn get_model_display_name(&self, model_id: &Uuid) -> String {
         // Return alias if available, otherwise return UUID
         if let Some(entry) = self.enabled_models.get(model_id) {
@@ -3,7 +3,9 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
+use super::usage::check_api_proxy_quota;
 use super::{log_request, ModelRegistry, ProxyError};
+use crate::ai::{FailoverChatStream, FailoverResponse, ForwardEndpoint, SimplifiedChatRequest};
 use crate::database::queries::models;
 
 #[derive(Debug)]
@@ -38,120 +40,131 @@ impl RequestRouter {
             }));
         }
 
-        // 3. Return aggregated models list with aliases
+        // 3. Model groups are addressed by name
+        for group_name in registry.list_model_group_names() {
+            models_data.push(serde_json::json!({
+                "id": group_name,
+                "object": "model",
+                "created": chrono::Utc::now().timestamp(),
+                "owned_by": "api-proxy-server"
+            }));
+        }
+
+        // 4. Return aggregated models list with aliases
         Ok(serde_json::json!({
             "object": "list",
             "data": models_data
         }))
     }
 
-    async fn extract_or_default_model_id(
+    async fn extract_or_default_model_ids(
         &self,
-        request: &mut serde_json::Value,
-    ) -> Result<(Uuid, String), ProxyError> {
+        request: &serde_json::Value,
+    ) -> Result<(Vec<Uuid>, String), ProxyError> {
+        let registry = self.registry.read().await;
+
         // Try to get model from request
-        let model_str_opt = request
-            .get("model")
-            .and_then(|m| m.as_str())
-            .map(|s| s.to_string());
-
-        if let Some(model_str) = model_str_opt {
-            // Resolve model identifier (UUID or alias) to actual UUID
-            let registry = self.registry.read().await;
-            let model_id = registry.resolve_model_identifier(&model_str).await?;
-
-            // Get the actual model name from database for provider compatibility
-            let model = models::get_model_by_id(model_id)
-                .await
-                .map_err(|e| ProxyError::DatabaseError(e.to_string()))?
-                .ok_or(ProxyError::ModelNotFound(model_id.to_string()))?;
+        if let Some(model_str) = request.get("model").and_then(|m| m.as_str()) {
+            // Resolve model identifier (UUID, alias or model group) to candidate models
+            let model_ids = registry.resolve_model_identifier(model_str).await?;
+
+            // Check if the models are enabled in proxy
+            if let Some(model_id) = model_ids.iter().find(|id| !registry.is_model_enabled(id)) {
+                return Err(ProxyError::ModelNotInProxy(*model_id));
+            }
 
-            // Update request with actual model name for provider compatibility
-            request["model"] = serde_json::json!(model.name);
-            Ok((model_id, model_str))
+            Ok((model_ids, model_str.to_string()))
         } else {
             // No model specified, use default
-            let registry = self.registry.read().await;
             let default_model = registry
                 .get_default_model()
                 .await
                 .ok_or(ProxyError::NoDefaultModel)?;
+            let display_name = registry.get_model_display_name(&default_model).await;
+
+            Ok((vec![default_model], display_name))
+        }
+    }
 
-            // Get the actual model name from database for provider compatibility
-            let model = models::get_model_by_id(default_model)
+    /// Surface unknown models before contacting any provider
+    async fn check_models_exist(&self, model_ids: &[Uuid]) -> Result<(), ProxyError> {
+        for model_id in model_ids {
+            models::get_model_by_id(*model_id)
                 .await
                 .map_err(|e| ProxyError::DatabaseError(e.to_string()))?
-                .ok_or(ProxyError::ModelNotFound(default_model.to_string()))?;
-
-            // Add default model name to request
-            request["model"] = serde_json::json!(model.name);
-            Ok((default_model, default_model.to_string()))
+                .ok_or(ProxyError::ModelNotFound(model_id.to_string()))?;
         }
+        Ok(())
+    }
+
+    /// Forward a request to the candidate models, falling through to the next one on transient
+    /// failures. The `model` field is rewritten to each candidate's provider model name.
+    async fn forward_to_models(
+        &self,
+        model_ids: &[Uuid],
+        endpoint: ForwardEndpoint,
+        request: serde_json::Value,
+    ) -> Result<FailoverResponse, ProxyError> {
+        check_api_proxy_quota().await?;
+        self.check_models_exist(model_ids).await?;
+
+        crate::ai::forward_request_with_failover(model_ids, endpoint, request)
+            .await
+            .map_err(|e| ProxyError::ServerUnreachable(e.to_string()))
     }
 
     /// Forward chat completion request to appropriate provider
     pub async fn forward_chat_request(
         &self,
-        mut request: serde_json::Value,
-    ) -> Result<reqwest::Response, ProxyError> {
-        // 1. Extract model identifier and resolve to UUID
-        let (model_id, _) = self.extract_or_default_model_id(&mut request).await?;
+        request: serde_json::Value,
+    ) -> Result<FailoverResponse, ProxyError> {
+        // 1. Resolve model identifier to candidate models
+        let (model_ids, display_name) = self.extract_or_default_model_ids(&request).await?;
 
         // 2. Log the request
-        let registry = self.registry.read().await;
-        let display_name = registry.get_model_display_name(&model_id).await;
         log_request("POST", "/chat/completions", "proxy", Some(&display_name));
 
-        // 3. Check if model is enabled in proxy
-        if !registry.is_model_enabled(&model_id) {
-            return Err(ProxyError::ModelNotInProxy(model_id));
-        }
-        drop(registry); // Release the lock early
-
-        // 4. Create AI model using simplified factory approach
-        let ai_model = crate::ai::model_manager::model_factory::create_ai_model(model_id)
+        // 3. Forward request, failing over across model group members
+        self.forward_to_models(&model_ids, ForwardEndpoint::ChatCompletions, request)
             .await
-            .map_err(|e| ProxyError::ServerUnreachable(e.to_string()))?;
-
-        // 5. Forward request using provider's implementation
-        let response = ai_model
-            .forward_chat_request(request)
-            .await
-            .map_err(|e| ProxyError::ServerUnreachable(e.to_string()))?;
-
-        Ok(response)
     }
 
     /// Forward embeddings request to appropriate provider
     pub async fn forward_embeddings_request(
         &self,
-        mut request: serde_json::Value,
-    ) -> Result<reqwest::Response, ProxyError> {
-        // 1. Extract model identifier and resolve to UUID
-        let (model_id, _) = self.extract_or_default_model_id(&mut request).await?;
+        request: serde_json::Value,
+    ) -> Result<FailoverResponse, ProxyError> {
+        // 1. Resolve model identifier to candidate models
+        let (model_ids, display_name) = self.extract_or_default_model_ids(&request).await?;
 
         // 2. Log the request
-        let registry = self.registry.read().await;
-        let display_name = registry.get_model_display_name(&model_id).await;
         log_request("POST", "/embeddings", "proxy", Some(&display_name));
 
-        // 3. Check if model is enabled in proxy
-        if !registry.is_model_enabled(&model_id) {
-            return Err(ProxyError::ModelNotInProxy(model_id));
-        }
-        drop(registry); // Release the lock early
-
-        // 4. Create AI model using simplified factory approach  
-        let ai_model = crate::ai::model_manager::model_factory::create_ai_model(model_id)
+        // 3. Forward request, failing over across model group members
+        self.forward_to_models(&model_ids, ForwardEndpoint::Embeddings, request)
             .await
-            .map_err(|e| ProxyError::ServerUnreachable(e.to_string()))?;
+    }
+
+    /// Start a streaming chat for an Anthropic Messages API request. Returns the stream and
+    /// the name the model was requested by.
+    pub async fn start_messages_stream(
+        &self,
+        request: &serde_json::Value,
+        chat_request: SimplifiedChatRequest,
+    ) -> Result<(FailoverChatStream, String), ProxyError> {
+        // 1. Resolve model identifier to candidate models
+        let (model_ids, display_name) = self.extract_or_default_model_ids(request).await?;
+
+        // 2. Log the request
+        log_request("POST", "/messages", "proxy", Some(&display_name));
 
-        // 5. Forward request using provider's implementation
-        let response = ai_model
-            .forward_chat_request(request)
+        // 3. Start the chat, failing over across model group members
+        check_api_proxy_quota().await?;
+        self.check_models_exist(&model_ids).await?;
+        let stream = crate::ai::chat_stream_with_failover(&model_ids, chat_request)
             .await
             .map_err(|e| ProxyError::ServerUnreachable(e.to_string()))?;
 
-        Ok(response)
+        Ok((stream, display_name))
     }
 }
@@ -3,22 +3,29 @@ use axum::{
     extract::{ConnectInfo, Extension},
     http::StatusCode,
     middleware,
-    response::{IntoResponse, Json, Response},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Json, Response,
+    },
     routing::{get, post},
     Router,
 };
-use futures_util::TryStreamExt;
+use futures_util::{StreamExt, TryStreamExt};
 use serde_json;
+use std::convert::Infallible;
 use std::net::SocketAddr;
 use std::sync::Arc;
 use std::thread;
 use tokio::sync::{oneshot, RwLock};
 use tower_http::cors::CorsLayer;
 
+use super::anthropic::{self, MessageEncoder, MessagesRequest};
+use super::usage::{self, UsageTracker};
 use super::{
     auth_middleware, configure_logging, host_validation_middleware, log_response, ModelRegistry,
     ProxyError, RequestRouter, SecurityValidator,
 };
+use crate::ai::FailoverResponse;
 use crate::database::models::api_proxy_server_model::*;
 
 #[derive(Debug)]
@@ -108,6 +115,7 @@ impl ApiProxyServer {
                 &format!("{}/chat/completions", config.prefix),
                 post(handle_chat_completions),
             )
+            .route(&format!("{}/messages", config.prefix), post(handle_messages))
             .route(
                 &format!("{}/embeddings", config.prefix),
                 post(handle_embeddings),
@@ -134,6 +142,7 @@ impl ApiProxyServer {
 
         tracing::info!("API Proxy Server starting on {} with routes:", bind_addr);
         tracing::info!("  POST {}/chat/completions", config.prefix);
+        tracing::info!("  POST {}/messages", config.prefix);
         tracing::info!("  GET {}/models", config.prefix);
         tracing::info!("  GET {}/health", config.prefix);
 
@@ -227,7 +236,7 @@ async fn handle_chat_completions(
     let _client_ip = remote_addr.ip().to_string();
 
     let result = match router.forward_chat_request(request).await {
-        Ok(response) => {
+        Ok(FailoverResponse { model, response }) => {
             // Extract response components
             let status = response.status();
             let headers = response.headers().clone();
@@ -237,6 +246,14 @@ async fn handle_chat_completions(
                 .bytes_stream()
                 .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e));
 
+            // Record the usage of successful responses once their body has been passed on
+            let body = if status.is_success() {
+                let tracker = UsageTracker::new(model.model_id(), model.provider_id());
+                Body::from_stream(usage::track_usage(stream, tracker))
+            } else {
+                Body::from_stream(stream)
+            };
+
             // Build Axum response with original headers and status
             let mut response_builder = Response::builder().status(status);
 
@@ -246,7 +263,7 @@ async fn handle_chat_completions(
             }
 
             // Return response with streaming body
-            match response_builder.body(Body::from_stream(stream)) {
+            match response_builder.body(body) {
                 Ok(response) => {
                     let duration = start_time.elapsed();
                     log_response(
@@ -263,28 +280,17 @@ async fn handle_chat_completions(
         Err(e) => {
             tracing::error!("Chat request failed: {}", e);
             let duration = start_time.elapsed();
-            let status_code = match e {
-                ProxyError::ModelNotInProxy(_)
-                | ProxyError::ModelNotFound(_)
-                | ProxyError::NoDefaultModel => StatusCode::NOT_FOUND,
-                ProxyError::Unauthorized => StatusCode::UNAUTHORIZED,
-                ProxyError::HostNotTrusted(_) => StatusCode::FORBIDDEN,
-                ProxyError::InvalidRequest(_)
-                | ProxyError::InvalidClientIP(_)
-                | ProxyError::InvalidCIDR(_) => StatusCode::BAD_REQUEST,
-                ProxyError::LocalModelNotRunning(_)
-                | ProxyError::ServerUnreachable(_)
-                | ProxyError::RemoteProviderMissingBaseUrl(_)
-                | ProxyError::ProxyDisabled => StatusCode::SERVICE_UNAVAILABLE,
-                _ => StatusCode::INTERNAL_SERVER_ERROR,
-            };
+            let status_code = proxy_error_status(&e);
             log_response(
                 "POST",
                 "/chat/completions",
                 status_code.as_u16(),
                 duration.as_millis() as u64,
             );
-            create_error_response(&e.to_string())
+            match &e {
+                ProxyError::QuotaExceeded(message) => create_quota_error_response(message),
+                _ => create_error_response(&e.to_string()),
+            }
         }
     };
 
@@ -309,6 +315,130 @@ fn create_error_response(message: &str) -> Response<Body> {
         .unwrap()
 }
 
+fn create_quota_error_response(message: &str) -> Response<Body> {
+    let error_response = serde_json::json!({
+        "error": {
+            "message": message,
+            "type": "proxy_error",
+            "code": "quota_exceeded"
+        }
+    });
+
+    Response::builder()
+        .status(StatusCode::TOO_MANY_REQUESTS)
+        .header("content-type", "application/json")
+        .body(Body::from(
+            serde_json::to_string(&error_response).unwrap_or_default(),
+        ))
+        .unwrap()
+}
+
+async fn handle_messages(
+    ConnectInfo(remote_addr): ConnectInfo<SocketAddr>,
+    Extension(router): Extension<Arc<RequestRouter>>,
+    Json(request): Json<serde_json::Value>,
+) -> Response {
+    let start_time = std::time::Instant::now();
+    let _client_ip = remote_addr.ip().to_string();
+
+    let messages_request: MessagesRequest = match serde_json::from_value(request.clone()) {
+        Ok(messages_request) => messages_request,
+        Err(e) => {
+            return create_anthropic_error_response(
+                ProxyError::InvalidRequest(e.to_string()),
+                start_time,
+            )
+        }
+    };
+
+    let started = match messages_request.to_chat_request() {
+        Ok(chat_request) => router.start_messages_stream(&request, chat_request).await,
+        Err(e) => Err(e),
+    };
+    let (chat, model) = match started {
+        Ok(started) => started,
+        Err(e) => return create_anthropic_error_response(e, start_time),
+    };
+
+    let mut encoder = MessageEncoder::new(model);
+    let (model_id, provider_id) = (chat.model.model_id(), chat.model.provider_id());
+    let mut chunks = chat.stream;
+
+    if messages_request.stream {
+        let stream = async_stream::stream! {
+            for (name, data) in encoder.start() {
+                yield Ok::<_, Infallible>(anthropic_event(name, &data));
+            }
+
+            let mut failed = false;
+            while let Some(chunk) = chunks.next().await {
+                match chunk {
+                    Ok(chunk) => {
+                        for (name, data) in encoder.push(chunk) {
+                            yield Ok(anthropic_event(name, &data));
+                        }
+                    }
+                    Err(e) => {
+                        tracing::error!("Messages stream failed: {}", e);
+                        let error = anthropic::error_body("api_error", &e.to_string());
+                        yield Ok(anthropic_event("error", &error));
+                        failed = true;
+                        break;
+                    }
+                }
+            }
+
+            if !failed {
+                for (name, data) in encoder.finish() {
+                    yield Ok(anthropic_event(name, &data));
+                }
+            }
+            usage::record_usage(model_id, provider_id, encoder.usage().cloned());
+        };
+
+        let duration = start_time.elapsed();
+        log_response("POST", "/messages", 200, duration.as_millis() as u64);
+        let ping = anthropic_event("ping", &serde_json::json!({ "type": "ping" }));
+        return Sse::new(stream)
+            .keep_alive(KeepAlive::new().event(ping))
+            .into_response();
+    }
+
+    while let Some(chunk) = chunks.next().await {
+        match chunk {
+            Ok(chunk) => {
+                encoder.push(chunk);
+            }
+            Err(e) => {
+                usage::record_usage(model_id, provider_id, encoder.usage().cloned());
+                return create_anthropic_error_response(
+                    ProxyError::ServerUnreachable(e.to_string()),
+                    start_time,
+                );
+            }
+        }
+    }
+    usage::record_usage(model_id, provider_id, encoder.usage().cloned());
+
+    let duration = start_time.elapsed();
+    log_response("POST", "/messages", 200, duration.as_millis() as u64);
+    Json(encoder.into_message()).into_response()
+}
+
+fn anthropic_event(name: &str, data: &serde_json::Value) -> Event {
+    Event::default().event(name).data(data.to_string())
+}
+
+fn create_anthropic_error_response(error: ProxyError, start_time: std::time::Instant) -> Response {
+    tracing::error!("Messages request failed: {}", error);
+    let status = proxy_error_status(&error);
+    let duration = start_time.elapsed();
+    log_response("POST", "/messages", status.as_u16(), duration.as_millis() as u64);
+
+    let body = anthropic::error_body(anthropic::error_type(status), &error.to_string());
+    (status, Json(body)).into_response()
+}
+
 async fn handle_embeddings(
     ConnectInfo(remote_addr): ConnectInfo<SocketAddr>,
     Extension(router): Extension<Arc<RequestRouter>>,
@@ -318,7 +448,7 @@ async fn handle_embeddings(
     let _client_ip = remote_addr.ip().to_string();
 
     let result = match router.forward_embeddings_request(request).await {
-        Ok(response) => {
+        Ok(FailoverResponse { model, response }) => {
             // Extract response components
             let status = response.status();
             let headers = response.headers().clone();
@@ -328,6 +458,14 @@ async fn handle_embeddings(
                 .bytes_stream()
                 .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e));
 
+            // Record the usage of successful responses once their body has been passed on
+            let body = if status.is_success() {
+                let tracker = UsageTracker::new(model.model_id(), model.provider_id());
+                Body::from_stream(usage::track_usage(stream, tracker))
+            } else {
+                Body::from_stream(stream)
+            };
+
             // Build Axum response with original headers and status
             let mut response_builder = Response::builder().status(status);
 
@@ -337,7 +475,7 @@ async fn handle_embeddings(
             }
 
             // Return response with streaming body
-            match response_builder.body(Body::from_stream(stream)) {
+            match response_builder.body(body) {
                 Ok(response) => {
                     let duration = start_time.elapsed();
                     log_response(
@@ -354,8 +492,16 @@ async fn handle_embeddings(
         Err(error) => {
             let duration = start_time.elapsed();
             tracing::error!("Embeddings proxy request failed: {}", error);
-            log_response("POST", "/embeddings", 502, duration.as_millis() as u64);
-            create_error_response("Embeddings request failed")
+            match &error {
+                ProxyError::QuotaExceeded(message) => {
+                    log_response("POST", "/embeddings", 429, duration.as_millis() as u64);
+                    create_quota_error_response(message)
+                }
+                _ => {
+                    log_response("POST", "/embeddings", 502, duration.as_millis() as u64);
+                    create_error_response("Embeddings request failed")
+                }
+            }
         }
     };
 
@@ -389,25 +535,31 @@ async fn handle_models(
 }
 
 fn map_proxy_error_to_status<T>(error: ProxyError) -> Result<T, StatusCode> {
+    Err(proxy_error_status(&error))
+}
+
+fn proxy_error_status(error: &ProxyError) -> StatusCode {
     match error {
         ProxyError::ModelNotInProxy(_)
         | ProxyError::ModelNotFound(_)
-        | ProxyError::NoDefaultModel => Err(StatusCode::NOT_FOUND),
+        | ProxyError::NoDefaultModel => StatusCode::NOT_FOUND,
+
+        ProxyError::Unauthorized => StatusCode::UNAUTHORIZED,
 
-        ProxyError::Unauthorized => Err(StatusCode::UNAUTHORIZED),
+        ProxyError::HostNotTrusted(_) => StatusCode::FORBIDDEN,
 
-        ProxyError::HostNotTrusted(_) => Err(StatusCode::FORBIDDEN),
+        ProxyError::QuotaExceeded(_) => StatusCode::TOO_MANY_REQUESTS,
 
         ProxyError::InvalidRequest(_)
         | ProxyError::InvalidClientIP(_)
-        | ProxyError::InvalidCIDR(_) => Err(StatusCode::BAD_REQUEST),
+        | ProxyError::InvalidCIDR(_) => StatusCode::BAD_REQUEST,
 
         ProxyError::LocalModelNotRunning(_)
         | ProxyError::ServerUnreachable(_)
-        | ProxyError::RemoteProviderMissingBaseUrl(_) => Err(StatusCode::SERVICE_UNAVAILABLE),
+        | ProxyError::RemoteProviderMissingBaseUrl(_) => StatusCode::SERVICE_UNAVAILABLE,
 
-        ProxyError::ProxyDisabled => Err(StatusCode::SERVICE_UNAVAILABLE),
+        ProxyError::ProxyDisabled => StatusCode::SERVICE_UNAVAILABLE,
 
-        _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
     }
 }
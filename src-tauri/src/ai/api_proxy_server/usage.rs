@@ -0,0 +1,175 @@
+//! Token usage and quotas of the API proxy server
+//!
+//! Forwarded responses are passed on untouched. Their body is scanned on the way for the
+//! `usage` object of OpenAI-compatible responses (the last SSE event when streaming, with
+//! `stream_options.include_usage`), and the request is recorded once the body has been sent
+//! or the client went away. Requests without reported usage still count as requests.
+
+use bytes::Bytes;
+use futures_util::{Stream, StreamExt};
+use serde_json::Value;
+use uuid::Uuid;
+
+use super::ProxyError;
+use crate::ai::core::providers::Usage;
+use crate::database::models::{NewTokenUsage, TokenUsageSource};
+use crate::database::queries::token_usage;
+
+// Larger bodies are passed on without looking for usage in them
+const MAX_SCANNED_SIZE: usize = 4 * 1024 * 1024;
+
+/// Fail when an `api_proxy` quota is reached
+pub async fn check_api_proxy_quota() -> Result<(), ProxyError> {
+    match token_usage::find_reached_api_proxy_quota().await {
+        Ok(Some(status)) => Err(ProxyError::QuotaExceeded(status.reached_message())),
+        Ok(None) => Ok(()),
+        Err(e) => Err(ProxyError::DatabaseError(e.to_string())),
+    }
+}
+
+/// Record a proxy request in the background
+pub fn record_usage(model_id: Uuid, provider_id: Uuid, usage: Option<Usage>) {
+    let entry = NewTokenUsage {
+        source: TokenUsageSource::ApiProxy,
+        user_id: None,
+        message_id: None,
+        model_id: Some(model_id),
+        provider_id: Some(provider_id),
+        usage,
+    };
+
+    if let Ok(handle) = tokio::runtime::Handle::try_current() {
+        handle.spawn(async move {
+            if let Err(e) = token_usage::record_token_usage(entry).await {
+                tracing::warn!("Failed to record API proxy token usage: {}", e);
+            }
+        });
+    }
+}
+
+/// Usage of an OpenAI-compatible response or stream event, if it carries one
+pub fn usage_from_value(value: &Value) -> Option<Usage> {
+    let usage = value.get("usage")?;
+    let count = |key: &str| {
+        usage
+            .get(key)
+            .and_then(Value::as_u64)
+            .map(|n| u32::try_from(n).unwrap_or(u32::MAX))
+    };
+
+    let prompt_tokens = count("prompt_tokens").or_else(|| count("input_tokens"));
+    let completion_tokens = count("completion_tokens").or_else(|| count("output_tokens"));
+    let total_tokens = count("total_tokens");
+    if prompt_tokens.is_none() && completion_tokens.is_none() && total_tokens.is_none() {
+        return None;
+    }
+
+    Some(Usage {
+        prompt_tokens,
+        completion_tokens,
+        total_tokens,
+    })
+}
+
+/// Collects the usage of a forwarded response body and records the request when dropped
+pub struct UsageTracker {
+    model_id: Uuid,
+    provider_id: Uuid,
+    line: Vec<u8>,
+    body: Vec<u8>,
+    scanned: usize,
+    usage: Option<Usage>,
+}
+
+impl UsageTracker {
+    pub fn new(model_id: Uuid, provider_id: Uuid) -> Self {
+        Self {
+            model_id,
+            provider_id,
+            line: Vec::new(),
+            body: Vec::new(),
+            scanned: 0,
+            usage: None,
+        }
+    }
+
+    pub fn push(&mut self, bytes: &[u8]) {
+        self.scanned += bytes.len();
+        if self.scanned > MAX_SCANNED_SIZE {
+            self.line.clear();
+            self.body.clear();
+            return;
+        }
+        self.body.extend_from_slice(bytes);
+        self.line.extend_from_slice(bytes);
+
+        while let Some(end) = self.line.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = self.line.drain(..=end).collect();
+            let data = std::str::from_utf8(&line)
+                .ok()
+                .and_then(|line| line.trim().strip_prefix("data:"));
+            if let Some(usage) = data
+                .and_then(|data| serde_json::from_str::<Value>(data.trim()).ok())
+                .and_then(|event| usage_from_value(&event))
+            {
+                self.usage = Some(usage);
+            }
+        }
+    }
+
+    /// Usage found in the body: the last SSE event with one, or the whole JSON body
+    fn usage(&self) -> Option<Usage> {
+        self.usage.clone().or_else(|| {
+            serde_json::from_slice::<Value>(&self.body)
+                .ok()
+                .and_then(|body| usage_from_value(&body))
+        })
+    }
+}
+
+impl Drop for UsageTracker {
+    fn drop(&mut self) {
+        record_usage(self.model_id, self.provider_id, self.usage());
+    }
+}
+
+/// Pass a forwarded response body on, recording its usage once it is dropped
+pub fn track_usage<S, E>(
+    stream: S,
+    mut tracker: UsageTracker,
+) -> impl Stream<Item = Result<Bytes, E>>
+where
+    S: Stream<Item = Result<Bytes, E>>,
+{
+    stream.inspect(move |chunk| {
+        if let Ok(bytes) = chunk {
+            tracker.push(bytes);
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_usage_in_split_sse_events() {
+        let mut tracker = UsageTracker::new(Uuid::new_v4(), Uuid::new_v4());
+        tracker.push(b"data: {\"choices\":[{\"delta\":{\"content\":\"Hi\"}}],\"usage\":null}\n\n");
+        tracker.push(b"data: {\"choices\":[],\"usage\":{\"prompt_tokens\":9,");
+        tracker.push(b"\"completion_tokens\":3,\"total_tokens\":12}}\n\ndata: [DONE]\n\n");
+
+        let usage = tracker.usage().unwrap();
+        assert_eq!(usage.prompt_tokens, Some(9));
+        assert_eq!(usage.total_tokens, Some(12));
+    }
+
+    #[test]
+    fn finds_usage_in_json_body() {
+        let mut tracker = UsageTracker::new(Uuid::new_v4(), Uuid::new_v4());
+        tracker.push(b"{\"object\":\"list\",\"data\":[],");
+        tracker.push(b"\"usage\":{\"prompt_tokens\":4,\"total_tokens\":4}}");
+
+        assert_eq!(tracker.usage().unwrap().total_tokens, Some(4));
+    }
+}
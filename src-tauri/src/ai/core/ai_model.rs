@@ -5,7 +5,7 @@ use uuid::Uuid;
 use crate::database::models::model::{ModelCapabilities, ModelParameters};
 use super::providers::{
     ChatMessage, ChatResponse, StreamingResponse,
-    EmbeddingsResponse, EmbeddingsInput, ToolDefinition
+    EmbeddingsResponse, EmbeddingsInput, ImageGenerationResponse, RerankResponse, ToolDefinition
 };
 
 /// Simplified chat request without model-specific fields
@@ -15,6 +15,7 @@ pub struct SimplifiedChatRequest {
     pub messages: Vec<ChatMessage>,
     pub stream: bool,
     pub tools: Option<Vec<ToolDefinition>>, // Optional tools to send to AI
+    pub parameters: Option<ModelParameters>, // Overrides of the model's own parameters
 }
 
 /// Simplified embeddings request without model-specific fields  
@@ -26,6 +27,24 @@ pub struct SimplifiedEmbeddingsRequest {
     pub dimensions: Option<u32>,
 }
 
+/// Simplified rerank request without model-specific fields
+/// AIModel will populate model info internally when delegating to AIProvider
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimplifiedRerankRequest {
+    pub query: String,
+    pub documents: Vec<String>,
+    pub top_n: Option<usize>,
+}
+
+/// Simplified image generation request without model-specific fields
+/// AIModel will populate model info internally when delegating to AIProvider
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimplifiedImageGenerationRequest {
+    pub prompt: String,
+    pub n: Option<u32>,
+    pub size: Option<String>,
+}
+
 /// AIModel trait - wraps Model database record with AIProvider functionality
 /// This provides a cleaner API by encapsulating both model data and provider logic
 #[async_trait]
@@ -63,6 +82,18 @@ pub trait AIModel: Send + Sync {
         request: SimplifiedEmbeddingsRequest
     ) -> Result<EmbeddingsResponse, Box<dyn std::error::Error + Send + Sync>>;
     
+    /// Rerank documents against a query - delegates to underlying AIProvider with model info populated
+    async fn rerank(
+        &self,
+        request: SimplifiedRerankRequest
+    ) -> Result<RerankResponse, Box<dyn std::error::Error + Send + Sync>>;
+    
+    /// Generate images from a prompt - delegates to underlying AIProvider with model info populated
+    async fn generate_images(
+        &self,
+        request: SimplifiedImageGenerationRequest
+    ) -> Result<ImageGenerationResponse, Box<dyn std::error::Error + Send + Sync>>;
+    
     /// Check if model supports streaming (delegates to provider)
     fn supports_streaming(&self) -> bool;
     
@@ -0,0 +1,125 @@
+//! OpenAI images API (`/images/generations`)
+//!
+//! Shared by the OpenAI-compatible providers and the local engines serving the same endpoint
+//! (mistral.rs diffusion models). Images come back either inline as base64 or as a URL to
+//! download; both end up as bytes in [`GeneratedImage`].
+
+use base64::Engine;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use super::providers::{GeneratedImage, ImageGenerationRequest, ImageGenerationResponse, Usage};
+
+#[derive(Debug, Deserialize)]
+struct ImagesResponse {
+    data: Vec<ImageData>,
+    #[serde(default)]
+    usage: Option<ImagesUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ImageData {
+    #[serde(default)]
+    b64_json: Option<String>,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default)]
+    revised_prompt: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ImagesUsage {
+    input_tokens: Option<u32>,
+    output_tokens: Option<u32>,
+    total_tokens: Option<u32>,
+}
+
+/// Request body for `/images/generations`. `response_format` is left out unless given, since
+/// the gpt-image models reject it.
+pub fn request_body(request: &ImageGenerationRequest, response_format: Option<&str>) -> Value {
+    let mut body = json!({
+        "model": request.model_name,
+        "prompt": request.prompt,
+    });
+    if let Some(n) = request.n {
+        body["n"] = json!(n);
+    }
+    if let Some(size) = &request.size {
+        body["size"] = json!(size);
+    }
+    if let Some(response_format) = response_format {
+        body["response_format"] = json!(response_format);
+    }
+    body
+}
+
+/// Read the images of a successful response, downloading those returned as URLs
+pub async fn parse_response(
+    client: &reqwest::Client,
+    response: reqwest::Response,
+) -> Result<ImageGenerationResponse, Box<dyn std::error::Error + Send + Sync>> {
+    let parsed: ImagesResponse = response.json().await?;
+
+    let mut images = Vec::with_capacity(parsed.data.len());
+    for image in parsed.data {
+        let data = match (image.b64_json, image.url) {
+            (Some(b64_json), _) => base64::engine::general_purpose::STANDARD.decode(b64_json)?,
+            (None, Some(url)) => client
+                .get(&url)
+                .send()
+                .await?
+                .error_for_status()?
+                .bytes()
+                .await?
+                .to_vec(),
+            (None, None) => return Err("Image generation response has no image data".into()),
+        };
+        images.push(GeneratedImage {
+            mime_type: image_mime_type(&data).to_string(),
+            data,
+            revised_prompt: image.revised_prompt,
+        });
+    }
+
+    Ok(ImageGenerationResponse {
+        images,
+        usage: parsed.usage.map(|usage| Usage {
+            prompt_tokens: usage.input_tokens,
+            completion_tokens: usage.output_tokens,
+            total_tokens: usage.total_tokens,
+        }),
+    })
+}
+
+/// Format of an image from its magic bytes; PNG, the API default, when unknown
+pub fn image_mime_type(data: &[u8]) -> &'static str {
+    if data.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        "image/jpeg"
+    } else if data.len() >= 12 && &data[..4] == b"RIFF" && &data[8..12] == b"WEBP" {
+        "image/webp"
+    } else {
+        "image/png"
+    }
+}
+
+/// File extension for an image type returned by [`image_mime_type`]
+pub fn image_extension(mime_type: &str) -> &'static str {
+    match mime_type {
+        "image/jpeg" => "jpg",
+        "image/webp" => "webp",
+        _ => "png",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_image_types() {
+        assert_eq!(image_mime_type(b"\x89PNG\r\n\x1a\n...."), "image/png");
+        assert_eq!(image_mime_type(&[0xFF, 0xD8, 0xFF, 0xE0, 0, 0]), "image/jpeg");
+        assert_eq!(image_mime_type(b"RIFF\0\0\0\0WEBPVP8 "), "image/webp");
+        assert_eq!(image_extension(image_mime_type(b"RIFF\0\0\0\0WEBPVP8 ")), "webp");
+    }
+}
@@ -6,9 +6,13 @@
 
 pub mod ai_model;
 pub mod device_detection;
+pub mod image_generation;
 pub mod model_instance;
 pub mod provider_base;
 pub mod providers;
+pub mod structured_output;
+pub mod thinking;
+pub mod tool_prompt;
 
 pub use ai_model::*;
 pub use model_instance::*;
@@ -2,10 +2,14 @@ use async_trait::async_trait;
 use uuid::Uuid;
 
 use crate::database::models::model::{Model, ModelCapabilities, ModelParameters};
-use super::ai_model::{AIModel, SimplifiedChatRequest, SimplifiedEmbeddingsRequest};
+use super::ai_model::{
+    AIModel, SimplifiedChatRequest, SimplifiedEmbeddingsRequest, SimplifiedImageGenerationRequest,
+    SimplifiedRerankRequest,
+};
 use super::providers::{
-    AIProvider, ChatRequest, ChatResponse, StreamingResponse,
-    EmbeddingsRequest, EmbeddingsResponse
+    AIProvider, ChatMessage, ChatRequest, ChatResponse, ContentPart, MessageContent,
+    StreamingResponse, EmbeddingsRequest, EmbeddingsResponse, ImageGenerationRequest,
+    ImageGenerationResponse, RerankRequest, RerankResponse
 };
 
 /// Concrete implementation of AIModel that wraps a Model database record with an AIProvider instance
@@ -30,6 +34,15 @@ impl ModelInstance {
     pub fn provider(&self) -> &dyn AIProvider {
         self.provider.as_ref()
     }
+
+    /// The messages to send, without earlier reasoning unless the provider replays it
+    fn messages_for_provider(&self, messages: Vec<ChatMessage>) -> Vec<ChatMessage> {
+        if self.provider.replays_thinking() {
+            messages
+        } else {
+            strip_thinking(messages)
+        }
+    }
 }
 
 #[async_trait]
@@ -59,14 +72,16 @@ impl AIModel for ModelInstance {
         request: SimplifiedChatRequest
     ) -> Result<ChatResponse, Box<dyn std::error::Error + Send + Sync>> {
         // Convert SimplifiedChatRequest to full ChatRequest with model info populated
+        let parameters = merge_parameters(self.model.parameters.as_ref(), request.parameters);
         let full_request = ChatRequest {
-            messages: request.messages,
+            messages: self.messages_for_provider(request.messages),
             model_name: self.model.name.clone(),
             model_id: self.model.id,
             provider_id: self.model.provider_id,
             stream: request.stream,
-            parameters: self.model.parameters.as_ref().cloned(),
             tools: request.tools, // Pass tools from simplified request
+            json_schema: parameters.as_ref().and_then(|p| p.json_schema.clone()),
+            parameters,
         };
         
         // Delegate to the underlying AIProvider
@@ -78,14 +93,16 @@ impl AIModel for ModelInstance {
         request: SimplifiedChatRequest
     ) -> Result<StreamingResponse, Box<dyn std::error::Error + Send + Sync>> {
         // Convert SimplifiedChatRequest to full ChatRequest with model info populated
+        let parameters = merge_parameters(self.model.parameters.as_ref(), request.parameters);
         let full_request = ChatRequest {
-            messages: request.messages,
+            messages: self.messages_for_provider(request.messages),
             model_name: self.model.name.clone(),
             model_id: self.model.id,
             provider_id: self.model.provider_id,
             stream: request.stream,
-            parameters: self.model.parameters.as_ref().cloned(),
             tools: request.tools, // Pass tools from simplified request
+            json_schema: parameters.as_ref().and_then(|p| p.json_schema.clone()),
+            parameters,
         };
         
         // Delegate to the underlying AIProvider
@@ -109,6 +126,40 @@ impl AIModel for ModelInstance {
         self.provider.embeddings(full_request).await
     }
     
+    async fn rerank(
+        &self,
+        request: SimplifiedRerankRequest
+    ) -> Result<RerankResponse, Box<dyn std::error::Error + Send + Sync>> {
+        // Convert SimplifiedRerankRequest to full RerankRequest with model info populated
+        let full_request = RerankRequest {
+            model_id: self.model.id,
+            model_name: self.model.name.clone(),
+            query: request.query,
+            documents: request.documents,
+            top_n: request.top_n,
+        };
+        
+        // Delegate to the underlying AIProvider
+        self.provider.rerank(full_request).await
+    }
+    
+    async fn generate_images(
+        &self,
+        request: SimplifiedImageGenerationRequest
+    ) -> Result<ImageGenerationResponse, Box<dyn std::error::Error + Send + Sync>> {
+        // Convert SimplifiedImageGenerationRequest to full ImageGenerationRequest with model info populated
+        let full_request = ImageGenerationRequest {
+            model_id: self.model.id,
+            model_name: self.model.name.clone(),
+            prompt: request.prompt,
+            n: request.n,
+            size: request.size,
+        };
+        
+        // Delegate to the underlying AIProvider
+        self.provider.generate_images(full_request).await
+    }
+    
     fn supports_streaming(&self) -> bool {
         self.provider.supports_streaming()
     }
@@ -167,4 +218,30 @@ impl AIModel for ModelInstance {
         
         None
     }
-}
\ No newline at end of file
+}
+/// The model's parameters with the request's overrides applied on top
+fn merge_parameters(
+    model_parameters: Option<&ModelParameters>,
+    overrides: Option<ModelParameters>,
+) -> Option<ModelParameters> {
+    match (model_parameters, overrides) {
+        (Some(parameters), Some(overrides)) => Some(parameters.with_overrides(&overrides)),
+        (parameters, overrides) => overrides.or_else(|| parameters.cloned()),
+    }
+}
+
+/// Drop the reasoning parts of the messages, and the messages left empty
+fn strip_thinking(messages: Vec<ChatMessage>) -> Vec<ChatMessage> {
+    messages
+        .into_iter()
+        .filter_map(|mut message| {
+            if let MessageContent::Multimodal(parts) = &mut message.content {
+                parts.retain(|part| !matches!(part, ContentPart::Thinking { .. }));
+                if parts.is_empty() {
+                    return None;
+                }
+            }
+            Some(message)
+        })
+        .collect()
+}
@@ -4,6 +4,8 @@ use serde::{Deserialize, Serialize};
 use std::pin::Pin;
 use uuid::Uuid;
 
+use crate::database::models::ModelCapabilities;
+
 // Re-export chat-related structs from models/chat
 pub use crate::database::models::chat::{
     AIProviderChatResponse as ChatResponse, ChatMessage, ChatRequest, ContentPart, FileReference,
@@ -24,6 +26,83 @@ pub struct ProxyConfig {
     pub ignore_ssl_certificates: bool,
 }
 
+/// Error returned by a provider API call. Keeps the HTTP status so callers can tell transient
+/// failures (rate limits, server errors, timeouts) from requests that will never succeed.
+#[derive(Debug)]
+pub struct ProviderApiError {
+    /// HTTP status of the response, None when the request never got one
+    pub status: Option<u16>,
+    pub message: String,
+}
+
+impl ProviderApiError {
+    pub fn new(status: reqwest::StatusCode, message: impl Into<String>) -> Self {
+        Self {
+            status: Some(status.as_u16()),
+            message: message.into(),
+        }
+    }
+
+    /// Whether the same request may succeed on another provider or a later attempt
+    pub fn is_retryable(&self) -> bool {
+        match self.status {
+            Some(status) => status == 408 || status == 429 || status >= 500,
+            None => true,
+        }
+    }
+}
+
+impl std::fmt::Display for ProviderApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for ProviderApiError {}
+
+/// Whether a provider error is transient: a retryable HTTP status, a timeout or a connection
+/// failure
+pub fn is_retryable_provider_error(error: &(dyn std::error::Error + Send + Sync + 'static)) -> bool {
+    if let Some(api_error) = error.downcast_ref::<ProviderApiError>() {
+        return api_error.is_retryable();
+    }
+    if let Some(http_error) = error.downcast_ref::<reqwest::Error>() {
+        return http_error.is_timeout()
+            || http_error.is_connect()
+            || http_error
+                .status()
+                .map_or(false, |s| s.as_u16() == 429 || s.is_server_error());
+    }
+    false
+}
+
+/// Rewrite the message of a provider error while keeping whether it is retryable
+pub fn map_provider_error(
+    error: Box<dyn std::error::Error + Send + Sync>,
+    rewrite: impl FnOnce(&str) -> Box<dyn std::error::Error + Send + Sync>,
+) -> Box<dyn std::error::Error + Send + Sync> {
+    let retryable = is_retryable_provider_error(error.as_ref());
+    let status = error
+        .downcast_ref::<ProviderApiError>()
+        .and_then(|e| e.status)
+        .or_else(|| {
+            error
+                .downcast_ref::<reqwest::Error>()
+                .and_then(|e| e.status())
+                .map(|s| s.as_u16())
+        });
+    let rewritten = rewrite(&error.to_string());
+
+    if retryable || status.is_some() {
+        Box::new(ProviderApiError {
+            status,
+            message: rewritten.to_string(),
+        })
+    } else {
+        rewritten
+    }
+}
+
 #[derive(Debug)]
 pub enum ProviderFileContent {
     ProviderFileId(String), // Use provider's uploaded file ID
@@ -69,6 +148,60 @@ pub struct EmbeddingsUsage {
     pub total_tokens: u32,
 }
 
+// Rerank-related data structures
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RerankRequest {
+    pub model_id: Uuid,
+    pub model_name: String,
+    pub query: String,
+    pub documents: Vec<String>,
+    pub top_n: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RerankResponse {
+    pub results: Vec<RerankResult>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RerankResult {
+    pub index: usize,
+    pub relevance_score: f32,
+}
+
+// Image generation data structures
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImageGenerationRequest {
+    pub model_id: Uuid,
+    pub model_name: String,
+    pub prompt: String,
+    pub n: Option<u32>,
+    pub size: Option<String>, // "1024x1024", provider default when unset
+}
+
+#[derive(Debug, Clone)]
+pub struct ImageGenerationResponse {
+    pub images: Vec<GeneratedImage>,
+    pub usage: Option<Usage>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GeneratedImage {
+    pub data: Vec<u8>,
+    pub mime_type: String,
+    /// Prompt the provider actually used, when it rewrote the one it was given
+    pub revised_prompt: Option<String>,
+}
+
+// Model listing data structures
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderModelInfo {
+    /// Model name as the provider expects it in requests
+    pub name: String,
+    pub display_name: String,
+    pub capabilities: ModelCapabilities,
+}
+
 #[async_trait]
 pub trait AIProvider: Send + Sync {
     async fn chat(
@@ -90,6 +223,12 @@ pub trait AIProvider: Send + Sync {
         true
     }
 
+    /// Whether earlier reasoning (`ContentPart::Thinking`) is sent back on follow-up turns.
+    /// Providers that don't are never given it.
+    fn replays_thinking(&self) -> bool {
+        false
+    }
+
     /// File management capabilities
     fn supports_file_upload(&self) -> bool {
         false
@@ -146,6 +285,29 @@ pub trait AIProvider: Send + Sync {
         Err("Embeddings not supported by this provider".into())
     }
 
+    /// Score documents against a query with a reranker (cross-encoder) model
+    async fn rerank(
+        &self,
+        _request: RerankRequest,
+    ) -> Result<RerankResponse, Box<dyn std::error::Error + Send + Sync>> {
+        Err("Reranking not supported by this provider".into())
+    }
+
+    /// Generate images from a text prompt
+    async fn generate_images(
+        &self,
+        _request: ImageGenerationRequest,
+    ) -> Result<ImageGenerationResponse, Box<dyn std::error::Error + Send + Sync>> {
+        Err("Image generation not supported by this provider".into())
+    }
+
+    /// List the models served by the provider, used to populate the provider's model list
+    async fn list_models(
+        &self,
+    ) -> Result<Vec<ProviderModelInfo>, Box<dyn std::error::Error + Send + Sync>> {
+        Err("Model listing not supported by this provider".into())
+    }
+
     /// Get the embedding dimension for this provider's embedding model
     /// Returns None if embeddings are not supported or dimension cannot be determined
     async fn get_embedding_dimension(&self, model_name: &str) -> Option<u32> {
@@ -167,3 +329,33 @@ pub trait AIProvider: Send + Sync {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{is_retryable_provider_error, map_provider_error, ProviderApiError};
+    use reqwest::StatusCode;
+
+    fn boxed(error: ProviderApiError) -> Box<dyn std::error::Error + Send + Sync> {
+        Box::new(error)
+    }
+
+    #[test]
+    fn rate_limits_and_server_errors_are_retryable() {
+        assert!(ProviderApiError::new(StatusCode::TOO_MANY_REQUESTS, "").is_retryable());
+        assert!(ProviderApiError::new(StatusCode::BAD_GATEWAY, "").is_retryable());
+        assert!(!ProviderApiError::new(StatusCode::BAD_REQUEST, "").is_retryable());
+        assert!(!ProviderApiError::new(StatusCode::UNAUTHORIZED, "").is_retryable());
+    }
+
+    #[test]
+    fn rewritten_errors_keep_their_status() {
+        let error = boxed(ProviderApiError::new(StatusCode::SERVICE_UNAVAILABLE, "overloaded"));
+        let mapped = map_provider_error(error, |message| format!("Wrapped: {}", message).into());
+        assert_eq!(mapped.to_string(), "Wrapped: overloaded");
+        assert!(is_retryable_provider_error(mapped.as_ref()));
+
+        let plain: Box<dyn std::error::Error + Send + Sync> = "invalid model".into();
+        let mapped = map_provider_error(plain, |message| message.to_string().into());
+        assert!(!is_retryable_provider_error(mapped.as_ref()));
+    }
+}
@@ -0,0 +1,99 @@
+//! Replies constrained to a JSON Schema
+//!
+//! Each provider passes `ChatRequest::json_schema` on in its own form: OpenAI
+//! `response_format`, Gemini `responseSchema`, a forced tool on Anthropic, a grammar on local
+//! engines. Not every model honours it, so the final reply is checked with [`reply_error`] and
+//! the model is asked again with [`retry_prompt`] when it does not match.
+
+use serde_json::{json, Value};
+
+/// OpenAI `response_format` asking for a reply that matches the schema
+pub fn response_format(schema: &Value) -> Value {
+    json!({
+        "type": "json_schema",
+        "json_schema": {
+            "name": "response",
+            "schema": schema
+        }
+    })
+}
+
+/// Instructions for models that take no schema, only a JSON mode
+pub fn schema_instructions(schema: &Value) -> String {
+    format!(
+        "Reply with only a JSON value matching this JSON Schema:\n{}",
+        schema
+    )
+}
+
+/// Why the reply does not match the schema, if it does not. A schema that does not compile
+/// is reported and the reply accepted, since asking again would not help.
+pub fn reply_error(schema: &Value, reply: &str) -> Option<String> {
+    let validator = match jsonschema::validator_for(schema) {
+        Ok(validator) => validator,
+        Err(e) => {
+            eprintln!("Warning: Skipping reply validation, invalid JSON schema: {}", e);
+            return None;
+        }
+    };
+
+    let value: Value = match serde_json::from_str(reply.trim()) {
+        Ok(value) => value,
+        Err(e) => return Some(format!("the reply is not valid JSON ({})", e)),
+    };
+
+    let errors: Vec<String> = validator
+        .iter_errors(&value)
+        .map(|error| {
+            let path = error.instance_path.to_string();
+            if path.is_empty() {
+                error.to_string()
+            } else {
+                format!("{}: {}", path, error)
+            }
+        })
+        .collect();
+
+    if errors.is_empty() {
+        None
+    } else {
+        Some(errors.join("; "))
+    }
+}
+
+/// Message asking the model to fix a reply that does not match the schema
+pub fn retry_prompt(schema: &Value, error: &str) -> String {
+    format!(
+        "Your reply does not match the required JSON Schema: {}.\n{}",
+        error,
+        schema_instructions(schema)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": { "city": { "type": "string" }, "days": { "type": "integer" } },
+            "required": ["city"]
+        })
+    }
+
+    #[test]
+    fn accepts_matching_replies() {
+        assert_eq!(reply_error(&schema(), r#" {"city": "Paris", "days": 3} "#), None);
+    }
+
+    #[test]
+    fn reports_mismatches_and_invalid_json() {
+        let error = reply_error(&schema(), r#"{"days": "three"}"#).unwrap();
+        assert!(error.contains("city"));
+        assert!(error.contains("/days"));
+
+        let error = reply_error(&schema(), "```json\n{\"city\": \"Paris\"}\n```").unwrap();
+        assert!(error.starts_with("the reply is not valid JSON"));
+    }
+}
@@ -0,0 +1,172 @@
+//! Reasoning written inline in answers
+//!
+//! Reasoning models served by llama.cpp, Ollama or OpenAI-compatible servers often write
+//! their reasoning at the start of the answer, between `<think>` and `</think>`.
+//! [`ThinkTagParser`] splits such a streamed answer into reasoning and text, holding back a
+//! tag cut across chunks. Only a tag opening the answer starts reasoning, so answers that
+//! merely mention the tags are left untouched.
+
+use super::providers::StreamingChunk;
+
+const OPEN_TAG: &str = "<think>";
+const CLOSE_TAG: &str = "</think>";
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum State {
+    #[default]
+    Start,
+    Thinking,
+    Text,
+}
+
+/// Reasoning and text parsed from a piece of an answer
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ThinkTagOutput {
+    pub thinking: Option<String>,
+    pub text: Option<String>,
+}
+
+impl ThinkTagOutput {
+    /// Add the parsed reasoning and text to a streaming chunk
+    pub fn append_to(self, chunk: &mut StreamingChunk) {
+        if let Some(thinking) = self.thinking {
+            chunk.thinking.get_or_insert_with(String::new).push_str(&thinking);
+        }
+        if let Some(text) = self.text {
+            chunk.content.get_or_insert_with(String::new).push_str(&text);
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ThinkTagParser {
+    state: State,
+    pending: String,
+    // Whitespace between the closing tag and the answer is dropped
+    trim_text: bool,
+}
+
+impl ThinkTagParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse the next piece of the answer
+    pub fn push(&mut self, delta: &str) -> ThinkTagOutput {
+        let mut output = ThinkTagOutput::default();
+        self.pending.push_str(delta);
+
+        if self.state == State::Start {
+            let trimmed = self.pending.trim_start();
+            if let Some(rest) = trimmed.strip_prefix(OPEN_TAG) {
+                self.pending = rest.to_string();
+                self.state = State::Thinking;
+            } else if OPEN_TAG.starts_with(trimmed) {
+                return output;
+            } else {
+                self.state = State::Text;
+            }
+        }
+
+        if self.state == State::Thinking {
+            match self.pending.find(CLOSE_TAG) {
+                Some(end) => {
+                    output.thinking = non_empty(self.pending[..end].to_string());
+                    self.pending = self.pending[end + CLOSE_TAG.len()..].to_string();
+                    self.state = State::Text;
+                    self.trim_text = true;
+                }
+                None => {
+                    let keep = partial_tag_len(&self.pending, CLOSE_TAG);
+                    let thinking = self.pending[..self.pending.len() - keep].to_string();
+                    self.pending.drain(..self.pending.len() - keep);
+                    output.thinking = non_empty(thinking);
+                    return output;
+                }
+            }
+        }
+
+        let mut text = std::mem::take(&mut self.pending);
+        if self.trim_text {
+            text = text.trim_start().to_string();
+            self.trim_text = text.is_empty();
+        }
+        output.text = non_empty(text);
+        output
+    }
+
+    /// Flush what was held back at the end of the answer
+    pub fn finish(&mut self) -> ThinkTagOutput {
+        let pending = std::mem::take(&mut self.pending);
+        match self.state {
+            State::Thinking => ThinkTagOutput {
+                thinking: non_empty(pending),
+                text: None,
+            },
+            State::Start | State::Text => ThinkTagOutput {
+                thinking: None,
+                text: non_empty(pending),
+            },
+        }
+    }
+}
+
+/// Split a whole answer into its reasoning and its text
+pub fn split_think_tags(answer: &str) -> (Option<String>, String) {
+    let mut parser = ThinkTagParser::new();
+    let first = parser.push(answer);
+    let last = parser.finish();
+
+    let thinking = match (first.thinking, last.thinking) {
+        (Some(a), Some(b)) => Some(a + &b),
+        (a, b) => a.or(b),
+    };
+    let text = first.text.unwrap_or_default() + &last.text.unwrap_or_default();
+    (thinking, text)
+}
+
+fn non_empty(s: String) -> Option<String> {
+    if s.is_empty() {
+        None
+    } else {
+        Some(s)
+    }
+}
+
+/// Length of the longest end of `s` that starts `tag`
+fn partial_tag_len(s: &str, tag: &str) -> usize {
+    (1..tag.len().min(s.len() + 1))
+        .rev()
+        .find(|&len| s.is_char_boundary(s.len() - len) && tag.starts_with(&s[s.len() - len..]))
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_streamed_reasoning_with_cut_tags() {
+        let mut parser = ThinkTagParser::new();
+        let mut thinking = String::new();
+        let mut text = String::new();
+
+        for delta in ["<thi", "nk>Sum", " it up</th", "ink>\n", "\nIt is 4", "."] {
+            let output = parser.push(delta);
+            thinking.push_str(&output.thinking.unwrap_or_default());
+            text.push_str(&output.text.unwrap_or_default());
+        }
+        let output = parser.finish();
+        text.push_str(&output.text.unwrap_or_default());
+
+        assert_eq!(thinking, "Sum it up");
+        assert_eq!(text, "It is 4.");
+    }
+
+    #[test]
+    fn leaves_answers_without_leading_tag_untouched() {
+        let answer = "Wrap it in <think></think> tags";
+        assert_eq!(split_think_tags(answer), (None, answer.to_string()));
+        assert_eq!(split_think_tags("<"), (None, "<".to_string()));
+    }
+}
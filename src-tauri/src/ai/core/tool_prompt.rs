@@ -0,0 +1,172 @@
+//! Tool calling for models without a native tool template
+//!
+//! When a local model's chat template has no tool support, the tools are described in the
+//! system prompt instead and the reply is constrained to [`reply_schema`]: either tool calls or
+//! a plain answer. [`parse_reply`] turns that reply back into text and tool uses, so the chat
+//! flow is the same as with native tool calls.
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use super::providers::{ToolDefinition, ToolUse};
+
+#[derive(Debug, Deserialize)]
+struct Reply {
+    #[serde(default)]
+    response: Option<Value>,
+    #[serde(default)]
+    tool_calls: Vec<ReplyToolCall>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReplyToolCall {
+    name: String,
+    #[serde(default)]
+    arguments: Value,
+}
+
+/// Answer and tool calls parsed from a constrained reply
+#[derive(Debug, Default, Clone)]
+pub struct ToolReply {
+    pub text: String,
+    pub tool_uses: Vec<ToolUse>,
+}
+
+/// System prompt describing the tools and the expected reply. With a response schema, the
+/// answer is JSON matching it rather than text.
+pub fn tool_prompt(tools: &[ToolDefinition], response_schema: Option<&Value>) -> String {
+    let mut prompt = String::from(
+        "You can call the tools below. To call one or more tools, reply with \
+         {\"tool_calls\": [{\"name\": \"<tool name>\", \"arguments\": {<arguments>}}]}. ",
+    );
+    match response_schema {
+        Some(schema) => prompt.push_str(&format!(
+            "To answer directly, reply with {{\"response\": <your answer>}}, your answer being \
+             JSON matching this JSON Schema: {}. ",
+            schema
+        )),
+        None => prompt.push_str("To answer directly, reply with {\"response\": \"<your answer>\"}. "),
+    }
+    prompt.push_str("Tool results are sent back to you in the next message.\n\nTools:");
+    for tool in tools {
+        prompt.push_str(&format!("\n- {}", tool.name));
+        if let Some(description) = tool.description.as_deref().filter(|d| !d.is_empty()) {
+            prompt.push_str(&format!(": {}", description));
+        }
+        prompt.push_str(&format!("\n  Arguments schema: {}", tool.input_schema));
+    }
+    prompt
+}
+
+/// JSON schema the reply is constrained to
+pub fn reply_schema(tools: &[ToolDefinition], response_schema: Option<&Value>) -> Value {
+    let calls: Vec<Value> = tools
+        .iter()
+        .map(|tool| {
+            json!({
+                "type": "object",
+                "properties": {
+                    "name": { "const": tool.name },
+                    "arguments": tool.input_schema
+                },
+                "required": ["name", "arguments"],
+                "additionalProperties": false
+            })
+        })
+        .collect();
+
+    json!({
+        "anyOf": [
+            {
+                "type": "object",
+                "properties": {
+                    "response": response_schema.cloned().unwrap_or(json!({ "type": "string" }))
+                },
+                "required": ["response"],
+                "additionalProperties": false
+            },
+            {
+                "type": "object",
+                "properties": {
+                    "tool_calls": { "type": "array", "minItems": 1, "items": { "anyOf": calls } }
+                },
+                "required": ["tool_calls"],
+                "additionalProperties": false
+            }
+        ]
+    })
+}
+
+/// Write earlier tool calls the way the model is asked to reply
+pub fn format_tool_calls<'a>(calls: impl IntoIterator<Item = (&'a str, &'a Value)>) -> String {
+    let calls: Vec<Value> = calls
+        .into_iter()
+        .map(|(name, arguments)| json!({ "name": name, "arguments": arguments }))
+        .collect();
+    json!({ "tool_calls": calls }).to_string()
+}
+
+/// Parse a reply; one that does not follow the schema is kept as text. The answer is kept
+/// as JSON when a response schema was given.
+pub fn parse_reply(reply: &str, json_answer: bool) -> ToolReply {
+    match serde_json::from_str::<Reply>(reply.trim()) {
+        Ok(parsed) => ToolReply {
+            text: match parsed.response {
+                Some(Value::String(text)) if !json_answer => text,
+                Some(value) => value.to_string(),
+                None => String::new(),
+            },
+            tool_uses: parsed
+                .tool_calls
+                .into_iter()
+                .map(|call| ToolUse {
+                    id: format!("call_{}", Uuid::new_v4().simple()),
+                    name: call.name,
+                    input: if call.arguments.is_null() {
+                        json!({})
+                    } else {
+                        call.arguments
+                    },
+                })
+                .collect(),
+        },
+        Err(_) => ToolReply {
+            text: reply.to_string(),
+            tool_uses: Vec::new(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_tool_calls_and_answers() {
+        let reply = parse_reply(
+            r#"{"tool_calls": [{"name": "search", "arguments": {"query": "rust"}}, {"name": "time"}]}"#,
+            false,
+        );
+        assert!(reply.text.is_empty());
+        assert_eq!(reply.tool_uses.len(), 2);
+        assert_eq!(reply.tool_uses[0].name, "search");
+        assert_eq!(reply.tool_uses[0].input, json!({ "query": "rust" }));
+        assert_eq!(reply.tool_uses[1].input, json!({}));
+        assert_ne!(reply.tool_uses[0].id, reply.tool_uses[1].id);
+
+        let reply = parse_reply(r#" {"response": "It is 4."} "#, false);
+        assert_eq!(reply.text, "It is 4.");
+        assert!(reply.tool_uses.is_empty());
+
+        let reply = parse_reply(r#"{"response": {"answer": 4}}"#, true);
+        assert_eq!(reply.text, r#"{"answer":4}"#);
+    }
+
+    #[test]
+    fn keeps_unstructured_replies_as_text() {
+        let reply = parse_reply("It is 4.", false);
+        assert_eq!(reply.text, "It is 4.");
+        assert!(reply.tool_uses.is_empty());
+    }
+}
@@ -545,6 +545,22 @@ impl LlamaCppEngine {
             println!("Enabled embeddings support for model: {}", model.display_name);
         }
 
+        // Add reranking flag if model capabilities include rerank support
+        if model.capabilities.as_option()
+            .and_then(|caps| caps.rerank)
+            .unwrap_or(false) {
+            args.push("--reranking".to_string());
+            tracing::info!("Enabled reranking support for model: {}", model.display_name);
+        }
+
+        // Tool calls are only parsed when the chat template is rendered with jinja
+        if model.capabilities.as_option()
+            .and_then(|caps| caps.tools)
+            .unwrap_or(false) {
+            args.push("--jinja".to_string());
+            println!("Enabled tool calling support for model: {}", model.display_name);
+        }
+
         Ok(args)
     }
 }
@@ -655,11 +655,16 @@ impl MistralRsEngine {
         let command = if model.file_format == crate::database::models::FileFormat::Gguf {
             // If file format is GGUF, force the command to be "gguf"
             crate::database::models::MistralRsCommand::Gguf
+        } else if let Some(command) = settings.and_then(|s| s.command) {
+            command
+        } else if model.capabilities.as_option()
+            .and_then(|caps| caps.image_generator)
+            .unwrap_or(false) {
+            // The auto-loader does not load diffusion models
+            crate::database::models::MistralRsCommand::Diffusion
         } else {
-            // Otherwise use the configured command or default to "run"
-            settings
-                .and_then(|s| s.command)
-                .unwrap_or(crate::database::models::MistralRsCommand::Run) // Default to "run" (auto-loader)
+            // Default to "run" (auto-loader)
+            crate::database::models::MistralRsCommand::Run
         };
 
         match command {
@@ -786,6 +791,24 @@ impl MistralRsEngine {
                 args.push("--toml-path".to_string());
                 args.push(model_path_absolute.clone());
             }
+            crate::database::models::MistralRsCommand::Diffusion => {
+                args.push("diffusion".to_string());
+                args.push("--model-id".to_string());
+                if let Some(model_id_name) = settings.and_then(|s| s.model_id_name.as_ref()) {
+                    args.push(model_id_name.clone());
+                } else {
+                    args.push(model_path_absolute.clone());
+                }
+
+                // Diffusion models need their architecture (flux, flux-offloaded)
+                let arch = settings
+                    .and_then(|s| s.arch.clone())
+                    .unwrap_or_else(|| "flux".to_string());
+                args.extend(["--arch".to_string(), arch]);
+                if let Some(dtype) = settings.and_then(|s| s.dtype.as_ref()) {
+                    args.extend(["--dtype".to_string(), dtype.clone()]);
+                }
+            }
             crate::database::models::MistralRsCommand::Run => {
                 // Default to run (auto-loader) for unknown model types
                 args.push("run".to_string());
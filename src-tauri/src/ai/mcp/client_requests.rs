@@ -0,0 +1,108 @@
+//! Requests sent by MCP servers to the client (sampling, roots, ping, ...)
+//!
+//! Transports hand every server-initiated JSON-RPC request to `handle_server_request` and
+//! write the returned response back to the server. A server request carries no user, so
+//! callers running a tool on behalf of a user register an `MCPRequestContext` for the
+//! server while the call is in flight.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+use crate::ai::mcp::protocol::{methods, MCPError, MCPRequest, MCPResponse};
+use crate::ai::mcp::roots::handle_list_roots;
+use crate::ai::mcp::sampling::handle_create_message;
+use crate::database::queries::mcp_servers;
+
+lazy_static::lazy_static! {
+    // Contexts of in-flight calls per server; the most recent one serves server requests
+    static ref REQUEST_CONTEXTS: Mutex<HashMap<Uuid, Vec<(Uuid, MCPRequestContext)>>> =
+        Mutex::new(HashMap::new());
+}
+
+/// User (and conversation) on whose behalf a server is being called
+#[derive(Debug, Clone, Copy)]
+pub struct MCPRequestContext {
+    pub user_id: Uuid,
+    pub conversation_id: Option<Uuid>,
+}
+
+/// Removes its request context when dropped
+pub struct MCPRequestContextGuard {
+    server_id: Uuid,
+    entry_id: Uuid,
+}
+
+impl Drop for MCPRequestContextGuard {
+    fn drop(&mut self) {
+        let mut contexts = REQUEST_CONTEXTS.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(entries) = contexts.get_mut(&self.server_id) {
+            entries.retain(|(entry_id, _)| *entry_id != self.entry_id);
+            if entries.is_empty() {
+                contexts.remove(&self.server_id);
+            }
+        }
+    }
+}
+
+/// Register the context of a call to a server until the returned guard is dropped
+pub fn enter_request_context(
+    server_id: Uuid,
+    context: MCPRequestContext,
+) -> MCPRequestContextGuard {
+    let entry_id = Uuid::new_v4();
+    REQUEST_CONTEXTS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .entry(server_id)
+        .or_default()
+        .push((entry_id, context));
+
+    MCPRequestContextGuard { server_id, entry_id }
+}
+
+/// Context for a request from a server: the latest in-flight call, or the server's owner
+pub async fn current_request_context(server_id: Uuid) -> Option<MCPRequestContext> {
+    let registered = REQUEST_CONTEXTS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&server_id)
+        .and_then(|entries| entries.last().map(|(_, context)| *context));
+    if registered.is_some() {
+        return registered;
+    }
+
+    match mcp_servers::get_mcp_server_by_id(server_id).await {
+        Ok(Some(server)) => server.user_id.map(|user_id| MCPRequestContext {
+            user_id,
+            conversation_id: None,
+        }),
+        Ok(None) => None,
+        Err(e) => {
+            tracing::error!("Failed to get MCP server {}: {}", server_id, e);
+            None
+        }
+    }
+}
+
+/// Whether a JSON-RPC message is a request (has both a method and an id)
+pub fn is_server_request(message: &serde_json::Value) -> bool {
+    message.get("method").is_some() && message.get("id").is_some()
+}
+
+/// Answer a request sent by an MCP server
+pub async fn handle_server_request(server_id: Uuid, request: MCPRequest) -> MCPResponse {
+    tracing::debug!("MCP server {} sent request '{}'", server_id, request.method);
+
+    let result = match request.method.as_str() {
+        methods::PING => Ok(serde_json::json!({})),
+        methods::CREATE_MESSAGE => handle_create_message(server_id, request.params).await,
+        methods::LIST_ROOTS => handle_list_roots(server_id).await,
+        _ => Err(MCPError::method_not_found()),
+    };
+
+    match result {
+        Ok(result) => MCPResponse::success(request.id, result),
+        Err(error) => MCPResponse::error(request.id, error),
+    }
+}
@@ -2,6 +2,13 @@ pub mod server_manager;
 pub mod transports;
 pub mod protocol;
 pub mod logging;
+pub mod client_requests;
+pub mod oauth;
+pub mod prompt_discovery;
+pub mod resource_discovery;
+pub mod roots;
+pub mod sandbox;
+pub mod sampling;
 pub mod tool_discovery;
 pub mod tool_executor;
 
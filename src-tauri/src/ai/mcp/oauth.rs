@@ -0,0 +1,995 @@
+//! OAuth 2.1 authorization for remote (HTTP/SSE) MCP servers
+//!
+//! Follows the MCP authorization spec (2025-06-18). A server answering 401 points at its
+//! protected resource metadata (RFC 9728) in `WWW-Authenticate`, which names its
+//! authorization server; the authorization server metadata (RFC 8414) gives the endpoints.
+//! A client is registered dynamically (RFC 7591) once per server and redirect URI, users
+//! authorize with PKCE (S256), and their tokens are stored per user and server and refreshed
+//! before they expire or when the server rejects them.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use rand::Rng;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use url::Url;
+use uuid::Uuid;
+
+use crate::database::models::mcp_server::MCPTransportType;
+use crate::database::models::{
+    MCPOAuthCallbackQuery, MCPOAuthClient, MCPOAuthStatus, MCPOAuthToken, MCPOAuthTokenGrant,
+    NewMCPOAuthClient, StartMCPOAuthResponse,
+};
+use crate::database::queries::{mcp_oauth, mcp_servers};
+
+/// Path of the endpoint authorization servers redirect users back to
+pub const CALLBACK_PATH: &str = "/api/mcp/oauth/callback";
+
+// Refresh tokens this long before they expire
+const EXPIRY_MARGIN_SECONDS: i64 = 60;
+// Time a user has to complete an authorization they started
+const PENDING_AUTHORIZATION_TTL: Duration = Duration::from_secs(10 * 60);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+lazy_static::lazy_static! {
+    // Challenge of each server's last 401, used to find its authorization server
+    static ref CHALLENGES: Mutex<HashMap<Uuid, AuthorizationChallenge>> =
+        Mutex::new(HashMap::new());
+    // Authorizations started by users, by state
+    static ref PENDING_AUTHORIZATIONS: Mutex<HashMap<String, PendingAuthorization>> =
+        Mutex::new(HashMap::new());
+    // One refresh at a time per user and server, so a rotated refresh token is used once
+    static ref REFRESH_LOCKS: Mutex<HashMap<(Uuid, Uuid), Arc<tokio::sync::Mutex<()>>>> =
+        Mutex::new(HashMap::new());
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum MCPOAuthError {
+    #[error("MCP server not found")]
+    ServerNotFound,
+    #[error("Only HTTP and SSE MCP servers use OAuth authorization")]
+    UnsupportedTransport,
+    #[error("MCP server has no URL")]
+    MissingServerUrl,
+    #[error("Invalid redirect URI: {0}")]
+    InvalidRedirectUri(String),
+    #[error("Authorization server discovery failed: {0}")]
+    Discovery(String),
+    #[error("Client registration failed: {0}")]
+    Registration(String),
+    #[error("Token request failed: {0}")]
+    Token(String),
+    #[error("Unknown or expired authorization request")]
+    InvalidState,
+    #[error("Authorization was denied: {0}")]
+    Denied(String),
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+}
+
+/// Returned by transports when a server needs a token the user has not granted
+#[derive(Debug, thiserror::Error)]
+#[error("MCP server requires authorization; authorize it from the server settings and retry")]
+pub struct MCPAuthorizationRequired {
+    pub server_id: Uuid,
+}
+
+/// Parameters of a `WWW-Authenticate: Bearer` challenge
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AuthorizationChallenge {
+    pub resource_metadata: Option<String>,
+    pub scope: Option<String>,
+}
+
+/// Protected resource metadata (RFC 9728)
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProtectedResourceMetadata {
+    pub resource: Option<String>,
+    #[serde(default)]
+    pub authorization_servers: Vec<String>,
+    pub scopes_supported: Option<Vec<String>>,
+}
+
+/// Authorization server metadata (RFC 8414)
+#[derive(Debug, Clone, Deserialize)]
+pub struct AuthorizationServerMetadata {
+    pub issuer: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    pub registration_endpoint: Option<String>,
+    pub code_challenge_methods_supported: Option<Vec<String>>,
+}
+
+/// Authorization server of an MCP server, with the resource and scope to request
+#[derive(Debug, Clone)]
+pub struct AuthorizationServerInfo {
+    pub metadata: AuthorizationServerMetadata,
+    pub resource: String,
+    pub scope: Option<String>,
+}
+
+/// Dynamic client registration response (RFC 7591)
+#[derive(Debug, Clone, Deserialize)]
+pub struct ClientRegistration {
+    pub client_id: String,
+    pub client_secret: Option<String>,
+}
+
+/// Token endpoint response
+#[derive(Debug, Clone, Deserialize)]
+pub struct TokenResponse {
+    pub access_token: String,
+    pub token_type: Option<String>,
+    pub expires_in: Option<i64>,
+    pub refresh_token: Option<String>,
+    pub scope: Option<String>,
+}
+
+impl TokenResponse {
+    /// Token to save; a refresh response may omit the refresh token and scope, which are kept
+    fn into_grant(self, previous: Option<&MCPOAuthToken>) -> MCPOAuthTokenGrant {
+        MCPOAuthTokenGrant {
+            access_token: self.access_token,
+            refresh_token: self
+                .refresh_token
+                .or_else(|| previous.and_then(|token| token.refresh_token.clone())),
+            token_type: self.token_type.unwrap_or_else(|| "Bearer".to_string()),
+            scope: self
+                .scope
+                .or_else(|| previous.and_then(|token| token.scope.clone())),
+            expires_at: self
+                .expires_in
+                .map(|seconds| chrono::Utc::now() + chrono::Duration::seconds(seconds)),
+        }
+    }
+}
+
+struct PendingAuthorization {
+    user_id: Uuid,
+    server_id: Uuid,
+    oauth_client_id: Uuid,
+    code_verifier: String,
+    created_at: Instant,
+}
+
+// ============================================
+// Protocol
+// ============================================
+
+/// Parse a `WWW-Authenticate` header; `None` unless it is a Bearer challenge
+pub fn parse_www_authenticate(header: &str) -> Option<AuthorizationChallenge> {
+    let header = header.trim();
+    let (scheme, params) = header
+        .split_once(char::is_whitespace)
+        .unwrap_or((header, ""));
+    if !scheme.eq_ignore_ascii_case("bearer") {
+        return None;
+    }
+
+    let mut challenge = AuthorizationChallenge::default();
+    let mut rest = params.trim_start();
+    while let Some((name, after)) = rest.split_once('=') {
+        let after = after.trim_start();
+        let (value, remaining) = match after.strip_prefix('"') {
+            Some(quoted) => {
+                let mut value = String::new();
+                let mut end = quoted.len();
+                let mut chars = quoted.char_indices();
+                while let Some((index, c)) = chars.next() {
+                    match c {
+                        '\\' => value.extend(chars.next().map(|(_, escaped)| escaped)),
+                        '"' => {
+                            end = index + 1;
+                            break;
+                        }
+                        _ => value.push(c),
+                    }
+                }
+                (value, &quoted[end..])
+            }
+            None => {
+                let end = after.find(',').unwrap_or(after.len());
+                (after[..end].trim().to_string(), &after[end..])
+            }
+        };
+
+        match name.trim().to_ascii_lowercase().as_str() {
+            "resource_metadata" => challenge.resource_metadata = Some(value),
+            "scope" => challenge.scope = Some(value),
+            _ => {}
+        }
+        rest = remaining.trim_start().trim_start_matches(',').trim_start();
+    }
+
+    Some(challenge)
+}
+
+/// PKCE code verifier and its S256 challenge
+pub fn generate_pkce_pair() -> (String, String) {
+    let verifier = random_token(32);
+    let challenge = pkce_challenge(&verifier);
+    (verifier, challenge)
+}
+
+/// S256 code challenge of a verifier (RFC 7636)
+pub fn pkce_challenge(verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
+}
+
+fn random_token(bytes: usize) -> String {
+    let mut rng = rand::rng();
+    let data: Vec<u8> = (0..bytes).map(|_| rng.random()).collect();
+    URL_SAFE_NO_PAD.encode(data)
+}
+
+/// Well-known URLs of a metadata document for an issuer or resource: inserted before the
+/// path first (RFC 8414 section 3.1), then at the root of the origin
+fn well_known_urls(base: &str, document: &str) -> Result<Vec<String>, MCPOAuthError> {
+    let url = Url::parse(base)
+        .map_err(|e| MCPOAuthError::Discovery(format!("Invalid URL '{}': {}", base, e)))?;
+    let origin = url.origin().ascii_serialization();
+    let path = url.path().trim_end_matches('/');
+
+    let mut urls = Vec::new();
+    if !path.is_empty() {
+        urls.push(format!("{}/.well-known/{}{}", origin, document, path));
+    }
+    urls.push(format!("{}/.well-known/{}", origin, document));
+    // OpenID Connect discovery appends to the issuer instead
+    if document == "openid-configuration" && !path.is_empty() {
+        urls.push(format!("{}{}/.well-known/{}", origin, path, document));
+    }
+    Ok(urls)
+}
+
+async fn fetch_metadata<T: serde::de::DeserializeOwned>(
+    client: &reqwest::Client,
+    url: &str,
+) -> Option<T> {
+    let response = client
+        .get(url)
+        .header(reqwest::header::ACCEPT, "application/json")
+        .timeout(REQUEST_TIMEOUT)
+        .send()
+        .await
+        .ok()?;
+    if !response.status().is_success() {
+        return None;
+    }
+    response.json().await.ok()
+}
+
+/// Find the authorization server of an MCP server from its challenge or well-known metadata.
+/// Servers without resource metadata use their origin as authorization server, and
+/// authorization servers without metadata the default `/authorize`, `/token` and `/register`
+/// endpoints.
+pub async fn discover_authorization_server(
+    client: &reqwest::Client,
+    server_url: &str,
+    challenge: Option<&AuthorizationChallenge>,
+) -> Result<AuthorizationServerInfo, MCPOAuthError> {
+    let mut resource_metadata_urls: Vec<String> = challenge
+        .and_then(|challenge| challenge.resource_metadata.clone())
+        .into_iter()
+        .collect();
+    resource_metadata_urls.extend(well_known_urls(server_url, "oauth-protected-resource")?);
+
+    let mut resource_metadata = None;
+    for url in &resource_metadata_urls {
+        if let Some(metadata) = fetch_metadata::<ProtectedResourceMetadata>(client, url).await {
+            resource_metadata = Some(metadata);
+            break;
+        }
+    }
+
+    let issuer = match resource_metadata
+        .as_ref()
+        .and_then(|metadata| metadata.authorization_servers.first())
+    {
+        Some(issuer) => issuer.clone(),
+        None => Url::parse(server_url)
+            .map_err(|e| MCPOAuthError::Discovery(format!("Invalid URL '{}': {}", server_url, e)))?
+            .origin()
+            .ascii_serialization(),
+    };
+
+    let mut metadata_urls = well_known_urls(&issuer, "oauth-authorization-server")?;
+    metadata_urls.extend(well_known_urls(&issuer, "openid-configuration")?);
+
+    let mut metadata = None;
+    for url in &metadata_urls {
+        if let Some(found) = fetch_metadata::<AuthorizationServerMetadata>(client, url).await {
+            metadata = Some(found);
+            break;
+        }
+    }
+    let metadata = metadata.unwrap_or_else(|| {
+        let base = issuer.trim_end_matches('/');
+        AuthorizationServerMetadata {
+            issuer: issuer.clone(),
+            authorization_endpoint: format!("{}/authorize", base),
+            token_endpoint: format!("{}/token", base),
+            registration_endpoint: Some(format!("{}/register", base)),
+            code_challenge_methods_supported: None,
+        }
+    });
+
+    if let Some(methods) = &metadata.code_challenge_methods_supported {
+        if !methods.iter().any(|method| method == "S256") {
+            return Err(MCPOAuthError::Discovery(
+                "Authorization server does not support PKCE with S256".to_string(),
+            ));
+        }
+    }
+
+    let resource = resource_metadata
+        .as_ref()
+        .and_then(|metadata| metadata.resource.clone())
+        .unwrap_or_else(|| server_url.trim_end_matches('/').to_string());
+    let scope = challenge
+        .and_then(|challenge| challenge.scope.clone())
+        .or_else(|| {
+            resource_metadata
+                .and_then(|metadata| metadata.scopes_supported)
+                .filter(|scopes| !scopes.is_empty())
+                .map(|scopes| scopes.join(" "))
+        });
+
+    Ok(AuthorizationServerInfo {
+        metadata,
+        resource,
+        scope,
+    })
+}
+
+/// Register a public client with the authorization server (RFC 7591)
+pub async fn register_client(
+    client: &reqwest::Client,
+    metadata: &AuthorizationServerMetadata,
+    redirect_uri: &str,
+    scope: Option<&str>,
+) -> Result<ClientRegistration, MCPOAuthError> {
+    let endpoint = metadata.registration_endpoint.as_ref().ok_or_else(|| {
+        MCPOAuthError::Registration(
+            "Authorization server does not support dynamic client registration".to_string(),
+        )
+    })?;
+
+    let mut body = serde_json::json!({
+        "client_name": "Ziee",
+        "redirect_uris": [redirect_uri],
+        "grant_types": ["authorization_code", "refresh_token"],
+        "response_types": ["code"],
+        "token_endpoint_auth_method": "none",
+    });
+    if let Some(scope) = scope {
+        body["scope"] = serde_json::Value::String(scope.to_string());
+    }
+
+    let response = client
+        .post(endpoint)
+        .json(&body)
+        .timeout(REQUEST_TIMEOUT)
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_body = response.text().await.unwrap_or_default();
+        return Err(MCPOAuthError::Registration(format!("HTTP {}: {}", status, error_body)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| MCPOAuthError::Registration(format!("Invalid registration response: {}", e)))
+}
+
+/// URL the user opens to authorize a client
+pub fn build_authorization_url(
+    client: &MCPOAuthClient,
+    code_challenge: &str,
+    state: &str,
+) -> Result<String, MCPOAuthError> {
+    let mut url = Url::parse(&client.authorization_endpoint).map_err(|e| {
+        MCPOAuthError::Discovery(format!("Invalid authorization endpoint: {}", e))
+    })?;
+    {
+        let mut query = url.query_pairs_mut();
+        query
+            .append_pair("response_type", "code")
+            .append_pair("client_id", &client.client_id)
+            .append_pair("redirect_uri", &client.redirect_uri)
+            .append_pair("code_challenge", code_challenge)
+            .append_pair("code_challenge_method", "S256")
+            .append_pair("state", state)
+            .append_pair("resource", &client.resource);
+        if let Some(scope) = &client.scope {
+            query.append_pair("scope", scope);
+        }
+    }
+    Ok(url.to_string())
+}
+
+/// Send a grant to the token endpoint
+pub async fn request_token(
+    client: &reqwest::Client,
+    token_endpoint: &str,
+    client_id: &str,
+    client_secret: Option<&str>,
+    params: &[(&str, &str)],
+) -> Result<TokenResponse, MCPOAuthError> {
+    let mut form = params.to_vec();
+    form.push(("client_id", client_id));
+    if let Some(client_secret) = client_secret {
+        form.push(("client_secret", client_secret));
+    }
+
+    let response = client
+        .post(token_endpoint)
+        .header(reqwest::header::ACCEPT, "application/json")
+        .form(&form)
+        .timeout(REQUEST_TIMEOUT)
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_body = response.text().await.unwrap_or_default();
+        return Err(MCPOAuthError::Token(format!("HTTP {}: {}", status, error_body)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| MCPOAuthError::Token(format!("Invalid token response: {}", e)))
+}
+
+// ============================================
+// Per-user authorization
+// ============================================
+
+/// Redirect URI used when the frontend does not provide one
+pub fn default_redirect_uri() -> String {
+    format!("http://127.0.0.1:{}{}", crate::global::get_http_port(), CALLBACK_PATH)
+}
+
+/// Remember the challenge of a 401 so authorization starts from the metadata it names
+pub fn record_challenge(server_id: Uuid, header: Option<&str>) {
+    let challenge = header.and_then(parse_www_authenticate).unwrap_or_default();
+    CHALLENGES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(server_id, challenge);
+}
+
+/// Start authorizing a user for a server: register a client if needed and return the URL
+/// the user opens to grant access
+pub async fn start_authorization(
+    user_id: Uuid,
+    server_id: Uuid,
+    redirect_uri: Option<String>,
+) -> Result<StartMCPOAuthResponse, MCPOAuthError> {
+    let server = mcp_servers::get_mcp_server_by_id(server_id)
+        .await?
+        .ok_or(MCPOAuthError::ServerNotFound)?;
+    if !matches!(server.transport_type, MCPTransportType::Http | MCPTransportType::Sse) {
+        return Err(MCPOAuthError::UnsupportedTransport);
+    }
+    let server_url = server.url.ok_or(MCPOAuthError::MissingServerUrl)?;
+
+    let redirect_uri = redirect_uri.unwrap_or_else(default_redirect_uri);
+    Url::parse(&redirect_uri).map_err(|e| MCPOAuthError::InvalidRedirectUri(e.to_string()))?;
+
+    let client = match mcp_oauth::get_oauth_client(server_id, &redirect_uri).await? {
+        Some(client) => client,
+        None => register_server_client(server_id, &server_url, &redirect_uri).await?,
+    };
+
+    let (code_verifier, code_challenge) = generate_pkce_pair();
+    let state = random_token(24);
+    let authorization_url = build_authorization_url(&client, &code_challenge, &state)?;
+
+    let mut pending = PENDING_AUTHORIZATIONS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    pending.retain(|_, authorization| {
+        authorization.created_at.elapsed() < PENDING_AUTHORIZATION_TTL
+    });
+    pending.insert(
+        state.clone(),
+        PendingAuthorization {
+            user_id,
+            server_id,
+            oauth_client_id: client.id,
+            code_verifier,
+            created_at: Instant::now(),
+        },
+    );
+
+    Ok(StartMCPOAuthResponse {
+        authorization_url,
+        state,
+    })
+}
+
+async fn register_server_client(
+    server_id: Uuid,
+    server_url: &str,
+    redirect_uri: &str,
+) -> Result<MCPOAuthClient, MCPOAuthError> {
+    let http = reqwest::Client::new();
+    let challenge = CHALLENGES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&server_id)
+        .cloned();
+
+    let info = discover_authorization_server(&http, server_url, challenge.as_ref()).await?;
+    let registration =
+        register_client(&http, &info.metadata, redirect_uri, info.scope.as_deref()).await?;
+
+    tracing::info!(
+        "Registered OAuth client for MCP server {} with {}",
+        server_id,
+        info.metadata.issuer
+    );
+
+    Ok(mcp_oauth::save_oauth_client(&NewMCPOAuthClient {
+        server_id,
+        issuer: info.metadata.issuer,
+        authorization_endpoint: info.metadata.authorization_endpoint,
+        token_endpoint: info.metadata.token_endpoint,
+        registration_endpoint: info.metadata.registration_endpoint,
+        client_id: registration.client_id,
+        client_secret: registration.client_secret,
+        redirect_uri: redirect_uri.to_string(),
+        resource: info.resource,
+        scope: info.scope,
+    })
+    .await?)
+}
+
+/// Exchange the code the authorization server redirected the user back with for a token.
+/// Returns the server the user authorized.
+pub async fn complete_authorization(query: MCPOAuthCallbackQuery) -> Result<Uuid, MCPOAuthError> {
+    let state = query.state.ok_or(MCPOAuthError::InvalidState)?;
+    let pending = PENDING_AUTHORIZATIONS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(&state)
+        .filter(|authorization| authorization.created_at.elapsed() < PENDING_AUTHORIZATION_TTL)
+        .ok_or(MCPOAuthError::InvalidState)?;
+
+    if let Some(error) = query.error {
+        return Err(MCPOAuthError::Denied(query.error_description.unwrap_or(error)));
+    }
+    let code = query
+        .code
+        .ok_or_else(|| MCPOAuthError::Token("Missing authorization code".to_string()))?;
+
+    let client = mcp_oauth::get_oauth_client_by_id(pending.oauth_client_id)
+        .await?
+        .ok_or(MCPOAuthError::InvalidState)?;
+
+    let response = request_token(
+        &reqwest::Client::new(),
+        &client.token_endpoint,
+        &client.client_id,
+        client.client_secret.as_deref(),
+        &[
+            ("grant_type", "authorization_code"),
+            ("code", &code),
+            ("redirect_uri", &client.redirect_uri),
+            ("code_verifier", &pending.code_verifier),
+            ("resource", &client.resource),
+        ],
+    )
+    .await?;
+
+    mcp_oauth::save_oauth_token(
+        pending.user_id,
+        pending.server_id,
+        client.id,
+        &response.into_grant(None),
+    )
+    .await?;
+
+    tracing::info!(
+        "User {} authorized MCP server {}",
+        pending.user_id,
+        pending.server_id
+    );
+
+    Ok(pending.server_id)
+}
+
+/// Access token of a user for a server, refreshed first when it is about to expire
+pub async fn get_access_token(
+    user_id: Uuid,
+    server_id: Uuid,
+) -> Result<Option<String>, MCPOAuthError> {
+    let Some(token) = mcp_oauth::get_oauth_token(user_id, server_id).await? else {
+        return Ok(None);
+    };
+    if !token.expires_within(EXPIRY_MARGIN_SECONDS) {
+        return Ok(Some(token.access_token));
+    }
+
+    refresh_access_token(user_id, server_id, Some(&token.access_token)).await
+}
+
+/// Refresh the token of a user for a server. `stale_token` is the access token that expired
+/// or was rejected; if another call already replaced it, the new one is returned as is.
+/// A token that cannot be refreshed is deleted, so the user has to authorize again.
+pub async fn refresh_access_token(
+    user_id: Uuid,
+    server_id: Uuid,
+    stale_token: Option<&str>,
+) -> Result<Option<String>, MCPOAuthError> {
+    let lock = REFRESH_LOCKS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .entry((user_id, server_id))
+        .or_default()
+        .clone();
+    let _guard = lock.lock().await;
+
+    let Some(token) = mcp_oauth::get_oauth_token(user_id, server_id).await? else {
+        return Ok(None);
+    };
+    if stale_token.map_or(false, |stale| stale != token.access_token)
+        && !token.expires_within(EXPIRY_MARGIN_SECONDS)
+    {
+        return Ok(Some(token.access_token));
+    }
+
+    let client = mcp_oauth::get_oauth_client_by_id(token.oauth_client_id).await?;
+    let (Some(refresh_token), Some(client)) = (token.refresh_token.clone(), client) else {
+        mcp_oauth::delete_oauth_token(user_id, server_id).await?;
+        return Ok(None);
+    };
+
+    let response = request_token(
+        &reqwest::Client::new(),
+        &client.token_endpoint,
+        &client.client_id,
+        client.client_secret.as_deref(),
+        &[
+            ("grant_type", "refresh_token"),
+            ("refresh_token", &refresh_token),
+            ("resource", &client.resource),
+        ],
+    )
+    .await;
+
+    match response {
+        Ok(response) => {
+            let saved = mcp_oauth::save_oauth_token(
+                user_id,
+                server_id,
+                client.id,
+                &response.into_grant(Some(&token)),
+            )
+            .await?;
+            Ok(Some(saved.access_token))
+        }
+        // The refresh token was rejected; network errors keep the token for a later retry
+        Err(MCPOAuthError::Token(message)) => {
+            tracing::warn!(
+                "Failed to refresh token of user {} for MCP server {}: {}",
+                user_id,
+                server_id,
+                message
+            );
+            mcp_oauth::delete_oauth_token(user_id, server_id).await?;
+            Ok(None)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Whether a user has authorized a server
+pub async fn authorization_status(
+    user_id: Uuid,
+    server_id: Uuid,
+) -> Result<MCPOAuthStatus, MCPOAuthError> {
+    let token = mcp_oauth::get_oauth_token(user_id, server_id).await?;
+
+    Ok(MCPOAuthStatus {
+        server_id,
+        authorized: token.is_some(),
+        scope: token.as_ref().and_then(|token| token.scope.clone()),
+        expires_at: token.as_ref().and_then(|token| token.expires_at),
+        refreshable: token.map_or(false, |token| token.refresh_token.is_some()),
+    })
+}
+
+/// Forget the token of a user for a server
+pub async fn revoke_authorization(user_id: Uuid, server_id: Uuid) -> Result<bool, MCPOAuthError> {
+    Ok(mcp_oauth::delete_oauth_token(user_id, server_id).await?)
+}
+
+/// Send a request to an MCP server with the user's bearer token. When the server rejects the
+/// token it is refreshed and the request sent once more; a 401 without a usable token records
+/// the server's challenge and fails with `MCPAuthorizationRequired`.
+pub async fn send_authorized<F>(
+    server_id: Uuid,
+    user_id: Option<Uuid>,
+    build_request: F,
+) -> Result<reqwest::Response, Box<dyn std::error::Error + Send + Sync>>
+where
+    F: Fn() -> reqwest::RequestBuilder,
+{
+    let mut token = match user_id {
+        Some(user_id) => get_access_token(user_id, server_id)
+            .await
+            .unwrap_or_else(|e| {
+                tracing::warn!("Failed to get token for MCP server {}: {}", server_id, e);
+                None
+            }),
+        None => None,
+    };
+    let mut refreshed = false;
+
+    loop {
+        let mut request = build_request();
+        if let Some(token) = &token {
+            request = request.bearer_auth(token);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| format!("HTTP request failed: {}", e))?;
+        if response.status() != reqwest::StatusCode::UNAUTHORIZED {
+            return Ok(response);
+        }
+
+        if let (Some(user_id), Some(rejected), false) = (user_id, token.clone(), refreshed) {
+            refreshed = true;
+            token = refresh_access_token(user_id, server_id, Some(&rejected))
+                .await
+                .unwrap_or_else(|e| {
+                    tracing::warn!("Failed to refresh token for MCP server {}: {}", server_id, e);
+                    None
+                });
+            if token.is_some() {
+                continue;
+            }
+        }
+
+        record_challenge(
+            server_id,
+            response
+                .headers()
+                .get(reqwest::header::WWW_AUTHENTICATE)
+                .and_then(|value| value.to_str().ok()),
+        );
+        return Err(Box::new(MCPAuthorizationRequired { server_id }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::get, routing::post, Form, Json, Router};
+    use tokio::net::TcpListener;
+
+    /// Authorization server whose metadata sits at path-inserted well-known URLs, protecting
+    /// an MCP server at `{base}/mcp`
+    async fn mock_authorization_server() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+
+        let resource_base = base.clone();
+        let issuer_base = base.clone();
+        let router = Router::new()
+            .route(
+                "/.well-known/oauth-protected-resource/mcp",
+                get(move || async move {
+                    Json(serde_json::json!({
+                        "resource": format!("{}/mcp", resource_base),
+                        "authorization_servers": [format!("{}/auth", resource_base)],
+                        "scopes_supported": ["tools", "prompts"],
+                    }))
+                }),
+            )
+            .route(
+                "/.well-known/oauth-authorization-server/auth",
+                get(move || async move {
+                    Json(serde_json::json!({
+                        "issuer": format!("{}/auth", issuer_base),
+                        "authorization_endpoint": format!("{}/auth/authorize", issuer_base),
+                        "token_endpoint": format!("{}/auth/token", issuer_base),
+                        "registration_endpoint": format!("{}/auth/register", issuer_base),
+                        "code_challenge_methods_supported": ["S256"],
+                    }))
+                }),
+            )
+            .route(
+                "/auth/register",
+                post(|Json(body): Json<serde_json::Value>| async move {
+                    assert_eq!(body["token_endpoint_auth_method"], "none");
+                    Json(serde_json::json!({
+                        "client_id": "client-1",
+                        "redirect_uris": body["redirect_uris"],
+                    }))
+                }),
+            )
+            .route(
+                "/auth/token",
+                post(|Form(form): Form<HashMap<String, String>>| async move {
+                    let field = |name: &str| form.get(name).map(String::as_str);
+                    assert_eq!(field("client_id"), Some("client-1"));
+                    match (field("grant_type"), field("code"), field("refresh_token")) {
+                        (Some("authorization_code"), Some("code-1"), _)
+                            if field("code_verifier").is_some() && field("resource").is_some() =>
+                        {
+                            Ok(Json(serde_json::json!({
+                                "access_token": "access-1",
+                                "token_type": "Bearer",
+                                "expires_in": 3600,
+                                "refresh_token": "refresh-1",
+                                "scope": "tools prompts",
+                            })))
+                        }
+                        (Some("refresh_token"), _, Some("refresh-1")) => {
+                            Ok(Json(serde_json::json!({
+                                "access_token": "access-2",
+                                "token_type": "Bearer",
+                                "expires_in": 3600,
+                            })))
+                        }
+                        _ => Err((
+                            axum::http::StatusCode::BAD_REQUEST,
+                            Json(serde_json::json!({ "error": "invalid_grant" })),
+                        )),
+                    }
+                }),
+            );
+
+        tokio::spawn(async move {
+            axum::serve(listener, router).await.unwrap();
+        });
+
+        base
+    }
+
+    #[test]
+    fn parses_bearer_challenges() {
+        let challenge = parse_www_authenticate(concat!(
+            r#"Bearer error="invalid_token", "#,
+            r#"resource_metadata="https://mcp.example.com/.well-known/oauth-protected-resource", "#,
+            r#"scope="files:read files:write""#,
+        ))
+        .unwrap();
+        assert_eq!(
+            challenge.resource_metadata.as_deref(),
+            Some("https://mcp.example.com/.well-known/oauth-protected-resource")
+        );
+        assert_eq!(challenge.scope.as_deref(), Some("files:read files:write"));
+
+        assert_eq!(
+            parse_www_authenticate("Bearer"),
+            Some(AuthorizationChallenge::default())
+        );
+        assert_eq!(parse_www_authenticate(r#"Basic realm="mcp""#), None);
+    }
+
+    #[test]
+    fn computes_s256_challenge() {
+        // Example from RFC 7636 appendix B
+        assert_eq!(
+            pkce_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+            "E9Melhoa2OwvFrEMTJguCHoeRSNDD2DHyIj5C5LmtA8"
+        );
+    }
+
+    #[tokio::test]
+    async fn discovers_registers_and_exchanges_tokens() {
+        let base = mock_authorization_server().await;
+        let http = reqwest::Client::new();
+
+        let info = discover_authorization_server(&http, &format!("{}/mcp", base), None)
+            .await
+            .unwrap();
+        assert_eq!(info.metadata.issuer, format!("{}/auth", base));
+        assert_eq!(info.metadata.token_endpoint, format!("{}/auth/token", base));
+        assert_eq!(info.resource, format!("{}/mcp", base));
+        assert_eq!(info.scope.as_deref(), Some("tools prompts"));
+
+        let redirect_uri = "http://127.0.0.1:1430/api/mcp/oauth/callback";
+        let registration = register_client(&http, &info.metadata, redirect_uri, None)
+            .await
+            .unwrap();
+        assert_eq!(registration.client_id, "client-1");
+
+        let client = MCPOAuthClient {
+            id: Uuid::new_v4(),
+            server_id: Uuid::new_v4(),
+            issuer: info.metadata.issuer.clone(),
+            authorization_endpoint: info.metadata.authorization_endpoint.clone(),
+            token_endpoint: info.metadata.token_endpoint.clone(),
+            registration_endpoint: info.metadata.registration_endpoint.clone(),
+            client_id: registration.client_id.clone(),
+            client_secret: None,
+            redirect_uri: redirect_uri.to_string(),
+            resource: info.resource.clone(),
+            scope: info.scope.clone(),
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        };
+        let (verifier, challenge) = generate_pkce_pair();
+        let authorization_url = build_authorization_url(&client, &challenge, "state-1").unwrap();
+        let query: HashMap<_, _> = Url::parse(&authorization_url)
+            .unwrap()
+            .query_pairs()
+            .into_owned()
+            .collect();
+        assert_eq!(query["code_challenge"], challenge);
+        assert_eq!(query["code_challenge_method"], "S256");
+        assert_eq!(query["resource"], info.resource);
+        assert_eq!(query["scope"], "tools prompts");
+
+        let token = request_token(
+            &http,
+            &client.token_endpoint,
+            &client.client_id,
+            None,
+            &[
+                ("grant_type", "authorization_code"),
+                ("code", "code-1"),
+                ("redirect_uri", redirect_uri),
+                ("code_verifier", &verifier),
+                ("resource", &client.resource),
+            ],
+        )
+        .await
+        .unwrap();
+        let grant = token.into_grant(None);
+        assert_eq!(grant.access_token, "access-1");
+        assert_eq!(grant.refresh_token.as_deref(), Some("refresh-1"));
+        assert!(grant.expires_at.is_some());
+
+        // A refresh response without a refresh token keeps the previous one
+        let previous = MCPOAuthToken {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            server_id: client.server_id,
+            oauth_client_id: client.id,
+            access_token: grant.access_token,
+            refresh_token: grant.refresh_token,
+            token_type: grant.token_type,
+            scope: grant.scope,
+            expires_at: grant.expires_at,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        };
+        let refreshed = request_token(
+            &http,
+            &client.token_endpoint,
+            &client.client_id,
+            None,
+            &[("grant_type", "refresh_token"), ("refresh_token", "refresh-1")],
+        )
+        .await
+        .unwrap()
+        .into_grant(Some(&previous));
+        assert_eq!(refreshed.access_token, "access-2");
+        assert_eq!(refreshed.refresh_token.as_deref(), Some("refresh-1"));
+        assert_eq!(refreshed.scope.as_deref(), Some("tools prompts"));
+
+        let rejected = request_token(
+            &http,
+            &client.token_endpoint,
+            &client.client_id,
+            None,
+            &[("grant_type", "refresh_token"), ("refresh_token", "revoked")],
+        )
+        .await;
+        assert!(matches!(rejected, Err(MCPOAuthError::Token(_))));
+    }
+}
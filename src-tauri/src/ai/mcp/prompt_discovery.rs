@@ -0,0 +1,112 @@
+//! MCP prompt discovery and retrieval
+//!
+//! Prompts listed by a server are cached in `mcp_prompts_cache` (refreshed every 10 minutes,
+//! like tools). Getting a prompt always goes to the server since its messages depend on the
+//! arguments.
+
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::ai::mcp::protocol::{
+    methods, GetPromptResponse, ListPromptsResponse, PromptContent, PromptRole, ResourceContent,
+};
+use crate::ai::mcp::tool_executor::send_mcp_server_request;
+use crate::database::models::{MCPPrompt, MCPPromptMessage, MCPPromptResult};
+use crate::database::queries::mcp_prompts;
+
+// Stop following list cursors after this many pages
+const MAX_LIST_PAGES: usize = 20;
+
+/// Check if prompts should be rediscovered (cache older than 10 minutes)
+pub async fn should_rediscover_prompts(server_id: Uuid) -> Result<bool, sqlx::Error> {
+    match mcp_prompts::get_prompts_discovered_at(server_id).await? {
+        None => Ok(true), // Never discovered
+        Some(discovered_at) => {
+            let cache_duration = chrono::Duration::minutes(10);
+            Ok(chrono::Utc::now().signed_duration_since(discovered_at) > cache_duration)
+        }
+    }
+}
+
+/// List the prompts of a server and replace its cached prompts
+pub async fn discover_and_cache_prompts(
+    server_id: Uuid,
+    user_id: Option<Uuid>,
+) -> Result<Vec<MCPPrompt>, Box<dyn std::error::Error + Send + Sync>> {
+    tracing::info!("Discovering prompts for server {}", server_id);
+
+    let mut discovered = Vec::new();
+    let mut cursor: Option<String> = None;
+
+    for _ in 0..MAX_LIST_PAGES {
+        let params = match &cursor {
+            Some(cursor) => serde_json::json!({ "cursor": cursor }),
+            None => serde_json::json!({}),
+        };
+        let result = send_mcp_server_request(server_id, user_id, methods::LIST_PROMPTS, params).await?;
+        let response: ListPromptsResponse = serde_json::from_value(result)
+            .map_err(|e| format!("Failed to parse prompts response: {}", e))?;
+
+        for prompt in response.prompts {
+            let arguments = serde_json::to_value(prompt.arguments.unwrap_or_default())?;
+            discovered.push((prompt.name, prompt.description, arguments));
+        }
+
+        cursor = response.next_cursor;
+        if cursor.is_none() {
+            break;
+        }
+    }
+
+    let prompts = mcp_prompts::cache_discovered_prompts(server_id, discovered).await?;
+
+    tracing::info!("Discovered {} prompts for server {}", prompts.len(), server_id);
+
+    Ok(prompts)
+}
+
+/// Get the messages of a prompt rendered with the given arguments
+pub async fn get_mcp_prompt(
+    server_id: Uuid,
+    user_id: Option<Uuid>,
+    prompt_name: &str,
+    arguments: Option<HashMap<String, String>>,
+) -> Result<MCPPromptResult, Box<dyn std::error::Error + Send + Sync>> {
+    let params = serde_json::json!({
+        "name": prompt_name,
+        "arguments": arguments.unwrap_or_default(),
+    });
+    let result = send_mcp_server_request(server_id, user_id, methods::GET_PROMPT, params).await?;
+    let response: GetPromptResponse = serde_json::from_value(result)
+        .map_err(|e| format!("Failed to parse prompt response: {}", e))?;
+
+    let messages = response
+        .messages
+        .into_iter()
+        .filter_map(|message| {
+            let role = match message.role {
+                PromptRole::User => "user",
+                PromptRole::Assistant => "assistant",
+                PromptRole::System => "system",
+            };
+            // Only text can be inserted into a chat message; images and binary resources are
+            // dropped
+            let text = match message.content {
+                PromptContent::Text { text } => text,
+                PromptContent::Resource {
+                    resource: ResourceContent::Text { text, .. },
+                } => text,
+                _ => return None,
+            };
+            Some(MCPPromptMessage {
+                role: role.to_string(),
+                text,
+            })
+        })
+        .collect();
+
+    Ok(MCPPromptResult {
+        description: response.description,
+        messages,
+    })
+}
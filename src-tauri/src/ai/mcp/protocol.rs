@@ -307,22 +307,21 @@ pub struct ReadResourceResponse {
     pub contents: Vec<ResourceContent>,
 }
 
+// Resource contents carry either a `text` or a base64 `blob` field, without a type tag
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(tag = "type")]
+#[serde(untagged)]
 pub enum ResourceContent {
-    #[serde(rename = "text")]
     Text {
         uri: String,
         text: String,
         #[serde(rename = "mimeType")]
         mime_type: Option<String>,
     },
-    #[serde(rename = "blob")]
     Blob {
         uri: String,
-        data: String,
+        blob: String,
         #[serde(rename = "mimeType")]
-        mime_type: String,
+        mime_type: Option<String>,
     },
 }
 
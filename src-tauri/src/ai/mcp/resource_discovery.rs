@@ -0,0 +1,107 @@
+//! MCP resource discovery and reading
+//!
+//! Resources listed by a server are cached in `mcp_resources_cache` (refreshed every 10
+//! minutes, like tools). Reading a resource always goes to the server.
+
+use uuid::Uuid;
+
+use crate::ai::mcp::protocol::{
+    methods, ListResourcesResponse, ReadResourceResponse, ResourceContent,
+};
+use crate::ai::mcp::tool_executor::send_mcp_server_request;
+use crate::database::models::{MCPResource, MCPResourceContent};
+use crate::database::queries::mcp_resources;
+
+// Stop following list cursors after this many pages
+const MAX_LIST_PAGES: usize = 20;
+
+/// Check if resources should be rediscovered (cache older than 10 minutes)
+pub async fn should_rediscover_resources(server_id: Uuid) -> Result<bool, sqlx::Error> {
+    match mcp_resources::get_resources_discovered_at(server_id).await? {
+        None => Ok(true), // Never discovered
+        Some(discovered_at) => {
+            let cache_duration = chrono::Duration::minutes(10);
+            Ok(chrono::Utc::now().signed_duration_since(discovered_at) > cache_duration)
+        }
+    }
+}
+
+/// List the resources of a server and replace its cached resources
+pub async fn discover_and_cache_resources(
+    server_id: Uuid,
+    user_id: Option<Uuid>,
+) -> Result<Vec<MCPResource>, Box<dyn std::error::Error + Send + Sync>> {
+    tracing::info!("Discovering resources for server {}", server_id);
+
+    let mut discovered = Vec::new();
+    let mut cursor: Option<String> = None;
+
+    for _ in 0..MAX_LIST_PAGES {
+        let params = match &cursor {
+            Some(cursor) => serde_json::json!({ "cursor": cursor }),
+            None => serde_json::json!({}),
+        };
+        let result = send_mcp_server_request(server_id, user_id, methods::LIST_RESOURCES, params).await?;
+        let response: ListResourcesResponse = serde_json::from_value(result)
+            .map_err(|e| format!("Failed to parse resources response: {}", e))?;
+
+        for resource in response.resources {
+            discovered.push((
+                resource.uri,
+                resource.name,
+                resource.description,
+                resource.mime_type,
+            ));
+        }
+
+        cursor = response.next_cursor;
+        if cursor.is_none() {
+            break;
+        }
+    }
+
+    let resources = mcp_resources::cache_discovered_resources(server_id, discovered).await?;
+
+    tracing::info!("Discovered {} resources for server {}", resources.len(), server_id);
+
+    Ok(resources)
+}
+
+/// Read the contents of a resource
+pub async fn read_mcp_resource(
+    server_id: Uuid,
+    user_id: Option<Uuid>,
+    uri: &str,
+) -> Result<Vec<MCPResourceContent>, Box<dyn std::error::Error + Send + Sync>> {
+    let params = serde_json::json!({ "uri": uri });
+    let result = send_mcp_server_request(server_id, user_id, methods::READ_RESOURCE, params).await?;
+    let response: ReadResourceResponse = serde_json::from_value(result)
+        .map_err(|e| format!("Failed to parse resource response: {}", e))?;
+
+    Ok(response
+        .contents
+        .into_iter()
+        .map(|content| match content {
+            ResourceContent::Text {
+                uri,
+                text,
+                mime_type,
+            } => MCPResourceContent {
+                uri,
+                mime_type,
+                text: Some(text),
+                blob: None,
+            },
+            ResourceContent::Blob {
+                uri,
+                blob,
+                mime_type,
+            } => MCPResourceContent {
+                uri,
+                mime_type,
+                text: None,
+                blob: Some(blob),
+            },
+        })
+        .collect())
+}
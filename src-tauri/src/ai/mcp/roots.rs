@@ -0,0 +1,348 @@
+//! MCP roots - the project of a conversation, offered to MCP servers as their root
+//!
+//! A project's files are copied into a directory of their own under
+//! `{APP_DATA_DIR}/mcp-roots/{project_id}`, so servers working on the root see the project's
+//! files and nothing else. The directory is built when a server lists its roots and dropped
+//! when the project's files change; servers that were given the root then receive
+//! `notifications/roots/list_changed`.
+//!
+//! System servers run once for all users, so a root given to them would leak one user's
+//! project into the calls of another: they are never offered a root.
+
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use url::Url;
+use uuid::Uuid;
+
+use crate::ai::mcp::client_requests::{current_request_context, MCPRequestContext};
+use crate::ai::mcp::protocol::{methods, ListRootsResponse, MCPError, Root};
+use crate::ai::mcp::transports::stdio::notify_stdio_server;
+use crate::database::get_database_pool;
+use crate::database::models::Project;
+use crate::database::queries::{chat, files, mcp_servers, projects};
+use crate::global::{get_app_data_dir, FILE_STORAGE};
+use crate::utils::file_storage::extract_extension;
+
+lazy_static::lazy_static! {
+    // Project each server was given as root the last time it listed roots (None: no root)
+    static ref SERVED_ROOTS: Mutex<HashMap<Uuid, Option<Uuid>>> = Mutex::new(HashMap::new());
+}
+
+/// Handle a `roots/list` request from an MCP server
+pub async fn handle_list_roots(server_id: Uuid) -> Result<Value, MCPError> {
+    let is_system = mcp_servers::get_mcp_server_by_id(server_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get MCP server {}: {}", server_id, e);
+            MCPError::internal_error("Database error")
+        })?
+        .map_or(true, |server| server.is_system);
+    if is_system {
+        return serde_json::to_value(ListRootsResponse { roots: Vec::new() })
+            .map_err(|e| MCPError::internal_error(&format!("Failed to encode roots: {}", e)));
+    }
+
+    let project = match current_request_context(server_id).await {
+        Some(context) => conversation_project(context).await.map_err(|e| {
+            tracing::error!("Failed to get project for MCP roots: {}", e);
+            MCPError::internal_error("Database error")
+        })?,
+        None => None,
+    };
+
+    let roots = match &project {
+        Some(project) => {
+            let root_dir = ensure_project_root(project).await.map_err(|e| {
+                tracing::error!("Failed to prepare root for project {}: {}", project.id, e);
+                MCPError::internal_error("Failed to prepare project root")
+            })?;
+            let uri = Url::from_directory_path(&root_dir)
+                .map_err(|_| MCPError::internal_error("Invalid project root path"))?;
+
+            vec![Root {
+                uri: uri.to_string(),
+                name: Some(project.name.clone()),
+            }]
+        }
+        None => Vec::new(),
+    };
+
+    SERVED_ROOTS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(server_id, project.map(|project| project.id));
+
+    serde_json::to_value(ListRootsResponse { roots })
+        .map_err(|e| MCPError::internal_error(&format!("Failed to encode roots: {}", e)))
+}
+
+/// Drop the root of a project after its files changed and tell the servers that were given
+/// the root to list their roots again
+pub async fn project_roots_changed(project_id: Uuid) {
+    let root_dir = project_root_dir(project_id);
+    if root_dir.exists() {
+        if let Err(e) = tokio::fs::remove_dir_all(&root_dir).await {
+            tracing::warn!("Failed to remove root of project {}: {}", project_id, e);
+        }
+    }
+
+    let server_ids: Vec<Uuid> = SERVED_ROOTS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .filter(|(_, served_project)| **served_project == Some(project_id))
+        .map(|(server_id, _)| *server_id)
+        .collect();
+
+    for server_id in server_ids {
+        notify_roots_list_changed(server_id).await;
+    }
+}
+
+/// Tell a server its roots changed when it is called from a conversation whose project
+/// differs from the root it was last given
+pub async fn sync_server_roots(server_id: Uuid, context: MCPRequestContext) {
+    let served_project = SERVED_ROOTS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&server_id)
+        .copied();
+
+    // Servers that never listed roots will ask for them on their own
+    let Some(served_project) = served_project else {
+        return;
+    };
+
+    match conversation_project(context).await {
+        Ok(project) => {
+            if project.map(|project| project.id) != served_project {
+                notify_roots_list_changed(server_id).await;
+            }
+        }
+        Err(e) => tracing::warn!("Failed to get project for MCP roots: {}", e),
+    }
+}
+
+async fn notify_roots_list_changed(server_id: Uuid) {
+    match notify_stdio_server(server_id, methods::ROOTS_LIST_CHANGED, None).await {
+        Ok(true) => tracing::debug!("Notified MCP server {} of changed roots", server_id),
+        Ok(false) => {
+            // Not running (or not a stdio server): its next session lists roots anyway
+            SERVED_ROOTS
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .remove(&server_id);
+        }
+        Err(e) => tracing::warn!("Failed to notify MCP server {} of changed roots: {}", server_id, e),
+    }
+}
+
+async fn conversation_project(context: MCPRequestContext) -> Result<Option<Project>, sqlx::Error> {
+    let Some(conversation_id) = context.conversation_id else {
+        return Ok(None);
+    };
+
+    let project_id = chat::get_conversation_by_id(conversation_id, context.user_id)
+        .await?
+        .and_then(|conversation| conversation.project_id);
+
+    match project_id {
+        Some(project_id) => {
+            let pool = get_database_pool()?;
+            projects::get_project_by_id(&pool, project_id, context.user_id).await
+        }
+        None => Ok(None),
+    }
+}
+
+fn project_root_dir(project_id: Uuid) -> PathBuf {
+    get_app_data_dir()
+        .join("mcp-roots")
+        .join(project_id.to_string())
+}
+
+/// Build the root directory of a project unless it already exists
+async fn ensure_project_root(
+    project: &Project,
+) -> Result<PathBuf, Box<dyn std::error::Error + Send + Sync>> {
+    let root_dir = project_root_dir(project.id);
+    if root_dir.exists() {
+        return Ok(root_dir);
+    }
+
+    let root_files: Vec<(Uuid, String, PathBuf)> = files::get_all_files_by_project(project.id)
+        .await?
+        .into_iter()
+        .map(|file| {
+            let source =
+                FILE_STORAGE.get_original_path(file.id, &extract_extension(&file.filename));
+            (file.id, file.filename, source)
+        })
+        .collect();
+
+    build_root_dir(&root_dir, &root_files).await?;
+    Ok(root_dir)
+}
+
+/// Copy `(file_id, filename, source)` files into `root_dir`. The directory is built next to
+/// its final place and moved in, so servers never see a partially copied root; a root that
+/// already exists is kept.
+async fn build_root_dir(
+    root_dir: &Path,
+    root_files: &[(Uuid, String, PathBuf)],
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let dir_name = root_dir
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let staging_dir = root_dir.with_file_name(format!(".{}-{}", dir_name, Uuid::new_v4()));
+    tokio::fs::create_dir_all(&staging_dir).await?;
+
+    let mut used_names = HashSet::new();
+    for (file_id, filename, source) in root_files {
+        let target = staging_dir.join(unique_file_name(filename, *file_id, &mut used_names));
+        if let Err(e) = tokio::fs::copy(source, &target).await {
+            tracing::warn!("Failed to copy file {} into project root: {}", file_id, e);
+        }
+    }
+
+    match tokio::fs::rename(&staging_dir, root_dir).await {
+        Ok(()) => Ok(()),
+        Err(e) => {
+            let _ = tokio::fs::remove_dir_all(&staging_dir).await;
+            // Another request may have built the root in the meantime
+            if root_dir.exists() {
+                Ok(())
+            } else {
+                Err(e.into())
+            }
+        }
+    }
+}
+
+/// File name safe to create inside the root, made unique with a " (n)" suffix
+fn unique_file_name(filename: &str, file_id: Uuid, used_names: &mut HashSet<String>) -> String {
+    let sanitized: String = filename
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    let sanitized = sanitized.trim().trim_start_matches('.').to_string();
+    let base_name = if sanitized.is_empty() {
+        file_id.to_string()
+    } else {
+        sanitized
+    };
+
+    let (stem, extension) = match base_name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => (stem.to_string(), format!(".{}", extension)),
+        _ => (base_name.clone(), String::new()),
+    };
+
+    let mut name = base_name;
+    let mut counter = 1;
+    while !used_names.insert(name.to_lowercase()) {
+        name = format!("{} ({}){}", stem, counter, extension);
+        counter += 1;
+    }
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unique_file_name_never_leaves_the_root() {
+        let mut used = HashSet::new();
+        let file_id = Uuid::new_v4();
+
+        assert_eq!(unique_file_name("..", file_id, &mut used), file_id.to_string());
+        assert_eq!(
+            unique_file_name("../../etc/passwd", Uuid::new_v4(), &mut used),
+            "_.._etc_passwd"
+        );
+        assert_eq!(
+            unique_file_name("..\\secrets.txt", Uuid::new_v4(), &mut used),
+            "_secrets.txt"
+        );
+        assert_eq!(
+            unique_file_name("C:notes.md", Uuid::new_v4(), &mut used),
+            "C_notes.md"
+        );
+    }
+
+    #[test]
+    fn unique_file_name_strips_leading_dots() {
+        let mut used = HashSet::new();
+
+        assert_eq!(unique_file_name(".env", Uuid::new_v4(), &mut used), "env");
+        assert_eq!(unique_file_name("  ...hidden.txt ", Uuid::new_v4(), &mut used), "hidden.txt");
+    }
+
+    #[test]
+    fn unique_file_name_suffixes_case_insensitive_collisions() {
+        let mut used = HashSet::new();
+
+        assert_eq!(unique_file_name("Report.PDF", Uuid::new_v4(), &mut used), "Report.PDF");
+        assert_eq!(unique_file_name("report.pdf", Uuid::new_v4(), &mut used), "report (1).pdf");
+        assert_eq!(unique_file_name("REPORT.pdf", Uuid::new_v4(), &mut used), "REPORT (2).pdf");
+        assert_eq!(unique_file_name("README", Uuid::new_v4(), &mut used), "README");
+        assert_eq!(unique_file_name("readme", Uuid::new_v4(), &mut used), "readme (1)");
+    }
+
+    #[tokio::test]
+    async fn build_root_dir_copies_files_under_safe_names() {
+        let temp = tempfile::tempdir().unwrap();
+        let sources = temp.path().join("sources");
+        tokio::fs::create_dir_all(&sources).await.unwrap();
+        tokio::fs::write(sources.join("a"), "first").await.unwrap();
+        tokio::fs::write(sources.join("b"), "second").await.unwrap();
+
+        let root_dir = temp.path().join("roots").join("project");
+        tokio::fs::create_dir_all(root_dir.parent().unwrap()).await.unwrap();
+        let root_files = vec![
+            (Uuid::new_v4(), "../Notes.txt".to_string(), sources.join("a")),
+            (Uuid::new_v4(), "_notes.txt".to_string(), sources.join("b")),
+            (Uuid::new_v4(), "missing.txt".to_string(), sources.join("missing")),
+        ];
+
+        build_root_dir(&root_dir, &root_files).await.unwrap();
+
+        let mut names: Vec<String> = std::fs::read_dir(&root_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["_Notes.txt", "_notes (1).txt"]);
+        assert_eq!(std::fs::read_to_string(root_dir.join("_Notes.txt")).unwrap(), "first");
+
+        // Nothing is left next to the root but the root itself
+        let siblings = std::fs::read_dir(root_dir.parent().unwrap()).unwrap().count();
+        assert_eq!(siblings, 1);
+    }
+
+    #[tokio::test]
+    async fn build_root_dir_keeps_an_existing_root() {
+        let temp = tempfile::tempdir().unwrap();
+        let root_dir = temp.path().join("project");
+        tokio::fs::create_dir_all(&root_dir).await.unwrap();
+        tokio::fs::write(root_dir.join("kept.txt"), "kept").await.unwrap();
+
+        let source = temp.path().join("source");
+        tokio::fs::write(&source, "new").await.unwrap();
+        let root_files = vec![(Uuid::new_v4(), "new.txt".to_string(), source)];
+
+        build_root_dir(&root_dir, &root_files).await.unwrap();
+
+        assert!(root_dir.join("kept.txt").exists());
+        assert!(!root_dir.join("new.txt").exists());
+        // project, source
+        assert_eq!(std::fs::read_dir(temp.path()).unwrap().count(), 2);
+    }
+}
@@ -0,0 +1,305 @@
+//! MCP sampling - completions requested by MCP servers through `sampling/createMessage`
+//!
+//! The completion runs on one of the user's enabled chat models, picked from the server's
+//! model preferences. Sampling goes through the same approvals as tool calls, using
+//! `sampling/createMessage` as the tool name, and every request is recorded in
+//! `mcp_execution_logs`.
+
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::ai::core::{ChatMessage, ChatRequest, MessageContent};
+use crate::ai::create_ai_provider_with_model_id;
+use crate::ai::mcp::client_requests::{current_request_context, MCPRequestContext};
+use crate::ai::mcp::protocol::{
+    methods, CreateMessageRequest, CreateMessageResponse, MCPError,
+    MessageContent as SamplingContent, MessageRole, ModelPreferences, ResourceContent,
+    SamplingMessage,
+};
+use crate::database::models::{
+    ApprovalRuleAction, MCPExecutionStatus, Model, ModelParameters, Provider,
+};
+use crate::database::queries::{
+    mcp_execution_logs, mcp_tool_approval_rules, mcp_tool_approvals, models,
+    user_group_providers,
+};
+
+// JSON-RPC error code MCP clients use when the user declines a sampling request
+const USER_REJECTED: i32 = -1;
+
+/// Handle a `sampling/createMessage` request from an MCP server
+pub async fn handle_create_message(
+    server_id: Uuid,
+    params: Option<Value>,
+) -> Result<Value, MCPError> {
+    let parameters = params.unwrap_or(Value::Null);
+    let request: CreateMessageRequest = serde_json::from_value(parameters.clone())
+        .map_err(|e| MCPError::invalid_params(&format!("Invalid sampling request: {}", e)))?;
+
+    let context = current_request_context(server_id)
+        .await
+        .ok_or_else(|| MCPError::internal_error("No user to run the sampling request for"))?;
+
+    let execution_id = mcp_execution_logs::create_execution_log(
+        context.user_id,
+        server_id,
+        context.conversation_id,
+        methods::CREATE_MESSAGE.to_string(),
+        Some(parameters),
+        None,
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to create sampling execution log: {}", e);
+        MCPError::internal_error("Database error")
+    })?;
+
+    let start_time = std::time::Instant::now();
+    let outcome = run_sampling(server_id, context, request).await;
+    let duration_ms = start_time.elapsed().as_millis() as i32;
+
+    let (status, result, error_message, error_code) = match &outcome {
+        Ok(result) => (MCPExecutionStatus::Completed, Some(result.clone()), None, None),
+        Err(error) if error.code == USER_REJECTED => (
+            MCPExecutionStatus::Cancelled,
+            None,
+            Some(error.message.clone()),
+            Some("NOT_APPROVED".to_string()),
+        ),
+        Err(error) => (
+            MCPExecutionStatus::Failed,
+            None,
+            Some(error.message.clone()),
+            Some(error.code.to_string()),
+        ),
+    };
+
+    if let Err(e) = mcp_execution_logs::complete_execution_log(
+        execution_id,
+        status,
+        result,
+        error_message,
+        error_code,
+        Some(duration_ms),
+    )
+    .await
+    {
+        tracing::error!("Failed to complete sampling execution log: {}", e);
+    }
+
+    outcome
+}
+
+async fn run_sampling(
+    server_id: Uuid,
+    context: MCPRequestContext,
+    request: CreateMessageRequest,
+) -> Result<Value, MCPError> {
+    let approved = is_sampling_approved(server_id, context).await.map_err(|e| {
+        tracing::error!("Failed to check sampling approval: {}", e);
+        MCPError::internal_error("Database error")
+    })?;
+    if !approved {
+        tracing::info!("Sampling request from MCP server {} was not approved", server_id);
+        return Err(MCPError::new(USER_REJECTED, "User rejected sampling request"));
+    }
+
+    let (provider, model) =
+        select_sampling_model(context.user_id, request.model_preferences.as_ref())
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to load models for sampling: {}", e);
+                MCPError::internal_error("Database error")
+            })?
+            .ok_or_else(|| MCPError::internal_error("No chat model available for sampling"))?;
+
+    tracing::info!(
+        "Sampling for MCP server {} with model {}",
+        server_id,
+        model.name
+    );
+
+    let mut messages = Vec::new();
+    if let Some(system_prompt) = request.system_prompt.as_ref().filter(|p| !p.trim().is_empty()) {
+        messages.push(ChatMessage::text("system", system_prompt));
+    }
+    messages.extend(request.messages.iter().filter_map(to_chat_message));
+
+    let parameters = ModelParameters {
+        max_tokens: Some(request.max_tokens),
+        temperature: request.temperature.map(|t| t as f32),
+        stop: request.stop_sequences.clone(),
+        ..model.parameters.to_option().unwrap_or_default()
+    };
+
+    let chat_request = ChatRequest {
+        messages,
+        model_name: model.name.clone(),
+        model_id: model.id,
+        provider_id: provider.id,
+        stream: false,
+        parameters: Some(parameters),
+        tools: None,
+        json_schema: None,
+    };
+
+    let ai_provider = create_ai_provider_with_model_id(&provider, Some(model.id))
+        .await
+        .map_err(|e| MCPError::internal_error(&format!("Failed to create AI provider: {}", e)))?;
+
+    let response = ai_provider
+        .chat(chat_request)
+        .await
+        .map_err(|e| MCPError::internal_error(&format!("Sampling failed: {}", e)))?;
+
+    let result = CreateMessageResponse {
+        role: MessageRole::Assistant,
+        content: SamplingContent::Text {
+            text: response.content,
+        },
+        model: model.name,
+        stop_reason: response.finish_reason.map(|reason| stop_reason(&reason)),
+    };
+
+    serde_json::to_value(result)
+        .map_err(|e| MCPError::internal_error(&format!("Failed to encode sampling result: {}", e)))
+}
+
+/// Sampling is approved like a tool call: an approval rule, a global auto-approval for the
+/// server, or an approval in the conversation the server is being called from
+async fn is_sampling_approved(
+    server_id: Uuid,
+    context: MCPRequestContext,
+) -> Result<bool, sqlx::Error> {
+    match context.conversation_id {
+        Some(conversation_id) => Ok(mcp_tool_approvals::check_tool_approval(
+            context.user_id,
+            conversation_id,
+            server_id,
+            methods::CREATE_MESSAGE,
+            None,
+        )
+        .await?
+        .map_or(false, |(approved, _)| approved)),
+        None => {
+            let evaluation = mcp_tool_approval_rules::evaluate_approval_rules_for_user(
+                context.user_id,
+                server_id,
+                methods::CREATE_MESSAGE,
+                None,
+            )
+            .await?;
+            if let Some(action) = evaluation.action {
+                return Ok(action == ApprovalRuleAction::Approve);
+            }
+
+            Ok(mcp_tool_approvals::get_global_tool_approval(
+                context.user_id,
+                server_id,
+                methods::CREATE_MESSAGE,
+            )
+            .await?
+            .map_or(false, |approval| {
+                approval.approved
+                    && approval.auto_approve
+                    && approval.expires_at.map_or(true, |exp| exp > chrono::Utc::now())
+            }))
+        }
+    }
+}
+
+/// Pick the model for a sampling request among the user's enabled chat models.
+/// Hints are matched in order against model names; without a match, local models are
+/// preferred when the server ranks cost or speed above intelligence.
+async fn select_sampling_model(
+    user_id: Uuid,
+    preferences: Option<&ModelPreferences>,
+) -> Result<Option<(Provider, Model)>, sqlx::Error> {
+    let mut candidates = Vec::new();
+    for provider in user_group_providers::get_providers_for_user(user_id).await? {
+        if !provider.enabled {
+            continue;
+        }
+        for model in models::get_models_by_provider_id(provider.id).await? {
+            if model.enabled && !model.is_deprecated && is_chat_model(&model) {
+                candidates.push((provider.clone(), model));
+            }
+        }
+    }
+
+    let hints = preferences
+        .and_then(|p| p.hints.as_ref())
+        .map(|hints| hints.iter().filter_map(|h| h.name.as_deref()).collect::<Vec<_>>())
+        .unwrap_or_default();
+    for hint in hints {
+        let hint = hint.to_lowercase();
+        if let Some(index) = candidates.iter().position(|(_, model)| {
+            model.name.to_lowercase().contains(&hint)
+                || model.display_name.to_lowercase().contains(&hint)
+        }) {
+            return Ok(Some(candidates.swap_remove(index)));
+        }
+    }
+
+    let prefer_local = preferences.map_or(false, |p| {
+        let intelligence = p.intelligence_priority.unwrap_or(0.0);
+        p.cost_priority.unwrap_or(0.0) > intelligence
+            || p.speed_priority.unwrap_or(0.0) > intelligence
+    });
+    let preferred = candidates
+        .iter()
+        .position(|(provider, _)| {
+            (provider.provider_type.as_str() == "local") == prefer_local
+        })
+        .unwrap_or(0);
+
+    if candidates.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(candidates.swap_remove(preferred)))
+    }
+}
+
+fn is_chat_model(model: &Model) -> bool {
+    match model.capabilities.as_option() {
+        Some(capabilities) => {
+            capabilities.chat != Some(false)
+                && capabilities.text_embedding != Some(true)
+                && capabilities.rerank != Some(true)
+        }
+        None => true,
+    }
+}
+
+/// Convert a sampling message to a chat message; images are not supported and are skipped
+fn to_chat_message(message: &SamplingMessage) -> Option<ChatMessage> {
+    let role = match message.role {
+        MessageRole::User => "user",
+        MessageRole::Assistant => "assistant",
+    };
+    let text = match &message.content {
+        SamplingContent::Text { text } => text.clone(),
+        SamplingContent::Resource {
+            resource: ResourceContent::Text { text, .. },
+        } => text.clone(),
+        _ => {
+            tracing::warn!("Skipping non-text sampling message content");
+            return None;
+        }
+    };
+
+    Some(ChatMessage {
+        role: role.to_string(),
+        content: MessageContent::Text(text),
+    })
+}
+
+/// Map provider finish reasons onto MCP stop reasons
+fn stop_reason(finish_reason: &str) -> String {
+    match finish_reason {
+        "stop" | "end_turn" => "endTurn".to_string(),
+        "length" | "max_tokens" => "maxTokens".to_string(),
+        "stop_sequence" => "stopSequence".to_string(),
+        other => other.to_string(),
+    }
+}
@@ -0,0 +1,259 @@
+//! Sandboxed execution of stdio MCP servers
+//!
+//! A server with an enabled `MCPSandboxProfile` is started through bubblewrap (`bwrap`) in
+//! fresh user, mount, PID, IPC and UTS namespaces, and optionally a network namespace with
+//! nothing in it. The host filesystem is mounted read-only except a scratch directory under
+//! `{APP_DATA_DIR}/mcp-sandbox/{server_id}`, which is also the server's home and working
+//! directory, and the profile's `writable_paths`. CPU and memory limits are rlimits set on
+//! the process before it execs, so the server and everything it spawns inherit them.
+//!
+//! Sandboxing is only available on Linux; a sandboxed server is refused rather than started
+//! without isolation when `bwrap` is missing or on other platforms. What the sandbox blocks
+//! shows up as ordinary errors of the server (`EROFS`, unreachable network, failed
+//! allocations); those are recognized in its stderr and exit status and logged as violations.
+
+use std::path::{Path, PathBuf};
+use std::process::ExitStatus;
+use tokio::process::Command;
+use uuid::Uuid;
+
+use crate::database::models::MCPSandboxProfile;
+use crate::global::get_app_data_dir;
+use crate::utils::resource_paths::ResourcePaths;
+
+#[derive(Debug, thiserror::Error)]
+pub enum SandboxError {
+    #[error("Sandboxing is only supported on Linux")]
+    UnsupportedPlatform,
+    #[error("Sandboxing requires bubblewrap (bwrap), which was not found")]
+    BubblewrapNotFound,
+    #[error("Invalid writable path '{0}': paths must be absolute")]
+    InvalidWritablePath(String),
+    #[error("Failed to prepare the sandbox scratch directory: {0}")]
+    ScratchDir(#[from] std::io::Error),
+}
+
+/// Scratch directory of a sandboxed server, its only writable location by default
+pub fn scratch_dir(server_id: Uuid) -> PathBuf {
+    get_app_data_dir()
+        .join("mcp-sandbox")
+        .join(server_id.to_string())
+}
+
+/// Build the command that runs `program` with `args` inside the sandbox of a server
+pub fn sandboxed_command(
+    server_id: Uuid,
+    profile: &MCPSandboxProfile,
+    program: &Path,
+    args: &[String],
+) -> Result<Command, SandboxError> {
+    if !cfg!(target_os = "linux") {
+        return Err(SandboxError::UnsupportedPlatform);
+    }
+
+    for path in &profile.writable_paths {
+        if !Path::new(path).is_absolute() {
+            return Err(SandboxError::InvalidWritablePath(path.clone()));
+        }
+    }
+
+    let bwrap = find_bubblewrap().ok_or(SandboxError::BubblewrapNotFound)?;
+    let scratch = scratch_dir(server_id);
+    std::fs::create_dir_all(&scratch)?;
+
+    let mut cmd = Command::new(bwrap);
+    cmd.args(bubblewrap_args(profile, &scratch, program, args));
+    apply_resource_limits(&mut cmd, profile);
+
+    Ok(cmd)
+}
+
+fn find_bubblewrap() -> Option<PathBuf> {
+    ResourcePaths::find_executable_binary("bwrap").or_else(|| {
+        std::env::var_os("PATH").and_then(|paths| {
+            std::env::split_paths(&paths)
+                .map(|dir| dir.join("bwrap"))
+                .find(|candidate| candidate.is_file())
+        })
+    })
+}
+
+fn bubblewrap_args(
+    profile: &MCPSandboxProfile,
+    scratch: &Path,
+    program: &Path,
+    args: &[String],
+) -> Vec<String> {
+    let scratch = scratch.to_string_lossy().to_string();
+
+    let mut bwrap_args: Vec<String> = [
+        "--ro-bind", "/", "/",
+        "--dev", "/dev",
+        "--proc", "/proc",
+        "--tmpfs", "/tmp",
+        "--unshare-user",
+        "--unshare-pid",
+        "--unshare-ipc",
+        "--unshare-uts",
+        "--die-with-parent",
+        "--new-session",
+    ]
+    .iter()
+    .map(|arg| arg.to_string())
+    .collect();
+
+    if profile.deny_network {
+        bwrap_args.push("--unshare-net".to_string());
+    }
+
+    for path in &profile.writable_paths {
+        bwrap_args.extend(["--bind".to_string(), path.clone(), path.clone()]);
+    }
+
+    bwrap_args.extend([
+        "--bind".to_string(), scratch.clone(), scratch.clone(),
+        "--chdir".to_string(), scratch.clone(),
+        "--setenv".to_string(), "HOME".to_string(), scratch.clone(),
+        "--setenv".to_string(), "TMPDIR".to_string(), "/tmp".to_string(),
+        "--".to_string(),
+        program.to_string_lossy().to_string(),
+    ]);
+    bwrap_args.extend(args.iter().cloned());
+
+    bwrap_args
+}
+
+#[cfg(target_os = "linux")]
+fn apply_resource_limits(cmd: &mut Command, profile: &MCPSandboxProfile) {
+    let memory_limit = profile.memory_limit_mb.map(|mb| mb.saturating_mul(1024 * 1024));
+    let cpu_limit = profile.cpu_time_limit_seconds;
+    if memory_limit.is_none() && cpu_limit.is_none() {
+        return;
+    }
+
+    // SAFETY: the closure runs in the forked child before exec and only calls setrlimit,
+    // which is async-signal-safe
+    unsafe {
+        cmd.pre_exec(move || {
+            let limits = [(libc::RLIMIT_AS, memory_limit), (libc::RLIMIT_CPU, cpu_limit)];
+            for (resource, value) in limits {
+                if let Some(value) = value {
+                    let limit = libc::rlimit {
+                        rlim_cur: value as libc::rlim_t,
+                        rlim_max: value as libc::rlim_t,
+                    };
+                    if libc::setrlimit(resource, &limit) != 0 {
+                        return Err(std::io::Error::last_os_error());
+                    }
+                }
+            }
+            Ok(())
+        });
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn apply_resource_limits(_cmd: &mut Command, _profile: &MCPSandboxProfile) {}
+
+/// Recognize a line of a sandboxed server's stderr as the sandbox blocking it
+pub fn detect_violation(profile: &MCPSandboxProfile, line: &str) -> Option<&'static str> {
+    let line = line.to_lowercase();
+
+    if line.contains("read-only file system") || line.contains("erofs") {
+        return Some("write outside the scratch directory blocked");
+    }
+    if profile.deny_network
+        && (line.contains("network is unreachable")
+            || line.contains("enetunreach")
+            || line.contains("temporary failure in name resolution")
+            || line.contains("eai_again"))
+    {
+        return Some("network access blocked");
+    }
+    if profile.memory_limit_mb.is_some()
+        && (line.contains("cannot allocate memory")
+            || line.contains("enomem")
+            || line.contains("out of memory"))
+    {
+        return Some("memory limit reached");
+    }
+
+    None
+}
+
+/// Explain the exit of a sandboxed server when a limit of its profile caused it
+#[cfg(target_os = "linux")]
+pub fn describe_exit(profile: &MCPSandboxProfile, status: &ExitStatus) -> Option<&'static str> {
+    use std::os::unix::process::ExitStatusExt;
+
+    // bwrap exits with 128 + signal when the server is killed by a signal
+    let signal = status
+        .signal()
+        .or_else(|| status.code().filter(|code| *code > 128).map(|code| code - 128))?;
+
+    if signal == libc::SIGXCPU && profile.cpu_time_limit_seconds.is_some() {
+        Some("CPU time limit exceeded")
+    } else if matches!(signal, libc::SIGSEGV | libc::SIGABRT)
+        && profile.memory_limit_mb.is_some()
+    {
+        Some("terminated, possibly by the memory limit")
+    } else {
+        None
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn describe_exit(_profile: &MCPSandboxProfile, _status: &ExitStatus) -> Option<&'static str> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mounts_everything_read_only_but_scratch_and_writable_paths() {
+        let profile = MCPSandboxProfile {
+            enabled: true,
+            deny_network: true,
+            writable_paths: vec!["/srv/data".to_string()],
+            ..Default::default()
+        };
+        let args = bubblewrap_args(
+            &profile,
+            Path::new("/data/mcp-sandbox/abc"),
+            Path::new("/usr/bin/node"),
+            &["server.js".to_string()],
+        );
+
+        assert_eq!(&args[..3], ["--ro-bind", "/", "/"]);
+        assert!(args.contains(&"--unshare-net".to_string()));
+        assert!(args
+            .windows(3)
+            .any(|w| w == ["--bind", "/srv/data", "/srv/data"]));
+        assert!(args
+            .windows(3)
+            .any(|w| w == ["--bind", "/data/mcp-sandbox/abc", "/data/mcp-sandbox/abc"]));
+        assert_eq!(&args[args.len() - 3..], ["--", "/usr/bin/node", "server.js"]);
+    }
+
+    #[test]
+    fn detects_violations_enabled_by_the_profile() {
+        let profile = MCPSandboxProfile {
+            enabled: true,
+            ..Default::default()
+        };
+
+        assert!(detect_violation(&profile, "EROFS: read-only file system, open '/etc/x'").is_some());
+        assert!(detect_violation(&profile, "connect ENETUNREACH 1.1.1.1:443").is_none());
+
+        let profile = MCPSandboxProfile {
+            deny_network: true,
+            ..profile
+        };
+        assert_eq!(
+            detect_violation(&profile, "connect ENETUNREACH 1.1.1.1:443"),
+            Some("network access blocked")
+        );
+    }
+}
@@ -146,6 +146,7 @@ async fn check_and_restart_failed_servers(
                 environment_variables: None,
                 url: None,
                 headers: None,
+                sandbox: None,
                 timeout_seconds: None,
                 max_restart_attempts: None,
             };
@@ -3,8 +3,10 @@ use std::sync::Arc;
 use tokio::sync::Mutex;
 use uuid::Uuid;
 
+use crate::database::models::mcp_server::MCPTransportType;
 use crate::database::queries::{mcp_tools, mcp_servers};
-use crate::ai::mcp::protocol::{MCPRequest, MCPResponse, ListToolsResponse as MCPListToolsResponse};
+use crate::ai::mcp::protocol::{methods, MCPRequest, MCPResponse, ListToolsResponse as MCPListToolsResponse};
+use crate::ai::mcp::tool_executor::send_mcp_server_request;
 
 // Global locks for preventing concurrent tool discovery for same server
 lazy_static::lazy_static! {
@@ -95,8 +97,12 @@ pub fn parse_tools_response(
     Ok(tools)
 }
 
-/// Core function to discover and cache tools from MCP server via HTTP
-pub async fn discover_and_cache_tools_http(server_id: Uuid) -> Result<i32, ToolDiscoveryError> {
+/// Core function to discover and cache tools from MCP server via HTTP. Remote servers are
+/// asked through their transport with the OAuth token of `user_id`, if they require one.
+pub async fn discover_and_cache_tools_http(
+    server_id: Uuid,
+    user_id: Option<Uuid>,
+) -> Result<i32, ToolDiscoveryError> {
     // Get or create discovery lock for this server
     let lock = {
         let mut locks = DISCOVERY_LOCKS.lock().await;
@@ -129,21 +135,28 @@ pub async fn discover_and_cache_tools_http(server_id: Uuid) -> Result<i32, ToolD
 
     tracing::info!("Discovering tools for server {} at {}", server_id, proxy_url);
 
-    // 3. Create and send tools/list request
-    let request = create_tools_list_request();
-    let response = send_mcp_request(&proxy_url, request).await?;
-
-    // 4. Handle response
-    if let Some(error) = response.error {
-        return Err(ToolDiscoveryError::MCPCommunication(format!(
-            "MCP error: {} - {}",
-            error.code, error.message
-        )));
-    }
+    // 3. Send tools/list: remote servers need a session (and maybe a token), the stdio
+    // proxy answers single requests
+    let result = if matches!(server.transport_type, MCPTransportType::Http | MCPTransportType::Sse) {
+        send_mcp_server_request(server_id, user_id, methods::LIST_TOOLS, serde_json::json!({}))
+            .await
+            .map_err(|e| ToolDiscoveryError::MCPCommunication(e.to_string()))?
+    } else {
+        let request = create_tools_list_request();
+        let response = send_mcp_request(&proxy_url, request).await?;
+
+        // 4. Handle response
+        if let Some(error) = response.error {
+            return Err(ToolDiscoveryError::MCPCommunication(format!(
+                "MCP error: {} - {}",
+                error.code, error.message
+            )));
+        }
 
-    let result = response
-        .result
-        .ok_or_else(|| ToolDiscoveryError::InvalidResponse("No result in MCP response".to_string()))?;
+        response
+            .result
+            .ok_or_else(|| ToolDiscoveryError::InvalidResponse("No result in MCP response".to_string()))?
+    };
 
     // 5. Parse tools from response
     let tools_response: MCPListToolsResponse = serde_json::from_value(result)
@@ -4,13 +4,19 @@
 //! MCP transport types (HTTP, SSE, Stdio via proxy).
 
 use serde_json::Value;
+use std::future::Future;
+use tokio::sync::broadcast;
 use uuid::Uuid;
 
 use crate::database::models::mcp_server::MCPTransportType;
 use crate::database::queries::mcp_servers;
-use crate::ai::mcp::protocol::{MCPRequest, MCPResponse, methods};
+use crate::ai::mcp::protocol::{
+    methods, CancelledNotification, MCPNotification, MCPRequest, MCPResponse,
+    ProgressNotification,
+};
 use crate::ai::mcp::transports::http::MCPHttpTransport;
 use crate::ai::mcp::transports::sse::MCPSSETransport;
+use crate::ai::mcp::transports::stdio::{notify_stdio_server, subscribe_stdio_notifications};
 use crate::ai::mcp::transports::MCPTransport;
 
 // ============================================
@@ -27,6 +33,14 @@ pub struct MCPToolExecutionResult {
     pub duration_ms: i64,
 }
 
+/// Progress reported by a server while a tool runs (`notifications/progress`)
+#[derive(Debug, Clone)]
+pub struct MCPToolProgress {
+    pub progress: f64,
+    pub total: Option<f64>,
+    pub message: Option<String>,
+}
+
 // ============================================
 // Error Types
 // ============================================
@@ -41,6 +55,7 @@ pub enum MCPToolExecutionError {
     InvalidResponse(String),
     ToolNotFound(String),
     ToolExecutionFailed { code: i32, message: String },
+    Cancelled,
 }
 
 impl std::fmt::Display for MCPToolExecutionError {
@@ -58,6 +73,7 @@ impl std::fmt::Display for MCPToolExecutionError {
             Self::ToolExecutionFailed { code, message } => {
                 write!(f, "Tool execution failed (code {}): {}", code, message)
             }
+            Self::Cancelled => write!(f, "Tool execution was cancelled"),
         }
     }
 }
@@ -72,6 +88,8 @@ impl std::error::Error for MCPToolExecutionError {}
 enum MCPTransportWrapper {
     Http(MCPHttpTransport),
     Sse(MCPSSETransport),
+    // HTTP transport to the proxy of a running stdio server
+    StdioProxy(MCPHttpTransport),
 }
 
 impl MCPTransportWrapper {
@@ -81,10 +99,41 @@ impl MCPTransportWrapper {
         request: MCPRequest,
     ) -> Result<MCPResponse, Box<dyn std::error::Error + Send + Sync>> {
         match self {
-            Self::Http(transport) => transport.send_mcp_request(request).await,
+            Self::Http(transport) | Self::StdioProxy(transport) => {
+                transport.send_mcp_request(request).await
+            }
             Self::Sse(transport) => transport.send_mcp_request(request).await,
         }
     }
+
+    /// Send a notification through the appropriate transport
+    pub async fn send_mcp_notification(
+        &self,
+        server_id: Uuid,
+        notification: MCPNotification,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        match self {
+            Self::Http(transport) => transport.send_mcp_notification(notification).await,
+            Self::Sse(transport) => transport.send_mcp_notification(notification).await,
+            // The proxy waits for a response; write to the server's stdin directly
+            Self::StdioProxy(_) => {
+                notify_stdio_server(server_id, &notification.method, notification.params).await?;
+                Ok(())
+            }
+        }
+    }
+
+    /// Subscribe to notifications the server sends while requests are in flight
+    pub fn subscribe_notifications(
+        &self,
+        server_id: Uuid,
+    ) -> Option<broadcast::Receiver<MCPNotification>> {
+        match self {
+            Self::Http(transport) => Some(transport.subscribe_notifications()),
+            Self::Sse(transport) => Some(transport.subscribe_notifications()),
+            Self::StdioProxy(_) => subscribe_stdio_notifications(server_id),
+        }
+    }
 }
 
 // ============================================
@@ -100,9 +149,39 @@ impl MCPTransportWrapper {
 /// 4. Returns structured result
 pub async fn execute_mcp_tool(
     server_id: Uuid,
+    user_id: Option<Uuid>,
     tool_name: String,
     arguments: Value,
 ) -> Result<MCPToolExecutionResult, Box<dyn std::error::Error + Send + Sync>> {
+    execute_mcp_tool_with_progress(
+        server_id,
+        user_id,
+        tool_name,
+        arguments,
+        Uuid::new_v4(),
+        |_| {},
+        std::future::pending(),
+    )
+    .await
+}
+
+/// Execute a tool via MCP transport, reporting the server's progress notifications to
+/// `on_progress`. When `cancelled` completes first, `notifications/cancelled` is sent to
+/// the server and `MCPToolExecutionError::Cancelled` is returned. Remote servers are called
+/// with the OAuth token of `user_id`, if they require one.
+pub async fn execute_mcp_tool_with_progress<F, C>(
+    server_id: Uuid,
+    user_id: Option<Uuid>,
+    tool_name: String,
+    arguments: Value,
+    call_id: Uuid,
+    mut on_progress: F,
+    cancelled: C,
+) -> Result<MCPToolExecutionResult, Box<dyn std::error::Error + Send + Sync>>
+where
+    F: FnMut(MCPToolProgress),
+    C: Future<Output = ()>,
+{
     tracing::info!(
         "Executing MCP tool '{}' on server {}",
         tool_name,
@@ -112,14 +191,44 @@ pub async fn execute_mcp_tool(
     let start_time = std::time::Instant::now();
 
     // Get transport for this server
-    let transport = get_or_create_transport(server_id).await?;
-
-    // Create tool call request
-    let request_id = format!("tool-{}", Uuid::new_v4());
-    let request = create_tool_call_request(&tool_name, &arguments, request_id);
-
-    // Send request via transport
-    let response = transport.send_mcp_request(request).await?;
+    let transport = get_or_create_transport(server_id, user_id).await?;
+    let mut notifications = transport.subscribe_notifications(server_id);
+
+    // Create tool call request; the request id doubles as the progress token
+    let request_id = format!("tool-{}", call_id);
+    let request = create_tool_call_request(&tool_name, &arguments, request_id.clone());
+
+    // Send request via transport, following progress until the response arrives
+    let response_future = transport.send_mcp_request(request);
+    tokio::pin!(response_future);
+    tokio::pin!(cancelled);
+
+    let response = loop {
+        tokio::select! {
+            response = &mut response_future => break response?,
+            _ = &mut cancelled => {
+                tracing::info!("Cancelling MCP tool '{}' on server {}", tool_name, server_id);
+                let notification = MCPNotification {
+                    jsonrpc: "2.0".to_string(),
+                    method: methods::CANCELLED.to_string(),
+                    params: serde_json::to_value(CancelledNotification {
+                        request_id: Value::String(request_id.clone()),
+                        reason: Some("Cancelled by user".to_string()),
+                    })
+                    .ok(),
+                };
+                if let Err(e) = transport.send_mcp_notification(server_id, notification).await {
+                    tracing::warn!("Failed to send cancellation to server {}: {}", server_id, e);
+                }
+                return Err(Box::new(MCPToolExecutionError::Cancelled));
+            }
+            notification = next_notification(&mut notifications) => {
+                if let Some(progress) = parse_progress(&notification, &request_id) {
+                    on_progress(progress);
+                }
+            }
+        }
+    };
 
     let duration_ms = start_time.elapsed().as_millis() as i64;
 
@@ -156,9 +265,43 @@ pub async fn execute_mcp_tool(
     })
 }
 
-/// Get or create transport for the given server
+/// Send a single MCP request (prompts/get, resources/read, ...) to a server and return its
+/// result, turning JSON-RPC errors into `MCPToolExecutionError::RequestFailed`
+pub async fn send_mcp_server_request(
+    server_id: Uuid,
+    user_id: Option<Uuid>,
+    method: &str,
+    params: Value,
+) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+    let transport = get_or_create_transport(server_id, user_id).await?;
+
+    let request = MCPRequest {
+        jsonrpc: "2.0".to_string(),
+        id: Some(serde_json::Value::String(format!("req-{}", Uuid::new_v4()))),
+        method: method.to_string(),
+        params: Some(params),
+    };
+
+    let response = transport.send_mcp_request(request).await?;
+
+    if let Some(error) = response.error {
+        return Err(Box::new(MCPToolExecutionError::RequestFailed(format!(
+            "{} (code {})",
+            error.message, error.code
+        ))));
+    }
+
+    response.result.ok_or_else(|| {
+        Box::new(MCPToolExecutionError::InvalidResponse(
+            "No result in MCP response".to_string(),
+        )) as Box<dyn std::error::Error + Send + Sync>
+    })
+}
+
+/// Get or create transport for the given server, authorized as the given user
 async fn get_or_create_transport(
     server_id: Uuid,
+    user_id: Option<Uuid>,
 ) -> Result<MCPTransportWrapper, Box<dyn std::error::Error + Send + Sync>> {
     // Get server from database
     let server = mcp_servers::get_mcp_server_by_id(server_id)
@@ -169,7 +312,7 @@ async fn get_or_create_transport(
     match server.transport_type {
         MCPTransportType::Http => {
             tracing::debug!("Creating HTTP transport for server {}", server_id);
-            let transport = MCPHttpTransport::new(&server)?;
+            let transport = MCPHttpTransport::new(&server)?.with_user(user_id);
 
             // Always initialize the session for newly created transport
             // Even if server is running, we need to establish our MCP session
@@ -180,7 +323,7 @@ async fn get_or_create_transport(
         }
         MCPTransportType::Sse => {
             tracing::debug!("Creating SSE transport for server {}", server_id);
-            let transport = MCPSSETransport::new(&server)?;
+            let transport = MCPSSETransport::new(&server)?.with_user(user_id);
 
             // Always initialize the session for newly created transport
             // Even if server is running, we need to establish our MCP session
@@ -217,7 +360,7 @@ async fn get_or_create_transport(
                     tracing::debug!("Initializing stdio proxy HTTP transport session...");
                     transport.start().await?;
 
-                    Ok(MCPTransportWrapper::Http(transport))
+                    Ok(MCPTransportWrapper::StdioProxy(transport))
                 } else {
                     // URL doesn't look like a proxy, server probably not started
                     Err(Box::new(MCPToolExecutionError::ServerNotRunning))
@@ -234,11 +377,49 @@ async fn get_or_create_transport(
 fn create_tool_call_request(tool_name: &str, arguments: &Value, request_id: String) -> MCPRequest {
     MCPRequest {
         jsonrpc: "2.0".to_string(),
-        id: Some(serde_json::Value::String(request_id)),
+        id: Some(serde_json::Value::String(request_id.clone())),
         method: methods::CALL_TOOL.to_string(), // "tools/call"
         params: Some(serde_json::json!({
             "name": tool_name,
             "arguments": arguments,
+            "_meta": { "progressToken": request_id },
         })),
     }
 }
+
+/// Wait for the next notification; never resolves once the subscription is gone
+async fn next_notification(
+    receiver: &mut Option<broadcast::Receiver<MCPNotification>>,
+) -> MCPNotification {
+    loop {
+        let Some(subscription) = receiver.as_mut() else {
+            return std::future::pending().await;
+        };
+        match subscription.recv().await {
+            Ok(notification) => return notification,
+            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(broadcast::error::RecvError::Closed) => *receiver = None,
+        }
+    }
+}
+
+/// Progress of the request with the given progress token, if the notification reports it
+fn parse_progress(notification: &MCPNotification, progress_token: &str) -> Option<MCPToolProgress> {
+    if notification.method != methods::PROGRESS {
+        return None;
+    }
+    let params = notification.params.as_ref()?;
+    let progress: ProgressNotification = serde_json::from_value(params.clone()).ok()?;
+    if progress.progress_token.as_str() != Some(progress_token) {
+        return None;
+    }
+
+    Some(MCPToolProgress {
+        progress: progress.progress,
+        total: progress.total,
+        message: params
+            .get("message")
+            .and_then(|message| message.as_str())
+            .map(|message| message.to_string()),
+    })
+}
@@ -1,17 +1,26 @@
 use async_trait::async_trait;
-use tokio::sync::Mutex;
+use eventsource_stream::Eventsource;
+use futures::StreamExt;
+use serde_json::Value;
+use tokio::sync::{Mutex, broadcast};
 use std::sync::Arc;
 use url::Url;
+use uuid::Uuid;
 
 use crate::database::models::mcp_server::MCPServer;
-use crate::ai::mcp::protocol::{MCPRequest, MCPResponse};
-use super::{MCPTransport, MCPConnectionInfo};
+use crate::ai::mcp::client_requests::{handle_server_request, is_server_request};
+use crate::ai::mcp::oauth::{self, MCPAuthorizationRequired};
+use crate::ai::mcp::protocol::{MCPRequest, MCPResponse, MCPNotification};
+use super::{build_http_client, MCPTransport, MCPConnectionInfo};
 
 pub struct MCPHttpTransport {
     server: MCPServer,
+    // User whose OAuth token is sent, if the server requires authorization
+    user_id: Option<Uuid>,
     client: reqwest::Client,
     base_url: String,
     initialized: Arc<Mutex<bool>>,
+    notification_sender: Arc<broadcast::Sender<MCPNotification>>,
 }
 
 impl MCPHttpTransport {
@@ -30,23 +39,34 @@ impl MCPHttpTransport {
             format!("{}/mcp", url.trim_end_matches('/'))
         };
 
+        let (notification_sender, _) = broadcast::channel(1000);
+
         Ok(Self {
             server: server.clone(),
-            client: reqwest::Client::new(),
+            user_id: None,
+            client: build_http_client(server)?,
             base_url,
             initialized: Arc::new(Mutex::new(false)),
+            notification_sender: Arc::new(notification_sender),
         })
     }
 
+    /// Send requests with the OAuth token of a user
+    pub fn with_user(mut self, user_id: Option<Uuid>) -> Self {
+        self.user_id = user_id;
+        self
+    }
+
     /// Internal method to send MCP request without initialization check
     async fn send_request_internal(&self, request: MCPRequest) -> Result<MCPResponse, Box<dyn std::error::Error + Send + Sync>> {
-        let response = self.client
-            .post(&self.base_url)
-            .json(&request)
-            .timeout(std::time::Duration::from_secs(30))
-            .send()
-            .await
-            .map_err(|e| format!("HTTP request failed: {}", e))?;
+        let response = oauth::send_authorized(self.server.id, self.user_id, || {
+            self.client
+                .post(&self.base_url)
+                .header(reqwest::header::ACCEPT, "application/json, text/event-stream")
+                .json(&request)
+                .timeout(std::time::Duration::from_secs(30))
+        })
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -54,6 +74,15 @@ impl MCPHttpTransport {
             return Err(format!("HTTP error {}: {}", status, error_body).into());
         }
 
+        let is_event_stream = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map_or(false, |content_type| content_type.starts_with("text/event-stream"));
+        if is_event_stream {
+            return self.read_event_stream_response(response, request.id).await;
+        }
+
         let mcp_response: MCPResponse = response
             .json()
             .await
@@ -62,6 +91,50 @@ impl MCPHttpTransport {
         Ok(mcp_response)
     }
 
+    /// Read a response sent as an event stream. The server may send requests of its own
+    /// (sampling, ping, ...) on the stream before the response; they are answered with a POST.
+    /// Notifications (progress, ...) are forwarded to `subscribe_notifications`.
+    async fn read_event_stream_response(
+        &self,
+        response: reqwest::Response,
+        request_id: Option<Value>,
+    ) -> Result<MCPResponse, Box<dyn std::error::Error + Send + Sync>> {
+        let stream = response.bytes_stream().eventsource();
+        futures::pin_mut!(stream);
+
+        while let Some(event) = stream.next().await {
+            let event = event.map_err(|e| format!("Failed to read MCP event stream: {}", e))?;
+            let message = match serde_json::from_str::<Value>(&event.data) {
+                Ok(message) => message,
+                Err(_) => continue,
+            };
+
+            if is_server_request(&message) {
+                if let Ok(server_request) = serde_json::from_value::<MCPRequest>(message) {
+                    let server_response = handle_server_request(self.server.id, server_request).await;
+                    oauth::send_authorized(self.server.id, self.user_id, || {
+                        self.client
+                            .post(&self.base_url)
+                            .json(&server_response)
+                            .timeout(std::time::Duration::from_secs(30))
+                    })
+                    .await
+                    .map_err(|e| format!("Failed to answer MCP server request: {}", e))?;
+                }
+            } else if message.get("id").is_some() {
+                if message.get("id") == request_id.as_ref() {
+                    let mcp_response: MCPResponse = serde_json::from_value(message)
+                        .map_err(|e| format!("Failed to parse MCP response: {}", e))?;
+                    return Ok(mcp_response);
+                }
+            } else if let Ok(notification) = serde_json::from_value::<MCPNotification>(message) {
+                let _ = self.notification_sender.send(notification);
+            }
+        }
+
+        Err("MCP event stream ended without a response".into())
+    }
+
     pub async fn send_mcp_request(&self, request: MCPRequest) -> Result<MCPResponse, Box<dyn std::error::Error + Send + Sync>> {
         // Check if initialized
         if !*self.initialized.lock().await {
@@ -71,6 +144,30 @@ impl MCPHttpTransport {
         self.send_request_internal(request).await
     }
 
+    /// Send a notification; the server only acknowledges it
+    pub async fn send_mcp_notification(
+        &self,
+        notification: MCPNotification,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let response = oauth::send_authorized(self.server.id, self.user_id, || {
+            self.client
+                .post(&self.base_url)
+                .json(&notification)
+                .timeout(std::time::Duration::from_secs(30))
+        })
+        .await?;
+
+        if !response.status().is_success() {
+            return Err(format!("HTTP error {}", response.status()).into());
+        }
+
+        Ok(())
+    }
+
+    pub fn subscribe_notifications(&self) -> broadcast::Receiver<MCPNotification> {
+        self.notification_sender.subscribe()
+    }
+
     async fn initialize_mcp_session(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         // Step 1: Send initialize request
         let init_request = MCPRequest {
@@ -120,8 +217,15 @@ impl MCPHttpTransport {
 #[async_trait]
 impl MCPTransport for MCPHttpTransport {
     async fn start(&self) -> Result<MCPConnectionInfo, Box<dyn std::error::Error + Send + Sync>> {
-        // Initialize MCP session with the HTTP server
-        self.initialize_mcp_session().await?;
+        // Initialize MCP session with the HTTP server. Without a user, a server requiring
+        // OAuth authorization is up; each user initializes a session with their own token.
+        match self.initialize_mcp_session().await {
+            Ok(()) => {}
+            Err(e) if self.user_id.is_none() && e.is::<MCPAuthorizationRequired>() => {
+                println!("[{}] MCP HTTP server requires user authorization", self.server.name);
+            }
+            Err(e) => return Err(e),
+        }
 
         // Extract port from URL if available
         let port = if let Ok(parsed_url) = Url::parse(&self.base_url) {
@@ -20,6 +20,27 @@ pub trait MCPTransport {
     async fn is_healthy(&self) -> bool;
 }
 
+/// HTTP client sending the static `headers` configured on a server with every request
+pub fn build_http_client(
+    server: &MCPServer,
+) -> Result<reqwest::Client, Box<dyn std::error::Error + Send + Sync>> {
+    let mut headers = reqwest::header::HeaderMap::new();
+    if let Some(configured) = server.headers.as_object() {
+        for (name, value) in configured {
+            let Some(value) = value.as_str() else {
+                continue;
+            };
+            let name = reqwest::header::HeaderName::from_bytes(name.as_bytes())
+                .map_err(|e| format!("Invalid header name '{}': {}", name, e))?;
+            let value = reqwest::header::HeaderValue::from_str(value)
+                .map_err(|e| format!("Invalid value for header '{}': {}", name, e))?;
+            headers.insert(name, value);
+        }
+    }
+
+    Ok(reqwest::Client::builder().default_headers(headers).build()?)
+}
+
 pub async fn create_mcp_transport(
     server: &MCPServer,
 ) -> Result<Box<dyn MCPTransport + Send + Sync>, Box<dyn std::error::Error + Send + Sync>> {
@@ -6,13 +6,18 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::{Mutex, oneshot, broadcast};
 use url::Url;
+use uuid::Uuid;
 
 use crate::database::models::mcp_server::MCPServer;
+use crate::ai::mcp::client_requests::{handle_server_request, is_server_request};
+use crate::ai::mcp::oauth::{self, MCPAuthorizationRequired};
 use crate::ai::mcp::protocol::{MCPRequest, MCPResponse, MCPNotification};
-use super::{MCPTransport, MCPConnectionInfo};
+use super::{build_http_client, MCPTransport, MCPConnectionInfo};
 
 pub struct MCPSSETransport {
     server: MCPServer,
+    // User whose OAuth token is sent, if the server requires authorization
+    user_id: Option<Uuid>,
     client: reqwest::Client,
     base_url: String,
     sse_url: String,
@@ -44,7 +49,8 @@ impl MCPSSETransport {
 
         Ok(Self {
             server: server.clone(),
-            client: reqwest::Client::new(),
+            user_id: None,
+            client: build_http_client(server)?,
             base_url: base_url.to_string(),
             sse_url,
             messages_url,
@@ -56,6 +62,12 @@ impl MCPSSETransport {
         })
     }
 
+    /// Send requests with the OAuth token of a user
+    pub fn with_user(mut self, user_id: Option<Uuid>) -> Self {
+        self.user_id = user_id;
+        self
+    }
+
     pub async fn send_mcp_request(&self, request: MCPRequest) -> Result<MCPResponse, Box<dyn std::error::Error + Send + Sync>> {
         let request_id = request.id.as_ref()
             .and_then(|id| id.as_str())
@@ -68,22 +80,18 @@ impl MCPSSETransport {
         self.response_handlers.lock().await.insert(request_id.clone(), response_sender);
 
         // Send request via HTTP POST to messages endpoint
-        let response = self.client
-            .post(&self.messages_url)
-            .json(&request)
-            .send()
-            .await
-            .map_err(|e| {
+        let response = match oauth::send_authorized(self.server.id, self.user_id, || {
+            self.client.post(&self.messages_url).json(&request)
+        })
+        .await
+        {
+            Ok(response) => response,
+            Err(e) => {
                 // Clean up handler on error
-                tokio::spawn({
-                    let handlers = Arc::clone(&self.response_handlers);
-                    let req_id = request_id.clone();
-                    async move {
-                        handlers.lock().await.remove(&req_id);
-                    }
-                });
-                format!("SSE request failed: {}", e)
-            })?;
+                self.response_handlers.lock().await.remove(&request_id);
+                return Err(e);
+            }
+        };
 
         if !response.status().is_success() {
             self.response_handlers.lock().await.remove(&request_id);
@@ -107,13 +115,16 @@ impl MCPSSETransport {
     async fn start_sse_listener(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let response_handlers = Arc::clone(&self.response_handlers);
         let notification_sender = Arc::clone(&self.notification_sender);
+        let server_id = self.server.id;
+        let user_id = self.user_id;
         let server_name = self.server.name.clone();
         let sse_url = self.sse_url.clone();
+        let messages_url = self.messages_url.clone();
         let client = self.client.clone();
 
         let handle = tokio::spawn(async move {
             loop {
-                match client.get(&sse_url).send().await {
+                match oauth::send_authorized(server_id, user_id, || client.get(&sse_url)).await {
                     Ok(response) => {
                         if !response.status().is_success() {
                             eprintln!("[{}] SSE connection failed: {}", server_name, response.status());
@@ -128,7 +139,23 @@ impl MCPSSETransport {
                             match event_result {
                                 Ok(event) => {
                                     if let Ok(json_value) = serde_json::from_str::<Value>(&event.data) {
-                                        if json_value.get("id").is_some() {
+                                        if is_server_request(&json_value) {
+                                            // This is a request from the server (sampling, ping, ...);
+                                            // the response is posted to the messages endpoint
+                                            if let Ok(request) = serde_json::from_value::<MCPRequest>(json_value) {
+                                                let client = client.clone();
+                                                let messages_url = messages_url.clone();
+                                                tokio::spawn(async move {
+                                                    let response = handle_server_request(server_id, request).await;
+                                                    let answer = oauth::send_authorized(server_id, user_id, || {
+                                                        client.post(&messages_url).json(&response)
+                                                    });
+                                                    if let Err(e) = answer.await {
+                                                        eprintln!("Failed to answer MCP server request: {}", e);
+                                                    }
+                                                });
+                                            }
+                                        } else if json_value.get("id").is_some() {
                                             // This is a response
                                             if let Ok(response) = serde_json::from_value::<MCPResponse>(json_value) {
                                                 if let Some(id) = response.id.as_ref().and_then(|v| v.as_str()) {
@@ -157,6 +184,10 @@ impl MCPSSETransport {
                             }
                         }
                     }
+                    Err(e) if e.is::<MCPAuthorizationRequired>() => {
+                        eprintln!("[{}] SSE connection requires authorization", server_name);
+                        break;
+                    }
                     Err(e) => {
                         eprintln!("[{}] Failed to connect to SSE: {}", server_name, e);
                     }
@@ -215,6 +246,23 @@ impl MCPSSETransport {
         Ok(())
     }
 
+    /// Send a notification to the messages endpoint; no response is expected
+    pub async fn send_mcp_notification(
+        &self,
+        notification: MCPNotification,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let response = oauth::send_authorized(self.server.id, self.user_id, || {
+            self.client.post(&self.messages_url).json(&notification)
+        })
+        .await?;
+
+        if !response.status().is_success() {
+            return Err(format!("SSE HTTP error: {}", response.status()).into());
+        }
+
+        Ok(())
+    }
+
     pub fn subscribe_notifications(&self) -> broadcast::Receiver<MCPNotification> {
         self.notification_sender.subscribe()
     }
@@ -229,8 +277,15 @@ impl MCPTransport for MCPSSETransport {
         // Give SSE connection time to establish
         tokio::time::sleep(std::time::Duration::from_millis(500)).await;
 
-        // Initialize MCP session
-        self.initialize_mcp_session().await?;
+        // Initialize MCP session. Without a user, a server requiring OAuth authorization is
+        // up; each user initializes a session with their own token.
+        match self.initialize_mcp_session().await {
+            Ok(()) => {}
+            Err(e) if self.user_id.is_none() && e.is::<MCPAuthorizationRequired>() => {
+                println!("[{}] MCP SSE server requires user authorization", self.server.name);
+            }
+            Err(e) => return Err(e),
+        }
 
         // Extract port from URL if available
         let port = if let Ok(parsed_url) = Url::parse(&self.base_url) {
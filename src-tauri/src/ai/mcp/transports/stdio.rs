@@ -19,10 +19,12 @@ use tokio::time::{timeout, Duration};
 use uuid::Uuid;
 use futures;
 
-use crate::database::models::mcp_server::MCPServer;
+use crate::database::models::mcp_server::{MCPSandboxProfile, MCPServer};
 use crate::database::queries::mcp_servers;
 use crate::ai::mcp::logging::MCPLogger;
 use crate::ai::mcp::protocol::{MCPRequest, MCPResponse, MCPNotification, InitializeRequest, InitializeResponse, MCPCapabilities, ClientInfo, methods, RootsCapability, PromptsCapability, ResourcesCapability, ToolsCapability, SessionCapability, StreamingCapability, MCPProtocolVersion, detect_protocol_version_from_request, parse_protocol_version};
+use crate::ai::mcp::client_requests::{handle_server_request, is_server_request};
+use crate::ai::mcp::sandbox::{self, SandboxError};
 use crate::ai::mcp::tool_discovery::{ToolDiscoveryClient, discover_and_cache_tools_direct};
 use crate::utils::resource_paths::ResourcePaths;
 use super::{MCPTransport, MCPConnectionInfo};
@@ -55,6 +57,51 @@ pub enum StdioTransportError {
     SecurityViolation(String),
     #[error("Protocol negotiation failed")]
     ProtocolNegotiationFailed,
+    #[error(transparent)]
+    Sandbox(#[from] SandboxError),
+}
+
+lazy_static::lazy_static! {
+    // Client sessions of running stdio servers, for notifications sent outside of a request
+    static ref CLIENT_SESSIONS: std::sync::Mutex<HashMap<Uuid, Arc<MCPClientSession>>> =
+        std::sync::Mutex::new(HashMap::new());
+}
+
+/// Send a notification to a running stdio server.
+/// Returns false when the server is not running.
+pub async fn notify_stdio_server(
+    server_id: Uuid,
+    method: &str,
+    params: Option<Value>,
+) -> Result<bool, StdioTransportError> {
+    let client_session = CLIENT_SESSIONS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&server_id)
+        .cloned();
+
+    match client_session {
+        Some(client_session) => {
+            client_session
+                .send_notification(MCPNotification {
+                    jsonrpc: "2.0".to_string(),
+                    method: method.to_string(),
+                    params,
+                })
+                .await?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Subscribe to the notifications of a running stdio server
+pub fn subscribe_stdio_notifications(server_id: Uuid) -> Option<broadcast::Receiver<MCPNotification>> {
+    CLIENT_SESSIONS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&server_id)
+        .map(|client_session| client_session.subscribe_notifications())
 }
 
 // Session management structures for 2025 spec
@@ -163,9 +210,31 @@ impl MCPClientSession {
         // Use command resolution for bundled runtime support
         let (resolved_command, resolved_args) = resolve_command(command, &args);
 
-        let mut cmd = Command::new(resolved_command);
-        cmd.args(resolved_args)
-            .stdin(std::process::Stdio::piped())
+        let sandbox_profile = server.sandbox_profile().map_err(|e| {
+            logger.log_exec("ERROR", &format!("Refusing to start MCP server with an invalid sandbox profile: {}", e));
+            StdioTransportError::ClientCommunication(format!("Invalid sandbox profile: {}", e))
+        })?;
+        let mut cmd = match &sandbox_profile {
+            Some(profile) => {
+                let cmd = sandbox::sandboxed_command(server.id, profile, &resolved_command, &resolved_args)
+                    .map_err(|e| {
+                        logger.log_exec("ERROR", &format!("Failed to sandbox MCP server process: {}", e));
+                        e
+                    })?;
+                logger.log_exec("INFO", &format!(
+                    "Sandboxing MCP server process (network: {}, scratch directory: {})",
+                    if profile.deny_network { "denied" } else { "allowed" },
+                    sandbox::scratch_dir(server.id).display()
+                ));
+                cmd
+            }
+            None => {
+                let mut cmd = Command::new(resolved_command);
+                cmd.args(resolved_args);
+                cmd
+            }
+        };
+        cmd.stdin(std::process::Stdio::piped())
             .stdout(std::process::Stdio::piped())
             .stderr(std::process::Stdio::piped())
             .env("IS_ZIEE_MCP", "1")
@@ -197,6 +266,7 @@ impl MCPClientSession {
         if let Some(stderr) = child.stderr.take() {
             let logger_clone = logger.clone();
             let server_name = server.name.clone();
+            let sandbox_profile = sandbox_profile.clone();
             tokio::spawn(async move {
                 let mut reader = BufReader::new(stderr);
                 let mut line = String::new();
@@ -208,6 +278,12 @@ impl MCPClientSession {
                             let trimmed = line.trim_end();
                             if !trimmed.is_empty() {
                                 logger_clone.log_stderr(trimmed);
+                                if let Some(violation) = sandbox_profile
+                                    .as_ref()
+                                    .and_then(|profile| sandbox::detect_violation(profile, trimmed))
+                                {
+                                    logger_clone.log_exec("WARN", &format!("Sandbox violation: {}", violation));
+                                }
                             }
                         }
                         Err(e) => {
@@ -224,7 +300,7 @@ impl MCPClientSession {
         *self.child_process.lock().await = Some(child);
 
         // Start stdout reader task
-        self.start_stdout_reader(stdout).await;
+        self.start_stdout_reader(stdout, sandbox_profile).await;
 
         // Send MCP initialize request with version-specific capabilities
         let capabilities = self.create_capabilities_for_version(&preferred_version);
@@ -336,8 +412,9 @@ impl MCPClientSession {
         // Register response handler
         self.response_handlers.lock().await.insert(request_id.clone(), response_sender);
 
-        // Send request
-        if let Some(stdin) = self.request_sender.lock().await.as_mut() {
+        // Send request; stdin is released before waiting so the stdout reader can answer
+        // requests the server makes in the meantime (e.g. sampling during a tool call)
+        let write_result = if let Some(stdin) = self.request_sender.lock().await.as_mut() {
             let request_str = serde_json::to_string(&request)
                 .map_err(|e| StdioTransportError::JsonError(e))?;
             let request_line = format!("{}\n", request_str);
@@ -345,27 +422,30 @@ impl MCPClientSession {
             // Log what we're sending to stdin
             logger.log_stdin(&request_str);
 
-            match timeout(Duration::from_secs(5), stdin.write_all(request_line.as_bytes())).await {
-                Ok(Ok(_)) => {
-                    // Wait for response
-                    match timeout(Duration::from_secs(30), response_receiver).await {
-                        Ok(Ok(response)) => Ok(response),
-                        Ok(Err(_)) => Err(StdioTransportError::ClientCommunication("Response channel closed".to_string())),
-                        Err(_) => {
-                            // Cleanup handler on timeout
-                            self.response_handlers.lock().await.remove(&request_id);
-                            Err(StdioTransportError::Timeout)
-                        }
+            timeout(Duration::from_secs(5), stdin.write_all(request_line.as_bytes())).await
+        } else {
+            self.response_handlers.lock().await.remove(&request_id);
+            return Err(StdioTransportError::ClientCommunication("No stdin available".to_string()));
+        };
+
+        match write_result {
+            Ok(Ok(_)) => {
+                // Wait for response
+                match timeout(Duration::from_secs(30), response_receiver).await {
+                    Ok(Ok(response)) => Ok(response),
+                    Ok(Err(_)) => Err(StdioTransportError::ClientCommunication("Response channel closed".to_string())),
+                    Err(_) => {
+                        // Cleanup handler on timeout
+                        self.response_handlers.lock().await.remove(&request_id);
+                        Err(StdioTransportError::Timeout)
                     }
                 }
-                Ok(Err(e)) => Err(StdioTransportError::ProcessSpawn(e)),
-                Err(_) => {
-                    self.response_handlers.lock().await.remove(&request_id);
-                    Err(StdioTransportError::Timeout)
-                }
             }
-        } else {
-            Err(StdioTransportError::ClientCommunication("No stdin available".to_string()))
+            Ok(Err(e)) => Err(StdioTransportError::ProcessSpawn(e)),
+            Err(_) => {
+                self.response_handlers.lock().await.remove(&request_id);
+                Err(StdioTransportError::Timeout)
+            }
         }
     }
 
@@ -389,11 +469,14 @@ impl MCPClientSession {
         self.notification_sender.subscribe()
     }
 
-    async fn start_stdout_reader(&self, stdout: ChildStdout) {
+    async fn start_stdout_reader(&self, stdout: ChildStdout, sandbox_profile: Option<MCPSandboxProfile>) {
         let logger = MCPLogger::new(self.server_id);
 
+        let child_process = Arc::clone(&self.child_process);
         let response_handlers = Arc::clone(&self.response_handlers);
         let notification_sender = Arc::clone(&self.notification_sender);
+        let request_sender = Arc::clone(&self.request_sender);
+        let server_id = self.server_id;
         let server_name = self.server_name.clone();
 
         tokio::spawn(async move {
@@ -406,6 +489,9 @@ impl MCPClientSession {
                     Ok(0) => {
                         logger.log_exec("INFO", "MCP server stdout closed");
                         println!("[{}] MCP server stdout closed", server_name);
+                        if let Some(profile) = &sandbox_profile {
+                            report_sandboxed_exit(&logger, profile, &child_process).await;
+                        }
                         break;
                     }
                     Ok(_) => {
@@ -415,7 +501,25 @@ impl MCPClientSession {
                             logger.log_stdout(line);
 
                             if let Ok(json_value) = serde_json::from_str::<Value>(line) {
-                                if json_value.get("id").is_some() {
+                                if is_server_request(&json_value) {
+                                    // This is a request from the server (sampling, ping, ...);
+                                    // answer it without blocking the reader
+                                    if let Ok(request) = serde_json::from_value::<MCPRequest>(json_value) {
+                                        let request_sender = Arc::clone(&request_sender);
+                                        tokio::spawn(async move {
+                                            let response = handle_server_request(server_id, request).await;
+                                            if let Ok(response_str) = serde_json::to_string(&response) {
+                                                MCPLogger::new(server_id).log_stdin(&response_str);
+                                                if let Some(stdin) = request_sender.lock().await.as_mut() {
+                                                    let response_line = format!("{}\n", response_str);
+                                                    if let Err(e) = stdin.write_all(response_line.as_bytes()).await {
+                                                        eprintln!("Failed to answer MCP server request: {}", e);
+                                                    }
+                                                }
+                                            }
+                                        });
+                                    }
+                                } else if json_value.get("id").is_some() {
                                     // This is a response
                                     if let Ok(response) = serde_json::from_value::<MCPResponse>(json_value) {
                                         if let Some(id_value) = response.id.as_ref() {
@@ -473,6 +577,25 @@ impl MCPClientSession {
     }
 }
 
+// Log why a sandboxed server exited when one of its limits killed it
+async fn report_sandboxed_exit(
+    logger: &MCPLogger,
+    profile: &MCPSandboxProfile,
+    child_process: &Mutex<Option<Child>>,
+) {
+    let mut child_guard = child_process.lock().await;
+    let Some(child) = child_guard.as_mut() else {
+        return;
+    };
+
+    // stdout closes just before the process exits
+    if let Ok(Ok(status)) = timeout(Duration::from_secs(2), child.wait()).await {
+        if let Some(reason) = sandbox::describe_exit(profile, &status) {
+            logger.log_exec("WARN", &format!("Sandbox violation: {} ({})", reason, status));
+        }
+    }
+}
+
 // Implement ToolDiscoveryClient trait for MCPClientSession
 impl ToolDiscoveryClient for MCPClientSession {
     type Error = StdioTransportError;
@@ -665,6 +788,10 @@ impl MCPTransport for MCPStdioTransport {
             state.proxy_port = port;
             state.proxy_url = proxy_url.clone();
         }
+        CLIENT_SESSIONS
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(self.server_id, Arc::clone(&client_session));
 
         // Update database with the proxy URL
         if let Err(e) = mcp_servers::update_mcp_server_proxy_url(&self.server_id, &proxy_url).await {
@@ -709,6 +836,11 @@ impl MCPTransport for MCPStdioTransport {
         let logger = MCPLogger::new(self.server_id);
         logger.log_exec("INFO", "Stopping MCP stdio transport");
 
+        CLIENT_SESSIONS
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&self.server_id);
+
         // Clear proxy URL from database
         if let Err(e) = mcp_servers::update_mcp_server_proxy_url(&self.server_id, "").await {
             eprintln!("Failed to clear MCP server proxy URL in database: {}", e);
@@ -39,7 +39,8 @@ pub use core::{
   ContentPart, FileReference, MessageContent, ModelInstance, ProviderFileContent,
   ProxyConfig, SimplifiedChatRequest,
   // New AIModel exports
-  SimplifiedEmbeddingsRequest, StreamingChunk, StreamingResponse, Usage,
+  SimplifiedEmbeddingsRequest, SimplifiedImageGenerationRequest, SimplifiedRerankRequest,
+  StreamingChunk, StreamingResponse, Usage,
 };
 pub use model_manager::{
   acquire_global_start_mutex,
@@ -61,4 +62,9 @@ pub use model_manager::{
 };
 // New model factory exports
 pub use model_manager::model_factory::{create_ai_model, create_ai_model_with_provider};
+// Model group failover exports
+pub use model_manager::failover::{
+  chat_stream_with_failover, forward_request_with_failover, generate_images_with_failover,
+  FailoverChatStream, FailoverImages, FailoverResponse, ForwardEndpoint,
+};
 pub use providers::*;
@@ -0,0 +1,186 @@
+use futures_util::StreamExt;
+use uuid::Uuid;
+
+use crate::ai::core::providers::is_retryable_provider_error;
+use crate::ai::model_manager::model_factory::create_ai_model;
+use crate::ai::core::providers::ImageGenerationResponse;
+use crate::ai::{AIModel, SimplifiedChatRequest, SimplifiedImageGenerationRequest, StreamingResponse};
+
+/// A streaming chat started on one of the candidate models
+pub struct FailoverChatStream {
+    /// The model that accepted the request
+    pub model: Box<dyn AIModel>,
+    pub stream: StreamingResponse,
+}
+
+/// A raw API request forwarded to one of the candidate models
+pub struct FailoverResponse {
+    /// The model that answered the request
+    pub model: Box<dyn AIModel>,
+    pub response: reqwest::Response,
+}
+
+/// Images generated by one of the candidate models
+pub struct FailoverImages {
+    /// The model that generated the images
+    pub model: Box<dyn AIModel>,
+    pub response: ImageGenerationResponse,
+}
+
+/// Start a streaming chat on the first candidate model that accepts it
+///
+/// A candidate is skipped when it cannot be created (e.g. a local model fails to start) or
+/// fails with a transient error (rate limit, server error, timeout) before producing any
+/// output. Other errors are returned right away since the next model would most likely reject
+/// the same request. Once a stream has started, errors are no longer retried.
+pub async fn chat_stream_with_failover(
+    model_ids: &[Uuid],
+    request: SimplifiedChatRequest,
+) -> Result<FailoverChatStream, Box<dyn std::error::Error + Send + Sync>> {
+    let mut last_error: Option<Box<dyn std::error::Error + Send + Sync>> = None;
+
+    for (attempt, model_id) in model_ids.iter().enumerate() {
+        let is_last = attempt + 1 == model_ids.len();
+
+        let model = match create_ai_model(*model_id).await {
+            Ok(model) => model,
+            Err(e) => {
+                tracing::warn!("Skipping model {}: failed to create it: {}", model_id, e);
+                last_error = Some(e);
+                continue;
+            }
+        };
+
+        if !model.supports_streaming() {
+            tracing::warn!("Skipping model {}: streaming is not supported", model_id);
+            last_error = Some("Provider does not support streaming responses".into());
+            continue;
+        }
+
+        let mut stream = match model.chat_stream(request.clone()).await {
+            Ok(stream) => stream,
+            Err(e) if !is_last && is_retryable_provider_error(e.as_ref()) => {
+                tracing::warn!("Model {} failed, trying the next one: {}", model_id, e);
+                last_error = Some(e);
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+
+        // Some providers only report failures such as rate limits as the first stream item
+        match stream.next().await {
+            Some(Err(e)) if !is_last && is_retryable_provider_error(e.as_ref()) => {
+                tracing::warn!("Model {} failed, trying the next one: {}", model_id, e);
+                last_error = Some(e);
+            }
+            first => {
+                let stream: StreamingResponse =
+                    Box::pin(futures_util::stream::iter(first).chain(stream));
+                return Ok(FailoverChatStream { model, stream });
+            }
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| "No model available to serve the request".into()))
+}
+
+/// Provider endpoint a raw API proxy request is forwarded to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForwardEndpoint {
+    ChatCompletions,
+    Embeddings,
+}
+
+/// Forward a raw request to the given endpoint of the first candidate model that accepts it, for
+/// the API proxy
+///
+/// Responses with a transient status (429, 408, 5xx) and connection failures fall through to
+/// the next candidate; the last candidate's response is returned as is. The `model` field of
+/// the request is rewritten to each candidate's provider model name.
+pub async fn forward_request_with_failover(
+    model_ids: &[Uuid],
+    endpoint: ForwardEndpoint,
+    mut request: serde_json::Value,
+) -> Result<FailoverResponse, Box<dyn std::error::Error + Send + Sync>> {
+    let mut last_error: Option<Box<dyn std::error::Error + Send + Sync>> = None;
+
+    for (attempt, model_id) in model_ids.iter().enumerate() {
+        let is_last = attempt + 1 == model_ids.len();
+
+        let model = match create_ai_model(*model_id).await {
+            Ok(model) => model,
+            Err(e) => {
+                tracing::warn!("Skipping model {}: failed to create it: {}", model_id, e);
+                last_error = Some(e);
+                continue;
+            }
+        };
+
+        request["model"] = serde_json::json!(model.model_name());
+
+        let result = match endpoint {
+            ForwardEndpoint::ChatCompletions => model.forward_chat_request(request.clone()).await,
+            ForwardEndpoint::Embeddings => model.forward_embeddings_request(request.clone()).await,
+        };
+        match result {
+            Ok(response) => {
+                let status = response.status();
+                let retryable = status.as_u16() == 408
+                    || status.as_u16() == 429
+                    || status.is_server_error();
+                if retryable && !is_last {
+                    tracing::warn!(
+                        "Model {} responded with {}, trying the next one",
+                        model_id,
+                        status
+                    );
+                    last_error =
+                        Some(format!("Model {} responded with {}", model_id, status).into());
+                    continue;
+                }
+                return Ok(FailoverResponse { model, response });
+            }
+            Err(e) if !is_last && is_retryable_provider_error(e.as_ref()) => {
+                tracing::warn!("Model {} failed, trying the next one: {}", model_id, e);
+                last_error = Some(e);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| "No model available to serve the request".into()))
+}
+
+/// Generate images on the first candidate model that accepts the request
+///
+/// Candidates are skipped on the same failures as in [`chat_stream_with_failover`].
+pub async fn generate_images_with_failover(
+    model_ids: &[Uuid],
+    request: SimplifiedImageGenerationRequest,
+) -> Result<FailoverImages, Box<dyn std::error::Error + Send + Sync>> {
+    let mut last_error: Option<Box<dyn std::error::Error + Send + Sync>> = None;
+
+    for (attempt, model_id) in model_ids.iter().enumerate() {
+        let is_last = attempt + 1 == model_ids.len();
+
+        let model = match create_ai_model(*model_id).await {
+            Ok(model) => model,
+            Err(e) => {
+                tracing::warn!("Skipping model {}: failed to create it: {}", model_id, e);
+                last_error = Some(e);
+                continue;
+            }
+        };
+
+        match model.generate_images(request.clone()).await {
+            Ok(response) => return Ok(FailoverImages { model, response }),
+            Err(e) if !is_last && is_retryable_provider_error(e.as_ref()) => {
+                tracing::warn!("Model {} failed, trying the next one: {}", model_id, e);
+                last_error = Some(e);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| "No model available to serve the request".into()))
+}
@@ -1,5 +1,6 @@
 pub mod auto_unload;
 pub mod core;
+pub mod failover;
 pub mod model_manager;
 pub mod model_factory;
 
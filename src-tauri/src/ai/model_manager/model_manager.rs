@@ -16,7 +16,8 @@ use crate::ai::{
     providers::{
         anthropic::AnthropicProvider, custom::CustomProvider, deepseek::DeepSeekProvider,
         gemini::GeminiProvider, groq::GroqProvider, huggingface::HuggingFaceProvider,
-        local::LocalProvider, mistral::MistralProvider, openai::OpenAIProvider,
+        local::LocalProvider, mistral::MistralProvider, ollama::OllamaProvider,
+        openai::OpenAIProvider,
     },
 };
 use crate::database::queries::models::get_model_by_id;
@@ -640,6 +641,7 @@ pub async fn create_ai_provider_with_model_id(
         "deepseek" => create_standard_provider!(DeepSeekProvider, provider, proxy_config),
         "custom" => create_standard_provider!(CustomProvider, provider, proxy_config),
         "huggingface" => create_standard_provider!(HuggingFaceProvider, provider, proxy_config),
+        "ollama" => create_standard_provider!(OllamaProvider, provider, proxy_config),
         "local" => {
             let model_id = model_id.ok_or("Model ID is required for local providers")?;
 
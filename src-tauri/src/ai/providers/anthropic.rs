@@ -10,13 +10,16 @@ use uuid::Uuid;
 use crate::ai::core::provider_base::build_http_client;
 use crate::ai::core::providers::{
     AIProvider, ChatRequest, ChatResponse, ContentPart, FileReference, MessageContent,
-    ProviderFileContent, ProxyConfig, StreamingChunk, StreamingResponse, Usage,
+    ProviderApiError, ProviderFileContent, ProxyConfig, StreamingChunk, StreamingResponse, Usage,
 };
 use crate::ai::file_helpers::{add_provider_mapping_to_file_ref, load_file_content};
 use crate::database::queries::files::{create_provider_file_mapping, get_provider_file_mapping};
 use crate::global::FILE_STORAGE;
 use crate::utils::file_storage::extract_extension;
 
+/// Tool the reply goes through when it must match a JSON Schema
+const JSON_OUTPUT_TOOL: &str = "json_response";
+
 #[derive(Debug, Clone)]
 pub struct AnthropicProvider {
     client: Client,
@@ -41,6 +44,8 @@ struct AnthropicContent {
     id: Option<String>,
     name: Option<String>,
     input: Option<serde_json::Value>,
+    // Thinking fields
+    thinking: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -57,6 +62,20 @@ struct AnthropicStreamResponse {
     content_block: Option<AnthropicContentBlock>,
     #[allow(dead_code)]
     index: Option<u32>,
+    // Input tokens come with `message_start`, output tokens with `message_delta`
+    message: Option<AnthropicStreamMessage>,
+    usage: Option<AnthropicStreamUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicStreamMessage {
+    usage: Option<AnthropicStreamUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicStreamUsage {
+    input_tokens: Option<u32>,
+    output_tokens: Option<u32>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -72,10 +91,13 @@ struct AnthropicContentBlock {
 
 #[derive(Debug, Deserialize)]
 struct AnthropicDelta {
-    #[serde(rename = "type")]
+    // Missing from the delta of `message_delta` events
+    #[serde(rename = "type", default)]
     delta_type: String,
     text: Option<String>,
     partial_json: Option<String>,
+    thinking: Option<String>,
+    signature: Option<String>,
     stop_reason: Option<String>,
 }
 
@@ -157,6 +179,7 @@ impl AnthropicProvider {
                                 }
                                 system_text.push_str(&format!("Tool result [{}]: {}", call_id, output));
                             }
+                            ContentPart::Thinking { .. } => {}
                         }
                     }
                     Ok((Some(system_text), None))
@@ -238,6 +261,16 @@ impl AnthropicProvider {
                         "content": output
                     }));
                 }
+                ContentPart::Thinking { text, signature } => {
+                    // Unsigned reasoning (e.g. from another provider) is rejected by the API
+                    if let Some(signature) = signature {
+                        content_array.push(json!({
+                            "type": "thinking",
+                            "thinking": text,
+                            "signature": signature
+                        }));
+                    }
+                }
             }
         }
 
@@ -437,38 +470,88 @@ impl AnthropicProvider {
             }
         }
 
+        let mut max_tokens = request.parameters.as_ref().and_then(|p| p.max_tokens).unwrap_or(4096);
+        // Extended thinking takes at least 1024 tokens, counted in max_tokens
+        let thinking_budget = request
+            .parameters
+            .as_ref()
+            .and_then(|p| p.thinking_budget)
+            .map(|budget| budget.max(1024));
+        if let Some(budget) = thinking_budget {
+            if max_tokens <= budget {
+                max_tokens = budget + 4096;
+            }
+        }
+
         let mut body = json!({
             "model": request.model_name,
             "messages": messages,
-            "max_tokens": request.parameters.as_ref().and_then(|p| p.max_tokens).unwrap_or(4096),
+            "max_tokens": max_tokens,
             "stream": request.stream
         });
 
-        if !system_message.is_empty() {
-            body["system"] = json!(system_message);
+        if let Some(budget) = thinking_budget {
+            body["thinking"] = json!({
+                "type": "enabled",
+                "budget_tokens": budget
+            });
         }
 
         // Add tools if provided
-        if let Some(tools) = &request.tools {
-            let anthropic_tools: Vec<Value> = tools
-                .iter()
-                .map(|tool| {
-                    json!({
-                        "name": tool.name,
-                        "description": tool.description,
-                        "input_schema": tool.input_schema
-                    })
+        let mut anthropic_tools: Vec<Value> = request
+            .tools
+            .iter()
+            .flatten()
+            .map(|tool| {
+                json!({
+                    "name": tool.name,
+                    "description": tool.description,
+                    "input_schema": tool.input_schema
                 })
-                .collect();
+            })
+            .collect();
+
+        // A JSON reply is forced through a tool taking the schema as its input
+        if let Some(schema) = &request.json_schema {
+            let other_tools = !anthropic_tools.is_empty();
+            anthropic_tools.push(json!({
+                "name": JSON_OUTPUT_TOOL,
+                "description": "Give your reply as the input of this tool",
+                "input_schema": Self::output_tool_schema(schema)
+            }));
+            if thinking_budget.is_some() {
+                // Tools cannot be forced with extended thinking
+                if !system_message.is_empty() {
+                    system_message.push('\n');
+                }
+                system_message.push_str(&format!(
+                    "Give your final reply by calling the {} tool.",
+                    JSON_OUTPUT_TOOL
+                ));
+            } else if other_tools {
+                body["tool_choice"] = json!({ "type": "any" });
+            } else {
+                body["tool_choice"] = json!({ "type": "tool", "name": JSON_OUTPUT_TOOL });
+            }
+        }
+
+        if !anthropic_tools.is_empty() {
             body["tools"] = json!(anthropic_tools);
         }
 
+        if !system_message.is_empty() {
+            body["system"] = json!(system_message);
+        }
+
         if let Some(parameters) = &request.parameters {
-            if let Some(temperature) = parameters.temperature {
-                body["temperature"] = json!(temperature);
-            }
-            if let Some(top_p) = parameters.top_p {
-                body["top_p"] = json!(top_p);
+            // Sampling parameters are not accepted with extended thinking
+            if thinking_budget.is_none() {
+                if let Some(temperature) = parameters.temperature {
+                    body["temperature"] = json!(temperature);
+                }
+                if let Some(top_p) = parameters.top_p {
+                    body["top_p"] = json!(top_p);
+                }
             }
             // Note: Anthropic doesn't support seed parameter, but we can add stop sequences
             if let Some(stop) = &parameters.stop {
@@ -478,6 +561,28 @@ impl AnthropicProvider {
 
         Ok(body)
     }
+
+    /// Input schema of the output tool. Tool inputs are objects, so other schemas are wrapped.
+    fn output_tool_schema(schema: &Value) -> Value {
+        if schema["type"] == "object" {
+            schema.clone()
+        } else {
+            json!({
+                "type": "object",
+                "properties": { "value": schema },
+                "required": ["value"]
+            })
+        }
+    }
+
+    /// The reply given as the output tool's input
+    fn output_tool_reply(schema: &Value, input: Value) -> String {
+        if schema["type"] == "object" {
+            input.to_string()
+        } else {
+            input.get("value").cloned().unwrap_or(Value::Null).to_string()
+        }
+    }
 }
 
 #[async_trait]
@@ -506,17 +611,32 @@ impl AIProvider for AnthropicProvider {
             .await?;
 
         if !response.status().is_success() {
+            let status = response.status();
             let error_text = response.text().await?;
-            return Err(format!("Anthropic API error: {}", error_text).into());
+            return Err(ProviderApiError::new(
+                status,
+                format!("Anthropic API error: {}", error_text),
+            )
+            .into());
         }
 
         let anthropic_response: AnthropicResponse = response.json().await?;
 
+        let output_reply = processed_request.json_schema.as_ref().and_then(|schema| {
+            anthropic_response
+                .content
+                .iter()
+                .find(|c| {
+                    c.content_type == "tool_use" && c.name.as_deref() == Some(JSON_OUTPUT_TOOL)
+                })
+                .map(|c| Self::output_tool_reply(schema, c.input.clone().unwrap_or_default()))
+        });
+
         // Check for tool use
         let tool_use = anthropic_response
             .content
             .iter()
-            .find(|c| c.content_type == "tool_use")
+            .find(|c| c.content_type == "tool_use" && c.name.as_deref() != Some(JSON_OUTPUT_TOOL))
             .and_then(|c| {
                 Some(crate::ai::core::providers::ToolUse {
                     id: c.id.clone()?,
@@ -525,12 +645,20 @@ impl AIProvider for AnthropicProvider {
                 })
             });
 
-        let content = anthropic_response
+        let thinking = anthropic_response
+            .content
+            .iter()
+            .filter(|c| c.content_type == "thinking")
+            .filter_map(|c| c.thinking.clone())
+            .collect::<Vec<_>>();
+
+        let text = anthropic_response
             .content
             .into_iter()
             .find(|c| c.content_type == "text")
             .and_then(|c| c.text)
             .unwrap_or_default();
+        let content = output_reply.clone().unwrap_or(text);
 
         let usage = anthropic_response.usage.map(|u| Usage {
             prompt_tokens: Some(u.input_tokens),
@@ -540,9 +668,13 @@ impl AIProvider for AnthropicProvider {
 
         Ok(ChatResponse {
             content,
-            finish_reason: anthropic_response.stop_reason,
+            finish_reason: match output_reply {
+                Some(_) => Some("stop".to_string()),
+                None => anthropic_response.stop_reason,
+            },
             usage,
             tool_use,
+            thinking: if thinking.is_empty() { None } else { Some(thinking.join("\n\n")) },
         })
     }
 
@@ -571,8 +703,13 @@ impl AIProvider for AnthropicProvider {
             .await?;
 
         if !response.status().is_success() {
+            let status = response.status();
             let error_text = response.text().await?;
-            return Err(format!("Anthropic API error: {}", error_text).into());
+            return Err(ProviderApiError::new(
+                status,
+                format!("Anthropic API error: {}", error_text),
+            )
+            .into());
         }
 
         use std::sync::{Arc, Mutex};
@@ -581,6 +718,12 @@ impl AIProvider for AnthropicProvider {
         let buffer = Arc::new(Mutex::new(String::new()));
         let current_tool_use = Arc::new(Mutex::new(None::<crate::ai::core::providers::ToolUse>));
         let tool_input_buffer = Arc::new(Mutex::new(String::new()));
+        let output_schema = processed_request.json_schema.clone();
+        let stream_usage = Arc::new(Mutex::new(Usage {
+            prompt_tokens: None,
+            completion_tokens: None,
+            total_tokens: None,
+        }));
 
         let stream = response.bytes_stream().map(move |result| {
             result.map_err(|e| e.into()).and_then(|bytes| {
@@ -604,6 +747,18 @@ impl AIProvider for AnthropicProvider {
                         if let Ok(chunk) = serde_json::from_str::<AnthropicStreamResponse>(json_str)
                         {
                             match chunk.event_type.as_str() {
+                                "message_start" => {
+                                    if let Some(usage) = chunk.message.and_then(|m| m.usage) {
+                                        stream_usage.lock().unwrap().prompt_tokens =
+                                            usage.input_tokens;
+                                    }
+                                }
+                                "message_delta" => {
+                                    if let Some(usage) = chunk.usage {
+                                        stream_usage.lock().unwrap().completion_tokens =
+                                            usage.output_tokens;
+                                    }
+                                }
                                 "content_block_start" => {
                                     // Handle initial content block if needed
                                     if let Some(content_block) = chunk.content_block {
@@ -614,6 +769,9 @@ impl AIProvider for AnthropicProvider {
                                                         content: content_block.text,
                                                         finish_reason: None,
                                                         tool_use: None,
+                                                        usage: None,
+                                                        thinking: None,
+                                                        thinking_signature: None,
                                                     });
                                                 }
                                             }
@@ -641,6 +799,32 @@ impl AIProvider for AnthropicProvider {
                                                     content: delta.text,
                                                     finish_reason: delta.stop_reason,
                                                     tool_use: None,
+                                                    usage: None,
+                                                    thinking: None,
+                                                    thinking_signature: None,
+                                                });
+                                            }
+                                        } else if delta.delta_type == "thinking_delta" {
+                                            if delta.thinking.is_some() {
+                                                chunks.push(StreamingChunk {
+                                                    content: None,
+                                                    finish_reason: None,
+                                                    tool_use: None,
+                                                    usage: None,
+                                                    thinking: delta.thinking,
+                                                    thinking_signature: None,
+                                                });
+                                            }
+                                        } else if delta.delta_type == "signature_delta" {
+                                            // Ends a thinking block, which can then be sent back
+                                            if delta.signature.is_some() {
+                                                chunks.push(StreamingChunk {
+                                                    content: None,
+                                                    finish_reason: None,
+                                                    tool_use: None,
+                                                    usage: None,
+                                                    thinking: None,
+                                                    thinking_signature: delta.signature,
                                                 });
                                             }
                                         } else if delta.delta_type == "input_json_delta" {
@@ -671,18 +855,40 @@ impl AIProvider for AnthropicProvider {
                                             }
                                         }
 
+                                        // The output tool carries the reply itself
+                                        let output_schema = output_schema
+                                            .as_ref()
+                                            .filter(|_| tool_use.name == JSON_OUTPUT_TOOL);
+                                        let (content, tool_use) = match output_schema {
+                                            Some(schema) => (
+                                                Some(Self::output_tool_reply(schema, tool_use.input)),
+                                                None,
+                                            ),
+                                            None => (None, Some(tool_use)),
+                                        };
                                         chunks.push(StreamingChunk {
-                                            content: None,
+                                            content,
                                             finish_reason: None,
-                                            tool_use: Some(tool_use),
+                                            tool_use,
+                                            usage: None,
+                                            thinking: None,
+                                            thinking_signature: None,
                                         });
                                     }
                                 }
                                 "message_stop" => {
+                                    let mut usage = stream_usage.lock().unwrap().clone();
+                                    usage.total_tokens = match (usage.prompt_tokens, usage.completion_tokens) {
+                                        (Some(prompt), Some(completion)) => Some(prompt + completion),
+                                        _ => None,
+                                    };
                                     chunks.push(StreamingChunk {
                                         content: None,
                                         finish_reason: Some("stop".to_string()),
                                         tool_use: None,
+                                        usage: Some(usage),
+                                        thinking: None,
+                                        thinking_signature: None,
                                     });
                                     break;
                                 }
@@ -710,6 +916,10 @@ impl AIProvider for AnthropicProvider {
         "anthropic"
     }
 
+    fn replays_thinking(&self) -> bool {
+        true
+    }
+
     fn supports_file_upload(&self) -> bool {
         true
     }
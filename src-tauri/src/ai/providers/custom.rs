@@ -4,7 +4,8 @@ use uuid::Uuid;
 use super::openai_compatible::OpenAICompatibleProvider;
 use crate::ai::core::providers::{
     AIProvider, ChatRequest, ChatResponse, ContentPart, EmbeddingsRequest, EmbeddingsResponse,
-    FileReference, MessageContent, ProviderFileContent, ProxyConfig, StreamingResponse,
+    FileReference, ImageGenerationRequest, ImageGenerationResponse, MessageContent,
+    ProviderFileContent, ProxyConfig, StreamingResponse, map_provider_error,
 };
 
 #[derive(Debug, Clone)]
@@ -81,6 +82,7 @@ impl CustomProvider {
                             ContentPart::ToolResult { call_id, output } => {
                                 format!("[Tool Result {}]: {}", call_id, output)
                             }
+                            ContentPart::Thinking { .. } => String::new(),
                         })
                         .collect();
 
@@ -151,7 +153,7 @@ impl AIProvider for CustomProvider {
         let processed_request = self.preprocess_request(request).await?;
         match self.inner.chat(processed_request).await {
             Ok(response) => Ok(response),
-            Err(e) => Err(self.handle_custom_errors(&e.to_string())),
+            Err(e) => Err(map_provider_error(e, |message| self.handle_custom_errors(message))),
         }
     }
 
@@ -162,7 +164,7 @@ impl AIProvider for CustomProvider {
         let processed_request = self.preprocess_request(request).await?;
         match self.inner.chat_stream(processed_request).await {
             Ok(response) => Ok(response),
-            Err(e) => Err(self.handle_custom_errors(&e.to_string())),
+            Err(e) => Err(map_provider_error(e, |message| self.handle_custom_errors(message))),
         }
     }
 
@@ -197,7 +199,7 @@ impl AIProvider for CustomProvider {
         // Delegate to the inner provider with enhanced error handling
         match self.inner.upload_file(file_data, filename, mime_type).await {
             Ok(result) => Ok(result),
-            Err(e) => Err(self.handle_custom_errors(&e.to_string())),
+            Err(e) => Err(map_provider_error(e, |message| self.handle_custom_errors(message))),
         }
     }
 
@@ -209,7 +211,7 @@ impl AIProvider for CustomProvider {
         // Delegate to the inner provider with enhanced error handling
         match self.inner.resolve_file_content(file_ref, provider_id).await {
             Ok(result) => Ok(result),
-            Err(e) => Err(self.handle_custom_errors(&e.to_string())),
+            Err(e) => Err(map_provider_error(e, |message| self.handle_custom_errors(message))),
         }
     }
 
@@ -219,7 +221,17 @@ impl AIProvider for CustomProvider {
     ) -> Result<reqwest::Response, Box<dyn std::error::Error + Send + Sync>> {
         match self.inner.forward_chat_request(request).await {
             Ok(response) => Ok(response),
-            Err(e) => Err(self.handle_custom_errors(&e.to_string())),
+            Err(e) => Err(map_provider_error(e, |message| self.handle_custom_errors(message))),
+        }
+    }
+
+    async fn forward_embeddings_request(
+        &self,
+        request: serde_json::Value,
+    ) -> Result<reqwest::Response, Box<dyn std::error::Error + Send + Sync>> {
+        match self.inner.forward_embeddings_request(request).await {
+            Ok(response) => Ok(response),
+            Err(e) => Err(map_provider_error(e, |message| self.handle_custom_errors(message))),
         }
     }
 
@@ -229,7 +241,17 @@ impl AIProvider for CustomProvider {
     ) -> Result<EmbeddingsResponse, Box<dyn std::error::Error + Send + Sync>> {
         match self.inner.embeddings_impl(request).await {
             Ok(response) => Ok(response),
-            Err(e) => Err(self.handle_custom_errors(&e.to_string())),
+            Err(e) => Err(map_provider_error(e, |message| self.handle_custom_errors(message))),
+        }
+    }
+
+    async fn generate_images(
+        &self,
+        request: ImageGenerationRequest,
+    ) -> Result<ImageGenerationResponse, Box<dyn std::error::Error + Send + Sync>> {
+        match self.inner.generate_images_impl(request).await {
+            Ok(response) => Ok(response),
+            Err(e) => Err(map_provider_error(e, |message| self.handle_custom_errors(message))),
         }
     }
 }
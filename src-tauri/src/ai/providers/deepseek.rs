@@ -5,6 +5,7 @@ use super::openai_compatible::OpenAICompatibleProvider;
 use crate::ai::core::providers::{
     AIProvider, ChatRequest, ChatResponse, ContentPart, EmbeddingsRequest, EmbeddingsResponse,
     FileReference, MessageContent, ProviderFileContent, ProxyConfig, StreamingResponse,
+    map_provider_error,
 };
 
 #[derive(Debug, Clone)]
@@ -53,6 +54,7 @@ impl DeepSeekProvider {
                         ContentPart::ToolResult { call_id, output } => {
                             text_parts.push(format!("[Tool Result {}]: {}", call_id, output));
                         }
+                        ContentPart::Thinking { .. } => {}
                         ContentPart::FileReference(file_ref) => {
                             // Convert file reference to text description
                             text_parts.push(format!(
@@ -126,7 +128,7 @@ impl AIProvider for DeepSeekProvider {
         let processed_request = self.preprocess_request(request).await?;
         match self.inner.chat(processed_request).await {
             Ok(response) => Ok(response),
-            Err(e) => Err(self.handle_deepseek_errors(&e.to_string())),
+            Err(e) => Err(map_provider_error(e, |message| self.handle_deepseek_errors(message))),
         }
     }
 
@@ -137,7 +139,7 @@ impl AIProvider for DeepSeekProvider {
         let processed_request = self.preprocess_request(request).await?;
         match self.inner.chat_stream(processed_request).await {
             Ok(response) => Ok(response),
-            Err(e) => Err(self.handle_deepseek_errors(&e.to_string())),
+            Err(e) => Err(map_provider_error(e, |message| self.handle_deepseek_errors(message))),
         }
     }
 
@@ -181,6 +183,13 @@ impl AIProvider for DeepSeekProvider {
         self.inner.forward_chat_request(request).await
     }
 
+    async fn forward_embeddings_request(
+        &self,
+        request: serde_json::Value,
+    ) -> Result<reqwest::Response, Box<dyn std::error::Error + Send + Sync>> {
+        self.inner.forward_embeddings_request(request).await
+    }
+
     async fn embeddings(
         &self,
         request: EmbeddingsRequest,
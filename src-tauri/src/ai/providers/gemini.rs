@@ -11,7 +11,7 @@ use crate::ai::core::provider_base::build_http_client;
 use crate::ai::core::providers::{
     AIProvider, ChatRequest, ChatResponse, ContentPart, EmbeddingData, EmbeddingsInput,
     EmbeddingsRequest, EmbeddingsResponse, EmbeddingsUsage, FileReference, MessageContent,
-    ProviderFileContent, ProxyConfig, StreamingChunk, StreamingResponse, Usage,
+    ProviderApiError, ProviderFileContent, ProxyConfig, StreamingChunk, StreamingResponse, Usage,
 };
 use crate::ai::file_helpers::{add_provider_mapping_to_file_ref, load_file_content};
 use crate::database::queries::files::{create_provider_file_mapping, get_provider_file_mapping};
@@ -50,6 +50,9 @@ struct GeminiContent {
 enum GeminiPart {
     Text {
         text: String,
+        // Set on the reasoning parts returned with `includeThoughts`
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        thought: bool,
     },
     FileData {
         #[serde(rename = "fileData")]
@@ -101,6 +104,20 @@ struct GeminiGenerationConfig {
     top_p: Option<f64>,
     #[serde(rename = "stopSequences")]
     stop_sequences: Option<Vec<String>>,
+    #[serde(rename = "thinkingConfig", skip_serializing_if = "Option::is_none")]
+    thinking_config: Option<GeminiThinkingConfig>,
+    #[serde(rename = "responseMimeType", skip_serializing_if = "Option::is_none")]
+    response_mime_type: Option<String>,
+    #[serde(rename = "responseSchema", skip_serializing_if = "Option::is_none")]
+    response_schema: Option<Value>,
+}
+
+#[derive(Debug, Serialize)]
+struct GeminiThinkingConfig {
+    #[serde(rename = "thinkingBudget")]
+    thinking_budget: i32,
+    #[serde(rename = "includeThoughts")]
+    include_thoughts: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -318,7 +335,7 @@ impl GeminiProvider {
             };
 
             let parts = match &msg.content {
-                MessageContent::Text(text) => vec![GeminiPart::Text { text: text.clone() }],
+                MessageContent::Text(text) => vec![GeminiPart::Text { text: text.clone(), thought: false }],
                 MessageContent::Multimodal(content_parts) => {
                     self.process_multimodal_parts(content_parts).await?
                 }
@@ -343,20 +360,23 @@ impl GeminiProvider {
         for part in parts {
             match part {
                 ContentPart::Text(text) => {
-                    gemini_parts.push(GeminiPart::Text { text: text.clone() });
+                    gemini_parts.push(GeminiPart::Text { text: text.clone(), thought: false });
                 }
                 ContentPart::ToolUse { id, name, input } => {
                     // Convert tool use to text format for Gemini
                     gemini_parts.push(GeminiPart::Text {
                         text: format!("[Tool Use {}] {}: {:?}", id, name, input),
+                        thought: false,
                     });
                 }
                 ContentPart::ToolResult { call_id, output } => {
                     // Convert tool result to text format for Gemini
                     gemini_parts.push(GeminiPart::Text {
                         text: format!("[Tool Result {}]: {}", call_id, output),
+                        thought: false,
                     });
                 }
+                ContentPart::Thinking { .. } => {}
                 ContentPart::FileReference(file_ref) => {
                     if let Some(mime_type) = &file_ref.mime_type {
                         if self.supported_file_types().contains(mime_type) {
@@ -427,6 +447,7 @@ impl GeminiProvider {
                                 }
                                 system_text.push_str(&format!("Tool result [{}]: {}", call_id, output));
                             }
+                            ContentPart::Thinking { .. } => {}
                             ContentPart::FileReference(file_ref) => {
                                 if !system_text.is_empty() {
                                     system_text.push('\n');
@@ -444,7 +465,10 @@ impl GeminiProvider {
             None
         } else {
             Some(GeminiContent {
-                parts: vec![GeminiPart::Text { text: system_text }],
+                parts: vec![GeminiPart::Text {
+                    text: system_text,
+                    thought: false,
+                }],
             })
         }
     }
@@ -464,6 +488,17 @@ impl GeminiProvider {
                 max_output_tokens: params.and_then(|p| p.max_tokens.map(|t| t as u32)),
                 top_p: params.and_then(|p| p.top_p).map(|t| t as f64),
                 stop_sequences: params.and_then(|p| p.stop.clone()),
+                thinking_config: params.and_then(|p| p.thinking_budget).map(|budget| {
+                    GeminiThinkingConfig {
+                        thinking_budget: budget,
+                        include_thoughts: true,
+                    }
+                }),
+                response_mime_type: request
+                    .json_schema
+                    .as_ref()
+                    .map(|_| "application/json".to_string()),
+                response_schema: request.json_schema.as_ref().map(Self::response_schema),
             }
         });
 
@@ -474,6 +509,84 @@ impl GeminiProvider {
 
         Ok(payload)
     }
+
+    /// Convert a JSON Schema to the OpenAPI subset `responseSchema` takes, dropping the
+    /// keywords Gemini rejects
+    fn response_schema(schema: &Value) -> Value {
+        const KEYWORDS: &[&str] = &[
+            "type", "format", "title", "description", "nullable", "enum", "items", "minItems",
+            "maxItems", "properties", "required", "minProperties", "maxProperties", "minLength",
+            "maxLength", "pattern", "minimum", "maximum", "anyOf", "propertyOrdering", "default",
+        ];
+
+        let Some(object) = schema.as_object() else {
+            return schema.clone();
+        };
+        let mut converted = serde_json::Map::new();
+        for (key, value) in object {
+            match key.as_str() {
+                "properties" => {
+                    let properties = value
+                        .as_object()
+                        .map(|properties| {
+                            properties
+                                .iter()
+                                .map(|(name, schema)| (name.clone(), Self::response_schema(schema)))
+                                .collect::<serde_json::Map<_, _>>()
+                        })
+                        .unwrap_or_default();
+                    converted.insert(key.clone(), Value::Object(properties));
+                }
+                "items" => {
+                    converted.insert(key.clone(), Self::response_schema(value));
+                }
+                "anyOf" => {
+                    let schemas = value
+                        .as_array()
+                        .map(|schemas| schemas.iter().map(Self::response_schema).collect())
+                        .unwrap_or_default();
+                    converted.insert(key.clone(), Value::Array(schemas));
+                }
+                // `["string", "null"]` is a nullable string
+                "type" if value.is_array() => {
+                    let types: Vec<&str> = value
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|t| t.as_str())
+                        .collect();
+                    if let Some(t) = types.iter().find(|t| **t != "null") {
+                        converted.insert(key.clone(), json!(t));
+                    }
+                    if types.contains(&"null") {
+                        converted.insert("nullable".to_string(), json!(true));
+                    }
+                }
+                "const" => {
+                    converted.insert("enum".to_string(), json!([value]));
+                }
+                _ if KEYWORDS.contains(&key.as_str()) => {
+                    converted.insert(key.clone(), value.clone());
+                }
+                _ => {}
+            }
+        }
+        Value::Object(converted)
+    }
+
+    /// Text and reasoning of a candidate's parts
+    fn split_parts(parts: Vec<GeminiPart>) -> (String, String) {
+        let mut text = String::new();
+        let mut thinking = String::new();
+        for part in parts {
+            match part {
+                GeminiPart::Text { text: t, thought: true } => thinking.push_str(&t),
+                GeminiPart::Text { text: t, thought: false } => text.push_str(&t),
+                _ => {}
+            }
+        }
+        (text, thinking)
+    }
 }
 
 #[async_trait]
@@ -498,23 +611,19 @@ impl AIProvider for GeminiProvider {
             .await?;
 
         if !response.status().is_success() {
+            let status = response.status();
             let error_text = response.text().await?;
-            return Err(format!("Gemini API error: {}", error_text).into());
+            return Err(ProviderApiError::new(
+                status,
+                format!("Gemini API error: {}", error_text),
+            )
+            .into());
         }
 
         let gemini_response: GeminiResponse = response.json().await?;
 
         if let Some(candidate) = gemini_response.candidates.into_iter().next() {
-            let content = candidate
-                .content
-                .parts
-                .into_iter()
-                .filter_map(|part| match part {
-                    GeminiPart::Text { text } => Some(text),
-                    _ => None,
-                })
-                .collect::<Vec<_>>()
-                .join("");
+            let (content, thinking) = Self::split_parts(candidate.content.parts);
 
             Ok(ChatResponse {
                 content,
@@ -525,6 +634,7 @@ impl AIProvider for GeminiProvider {
                     total_tokens: u.total_token_count,
                 }),
                 tool_use: None, // Gemini provider doesn't support tool calling yet
+                thinking: Some(thinking).filter(|t| !t.is_empty()),
             })
         } else {
             Err("No candidates returned from Gemini API".into())
@@ -551,8 +661,13 @@ impl AIProvider for GeminiProvider {
             .await?;
 
         if !response.status().is_success() {
+            let status = response.status();
             let error_text = response.text().await?;
-            return Err(format!("Gemini API error: {}", error_text).into());
+            return Err(ProviderApiError::new(
+                status,
+                format!("Gemini API error: {}", error_text),
+            )
+            .into());
         }
 
         use std::sync::{Arc, Mutex};
@@ -581,25 +696,29 @@ impl AIProvider for GeminiProvider {
                         // Gemini returns JSON objects separated by newlines
                         match serde_json::from_str::<GeminiResponse>(&line) {
                             Ok(gemini_response) => {
+                                // Every response carries the usage so far
+                                let usage = gemini_response.usage_metadata.map(|u| Usage {
+                                    prompt_tokens: u.prompt_token_count,
+                                    completion_tokens: u.candidates_token_count,
+                                    total_tokens: u.total_token_count,
+                                });
                                 if let Some(candidate) =
                                     gemini_response.candidates.into_iter().next()
                                 {
-                                    let content = candidate
-                                        .content
-                                        .parts
-                                        .into_iter()
-                                        .filter_map(|part| match part {
-                                            GeminiPart::Text { text } => Some(text),
-                                            _ => None,
-                                        })
-                                        .collect::<Vec<_>>()
-                                        .join("");
-
-                                    if !content.is_empty() {
+                                    let (content, thinking) =
+                                        GeminiProvider::split_parts(candidate.content.parts);
+
+                                    if !content.is_empty()
+                                        || !thinking.is_empty()
+                                        || candidate.finish_reason.is_some()
+                                    {
                                         result = Some(Ok(StreamingChunk {
-                                            content: Some(content),
+                                            content: Some(content).filter(|c| !c.is_empty()),
                                             finish_reason: candidate.finish_reason,
                                             tool_use: None,
+                                            usage,
+                                            thinking: Some(thinking).filter(|t| !t.is_empty()),
+                                            thinking_signature: None,
                                         }));
                                         break;
                                     }
@@ -618,6 +737,9 @@ impl AIProvider for GeminiProvider {
                         content: None,
                         finish_reason: None,
                         tool_use: None,
+                        usage: None,
+                        thinking: None,
+                        thinking_signature: None,
                     }))
                 }
                 Err(e) => Err(Box::new(e) as Box<dyn std::error::Error + Send + Sync>),
@@ -90,6 +90,7 @@ impl GroqProvider {
                             ContentPart::ToolResult { call_id, output } => {
                                 format!("[Tool Result {}]: {}", call_id, output)
                             }
+                            ContentPart::Thinking { .. } => String::new(),
                         })
                         .collect();
 
@@ -240,6 +241,13 @@ impl AIProvider for GroqProvider {
         self.inner.forward_chat_request(request).await
     }
 
+    async fn forward_embeddings_request(
+        &self,
+        request: serde_json::Value,
+    ) -> Result<reqwest::Response, Box<dyn std::error::Error + Send + Sync>> {
+        self.inner.forward_embeddings_request(request).await
+    }
+
     async fn embeddings(
         &self,
         request: EmbeddingsRequest,
@@ -9,10 +9,12 @@ use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
 use crate::ai::core::provider_base::build_http_client;
+use crate::ai::core::structured_output;
 use crate::ai::core::providers::{
     AIProvider, ChatRequest, ChatResponse, ContentPart, EmbeddingData, EmbeddingsInput,
     EmbeddingsRequest, EmbeddingsResponse, EmbeddingsUsage, FileReference, MessageContent,
-    ProviderFileContent, ProxyConfig, StreamingChunk, StreamingResponse, Usage,
+    ProviderApiError, ProviderFileContent, ProxyConfig, StreamingChunk, StreamingResponse, Usage,
+    map_provider_error,
 };
 use crate::ai::file_helpers::{add_provider_mapping_to_file_ref, load_file_content};
 use crate::database::queries::files::{create_provider_file_mapping, get_provider_file_mapping};
@@ -74,6 +76,8 @@ struct HuggingFaceUsage {
 #[derive(Debug, Deserialize)]
 struct HuggingFaceStreamResponse {
     choices: Vec<HuggingFaceStreamChoice>,
+    // Sent with the last chunk when `stream_options.include_usage` is set
+    usage: Option<HuggingFaceUsage>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -231,6 +235,7 @@ impl HuggingFaceProvider {
                         text: format!("[Tool Result {}]: {}", call_id, output),
                     });
                 }
+                ContentPart::Thinking { .. } => {}
                 ContentPart::FileReference(file_ref) => {
                     if let Some(mime_type) = &file_ref.mime_type {
                         if self.is_supported_image_type(mime_type) {
@@ -384,6 +389,7 @@ impl HuggingFaceProvider {
                                 ContentPart::ToolResult { call_id, output } => {
                                     format!("[Tool Result {}]: {}", call_id, output)
                                 }
+                                ContentPart::Thinking { .. } => String::new(),
                             })
                             .collect();
 
@@ -486,6 +492,10 @@ impl HuggingFaceProvider {
             "stream": effective_stream
         });
 
+        if effective_stream {
+            payload["stream_options"] = json!({ "include_usage": true });
+        }
+
         // Add optional parameters with model-aware optimizations
         if let Some(params) = params {
             if let Some(temperature) = params.temperature {
@@ -515,6 +525,10 @@ impl HuggingFaceProvider {
             payload["max_tokens"] = json!(default_max_tokens);
         }
 
+        if let Some(schema) = &request.json_schema {
+            payload["response_format"] = structured_output::response_format(schema);
+        }
+
         // Apply model-specific optimizations
         self.apply_model_optimizations(&mut payload, &model_config);
 
@@ -561,8 +575,12 @@ impl AIProvider for HuggingFaceProvider {
             .await?;
 
         if !response.status().is_success() {
+            let status = response.status();
             let error_text = response.text().await?;
-            return Err(self.handle_huggingface_errors(&error_text));
+            return Err(map_provider_error(
+                ProviderApiError::new(status, error_text).into(),
+                |message| self.handle_huggingface_errors(message),
+            ));
         }
 
         let hf_response: HuggingFaceResponse = response.json().await?;
@@ -592,6 +610,7 @@ impl AIProvider for HuggingFaceProvider {
                     total_tokens: u.total_tokens,
                 }),
                 tool_use: None, // HuggingFace provider doesn't support tool calling yet
+                thinking: None,
             })
         } else {
             Err("No choices returned from Hugging Face API".into())
@@ -614,8 +633,12 @@ impl AIProvider for HuggingFaceProvider {
             .await?;
 
         if !response.status().is_success() {
+            let status = response.status();
             let error_text = response.text().await?;
-            return Err(self.handle_huggingface_errors(&error_text));
+            return Err(map_provider_error(
+                ProviderApiError::new(status, error_text).into(),
+                |message| self.handle_huggingface_errors(message),
+            ));
         }
 
         let buffer = Arc::new(Mutex::new(String::new()));
@@ -628,7 +651,7 @@ impl AIProvider for HuggingFaceProvider {
                     let mut buffer_guard = buffer.lock().unwrap();
                     buffer_guard.push_str(&chunk);
 
-                    let mut result = None;
+                    let mut result: Option<StreamingChunk> = None;
                     while let Some(line_end) = buffer_guard.find('\n') {
                         let line = buffer_guard[..line_end].trim().to_string();
                         buffer_guard.drain(..=line_end);
@@ -640,13 +663,32 @@ impl AIProvider for HuggingFaceProvider {
                         if let Some(data) = line.strip_prefix("data: ") {
                             match serde_json::from_str::<HuggingFaceStreamResponse>(data) {
                                 Ok(stream_response) => {
+                                    // Merge the events that arrived together
+                                    let chunk = result.get_or_insert(StreamingChunk {
+                                        content: None,
+                                        finish_reason: None,
+                                        tool_use: None,
+                                        usage: None,
+                                        thinking: None,
+                                        thinking_signature: None,
+                                    });
+                                    if let Some(u) = stream_response.usage {
+                                        chunk.usage = Some(Usage {
+                                            prompt_tokens: u.prompt_tokens,
+                                            completion_tokens: u.completion_tokens,
+                                            total_tokens: u.total_tokens,
+                                        });
+                                    }
                                     if let Some(choice) = stream_response.choices.into_iter().next() {
-                                        result = Some(Ok(StreamingChunk {
-                                            content: choice.delta.content,
-                                            finish_reason: choice.finish_reason,
-                                            tool_use: None,
-                                        }));
-                                        break;
+                                        if let Some(content) = choice.delta.content {
+                                            chunk
+                                                .content
+                                                .get_or_insert_with(String::new)
+                                                .push_str(&content);
+                                        }
+                                        if choice.finish_reason.is_some() {
+                                            chunk.finish_reason = choice.finish_reason;
+                                        }
                                     }
                                 }
                                 Err(e) => {
@@ -659,10 +701,13 @@ impl AIProvider for HuggingFaceProvider {
                         }
                     }
 
-                    result.unwrap_or(Ok(StreamingChunk {
+                    Ok(result.unwrap_or(StreamingChunk {
                         content: None,
                         finish_reason: None,
                         tool_use: None,
+                        usage: None,
+                        thinking: None,
+                        thinking_signature: None,
                     }))
                 }
                 Err(e) => Err(Box::new(e) as Box<dyn std::error::Error + Send + Sync>),
@@ -3,13 +3,20 @@ use futures_util::StreamExt;
 use reqwest::Client;
 use serde::Deserialize;
 use serde_json::json;
+use std::collections::BTreeMap;
 use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
 use crate::ai::core::providers::{
     AIProvider, ChatRequest, ChatResponse, ContentPart, EmbeddingsRequest, EmbeddingsResponse,
-    FileReference, MessageContent, StreamingChunk, StreamingResponse, Usage,
+    FileReference, ImageGenerationRequest, ImageGenerationResponse, MessageContent,
+    ProviderApiError, RerankRequest, RerankResponse, StreamingChunk, StreamingResponse,
+    ToolDefinition, ToolUse, Usage,
 };
+use crate::ai::core::image_generation;
+use crate::ai::core::structured_output;
+use crate::ai::core::thinking::{split_think_tags, ThinkTagParser};
+use crate::ai::core::tool_prompt::{self, format_tool_calls, parse_reply};
 use crate::ai::file_helpers::{get_file_content_for_local_provider, LocalProviderFileContent};
 use crate::database::models::model::ModelCapabilities;
 use crate::database::queries::models::get_model_by_id;
@@ -34,7 +41,24 @@ struct LocalChoice {
 
 #[derive(Debug, Deserialize)]
 struct LocalMessage {
-    content: String,
+    // Left out when the model only calls tools
+    content: Option<String>,
+    // Set when the server parses the reasoning out of the answer
+    reasoning_content: Option<String>,
+    tool_calls: Option<Vec<LocalToolCall>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LocalToolCall {
+    #[serde(default)]
+    id: String,
+    function: LocalFunction,
+}
+
+#[derive(Debug, Deserialize)]
+struct LocalFunction {
+    name: String,
+    arguments: String, // JSON string
 }
 
 #[derive(Debug, Deserialize)]
@@ -47,6 +71,8 @@ struct LocalUsage {
 #[derive(Debug, Deserialize)]
 struct LocalStreamResponse {
     choices: Vec<LocalStreamChoice>,
+    // Sent with the last chunk when `stream_options.include_usage` is set
+    usage: Option<LocalUsage>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -58,6 +84,73 @@ struct LocalStreamChoice {
 #[derive(Debug, Deserialize)]
 struct LocalStreamDelta {
     content: Option<String>,
+    reasoning_content: Option<String>,
+    tool_calls: Option<Vec<LocalToolCallDelta>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LocalToolCallDelta {
+    index: u32,
+    id: Option<String>,
+    function: Option<LocalFunctionDelta>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LocalFunctionDelta {
+    name: Option<String>,
+    arguments: Option<String>,
+}
+
+/// `/props` of llama.cpp servers
+#[derive(Debug, Deserialize)]
+struct LocalServerProps {
+    chat_template: Option<String>,
+    chat_template_caps: Option<LocalChatTemplateCaps>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LocalChatTemplateCaps {
+    supports_tool_calls: Option<bool>,
+}
+
+impl LocalServerProps {
+    fn supports_tool_calls(&self) -> bool {
+        match self
+            .chat_template_caps
+            .as_ref()
+            .and_then(|caps| caps.supports_tool_calls)
+        {
+            Some(supported) => supported,
+            // Older servers only report the template itself
+            None => self
+                .chat_template
+                .as_deref()
+                .map_or(true, |template| template.contains("tool")),
+        }
+    }
+}
+
+/// How tools are offered to the local server
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ToolMode {
+    /// Sent as `tools` and rendered by the model's chat template
+    Native,
+    /// Described in the system prompt, with the reply constrained by a JSON schema
+    Prompt,
+}
+
+/// Build a tool use from an OpenAI-style call, whose arguments are a JSON string
+fn to_tool_use(id: String, name: String, arguments: &str) -> ToolUse {
+    ToolUse {
+        // Not every server sends call ids
+        id: if id.is_empty() {
+            format!("call_{}", Uuid::new_v4().simple())
+        } else {
+            id
+        },
+        name,
+        input: serde_json::from_str(arguments).unwrap_or(json!({})),
+    }
 }
 
 impl LocalProvider {
@@ -79,11 +172,19 @@ impl LocalProvider {
         request: &ChatRequest,
         stream: bool,
         capabilities: Option<&ModelCapabilities>,
+        tool_mode: Option<ToolMode>,
     ) -> Result<serde_json::Value, Box<dyn std::error::Error + Send + Sync>> {
         // Process messages using OpenAI-compatible format
         let mut processed_messages = Vec::new();
 
         for message in &request.messages {
+            if let (Some(mode), MessageContent::Multimodal(parts)) = (tool_mode, &message.content) {
+                if let Some(tool_messages) = Self::tool_messages(&message.role, parts, mode) {
+                    processed_messages.extend(tool_messages);
+                    continue;
+                }
+            }
+
             let openai_message = match &message.content {
                 MessageContent::Text(text) => {
                     // Simple text message
@@ -107,6 +208,19 @@ impl LocalProvider {
             processed_messages.push(openai_message);
         }
 
+        let tools = request.tools.as_deref().unwrap_or_default();
+        if tool_mode == Some(ToolMode::Prompt) {
+            // Chat templates often allow a single system message, so extend the existing one
+            let prompt = tool_prompt::tool_prompt(tools, request.json_schema.as_ref());
+            match processed_messages.first_mut() {
+                Some(first) if first["role"] == "system" && first["content"].is_string() => {
+                    let instructions = first["content"].as_str().unwrap_or_default().to_string();
+                    first["content"] = json!(format!("{}\n\n{}", instructions, prompt));
+                }
+                _ => processed_messages.insert(0, json!({ "role": "system", "content": prompt })),
+            }
+        }
+
         let params = request.parameters.as_ref();
         let mut payload = json!({
             "model": "default".to_string(), // Use "default" for local provider
@@ -119,6 +233,10 @@ impl LocalProvider {
             "stream": stream
         });
 
+        if stream {
+            payload["stream_options"] = json!({ "include_usage": true });
+        }
+
         // Add optional parameters if present
         if let Some(params) = params {
             if let Some(seed) = params.seed {
@@ -129,9 +247,133 @@ impl LocalProvider {
             }
         }
 
+        // llama.cpp and mistral.rs turn the schemas into a grammar constraining the reply
+        match tool_mode {
+            Some(ToolMode::Native) | None => {
+                let openai_tools: Vec<serde_json::Value> = tools
+                    .iter()
+                    .map(|tool| {
+                        json!({
+                            "type": "function",
+                            "function": {
+                                "name": tool.name,
+                                "description": tool.description,
+                                "parameters": tool.input_schema
+                            }
+                        })
+                    })
+                    .collect();
+                if !openai_tools.is_empty() {
+                    payload["tools"] = json!(openai_tools);
+                }
+                if let Some(schema) = &request.json_schema {
+                    payload["response_format"] = structured_output::response_format(schema);
+                }
+            }
+            Some(ToolMode::Prompt) => {
+                let schema = tool_prompt::reply_schema(tools, request.json_schema.as_ref());
+                payload["response_format"] = structured_output::response_format(&schema);
+            }
+        }
+
         Ok(payload)
     }
 
+    /// Pick how tools are offered, if the request has any. llama.cpp reports whether the
+    /// model's chat template supports tool calls; mistral.rs parses them itself.
+    async fn tool_mode(&self, tools: Option<&[ToolDefinition]>) -> Option<ToolMode> {
+        if tools.map_or(true, |tools| tools.is_empty()) {
+            return None;
+        }
+
+        let props = match self.client.get(format!("{}/props", self.base_url)).send().await {
+            Ok(response) if response.status().is_success() => {
+                response.json::<LocalServerProps>().await.ok()
+            }
+            _ => None,
+        };
+
+        if props.map_or(true, |props| props.supports_tool_calls()) {
+            Some(ToolMode::Native)
+        } else {
+            Some(ToolMode::Prompt)
+        }
+    }
+
+    /// Convert a message made of tool uses or tool results. Natively they become
+    /// `tool_calls` and `tool` messages; in the prompt format they are written the way the
+    /// model replies, with results sent as user messages.
+    fn tool_messages(
+        role: &str,
+        parts: &[ContentPart],
+        mode: ToolMode,
+    ) -> Option<Vec<serde_json::Value>> {
+        let mut tool_uses = Vec::new();
+        let mut tool_results = Vec::new();
+        for part in parts {
+            match part {
+                ContentPart::ToolUse { id, name, input } => tool_uses.push((id, name, input)),
+                ContentPart::ToolResult { call_id, output } => tool_results.push((call_id, output)),
+                _ => return None,
+            }
+        }
+        if tool_uses.is_empty() && tool_results.is_empty() {
+            return None;
+        }
+
+        let mut messages = Vec::new();
+        match mode {
+            ToolMode::Native => {
+                if !tool_uses.is_empty() {
+                    let tool_calls: Vec<serde_json::Value> = tool_uses
+                        .iter()
+                        .map(|(id, name, input)| {
+                            json!({
+                                "id": id,
+                                "type": "function",
+                                "function": { "name": name, "arguments": input.to_string() }
+                            })
+                        })
+                        .collect();
+                    messages.push(json!({
+                        "role": role,
+                        "content": null,
+                        "tool_calls": tool_calls
+                    }));
+                }
+                for (call_id, output) in tool_results {
+                    messages.push(json!({
+                        "role": "tool",
+                        "tool_call_id": call_id,
+                        "content": output
+                    }));
+                }
+            }
+            ToolMode::Prompt => {
+                if !tool_uses.is_empty() {
+                    let calls = tool_uses
+                        .iter()
+                        .map(|(_, name, input)| (name.as_str(), *input));
+                    messages.push(json!({
+                        "role": role,
+                        "content": format_tool_calls(calls)
+                    }));
+                }
+                if !tool_results.is_empty() {
+                    let results: Vec<String> = tool_results
+                        .iter()
+                        .map(|(_, output)| format!("Tool result:\n{}", output))
+                        .collect();
+                    messages.push(json!({
+                        "role": "user",
+                        "content": results.join("\n\n")
+                    }));
+                }
+            }
+        }
+        Some(messages)
+    }
+
 
     /// Fetch model capabilities from database using model_id
     async fn get_model_capabilities(&self, model_id: Uuid) -> Option<ModelCapabilities> {
@@ -182,6 +424,7 @@ impl LocalProvider {
                         "text": format!("[Tool Result {}]: {}", call_id, output)
                     }));
                 }
+                ContentPart::Thinking { .. } => {}
                 ContentPart::FileReference(file_ref) => {
                     match self
                         .process_file_with_enhanced_support(file_ref, supports_vision)
@@ -292,130 +535,16 @@ impl AIProvider for LocalProvider {
         &self,
         request: ChatRequest,
     ) -> Result<ChatResponse, Box<dyn std::error::Error + Send + Sync>> {
-        let url = format!("{}/v1/chat/completions", self.base_url);
-
-        // Fetch model capabilities from database
-        let capabilities = self.get_model_capabilities(request.model_id).await;
-
-        let payload = self
-            .build_request_with_capabilities(&request, false, capabilities.as_ref())
-            .await?;
-
-        let response = self
-            .client
-            .post(&url)
-            .header("Content-Type", "application/json")
-            .json(&payload)
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            let error_text = response.text().await?;
-            return Err(format!("local API error: {}", error_text).into());
-        }
-
-        let api_response: LocalResponse = response.json().await?;
-
-        if let Some(choice) = api_response.choices.into_iter().next() {
-            Ok(ChatResponse {
-                content: choice.message.content,
-                finish_reason: choice.finish_reason,
-                usage: api_response.usage.map(|u| Usage {
-                    prompt_tokens: u.prompt_tokens,
-                    completion_tokens: u.completion_tokens,
-                    total_tokens: u.total_tokens,
-                }),
-                tool_use: None, // Local provider doesn't support tool calling yet
-            })
-        } else {
-            Err("No choices returned from local API".into())
-        }
+        // Model capabilities are fetched from the database
+        self.chat_with_capabilities(request, None).await
     }
 
     async fn chat_stream(
         &self,
         request: ChatRequest,
     ) -> Result<StreamingResponse, Box<dyn std::error::Error + Send + Sync>> {
-        let url = format!("{}/v1/chat/completions", self.base_url);
-
-        // Fetch model capabilities from database
-        let capabilities = self.get_model_capabilities(request.model_id).await;
-
-        let payload = self
-            .build_request_with_capabilities(&request, true, capabilities.as_ref())
-            .await?;
-
-        //print payload as json for debugging
-        // println!("Payload for Local chat stream: {}", serde_json::to_string_pretty(&payload)?);
-
-        let response = self
-            .client
-            .post(&url)
-            .header("Content-Type", "application/json")
-            .json(&payload)
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            let error_text = response.text().await?;
-            return Err(format!("local API error: {}", error_text).into());
-        }
-
-        // Create a buffer to accumulate partial SSE chunks
-        let buffer = Arc::new(Mutex::new(String::new()));
-
-        let stream = response.bytes_stream().map(move |result| {
-            let buffer = buffer.clone();
-            match result {
-                Ok(bytes) => {
-                    let chunk = String::from_utf8_lossy(&bytes);
-                    let mut buffer_guard = buffer.lock().unwrap();
-                    buffer_guard.push_str(&chunk);
-
-                    // Process complete lines from buffer
-                    let mut result = None;
-                    while let Some(line_end) = buffer_guard.find('\n') {
-                        let line = buffer_guard[..line_end].trim().to_string();
-                        buffer_guard.drain(..=line_end);
-
-                        if line.is_empty() || line == "data: [DONE]" {
-                            continue;
-                        }
-
-                        if let Some(data) = line.strip_prefix("data: ") {
-                            match serde_json::from_str::<LocalStreamResponse>(data) {
-                                Ok(stream_response) => {
-                                    if let Some(choice) = stream_response.choices.into_iter().next()
-                                    {
-                                        result = Some(Ok(StreamingChunk {
-                                            content: choice.delta.content,
-                                            finish_reason: choice.finish_reason,
-                                            tool_use: None,
-                                        }));
-                                        break;
-                                    }
-                                }
-                                Err(e) => {
-                                    eprintln!(
-                                        "Failed to parse Local streaming response: {} for data: {}",
-                                        e, data
-                                    );
-                                }
-                            }
-                        }
-                    }
-
-                    result.unwrap_or(Ok(StreamingChunk {
-                        content: None,
-                        finish_reason: None,
-                        tool_use: None,
-                    }))
-                }
-                Err(e) => Err(Box::new(e) as Box<dyn std::error::Error + Send + Sync>),
-            }
-        });
-
-        Ok(Box::pin(stream))
+        // Model capabilities are fetched from the database
+        self.chat_stream_with_capabilities(request, None).await
     }
 
     fn provider_name(&self) -> &'static str {
@@ -497,6 +626,70 @@ impl AIProvider for LocalProvider {
         let embeddings_response: EmbeddingsResponse = response.json().await?;
         Ok(embeddings_response)
     }
+
+    async fn rerank(
+        &self,
+        request: RerankRequest,
+    ) -> Result<RerankResponse, Box<dyn std::error::Error + Send + Sync>> {
+        // llama.cpp server exposes a Jina/Cohere-style rerank endpoint when started with --reranking
+        let url = format!("{}/v1/rerank", self.base_url);
+
+        let mut json_body = serde_json::json!({
+            "model": request.model_name,
+            "query": request.query,
+            "documents": request.documents
+        });
+
+        if let Some(top_n) = request.top_n {
+            json_body["top_n"] = serde_json::Value::Number(top_n.into());
+        }
+
+        let response = self
+            .client
+            .post(&url)
+            .header("Content-Type", "application/json")
+            .json(&json_body)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await?;
+            return Err(format!("HTTP {}: {}", status, error_text).into());
+        }
+
+        let rerank_response: RerankResponse = response.json().await?;
+        Ok(rerank_response)
+    }
+
+    async fn generate_images(
+        &self,
+        request: ImageGenerationRequest,
+    ) -> Result<ImageGenerationResponse, Box<dyn std::error::Error + Send + Sync>> {
+        // mistral.rs serves diffusion models on the OpenAI images endpoint; its URLs point to
+        // files on the server, so the images are asked for inline
+        let url = format!("{}/v1/images/generations", self.base_url);
+
+        let response = self
+            .client
+            .post(&url)
+            .header("Content-Type", "application/json")
+            .json(&image_generation::request_body(&request, Some("b64_json")))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await?;
+            return Err(ProviderApiError::new(
+                status,
+                format!("Local API error: {}", error_text),
+            )
+            .into());
+        }
+
+        image_generation::parse_response(&self.client, response).await
+    }
 }
 
 // Public method to create LocalProvider with file handling capabilities
@@ -516,8 +709,9 @@ impl LocalProvider {
             self.get_model_capabilities(request.model_id).await
         };
 
+        let tool_mode = self.tool_mode(request.tools.as_deref()).await;
         let payload = self
-            .build_request_with_capabilities(&request, false, final_capabilities.as_ref())
+            .build_request_with_capabilities(&request, false, final_capabilities.as_ref(), tool_mode)
             .await?;
 
         let response = self
@@ -529,22 +723,43 @@ impl LocalProvider {
             .await?;
 
         if !response.status().is_success() {
+            let status = response.status();
             let error_text = response.text().await?;
-            return Err(format!("Local API error: {}", error_text).into());
+            return Err(ProviderApiError::new(
+                status,
+                format!("Local API error: {}", error_text),
+            )
+            .into());
         }
 
         let api_response: LocalResponse = response.json().await?;
 
         if let Some(choice) = api_response.choices.into_iter().next() {
+            let (inline_thinking, mut content) =
+                split_think_tags(&choice.message.content.unwrap_or_default());
+            let mut tool_uses: Vec<ToolUse> = choice
+                .message
+                .tool_calls
+                .unwrap_or_default()
+                .into_iter()
+                .map(|call| to_tool_use(call.id, call.function.name, &call.function.arguments))
+                .collect();
+            if tool_mode == Some(ToolMode::Prompt) {
+                let reply = parse_reply(&content, request.json_schema.is_some());
+                content = reply.text;
+                tool_uses.extend(reply.tool_uses);
+            }
+
             Ok(ChatResponse {
-                content: choice.message.content,
+                content,
                 finish_reason: choice.finish_reason,
                 usage: api_response.usage.map(|u| Usage {
                     prompt_tokens: u.prompt_tokens,
                     completion_tokens: u.completion_tokens,
                     total_tokens: u.total_tokens,
                 }),
-                tool_use: None, // Local provider doesn't support tool calling yet
+                tool_use: tool_uses.into_iter().next(),
+                thinking: choice.message.reasoning_content.or(inline_thinking),
             })
         } else {
             Err("No choices returned from local API".into())
@@ -566,8 +781,9 @@ impl LocalProvider {
             self.get_model_capabilities(request.model_id).await
         };
 
+        let tool_mode = self.tool_mode(request.tools.as_deref()).await;
         let payload = self
-            .build_request_with_capabilities(&request, true, final_capabilities.as_ref())
+            .build_request_with_capabilities(&request, true, final_capabilities.as_ref(), tool_mode)
             .await?;
 
         let response = self
@@ -579,63 +795,160 @@ impl LocalProvider {
             .await?;
 
         if !response.status().is_success() {
+            let status = response.status();
             let error_text = response.text().await?;
-            return Err(format!("local API error: {}", error_text).into());
+            return Err(ProviderApiError::new(
+                status,
+                format!("local API error: {}", error_text),
+            )
+            .into());
         }
 
         // Create a buffer to accumulate partial SSE chunks
         let buffer = Arc::new(Mutex::new(String::new()));
+        let think_tags = Arc::new(Mutex::new(ThinkTagParser::new()));
+        // Streamed tool calls by index: (id, name, arguments)
+        let current_tool_calls = Arc::new(Mutex::new(BTreeMap::<u32, (String, String, String)>::new()));
+        // A constrained reply is only parsed once complete
+        let prompt_reply = Arc::new(Mutex::new(String::new()));
+        let json_answer = request.json_schema.is_some();
+
+        let stream = response
+            .bytes_stream()
+            .map(move |result| {
+                let buffer = buffer.clone();
+                match result {
+                    Ok(bytes) => {
+                        let chunk = String::from_utf8_lossy(&bytes);
+                        let mut buffer_guard = buffer.lock().unwrap();
+                        buffer_guard.push_str(&chunk);
+
+                        // Process complete lines from buffer
+                        let mut result: Option<StreamingChunk> = None;
+                        let mut tool_uses = Vec::new();
+                        while let Some(line_end) = buffer_guard.find('\n') {
+                            let line = buffer_guard[..line_end].trim().to_string();
+                            buffer_guard.drain(..=line_end);
+
+                            if line.is_empty() || line == "data: [DONE]" {
+                                continue;
+                            }
 
-        let stream = response.bytes_stream().map(move |result| {
-            let buffer = buffer.clone();
-            match result {
-                Ok(bytes) => {
-                    let chunk = String::from_utf8_lossy(&bytes);
-                    let mut buffer_guard = buffer.lock().unwrap();
-                    buffer_guard.push_str(&chunk);
-
-                    // Process complete lines from buffer
-                    let mut result = None;
-                    while let Some(line_end) = buffer_guard.find('\n') {
-                        let line = buffer_guard[..line_end].trim().to_string();
-                        buffer_guard.drain(..=line_end);
-
-                        if line.is_empty() || line == "data: [DONE]" {
-                            continue;
-                        }
-
-                        if let Some(data) = line.strip_prefix("data: ") {
-                            match serde_json::from_str::<LocalStreamResponse>(data) {
-                                Ok(stream_response) => {
-                                    if let Some(choice) = stream_response.choices.into_iter().next()
-                                    {
-                                        result = Some(Ok(StreamingChunk {
-                                            content: choice.delta.content,
-                                            finish_reason: choice.finish_reason,
+                            if let Some(data) = line.strip_prefix("data: ") {
+                                match serde_json::from_str::<LocalStreamResponse>(data) {
+                                    Ok(stream_response) => {
+                                        // Merge the events that arrived together
+                                        let chunk = result.get_or_insert(StreamingChunk {
+                                            content: None,
+                                            finish_reason: None,
                                             tool_use: None,
-                                        }));
-                                        break;
+                                            usage: None,
+                                            thinking: None,
+                                            thinking_signature: None,
+                                        });
+                                        if let Some(u) = stream_response.usage {
+                                            chunk.usage = Some(Usage {
+                                                prompt_tokens: u.prompt_tokens,
+                                                completion_tokens: u.completion_tokens,
+                                                total_tokens: u.total_tokens,
+                                            });
+                                        }
+                                        if let Some(choice) = stream_response.choices.into_iter().next()
+                                        {
+                                            let mut tool_guard = current_tool_calls.lock().unwrap();
+
+                                            // Parallel calls stream their arguments under their own index
+                                            for delta in choice.delta.tool_calls.iter().flatten() {
+                                                let (id, name, args) =
+                                                    tool_guard.entry(delta.index).or_default();
+                                                if let Some(delta_id) = &delta.id {
+                                                    id.push_str(delta_id);
+                                                }
+                                                if let Some(func) = &delta.function {
+                                                    if let Some(delta_name) = &func.name {
+                                                        name.push_str(delta_name);
+                                                    }
+                                                    if let Some(delta_args) = &func.arguments {
+                                                        args.push_str(delta_args);
+                                                    }
+                                                }
+                                            }
+
+                                            if let Some(reasoning) = choice.delta.reasoning_content {
+                                                chunk
+                                                    .thinking
+                                                    .get_or_insert_with(String::new)
+                                                    .push_str(&reasoning);
+                                            }
+                                            if let Some(content) = choice.delta.content {
+                                                if tool_mode == Some(ToolMode::Prompt) {
+                                                    prompt_reply.lock().unwrap().push_str(&content);
+                                                } else {
+                                                    think_tags.lock().unwrap().push(&content).append_to(chunk);
+                                                }
+                                            }
+                                            if choice.finish_reason.is_some() {
+                                                think_tags.lock().unwrap().finish().append_to(chunk);
+                                                for (_, (id, name, args)) in std::mem::take(&mut *tool_guard) {
+                                                    tool_uses.push(to_tool_use(id, name, &args));
+                                                }
+                                                if tool_mode == Some(ToolMode::Prompt) {
+                                                    let reply = parse_reply(
+                                                        &std::mem::take(&mut *prompt_reply.lock().unwrap()),
+                                                        json_answer,
+                                                    );
+                                                    if !reply.text.is_empty() {
+                                                        chunk
+                                                            .content
+                                                            .get_or_insert_with(String::new)
+                                                            .push_str(&reply.text);
+                                                    }
+                                                    tool_uses.extend(reply.tool_uses);
+                                                }
+                                                chunk.finish_reason = choice.finish_reason;
+                                            }
+                                        }
+                                    }
+                                    Err(e) => {
+                                        eprintln!(
+                                            "Failed to parse Local streaming response: {} for data: {}",
+                                            e, data
+                                        );
                                     }
-                                }
-                                Err(e) => {
-                                    eprintln!(
-                                        "Failed to parse Local streaming response: {} for data: {}",
-                                        e, data
-                                    );
                                 }
                             }
                         }
-                    }
 
-                    result.unwrap_or(Ok(StreamingChunk {
-                        content: None,
-                        finish_reason: None,
-                        tool_use: None,
-                    }))
+                        let mut chunk = result.unwrap_or(StreamingChunk {
+                            content: None,
+                            finish_reason: None,
+                            tool_use: None,
+                            usage: None,
+                            thinking: None,
+                            thinking_signature: None,
+                        });
+
+                        // One chunk per tool use; the last one carries the finish reason
+                        let mut chunks = Vec::new();
+                        let last_tool_use = tool_uses.pop();
+                        for tool_use in tool_uses {
+                            chunks.push(Ok(StreamingChunk {
+                                content: None,
+                                finish_reason: None,
+                                tool_use: Some(tool_use),
+                                usage: None,
+                                thinking: None,
+                                thinking_signature: None,
+                            }));
+                        }
+                        chunk.tool_use = last_tool_use;
+                        chunks.push(Ok(chunk));
+                        chunks
+                    }
+                    Err(e) => vec![Err(Box::new(e) as Box<dyn std::error::Error + Send + Sync>)],
                 }
-                Err(e) => Err(Box::new(e) as Box<dyn std::error::Error + Send + Sync>),
-            }
-        });
+            })
+            .flat_map(futures_util::stream::iter);
 
         Ok(Box::pin(stream))
     }
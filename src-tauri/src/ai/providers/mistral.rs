@@ -8,10 +8,11 @@ use serde_json::{json, Value};
 use uuid::Uuid;
 
 use crate::ai::core::provider_base::build_http_client;
+use crate::ai::core::structured_output;
 use crate::ai::core::providers::{
     AIProvider, ChatRequest, ChatResponse, ContentPart, EmbeddingsRequest, EmbeddingsResponse,
-    FileReference, MessageContent, ProviderFileContent, ProxyConfig, StreamingChunk,
-    StreamingResponse, Usage,
+    FileReference, MessageContent, ProviderApiError, ProviderFileContent, ProxyConfig,
+    StreamingChunk, StreamingResponse, Usage,
 };
 use crate::ai::file_helpers::{add_provider_mapping_to_file_ref, load_file_content};
 use crate::database::queries::files::{create_provider_file_mapping, get_provider_file_mapping};
@@ -73,6 +74,8 @@ struct MistralUsage {
 #[derive(Debug, Deserialize)]
 struct MistralStreamResponse {
     choices: Vec<MistralStreamChoice>,
+    // Sent with the last chunk
+    usage: Option<MistralUsage>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -180,6 +183,7 @@ impl MistralProvider {
                         text: format!("[Tool Result {}]: {}", call_id, output),
                     });
                 }
+                ContentPart::Thinking { .. } => {}
                 ContentPart::FileReference(file_ref) => {
                     if let Some(mime_type) = &file_ref.mime_type {
                         if self.is_supported_image_type(mime_type) {
@@ -337,6 +341,7 @@ impl MistralProvider {
                                 ContentPart::ToolResult { call_id, output } => {
                                     format!("[Tool Result {}]: {}", call_id, output)
                                 }
+                                ContentPart::Thinking { .. } => String::new(),
                             })
                             .collect();
 
@@ -441,6 +446,10 @@ impl MistralProvider {
             payload["max_tokens"] = json!(default_max_tokens);
         }
 
+        if let Some(schema) = &request.json_schema {
+            payload["response_format"] = structured_output::response_format(schema);
+        }
+
         Ok(payload)
     }
 }
@@ -466,8 +475,13 @@ impl AIProvider for MistralProvider {
             .await?;
 
         if !response.status().is_success() {
+            let status = response.status();
             let error_text = response.text().await?;
-            return Err(format!("Mistral API error: {}", error_text).into());
+            return Err(ProviderApiError::new(
+                status,
+                format!("Mistral API error: {}", error_text),
+            )
+            .into());
         }
 
         let mistral_response: MistralResponse = response.json().await?;
@@ -497,6 +511,7 @@ impl AIProvider for MistralProvider {
                     total_tokens: u.total_tokens,
                 }),
                 tool_use: None, // Mistral provider doesn't support tool calling yet
+                thinking: None,
             })
         } else {
             Err("No choices returned from Mistral API".into())
@@ -522,8 +537,13 @@ impl AIProvider for MistralProvider {
             .await?;
 
         if !response.status().is_success() {
+            let status = response.status();
             let error_text = response.text().await?;
-            return Err(format!("Mistral API error: {}", error_text).into());
+            return Err(ProviderApiError::new(
+                status,
+                format!("Mistral API error: {}", error_text),
+            )
+            .into());
         }
 
         use std::sync::{Arc, Mutex};
@@ -538,7 +558,7 @@ impl AIProvider for MistralProvider {
                     let mut buffer_guard = buffer.lock().unwrap();
                     buffer_guard.push_str(&chunk);
 
-                    let mut result = None;
+                    let mut result: Option<StreamingChunk> = None;
                     while let Some(line_end) = buffer_guard.find('\n') {
                         let line = buffer_guard[..line_end].trim().to_string();
                         buffer_guard.drain(..=line_end);
@@ -550,13 +570,32 @@ impl AIProvider for MistralProvider {
                         if let Some(data) = line.strip_prefix("data: ") {
                             match serde_json::from_str::<MistralStreamResponse>(data) {
                                 Ok(stream_response) => {
+                                    // Merge the events that arrived together
+                                    let chunk = result.get_or_insert(StreamingChunk {
+                                        content: None,
+                                        finish_reason: None,
+                                        tool_use: None,
+                                        usage: None,
+                                        thinking: None,
+                                        thinking_signature: None,
+                                    });
+                                    if let Some(u) = stream_response.usage {
+                                        chunk.usage = Some(Usage {
+                                            prompt_tokens: u.prompt_tokens,
+                                            completion_tokens: u.completion_tokens,
+                                            total_tokens: u.total_tokens,
+                                        });
+                                    }
                                     if let Some(choice) = stream_response.choices.into_iter().next() {
-                                        result = Some(Ok(StreamingChunk {
-                                            content: choice.delta.content,
-                                            finish_reason: choice.finish_reason,
-                                            tool_use: None,
-                                        }));
-                                        break;
+                                        if let Some(content) = choice.delta.content {
+                                            chunk
+                                                .content
+                                                .get_or_insert_with(String::new)
+                                                .push_str(&content);
+                                        }
+                                        if choice.finish_reason.is_some() {
+                                            chunk.finish_reason = choice.finish_reason;
+                                        }
                                     }
                                 }
                                 Err(e) => {
@@ -569,10 +608,13 @@ impl AIProvider for MistralProvider {
                         }
                     }
 
-                    result.unwrap_or(Ok(StreamingChunk {
+                    Ok(result.unwrap_or(StreamingChunk {
                         content: None,
                         finish_reason: None,
                         tool_use: None,
+                        usage: None,
+                        thinking: None,
+                        thinking_signature: None,
                     }))
                 }
                 Err(e) => Err(Box::new(e) as Box<dyn std::error::Error + Send + Sync>),
@@ -687,6 +729,24 @@ impl AIProvider for MistralProvider {
         Ok(response)
     }
 
+    async fn forward_embeddings_request(
+        &self,
+        request: serde_json::Value,
+    ) -> Result<reqwest::Response, Box<dyn std::error::Error + Send + Sync>> {
+        let url = format!("{}/embeddings", self.base_url);
+
+        let response = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .header("Content-Type", "application/json")
+            .json(&request)
+            .send()
+            .await?;
+
+        Ok(response)
+    }
+
     async fn embeddings(
         &self,
         request: EmbeddingsRequest,
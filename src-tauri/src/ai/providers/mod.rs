@@ -1,7 +1,7 @@
 //! External AI provider implementations
 //!
 //! This module contains implementations for various external AI providers
-//! including OpenAI, Anthropic, Groq, Gemini, Mistral, Hugging Face, and custom providers.
+//! including OpenAI, Anthropic, Groq, Gemini, Mistral, Hugging Face, Ollama, and custom providers.
 
 pub mod anthropic;
 pub mod custom;
@@ -11,6 +11,7 @@ pub mod groq;
 pub mod huggingface;
 pub mod local;
 pub mod mistral;
+pub mod ollama;
 pub mod openai;
 pub mod openai_compatible;
 pub mod openai_types;
@@ -23,6 +24,7 @@ pub use groq::*;
 pub use huggingface::*;
 pub use local::*;
 pub use mistral::*;
+pub use ollama::*;
 pub use openai::*;
 pub use openai_compatible::*;
 pub use openai_types::*;
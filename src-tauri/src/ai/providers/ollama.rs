@@ -0,0 +1,949 @@
+use async_trait::async_trait;
+use base64::Engine;
+use futures_util::StreamExt;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::ai::core::provider_base::build_http_client;
+use crate::ai::core::providers::{
+    AIProvider, ChatMessage, ChatRequest, ChatResponse, ContentPart, EmbeddingData,
+    EmbeddingsInput, EmbeddingsRequest, EmbeddingsResponse, EmbeddingsUsage, FileReference,
+    MessageContent, ProviderApiError, ProviderFileContent, ProviderModelInfo, ProxyConfig,
+    StreamingChunk, StreamingResponse, ToolUse, Usage,
+};
+use crate::ai::core::thinking::{split_think_tags, ThinkTagParser};
+use crate::ai::file_helpers::load_file_content;
+use crate::database::models::ModelCapabilities;
+
+const DEFAULT_OLLAMA_URL: &str = "http://localhost:11434";
+
+/// Provider for Ollama's native API (`/api/chat`, `/api/embed`, `/api/tags`)
+#[derive(Debug, Clone)]
+pub struct OllamaProvider {
+    client: Client,
+    api_key: String,
+    base_url: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct OllamaMessage {
+    role: String,
+    #[serde(default)]
+    content: String,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    images: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    tool_calls: Vec<OllamaToolCall>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_name: Option<String>,
+    // Reasoning of thinking models when `think` is set; never sent back
+    #[serde(default, skip_serializing)]
+    thinking: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct OllamaToolCall {
+    function: OllamaFunctionCall,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct OllamaFunctionCall {
+    name: String,
+    #[serde(default)]
+    arguments: Value,
+}
+
+/// A `/api/chat` response; streaming responses send one of these per NDJSON line
+#[derive(Debug, Deserialize)]
+struct OllamaChatResponse {
+    message: Option<OllamaMessage>,
+    #[serde(default)]
+    done: bool,
+    done_reason: Option<String>,
+    prompt_eval_count: Option<u32>,
+    eval_count: Option<u32>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaEmbedResponse {
+    embeddings: Vec<Vec<f32>>,
+    prompt_eval_count: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaTagsResponse {
+    models: Vec<OllamaTag>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaTag {
+    name: String,
+    details: Option<OllamaTagDetails>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaTagDetails {
+    quantization_level: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaShowResponse {
+    #[serde(default)]
+    capabilities: Vec<String>,
+}
+
+impl OllamaProvider {
+    pub fn new(
+        api_key: String,
+        base_url: Option<String>,
+        proxy_config: Option<ProxyConfig>,
+        _provider_id: Uuid,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let base_url = base_url
+            .filter(|url| !url.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_OLLAMA_URL.to_string());
+        // The native API lives at the server root; accept URLs copied from OpenAI-style configs
+        let base_url = base_url
+            .trim_end_matches('/')
+            .trim_end_matches("/v1")
+            .trim_end_matches("/api")
+            .to_string();
+        let client = build_http_client(&base_url, proxy_config.as_ref())?;
+
+        Ok(Self {
+            client,
+            api_key,
+            base_url,
+        })
+    }
+
+    /// Ollama runs without authentication by default; send a key only when one is configured
+    /// (e.g. a server behind an authenticating reverse proxy)
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        let builder = self
+            .client
+            .request(method, format!("{}{}", self.base_url, path))
+            .header("Content-Type", "application/json");
+
+        if self.api_key.trim().is_empty() {
+            builder
+        } else {
+            builder.header("Authorization", format!("Bearer {}", self.api_key))
+        }
+    }
+
+    async fn error_from_response(
+        response: reqwest::Response,
+    ) -> Box<dyn std::error::Error + Send + Sync> {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        // Ollama reports errors as {"error": "..."}
+        let message = serde_json::from_str::<Value>(&error_text)
+            .ok()
+            .and_then(|body| body["error"].as_str().map(|s| s.to_string()))
+            .unwrap_or(error_text);
+
+        ProviderApiError::new(status, format!("Ollama API error: {}", message)).into()
+    }
+
+    fn is_supported_image_type(&self, mime_type: &str) -> bool {
+        matches!(
+            mime_type,
+            "image/jpeg" | "image/jpg" | "image/png" | "image/webp" | "image/gif"
+        )
+    }
+
+    /// Load an image attachment as the raw base64 string Ollama expects in `images`
+    async fn load_image(
+        &self,
+        file_ref: &FileReference,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let file_data = load_file_content(file_ref.file_id).await?;
+        Ok(base64::engine::general_purpose::STANDARD.encode(&file_data))
+    }
+
+    /// Convert messages to Ollama format
+    ///
+    /// Images go in the message's `images` list, tool calls in `tool_calls`, and every tool
+    /// result becomes its own `tool` message following the assistant message that requested it.
+    async fn convert_messages_to_ollama(
+        &self,
+        messages: &[ChatMessage],
+    ) -> Result<Vec<OllamaMessage>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ollama_messages = Vec::new();
+        // Tool results only carry the call ID; Ollama wants the tool name
+        let mut tool_names_by_call_id = std::collections::HashMap::new();
+
+        for message in messages {
+            let parts = match &message.content {
+                MessageContent::Text(text) => {
+                    ollama_messages.push(OllamaMessage {
+                        role: message.role.clone(),
+                        content: text.clone(),
+                        ..Default::default()
+                    });
+                    continue;
+                }
+                MessageContent::Multimodal(parts) => parts,
+            };
+
+            let mut ollama_message = OllamaMessage {
+                role: message.role.clone(),
+                ..Default::default()
+            };
+            let mut text_parts = Vec::new();
+            let mut tool_results = Vec::new();
+
+            for part in parts {
+                match part {
+                    ContentPart::Text(text) => text_parts.push(text.clone()),
+                    ContentPart::FileReference(file_ref) => {
+                        let mime_type = file_ref.mime_type.as_deref().unwrap_or_default();
+                        if !self.is_supported_image_type(mime_type) {
+                            text_parts.push(format!("[File: {}]", file_ref.filename));
+                            continue;
+                        }
+
+                        match self.load_image(file_ref).await {
+                            Ok(image) => ollama_message.images.push(image),
+                            Err(e) => {
+                                eprintln!("Error processing image {}: {}", file_ref.filename, e);
+                                text_parts.push(format!("[Image: {}]", file_ref.filename));
+                            }
+                        }
+                    }
+                    ContentPart::ToolUse { id, name, input } => {
+                        tool_names_by_call_id.insert(id.clone(), name.clone());
+                        ollama_message.tool_calls.push(OllamaToolCall {
+                            function: OllamaFunctionCall {
+                                name: name.clone(),
+                                arguments: input.clone(),
+                            },
+                        });
+                    }
+                    ContentPart::ToolResult { call_id, output } => {
+                        tool_results.push(OllamaMessage {
+                            role: "tool".to_string(),
+                            content: output.clone(),
+                            tool_name: tool_names_by_call_id.get(call_id).cloned(),
+                            ..Default::default()
+                        });
+                    }
+                    ContentPart::Thinking { .. } => {}
+                }
+            }
+
+            ollama_message.content = text_parts.join("\n");
+            let has_content = !ollama_message.content.is_empty()
+                || !ollama_message.images.is_empty()
+                || !ollama_message.tool_calls.is_empty();
+            if has_content || tool_results.is_empty() {
+                ollama_messages.push(ollama_message);
+            }
+            ollama_messages.extend(tool_results);
+        }
+
+        Ok(ollama_messages)
+    }
+
+    async fn prepare_request(
+        &self,
+        request: &ChatRequest,
+    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        let messages = self.convert_messages_to_ollama(&request.messages).await?;
+
+        let mut payload = json!({
+            "model": request.model_name,
+            "messages": messages,
+            "stream": request.stream
+        });
+
+        // Sampling parameters go in `options` for the native API
+        if let Some(params) = &request.parameters {
+            let mut options = serde_json::Map::new();
+            if let Some(max_tokens) = params.max_tokens {
+                options.insert("num_predict".to_string(), json!(max_tokens));
+            }
+            if let Some(temperature) = params.temperature {
+                options.insert("temperature".to_string(), json!(temperature));
+            }
+            if let Some(top_k) = params.top_k {
+                options.insert("top_k".to_string(), json!(top_k));
+            }
+            if let Some(top_p) = params.top_p {
+                options.insert("top_p".to_string(), json!(top_p));
+            }
+            if let Some(min_p) = params.min_p {
+                options.insert("min_p".to_string(), json!(min_p));
+            }
+            if let Some(repeat_last_n) = params.repeat_last_n {
+                options.insert("repeat_last_n".to_string(), json!(repeat_last_n));
+            }
+            if let Some(repeat_penalty) = params.repeat_penalty {
+                options.insert("repeat_penalty".to_string(), json!(repeat_penalty));
+            }
+            if let Some(presence_penalty) = params.presence_penalty {
+                options.insert("presence_penalty".to_string(), json!(presence_penalty));
+            }
+            if let Some(frequency_penalty) = params.frequency_penalty {
+                options.insert("frequency_penalty".to_string(), json!(frequency_penalty));
+            }
+            if let Some(seed) = params.seed {
+                options.insert("seed".to_string(), json!(seed));
+            }
+            if let Some(stop) = &params.stop {
+                options.insert("stop".to_string(), json!(stop));
+            }
+            if !options.is_empty() {
+                payload["options"] = Value::Object(options);
+            }
+            // Ollama takes no budget, only whether thinking models return their reasoning
+            if params.thinking_budget.is_some() {
+                payload["think"] = json!(true);
+            }
+        }
+
+        if let Some(tools) = &request.tools {
+            let ollama_tools: Vec<Value> = tools
+                .iter()
+                .map(|tool| {
+                    json!({
+                        "type": "function",
+                        "function": {
+                            "name": tool.name,
+                            "description": tool.description,
+                            "parameters": tool.input_schema
+                        }
+                    })
+                })
+                .collect();
+            payload["tools"] = json!(ollama_tools);
+        }
+
+        // Ollama takes the schema itself as the output format
+        if let Some(schema) = &request.json_schema {
+            payload["format"] = schema.clone();
+        }
+
+        Ok(payload)
+    }
+
+    /// Ollama does not assign IDs to tool calls, so generate one per call
+    fn convert_tool_calls(tool_calls: &[OllamaToolCall]) -> Vec<ToolUse> {
+        tool_calls
+            .iter()
+            .map(|call| ToolUse {
+                id: format!("call_{}", Uuid::new_v4().simple()),
+                name: call.function.name.clone(),
+                input: call.function.arguments.clone(),
+            })
+            .collect()
+    }
+
+    fn finish_reason(response: &OllamaChatResponse, has_tool_calls: bool) -> Option<String> {
+        if has_tool_calls {
+            Some("tool_calls".to_string())
+        } else if response.done {
+            Some(
+                response
+                    .done_reason
+                    .clone()
+                    .unwrap_or_else(|| "stop".to_string()),
+            )
+        } else {
+            None
+        }
+    }
+
+    /// Token counts of a finished response
+    fn usage(response: &OllamaChatResponse) -> Option<Usage> {
+        if !response.done {
+            return None;
+        }
+        Some(Usage {
+            prompt_tokens: response.prompt_eval_count,
+            completion_tokens: response.eval_count,
+            total_tokens: match (response.prompt_eval_count, response.eval_count) {
+                (Some(prompt), Some(completion)) => Some(prompt + completion),
+                _ => None,
+            },
+        })
+    }
+
+    /// Turn one NDJSON line of a streaming `/api/chat` response into chunks. A line with several
+    /// tool calls yields one chunk per call.
+    fn parse_stream_line(
+        line: &str,
+        think_tags: &mut ThinkTagParser,
+    ) -> Vec<Result<StreamingChunk, Box<dyn std::error::Error + Send + Sync>>> {
+        let response = match serde_json::from_str::<OllamaChatResponse>(line) {
+            Ok(response) => response,
+            Err(e) => {
+                eprintln!(
+                    "Failed to parse Ollama streaming response: {} for data: {}",
+                    e, line
+                );
+                return Vec::new();
+            }
+        };
+
+        if let Some(error) = response.error {
+            return vec![Err(format!("Ollama API error: {}", error).into())];
+        }
+
+        // Models without `think` support write their reasoning in the content
+        let mut text = StreamingChunk {
+            content: None,
+            finish_reason: None,
+            tool_use: None,
+            usage: None,
+            thinking: None,
+            thinking_signature: None,
+        };
+        let tool_calls = match response.message.as_ref() {
+            Some(message) => {
+                text.thinking = message.thinking.clone().filter(|t| !t.is_empty());
+                think_tags.push(&message.content).append_to(&mut text);
+                Self::convert_tool_calls(&message.tool_calls)
+            }
+            None => Vec::new(),
+        };
+        if response.done {
+            think_tags.finish().append_to(&mut text);
+        }
+        let finish_reason = Self::finish_reason(&response, !tool_calls.is_empty());
+        let usage = Self::usage(&response);
+
+        if tool_calls.is_empty() {
+            if text.content.is_none() && text.thinking.is_none() && finish_reason.is_none() {
+                return Vec::new();
+            }
+            text.finish_reason = finish_reason;
+            text.usage = usage;
+            return vec![Ok(text)];
+        }
+
+        let mut chunks = Vec::new();
+        if text.content.is_some() || text.thinking.is_some() {
+            chunks.push(Ok(text));
+        }
+        // Only the last tool call finishes the turn, so consumers collect every call
+        let last = tool_calls.len() - 1;
+        for (index, tool_use) in tool_calls.into_iter().enumerate() {
+            chunks.push(Ok(StreamingChunk {
+                content: None,
+                finish_reason: if index == last { finish_reason.clone() } else { None },
+                tool_use: Some(tool_use),
+                usage: if index == last { usage.clone() } else { None },
+                thinking: None,
+                thinking_signature: None,
+            }));
+        }
+        chunks
+    }
+
+    /// Look up what a model can do through `/api/show`. Servers that do not report
+    /// capabilities are treated as serving a chat model.
+    async fn get_model_capabilities(&self, model_name: &str) -> ModelCapabilities {
+        let response = self
+            .request(reqwest::Method::POST, "/api/show")
+            .json(&json!({ "model": model_name }))
+            .send()
+            .await;
+
+        let reported = match response {
+            Ok(response) if response.status().is_success() => response
+                .json::<OllamaShowResponse>()
+                .await
+                .map(|show| show.capabilities)
+                .unwrap_or_default(),
+            Ok(response) => {
+                eprintln!(
+                    "Failed to get Ollama model info for {}: HTTP {}",
+                    model_name,
+                    response.status()
+                );
+                Vec::new()
+            }
+            Err(e) => {
+                eprintln!("Failed to get Ollama model info for {}: {}", model_name, e);
+                Vec::new()
+            }
+        };
+
+        if reported.is_empty() {
+            return ModelCapabilities {
+                chat: Some(true),
+                ..Default::default()
+            };
+        }
+
+        let has = |capability: &str| reported.iter().any(|c| c == capability);
+        ModelCapabilities {
+            chat: Some(has("completion")),
+            vision: Some(has("vision")),
+            tools: Some(has("tools")),
+            text_embedding: Some(has("embedding")),
+            ..Default::default()
+        }
+    }
+}
+
+#[async_trait]
+impl AIProvider for OllamaProvider {
+    async fn chat(
+        &self,
+        request: ChatRequest,
+    ) -> Result<ChatResponse, Box<dyn std::error::Error + Send + Sync>> {
+        let mut request = request;
+        request.stream = false;
+
+        let payload = self.prepare_request(&request).await?;
+
+        let response = self
+            .request(reqwest::Method::POST, "/api/chat")
+            .json(&payload)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Self::error_from_response(response).await);
+        }
+
+        let ollama_response: OllamaChatResponse = response.json().await?;
+        if let Some(error) = ollama_response.error {
+            return Err(format!("Ollama API error: {}", error).into());
+        }
+
+        let message = ollama_response
+            .message
+            .as_ref()
+            .ok_or("No message returned from Ollama API")?;
+        let tool_use = Self::convert_tool_calls(&message.tool_calls)
+            .into_iter()
+            .next();
+
+        let (inline_thinking, content) = split_think_tags(&message.content);
+
+        Ok(ChatResponse {
+            content,
+            finish_reason: Self::finish_reason(&ollama_response, tool_use.is_some()),
+            usage: Self::usage(&ollama_response),
+            tool_use,
+            thinking: message.thinking.clone().filter(|t| !t.is_empty()).or(inline_thinking),
+        })
+    }
+
+    async fn chat_stream(
+        &self,
+        request: ChatRequest,
+    ) -> Result<StreamingResponse, Box<dyn std::error::Error + Send + Sync>> {
+        let mut request = request;
+        request.stream = true;
+
+        let payload = self.prepare_request(&request).await?;
+
+        let response = self
+            .request(reqwest::Method::POST, "/api/chat")
+            .json(&payload)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Self::error_from_response(response).await);
+        }
+
+        use std::sync::{Arc, Mutex};
+
+        let buffer = Arc::new(Mutex::new(String::new()));
+        let think_tags = Arc::new(Mutex::new(ThinkTagParser::new()));
+
+        // NDJSON: one JSON object per line, and a line may be split across network chunks
+        let stream = response
+            .bytes_stream()
+            .map(move |result| {
+                let buffer = buffer.clone();
+                let think_tags = think_tags.clone();
+                match result {
+                    Ok(bytes) => {
+                        let mut buffer_guard = buffer.lock().unwrap();
+                        buffer_guard.push_str(&String::from_utf8_lossy(&bytes));
+
+                        let mut chunks = Vec::new();
+                        while let Some(line_end) = buffer_guard.find('\n') {
+                            let line = buffer_guard[..line_end].trim().to_string();
+                            buffer_guard.drain(..=line_end);
+
+                            if !line.is_empty() {
+                                chunks.extend(Self::parse_stream_line(
+                                    &line,
+                                    &mut think_tags.lock().unwrap(),
+                                ));
+                            }
+                        }
+                        chunks
+                    }
+                    Err(e) => vec![Err(Box::new(e) as Box<dyn std::error::Error + Send + Sync>)],
+                }
+            })
+            .flat_map(futures_util::stream::iter);
+
+        Ok(Box::pin(stream))
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "ollama"
+    }
+
+    fn supports_file_upload(&self) -> bool {
+        true
+    }
+
+    fn supported_file_types(&self) -> Vec<String> {
+        vec![
+            "image/jpeg".to_string(),
+            "image/jpg".to_string(),
+            "image/png".to_string(),
+            "image/webp".to_string(),
+            "image/gif".to_string(),
+        ]
+    }
+
+    async fn resolve_file_content(
+        &self,
+        file_ref: &mut FileReference,
+        _provider_id: Uuid,
+    ) -> Result<ProviderFileContent, Box<dyn std::error::Error + Send + Sync>> {
+        let mime_type = file_ref.mime_type.clone().unwrap_or_default();
+        if !self.is_supported_image_type(&mime_type) {
+            return Err(format!("Unsupported file type: {}", mime_type).into());
+        }
+
+        Ok(ProviderFileContent::DirectEmbed {
+            data: self.load_image(file_ref).await?,
+            mime_type,
+        })
+    }
+
+    async fn forward_chat_request(
+        &self,
+        request: serde_json::Value,
+    ) -> Result<reqwest::Response, Box<dyn std::error::Error + Send + Sync>> {
+        // The API proxy speaks the OpenAI format, which Ollama also serves under /v1
+        let response = self
+            .request(reqwest::Method::POST, "/v1/chat/completions")
+            .json(&request)
+            .send()
+            .await?;
+
+        Ok(response)
+    }
+
+    async fn forward_embeddings_request(
+        &self,
+        request: serde_json::Value,
+    ) -> Result<reqwest::Response, Box<dyn std::error::Error + Send + Sync>> {
+        let response = self
+            .request(reqwest::Method::POST, "/v1/embeddings")
+            .json(&request)
+            .send()
+            .await?;
+
+        Ok(response)
+    }
+
+    async fn embeddings(
+        &self,
+        request: EmbeddingsRequest,
+    ) -> Result<EmbeddingsResponse, Box<dyn std::error::Error + Send + Sync>> {
+        let input = match &request.input {
+            EmbeddingsInput::Single(text) => json!(text),
+            EmbeddingsInput::Multiple(texts) => json!(texts),
+        };
+        let mut payload = json!({
+            "model": request.model_name,
+            "input": input
+        });
+        if let Some(dimensions) = request.dimensions {
+            payload["dimensions"] = json!(dimensions);
+        }
+
+        let response = self
+            .request(reqwest::Method::POST, "/api/embed")
+            .json(&payload)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Self::error_from_response(response).await);
+        }
+
+        let embed_response: OllamaEmbedResponse = response.json().await?;
+        let prompt_tokens = embed_response.prompt_eval_count.unwrap_or(0);
+
+        Ok(EmbeddingsResponse {
+            object: "list".to_string(),
+            data: embed_response
+                .embeddings
+                .into_iter()
+                .enumerate()
+                .map(|(index, embedding)| EmbeddingData {
+                    object: "embedding".to_string(),
+                    index: index as u32,
+                    embedding,
+                })
+                .collect(),
+            model: request.model_name,
+            usage: EmbeddingsUsage {
+                prompt_tokens,
+                total_tokens: prompt_tokens,
+            },
+        })
+    }
+
+    async fn list_models(
+        &self,
+    ) -> Result<Vec<ProviderModelInfo>, Box<dyn std::error::Error + Send + Sync>> {
+        let response = self
+            .request(reqwest::Method::GET, "/api/tags")
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Self::error_from_response(response).await);
+        }
+
+        let tags: OllamaTagsResponse = response.json().await?;
+
+        let mut models = Vec::with_capacity(tags.models.len());
+        for tag in tags.models {
+            // e.g. "llama3.2:3b" → "llama3.2 3b (Q4_K_M)"
+            let mut display_name = tag.name.replace(':', " ");
+            if let Some(quantization) = tag
+                .details
+                .as_ref()
+                .and_then(|d| d.quantization_level.as_ref())
+            {
+                display_name = format!("{} ({})", display_name, quantization);
+            }
+
+            let capabilities = self.get_model_capabilities(&tag.name).await;
+            models.push(ProviderModelInfo {
+                name: tag.name,
+                display_name,
+                capabilities,
+            });
+        }
+
+        Ok(models)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OllamaProvider;
+    use crate::ai::core::providers::{
+        AIProvider, ChatMessage, ChatRequest, EmbeddingsInput, EmbeddingsRequest,
+    };
+    use futures_util::StreamExt;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use uuid::Uuid;
+
+    /// Serve canned bodies by request path, closing the connection after each response.
+    /// Bodies are written in two halves so clients have to handle split NDJSON lines.
+    async fn mock_server(routes: Vec<(&'static str, String)>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut request = Vec::new();
+                let mut buf = [0u8; 4096];
+                // Read the headers, then as much body as Content-Length announces
+                loop {
+                    let n = socket.read(&mut buf).await.unwrap();
+                    request.extend_from_slice(&buf[..n]);
+                    let text = String::from_utf8_lossy(&request);
+                    if let Some(header_end) = text.find("\r\n\r\n") {
+                        let content_length = text[..header_end]
+                            .lines()
+                            .find_map(|line| {
+                                let (name, value) = line.split_once(':')?;
+                                name.eq_ignore_ascii_case("content-length")
+                                    .then(|| value.trim().parse::<usize>().ok())?
+                            })
+                            .unwrap_or(0);
+                        if request.len() >= header_end + 4 + content_length {
+                            break;
+                        }
+                    }
+                    if n == 0 {
+                        break;
+                    }
+                }
+
+                let request = String::from_utf8_lossy(&request);
+                let path = request.split_whitespace().nth(1).unwrap_or("/");
+                let body = routes
+                    .iter()
+                    .find(|(route, _)| *route == path)
+                    .map(|(_, body)| body.clone());
+
+                let (status, body) = match body {
+                    Some(body) => ("200 OK", body),
+                    None => ("404 Not Found", r#"{"error":"not found"}"#.to_string()),
+                };
+                let headers = format!(
+                    "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    status,
+                    body.len()
+                );
+                let (first, second) = body.split_at(body.len() / 2);
+                socket.write_all(headers.as_bytes()).await.unwrap();
+                socket.write_all(first.as_bytes()).await.unwrap();
+                socket.flush().await.unwrap();
+                socket.write_all(second.as_bytes()).await.unwrap();
+                let _ = socket.shutdown().await;
+            }
+        });
+
+        format!("http://{}", address)
+    }
+
+    fn provider(base_url: String) -> OllamaProvider {
+        OllamaProvider::new(String::new(), Some(base_url), None, Uuid::nil()).unwrap()
+    }
+
+    fn chat_request() -> ChatRequest {
+        ChatRequest {
+            messages: vec![ChatMessage::text("user", "Hi")],
+            model_name: "llama3.2".to_string(),
+            model_id: Uuid::nil(),
+            provider_id: Uuid::nil(),
+            stream: true,
+            parameters: None,
+            tools: None,
+            json_schema: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn streams_ndjson_chat_responses() {
+        let body = [
+            r#"{"message":{"role":"assistant","content":"Hel"},"done":false}"#,
+            r#"{"message":{"role":"assistant","content":"lo"},"done":false}"#,
+            r#"{"message":{"role":"assistant","content":""},"done":true,"done_reason":"stop","prompt_eval_count":3,"eval_count":2}"#,
+        ]
+        .join("\n")
+            + "\n";
+        let base_url = mock_server(vec![("/api/chat", body)]).await;
+
+        let mut stream = provider(base_url).chat_stream(chat_request()).await.unwrap();
+        let mut content = String::new();
+        let mut finish_reason = None;
+        let mut usage = None;
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.unwrap();
+            content.push_str(chunk.content.as_deref().unwrap_or_default());
+            finish_reason = chunk.finish_reason.or(finish_reason);
+            usage = chunk.usage.or(usage);
+        }
+
+        assert_eq!(content, "Hello");
+        assert_eq!(finish_reason.as_deref(), Some("stop"));
+        assert_eq!(usage.and_then(|u| u.total_tokens), Some(5));
+    }
+
+    #[tokio::test]
+    async fn separates_thinking_from_content() {
+        let body = [
+            r#"{"message":{"role":"assistant","content":"","thinking":"Greet back."},"done":false}"#,
+            r#"{"message":{"role":"assistant","content":"<think>Or not</thi"},"done":false}"#,
+            r#"{"message":{"role":"assistant","content":"nk>\n\nHello"},"done":false}"#,
+            r#"{"message":{"role":"assistant","content":""},"done":true,"done_reason":"stop"}"#,
+        ]
+        .join("\n")
+            + "\n";
+        let base_url = mock_server(vec![("/api/chat", body)]).await;
+
+        let mut stream = provider(base_url).chat_stream(chat_request()).await.unwrap();
+        let mut content = String::new();
+        let mut thinking = String::new();
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.unwrap();
+            content.push_str(chunk.content.as_deref().unwrap_or_default());
+            thinking.push_str(chunk.thinking.as_deref().unwrap_or_default());
+        }
+
+        assert_eq!(thinking, "Greet back.Or not");
+        assert_eq!(content, "Hello");
+    }
+
+    #[tokio::test]
+    async fn maps_tool_calls() {
+        let body = r#"{"message":{"role":"assistant","content":"","tool_calls":[{"function":{"name":"get_weather","arguments":{"city":"Paris"}}}]},"done":true,"done_reason":"stop"}"#;
+        let base_url = mock_server(vec![("/api/chat", body.to_string())]).await;
+
+        let mut request = chat_request();
+        request.stream = false;
+        let response = provider(base_url).chat(request).await.unwrap();
+
+        let tool_use = response.tool_use.unwrap();
+        assert_eq!(tool_use.name, "get_weather");
+        assert_eq!(tool_use.input["city"], "Paris");
+        assert_eq!(response.finish_reason.as_deref(), Some("tool_calls"));
+    }
+
+    #[tokio::test]
+    async fn reports_api_errors_with_status() {
+        let base_url = mock_server(vec![]).await;
+
+        let error = provider(base_url).chat(chat_request()).await.unwrap_err();
+        assert_eq!(error.to_string(), "Ollama API error: not found");
+    }
+
+    #[tokio::test]
+    async fn generates_embeddings() {
+        let body = r#"{"model":"nomic-embed-text","embeddings":[[0.1,0.2],[0.3,0.4]],"prompt_eval_count":4}"#;
+        let base_url = mock_server(vec![("/api/embed", body.to_string())]).await;
+
+        let response = provider(base_url)
+            .embeddings(EmbeddingsRequest {
+                model_id: Uuid::nil(),
+                model_name: "nomic-embed-text".to_string(),
+                input: EmbeddingsInput::Multiple(vec!["a".to_string(), "b".to_string()]),
+                encoding_format: None,
+                dimensions: None,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(response.data.len(), 2);
+        assert_eq!(response.data[1].index, 1);
+        assert_eq!(response.data[1].embedding, vec![0.3, 0.4]);
+        assert_eq!(response.usage.prompt_tokens, 4);
+    }
+
+    #[tokio::test]
+    async fn lists_models_with_capabilities() {
+        let tags = r#"{"models":[{"name":"llava:7b","details":{"quantization_level":"Q4_0"}}]}"#;
+        let show = r#"{"capabilities":["completion","vision"]}"#;
+        let base_url = mock_server(vec![
+            ("/api/tags", tags.to_string()),
+            ("/api/show", show.to_string()),
+        ])
+        .await;
+
+        let models = provider(base_url).list_models().await.unwrap();
+
+        assert_eq!(models.len(), 1);
+        assert_eq!(models[0].name, "llava:7b");
+        assert_eq!(models[0].display_name, "llava 7b (Q4_0)");
+        assert_eq!(models[0].capabilities.vision, Some(true));
+        assert_eq!(models[0].capabilities.tools, Some(false));
+    }
+}
@@ -5,7 +5,8 @@ use uuid::Uuid;
 use super::openai_compatible::OpenAICompatibleProvider;
 use crate::ai::core::providers::{
     AIProvider, ChatRequest, ChatResponse, ContentPart, EmbeddingsRequest, EmbeddingsResponse,
-    FileReference, MessageContent, ProviderFileContent, ProxyConfig, StreamingResponse,
+    FileReference, ImageGenerationRequest, ImageGenerationResponse, MessageContent,
+    ProviderFileContent, ProxyConfig, StreamingResponse, map_provider_error,
 };
 use crate::ai::file_helpers::load_file_content;
 
@@ -107,6 +108,7 @@ impl OpenAIProvider {
                             ContentPart::ToolResult { call_id, output } => {
                                 format!("[Tool Result {}]: {}", call_id, output)
                             }
+                            ContentPart::Thinking { .. } => String::new(),
                         })
                         .collect();
 
@@ -194,7 +196,7 @@ impl AIProvider for OpenAIProvider {
         let processed_request = self.preprocess_request(request).await?;
         match self.inner.chat(processed_request).await {
             Ok(response) => Ok(response),
-            Err(e) => Err(self.handle_openai_errors(&e.to_string())),
+            Err(e) => Err(map_provider_error(e, |message| self.handle_openai_errors(message))),
         }
     }
 
@@ -205,7 +207,7 @@ impl AIProvider for OpenAIProvider {
         let processed_request = self.preprocess_request(request).await?;
         match self.inner.chat_stream(processed_request).await {
             Ok(response) => Ok(response),
-            Err(e) => Err(self.handle_openai_errors(&e.to_string())),
+            Err(e) => Err(map_provider_error(e, |message| self.handle_openai_errors(message))),
         }
     }
 
@@ -299,13 +301,30 @@ impl AIProvider for OpenAIProvider {
         self.inner.forward_chat_request(request).await
     }
 
+    async fn forward_embeddings_request(
+        &self,
+        request: serde_json::Value,
+    ) -> Result<reqwest::Response, Box<dyn std::error::Error + Send + Sync>> {
+        self.inner.forward_embeddings_request(request).await
+    }
+
     async fn embeddings(
         &self,
         request: EmbeddingsRequest,
     ) -> Result<EmbeddingsResponse, Box<dyn std::error::Error + Send + Sync>> {
         match self.inner.embeddings_impl(request).await {
             Ok(response) => Ok(response),
-            Err(e) => Err(self.handle_openai_errors(&e.to_string())),
+            Err(e) => Err(map_provider_error(e, |message| self.handle_openai_errors(message))),
+        }
+    }
+
+    async fn generate_images(
+        &self,
+        request: ImageGenerationRequest,
+    ) -> Result<ImageGenerationResponse, Box<dyn std::error::Error + Send + Sync>> {
+        match self.inner.generate_images_impl(request).await {
+            Ok(response) => Ok(response),
+            Err(e) => Err(map_provider_error(e, |message| self.handle_openai_errors(message))),
         }
     }
 }
@@ -4,14 +4,18 @@ use futures_util::StreamExt;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::BTreeMap;
 use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
+use crate::ai::core::image_generation;
 use crate::ai::core::provider_base::build_http_client;
+use crate::ai::core::structured_output;
+use crate::ai::core::thinking::{split_think_tags, ThinkTagParser};
 use crate::ai::core::providers::{
     AIProvider, ChatRequest, ChatResponse, ContentPart, EmbeddingsRequest, EmbeddingsResponse,
-    FileReference, MessageContent, ProviderFileContent, ProxyConfig, StreamingChunk,
-    StreamingResponse, Usage,
+    FileReference, ImageGenerationRequest, ImageGenerationResponse, MessageContent,
+    ProviderApiError, ProviderFileContent, ProxyConfig, StreamingChunk, StreamingResponse, Usage,
 };
 use crate::ai::file_helpers::load_file_content;
 
@@ -40,6 +44,11 @@ struct OpenAICompatibleMessage {
     role: String,
     content: Option<OpenAICompatibleContent>,
     tool_calls: Option<Vec<OpenAIToolCall>>,
+    // Reasoning of reasoning models, never sent back
+    #[serde(default, skip_serializing)]
+    reasoning_content: Option<String>,
+    #[serde(default, skip_serializing)]
+    reasoning: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -89,6 +98,8 @@ struct OpenAICompatibleUsage {
 #[derive(Debug, Deserialize)]
 struct OpenAICompatibleStreamResponse {
     choices: Vec<OpenAICompatibleStreamChoice>,
+    // Sent in a last chunk without choices when `stream_options.include_usage` is set
+    usage: Option<OpenAICompatibleUsage>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -101,6 +112,9 @@ struct OpenAICompatibleStreamChoice {
 struct OpenAICompatibleStreamDelta {
     content: Option<String>,
     tool_calls: Option<Vec<OpenAIToolCallDelta>>,
+    // `reasoning_content` (DeepSeek, vLLM, llama.cpp) or `reasoning` (OpenRouter)
+    reasoning_content: Option<String>,
+    reasoning: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -171,6 +185,7 @@ impl OpenAICompatibleProvider {
                         text: format!("[Tool Result {}]: {}", call_id, output),
                     });
                 }
+                ContentPart::Thinking { .. } => {}
                 ContentPart::FileReference(file_ref) => {
                     if let Some(mime_type) = &file_ref.mime_type {
                         if self.is_supported_image_type(mime_type) {
@@ -277,6 +292,8 @@ impl OpenAICompatibleProvider {
                     role: message.role.clone(),
                     content: Some(OpenAICompatibleContent::Text(text.clone())),
                     tool_calls: None,
+                    reasoning_content: None,
+                    reasoning: None,
                 },
                 MessageContent::Multimodal(parts) => {
                     if self.supports_vision() {
@@ -285,6 +302,8 @@ impl OpenAICompatibleProvider {
                             role: message.role.clone(),
                             content: Some(OpenAICompatibleContent::Array(content_parts)),
                             tool_calls: None,
+                            reasoning_content: None,
+                            reasoning: None,
                         }
                     } else {
                         // Convert to text for non-vision providers
@@ -301,6 +320,7 @@ impl OpenAICompatibleProvider {
                                 ContentPart::ToolResult { call_id, output } => {
                                     format!("[Tool Result {}]: {}", call_id, output)
                                 }
+                                ContentPart::Thinking { .. } => String::new(),
                             })
                             .collect();
 
@@ -308,6 +328,8 @@ impl OpenAICompatibleProvider {
                             role: message.role.clone(),
                             content: Some(OpenAICompatibleContent::Text(text_parts.join("\n"))),
                             tool_calls: None,
+                            reasoning_content: None,
+                            reasoning: None,
                         }
                     }
                 }
@@ -331,6 +353,10 @@ impl OpenAICompatibleProvider {
             "stream": stream
         });
 
+        if stream {
+            payload["stream_options"] = json!({ "include_usage": true });
+        }
+
         // Add tools if provided
         if let Some(tools) = &request.tools {
             let openai_tools: Vec<serde_json::Value> = tools
@@ -349,6 +375,14 @@ impl OpenAICompatibleProvider {
             payload["tools"] = json!(openai_tools);
         }
 
+        if let Some(schema) = &request.json_schema {
+            payload["response_format"] = if self.takes_json_schema() {
+                structured_output::response_format(schema)
+            } else {
+                json!({ "type": "json_object" })
+            };
+        }
+
         // Add optional parameters if present
         if let Some(params) = params {
             if let Some(seed) = params.seed {
@@ -367,12 +401,32 @@ impl OpenAICompatibleProvider {
         request: &ChatRequest,
         stream: bool,
     ) -> Result<serde_json::Value, Box<dyn std::error::Error + Send + Sync>> {
-        let messages = self.convert_messages_to_openai(&request.messages).await?;
+        let mut messages = self.convert_messages_to_openai(&request.messages).await?;
+        if let Some(schema) = request.json_schema.as_ref().filter(|_| !self.takes_json_schema()) {
+            messages.insert(
+                0,
+                OpenAICompatibleMessage {
+                    role: "system".to_string(),
+                    content: Some(OpenAICompatibleContent::Text(
+                        structured_output::schema_instructions(schema),
+                    )),
+                    tool_calls: None,
+                    reasoning_content: None,
+                    reasoning: None,
+                },
+            );
+        }
         let mut payload = self.build_request(request, stream);
         payload["messages"] = json!(messages);
         Ok(payload)
     }
 
+    /// Whether the provider accepts a JSON Schema in `response_format`. DeepSeek only has a
+    /// JSON mode, so the schema goes in the instructions instead.
+    fn takes_json_schema(&self) -> bool {
+        self.provider_name != "deepseek"
+    }
+
     fn get_endpoint_url(&self) -> String {
         // Handle different endpoint patterns
         if self.base_url.contains("/v1") || self.base_url.contains("/openai") {
@@ -387,6 +441,30 @@ impl OpenAICompatibleProvider {
         self.provider_name != "custom" || !self.api_key.is_empty()
     }
 
+    /// Forward a raw API proxy request to a path under base_url and return the raw response
+    async fn forward_request(
+        &self,
+        path: &str,
+        request: serde_json::Value,
+    ) -> Result<reqwest::Response, Box<dyn std::error::Error + Send + Sync>> {
+        let url = format!("{}{}", self.base_url, path);
+
+        let mut req_builder = self
+            .client
+            .post(&url)
+            .header("Content-Type", "application/json")
+            .json(&request);
+
+        // Add authentication if needed
+        if self.should_include_auth() {
+            req_builder = req_builder.header("Authorization", format!("Bearer {}", self.api_key));
+        }
+
+        // Send request and return raw response
+        let response = req_builder.send().await?;
+        Ok(response)
+    }
+
     /// Get max file size based on provider
     fn get_max_file_size(&self) -> u64 {
         match self.provider_name {
@@ -419,8 +497,13 @@ impl AIProvider for OpenAICompatibleProvider {
         let response = req_builder.send().await?;
 
         if !response.status().is_success() {
+            let status = response.status();
             let error_text = response.text().await?;
-            return Err(format!("{} API error: {}", self.provider_name, error_text).into());
+            return Err(ProviderApiError::new(
+                status,
+                format!("{} API error: {}", self.provider_name, error_text),
+            )
+            .into());
         }
 
         let api_response: OpenAICompatibleResponse = response.json().await?;
@@ -437,6 +520,7 @@ impl AIProvider for OpenAICompatibleProvider {
                 })
             });
 
+            let reasoning = choice.message.reasoning_content.or(choice.message.reasoning);
             let content = match choice.message.content {
                 Some(OpenAICompatibleContent::Text(text)) => text,
                 Some(OpenAICompatibleContent::Array(parts)) => {
@@ -452,6 +536,8 @@ impl AIProvider for OpenAICompatibleProvider {
                 }
                 None => String::new(),
             };
+            // Models without a reasoning parser on the server answer with inline tags
+            let (inline_thinking, content) = split_think_tags(&content);
 
             Ok(ChatResponse {
                 content,
@@ -462,6 +548,7 @@ impl AIProvider for OpenAICompatibleProvider {
                     total_tokens: u.total_tokens,
                 }),
                 tool_use,
+                thinking: reasoning.or(inline_thinking),
             })
         } else {
             Err(format!("No choices returned from {} API", self.provider_name).into())
@@ -488,19 +575,26 @@ impl AIProvider for OpenAICompatibleProvider {
         let response = req_builder.send().await?;
 
         if !response.status().is_success() {
+            let status = response.status();
             let error_text = response.text().await?;
-            return Err(format!("{} API error: {}", self.provider_name, error_text).into());
+            return Err(ProviderApiError::new(
+                status,
+                format!("{} API error: {}", self.provider_name, error_text),
+            )
+            .into());
         }
 
         // Create a buffer to accumulate partial SSE chunks
         let buffer = Arc::new(Mutex::new(String::new()));
-        // Track current tool call being accumulated: (id, name, arguments)
-        let current_tool_call = Arc::new(Mutex::new(None::<(String, String, String)>));
+        // Tool calls being accumulated by their index: (id, name, arguments)
+        let current_tool_calls = Arc::new(Mutex::new(BTreeMap::<u32, (String, String, String)>::new()));
+        let think_tags = Arc::new(Mutex::new(ThinkTagParser::new()));
         let provider_name = self.provider_name;
 
         let stream = response.bytes_stream().map(move |result| {
             let buffer = buffer.clone();
-            let current_tool_call = current_tool_call.clone();
+            let current_tool_calls = current_tool_calls.clone();
+            let think_tags = think_tags.clone();
             match result {
                 Ok(bytes) => {
                     let chunk = String::from_utf8_lossy(&bytes);
@@ -509,6 +603,8 @@ impl AIProvider for OpenAICompatibleProvider {
 
                     // Process complete lines from buffer
                     let mut result = None;
+                    let mut usage = None;
+                    let mut tool_uses = Vec::new();
                     while let Some(line_end) = buffer_guard.find('\n') {
                         let line = buffer_guard[..line_end].trim().to_string();
                         buffer_guard.drain(..=line_end);
@@ -520,50 +616,41 @@ impl AIProvider for OpenAICompatibleProvider {
                         if let Some(data) = line.strip_prefix("data: ") {
                             match serde_json::from_str::<OpenAICompatibleStreamResponse>(data) {
                                 Ok(stream_response) => {
+                                    if let Some(u) = stream_response.usage {
+                                        usage = Some(Usage {
+                                            prompt_tokens: u.prompt_tokens,
+                                            completion_tokens: u.completion_tokens,
+                                            total_tokens: u.total_tokens,
+                                        });
+                                    }
                                     if let Some(choice) = stream_response.choices.into_iter().next()
                                     {
-                                        let mut tool_use = None;
+                                        let mut tool_guard = current_tool_calls.lock().unwrap();
 
-                                        // Handle tool call deltas
+                                        // Handle tool call deltas; parallel calls stream under their own index
                                         if let Some(tool_call_deltas) = &choice.delta.tool_calls {
-                                            let mut tool_guard = current_tool_call.lock().unwrap();
-
                                             for delta in tool_call_deltas {
-                                                if delta.index == 0 {
-                                                    // Initialize or update tool call
-                                                    if let Some((ref mut id, ref mut name, ref mut args)) = *tool_guard {
-                                                        // Append to existing tool call
-                                                        if let Some(delta_id) = &delta.id {
-                                                            id.push_str(delta_id);
-                                                        }
-                                                        if let Some(func) = &delta.function {
-                                                            if let Some(delta_name) = &func.name {
-                                                                name.push_str(delta_name);
-                                                            }
-                                                            if let Some(delta_args) = &func.arguments {
-                                                                args.push_str(delta_args);
-                                                            }
-                                                        }
-                                                    } else if let Some(delta_id) = &delta.id {
-                                                        // Start new tool call
-                                                        let name = delta.function.as_ref()
-                                                            .and_then(|f| f.name.clone())
-                                                            .unwrap_or_default();
-                                                        let args = delta.function.as_ref()
-                                                            .and_then(|f| f.arguments.clone())
-                                                            .unwrap_or_default();
-                                                        *tool_guard = Some((delta_id.clone(), name, args));
+                                                let (id, name, args) =
+                                                    tool_guard.entry(delta.index).or_default();
+                                                if let Some(delta_id) = &delta.id {
+                                                    id.push_str(delta_id);
+                                                }
+                                                if let Some(func) = &delta.function {
+                                                    if let Some(delta_name) = &func.name {
+                                                        name.push_str(delta_name);
+                                                    }
+                                                    if let Some(delta_args) = &func.arguments {
+                                                        args.push_str(delta_args);
                                                     }
                                                 }
                                             }
                                         }
 
-                                        // If finish_reason is tool_calls, return the complete tool use
+                                        // If finish_reason is tool_calls, return every complete tool use
                                         if choice.finish_reason.as_deref() == Some("tool_calls") {
-                                            let mut tool_guard = current_tool_call.lock().unwrap();
-                                            if let Some((id, name, args)) = tool_guard.take() {
+                                            for (_, (id, name, args)) in std::mem::take(&mut *tool_guard) {
                                                 let input = serde_json::from_str(&args).unwrap_or(serde_json::json!({}));
-                                                tool_use = Some(crate::ai::core::providers::ToolUse {
+                                                tool_uses.push(crate::ai::core::providers::ToolUse {
                                                     id,
                                                     name,
                                                     input,
@@ -571,12 +658,30 @@ impl AIProvider for OpenAICompatibleProvider {
                                             }
                                         }
 
-                                        result = Some(Ok(StreamingChunk {
-                                            content: choice.delta.content,
-                                            finish_reason: choice.finish_reason,
-                                            tool_use,
-                                        }));
-                                        break;
+                                        // Merge the events that arrived together
+                                        let chunk = result.get_or_insert(StreamingChunk {
+                                            content: None,
+                                            finish_reason: None,
+                                            tool_use: None,
+                                            usage: None,
+                                            thinking: None,
+                                            thinking_signature: None,
+                                        });
+                                        if let Some(reasoning) =
+                                            choice.delta.reasoning_content.or(choice.delta.reasoning)
+                                        {
+                                            chunk
+                                                .thinking
+                                                .get_or_insert_with(String::new)
+                                                .push_str(&reasoning);
+                                        }
+                                        if let Some(content) = choice.delta.content {
+                                            think_tags.lock().unwrap().push(&content).append_to(chunk);
+                                        }
+                                        if choice.finish_reason.is_some() {
+                                            think_tags.lock().unwrap().finish().append_to(chunk);
+                                            chunk.finish_reason = choice.finish_reason;
+                                        }
                                     }
                                 }
                                 Err(e) => {
@@ -589,15 +694,37 @@ impl AIProvider for OpenAICompatibleProvider {
                         }
                     }
 
-                    result.unwrap_or(Ok(StreamingChunk {
+                    let mut chunk = result.unwrap_or(StreamingChunk {
                         content: None,
                         finish_reason: None,
                         tool_use: None,
-                    }))
+                        usage: None,
+                        thinking: None,
+                        thinking_signature: None,
+                    });
+
+                    // One chunk per tool use; the last one carries the finish reason
+                    let mut chunks = Vec::new();
+                    let last_tool_use = tool_uses.pop();
+                    for tool_use in tool_uses {
+                        chunks.push(Ok(StreamingChunk {
+                            content: None,
+                            finish_reason: None,
+                            tool_use: Some(tool_use),
+                            usage: None,
+                            thinking: None,
+                            thinking_signature: None,
+                        }));
+                    }
+                    chunk.tool_use = last_tool_use;
+                    chunk.usage = usage;
+                    chunks.push(Ok(chunk));
+                    chunks
                 }
-                Err(e) => Err(Box::new(e) as Box<dyn std::error::Error + Send + Sync>),
+                Err(e) => vec![Err(Box::new(e) as Box<dyn std::error::Error + Send + Sync>)],
             }
         });
+        let stream = stream.flat_map(futures_util::stream::iter);
 
         Ok(Box::pin(stream))
     }
@@ -703,7 +830,21 @@ impl AIProvider for OpenAICompatibleProvider {
         request: serde_json::Value,
     ) -> Result<reqwest::Response, Box<dyn std::error::Error + Send + Sync>> {
         // base_url already contains /v1, just append /chat/completions
-        let url = format!("{}/chat/completions", self.base_url);
+        self.forward_request("/chat/completions", request).await
+    }
+
+    async fn forward_embeddings_request(
+        &self,
+        request: serde_json::Value,
+    ) -> Result<reqwest::Response, Box<dyn std::error::Error + Send + Sync>> {
+        self.forward_request("/embeddings", request).await
+    }
+
+    async fn embeddings(
+        &self,
+        request: EmbeddingsRequest,
+    ) -> Result<EmbeddingsResponse, Box<dyn std::error::Error + Send + Sync>> {
+        let url = format!("{}/embeddings", self.base_url);
 
         let mut req_builder = self
             .client
@@ -716,22 +857,29 @@ impl AIProvider for OpenAICompatibleProvider {
             req_builder = req_builder.header("Authorization", format!("Bearer {}", self.api_key));
         }
 
-        // Send request and return raw response
         let response = req_builder.send().await?;
-        Ok(response)
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await?;
+            return Err(format!("HTTP {}: {}", status, error_text).into());
+        }
+
+        let embeddings_response: EmbeddingsResponse = response.json().await?;
+        Ok(embeddings_response)
     }
 
-    async fn embeddings(
+    async fn generate_images(
         &self,
-        request: EmbeddingsRequest,
-    ) -> Result<EmbeddingsResponse, Box<dyn std::error::Error + Send + Sync>> {
-        let url = format!("{}/embeddings", self.base_url);
+        request: ImageGenerationRequest,
+    ) -> Result<ImageGenerationResponse, Box<dyn std::error::Error + Send + Sync>> {
+        let url = format!("{}/images/generations", self.base_url);
 
         let mut req_builder = self
             .client
             .post(&url)
             .header("Content-Type", "application/json")
-            .json(&request);
+            .json(&image_generation::request_body(&request, None));
 
         // Add authentication if needed
         if self.should_include_auth() {
@@ -743,11 +891,14 @@ impl AIProvider for OpenAICompatibleProvider {
         if !response.status().is_success() {
             let status = response.status();
             let error_text = response.text().await?;
-            return Err(format!("HTTP {}: {}", status, error_text).into());
+            return Err(ProviderApiError::new(
+                status,
+                format!("{} API error: {}", self.provider_name, error_text),
+            )
+            .into());
         }
 
-        let embeddings_response: EmbeddingsResponse = response.json().await?;
-        Ok(embeddings_response)
+        image_generation::parse_response(&self.client, response).await
     }
 }
 
@@ -758,4 +909,152 @@ impl OpenAICompatibleProvider {
     ) -> Result<EmbeddingsResponse, Box<dyn std::error::Error + Send + Sync>> {
         self.embeddings(request).await
     }
+
+    pub async fn generate_images_impl(
+        &self,
+        request: ImageGenerationRequest,
+    ) -> Result<ImageGenerationResponse, Box<dyn std::error::Error + Send + Sync>> {
+        self.generate_images(request).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OpenAICompatibleProvider;
+    use crate::ai::core::providers::{AIProvider, ChatMessage, ChatRequest};
+    use futures_util::StreamExt;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use uuid::Uuid;
+
+    /// Serve canned bodies by request path, closing the connection after each response.
+    /// Bodies are written in two halves so clients have to handle split SSE events.
+    async fn mock_server(routes: Vec<(&'static str, String)>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut request = Vec::new();
+                let mut buf = [0u8; 4096];
+                // Read the headers, then as much body as Content-Length announces
+                loop {
+                    let n = socket.read(&mut buf).await.unwrap();
+                    request.extend_from_slice(&buf[..n]);
+                    let text = String::from_utf8_lossy(&request);
+                    if let Some(header_end) = text.find("\r\n\r\n") {
+                        let content_length = text[..header_end]
+                            .lines()
+                            .find_map(|line| {
+                                let (name, value) = line.split_once(':')?;
+                                name.eq_ignore_ascii_case("content-length")
+                                    .then(|| value.trim().parse::<usize>().ok())?
+                            })
+                            .unwrap_or(0);
+                        if request.len() >= header_end + 4 + content_length {
+                            break;
+                        }
+                    }
+                    if n == 0 {
+                        break;
+                    }
+                }
+
+                let request = String::from_utf8_lossy(&request);
+                let path = request.split_whitespace().nth(1).unwrap_or("/");
+                let body = routes
+                    .iter()
+                    .find(|(route, _)| *route == path)
+                    .map(|(_, body)| body.clone());
+
+                let (status, body) = match body {
+                    Some(body) => ("200 OK", body),
+                    None => ("404 Not Found", r#"{"error":"not found"}"#.to_string()),
+                };
+                let headers = format!(
+                    "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    status,
+                    body.len()
+                );
+                let (first, second) = body.split_at(body.len() / 2);
+                socket.write_all(headers.as_bytes()).await.unwrap();
+                socket.write_all(first.as_bytes()).await.unwrap();
+                socket.flush().await.unwrap();
+                socket.write_all(second.as_bytes()).await.unwrap();
+                let _ = socket.shutdown().await;
+            }
+        });
+
+        format!("http://{}/v1", address)
+    }
+
+    fn provider(base_url: String) -> OpenAICompatibleProvider {
+        OpenAICompatibleProvider::new("sk-test".to_string(), base_url, "openai", None, Uuid::nil())
+            .unwrap()
+    }
+
+    fn chat_request() -> ChatRequest {
+        ChatRequest {
+            messages: vec![ChatMessage::text("user", "Weather and time in Paris?")],
+            model_name: "gpt-4o-mini".to_string(),
+            model_id: Uuid::nil(),
+            provider_id: Uuid::nil(),
+            stream: true,
+            parameters: None,
+            tools: None,
+            json_schema: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn streams_interleaved_tool_calls_by_index() {
+        let events = [
+            r#"{"choices":[{"delta":{"role":"assistant","tool_calls":[{"index":0,"id":"call_a","type":"function","function":{"name":"get_weather","arguments":""}}]},"finish_reason":null}]}"#,
+            r#"{"choices":[{"delta":{"tool_calls":[{"index":1,"id":"call_b","type":"function","function":{"name":"get_time","arguments":""}}]},"finish_reason":null}]}"#,
+            r#"{"choices":[{"delta":{"tool_calls":[{"index":0,"function":{"arguments":"{\"city\":"}}]},"finish_reason":null}]}"#,
+            r#"{"choices":[{"delta":{"tool_calls":[{"index":1,"function":{"arguments":"{\"timezone\":\"Europe/Paris\"}"}}]},"finish_reason":null}]}"#,
+            r#"{"choices":[{"delta":{"tool_calls":[{"index":0,"function":{"arguments":"\"Paris\"}"}}]},"finish_reason":null}]}"#,
+            r#"{"choices":[{"delta":{},"finish_reason":"tool_calls"}]}"#,
+        ];
+        let body = events
+            .iter()
+            .map(|event| format!("data: {}\n\n", event))
+            .collect::<String>()
+            + "data: [DONE]\n\n";
+        let base_url = mock_server(vec![("/v1/chat/completions", body)]).await;
+
+        let mut stream = provider(base_url).chat_stream(chat_request()).await.unwrap();
+        let mut tool_uses = Vec::new();
+        let mut finish_reason = None;
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.unwrap();
+            tool_uses.extend(chunk.tool_use);
+            finish_reason = chunk.finish_reason.or(finish_reason);
+        }
+
+        assert_eq!(tool_uses.len(), 2);
+        assert_eq!(tool_uses[0].id, "call_a");
+        assert_eq!(tool_uses[0].name, "get_weather");
+        assert_eq!(tool_uses[0].input, serde_json::json!({ "city": "Paris" }));
+        assert_eq!(tool_uses[1].id, "call_b");
+        assert_eq!(tool_uses[1].name, "get_time");
+        assert_eq!(tool_uses[1].input, serde_json::json!({ "timezone": "Europe/Paris" }));
+        assert_eq!(finish_reason.as_deref(), Some("tool_calls"));
+    }
+
+    #[tokio::test]
+    async fn forwards_embeddings_to_embeddings_endpoint() {
+        let body = r#"{"object":"list","data":[{"object":"embedding","index":0,"embedding":[0.1,0.2]}],"model":"text-embedding-3-small"}"#;
+        let base_url = mock_server(vec![("/v1/embeddings", body.to_string())]).await;
+
+        let request = serde_json::json!({ "model": "text-embedding-3-small", "input": "Hi" });
+        let response = provider(base_url)
+            .forward_embeddings_request(request)
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), 200);
+        let json: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(json["data"][0]["embedding"][1], 0.2);
+    }
 }
@@ -1,14 +1,16 @@
 // RAG Engine implementations
 
 pub mod settings;
+pub mod simple_graph;
 pub mod simple_vector;
 pub mod traits;
 
+pub use simple_graph::RAGSimpleGraphEngine;
 pub use simple_vector::RAGSimpleVectorEngine;
 pub use traits::{RAGEngine, RAGEngineType};
 
 use crate::ai::rag::{
-    rag_file_storage::RagFileStorage, service::queries::get_engine_type_for_instance, RAGErrorCode, RAGInstanceErrorCode, RAGResult,
+    rag_file_storage::RagFileStorage, service::queries::get_engine_type_for_instance, RAGResult,
 };
 use serde::{Deserialize, Serialize};
 use std::sync::OnceLock;
@@ -50,15 +52,16 @@ impl RAGEngineFactory {
                 let engine = RAGSimpleVectorEngine::new(instance_id).await?;
                 Ok(Box::new(engine))
             }
-            RAGEngineType::SimpleGraph => Err(RAGErrorCode::Instance(
-                RAGInstanceErrorCode::ConfigurationError,
-            )),
+            RAGEngineType::SimpleGraph => {
+                let engine = RAGSimpleGraphEngine::new(instance_id).await?;
+                Ok(Box::new(engine))
+            }
         }
     }
 
     /// Get supported engine types
     pub fn supported_engine_types() -> Vec<RAGEngineType> {
-        vec![RAGEngineType::SimpleVector]
+        vec![RAGEngineType::SimpleVector, RAGEngineType::SimpleGraph]
     }
 }
 
@@ -106,7 +109,7 @@ impl EngineCapabilities {
                 ],
             },
             RAGEngineType::SimpleGraph => Self {
-                supports_vector_similarity: false,
+                supports_vector_similarity: true,
                 supports_graph_queries: true,
                 supports_entity_extraction: true,
                 supports_relationship_extraction: true,
@@ -1,5 +1,6 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 // Supporting enums
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, sqlx::Type)]
@@ -10,6 +11,22 @@ pub enum RAGChunkSelectionMethod {
     Vector,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, sqlx::Type)]
+pub enum RAGRetrievalMode {
+    #[serde(rename = "vector")]
+    Vector,
+    #[serde(rename = "hybrid")]
+    Hybrid,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, sqlx::Type)]
+pub enum RAGChunkingStrategy {
+    #[serde(rename = "token")]
+    Token,
+    #[serde(rename = "structure")]
+    Structure,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, sqlx::Type)]
 pub enum RAGSimpleGraphQueryMode {
     #[serde(rename = "local")]
@@ -33,6 +50,9 @@ pub struct RAGSimpleVectorIndexingSettings {
     pub chunk_overlap_token_size: Option<i32>,
     pub max_parallel_insert: Option<i32>,
     pub embedding_batch_size: Option<i32>,
+    pub chunking_strategy: Option<RAGChunkingStrategy>,
+    pub enable_semantic_split: Option<bool>,
+    pub semantic_split_threshold: Option<f32>,
 }
 
 impl RAGSimpleVectorIndexingSettings {
@@ -51,6 +71,20 @@ impl RAGSimpleVectorIndexingSettings {
     pub fn embedding_batch_size(&self) -> usize {
         self.embedding_batch_size.unwrap_or(32) as usize // DEFAULT_EMBEDDING_BATCH_SIZE
     }
+
+    pub fn chunking_strategy(&self) -> RAGChunkingStrategy {
+        self.chunking_strategy
+            .clone()
+            .unwrap_or(RAGChunkingStrategy::Token)
+    }
+
+    pub fn enable_semantic_split(&self) -> bool {
+        self.enable_semantic_split.unwrap_or(false)
+    }
+
+    pub fn semantic_split_threshold(&self) -> f32 {
+        self.semantic_split_threshold.unwrap_or(0.75) // DEFAULT_SEMANTIC_SPLIT_THRESHOLD
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, sqlx::Type)]
@@ -65,6 +99,10 @@ pub struct RAGSimpleVectorQueryingSettings {
     pub prompt_template_post_query: Option<String>,
     pub enable_rerank: Option<bool>,
     pub min_rerank_score: Option<f32>,
+    pub rerank_model_id: Option<Uuid>,
+    pub rerank_top_k: Option<i32>,
+    pub retrieval_mode: Option<RAGRetrievalMode>,
+    pub rrf_k: Option<i32>,
 }
 
 impl RAGSimpleVectorQueryingSettings {
@@ -101,6 +139,22 @@ impl RAGSimpleVectorQueryingSettings {
     pub fn min_rerank_score(&self) -> f32 {
         self.min_rerank_score.unwrap_or(0.0) // DEFAULT_MIN_RERANK_SCORE
     }
+
+    pub fn rerank_top_k(&self) -> usize {
+        self.rerank_top_k
+            .map(|k| k as usize)
+            .unwrap_or_else(|| self.chunk_top_k()) // DEFAULT_CHUNK_TOP_K
+    }
+
+    pub fn retrieval_mode(&self) -> RAGRetrievalMode {
+        self.retrieval_mode
+            .clone()
+            .unwrap_or(RAGRetrievalMode::Vector)
+    }
+
+    pub fn rrf_k(&self) -> usize {
+        self.rrf_k.unwrap_or(60).max(1) as usize // DEFAULT_RRF_K
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, sqlx::Type)]
@@ -118,6 +172,9 @@ impl RAGSimpleVectorEngineSettings {
                 chunk_overlap_token_size: None,
                 max_parallel_insert: None,
                 embedding_batch_size: None,
+                chunking_strategy: None,
+                enable_semantic_split: None,
+                semantic_split_threshold: None,
             })
     }
 
@@ -135,6 +192,10 @@ impl RAGSimpleVectorEngineSettings {
                 prompt_template_post_query: None,
                 enable_rerank: None,
                 min_rerank_score: None,
+                rerank_model_id: None,
+                rerank_top_k: None,
+                retrieval_mode: None,
+                rrf_k: None,
             })
     }
 }
@@ -150,6 +211,9 @@ pub struct RAGSimpleGraphIndexingSettings {
     pub summary_max_tokens: Option<i32>,
     pub entity_types: Option<Vec<String>>,
     pub extraction_language: Option<String>,
+    pub chunking_strategy: Option<RAGChunkingStrategy>,
+    pub enable_semantic_split: Option<bool>,
+    pub semantic_split_threshold: Option<f32>,
 }
 
 impl RAGSimpleGraphIndexingSettings {
@@ -194,6 +258,20 @@ impl RAGSimpleGraphIndexingSettings {
             .clone()
             .unwrap_or_else(|| "English".to_string()) // DEFAULT_SUMMARY_LANGUAGE
     }
+
+    pub fn chunking_strategy(&self) -> RAGChunkingStrategy {
+        self.chunking_strategy
+            .clone()
+            .unwrap_or(RAGChunkingStrategy::Token)
+    }
+
+    pub fn enable_semantic_split(&self) -> bool {
+        self.enable_semantic_split.unwrap_or(false)
+    }
+
+    pub fn semantic_split_threshold(&self) -> f32 {
+        self.semantic_split_threshold.unwrap_or(0.75) // DEFAULT_SEMANTIC_SPLIT_THRESHOLD
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, sqlx::Type)]
@@ -211,6 +289,8 @@ pub struct RAGSimpleGraphQueryingSettings {
     pub prompt_template_post_query: Option<String>,
     pub enable_rerank: Option<bool>,
     pub min_rerank_score: Option<f32>,
+    pub rerank_model_id: Option<Uuid>,
+    pub rerank_top_k: Option<i32>,
 }
 
 impl RAGSimpleGraphQueryingSettings {
@@ -261,6 +341,12 @@ impl RAGSimpleGraphQueryingSettings {
     pub fn min_rerank_score(&self) -> f32 {
         self.min_rerank_score.unwrap_or(0.0) // DEFAULT_MIN_RERANK_SCORE
     }
+
+    pub fn rerank_top_k(&self) -> usize {
+        self.rerank_top_k
+            .map(|k| k as usize)
+            .unwrap_or_else(|| self.chunk_top_k()) // DEFAULT_CHUNK_TOP_K
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, sqlx::Type)]
@@ -282,6 +368,9 @@ impl RAGSimpleGraphEngineSettings {
                 summary_max_tokens: None,
                 entity_types: None,
                 extraction_language: None,
+                chunking_strategy: None,
+                enable_semantic_split: None,
+                semantic_split_threshold: None,
             })
     }
 
@@ -302,6 +391,8 @@ impl RAGSimpleGraphEngineSettings {
                 prompt_template_post_query: None,
                 enable_rerank: None,
                 min_rerank_score: None,
+                rerank_model_id: None,
+                rerank_top_k: None,
             })
     }
 }
@@ -0,0 +1,129 @@
+// Core RAGSimpleGraphEngine struct and basic methods
+
+use super::queries;
+use crate::ai::core::AIModel;
+use crate::ai::rag::{
+    engines::{
+        settings::RAGSimpleGraphEngineSettings,
+        traits::{RAGEngine, RAGEngineType},
+    },
+    PipelineStage, ProcessingStatus, RAGErrorCode, RAGInstanceErrorCode, RAGQuery,
+    RAGQueryResponse, RAGResult,
+};
+use async_trait::async_trait;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Simple Graph RAG Engine (LightRAG-style knowledge graph on Apache AGE)
+pub struct RAGSimpleGraphEngine {
+    // === INSTANCE ===
+    pub(super) id: Uuid,
+    pub(super) rag_instance: crate::ai::rag::types::RAGInstanceInfo,
+    // === GRAPH ===
+    pub(super) graph_name: String,
+}
+
+/// Build the AGE graph name used for a RAG instance
+pub fn graph_name_for_instance(instance_id: Uuid) -> String {
+    format!("rag_graph_{}", instance_id.to_string().replace('-', "_"))
+}
+
+impl RAGSimpleGraphEngine {
+    pub async fn new(instance_id: Uuid) -> RAGResult<Self> {
+        let instance_info = crate::ai::rag::utils::get_rag_instance_info(instance_id)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to get RAG instance info: {}", e);
+                RAGErrorCode::Instance(RAGInstanceErrorCode::ConfigurationError)
+            })?;
+
+        let graph_name = instance_info
+            .instance
+            .age_graph_name
+            .clone()
+            .unwrap_or_else(|| graph_name_for_instance(instance_id));
+
+        Ok(Self {
+            // === INSTANCE ===
+            id: instance_id,
+            rag_instance: instance_info,
+            // === GRAPH ===
+            graph_name,
+        })
+    }
+
+    pub(super) async fn update_pipeline_status(
+        &self,
+        file_id: Uuid,
+        stage: PipelineStage,
+        status: ProcessingStatus,
+    ) -> RAGResult<()> {
+        crate::ai::rag::engines::simple_vector::queries::update_pipeline_status(
+            self.id, file_id, stage, status,
+        )
+        .await
+    }
+
+    /// Get engine settings, falling back to defaults when not configured
+    pub(super) fn settings(&self) -> RAGSimpleGraphEngineSettings {
+        self.rag_instance
+            .instance
+            .engine_settings
+            .simple_graph
+            .clone()
+            .unwrap_or(RAGSimpleGraphEngineSettings {
+                indexing: None,
+                querying: None,
+            })
+    }
+
+    /// Get the LLM model used for extraction and keyword generation
+    pub(super) fn llm_model(&self) -> RAGResult<&Arc<dyn AIModel>> {
+        self.rag_instance.models.llm_model.as_ref().ok_or_else(|| {
+            tracing::error!("No LLM model configured for graph RAG instance {}", self.id);
+            RAGErrorCode::Instance(RAGInstanceErrorCode::LlmModelNotConfig)
+        })
+    }
+
+    /// Get RAG instance info
+    pub fn rag_instance(&self) -> &crate::ai::rag::types::RAGInstanceInfo {
+        &self.rag_instance
+    }
+
+    /// Get the AGE graph name backing this instance
+    pub fn graph_name(&self) -> &str {
+        &self.graph_name
+    }
+}
+
+#[async_trait]
+impl RAGEngine for RAGSimpleGraphEngine {
+    fn engine_type(&self) -> RAGEngineType {
+        RAGEngineType::SimpleGraph
+    }
+
+    async fn process_file(&self, file_id: Uuid) -> RAGResult<()> {
+        self.process_file_impl(file_id).await
+    }
+
+    async fn initialize(&self, _settings: serde_json::Value) -> RAGResult<()> {
+        // Entity extraction cannot run without an LLM
+        self.llm_model()?;
+
+        // Make sure the per-instance AGE graph exists and is registered
+        queries::ensure_age_graph(self.id, &self.graph_name).await
+    }
+
+    async fn query(&self, query: RAGQuery) -> RAGResult<RAGQueryResponse> {
+        self.query_impl(query).await
+    }
+
+    async fn validate_configuration(&self, _settings: serde_json::Value) -> RAGResult<()> {
+        self.llm_model()?;
+        Ok(())
+    }
+
+    fn get_capabilities(&self) -> crate::ai::rag::engines::EngineCapabilities {
+        crate::ai::rag::engines::EngineCapabilities::for_engine_type(&RAGEngineType::SimpleGraph)
+    }
+}
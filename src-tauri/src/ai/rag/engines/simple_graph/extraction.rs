@@ -0,0 +1,255 @@
+// LLM prompts and response parsing for Simple Graph RAG Engine
+
+use serde::{Deserialize, Serialize};
+
+/// Entity extracted from a chunk by the LLM
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtractedEntity {
+    pub name: String,
+    #[serde(default, alias = "type")]
+    pub entity_type: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+/// Relationship extracted from a chunk by the LLM
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtractedRelationship {
+    #[serde(alias = "src_id", alias = "source_entity")]
+    pub source: String,
+    #[serde(alias = "tgt_id", alias = "target_entity")]
+    pub target: String,
+    #[serde(default, alias = "type", alias = "keywords")]
+    pub relationship_type: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default = "default_relationship_weight", alias = "strength")]
+    pub weight: f32,
+}
+
+fn default_relationship_weight() -> f32 {
+    1.0
+}
+
+/// Entities and relationships extracted from a single chunk
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExtractionResult {
+    #[serde(default)]
+    pub entities: Vec<ExtractedEntity>,
+    #[serde(default)]
+    pub relationships: Vec<ExtractedRelationship>,
+}
+
+/// Query keywords split into high-level (themes) and low-level (specific entities)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct QueryKeywords {
+    #[serde(default)]
+    pub high_level_keywords: Vec<String>,
+    #[serde(default)]
+    pub low_level_keywords: Vec<String>,
+}
+
+impl QueryKeywords {
+    pub fn is_empty(&self) -> bool {
+        self.high_level_keywords.is_empty() && self.low_level_keywords.is_empty()
+    }
+}
+
+/// Normalize an entity name so the same entity extracted from different chunks merges
+pub fn normalize_entity_name(name: &str) -> String {
+    name.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_matches(|c: char| c == '"' || c == '\'')
+        .to_uppercase()
+}
+
+/// Build the entity/relationship extraction prompt (LightRAG entity_extraction pattern)
+pub fn build_extraction_prompt(text: &str, entity_types: &[String], language: &str) -> String {
+    format!(
+        r#"-Goal-
+Given a text document and a list of entity types, identify all entities of those types in the text and all relationships among the identified entities.
+Use {language} as the output language for descriptions.
+
+-Entity types-
+[{entity_types}]
+
+-Steps-
+1. Identify all entities. For each entity provide:
+- name: name of the entity, capitalized as in the text
+- entity_type: one of the entity types listed above
+- description: comprehensive description of the entity's attributes and activities
+2. From the entities identified in step 1, identify all pairs of (source, target) entities that are clearly related. For each pair provide:
+- source: name of the source entity, as identified in step 1
+- target: name of the target entity, as identified in step 1
+- relationship_type: a short label for the relationship (e.g. "works_for", "located_in")
+- description: explanation of why the source and target entities are related
+- weight: a number between 1 and 10 indicating the strength of the relationship
+
+-Output format-
+Return ONLY a JSON object, without any explanation, in this exact shape:
+{{"entities": [{{"name": "", "entity_type": "", "description": ""}}], "relationships": [{{"source": "", "target": "", "relationship_type": "", "description": "", "weight": 1}}]}}
+
+-Text-
+{text}"#,
+        language = language,
+        entity_types = entity_types.join(", "),
+        text = text,
+    )
+}
+
+/// Follow-up prompt asking the LLM for entities it missed in the previous round
+pub const GLEANING_PROMPT: &str = "MANY entities and relationships were missed in the last extraction. \
+Add them below using the same JSON format. Return ONLY the JSON object with the newly found entities and relationships.";
+
+/// Build the prompt used to summarize a merged entity description
+pub fn build_summary_prompt(entity_name: &str, description: &str, language: &str) -> String {
+    format!(
+        r#"You are a helpful assistant responsible for generating a comprehensive summary of the data provided below.
+Given an entity and a list of descriptions, all related to the same entity, concatenate all of these into a single, comprehensive description in the third person.
+If the descriptions are contradictory, resolve the contradictions and provide a single, coherent summary.
+Use {language} as the output language. Return only the summary.
+
+Entity: {entity_name}
+Descriptions:
+{description}"#,
+        language = language,
+        entity_name = entity_name,
+        description = description,
+    )
+}
+
+/// Build the keyword extraction prompt used at query time
+pub fn build_keywords_prompt(query: &str) -> String {
+    format!(
+        r#"Identify both high-level and low-level keywords in the user's query.
+High-level keywords focus on overarching concepts or themes; low-level keywords focus on specific entities, details, or concrete terms.
+
+Return ONLY a JSON object in this exact shape:
+{{"high_level_keywords": [], "low_level_keywords": []}}
+
+Query: {query}"#,
+        query = query,
+    )
+}
+
+/// Extract the JSON object from an LLM response (strips code fences and surrounding prose)
+fn extract_json_object(output: &str) -> Option<&str> {
+    let start = output.find('{')?;
+    let end = output.rfind('}')?;
+    if end < start {
+        return None;
+    }
+    Some(&output[start..=end])
+}
+
+/// Parse entity/relationship extraction output, returning an empty result on malformed JSON
+pub fn parse_extraction_output(output: &str) -> ExtractionResult {
+    let Some(json) = extract_json_object(output) else {
+        tracing::warn!("Entity extraction output contained no JSON object");
+        return ExtractionResult::default();
+    };
+
+    match serde_json::from_str::<ExtractionResult>(json) {
+        Ok(mut result) => {
+            result.entities.retain(|e| !e.name.trim().is_empty());
+            result
+                .relationships
+                .retain(|r| !r.source.trim().is_empty() && !r.target.trim().is_empty());
+            result
+        }
+        Err(e) => {
+            tracing::warn!("Failed to parse entity extraction output: {}", e);
+            ExtractionResult::default()
+        }
+    }
+}
+
+/// Parse keyword extraction output, returning empty keywords on malformed JSON
+pub fn parse_keywords_output(output: &str) -> QueryKeywords {
+    extract_json_object(output)
+        .and_then(|json| serde_json::from_str::<QueryKeywords>(json).ok())
+        .unwrap_or_default()
+}
+
+/// Fallback keyword extraction when the LLM is unavailable or returns nothing useful
+pub fn fallback_keywords(query: &str) -> QueryKeywords {
+    let words: Vec<String> = query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.chars().count() > 2)
+        .map(|w| w.to_string())
+        .collect();
+
+    QueryKeywords {
+        high_level_keywords: words.clone(),
+        low_level_keywords: words,
+    }
+}
+
+/// Merge a gleaning round into the accumulated result, skipping duplicates
+pub fn merge_extraction(into: &mut ExtractionResult, other: ExtractionResult) {
+    for entity in other.entities {
+        let name = normalize_entity_name(&entity.name);
+        if !into
+            .entities
+            .iter()
+            .any(|e| normalize_entity_name(&e.name) == name)
+        {
+            into.entities.push(entity);
+        }
+    }
+
+    for relationship in other.relationships {
+        let source = normalize_entity_name(&relationship.source);
+        let target = normalize_entity_name(&relationship.target);
+        if !into.relationships.iter().any(|r| {
+            normalize_entity_name(&r.source) == source
+                && normalize_entity_name(&r.target) == target
+                && r.relationship_type == relationship.relationship_type
+        }) {
+            into.relationships.push(relationship);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_extraction_output_with_code_fence() {
+        let output = r#"Here is the result:
+```json
+{"entities": [{"name": "Alan Turing", "type": "person", "description": "Mathematician"},
+              {"name": "", "entity_type": "person"}],
+ "relationships": [{"source": "Alan Turing", "target": "Bletchley Park", "relationship_type": "worked_at", "strength": 8}]}
+```"#;
+
+        let result = parse_extraction_output(output);
+        assert_eq!(result.entities.len(), 1);
+        assert_eq!(result.entities[0].entity_type, "person");
+        assert_eq!(result.relationships.len(), 1);
+        assert_eq!(result.relationships[0].weight, 8.0);
+    }
+
+    #[test]
+    fn test_parse_extraction_output_malformed() {
+        let result = parse_extraction_output("no entities found");
+        assert!(result.entities.is_empty());
+        assert!(result.relationships.is_empty());
+    }
+
+    #[test]
+    fn test_merge_extraction_skips_duplicates() {
+        let mut base = parse_extraction_output(
+            r#"{"entities": [{"name": "Ada Lovelace", "entity_type": "person"}]}"#,
+        );
+        let gleaned = parse_extraction_output(
+            r#"{"entities": [{"name": "ada  lovelace"}, {"name": "Charles Babbage"}]}"#,
+        );
+
+        merge_extraction(&mut base, gleaned);
+        assert_eq!(base.entities.len(), 2);
+        assert_eq!(normalize_entity_name(&base.entities[1].name), "CHARLES BABBAGE");
+    }
+}
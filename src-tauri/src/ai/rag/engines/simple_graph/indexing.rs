@@ -0,0 +1,646 @@
+// File processing, entity extraction and graph storage for Simple Graph RAG Engine
+
+use super::core::graph_name_for_instance;
+use super::extraction::{
+    self, normalize_entity_name, ExtractedEntity, ExtractedRelationship, ExtractionResult,
+};
+use super::queries;
+use super::RAGSimpleGraphEngine;
+use crate::ai::core::providers::ChatMessage;
+use crate::ai::rag::engines::get_rag_file_storage;
+use crate::ai::rag::engines::settings::RAGChunkingStrategy;
+use crate::ai::rag::engines::simple_vector::overlap::chunk_markdown_structure;
+use crate::ai::rag::engines::simple_vector::queries as vector_queries;
+use crate::ai::rag::{
+    processors::{chunk::TokenBasedChunker, text},
+    types::TextChunk,
+    PipelineStage, ProcessingStatus, RAGErrorCode, RAGIndexingErrorCode, RAGInstanceErrorCode,
+    RAGResult,
+};
+use crate::ai::SimplifiedChatRequest;
+use futures::StreamExt;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Number of chunks sent to the embedding model per request
+const EMBEDDING_BATCH_SIZE: usize = 32;
+
+/// Number of chunks processed concurrently by the extraction LLM
+const EXTRACTION_CONCURRENCY: usize = 4;
+
+impl RAGSimpleGraphEngine {
+    /// Mark a pipeline stage as failed and return the original error
+    async fn fail_stage(
+        &self,
+        file_id: Uuid,
+        stage: PipelineStage,
+        error_msg: String,
+        error: RAGErrorCode,
+    ) -> RAGResult<()> {
+        tracing::error!("{}", error_msg);
+        self.update_pipeline_status(file_id, stage, ProcessingStatus::Failed(error_msg))
+            .await?;
+        Err(error)
+    }
+
+    /// Send a single prompt to the instance's LLM and return the text response
+    pub(super) async fn complete(&self, prompt: String) -> RAGResult<String> {
+        let llm_model = self.llm_model()?;
+
+        let response = llm_model
+            .chat(SimplifiedChatRequest {
+                messages: vec![ChatMessage::text("user", &prompt)],
+                stream: false,
+                tools: None,
+                parameters: None,
+            })
+            .await
+            .map_err(|e| {
+                tracing::error!("LLM completion failed for graph instance {}: {}", self.id, e);
+                RAGErrorCode::Indexing(RAGIndexingErrorCode::ProcessingError)
+            })?;
+
+        Ok(response.content)
+    }
+
+    /// Embed chunk contents so the graph instance also supports naive vector retrieval
+    async fn embed_chunks(&self, chunks: &[TextChunk]) -> RAGResult<Vec<Vec<f32>>> {
+        let mut all_embeddings = Vec::with_capacity(chunks.len());
+
+        for batch in chunks.chunks(EMBEDDING_BATCH_SIZE) {
+            let embedding_request = crate::ai::SimplifiedEmbeddingsRequest {
+                input: crate::ai::core::providers::EmbeddingsInput::Multiple(
+                    batch.iter().map(|c| c.content.clone()).collect(),
+                ),
+                encoding_format: Some("float".to_string()),
+                dimensions: None,
+            };
+
+            let response = self
+                .rag_instance
+                .models
+                .embedding_model
+                .embeddings(embedding_request)
+                .await
+                .map_err(|e| {
+                    tracing::error!("AI model embeddings error: {}", e);
+                    RAGErrorCode::Indexing(RAGIndexingErrorCode::EmbeddingGenerationFailed)
+                })?;
+
+            all_embeddings.extend(response.data.into_iter().map(|d| d.embedding));
+        }
+
+        if all_embeddings.len() != chunks.len() {
+            tracing::error!(
+                "Mismatch between chunks and embeddings count: {} vs {}",
+                chunks.len(),
+                all_embeddings.len()
+            );
+            return Err(RAGErrorCode::Indexing(
+                RAGIndexingErrorCode::EmbeddingGenerationFailed,
+            ));
+        }
+
+        Ok(all_embeddings)
+    }
+
+    /// Extract entities and relationships from one chunk, with gleaning rounds
+    async fn extract_from_chunk(&self, chunk: &TextChunk) -> RAGResult<ExtractionResult> {
+        let indexing_settings = self.settings().indexing();
+        let prompt = extraction::build_extraction_prompt(
+            &chunk.content,
+            &indexing_settings.entity_types(),
+            &indexing_settings.extraction_language(),
+        );
+
+        let llm_model = self.llm_model()?;
+        let mut messages = vec![ChatMessage::text("user", &prompt)];
+
+        let mut result = ExtractionResult::default();
+        for round in 0..=indexing_settings.entity_extract_max_gleaning() {
+            if round > 0 {
+                messages.push(ChatMessage::text("user", extraction::GLEANING_PROMPT));
+            }
+
+            let response = llm_model
+                .chat(SimplifiedChatRequest {
+                    messages: messages.clone(),
+                    stream: false,
+                    tools: None,
+                    parameters: None,
+                })
+                .await
+                .map_err(|e| {
+                    tracing::error!(
+                        "Entity extraction failed for chunk {}: {}",
+                        chunk.chunk_index,
+                        e
+                    );
+                    RAGErrorCode::Indexing(RAGIndexingErrorCode::ProcessingError)
+                })?;
+
+            let gleaned = extraction::parse_extraction_output(&response.content);
+            let found_new = !gleaned.entities.is_empty() || !gleaned.relationships.is_empty();
+            messages.push(ChatMessage::text("assistant", &response.content));
+            extraction::merge_extraction(&mut result, gleaned);
+
+            // Stop gleaning once the LLM has nothing more to add
+            if round > 0 && !found_new {
+                break;
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Merge an entity into the relational store and the AGE graph, summarizing when needed
+    async fn store_entity(&self, file_id: Uuid, entity: &ExtractedEntity) -> RAGResult<Uuid> {
+        let indexing_settings = self.settings().indexing();
+        let name = normalize_entity_name(&entity.name);
+
+        let merged = queries::upsert_entity(
+            self.id,
+            file_id,
+            &name,
+            &entity.entity_type,
+            &entity.description,
+        )
+        .await?;
+
+        let mut description = merged.description.unwrap_or_default();
+        if merged.description_fragments as usize >= indexing_settings.force_llm_summary_on_merge() {
+            let max_chars = indexing_settings.summary_max_tokens() * 4;
+            let source: String = description.chars().take(max_chars).collect();
+            match self
+                .complete(extraction::build_summary_prompt(
+                    &name,
+                    &source,
+                    &indexing_settings.extraction_language(),
+                ))
+                .await
+            {
+                Ok(summary) if !summary.trim().is_empty() => {
+                    description = summary.trim().to_string();
+                    queries::update_entity_description(merged.id, &description).await?;
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    // Keep the concatenated description when summarization fails
+                    tracing::warn!("Entity summary failed for {}: {}", name, e);
+                }
+            }
+        }
+
+        queries::merge_graph_entity(
+            &self.graph_name,
+            merged.id,
+            &name,
+            &entity.entity_type,
+            &description,
+        )
+        .await?;
+
+        Ok(merged.id)
+    }
+
+    /// Merge a relationship into the relational store and the AGE graph
+    async fn store_relationship(
+        &self,
+        file_id: Uuid,
+        relationship: &ExtractedRelationship,
+        entity_ids: &HashMap<String, Uuid>,
+    ) -> RAGResult<Option<Uuid>> {
+        let source = normalize_entity_name(&relationship.source);
+        let target = normalize_entity_name(&relationship.target);
+
+        let (Some(&source_id), Some(&target_id)) = (entity_ids.get(&source), entity_ids.get(&target))
+        else {
+            tracing::debug!(
+                "Skipping relationship {} -> {}: endpoint entity not stored",
+                source,
+                target
+            );
+            return Ok(None);
+        };
+
+        let relationship_type = if relationship.relationship_type.trim().is_empty() {
+            "related_to".to_string()
+        } else {
+            relationship.relationship_type.trim().to_lowercase()
+        };
+
+        let relationship_id = queries::upsert_relationship(
+            self.id,
+            file_id,
+            source_id,
+            target_id,
+            &relationship_type,
+            &relationship.description,
+            relationship.weight,
+        )
+        .await?;
+
+        queries::merge_graph_relationship(
+            &self.graph_name,
+            relationship_id,
+            source_id,
+            target_id,
+            &relationship_type,
+            &relationship.description,
+            relationship.weight,
+        )
+        .await?;
+
+        Ok(Some(relationship_id))
+    }
+
+    /// Store entities and relationships extracted from all chunks of a file, replacing what an
+    /// earlier version of the file contributed
+    async fn store_graph(
+        &self,
+        file_id: Uuid,
+        extractions: &[ExtractionResult],
+    ) -> RAGResult<(HashMap<String, Uuid>, usize)> {
+        remove_file_from_graph(self.id, file_id).await?;
+
+        let max_graph_nodes = self.settings().indexing().max_graph_nodes();
+        let mut node_count = queries::count_entities(self.id).await?;
+        let mut entity_ids: HashMap<String, Uuid> = HashMap::new();
+
+        for entity in extractions.iter().flat_map(|e| e.entities.iter()) {
+            let name = normalize_entity_name(&entity.name);
+            if entity_ids.contains_key(&name) {
+                // Merge additional descriptions from later chunks
+                self.store_entity(file_id, entity).await?;
+                continue;
+            }
+
+            if node_count >= max_graph_nodes {
+                tracing::warn!(
+                    "Graph for instance {} reached max_graph_nodes ({}), skipping entity {}",
+                    self.id,
+                    max_graph_nodes,
+                    name
+                );
+                continue;
+            }
+
+            let entity_id = self.store_entity(file_id, entity).await?;
+            entity_ids.insert(name, entity_id);
+            node_count += 1;
+        }
+
+        let mut relationship_count = 0;
+        for relationship in extractions.iter().flat_map(|e| e.relationships.iter()) {
+            if self
+                .store_relationship(file_id, relationship, &entity_ids)
+                .await?
+                .is_some()
+            {
+                relationship_count += 1;
+            }
+        }
+
+        Ok((entity_ids, relationship_count))
+    }
+
+    /// Complete file processing pipeline
+    pub async fn process_file_impl(&self, file_id: Uuid) -> RAGResult<()> {
+        let start_time = std::time::Instant::now();
+
+        // Make sure the AGE graph exists before writing vertices
+        queries::ensure_age_graph(self.id, &self.graph_name).await?;
+
+        let filename = self.get_filename_from_db(file_id).await?;
+        tracing::info!("Starting graph processing for file: {}", filename);
+
+        // Step 1: Text extraction
+        self.update_pipeline_status(
+            file_id,
+            PipelineStage::TextExtraction,
+            ProcessingStatus::InProgress,
+        )
+        .await?;
+
+        let extension = std::path::Path::new(&filename)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("txt");
+
+        let file_path = get_rag_file_storage().get_file_path(self.id, file_id, extension);
+
+        let Some(file_path_str) = file_path.to_str().filter(|_| file_path.exists()) else {
+            return self
+                .fail_stage(
+                    file_id,
+                    PipelineStage::TextExtraction,
+                    format!("File not found at path: {:?}", file_path),
+                    RAGErrorCode::Indexing(RAGIndexingErrorCode::FileReadError),
+                )
+                .await;
+        };
+
+        let processing_result = match text::extract_text_from_file(file_path_str).await {
+            Ok(result) => result,
+            Err(e) => {
+                let error_msg = format!("Text extraction failed for {}: {}", filename, e);
+                return self
+                    .fail_stage(file_id, PipelineStage::TextExtraction, error_msg, e)
+                    .await;
+            }
+        };
+
+        self.update_pipeline_status(
+            file_id,
+            PipelineStage::TextExtraction,
+            ProcessingStatus::Completed,
+        )
+        .await?;
+
+        let metadata_json =
+            serde_json::to_value(processing_result.metadata).unwrap_or(serde_json::Value::Null);
+        vector_queries::update_file_metadata(self.id, file_id, metadata_json).await?;
+
+        // Step 2: Chunking with the graph engine's chunk size
+        self.update_pipeline_status(file_id, PipelineStage::Chunking, ProcessingStatus::InProgress)
+            .await?;
+
+        let indexing_settings = self.settings().indexing();
+        let chunks = match indexing_settings.chunking_strategy() {
+            RAGChunkingStrategy::Structure => {
+                chunk_markdown_structure(
+                    self.rag_instance.models.embedding_model.as_ref(),
+                    &processing_result.markdown,
+                    indexing_settings.chunk_token_size(),
+                    indexing_settings.chunk_overlap_token_size(),
+                    indexing_settings
+                        .enable_semantic_split()
+                        .then(|| indexing_settings.semantic_split_threshold()),
+                )
+                .await
+            }
+            RAGChunkingStrategy::Token => {
+                TokenBasedChunker::new()
+                    .chunk(
+                        &processing_result.content,
+                        Some(indexing_settings.chunk_token_size()),
+                        Some(indexing_settings.chunk_overlap_token_size()),
+                        false,
+                        0.7,
+                    )
+                    .await
+            }
+        };
+        let chunks = match chunks {
+            Ok(chunks) => chunks,
+            Err(e) => {
+                let error_msg = format!("Text chunking failed for {}: {}", filename, e);
+                return self
+                    .fail_stage(file_id, PipelineStage::Chunking, error_msg, e)
+                    .await;
+            }
+        };
+
+        self.update_pipeline_status(file_id, PipelineStage::Chunking, ProcessingStatus::Completed)
+            .await?;
+
+        // Step 3: Embeddings for naive/mix retrieval
+        self.update_pipeline_status(file_id, PipelineStage::Embedding, ProcessingStatus::InProgress)
+            .await?;
+
+        let embeddings = match self.embed_chunks(&chunks).await {
+            Ok(embeddings) => embeddings,
+            Err(e) => {
+                let error_msg = format!("Embedding generation failed for {}: {}", filename, e);
+                return self
+                    .fail_stage(file_id, PipelineStage::Embedding, error_msg, e)
+                    .await;
+            }
+        };
+
+        self.update_pipeline_status(file_id, PipelineStage::Embedding, ProcessingStatus::Completed)
+            .await?;
+
+        // Step 4: Entity extraction with the instance LLM
+        self.update_pipeline_status(
+            file_id,
+            PipelineStage::EntityExtraction,
+            ProcessingStatus::InProgress,
+        )
+        .await?;
+
+        let extraction_results: Vec<RAGResult<ExtractionResult>> = futures::stream::iter(
+            chunks.iter().map(|chunk| self.extract_from_chunk(chunk)),
+        )
+        .buffered(EXTRACTION_CONCURRENCY)
+        .collect()
+        .await;
+
+        let mut extractions = Vec::with_capacity(extraction_results.len());
+        for result in extraction_results {
+            match result {
+                Ok(extraction) => extractions.push(extraction),
+                Err(e) => {
+                    let error_msg = format!("Entity extraction failed for {}: {}", filename, e);
+                    return self
+                        .fail_stage(file_id, PipelineStage::EntityExtraction, error_msg, e)
+                        .await;
+                }
+            }
+        }
+
+        self.update_pipeline_status(
+            file_id,
+            PipelineStage::EntityExtraction,
+            ProcessingStatus::Completed,
+        )
+        .await?;
+
+        // Step 5: Merge entities/relationships into the graph
+        self.update_pipeline_status(
+            file_id,
+            PipelineStage::RelationshipExtraction,
+            ProcessingStatus::InProgress,
+        )
+        .await?;
+
+        let stored = self.store_graph(file_id, &extractions).await;
+        let (entity_ids, relationship_count) = match stored {
+            Ok(stored) => stored,
+            Err(e) => {
+                let error_msg = format!("Graph storage failed for {}: {}", filename, e);
+                return self
+                    .fail_stage(file_id, PipelineStage::RelationshipExtraction, error_msg, e)
+                    .await;
+            }
+        };
+
+        self.update_pipeline_status(
+            file_id,
+            PipelineStage::RelationshipExtraction,
+            ProcessingStatus::Completed,
+        )
+        .await?;
+
+        // Step 6: Store chunks for both graph and vector retrieval
+        self.update_pipeline_status(file_id, PipelineStage::Indexing, ProcessingStatus::InProgress)
+            .await?;
+
+        if let Err(e) = self
+            .store_chunks(file_id, chunks, embeddings, &extractions)
+            .await
+        {
+            let error_msg = format!("Index storage failed for {}: {}", filename, e);
+            return self
+                .fail_stage(file_id, PipelineStage::Indexing, error_msg, e)
+                .await;
+        }
+
+        queries::update_graph_statistics(self.id, &self.graph_name).await?;
+
+        self.update_pipeline_status(file_id, PipelineStage::Indexing, ProcessingStatus::Completed)
+            .await?;
+
+        self.update_pipeline_status(file_id, PipelineStage::Completed, ProcessingStatus::Completed)
+            .await?;
+
+        tracing::info!(
+            "Processed file {} for graph instance {} in {:?}: {} entities, {} relationships",
+            filename,
+            self.id,
+            start_time.elapsed(),
+            entity_ids.len(),
+            relationship_count
+        );
+
+        Ok(())
+    }
+
+    /// Store graph chunks (with entity names) and their vector documents
+    async fn store_chunks(
+        &self,
+        file_id: Uuid,
+        chunks: Vec<TextChunk>,
+        embeddings: Vec<Vec<f32>>,
+        extractions: &[ExtractionResult],
+    ) -> RAGResult<()> {
+        queries::delete_graph_chunks_for_file(self.id, file_id).await?;
+
+        // Vector documents are upserted by chunk index; drop any left over from a longer version
+        let chunk_count = chunks.len();
+        vector_queries::delete_vector_documents_from_index(self.id, file_id, chunk_count as i32)
+            .await?;
+
+        for ((chunk, embedding), extraction) in chunks
+            .into_iter()
+            .zip(embeddings.into_iter())
+            .zip(extractions.iter())
+        {
+            let entity_names: Vec<String> = extraction
+                .entities
+                .iter()
+                .map(|e| normalize_entity_name(&e.name))
+                .collect();
+            let relationships: Vec<String> = extraction
+                .relationships
+                .iter()
+                .map(|r| {
+                    format!(
+                        "{} -> {}",
+                        normalize_entity_name(&r.source),
+                        normalize_entity_name(&r.target)
+                    )
+                })
+                .collect();
+
+            let mut metadata = chunk.metadata.clone();
+            metadata.insert("entities".to_string(), serde_json::json!(entity_names));
+            let metadata = serde_json::to_value(&metadata).unwrap_or_default();
+
+            queries::upsert_graph_chunk(
+                self.id,
+                file_id,
+                chunk.chunk_index as i32,
+                &chunk.content,
+                &chunk.content_hash,
+                chunk.token_count as i32,
+                serde_json::json!(entity_names),
+                serde_json::json!(relationships),
+                metadata.clone(),
+            )
+            .await?;
+
+            vector_queries::upsert_vector_document(
+                self.id,
+                file_id,
+                chunk.chunk_index as i32,
+                &chunk.content,
+                &chunk.content_hash,
+                chunk.token_count as i32,
+                &embedding,
+                metadata,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    pub(super) async fn get_filename_from_db(&self, file_id: Uuid) -> RAGResult<String> {
+        vector_queries::get_filename_from_db(file_id).await
+    }
+}
+
+/// Remove what a file contributed to an instance's graph before it is re-indexed or deleted.
+/// Entities and relationships only that file mentioned are deleted; the others are recomputed
+/// from the remaining files and written back to the AGE graph.
+pub async fn remove_file_from_graph(instance_id: Uuid, file_id: Uuid) -> RAGResult<()> {
+    let removed = queries::remove_file_contributions(instance_id, file_id).await?;
+    if removed.is_empty() {
+        return Ok(());
+    }
+
+    let graph_name = crate::database::queries::rag_instances::get_rag_instance_by_id(instance_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to load RAG instance {}: {}", instance_id, e);
+            RAGErrorCode::Instance(RAGInstanceErrorCode::DatabaseError)
+        })?
+        .and_then(|instance| instance.age_graph_name)
+        .unwrap_or_else(|| graph_name_for_instance(instance_id));
+
+    queries::delete_graph_elements(
+        &graph_name,
+        &removed.deleted_entity_ids,
+        &removed.deleted_relationship_ids,
+    )
+    .await?;
+
+    for entity in queries::get_entities_by_ids(instance_id, &removed.updated_entity_ids).await? {
+        queries::merge_graph_entity(
+            &graph_name,
+            entity.id,
+            &entity.name,
+            entity.entity_type.as_deref().unwrap_or_default(),
+            entity.description.as_deref().unwrap_or_default(),
+        )
+        .await?;
+    }
+
+    for relationship in
+        queries::get_relationships_by_ids(instance_id, &removed.updated_relationship_ids).await?
+    {
+        queries::merge_graph_relationship(
+            &graph_name,
+            relationship.id,
+            relationship.source_entity_id,
+            relationship.target_entity_id,
+            &relationship.relationship_type,
+            relationship.description.as_deref().unwrap_or_default(),
+            relationship.weight,
+        )
+        .await?;
+    }
+
+    queries::update_graph_statistics(instance_id, &graph_name).await
+}
@@ -0,0 +1,8 @@
+pub mod core; // Main RAGSimpleGraphEngine struct and basic methods
+pub mod extraction; // LLM prompts and parsers for entity/relationship/keyword extraction
+pub mod indexing; // File processing, entity extraction and graph storage
+pub mod queries; // Database and Apache AGE query functions
+pub mod querying; // Query processing methods (local/global/hybrid/naive/mix)
+
+// Re-export main engine for external use
+pub use core::RAGSimpleGraphEngine;
@@ -0,0 +1,1123 @@
+// Database and Apache AGE queries for Simple Graph RAG Engine
+
+use crate::ai::rag::engines::simple_vector::queries::FileFilterParams;
+use crate::ai::rag::models::{SimpleGraphChunk, SimpleGraphEntity, SimpleGraphRelationship};
+use crate::ai::rag::{
+    RAGErrorCode, RAGIndexingErrorCode, RAGInstanceErrorCode, RAGQueryFilter,
+    RAGQueryingErrorCode, RAGResult,
+};
+use crate::database::get_database_pool;
+use sqlx::Row;
+use uuid::Uuid;
+
+/// Result of merging an extracted entity into the entity table
+#[derive(Debug, Clone)]
+pub struct MergedEntity {
+    pub id: Uuid,
+    pub description: Option<String>,
+    pub description_fragments: i32,
+}
+
+/// Graph rows affected by dropping the contributions of one file
+#[derive(Debug, Clone, Default)]
+pub struct RemovedContributions {
+    /// Entities and relationships no other file still contributes to
+    pub deleted_entity_ids: Vec<Uuid>,
+    pub deleted_relationship_ids: Vec<Uuid>,
+    /// Entities and relationships recomputed from the remaining contributions
+    pub updated_entity_ids: Vec<Uuid>,
+    pub updated_relationship_ids: Vec<Uuid>,
+}
+
+impl RemovedContributions {
+    pub fn is_empty(&self) -> bool {
+        self.deleted_entity_ids.is_empty()
+            && self.deleted_relationship_ids.is_empty()
+            && self.updated_entity_ids.is_empty()
+            && self.updated_relationship_ids.is_empty()
+    }
+}
+
+/// Escape a value for use inside a single-quoted cypher string literal. The query is itself
+/// dollar-quoted SQL, so every `$` is dropped: removing only the `$cypher$` tag lets nested tags
+/// such as `$cyp$cypher$her$` rebuild it and close the quote.
+pub fn escape_cypher_string(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('\'', "\\'")
+        .replace(['\n', '\r'], " ")
+        .replace('$', "")
+}
+
+/// Escape LIKE wildcards and wrap the keyword for a substring ILIKE match
+fn like_pattern(keyword: &str) -> String {
+    let escaped = keyword
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{}%", escaped)
+}
+
+/// Strip the surrounding quotes agtype adds when casting a string value to text
+fn agtype_to_string(value: &str) -> String {
+    value.trim().trim_matches('"').to_string()
+}
+
+/// Create the AGE graph for an instance if needed and register it in age_graphs
+pub async fn ensure_age_graph(instance_id: Uuid, graph_name: &str) -> RAGResult<()> {
+    let database = get_database_pool().map_err(|e| {
+        tracing::error!("Failed to get database pool for AGE graph setup: {}", e);
+        RAGErrorCode::Instance(RAGInstanceErrorCode::DatabaseError)
+    })?;
+
+    sqlx::query!("SELECT create_age_graph($1)", graph_name)
+        .fetch_one(&*database)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to create AGE graph {}: {}", graph_name, e);
+            RAGErrorCode::Instance(RAGInstanceErrorCode::DatabaseError)
+        })?;
+
+    sqlx::query!(
+        "UPDATE rag_instances SET age_graph_name = $2 WHERE id = $1 AND age_graph_name IS DISTINCT FROM $2",
+        instance_id,
+        graph_name
+    )
+    .execute(&*database)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to store AGE graph name for instance {}: {}", instance_id, e);
+        RAGErrorCode::Instance(RAGInstanceErrorCode::DatabaseError)
+    })?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO age_graphs (rag_instance_id, graph_name, status)
+        VALUES ($1, $2, 'active')
+        ON CONFLICT (graph_name) DO UPDATE SET status = 'active', last_updated = NOW()
+        "#,
+        instance_id,
+        graph_name
+    )
+    .execute(&*database)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to register AGE graph {}: {}", graph_name, e);
+        RAGErrorCode::Instance(RAGInstanceErrorCode::DatabaseError)
+    })?;
+
+    Ok(())
+}
+
+/// Refresh node/edge counts for an instance's AGE graph
+pub async fn update_graph_statistics(instance_id: Uuid, graph_name: &str) -> RAGResult<()> {
+    let database = get_database_pool().map_err(|e| {
+        tracing::error!("Failed to get database pool for graph statistics: {}", e);
+        RAGErrorCode::Instance(RAGInstanceErrorCode::DatabaseError)
+    })?;
+
+    sqlx::query!(
+        r#"
+        UPDATE age_graphs SET
+            node_count = (SELECT COUNT(*) FROM simple_graph_entities WHERE rag_instance_id = $1),
+            edge_count = (SELECT COUNT(*) FROM simple_graph_relationships WHERE rag_instance_id = $1),
+            last_updated = NOW()
+        WHERE graph_name = $2
+        "#,
+        instance_id,
+        graph_name
+    )
+    .execute(&*database)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to update statistics for AGE graph {}: {}", graph_name, e);
+        RAGErrorCode::Instance(RAGInstanceErrorCode::DatabaseError)
+    })?;
+
+    Ok(())
+}
+
+/// Count entities currently stored for an instance
+pub async fn count_entities(instance_id: Uuid) -> RAGResult<usize> {
+    let database = get_database_pool().map_err(|e| {
+        tracing::error!("Failed to get database pool for entity count: {}", e);
+        RAGErrorCode::Instance(RAGInstanceErrorCode::DatabaseError)
+    })?;
+
+    let count = sqlx::query_scalar!(
+        "SELECT COUNT(*) FROM simple_graph_entities WHERE rag_instance_id = $1",
+        instance_id
+    )
+    .fetch_one(&*database)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to count entities for instance {}: {}", instance_id, e);
+        RAGErrorCode::Instance(RAGInstanceErrorCode::DatabaseError)
+    })?;
+
+    Ok(count.unwrap_or(0) as usize)
+}
+
+/// Remove graph chunks of a file before it is re-processed
+pub async fn delete_graph_chunks_for_file(instance_id: Uuid, file_id: Uuid) -> RAGResult<()> {
+    let database = get_database_pool().map_err(|e| {
+        tracing::error!("Failed to get database pool for graph chunk cleanup: {}", e);
+        RAGErrorCode::Instance(RAGInstanceErrorCode::DatabaseError)
+    })?;
+
+    sqlx::query!(
+        "DELETE FROM simple_graph_chunks WHERE rag_instance_id = $1 AND file_id = $2",
+        instance_id,
+        file_id
+    )
+    .execute(&*database)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to delete graph chunks for file {}: {}", file_id, e);
+        RAGErrorCode::Instance(RAGInstanceErrorCode::DatabaseError)
+    })?;
+
+    Ok(())
+}
+
+/// Insert or update a graph chunk with the entities/relationships extracted from it
+pub async fn upsert_graph_chunk(
+    instance_id: Uuid,
+    file_id: Uuid,
+    chunk_index: i32,
+    content: &str,
+    content_hash: &str,
+    token_count: i32,
+    entities: serde_json::Value,
+    relationships: serde_json::Value,
+    metadata: serde_json::Value,
+) -> RAGResult<()> {
+    let database = get_database_pool().map_err(|e| {
+        tracing::error!("Failed to get database pool for graph chunk upsert: {}", e);
+        RAGErrorCode::Instance(RAGInstanceErrorCode::DatabaseError)
+    })?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO simple_graph_chunks (
+            rag_instance_id, file_id, chunk_index, content, content_hash,
+            token_count, entities, relationships, metadata
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        ON CONFLICT (rag_instance_id, file_id, chunk_index)
+        DO UPDATE SET
+            content = EXCLUDED.content,
+            content_hash = EXCLUDED.content_hash,
+            token_count = EXCLUDED.token_count,
+            entities = EXCLUDED.entities,
+            relationships = EXCLUDED.relationships,
+            metadata = EXCLUDED.metadata,
+            updated_at = NOW()
+        "#,
+        instance_id,
+        file_id,
+        chunk_index,
+        content,
+        content_hash,
+        token_count,
+        entities,
+        relationships,
+        metadata
+    )
+    .execute(&*database)
+    .await
+    .map_err(|e| {
+        tracing::error!(
+            "Failed to upsert graph chunk {} for file {}: {}",
+            chunk_index,
+            file_id,
+            e
+        );
+        RAGErrorCode::Indexing(RAGIndexingErrorCode::ProcessingError)
+    })?;
+
+    Ok(())
+}
+
+/// Insert an entity or merge it into the existing one with the same name
+pub async fn upsert_entity(
+    instance_id: Uuid,
+    file_id: Uuid,
+    name: &str,
+    entity_type: &str,
+    description: &str,
+) -> RAGResult<MergedEntity> {
+    let database = get_database_pool().map_err(|e| {
+        tracing::error!("Failed to get database pool for entity upsert: {}", e);
+        RAGErrorCode::Instance(RAGInstanceErrorCode::DatabaseError)
+    })?;
+
+    let description = if description.trim().is_empty() {
+        None
+    } else {
+        Some(description.trim())
+    };
+    let entity_type = if entity_type.trim().is_empty() {
+        None
+    } else {
+        Some(entity_type.trim().to_lowercase())
+    };
+
+    let mut tx = database.begin().await.map_err(|e| {
+        tracing::error!("Failed to begin entity upsert transaction: {}", e);
+        RAGErrorCode::Instance(RAGInstanceErrorCode::DatabaseError)
+    })?;
+
+    // Descriptions from different chunks are appended; the fragment counter drives LLM summarization
+    let row = sqlx::query!(
+        r#"
+        INSERT INTO simple_graph_entities (
+            rag_instance_id, name, entity_type, description, importance_score, extraction_metadata
+        ) VALUES ($1, $2, $3, $4, 1.0, jsonb_build_object('description_fragments', 1))
+        ON CONFLICT (rag_instance_id, name)
+        DO UPDATE SET
+            entity_type = COALESCE(simple_graph_entities.entity_type, EXCLUDED.entity_type),
+            description = CASE
+                WHEN simple_graph_entities.description IS NULL OR simple_graph_entities.description = ''
+                    THEN EXCLUDED.description
+                WHEN EXCLUDED.description IS NULL
+                    OR position(EXCLUDED.description in simple_graph_entities.description) > 0
+                    THEN simple_graph_entities.description
+                ELSE simple_graph_entities.description || E'\n' || EXCLUDED.description
+            END,
+            importance_score = COALESCE(simple_graph_entities.importance_score, 0.0) + 1.0,
+            extraction_metadata = jsonb_set(
+                COALESCE(simple_graph_entities.extraction_metadata, '{}'::jsonb),
+                '{description_fragments}',
+                to_jsonb(
+                    COALESCE((simple_graph_entities.extraction_metadata->>'description_fragments')::int, 1)
+                    + CASE
+                        WHEN EXCLUDED.description IS NULL
+                            OR simple_graph_entities.description IS NULL
+                            OR position(EXCLUDED.description in simple_graph_entities.description) > 0
+                            THEN 0
+                        ELSE 1
+                    END
+                )
+            ),
+            updated_at = NOW()
+        RETURNING id, description,
+            COALESCE((extraction_metadata->>'description_fragments')::int, 1) as "description_fragments!"
+        "#,
+        instance_id,
+        name,
+        entity_type,
+        description
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to upsert entity {}: {}", name, e);
+        RAGErrorCode::Indexing(RAGIndexingErrorCode::ProcessingError)
+    })?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO simple_graph_contributions (rag_instance_id, file_id, entity_id, description)
+        VALUES ($1, $2, $3, $4)
+        "#,
+        instance_id,
+        file_id,
+        row.id,
+        description
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| {
+        tracing::error!(
+            "Failed to record contribution of file {} to entity {}: {}",
+            file_id,
+            name,
+            e
+        );
+        RAGErrorCode::Indexing(RAGIndexingErrorCode::ProcessingError)
+    })?;
+
+    tx.commit().await.map_err(|e| {
+        tracing::error!("Failed to commit upsert of entity {}: {}", name, e);
+        RAGErrorCode::Indexing(RAGIndexingErrorCode::ProcessingError)
+    })?;
+
+    Ok(MergedEntity {
+        id: row.id,
+        description: row.description,
+        description_fragments: row.description_fragments,
+    })
+}
+
+/// Replace an entity description with an LLM summary and reset its fragment counter
+pub async fn update_entity_description(entity_id: Uuid, description: &str) -> RAGResult<()> {
+    let database = get_database_pool().map_err(|e| {
+        tracing::error!("Failed to get database pool for entity description update: {}", e);
+        RAGErrorCode::Instance(RAGInstanceErrorCode::DatabaseError)
+    })?;
+
+    sqlx::query!(
+        r#"
+        UPDATE simple_graph_entities SET
+            description = $2,
+            extraction_metadata = jsonb_set(
+                COALESCE(extraction_metadata, '{}'::jsonb), '{description_fragments}', '1'::jsonb
+            ),
+            updated_at = NOW()
+        WHERE id = $1
+        "#,
+        entity_id,
+        description
+    )
+    .execute(&*database)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to update description for entity {}: {}", entity_id, e);
+        RAGErrorCode::Indexing(RAGIndexingErrorCode::ProcessingError)
+    })?;
+
+    Ok(())
+}
+
+/// Insert a relationship or merge it into the existing one between the same entities
+pub async fn upsert_relationship(
+    instance_id: Uuid,
+    file_id: Uuid,
+    source_entity_id: Uuid,
+    target_entity_id: Uuid,
+    relationship_type: &str,
+    description: &str,
+    weight: f32,
+) -> RAGResult<Uuid> {
+    let database = get_database_pool().map_err(|e| {
+        tracing::error!("Failed to get database pool for relationship upsert: {}", e);
+        RAGErrorCode::Instance(RAGInstanceErrorCode::DatabaseError)
+    })?;
+
+    let description = if description.trim().is_empty() {
+        None
+    } else {
+        Some(description.trim())
+    };
+
+    let mut tx = database.begin().await.map_err(|e| {
+        tracing::error!("Failed to begin relationship upsert transaction: {}", e);
+        RAGErrorCode::Instance(RAGInstanceErrorCode::DatabaseError)
+    })?;
+
+    let relationship_id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO simple_graph_relationships (
+            rag_instance_id, source_entity_id, target_entity_id, relationship_type, description, weight
+        ) VALUES ($1, $2, $3, $4, $5, $6)
+        ON CONFLICT (rag_instance_id, source_entity_id, target_entity_id, relationship_type)
+        DO UPDATE SET
+            description = CASE
+                WHEN simple_graph_relationships.description IS NULL THEN EXCLUDED.description
+                WHEN EXCLUDED.description IS NULL
+                    OR position(EXCLUDED.description in simple_graph_relationships.description) > 0
+                    THEN simple_graph_relationships.description
+                ELSE simple_graph_relationships.description || E'\n' || EXCLUDED.description
+            END,
+            weight = COALESCE(simple_graph_relationships.weight, 0.0) + EXCLUDED.weight,
+            updated_at = NOW()
+        RETURNING id
+        "#,
+        instance_id,
+        source_entity_id,
+        target_entity_id,
+        relationship_type,
+        description,
+        weight as f64
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| {
+        tracing::error!(
+            "Failed to upsert relationship {} -> {}: {}",
+            source_entity_id,
+            target_entity_id,
+            e
+        );
+        RAGErrorCode::Indexing(RAGIndexingErrorCode::ProcessingError)
+    })?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO simple_graph_contributions (
+            rag_instance_id, file_id, relationship_id, description, weight
+        ) VALUES ($1, $2, $3, $4, $5)
+        "#,
+        instance_id,
+        file_id,
+        relationship_id,
+        description,
+        weight as f64
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| {
+        tracing::error!(
+            "Failed to record contribution of file {} to relationship {}: {}",
+            file_id,
+            relationship_id,
+            e
+        );
+        RAGErrorCode::Indexing(RAGIndexingErrorCode::ProcessingError)
+    })?;
+
+    tx.commit().await.map_err(|e| {
+        tracing::error!("Failed to commit upsert of relationship {}: {}", relationship_id, e);
+        RAGErrorCode::Indexing(RAGIndexingErrorCode::ProcessingError)
+    })?;
+
+    Ok(relationship_id)
+}
+
+/// Drop the contributions of a file and recompute the entities and relationships it touched.
+/// Rows left without any contribution are deleted, together with relationships of deleted
+/// entities.
+pub async fn remove_file_contributions(
+    instance_id: Uuid,
+    file_id: Uuid,
+) -> RAGResult<RemovedContributions> {
+    let database = get_database_pool().map_err(|e| {
+        tracing::error!("Failed to get database pool for graph contribution cleanup: {}", e);
+        RAGErrorCode::Instance(RAGInstanceErrorCode::DatabaseError)
+    })?;
+
+    let map_err = |e: sqlx::Error| {
+        tracing::error!("Failed to remove graph contributions of file {}: {}", file_id, e);
+        RAGErrorCode::Indexing(RAGIndexingErrorCode::ProcessingError)
+    };
+
+    let mut tx = database.begin().await.map_err(map_err)?;
+
+    let removed = sqlx::query!(
+        r#"
+        DELETE FROM simple_graph_contributions
+        WHERE rag_instance_id = $1 AND file_id = $2
+        RETURNING entity_id, relationship_id
+        "#,
+        instance_id,
+        file_id
+    )
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(map_err)?;
+
+    let mut entity_ids: Vec<Uuid> = removed.iter().filter_map(|r| r.entity_id).collect();
+    let mut relationship_ids: Vec<Uuid> = removed.iter().filter_map(|r| r.relationship_id).collect();
+    entity_ids.sort_unstable();
+    entity_ids.dedup();
+    relationship_ids.sort_unstable();
+    relationship_ids.dedup();
+
+    if entity_ids.is_empty() && relationship_ids.is_empty() {
+        return Ok(RemovedContributions::default());
+    }
+
+    let deleted_entity_ids = sqlx::query_scalar!(
+        r#"
+        DELETE FROM simple_graph_entities e
+        WHERE e.id = ANY($1)
+            AND NOT EXISTS (SELECT 1 FROM simple_graph_contributions c WHERE c.entity_id = e.id)
+        RETURNING e.id
+        "#,
+        &entity_ids
+    )
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(map_err)?;
+
+    let deleted_relationship_ids = sqlx::query_scalar!(
+        r#"
+        DELETE FROM simple_graph_relationships r
+        WHERE r.rag_instance_id = $1
+            AND (
+                (r.id = ANY($2)
+                    AND NOT EXISTS (
+                        SELECT 1 FROM simple_graph_contributions c WHERE c.relationship_id = r.id
+                    ))
+                OR r.source_entity_id = ANY($3)
+                OR r.target_entity_id = ANY($3)
+            )
+        RETURNING r.id
+        "#,
+        instance_id,
+        &relationship_ids,
+        &deleted_entity_ids
+    )
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(map_err)?;
+
+    // Importance counts the remaining mentions, descriptions are rebuilt from their fragments
+    let updated_entity_ids = sqlx::query_scalar!(
+        r#"
+        UPDATE simple_graph_entities e SET
+            description = c.description,
+            importance_score = c.mentions,
+            extraction_metadata = jsonb_set(
+                COALESCE(e.extraction_metadata, '{}'::jsonb),
+                '{description_fragments}',
+                to_jsonb(GREATEST(c.fragments, 1))
+            ),
+            updated_at = NOW()
+        FROM (
+            SELECT entity_id,
+                COUNT(*)::float AS mentions,
+                string_agg(DISTINCT description, E'\n') AS description,
+                COUNT(DISTINCT description)::int AS fragments
+            FROM simple_graph_contributions
+            WHERE entity_id = ANY($1)
+            GROUP BY entity_id
+        ) c
+        WHERE e.id = c.entity_id
+        RETURNING e.id
+        "#,
+        &entity_ids
+    )
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(map_err)?;
+
+    let updated_relationship_ids = sqlx::query_scalar!(
+        r#"
+        UPDATE simple_graph_relationships r SET
+            description = c.description,
+            weight = c.weight,
+            updated_at = NOW()
+        FROM (
+            SELECT relationship_id,
+                SUM(weight) AS weight,
+                string_agg(DISTINCT description, E'\n') AS description
+            FROM simple_graph_contributions
+            WHERE relationship_id = ANY($1)
+            GROUP BY relationship_id
+        ) c
+        WHERE r.id = c.relationship_id
+        RETURNING r.id
+        "#,
+        &relationship_ids
+    )
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(map_err)?;
+
+    tx.commit().await.map_err(map_err)?;
+
+    Ok(RemovedContributions {
+        deleted_entity_ids,
+        deleted_relationship_ids,
+        updated_entity_ids,
+        updated_relationship_ids,
+    })
+}
+
+/// Delete entity vertices (with their edges) and relationship edges from the AGE graph
+pub async fn delete_graph_elements(
+    graph_name: &str,
+    entity_ids: &[Uuid],
+    relationship_ids: &[Uuid],
+) -> RAGResult<()> {
+    if entity_ids.is_empty() && relationship_ids.is_empty() {
+        return Ok(());
+    }
+
+    let database = get_database_pool().map_err(|e| {
+        tracing::error!("Failed to get database pool for graph element removal: {}", e);
+        RAGErrorCode::Instance(RAGInstanceErrorCode::DatabaseError)
+    })?;
+
+    let id_list = |ids: &[Uuid]| {
+        ids.iter()
+            .map(|id| format!("'{}'", id))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    if !relationship_ids.is_empty() {
+        let cypher = format!(
+            r#"SELECT * FROM cypher('{graph}', $cypher$
+                MATCH ()-[r:RELATED]->()
+                WHERE r.relationship_id IN [{ids}]
+                DELETE r
+            $cypher$) AS (r agtype)"#,
+            graph = graph_name,
+            ids = id_list(relationship_ids),
+        );
+
+        sqlx::query(&cypher)
+            .execute(&*database)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to delete edges from AGE graph {}: {}", graph_name, e);
+                RAGErrorCode::Indexing(RAGIndexingErrorCode::ProcessingError)
+            })?;
+    }
+
+    if !entity_ids.is_empty() {
+        let cypher = format!(
+            r#"SELECT * FROM cypher('{graph}', $cypher$
+                MATCH (n:Entity)
+                WHERE n.entity_id IN [{ids}]
+                DETACH DELETE n
+            $cypher$) AS (n agtype)"#,
+            graph = graph_name,
+            ids = id_list(entity_ids),
+        );
+
+        sqlx::query(&cypher)
+            .execute(&*database)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to delete vertices from AGE graph {}: {}", graph_name, e);
+                RAGErrorCode::Indexing(RAGIndexingErrorCode::ProcessingError)
+            })?;
+    }
+
+    Ok(())
+}
+
+/// MERGE an entity vertex into the AGE graph
+pub async fn merge_graph_entity(
+    graph_name: &str,
+    entity_id: Uuid,
+    name: &str,
+    entity_type: &str,
+    description: &str,
+) -> RAGResult<()> {
+    let database = get_database_pool().map_err(|e| {
+        tracing::error!("Failed to get database pool for graph vertex merge: {}", e);
+        RAGErrorCode::Instance(RAGInstanceErrorCode::DatabaseError)
+    })?;
+
+    let cypher = format!(
+        r#"SELECT * FROM cypher('{graph}', $cypher$
+            MERGE (n:Entity {{entity_id: '{id}'}})
+            SET n.name = '{name}', n.entity_type = '{entity_type}', n.description = '{description}'
+            RETURN n
+        $cypher$) AS (n agtype)"#,
+        graph = graph_name,
+        id = entity_id,
+        name = escape_cypher_string(name),
+        entity_type = escape_cypher_string(entity_type),
+        description = escape_cypher_string(description),
+    );
+
+    sqlx::query(&cypher)
+        .execute(&*database)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to merge entity {} into AGE graph: {}", entity_id, e);
+            RAGErrorCode::Indexing(RAGIndexingErrorCode::ProcessingError)
+        })?;
+
+    Ok(())
+}
+
+/// MERGE a relationship edge between two entity vertices in the AGE graph
+pub async fn merge_graph_relationship(
+    graph_name: &str,
+    relationship_id: Uuid,
+    source_entity_id: Uuid,
+    target_entity_id: Uuid,
+    relationship_type: &str,
+    description: &str,
+    weight: f32,
+) -> RAGResult<()> {
+    let database = get_database_pool().map_err(|e| {
+        tracing::error!("Failed to get database pool for graph edge merge: {}", e);
+        RAGErrorCode::Instance(RAGInstanceErrorCode::DatabaseError)
+    })?;
+
+    let cypher = format!(
+        r#"SELECT * FROM cypher('{graph}', $cypher$
+            MATCH (a:Entity {{entity_id: '{source}'}}), (b:Entity {{entity_id: '{target}'}})
+            MERGE (a)-[r:RELATED {{relationship_id: '{id}'}}]->(b)
+            SET r.relationship_type = '{relationship_type}', r.description = '{description}', r.weight = {weight}
+            RETURN r
+        $cypher$) AS (r agtype)"#,
+        graph = graph_name,
+        source = source_entity_id,
+        target = target_entity_id,
+        id = relationship_id,
+        relationship_type = escape_cypher_string(relationship_type),
+        description = escape_cypher_string(description),
+        weight = weight,
+    );
+
+    sqlx::query(&cypher)
+        .execute(&*database)
+        .await
+        .map_err(|e| {
+            tracing::error!(
+                "Failed to merge relationship {} into AGE graph: {}",
+                relationship_id,
+                e
+            );
+            RAGErrorCode::Indexing(RAGIndexingErrorCode::ProcessingError)
+        })?;
+
+    Ok(())
+}
+
+/// Expand one hop around the given entities in the AGE graph.
+/// Returns (neighbor entity id, relationship id) pairs.
+pub async fn get_graph_neighbors(
+    graph_name: &str,
+    entity_ids: &[Uuid],
+    limit: usize,
+) -> RAGResult<Vec<(Uuid, Uuid)>> {
+    if entity_ids.is_empty() || limit == 0 {
+        return Ok(vec![]);
+    }
+
+    let database = get_database_pool().map_err(|e| {
+        tracing::error!("Failed to get database pool for graph traversal: {}", e);
+        RAGErrorCode::Querying(RAGQueryingErrorCode::SearchIndexUnavailable)
+    })?;
+
+    let id_list = entity_ids
+        .iter()
+        .map(|id| format!("'{}'", id))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let cypher = format!(
+        r#"SELECT neighbor_id::text, relationship_id::text FROM cypher('{graph}', $cypher$
+            MATCH (a:Entity)-[r:RELATED]-(b:Entity)
+            WHERE a.entity_id IN [{ids}]
+            RETURN b.entity_id, r.relationship_id
+            ORDER BY r.weight DESC
+            LIMIT {limit}
+        $cypher$) AS (neighbor_id agtype, relationship_id agtype)"#,
+        graph = graph_name,
+        ids = id_list,
+        limit = limit,
+    );
+
+    let rows = sqlx::query(&cypher)
+        .fetch_all(&*database)
+        .await
+        .map_err(|e| {
+            tracing::error!("AGE neighbor traversal failed on graph {}: {}", graph_name, e);
+            RAGErrorCode::Querying(RAGQueryingErrorCode::SearchIndexUnavailable)
+        })?;
+
+    let neighbors = rows
+        .iter()
+        .filter_map(|row| {
+            let neighbor: String = row.try_get(0).ok()?;
+            let relationship: String = row.try_get(1).ok()?;
+            Some((
+                Uuid::parse_str(&agtype_to_string(&neighbor)).ok()?,
+                Uuid::parse_str(&agtype_to_string(&relationship)).ok()?,
+            ))
+        })
+        .collect();
+
+    Ok(neighbors)
+}
+
+/// Find entities whose name or description matches any of the keywords
+pub async fn find_entities_by_keywords(
+    instance_id: Uuid,
+    keywords: &[String],
+    limit: usize,
+) -> RAGResult<Vec<SimpleGraphEntity>> {
+    if keywords.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let database = get_database_pool().map_err(|e| {
+        tracing::error!("Failed to get database pool for entity search: {}", e);
+        RAGErrorCode::Querying(RAGQueryingErrorCode::SearchIndexUnavailable)
+    })?;
+
+    let patterns: Vec<String> = keywords.iter().map(|k| like_pattern(k)).collect();
+
+    let rows = sqlx::query!(
+        r#"
+        SELECT id, rag_instance_id, name, entity_type, description, importance_score,
+               extraction_metadata, created_at, updated_at
+        FROM simple_graph_entities
+        WHERE rag_instance_id = $1
+          AND (name ILIKE ANY($2) OR description ILIKE ANY($2))
+        ORDER BY (name ILIKE ANY($2)) DESC, importance_score DESC NULLS LAST
+        LIMIT $3
+        "#,
+        instance_id,
+        &patterns[..],
+        limit as i64
+    )
+    .fetch_all(&*database)
+    .await
+    .map_err(|e| {
+        tracing::error!("Entity keyword search failed for instance {}: {}", instance_id, e);
+        RAGErrorCode::Querying(RAGQueryingErrorCode::SearchIndexUnavailable)
+    })?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| SimpleGraphEntity {
+            id: row.id,
+            rag_instance_id: row.rag_instance_id,
+            name: row.name,
+            entity_type: row.entity_type,
+            description: row.description,
+            importance_score: row.importance_score.unwrap_or(0.0) as f32,
+            extraction_metadata: row.extraction_metadata.unwrap_or_default(),
+            created_at: row.created_at.unwrap_or_default(),
+            updated_at: row.updated_at.unwrap_or_default(),
+        })
+        .collect())
+}
+
+/// Load entities by id
+pub async fn get_entities_by_ids(
+    instance_id: Uuid,
+    entity_ids: &[Uuid],
+) -> RAGResult<Vec<SimpleGraphEntity>> {
+    if entity_ids.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let database = get_database_pool().map_err(|e| {
+        tracing::error!("Failed to get database pool for entity lookup: {}", e);
+        RAGErrorCode::Querying(RAGQueryingErrorCode::SearchIndexUnavailable)
+    })?;
+
+    let rows = sqlx::query!(
+        r#"
+        SELECT id, rag_instance_id, name, entity_type, description, importance_score,
+               extraction_metadata, created_at, updated_at
+        FROM simple_graph_entities
+        WHERE rag_instance_id = $1 AND id = ANY($2)
+        ORDER BY importance_score DESC NULLS LAST
+        "#,
+        instance_id,
+        entity_ids
+    )
+    .fetch_all(&*database)
+    .await
+    .map_err(|e| {
+        tracing::error!("Entity lookup failed for instance {}: {}", instance_id, e);
+        RAGErrorCode::Querying(RAGQueryingErrorCode::SearchIndexUnavailable)
+    })?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| SimpleGraphEntity {
+            id: row.id,
+            rag_instance_id: row.rag_instance_id,
+            name: row.name,
+            entity_type: row.entity_type,
+            description: row.description,
+            importance_score: row.importance_score.unwrap_or(0.0) as f32,
+            extraction_metadata: row.extraction_metadata.unwrap_or_default(),
+            created_at: row.created_at.unwrap_or_default(),
+            updated_at: row.updated_at.unwrap_or_default(),
+        })
+        .collect())
+}
+
+/// Find relationships whose type or description matches any of the keywords
+pub async fn find_relationships_by_keywords(
+    instance_id: Uuid,
+    keywords: &[String],
+    limit: usize,
+) -> RAGResult<Vec<SimpleGraphRelationship>> {
+    if keywords.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let database = get_database_pool().map_err(|e| {
+        tracing::error!("Failed to get database pool for relationship search: {}", e);
+        RAGErrorCode::Querying(RAGQueryingErrorCode::SearchIndexUnavailable)
+    })?;
+
+    let patterns: Vec<String> = keywords.iter().map(|k| like_pattern(k)).collect();
+
+    let rows = sqlx::query!(
+        r#"
+        SELECT id, rag_instance_id, source_entity_id, target_entity_id, relationship_type,
+               description, weight, extraction_metadata, created_at, updated_at
+        FROM simple_graph_relationships
+        WHERE rag_instance_id = $1
+          AND (relationship_type ILIKE ANY($2) OR description ILIKE ANY($2))
+        ORDER BY weight DESC NULLS LAST
+        LIMIT $3
+        "#,
+        instance_id,
+        &patterns[..],
+        limit as i64
+    )
+    .fetch_all(&*database)
+    .await
+    .map_err(|e| {
+        tracing::error!(
+            "Relationship keyword search failed for instance {}: {}",
+            instance_id,
+            e
+        );
+        RAGErrorCode::Querying(RAGQueryingErrorCode::SearchIndexUnavailable)
+    })?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| SimpleGraphRelationship {
+            id: row.id,
+            rag_instance_id: row.rag_instance_id,
+            source_entity_id: row.source_entity_id,
+            target_entity_id: row.target_entity_id,
+            relationship_type: row.relationship_type,
+            description: row.description,
+            weight: row.weight.unwrap_or(1.0) as f32,
+            extraction_metadata: row.extraction_metadata.unwrap_or_default(),
+            created_at: row.created_at.unwrap_or_default(),
+            updated_at: row.updated_at.unwrap_or_default(),
+        })
+        .collect())
+}
+
+/// Load relationships by id
+pub async fn get_relationships_by_ids(
+    instance_id: Uuid,
+    relationship_ids: &[Uuid],
+) -> RAGResult<Vec<SimpleGraphRelationship>> {
+    if relationship_ids.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let database = get_database_pool().map_err(|e| {
+        tracing::error!("Failed to get database pool for relationship lookup: {}", e);
+        RAGErrorCode::Querying(RAGQueryingErrorCode::SearchIndexUnavailable)
+    })?;
+
+    let rows = sqlx::query!(
+        r#"
+        SELECT id, rag_instance_id, source_entity_id, target_entity_id, relationship_type,
+               description, weight, extraction_metadata, created_at, updated_at
+        FROM simple_graph_relationships
+        WHERE rag_instance_id = $1 AND id = ANY($2)
+        ORDER BY weight DESC NULLS LAST
+        "#,
+        instance_id,
+        relationship_ids
+    )
+    .fetch_all(&*database)
+    .await
+    .map_err(|e| {
+        tracing::error!("Relationship lookup failed for instance {}: {}", instance_id, e);
+        RAGErrorCode::Querying(RAGQueryingErrorCode::SearchIndexUnavailable)
+    })?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| SimpleGraphRelationship {
+            id: row.id,
+            rag_instance_id: row.rag_instance_id,
+            source_entity_id: row.source_entity_id,
+            target_entity_id: row.target_entity_id,
+            relationship_type: row.relationship_type,
+            description: row.description,
+            weight: row.weight.unwrap_or(1.0) as f32,
+            extraction_metadata: row.extraction_metadata.unwrap_or_default(),
+            created_at: row.created_at.unwrap_or_default(),
+            updated_at: row.updated_at.unwrap_or_default(),
+        })
+        .collect())
+}
+
+/// Get the chunks mentioning any of the given entities, ranked by number of matching entities.
+/// Returns (chunk, matched entity count) pairs.
+pub async fn get_chunks_by_entity_names(
+    instance_id: Uuid,
+    entity_names: &[String],
+    limit: usize,
+    filter: Option<&RAGQueryFilter>,
+) -> RAGResult<Vec<(SimpleGraphChunk, i64)>> {
+    if entity_names.is_empty() || limit == 0 {
+        return Ok(vec![]);
+    }
+
+    let database = get_database_pool().map_err(|e| {
+        tracing::error!("Failed to get database pool for graph chunk lookup: {}", e);
+        RAGErrorCode::Querying(RAGQueryingErrorCode::SearchIndexUnavailable)
+    })?;
+
+    let file_filter = FileFilterParams::from_filter(filter);
+
+    let rows = sqlx::query!(
+        r#"
+        SELECT c.id, c.rag_instance_id, c.file_id, c.chunk_index, c.content, c.content_hash,
+               c.token_count, c.entities, c.relationships, c.metadata, c.created_at, c.updated_at,
+               (SELECT COUNT(*) FROM jsonb_array_elements_text(c.entities) e WHERE e = ANY($2)) as "match_count!"
+        FROM simple_graph_chunks c
+        WHERE c.rag_instance_id = $1 AND c.entities ?| $2
+          AND (NOT $4 OR rag_file_matches_filter(
+              c.rag_instance_id, c.file_id, $5, $6, $7, $8, $9
+          ))
+        ORDER BY "match_count!" DESC, c.chunk_index ASC
+        LIMIT $3
+        "#,
+        instance_id,
+        entity_names,
+        limit as i64,
+        file_filter.enabled,
+        file_filter.file_ids.as_deref(),
+        file_filter.file_types.as_deref(),
+        file_filter.uploaded_after,
+        file_filter.uploaded_before,
+        file_filter.tags.as_deref()
+    )
+    .fetch_all(&*database)
+    .await
+    .map_err(|e| {
+        tracing::error!("Graph chunk lookup failed for instance {}: {}", instance_id, e);
+        RAGErrorCode::Querying(RAGQueryingErrorCode::SearchIndexUnavailable)
+    })?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            (
+                SimpleGraphChunk {
+                    id: row.id,
+                    rag_instance_id: row.rag_instance_id,
+                    file_id: row.file_id,
+                    chunk_index: row.chunk_index,
+                    content: row.content,
+                    content_hash: row.content_hash,
+                    token_count: row.token_count,
+                    entities: row.entities.unwrap_or_default(),
+                    relationships: row.relationships.unwrap_or_default(),
+                    metadata: row.metadata.unwrap_or_default(),
+                    created_at: row.created_at.unwrap_or_default(),
+                    updated_at: row.updated_at.unwrap_or_default(),
+                },
+                row.match_count,
+            )
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_cypher_string_cannot_close_dollar_quote() {
+        for value in ["$cyp$cypher$her$", "$cypher$) AS (n agtype); DROP TABLE x; --", "$$"] {
+            assert!(!escape_cypher_string(value).contains('$'), "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_escape_cypher_string_quotes() {
+        assert_eq!(escape_cypher_string(r"it's a\b"), r"it\'s a\\b");
+        assert_eq!(escape_cypher_string("line\nbreak"), "line break");
+    }
+}
@@ -0,0 +1,470 @@
+// Query processing methods for Simple Graph RAG Engine
+
+use super::extraction::{self, QueryKeywords};
+use super::queries;
+use super::RAGSimpleGraphEngine;
+use crate::ai::rag::engines::simple_vector::queries::similarity_search_documents;
+use crate::ai::rag::engines::simple_vector::utils::SimpleTokenizer;
+use crate::ai::rag::models::{SimpleGraphChunk, SimpleGraphEntity, SimpleGraphRelationship};
+use crate::ai::rag::processors::generation::generate_answer;
+use crate::ai::rag::processors::rerank::rerank_chunks;
+use crate::ai::rag::{
+    QueryMode, RAGErrorCode, RAGQuery, RAGQueryFilter, RAGQueryResponse, RAGQueryingErrorCode,
+    RAGResult, RAGSource, SimpleVectorDocument,
+};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// Similarity threshold for the naive (vector) part of graph queries
+const NAIVE_SIMILARITY_THRESHOLD: f32 = 0.5;
+
+/// Entities, relationships and chunks gathered from the knowledge graph
+#[derive(Debug, Default)]
+pub(super) struct GraphContext {
+    pub entities: Vec<SimpleGraphEntity>,
+    pub relationships: Vec<SimpleGraphRelationship>,
+    pub chunks: Vec<(SimpleVectorDocument, f32)>,
+}
+
+/// Keep items from the front of the list until the token budget is used up
+fn truncate_by_tokens<T>(
+    items: Vec<T>,
+    max_tokens: usize,
+    tokenizer: &SimpleTokenizer,
+    text: impl Fn(&T) -> String,
+) -> Vec<T> {
+    let mut total_tokens = 0;
+    items
+        .into_iter()
+        .take_while(|item| {
+            total_tokens += tokenizer.count_tokens(&text(item));
+            total_tokens <= max_tokens
+        })
+        .collect()
+}
+
+/// Convert a graph chunk into the document type shared by all RAG sources
+fn graph_chunk_to_document(chunk: SimpleGraphChunk) -> SimpleVectorDocument {
+    let mut metadata = chunk.metadata;
+    if let Some(map) = metadata.as_object_mut() {
+        map.insert("entities".to_string(), chunk.entities);
+        map.insert("relationships".to_string(), chunk.relationships);
+    }
+
+    SimpleVectorDocument {
+        id: chunk.id,
+        rag_instance_id: chunk.rag_instance_id,
+        file_id: chunk.file_id,
+        chunk_index: chunk.chunk_index,
+        content: chunk.content,
+        content_hash: chunk.content_hash,
+        token_count: chunk.token_count,
+        metadata,
+        created_at: chunk.created_at,
+        updated_at: chunk.updated_at,
+    }
+}
+
+/// Render entities and relationships as plain-text context for answer generation
+fn format_graph_context(
+    entities: &[SimpleGraphEntity],
+    relationships: &[SimpleGraphRelationship],
+) -> String {
+    let names: HashMap<Uuid, &str> = entities.iter().map(|e| (e.id, e.name.as_str())).collect();
+    let mut lines = Vec::new();
+
+    if !entities.is_empty() {
+        lines.push("Entities:".to_string());
+        for entity in entities {
+            lines.push(format!(
+                "- {} ({}): {}",
+                entity.name,
+                entity.entity_type.as_deref().unwrap_or("UNKNOWN"),
+                entity.description.as_deref().unwrap_or_default()
+            ));
+        }
+    }
+
+    if !relationships.is_empty() {
+        lines.push("Relationships:".to_string());
+        for relationship in relationships {
+            lines.push(format!(
+                "- {} -> {} ({}): {}",
+                names.get(&relationship.source_entity_id).unwrap_or(&"?"),
+                names.get(&relationship.target_entity_id).unwrap_or(&"?"),
+                relationship.relationship_type,
+                relationship.description.as_deref().unwrap_or_default()
+            ));
+        }
+    }
+
+    lines.join("\n")
+}
+
+impl RAGSimpleGraphEngine {
+    /// Extract high/low level keywords from the query with the LLM (LightRAG get_keywords_from_query)
+    pub(super) async fn extract_keywords(&self, query_text: &str) -> QueryKeywords {
+        let keywords = match self
+            .complete(extraction::build_keywords_prompt(query_text))
+            .await
+        {
+            Ok(output) => extraction::parse_keywords_output(&output),
+            Err(e) => {
+                tracing::warn!("Keyword extraction failed, using query terms: {}", e);
+                QueryKeywords::default()
+            }
+        };
+
+        if keywords.is_empty() {
+            extraction::fallback_keywords(query_text)
+        } else {
+            keywords
+        }
+    }
+
+    /// Local retrieval: entities matching low-level keywords plus their one-hop neighborhood
+    async fn get_local_context(&self, keywords: &[String]) -> RAGResult<GraphContext> {
+        let querying_settings = self.settings().querying();
+
+        let seeds =
+            queries::find_entities_by_keywords(self.id, keywords, querying_settings.top_k())
+                .await?;
+        let seed_ids: Vec<Uuid> = seeds.iter().map(|e| e.id).collect();
+
+        let neighbors = queries::get_graph_neighbors(
+            &self.graph_name,
+            &seed_ids,
+            querying_settings.max_graph_nodes_per_query(),
+        )
+        .await?;
+
+        let mut seen: HashSet<Uuid> = seed_ids.iter().copied().collect();
+        let neighbor_ids: Vec<Uuid> = neighbors
+            .iter()
+            .map(|(entity_id, _)| *entity_id)
+            .filter(|id| seen.insert(*id))
+            .collect();
+        let relationship_ids: Vec<Uuid> = neighbors
+            .iter()
+            .map(|(_, relationship_id)| *relationship_id)
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+
+        let mut entities = seeds;
+        entities.extend(queries::get_entities_by_ids(self.id, &neighbor_ids).await?);
+        let relationships = queries::get_relationships_by_ids(self.id, &relationship_ids).await?;
+
+        Ok(GraphContext {
+            entities,
+            relationships,
+            chunks: vec![],
+        })
+    }
+
+    /// Global retrieval: relationships matching high-level keywords plus their endpoints
+    async fn get_global_context(&self, keywords: &[String]) -> RAGResult<GraphContext> {
+        let querying_settings = self.settings().querying();
+
+        let relationships =
+            queries::find_relationships_by_keywords(self.id, keywords, querying_settings.top_k())
+                .await?;
+
+        let mut seen = HashSet::new();
+        let entity_ids: Vec<Uuid> = relationships
+            .iter()
+            .flat_map(|r| [r.source_entity_id, r.target_entity_id])
+            .filter(|id| seen.insert(*id))
+            .collect();
+        let entities = queries::get_entities_by_ids(self.id, &entity_ids).await?;
+
+        Ok(GraphContext {
+            entities,
+            relationships,
+            chunks: vec![],
+        })
+    }
+
+    /// Naive retrieval: plain vector similarity over the instance's chunks
+    async fn get_naive_context(
+        &self,
+        query_text: &str,
+        filter: Option<&RAGQueryFilter>,
+    ) -> RAGResult<Vec<(SimpleVectorDocument, f32)>> {
+        let embedding_request = crate::ai::SimplifiedEmbeddingsRequest {
+            input: crate::ai::core::providers::EmbeddingsInput::Single(query_text.to_string()),
+            encoding_format: Some("float".to_string()),
+            dimensions: None,
+        };
+
+        let query_embedding = self
+            .rag_instance
+            .models
+            .embedding_model
+            .embeddings(embedding_request)
+            .await
+            .map_err(|e| {
+                tracing::error!("Query embedding generation failed: {}", e);
+                RAGErrorCode::Querying(RAGQueryingErrorCode::EmbeddingGenerationFailed)
+            })?
+            .data
+            .into_iter()
+            .next()
+            .map(|d| d.embedding)
+            .unwrap_or_default();
+
+        similarity_search_documents(
+            self.id,
+            &query_embedding,
+            self.settings().querying().chunk_top_k(),
+            NAIVE_SIMILARITY_THRESHOLD,
+            filter,
+        )
+        .await
+    }
+
+    /// Build graph context for local/global/hybrid modes and resolve the related chunks
+    async fn get_graph_context(
+        &self,
+        keywords: &QueryKeywords,
+        use_local: bool,
+        use_global: bool,
+        filter: Option<&RAGQueryFilter>,
+    ) -> RAGResult<GraphContext> {
+        let querying_settings = self.settings().querying();
+        let tokenizer = SimpleTokenizer::new();
+
+        let mut context = GraphContext::default();
+        if use_local {
+            let local = self.get_local_context(&keywords.low_level_keywords).await?;
+            context.entities.extend(local.entities);
+            context.relationships.extend(local.relationships);
+        }
+        if use_global {
+            let global = self.get_global_context(&keywords.high_level_keywords).await?;
+            context.entities.extend(global.entities);
+            context.relationships.extend(global.relationships);
+        }
+
+        // Deduplicate while keeping the retrieval order (seeds first)
+        let mut seen_entities = HashSet::new();
+        context.entities.retain(|e| seen_entities.insert(e.id));
+        let mut seen_relationships = HashSet::new();
+        context.relationships.retain(|r| seen_relationships.insert(r.id));
+
+        context.entities = truncate_by_tokens(
+            context.entities,
+            querying_settings.max_entity_tokens(),
+            &tokenizer,
+            |e| format!("{} {}", e.name, e.description.as_deref().unwrap_or_default()),
+        );
+        context.relationships = truncate_by_tokens(
+            context.relationships,
+            querying_settings.max_relation_tokens(),
+            &tokenizer,
+            |r| {
+                format!(
+                    "{} {}",
+                    r.relationship_type,
+                    r.description.as_deref().unwrap_or_default()
+                )
+            },
+        );
+
+        // Relationship endpoints contribute their chunks as well
+        let mut entity_names: Vec<String> = context.entities.iter().map(|e| e.name.clone()).collect();
+        let known: HashSet<Uuid> = context.entities.iter().map(|e| e.id).collect();
+        let missing_endpoints: Vec<Uuid> = context
+            .relationships
+            .iter()
+            .flat_map(|r| [r.source_entity_id, r.target_entity_id])
+            .filter(|id| !known.contains(id))
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        entity_names.extend(
+            queries::get_entities_by_ids(self.id, &missing_endpoints)
+                .await?
+                .into_iter()
+                .map(|e| e.name),
+        );
+
+        let chunk_limit = querying_settings.chunk_top_k()
+            * querying_settings.related_chunk_number().max(1);
+        let name_count = entity_names.len().max(1) as f32;
+        context.chunks =
+            queries::get_chunks_by_entity_names(self.id, &entity_names, chunk_limit, filter)
+                .await?
+                .into_iter()
+                .map(|(chunk, match_count)| {
+                    let score = (match_count as f32 / name_count).min(1.0);
+                    (graph_chunk_to_document(chunk), score)
+                })
+                .take(querying_settings.chunk_top_k())
+                .collect();
+
+        Ok(context)
+    }
+
+    /// Complete RAG query processing across LightRAG query modes
+    pub async fn query_impl(&self, query: RAGQuery) -> RAGResult<RAGQueryResponse> {
+        let start_time = std::time::Instant::now();
+        tracing::info!(
+            "Starting graph RAG query: {} (mode: {:?})",
+            query.text,
+            query.mode
+        );
+
+        let querying_settings = self.settings().querying();
+        let tokenizer = SimpleTokenizer::new();
+        let mut metadata = HashMap::new();
+
+        let (use_local, use_global, use_naive) = match query.mode {
+            QueryMode::Local => (true, false, false),
+            QueryMode::Global => (false, true, false),
+            QueryMode::Hybrid => (true, true, false),
+            QueryMode::Mix => (true, true, true),
+            QueryMode::Naive => (false, false, true),
+            QueryMode::Bypass => (false, false, false),
+        };
+
+        let mut context = GraphContext::default();
+        if use_local || use_global {
+            let keywords = self.extract_keywords(&query.text).await;
+            context = self
+                .get_graph_context(&keywords, use_local, use_global, query.filter.as_ref())
+                .await?;
+            metadata.insert(
+                "high_level_keywords".to_string(),
+                serde_json::json!(keywords.high_level_keywords),
+            );
+            metadata.insert(
+                "low_level_keywords".to_string(),
+                serde_json::json!(keywords.low_level_keywords),
+            );
+        }
+        if use_naive {
+            context
+                .chunks
+                .extend(self.get_naive_context(&query.text, query.filter.as_ref()).await?);
+        }
+
+        // Merge graph and vector chunks, keeping the best score per chunk
+        let mut best: HashMap<(Uuid, i32), (SimpleVectorDocument, f32)> = HashMap::new();
+        for (document, score) in context.chunks {
+            let key = (document.file_id, document.chunk_index);
+            match best.get(&key) {
+                Some((_, existing)) if *existing >= score => {}
+                _ => {
+                    best.insert(key, (document, score));
+                }
+            }
+        }
+        let mut chunks: Vec<(SimpleVectorDocument, f32)> = best.into_values().collect();
+        chunks.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+        let chunk_budget = querying_settings.max_total_tokens().saturating_sub(
+            context
+                .entities
+                .iter()
+                .map(|e| tokenizer.count_tokens(e.description.as_deref().unwrap_or_default()))
+                .sum::<usize>()
+                + context
+                    .relationships
+                    .iter()
+                    .map(|r| tokenizer.count_tokens(r.description.as_deref().unwrap_or_default()))
+                    .sum::<usize>(),
+        );
+        let chunks = truncate_by_tokens(chunks, chunk_budget, &tokenizer, |(document, _)| {
+            document.content.clone()
+        });
+        let chunks_retrieved = chunks.len();
+
+        // Optional rerank stage: re-score candidates and truncate to the final k
+        let (chunks, confidence_score, rerank_applied) = if querying_settings.enable_rerank() {
+            let output = rerank_chunks(
+                &self.rag_instance.models,
+                &query.text,
+                chunks,
+                querying_settings.rerank_top_k(),
+                querying_settings.min_rerank_score(),
+            )
+            .await;
+            (output.chunks, output.confidence_score, output.applied)
+        } else {
+            (chunks, None, false)
+        };
+
+        metadata.insert(
+            "entities".to_string(),
+            serde_json::json!(context
+                .entities
+                .iter()
+                .map(|e| serde_json::json!({
+                    "name": e.name,
+                    "entity_type": e.entity_type,
+                    "description": e.description,
+                }))
+                .collect::<Vec<_>>()),
+        );
+        metadata.insert(
+            "relationships".to_string(),
+            serde_json::json!(context
+                .relationships
+                .iter()
+                .map(|r| serde_json::json!({
+                    "source_entity_id": r.source_entity_id,
+                    "target_entity_id": r.target_entity_id,
+                    "relationship_type": r.relationship_type,
+                    "description": r.description,
+                    "weight": r.weight,
+                }))
+                .collect::<Vec<_>>()),
+        );
+        metadata.insert(
+            "chunks_retrieved".to_string(),
+            serde_json::json!(chunks_retrieved),
+        );
+        metadata.insert(
+            "rerank_applied".to_string(),
+            serde_json::json!(rerank_applied),
+        );
+
+        let sources: Vec<RAGSource> = chunks
+            .into_iter()
+            .map(|(document, similarity_score)| RAGSource {
+                document,
+                similarity_score,
+            })
+            .collect();
+
+        let answer = if query.generate_answer {
+            let graph_context = format_graph_context(&context.entities, &context.relationships);
+            let prompt_template = self.rag_instance.instance.get_prompt_template_post_query();
+            let answer = generate_answer(
+                &self.rag_instance.models,
+                &query.text,
+                &sources,
+                Some(&graph_context),
+                prompt_template.as_deref(),
+            )
+            .await?;
+            metadata.insert(
+                "citations_count".to_string(),
+                serde_json::json!(answer.citations.len()),
+            );
+            Some(answer)
+        } else {
+            None
+        };
+
+        Ok(RAGQueryResponse {
+            sources,
+            mode_used: query.mode,
+            confidence_score,
+            processing_time_ms: start_time.elapsed().as_millis() as u64,
+            metadata,
+            answer,
+        })
+    }
+}
@@ -76,6 +76,21 @@ impl RAGEngine for RAGSimpleVectorEngine {
     }
 
     fn get_capabilities(&self) -> crate::ai::rag::engines::EngineCapabilities {
-        crate::ai::rag::engines::EngineCapabilities::for_engine_type(&RAGEngineType::SimpleVector)
+        let mut capabilities =
+            crate::ai::rag::engines::EngineCapabilities::for_engine_type(&RAGEngineType::SimpleVector);
+
+        // Hybrid (lexical + vector) retrieval is opt-in through the querying settings
+        capabilities.supports_hybrid_queries = self
+            .rag_instance
+            .instance
+            .engine_settings
+            .simple_vector
+            .as_ref()
+            .map_or(false, |s| {
+                s.querying().retrieval_mode()
+                    == crate::ai::rag::engines::settings::RAGRetrievalMode::Hybrid
+            });
+
+        capabilities
     }
 }
\ No newline at end of file
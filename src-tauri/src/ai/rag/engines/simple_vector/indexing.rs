@@ -1,13 +1,17 @@
 // File processing, indexing methods, and embedding operations for Simple Vector RAG Engine
 
+use super::overlap::chunk_markdown_structure;
 use super::queries;
+use super::types::IncrementalIndexStats;
 use super::RAGSimpleVectorEngine;
 use crate::ai::rag::engines::get_rag_file_storage;
+use crate::ai::rag::engines::settings::RAGChunkingStrategy;
 use crate::ai::rag::{
     processors::{chunk::TokenBasedChunker, text},
     types::TextChunk,
     PipelineStage, ProcessingStatus, RAGErrorCode, RAGIndexingErrorCode, RAGInstanceErrorCode, RAGResult,
 };
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
 impl RAGSimpleVectorEngine {
@@ -99,6 +103,122 @@ impl RAGSimpleVectorEngine {
         Ok(all_embeddings)
     }
 
+    /// Embed only chunks whose content hash has no stored embedding in the instance and reuse
+    /// the rest. Returns one embedding per chunk, the stored document of this file each chunk
+    /// can keep (see `match_stored_documents`), and the counts for rag_metadata.
+    pub(super) async fn embed_chunks_incrementally(
+        &self,
+        file_id: Uuid,
+        chunks: &[TextChunk],
+    ) -> RAGResult<(Vec<Vec<f32>>, Vec<Option<i32>>, IncrementalIndexStats)> {
+        let stored = queries::get_document_hashes_for_file(self.id, file_id).await?;
+        let kept = match_stored_documents(&stored, chunks);
+
+        let mut hashes: Vec<String> = chunks.iter().map(|c| c.content_hash.clone()).collect();
+        hashes.sort();
+        hashes.dedup();
+        let mut known = queries::get_embeddings_by_content_hash(self.id, &hashes).await?;
+
+        let reused_embeddings = chunks
+            .iter()
+            .zip(&kept)
+            .filter(|(c, kept)| kept.is_none() && known.contains_key(&c.content_hash))
+            .count();
+
+        // Embed each missing hash once, even if it appears in several chunks
+        let mut seen = HashSet::new();
+        let to_embed: Vec<TextChunk> = chunks
+            .iter()
+            .filter(|c| {
+                !known.contains_key(&c.content_hash) && seen.insert(c.content_hash.clone())
+            })
+            .cloned()
+            .collect();
+
+        if !to_embed.is_empty() {
+            let fresh = self.process_embeddings_in_batches(&to_embed).await?;
+            if fresh.len() != to_embed.len() {
+                tracing::error!(
+                    "Embedding count mismatch: {} embeddings for {} chunks",
+                    fresh.len(),
+                    to_embed.len()
+                );
+                return Err(RAGErrorCode::Indexing(
+                    RAGIndexingErrorCode::EmbeddingGenerationFailed,
+                ));
+            }
+            for (chunk, embedding) in to_embed.iter().zip(fresh) {
+                known.insert(chunk.content_hash.clone(), embedding);
+            }
+        }
+
+        let embeddings = chunks
+            .iter()
+            .map(|c| {
+                known.get(&c.content_hash).cloned().ok_or_else(|| {
+                    tracing::error!("Missing embedding for chunk {}", c.chunk_index);
+                    RAGErrorCode::Indexing(RAGIndexingErrorCode::EmbeddingGenerationFailed)
+                })
+            })
+            .collect::<RAGResult<Vec<_>>>()?;
+
+        let unchanged_chunks = kept.iter().filter(|k| k.is_some()).count();
+        let stats = IncrementalIndexStats {
+            total_chunks: chunks.len(),
+            unchanged_chunks,
+            reused_embeddings,
+            embedded_chunks: to_embed.len(),
+            removed_chunks: stored.len() - unchanged_chunks,
+        };
+
+        tracing::info!(
+            "Incremental embedding for file {}: {} chunks, {} unchanged, {} reused, {} embedded, {} removed",
+            file_id,
+            stats.total_chunks,
+            stats.unchanged_chunks,
+            stats.reused_embeddings,
+            stats.embedded_chunks,
+            stats.removed_chunks
+        );
+
+        Ok((embeddings, kept, stats))
+    }
+
+    /// Split extracted text into chunks using the configured chunking strategy
+    pub(super) async fn chunk_extracted_text(
+        &self,
+        content: &str,
+        markdown: &str,
+    ) -> RAGResult<Vec<TextChunk>> {
+        let indexing_settings = self
+            .rag_instance
+            .instance
+            .engine_settings
+            .simple_vector
+            .as_ref()
+            .map(|s| s.indexing());
+
+        match indexing_settings {
+            Some(settings) if settings.chunking_strategy() == RAGChunkingStrategy::Structure => {
+                chunk_markdown_structure(
+                    self.rag_instance.models.embedding_model.as_ref(),
+                    markdown,
+                    settings.chunk_token_size(),
+                    settings.chunk_overlap_token_size(),
+                    settings
+                        .enable_semantic_split()
+                        .then(|| settings.semantic_split_threshold()),
+                )
+                .await
+            }
+            _ => {
+                let chunker = TokenBasedChunker::new();
+                let raw_chunks = chunker.chunk(content, None, None, true, 0.7).await?;
+                chunker.process_chunks(raw_chunks).await
+            }
+        }
+    }
+
     /// Store chunks with metadata and embeddings in the database
     pub(super) async fn store_chunks_with_metadata(
         &self,
@@ -225,6 +345,7 @@ impl RAGSimpleVectorEngine {
         };
 
         let content = processing_result.content;
+        let markdown = processing_result.markdown;
         let metadata = processing_result.metadata;
         let quality_score = processing_result.quality_score;
 
@@ -254,8 +375,7 @@ impl RAGSimpleVectorEngine {
         )
         .await?;
 
-        let chunker = TokenBasedChunker::new();
-        let raw_chunks = match chunker.chunk(&content, None, None, true, 0.7).await {
+        let optimized_chunks = match self.chunk_extracted_text(&content, &markdown).await {
             Ok(chunks) => chunks,
             Err(e) => {
                 let error_msg = format!("Text chunking failed for {}: {}", filename, e);
@@ -270,21 +390,6 @@ impl RAGSimpleVectorEngine {
             }
         };
 
-        let optimized_chunks = match chunker.process_chunks(raw_chunks).await {
-            Ok(chunks) => chunks,
-            Err(e) => {
-                let error_msg = format!("Chunk processing failed for {}: {}", filename, e);
-                tracing::error!("{}", error_msg);
-                self.update_pipeline_status(
-                    file_id,
-                    PipelineStage::Chunking,
-                    ProcessingStatus::Failed(error_msg.clone()),
-                )
-                .await?;
-                return Err(e);
-            }
-        };
-
         tracing::info!(
             "Advanced processing completed: {} optimized chunks selected via chunking service",
             optimized_chunks.len()
@@ -305,11 +410,11 @@ impl RAGSimpleVectorEngine {
         )
         .await?;
 
-        let embeddings = match self
-            .process_embeddings_in_batches(&optimized_chunks)
+        let (embeddings, kept, index_stats) = match self
+            .embed_chunks_incrementally(file_id, &optimized_chunks)
             .await
         {
-            Ok(embeddings) => embeddings,
+            Ok(result) => result,
             Err(e) => {
                 let error_msg = format!("Embedding generation failed for {}: {}", filename, e);
                 tracing::error!("{}", error_msg);
@@ -338,9 +443,35 @@ impl RAGSimpleVectorEngine {
         )
         .await?;
 
-        match self.store_chunks_with_metadata(file_id, optimized_chunks, embeddings)
+        // Unchanged chunks keep their stored documents, moved to their new chunk index; the other
+        // documents are dropped and the remaining chunks written
+        let moves: Vec<(i32, i32)> = optimized_chunks
+            .iter()
+            .zip(&kept)
+            .filter_map(|(chunk, kept)| kept.map(|from| (from, chunk.chunk_index as i32)))
+            .collect();
+        let (changed_chunks, changed_embeddings): (Vec<TextChunk>, Vec<Vec<f32>>) =
+            optimized_chunks
+                .into_iter()
+                .zip(embeddings)
+                .zip(kept)
+                .filter(|(_, kept)| kept.is_none())
+                .map(|(pair, _)| pair)
+                .unzip();
+
+        let store_result = async {
+            queries::retain_vector_documents(self.id, file_id, &moves).await?;
+            self.store_chunks_with_metadata(file_id, changed_chunks, changed_embeddings)
+                .await?;
+            queries::merge_file_metadata(
+                self.id,
+                file_id,
+                serde_json::json!({ "indexing_stats": index_stats }),
+            )
             .await
-        {
+        };
+
+        match store_result.await {
             Ok(()) => (),
             Err(e) => {
                 let error_msg = format!("Index storage failed for {}: {}", filename, e);
@@ -380,4 +511,84 @@ impl RAGSimpleVectorEngine {
 
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+/// For each chunk, the chunk index of a stored document of the file with the same content hash
+/// that can be kept instead of writing the chunk again. Matching is by hash alone so content
+/// that only moved is not rewritten; a document at the chunk's own index is preferred and each
+/// stored document is kept for at most one chunk.
+fn match_stored_documents(stored: &[(i32, String)], chunks: &[TextChunk]) -> Vec<Option<i32>> {
+    let mut available: HashMap<&str, Vec<i32>> = HashMap::new();
+    for (chunk_index, hash) in stored {
+        available.entry(hash.as_str()).or_default().push(*chunk_index);
+    }
+
+    let mut kept: Vec<Option<i32>> = chunks
+        .iter()
+        .map(|chunk| {
+            let indexes = available.get_mut(chunk.content_hash.as_str())?;
+            let position = indexes
+                .iter()
+                .position(|index| *index == chunk.chunk_index as i32)?;
+            Some(indexes.remove(position))
+        })
+        .collect();
+
+    for (chunk, kept) in chunks.iter().zip(kept.iter_mut()) {
+        if kept.is_none() {
+            if let Some(indexes) = available.get_mut(chunk.content_hash.as_str()) {
+                if !indexes.is_empty() {
+                    *kept = Some(indexes.remove(0));
+                }
+            }
+        }
+    }
+
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(chunk_index: usize, hash: &str) -> TextChunk {
+        TextChunk {
+            id: None,
+            content: String::new(),
+            content_hash: hash.to_string(),
+            token_count: 0,
+            chunk_index,
+            metadata: HashMap::new(),
+        }
+    }
+
+    fn stored(hashes: &[&str]) -> Vec<(i32, String)> {
+        hashes
+            .iter()
+            .enumerate()
+            .map(|(i, h)| (i as i32, h.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_match_stored_documents_keeps_unchanged_positions() {
+        let chunks = vec![chunk(0, "a"), chunk(1, "x"), chunk(2, "c")];
+        let kept = match_stored_documents(&stored(&["a", "b", "c"]), &chunks);
+        assert_eq!(kept, vec![Some(0), None, Some(2)]);
+    }
+
+    #[test]
+    fn test_match_stored_documents_follows_shifted_content() {
+        // A chunk inserted at the start shifts every other chunk by one
+        let chunks = vec![chunk(0, "new"), chunk(1, "a"), chunk(2, "b"), chunk(3, "c")];
+        let kept = match_stored_documents(&stored(&["a", "b", "c"]), &chunks);
+        assert_eq!(kept, vec![None, Some(0), Some(1), Some(2)]);
+    }
+
+    #[test]
+    fn test_match_stored_documents_uses_each_document_once() {
+        let chunks = vec![chunk(0, "a"), chunk(1, "a"), chunk(2, "a")];
+        let kept = match_stored_documents(&stored(&["b", "a", "a"]), &chunks);
+        assert_eq!(kept, vec![None, Some(1), Some(2)]);
+    }
+}
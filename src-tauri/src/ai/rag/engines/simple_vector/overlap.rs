@@ -1,2 +1,148 @@
 // Overlap management and semantic boundaries for Simple Vector RAG Engine
-// TODO: Implement semantic overlap management features
\ No newline at end of file
+
+use crate::ai::core::AIModel;
+use crate::ai::rag::processors::chunk::{ChunkUnit, MarkdownBlockKind, StructureAwareChunker};
+use crate::ai::rag::{types::TextChunk, RAGErrorCode, RAGIndexingErrorCode, RAGResult};
+
+/// Number of units embedded per request when detecting semantic boundaries
+const SEMANTIC_EMBEDDING_BATCH_SIZE: usize = 64;
+
+/// Chunk extracted markdown along its structure. When `semantic_split_threshold` is set,
+/// adjacent paragraph units whose embedding similarity falls below it are preferred split
+/// points.
+pub async fn chunk_markdown_structure(
+    embedding_model: &dyn AIModel,
+    markdown: &str,
+    max_token_size: usize,
+    overlap_token_size: usize,
+    semantic_split_threshold: Option<f32>,
+) -> RAGResult<Vec<TextChunk>> {
+    let chunker = StructureAwareChunker::new();
+    let units = chunker.split_units(markdown, max_token_size);
+
+    let break_before = match semantic_split_threshold {
+        Some(threshold) => Some(semantic_breakpoints(embedding_model, &units, threshold).await?),
+        None => None,
+    };
+
+    let chunks = chunker.pack_units(
+        units,
+        max_token_size,
+        overlap_token_size,
+        break_before.as_deref(),
+    );
+
+    tracing::info!(
+        "Structure-aware chunking completed: {} chunks (max_tokens={}, overlap={}, semantic={})",
+        chunks.len(),
+        max_token_size,
+        overlap_token_size,
+        semantic_split_threshold.is_some()
+    );
+
+    Ok(chunks)
+}
+
+/// Mark units that start a new topic: consecutive paragraph units whose embeddings are less
+/// similar than `threshold`. Structural boundaries are already handled by the chunker.
+async fn semantic_breakpoints(
+    embedding_model: &dyn AIModel,
+    units: &[ChunkUnit],
+    threshold: f32,
+) -> RAGResult<Vec<bool>> {
+    let prose: Vec<usize> = units
+        .iter()
+        .enumerate()
+        .filter(|(_, u)| u.kind == MarkdownBlockKind::Paragraph)
+        .map(|(i, _)| i)
+        .collect();
+    if prose.len() < 2 {
+        return Ok(vec![false; units.len()]);
+    }
+
+    let mut embeddings: Vec<Vec<f32>> = Vec::with_capacity(prose.len());
+    for batch in prose.chunks(SEMANTIC_EMBEDDING_BATCH_SIZE) {
+        let request = crate::ai::SimplifiedEmbeddingsRequest {
+            input: crate::ai::core::providers::EmbeddingsInput::Multiple(
+                batch.iter().map(|&i| units[i].content.clone()).collect(),
+            ),
+            encoding_format: Some("float".to_string()),
+            dimensions: None,
+        };
+        let response = embedding_model.embeddings(request).await.map_err(|e| {
+            tracing::error!("Embedding generation for semantic chunking failed: {}", e);
+            RAGErrorCode::Indexing(RAGIndexingErrorCode::EmbeddingGenerationFailed)
+        })?;
+        embeddings.extend(response.data.into_iter().map(|d| d.embedding));
+    }
+
+    if embeddings.len() != prose.len() {
+        tracing::warn!(
+            "Semantic chunking received {} embeddings for {} units, skipping semantic splits",
+            embeddings.len(),
+            prose.len()
+        );
+        return Ok(vec![false; units.len()]);
+    }
+
+    Ok(mark_breakpoints(units.len(), &prose, &embeddings, threshold))
+}
+
+/// Flag each prose unit that is less similar than `threshold` to the prose unit right before
+/// it. `embeddings[i]` belongs to unit `prose[i]`.
+fn mark_breakpoints(
+    unit_count: usize,
+    prose: &[usize],
+    embeddings: &[Vec<f32>],
+    threshold: f32,
+) -> Vec<bool> {
+    let mut breaks = vec![false; unit_count];
+    for pair in 1..prose.len() {
+        let (previous, current) = (prose[pair - 1], prose[pair]);
+        // Only compare sentences that are next to each other in the document
+        if current != previous + 1 {
+            continue;
+        }
+        if cosine_similarity(&embeddings[pair - 1], &embeddings[pair]) < threshold {
+            breaks[current] = true;
+        }
+    }
+    breaks
+}
+
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b: f32 = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    dot / (norm_a * norm_b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mark_breakpoints_only_between_adjacent_dissimilar_sentences() {
+        let units = StructureAwareChunker::new().split_units(
+            "Cats purr. Cats meow.\n\n```\ncode\n```\n\nDogs bark. Dogs run.",
+            100,
+        );
+        let prose = vec![0, 1, 3, 4];
+        assert!(prose.iter().all(|&i| units[i].kind == MarkdownBlockKind::Paragraph));
+
+        let embeddings = vec![vec![1.0, 0.0], vec![1.0, 0.0], vec![0.0, 1.0], vec![1.0, 0.0]];
+        let breaks = mark_breakpoints(units.len(), &prose, &embeddings, 0.5);
+
+        // Units 1 and 3 are separated by the code fence, which is a structural boundary already
+        assert_eq!(breaks, vec![false, false, false, false, true]);
+    }
+
+    #[test]
+    fn test_cosine_similarity_of_zero_vector() {
+        assert_eq!(cosine_similarity(&[0.0, 0.0], &[1.0, 0.0]), 0.0);
+        assert!((cosine_similarity(&[1.0, 1.0], &[2.0, 2.0]) - 1.0).abs() < 1e-6);
+    }
+}
@@ -3,10 +3,12 @@
 use crate::ai::rag::{
     PipelineStage, ProcessingStatus, RAGErrorCode, RAGInstanceErrorCode, RAGQueryingErrorCode, RAGResult,
 };
-use crate::ai::rag::SimpleVectorDocument;
+use crate::ai::rag::{RAGQueryFilter, SimpleVectorDocument};
+use super::utils::build_or_tsquery;
 use crate::database::get_database_pool;
 use pgvector::HalfVector;
 use serde::{Serialize, Deserialize};
+use std::collections::HashMap;
 use uuid::Uuid;
 
 /// Update pipeline status for a file in a RAG instance
@@ -102,7 +104,8 @@ pub async fn get_filename_from_db(file_id: Uuid) -> RAGResult<String> {
     Ok(filename)
 }
 
-/// Insert or update vector document
+/// Insert or update vector document. An updated chunk drops the embedding a migration staged
+/// for its old content.
 pub async fn upsert_vector_document(
     instance_id: Uuid,
     file_id: Uuid,
@@ -134,6 +137,7 @@ pub async fn upsert_vector_document(
             content_hash = EXCLUDED.content_hash,
             token_count = EXCLUDED.token_count,
             embedding = EXCLUDED.embedding,
+            embedding_next = NULL,
             metadata = EXCLUDED.metadata,
             updated_at = NOW()
         "#,
@@ -160,21 +164,43 @@ pub async fn upsert_vector_document(
     Ok(())
 }
 
-/// Update file metadata in rag_instance_files table
+/// Store the text extraction metadata of a file in rag_instance_files. It is merged into
+/// rag_metadata so the user-defined tags set at upload survive processing.
 pub async fn update_file_metadata(
     instance_id: Uuid,
     file_id: Uuid,
     metadata: serde_json::Value,
+) -> RAGResult<()> {
+    merge_file_metadata(instance_id, file_id, extraction_metadata_patch(metadata)).await
+}
+
+/// Keys of extraction metadata to merge into rag_metadata: everything but `tags`, which belong
+/// to the user. Anything but an object merges nothing.
+fn extraction_metadata_patch(metadata: serde_json::Value) -> serde_json::Value {
+    match metadata {
+        serde_json::Value::Object(mut map) => {
+            map.remove("tags");
+            serde_json::Value::Object(map)
+        }
+        _ => serde_json::json!({}),
+    }
+}
+
+/// Merge keys into rag_metadata of a file in rag_instance_files, keeping existing keys
+pub async fn merge_file_metadata(
+    instance_id: Uuid,
+    file_id: Uuid,
+    metadata: serde_json::Value,
 ) -> RAGResult<()> {
     let database = get_database_pool().map_err(|e| {
-        tracing::error!("Failed to get database pool for file metadata update: {}", e);
+        tracing::error!("Failed to get database pool for file metadata merge: {}", e);
         RAGErrorCode::Instance(RAGInstanceErrorCode::DatabaseError)
     })?;
 
     sqlx::query!(
         r#"
-        UPDATE rag_instance_files 
-        SET rag_metadata = $3,
+        UPDATE rag_instance_files
+        SET rag_metadata = COALESCE(rag_metadata, '{}'::jsonb) || $3,
             updated_at = NOW()
         WHERE rag_instance_id = $1 AND file_id = $2
         "#,
@@ -186,7 +212,7 @@ pub async fn update_file_metadata(
     .await
     .map_err(|e| {
         tracing::error!(
-            "Failed to update file metadata for instance {} and file {}: {}",
+            "Failed to merge file metadata for instance {} and file {}: {}",
             instance_id,
             file_id,
             e
@@ -197,6 +223,200 @@ pub async fn update_file_metadata(
     Ok(())
 }
 
+/// Get chunk index and content hash of the documents already stored for a file
+pub async fn get_document_hashes_for_file(
+    instance_id: Uuid,
+    file_id: Uuid,
+) -> RAGResult<Vec<(i32, String)>> {
+    let database = get_database_pool().map_err(|e| {
+        tracing::error!("Failed to get database pool for document hash lookup: {}", e);
+        RAGErrorCode::Instance(RAGInstanceErrorCode::DatabaseError)
+    })?;
+
+    let rows = sqlx::query!(
+        r#"
+        SELECT chunk_index, content_hash
+        FROM simple_vector_documents
+        WHERE rag_instance_id = $1 AND file_id = $2
+        ORDER BY chunk_index
+        "#,
+        instance_id,
+        file_id
+    )
+    .fetch_all(&*database)
+    .await
+    .map_err(|e| {
+        tracing::error!(
+            "Failed to get document hashes for instance {} and file {}: {}",
+            instance_id,
+            file_id,
+            e
+        );
+        RAGErrorCode::Instance(RAGInstanceErrorCode::DatabaseError)
+    })?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| (row.chunk_index, row.content_hash))
+        .collect())
+}
+
+/// Get stored embeddings for content hashes from any file of the instance
+pub async fn get_embeddings_by_content_hash(
+    instance_id: Uuid,
+    content_hashes: &[String],
+) -> RAGResult<HashMap<String, Vec<f32>>> {
+    if content_hashes.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let database = get_database_pool().map_err(|e| {
+        tracing::error!("Failed to get database pool for embedding lookup: {}", e);
+        RAGErrorCode::Instance(RAGInstanceErrorCode::DatabaseError)
+    })?;
+
+    let rows = sqlx::query!(
+        r#"
+        SELECT DISTINCT ON (content_hash)
+            content_hash,
+            embedding::vector::real[] AS "embedding!"
+        FROM simple_vector_documents
+        WHERE rag_instance_id = $1
+          AND content_hash = ANY($2)
+          AND embedding IS NOT NULL
+        ORDER BY content_hash, updated_at DESC
+        "#,
+        instance_id,
+        content_hashes
+    )
+    .fetch_all(&*database)
+    .await
+    .map_err(|e| {
+        tracing::error!(
+            "Failed to get embeddings by content hash for instance {}: {}",
+            instance_id,
+            e
+        );
+        RAGErrorCode::Instance(RAGInstanceErrorCode::DatabaseError)
+    })?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| (row.content_hash, row.embedding))
+        .collect())
+}
+
+/// Delete documents of a file from a chunk index onwards, returning how many were removed
+pub async fn delete_vector_documents_from_index(
+    instance_id: Uuid,
+    file_id: Uuid,
+    from_chunk_index: i32,
+) -> RAGResult<u64> {
+    let database = get_database_pool().map_err(|e| {
+        tracing::error!("Failed to get database pool for vector document cleanup: {}", e);
+        RAGErrorCode::Instance(RAGInstanceErrorCode::DatabaseError)
+    })?;
+
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM simple_vector_documents
+        WHERE rag_instance_id = $1 AND file_id = $2 AND chunk_index >= $3
+        "#,
+        instance_id,
+        file_id,
+        from_chunk_index
+    )
+    .execute(&*database)
+    .await
+    .map_err(|e| {
+        tracing::error!(
+            "Failed to delete stale vector documents for instance {} and file {}: {}",
+            instance_id,
+            file_id,
+            e
+        );
+        RAGErrorCode::Instance(RAGInstanceErrorCode::DatabaseError)
+    })?;
+
+    Ok(result.rows_affected())
+}
+
+/// Keep only the given documents of a file, moving each from its stored chunk index to its new
+/// one, and delete the others
+pub async fn retain_vector_documents(
+    instance_id: Uuid,
+    file_id: Uuid,
+    moves: &[(i32, i32)],
+) -> RAGResult<()> {
+    let database = get_database_pool().map_err(|e| {
+        tracing::error!("Failed to get database pool for vector document cleanup: {}", e);
+        RAGErrorCode::Instance(RAGInstanceErrorCode::DatabaseError)
+    })?;
+
+    let map_err = |e: sqlx::Error| {
+        tracing::error!(
+            "Failed to rearrange vector documents for instance {} and file {}: {}",
+            instance_id,
+            file_id,
+            e
+        );
+        RAGErrorCode::Instance(RAGInstanceErrorCode::DatabaseError)
+    };
+
+    let (from_indexes, to_indexes): (Vec<i32>, Vec<i32>) = moves.iter().copied().unzip();
+
+    let mut tx = database.begin().await.map_err(map_err)?;
+
+    sqlx::query!(
+        r#"
+        DELETE FROM simple_vector_documents
+        WHERE rag_instance_id = $1 AND file_id = $2 AND NOT (chunk_index = ANY($3))
+        "#,
+        instance_id,
+        file_id,
+        &from_indexes
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(map_err)?;
+
+    // Park moved documents on negative indexes first so no move collides with the unique
+    // (instance, file, chunk index) key of a document that has not moved yet
+    sqlx::query!(
+        r#"
+        UPDATE simple_vector_documents d
+        SET chunk_index = -1 - m.to_index, updated_at = NOW()
+        FROM unnest($3::int[], $4::int[]) AS m(from_index, to_index)
+        WHERE d.rag_instance_id = $1 AND d.file_id = $2
+          AND d.chunk_index = m.from_index AND m.from_index <> m.to_index
+        "#,
+        instance_id,
+        file_id,
+        &from_indexes,
+        &to_indexes
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(map_err)?;
+
+    sqlx::query!(
+        r#"
+        UPDATE simple_vector_documents
+        SET chunk_index = -1 - chunk_index
+        WHERE rag_instance_id = $1 AND file_id = $2 AND chunk_index < 0
+        "#,
+        instance_id,
+        file_id
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(map_err)?;
+
+    tx.commit().await.map_err(map_err)?;
+
+    Ok(())
+}
+
 /// Vector search result with similarity score
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct VectorSearchResult {
@@ -308,12 +528,38 @@ pub async fn get_documents_by_files(
     Ok(documents)
 }
 
-/// Perform similarity search returning complete SimpleVectorDocument with similarity scores
+/// Bind values for `rag_file_matches_filter`, the file filter shared by the search queries
+pub(crate) struct FileFilterParams {
+    pub enabled: bool,
+    pub file_ids: Option<Vec<Uuid>>,
+    pub file_types: Option<Vec<String>>,
+    pub uploaded_after: Option<chrono::DateTime<chrono::Utc>>,
+    pub uploaded_before: Option<chrono::DateTime<chrono::Utc>>,
+    pub tags: Option<Vec<String>>,
+}
+
+impl FileFilterParams {
+    pub(crate) fn from_filter(filter: Option<&RAGQueryFilter>) -> Self {
+        let filter = filter.filter(|f| !f.is_empty());
+        Self {
+            enabled: filter.is_some(),
+            file_ids: filter.and_then(|f| f.file_ids.clone()),
+            file_types: filter.and_then(|f| f.normalized_file_types()),
+            uploaded_after: filter.and_then(|f| f.uploaded_after),
+            uploaded_before: filter.and_then(|f| f.uploaded_before),
+            tags: filter.and_then(|f| f.required_tags()),
+        }
+    }
+}
+
+/// Perform similarity search returning complete SimpleVectorDocument with similarity scores.
+/// The file filter is evaluated in SQL so `top_k` applies to matching chunks only.
 pub async fn similarity_search_documents(
     instance_id: Uuid,
     query_embedding: &[f32],
     top_k: usize,
     similarity_threshold: f32,
+    filter: Option<&RAGQueryFilter>,
 ) -> RAGResult<Vec<(SimpleVectorDocument, f32)>> {
     let database = get_database_pool().map_err(|e| {
         tracing::error!("Failed to get database pool for similarity search: {}", e);
@@ -321,23 +567,33 @@ pub async fn similarity_search_documents(
     })?;
 
     let query_vector = HalfVector::from_f32_slice(query_embedding);
+    let file_filter = FileFilterParams::from_filter(filter);
     
     // Use query! macro for compile-time checking
     let rows = sqlx::query!(
         r#"
         SELECT 
-            id, rag_instance_id, file_id, chunk_index, content, content_hash,
-            token_count, metadata, created_at, updated_at,
-            (1 - (embedding <=> $1::halfvec))::float4 as similarity_score
-        FROM simple_vector_documents 
-        WHERE rag_instance_id = $2
-          AND 1 - (embedding <=> $1::halfvec) >= $3
-        ORDER BY embedding <=> $1::halfvec LIMIT $4
+            d.id, d.rag_instance_id, d.file_id, d.chunk_index, d.content, d.content_hash,
+            d.token_count, d.metadata, d.created_at, d.updated_at,
+            (1 - (d.embedding <=> $1::halfvec))::float4 as similarity_score
+        FROM simple_vector_documents d
+        WHERE d.rag_instance_id = $2
+          AND 1 - (d.embedding <=> $1::halfvec) >= $3
+          AND (NOT $5 OR rag_file_matches_filter(
+              d.rag_instance_id, d.file_id, $6, $7, $8, $9, $10
+          ))
+        ORDER BY d.embedding <=> $1::halfvec LIMIT $4
         "#,
         query_vector as HalfVector,
         instance_id,
         similarity_threshold as f64,
-        top_k as i64
+        top_k as i64,
+        file_filter.enabled,
+        file_filter.file_ids.as_deref(),
+        file_filter.file_types.as_deref(),
+        file_filter.uploaded_after,
+        file_filter.uploaded_before,
+        file_filter.tags.as_deref()
     )
     .fetch_all(&*database)
     .await
@@ -372,3 +628,150 @@ pub async fn similarity_search_documents(
 
     Ok(documents_with_scores)
 }
+
+/// Perform full-text search returning complete SimpleVectorDocument with lexical rank scores
+pub async fn fulltext_search_documents(
+    instance_id: Uuid,
+    query_text: &str,
+    top_k: usize,
+    filter: Option<&RAGQueryFilter>,
+) -> RAGResult<Vec<(SimpleVectorDocument, f32)>> {
+    let database = get_database_pool().map_err(|e| {
+        tracing::error!("Failed to get database pool for full-text search: {}", e);
+        RAGErrorCode::Querying(RAGQueryingErrorCode::SimilaritySearchFailed)
+    })?;
+
+    let lexemes = sqlx::query_scalar!(
+        r#"SELECT unnest(tsvector_to_array(to_tsvector('simple', $1))) as "lexeme!""#,
+        query_text
+    )
+    .fetch_all(&*database)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to parse full-text query for instance {}: {}", instance_id, e);
+        RAGErrorCode::Querying(RAGQueryingErrorCode::SimilaritySearchFailed)
+    })?;
+
+    let ts_query = build_or_tsquery(&lexemes);
+    if ts_query.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let file_filter = FileFilterParams::from_filter(filter);
+
+    let rows = sqlx::query!(
+        r#"
+        WITH q AS (SELECT $1::text::tsquery AS query)
+        SELECT
+            d.id, d.rag_instance_id, d.file_id, d.chunk_index, d.content, d.content_hash,
+            d.token_count, d.metadata, d.created_at, d.updated_at,
+            ts_rank_cd(d.content_tsv, q.query)::float4 as rank_score
+        FROM simple_vector_documents d, q
+        WHERE d.rag_instance_id = $2
+          AND d.content_tsv @@ q.query
+          AND (NOT $4 OR rag_file_matches_filter(
+              d.rag_instance_id, d.file_id, $5, $6, $7, $8, $9
+          ))
+        ORDER BY rank_score DESC
+        LIMIT $3
+        "#,
+        ts_query,
+        instance_id,
+        top_k as i64,
+        file_filter.enabled,
+        file_filter.file_ids.as_deref(),
+        file_filter.file_types.as_deref(),
+        file_filter.uploaded_after,
+        file_filter.uploaded_before,
+        file_filter.tags.as_deref()
+    )
+    .fetch_all(&*database)
+    .await
+    .map_err(|e| {
+        tracing::error!(
+            "Failed to execute full-text search for instance {}: {}",
+            instance_id,
+            e
+        );
+        RAGErrorCode::Querying(RAGQueryingErrorCode::SimilaritySearchFailed)
+    })?;
+
+    let documents_with_scores: Vec<(SimpleVectorDocument, f32)> = rows
+        .into_iter()
+        .map(|row| {
+            let document = SimpleVectorDocument {
+                id: row.id,
+                rag_instance_id: row.rag_instance_id,
+                file_id: row.file_id,
+                chunk_index: row.chunk_index,
+                content: row.content,
+                content_hash: row.content_hash,
+                token_count: row.token_count,
+                metadata: row.metadata.unwrap_or_else(|| serde_json::json!({})),
+                created_at: row.created_at.unwrap_or_else(|| chrono::Utc::now()),
+                updated_at: row.updated_at.unwrap_or_else(|| chrono::Utc::now()),
+            };
+            (document, row.rank_score.unwrap_or(0.0))
+        })
+        .collect();
+
+    Ok(documents_with_scores)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Shallow merge of two objects, as `jsonb || jsonb` does
+    fn jsonb_concat(left: serde_json::Value, right: serde_json::Value) -> serde_json::Value {
+        let mut merged = left.as_object().cloned().unwrap_or_default();
+        merged.extend(right.as_object().cloned().unwrap_or_default());
+        serde_json::Value::Object(merged)
+    }
+
+    #[test]
+    fn extraction_metadata_patch_keeps_user_tags() {
+        let patch = extraction_metadata_patch(serde_json::json!({
+            "tags": ["from-document"],
+            "word_count": 120
+        }));
+        assert_eq!(patch, serde_json::json!({ "word_count": 120 }));
+
+        assert_eq!(
+            extraction_metadata_patch(serde_json::Value::Null),
+            serde_json::json!({})
+        );
+    }
+
+    #[test]
+    fn uploaded_tags_still_match_filter_after_processing() {
+        // Tags set at upload
+        let uploaded = serde_json::json!({ "tags": ["finance", "2024"] });
+
+        // Text extraction, then incremental indexing stats
+        let processed = jsonb_concat(
+            uploaded,
+            extraction_metadata_patch(serde_json::json!({ "page_count": 3 })),
+        );
+        let processed = jsonb_concat(
+            processed,
+            serde_json::json!({ "indexing_stats": { "total_chunks": 4 } }),
+        );
+
+        let filter = RAGQueryFilter {
+            tags: Some(vec!["finance".to_string()]),
+            ..Default::default()
+        };
+        let params = FileFilterParams::from_filter(Some(&filter));
+        assert!(params.enabled);
+
+        let stored_tags: Vec<String> =
+            serde_json::from_value(processed["tags"].clone()).unwrap_or_default();
+        assert!(params
+            .tags
+            .unwrap()
+            .iter()
+            .all(|tag| stored_tags.contains(tag)));
+        assert_eq!(processed["page_count"], 3);
+    }
+}
@@ -1,10 +1,14 @@
 // Query processing methods for Simple Vector RAG Engine
 
-use super::queries::similarity_search_documents;
+use super::queries::{fulltext_search_documents, similarity_search_documents};
+use super::utils::reciprocal_rank_fusion;
 use super::RAGSimpleVectorEngine;
+use crate::ai::rag::engines::settings::RAGRetrievalMode;
+use crate::ai::rag::processors::generation::generate_answer;
+use crate::ai::rag::processors::rerank::rerank_chunks;
 use crate::ai::rag::{
-    RAGErrorCode, RAGQuery, RAGQueryResponse, RAGQueryingErrorCode, RAGResult, RAGSource,
-    SimpleVectorDocument,
+    RAGErrorCode, RAGQuery, RAGQueryFilter, RAGQueryResponse, RAGQueryingErrorCode, RAGResult,
+    RAGSource, SimpleVectorDocument,
 };
 use std::collections::HashMap;
 
@@ -13,6 +17,7 @@ impl RAGSimpleVectorEngine {
     pub(super) async fn get_vector_context(
         &self,
         query_text: &str,
+        filter: Option<&RAGQueryFilter>,
     ) -> RAGResult<Vec<(SimpleVectorDocument, f32)>> {
         // 1. Generate query embedding
         let query_embedding = self.generate_query_embedding(query_text).await?;
@@ -39,6 +44,7 @@ impl RAGSimpleVectorEngine {
             &query_embedding,
             search_top_k,
             similarity_threshold.unwrap_or(0.5),
+            filter,
         )
         .await?;
 
@@ -47,7 +53,51 @@ impl RAGSimpleVectorEngine {
             results.len(),
             search_top_k
         );
-        Ok(results)
+
+        // 4. Fuse with lexical matches when hybrid retrieval is enabled
+        let querying_settings = self
+            .rag_instance
+            .instance
+            .engine_settings
+            .simple_vector
+            .as_ref()
+            .map(|s| s.querying());
+        match querying_settings {
+            Some(settings) if settings.retrieval_mode() == RAGRetrievalMode::Hybrid => {
+                self.fuse_with_lexical_context(
+                    query_text,
+                    results,
+                    search_top_k,
+                    settings.rrf_k(),
+                    filter,
+                )
+                .await
+            }
+            _ => Ok(results),
+        }
+    }
+
+    /// Combine vector results with full-text matches using reciprocal rank fusion
+    pub(super) async fn fuse_with_lexical_context(
+        &self,
+        query_text: &str,
+        vector_results: Vec<(SimpleVectorDocument, f32)>,
+        search_top_k: usize,
+        rrf_k: usize,
+        filter: Option<&RAGQueryFilter>,
+    ) -> RAGResult<Vec<(SimpleVectorDocument, f32)>> {
+        let lexical_results =
+            fulltext_search_documents(self.id, query_text, search_top_k, filter).await?;
+
+        tracing::info!(
+            "Lexical context retrieval: {} documents (search_top_k: {})",
+            lexical_results.len(),
+            search_top_k
+        );
+
+        let mut fused = reciprocal_rank_fusion(vec![vector_results, lexical_results], rrf_k);
+        fused.truncate(search_top_k);
+        Ok(fused)
     }
 
     /// Generate embedding for query text with high priority
@@ -85,6 +135,7 @@ impl RAGSimpleVectorEngine {
             confidence_score: Some(0.0),
             processing_time_ms: processing_time,
             metadata: HashMap::new(),
+            answer: None,
         }
     }
 
@@ -93,16 +144,43 @@ impl RAGSimpleVectorEngine {
         let start_time = std::time::Instant::now();
 
         // Vector retrieval only
-        let raw_chunks = self.get_vector_context(&query.text).await?;
+        let raw_chunks = self
+            .get_vector_context(&query.text, query.filter.as_ref())
+            .await?;
 
         if raw_chunks.is_empty() {
             tracing::warn!("No relevant chunks found for query: {}", query.text);
             return Ok(self.handle_empty_results(query, start_time.elapsed().as_millis() as u64));
         }
 
+        let chunks_retrieved = raw_chunks.len();
+
+        // Optional rerank stage: re-score candidates and truncate to the final k
+        let querying_settings = self
+            .rag_instance
+            .instance
+            .engine_settings
+            .simple_vector
+            .as_ref()
+            .map(|s| s.querying());
+        let (chunks, confidence_score, rerank_applied) = match querying_settings {
+            Some(settings) if settings.enable_rerank() => {
+                let output = rerank_chunks(
+                    &self.rag_instance.models,
+                    &query.text,
+                    raw_chunks,
+                    settings.rerank_top_k(),
+                    settings.min_rerank_score(),
+                )
+                .await;
+                (output.chunks, output.confidence_score, output.applied)
+            }
+            _ => (raw_chunks, None, false),
+        };
+
         let processing_time = start_time.elapsed().as_millis() as u64;
 
-        let sources: Vec<RAGSource> = raw_chunks
+        let sources: Vec<RAGSource> = chunks
             .into_iter()
             .map(|(document, similarity_score)| RAGSource {
                 document,
@@ -113,27 +191,56 @@ impl RAGSimpleVectorEngine {
         let mut metadata = HashMap::new();
         metadata.insert(
             "chunks_retrieved".to_string(),
-            serde_json::json!(sources.len()),
+            serde_json::json!(chunks_retrieved),
+        );
+        metadata.insert(
+            "rerank_applied".to_string(),
+            serde_json::json!(rerank_applied),
         );
 
         Ok(RAGQueryResponse {
             sources,
             mode_used: query.mode.clone(),
-            confidence_score: None,
+            confidence_score,
             processing_time_ms: processing_time,
             metadata,
+            answer: None,
         })
     }
 
-    /// Complete RAG query processing - all modes now use vector search only
+    /// Complete RAG query processing - all modes use vector search, optionally followed by
+    /// answer generation
     pub async fn query_impl(&self, query: RAGQuery) -> RAGResult<RAGQueryResponse> {
         tracing::info!(
-            "Starting RAG query: {} (mode: {:?})",
+            "Starting RAG query: {} (mode: {:?}, generate answer: {})",
             query.text,
-            query.mode
+            query.mode,
+            query.generate_answer
         );
 
-        // All query modes now use vector search only
-        self.vector_search(&query).await
+        let start_time = std::time::Instant::now();
+
+        // All query modes use vector search for retrieval
+        let mut response = self.vector_search(&query).await?;
+
+        if query.generate_answer {
+            let prompt_template = self.rag_instance.instance.get_prompt_template_post_query();
+            let answer = generate_answer(
+                &self.rag_instance.models,
+                &query.text,
+                &response.sources,
+                None,
+                prompt_template.as_deref(),
+            )
+            .await?;
+            response.metadata.insert(
+                "citations_count".to_string(),
+                serde_json::json!(answer.citations.len()),
+            );
+            response.answer = Some(answer);
+            response.processing_time_ms = start_time.elapsed().as_millis() as u64;
+        }
+
+        Ok(response)
     }
 }
\ No newline at end of file
@@ -99,3 +99,13 @@ pub enum OverlapStrategy {
     Dynamic,       // Dynamic based on content analysis
     ContextWindow, // Context window management
 }
+
+/// Chunk counts of an incremental re-index, stored in rag_metadata
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IncrementalIndexStats {
+    pub total_chunks: usize,
+    pub unchanged_chunks: usize,
+    pub reused_embeddings: usize,
+    pub embedded_chunks: usize,
+    pub removed_chunks: usize,
+}
@@ -88,6 +88,51 @@ pub async fn truncate_chunks_by_tokens(
     Ok(truncated_chunks)
 }
 
+/// Reciprocal rank fusion of several ranked chunk lists.
+/// Each chunk scores sum(1 / (k + rank)) over the lists it appears in; the result is
+/// sorted by fused score and each score is normalized so the best chunk has 1.0.
+pub fn reciprocal_rank_fusion(
+    ranked_lists: Vec<Vec<(SimpleVectorDocument, f32)>>,
+    k: usize,
+) -> Vec<(SimpleVectorDocument, f32)> {
+    let mut fused: std::collections::HashMap<uuid::Uuid, (SimpleVectorDocument, f32)> =
+        std::collections::HashMap::new();
+
+    for list in ranked_lists {
+        for (rank, (document, _)) in list.into_iter().enumerate() {
+            let contribution = 1.0 / (k + rank + 1) as f32;
+            fused
+                .entry(document.id)
+                .and_modify(|(_, score)| *score += contribution)
+                .or_insert((document, contribution));
+        }
+    }
+
+    let mut results: Vec<(SimpleVectorDocument, f32)> = fused.into_values().collect();
+    results.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+    if let Some(max_score) = results.first().map(|(_, score)| *score) {
+        if max_score > 0.0 {
+            for (_, score) in results.iter_mut() {
+                *score /= max_score;
+            }
+        }
+    }
+
+    results
+}
+
+/// Build a tsquery that ORs the given lexemes so partial matches still rank.
+/// Each lexeme is quoted since the simple parser keeps characters that are tsquery operators.
+pub fn build_or_tsquery(lexemes: &[String]) -> String {
+    lexemes
+        .iter()
+        .filter(|lexeme| !lexeme.is_empty())
+        .map(|lexeme| format!("'{}'", lexeme.replace('\\', "\\\\").replace('\'', "''")))
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
+
 /// Format chunks as context for LLM (LightRAG context formatting pattern)
 pub fn format_chunks_as_context(chunks: &[(SimpleVectorDocument, f32)]) -> String {
     let mut context_parts = Vec::new();
@@ -125,17 +170,6 @@ pub fn post_process_llm_response(response: String, query: &str, system_prompt: &
         .to_string()
 }
 
-/// Two-stage reranking algorithm (LightRAG apply_rerank_if_enabled)
-pub async fn apply_rerank_if_enabled(
-    _query_text: &str,
-    chunks: Vec<(SimpleVectorDocument, f32)>
-) -> RAGResult<Vec<(SimpleVectorDocument, f32)>> {
-    // For now, just return chunks unchanged since reranking model is not implemented
-    // TODO: Implement actual reranking when rerank models are available
-    tracing::debug!("Reranking requested but not implemented, returning original chunks");
-    Ok(chunks)
-}
-
 /// Get maximum total tokens for the model (LightRAG default: 30000)
 pub fn get_max_total_tokens() -> usize {
     // LightRAG default is 30000 tokens - should be configurable from engine settings
@@ -146,4 +180,71 @@ pub fn get_max_total_tokens() -> usize {
 /// Get tokenizer for token counting (simplified implementation)
 pub fn get_tokenizer() -> SimpleTokenizer {
     SimpleTokenizer::new()
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn document(id: Uuid) -> (SimpleVectorDocument, f32) {
+        (
+            SimpleVectorDocument {
+                id,
+                rag_instance_id: Uuid::nil(),
+                file_id: Uuid::nil(),
+                chunk_index: 0,
+                content: String::new(),
+                content_hash: String::new(),
+                token_count: 0,
+                metadata: serde_json::json!({}),
+                created_at: chrono::Utc::now(),
+                updated_at: chrono::Utc::now(),
+            },
+            0.0,
+        )
+    }
+
+    #[test]
+    fn test_reciprocal_rank_fusion_ranks_chunks_found_by_both_lists_first() {
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let vector = vec![document(a), document(b)];
+        let lexical = vec![document(c), document(b)];
+
+        let fused = reciprocal_rank_fusion(vec![vector, lexical], 60);
+        let ids: Vec<Uuid> = fused.iter().map(|(d, _)| d.id).collect();
+
+        assert_eq!(ids.len(), 3);
+        assert_eq!(ids[0], b);
+        assert_eq!(fused[0].1, 1.0);
+        assert!(fused.iter().all(|(_, score)| *score > 0.0 && *score <= 1.0));
+    }
+
+    #[test]
+    fn test_reciprocal_rank_fusion_with_zero_k() {
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let fused = reciprocal_rank_fusion(vec![vec![document(a), document(b)]], 0);
+
+        assert_eq!(fused[0].0.id, a);
+        assert_eq!(fused[0].1, 1.0);
+        assert_eq!(fused[1].1, 0.5);
+    }
+
+    #[test]
+    fn test_reciprocal_rank_fusion_of_empty_lists() {
+        assert!(reciprocal_rank_fusion(vec![vec![], vec![]], 60).is_empty());
+    }
+
+    #[test]
+    fn test_build_or_tsquery_quotes_every_lexeme() {
+        let lexemes = vec!["rust".to_string(), "a&b".to_string(), "!not".to_string()];
+        assert_eq!(build_or_tsquery(&lexemes), "'rust' | 'a&b' | '!not'");
+    }
+
+    #[test]
+    fn test_build_or_tsquery_escapes_quotes_and_backslashes() {
+        let lexemes = vec!["it's".to_string(), "c:\\dir".to_string(), String::new()];
+        assert_eq!(build_or_tsquery(&lexemes), "'it''s' | 'c:\\\\dir'");
+        assert_eq!(build_or_tsquery(&[]), "");
+    }
+}
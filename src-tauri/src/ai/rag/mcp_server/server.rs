@@ -13,7 +13,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use uuid::Uuid;
 
-use crate::ai::rag::{RAGQuery, QueryMode};
+use crate::ai::rag::{QueryMode, RAGQuery, RAGQueryFilter};
 use crate::database::queries::rag_instances::get_rag_instance_by_id;
 
 // ============================================
@@ -63,6 +63,10 @@ pub struct RAGQueryArguments {
     pub text: String,
     #[serde(default = "default_query_mode")]
     pub mode: String,
+    #[serde(default)]
+    pub generate_answer: bool,
+    #[serde(default)]
+    pub filter: Option<RAGQueryFilter>,
 }
 
 fn default_query_mode() -> String {
@@ -202,6 +206,42 @@ impl UnifiedRagMcpServer {
                                 "enum": ["naive", "local", "global", "hybrid", "mix", "bypass"],
                                 "default": "naive",
                                 "description": "Query mode (default: naive)"
+                            },
+                            "generate_answer": {
+                                "type": "boolean",
+                                "default": false,
+                                "description": "Generate an answer with citation markers from the retrieved chunks instead of returning chunks only"
+                            },
+                            "filter": {
+                                "type": "object",
+                                "description": "Only search files matching every given criterion",
+                                "properties": {
+                                    "file_ids": {
+                                        "type": "array",
+                                        "items": { "type": "string", "format": "uuid" },
+                                        "description": "Only search these files"
+                                    },
+                                    "file_types": {
+                                        "type": "array",
+                                        "items": { "type": "string" },
+                                        "description": "File extensions (e.g. \"pdf\", \"md\") or MIME types"
+                                    },
+                                    "uploaded_after": {
+                                        "type": "string",
+                                        "format": "date-time",
+                                        "description": "Only files uploaded at or after this time (RFC 3339)"
+                                    },
+                                    "uploaded_before": {
+                                        "type": "string",
+                                        "format": "date-time",
+                                        "description": "Only files uploaded before this time (RFC 3339)"
+                                    },
+                                    "tags": {
+                                        "type": "array",
+                                        "items": { "type": "string" },
+                                        "description": "Files must carry all of these tags"
+                                    }
+                                }
                             }
                         },
                         "required": ["text"]
@@ -332,8 +372,7 @@ impl UnifiedRagMcpServer {
         instance_id: Uuid,
         arguments: RAGQueryArguments,
     ) -> Result<Value, String> {
-        use crate::ai::rag::engines::simple_vector::RAGSimpleVectorEngine;
-        use crate::ai::rag::engines::traits::RAGEngine;
+        use crate::ai::rag::engines::RAGEngineFactory;
 
         // Verify instance exists
         let instance = get_rag_instance_by_id(instance_id)
@@ -348,10 +387,12 @@ impl UnifiedRagMcpServer {
         let query = RAGQuery {
             text: arguments.text.clone(),
             mode: arguments.to_query_mode(),
+            generate_answer: arguments.generate_answer,
+            filter: arguments.filter.clone(),
         };
 
-        // Create RAG engine
-        let engine = RAGSimpleVectorEngine::new(instance_id)
+        // Create RAG engine for the instance's engine type
+        let engine = RAGEngineFactory::create_engine(instance_id)
             .await
             .map_err(|e| format!("Failed to create RAG engine: {}", e))?;
 
@@ -362,6 +403,21 @@ impl UnifiedRagMcpServer {
             .map_err(|e| format!("RAG query failed: {}", e))?;
 
         // Format response as MCP Content
+        if let Some(answer) = &response.answer {
+            return Ok(json!({
+                "type": "text",
+                "text": serde_json::to_string_pretty(&json!({
+                    "answer": answer.text,
+                    "citations": answer.citations,
+                    "sources": response.sources,
+                    "mode_used": format!("{:?}", response.mode_used),
+                    "confidence_score": response.confidence_score,
+                    "processing_time_ms": response.processing_time_ms,
+                    "metadata": response.metadata,
+                })).unwrap_or_default()
+            }));
+        }
+
         Ok(json!({
             "type": "text",
             "text": serde_json::to_string_pretty(&json!({
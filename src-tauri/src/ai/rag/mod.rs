@@ -323,6 +323,54 @@ impl std::fmt::Display for PipelineStage {
 pub struct RAGQuery {
     pub text: String,
     pub mode: QueryMode,
+    /// Synthesize an answer from the retrieved chunks with the instance's LLM
+    pub generate_answer: bool,
+    /// Restrict retrieval to files matching the filter
+    pub filter: Option<RAGQueryFilter>,
+}
+
+/// File-level filter applied to RAG retrieval. Every criterion that is set must match.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct RAGQueryFilter {
+    /// Only search chunks of these files
+    pub file_ids: Option<Vec<Uuid>>,
+    /// File extensions (e.g. "pdf", "md") or MIME types (e.g. "application/pdf")
+    pub file_types: Option<Vec<String>>,
+    /// Only files uploaded at or after this time
+    pub uploaded_after: Option<chrono::DateTime<chrono::Utc>>,
+    /// Only files uploaded before this time
+    pub uploaded_before: Option<chrono::DateTime<chrono::Utc>>,
+    /// Files must carry all of these tags
+    pub tags: Option<Vec<String>>,
+}
+
+impl RAGQueryFilter {
+    /// Whether the filter restricts anything at all
+    pub fn is_empty(&self) -> bool {
+        self.file_ids.is_none()
+            && self.file_types.as_ref().map_or(true, |t| t.is_empty())
+            && self.uploaded_after.is_none()
+            && self.uploaded_before.is_none()
+            && self.tags.as_ref().map_or(true, |t| t.is_empty())
+    }
+
+    /// File types normalized for matching: lowercase, without a leading dot
+    pub fn normalized_file_types(&self) -> Option<Vec<String>> {
+        self.file_types
+            .as_ref()
+            .filter(|types| !types.is_empty())
+            .map(|types| {
+                types
+                    .iter()
+                    .map(|t| t.trim().trim_start_matches('.').to_lowercase())
+                    .collect()
+            })
+    }
+
+    /// Tags to require, or None when no tag criterion is set
+    pub fn required_tags(&self) -> Option<Vec<String>> {
+        self.tags.clone().filter(|tags| !tags.is_empty())
+    }
 }
 
 /// Query modes (inspired by LightRAG)
@@ -350,6 +398,27 @@ pub struct RAGQueryResponse {
     pub confidence_score: Option<f32>,
     pub processing_time_ms: u64,
     pub metadata: HashMap<String, serde_json::Value>,
+    pub answer: Option<RAGAnswer>,
+}
+
+/// Generated answer with citations back to the retrieved sources
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RAGAnswer {
+    /// Answer text with inline citation markers such as [1]
+    pub text: String,
+    /// Sources referenced by the citation markers, in order of first appearance
+    pub citations: Vec<RAGCitation>,
+}
+
+/// Mapping from an inline citation marker to a source document
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RAGCitation {
+    /// Citation marker number as it appears in the answer text
+    pub marker: usize,
+    pub document_id: Uuid,
+    pub file_id: Uuid,
+    pub filename: Option<String>,
+    pub chunk_index: i32,
 }
 
 /// Source information for RAG responses
@@ -1,5 +1,5 @@
 // Chunking implementation - combined from chunk module
-// Contains types and token-based chunker implementation
+// Contains types, the token-based chunker and the structure-aware markdown chunker
 
 use crate::ai::rag::{types::TextChunk, RAGResult};
 use regex::Regex;
@@ -65,13 +65,14 @@ impl TokenBasedChunker {
     }
 
     /// Calculate content hash for chunk identification
-    fn calculate_content_hash(&self, content: &str) -> String {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-
-        let mut hasher = DefaultHasher::new();
-        content.hash(&mut hasher);
-        format!("{:x}", hasher.finish())
+    /// Stable across builds so stored hashes can be compared when re-indexing; migration
+    /// 00000000000019 rehashes chunks stored with the previous DefaultHasher value
+    pub(crate) fn calculate_content_hash(&self, content: &str) -> String {
+        use sha2::{Digest, Sha256};
+
+        let mut hasher = Sha256::new();
+        hasher.update(content.as_bytes());
+        format!("{:x}", hasher.finalize())
     }
 }
 
@@ -654,3 +655,603 @@ impl TokenBasedChunker {
         (uniqueness_ratio + length_score) / 2.0
     }
 }
+
+/// Kind of markdown block a chunk unit comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarkdownBlockKind {
+    Heading,
+    Paragraph,
+    CodeFence,
+    Table,
+    List,
+}
+
+impl MarkdownBlockKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            MarkdownBlockKind::Heading => "heading",
+            MarkdownBlockKind::Paragraph => "paragraph",
+            MarkdownBlockKind::CodeFence => "code",
+            MarkdownBlockKind::Table => "table",
+            MarkdownBlockKind::List => "list",
+        }
+    }
+}
+
+/// Smallest piece of a markdown document the structure-aware chunker packs into chunks.
+/// Paragraphs are split into sentences; code fences, tables and lists stay whole unless
+/// they exceed the chunk size on their own.
+#[derive(Debug, Clone)]
+pub struct ChunkUnit {
+    pub content: String,
+    pub kind: MarkdownBlockKind,
+    pub heading_path: Vec<String>,
+    pub block_index: usize,
+    pub token_count: usize,
+}
+
+/// Markdown-aware chunker that never splits inside code fences, tables or list items
+/// when avoidable and records the heading path of every chunk
+pub struct StructureAwareChunker {
+    token_chunker: TokenBasedChunker,
+    heading_pattern: Regex,
+    list_item_pattern: Regex,
+}
+
+impl StructureAwareChunker {
+    pub fn new() -> Self {
+        Self {
+            token_chunker: TokenBasedChunker::new(),
+            heading_pattern: Regex::new(r"^(#{1,6})\s+(.+?)\s*#*\s*$").unwrap(),
+            list_item_pattern: Regex::new(r"^\s*([-*+]|\d+[.)])\s+").unwrap(),
+        }
+    }
+
+    /// Split markdown into chunk units, tracking the heading path of each unit
+    pub fn split_units(&self, markdown: &str, max_token_size: usize) -> Vec<ChunkUnit> {
+        let lines: Vec<&str> = markdown.lines().collect();
+        let mut units = Vec::new();
+        let mut heading_path: Vec<String> = Vec::new();
+        let mut block_index = 0;
+        let mut i = 0;
+
+        while i < lines.len() {
+            let line = lines[i];
+            let trimmed = line.trim_start();
+
+            if trimmed.is_empty() {
+                i += 1;
+                continue;
+            }
+
+            // Code fence: everything up to the matching closing fence
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                let fence = &trimmed[..3];
+                let start = i;
+                i += 1;
+                while i < lines.len() && !lines[i].trim_start().starts_with(fence) {
+                    i += 1;
+                }
+                let end = (i + 1).min(lines.len());
+                i = end;
+                self.push_block(
+                    &mut units,
+                    &lines[start..end],
+                    MarkdownBlockKind::CodeFence,
+                    &heading_path,
+                    block_index,
+                    max_token_size,
+                );
+                block_index += 1;
+                continue;
+            }
+
+            // Heading: updates the heading path and becomes its own unit
+            if let Some(captures) = self.heading_pattern.captures(trimmed) {
+                let level = captures[1].len();
+                heading_path.truncate(level - 1);
+                while heading_path.len() < level - 1 {
+                    heading_path.push(String::new());
+                }
+                heading_path.push(captures[2].to_string());
+                units.push(self.make_unit(
+                    trimmed.to_string(),
+                    MarkdownBlockKind::Heading,
+                    &heading_path,
+                    block_index,
+                ));
+                block_index += 1;
+                i += 1;
+                continue;
+            }
+
+            // Table: consecutive pipe-delimited rows
+            if trimmed.starts_with('|') {
+                let start = i;
+                while i < lines.len() && lines[i].trim_start().starts_with('|') {
+                    i += 1;
+                }
+                self.push_block(
+                    &mut units,
+                    &lines[start..i],
+                    MarkdownBlockKind::Table,
+                    &heading_path,
+                    block_index,
+                    max_token_size,
+                );
+                block_index += 1;
+                continue;
+            }
+
+            // List: items plus their indented continuation lines
+            if self.list_item_pattern.is_match(line) {
+                let start = i;
+                i += 1;
+                while i < lines.len() {
+                    let next = lines[i];
+                    let continuation =
+                        next.starts_with(|c: char| c.is_whitespace()) && !next.trim().is_empty();
+                    if self.list_item_pattern.is_match(next) || continuation {
+                        i += 1;
+                    } else {
+                        break;
+                    }
+                }
+                self.push_block(
+                    &mut units,
+                    &lines[start..i],
+                    MarkdownBlockKind::List,
+                    &heading_path,
+                    block_index,
+                    max_token_size,
+                );
+                block_index += 1;
+                continue;
+            }
+
+            // Paragraph: consecutive lines until a blank line or another block starts
+            let start = i;
+            i += 1;
+            while i < lines.len() {
+                let next = lines[i].trim_start();
+                if next.is_empty()
+                    || next.starts_with("```")
+                    || next.starts_with("~~~")
+                    || next.starts_with('|')
+                    || self.heading_pattern.is_match(next)
+                    || self.list_item_pattern.is_match(lines[i])
+                {
+                    break;
+                }
+                i += 1;
+            }
+            let paragraph = lines[start..i]
+                .iter()
+                .map(|l| l.trim())
+                .collect::<Vec<_>>()
+                .join(" ");
+            for sentence in split_sentences(&paragraph) {
+                for piece in self.split_oversized_text(&sentence, max_token_size) {
+                    units.push(self.make_unit(
+                        piece,
+                        MarkdownBlockKind::Paragraph,
+                        &heading_path,
+                        block_index,
+                    ));
+                }
+            }
+            block_index += 1;
+        }
+
+        units
+    }
+
+    /// Pack units into chunks. A new chunk starts at every heading, whenever the next unit
+    /// would exceed `max_token_size`, and at semantic breakpoints (`break_before[i]` means a
+    /// split is preferred before unit `i`) once the current chunk is reasonably filled.
+    /// Trailing paragraph sentences are repeated at the start of the next chunk in the same
+    /// section as overlap.
+    pub fn pack_units(
+        &self,
+        units: Vec<ChunkUnit>,
+        max_token_size: usize,
+        overlap_token_size: usize,
+        break_before: Option<&[bool]>,
+    ) -> Vec<TextChunk> {
+        let min_fill_tokens = max_token_size / 4;
+        let mut chunks = Vec::new();
+        let mut current: Vec<ChunkUnit> = Vec::new();
+        let mut current_tokens = 0;
+
+        for (index, unit) in units.into_iter().enumerate() {
+            let semantic_break = break_before
+                .and_then(|breaks| breaks.get(index).copied())
+                .unwrap_or(false);
+
+            let flush_reason = if current.is_empty() {
+                None
+            } else if unit.kind == MarkdownBlockKind::Heading {
+                Some(false)
+            } else if current_tokens + unit.token_count > max_token_size
+                || (semantic_break && current_tokens >= min_fill_tokens)
+            {
+                Some(true)
+            } else {
+                None
+            };
+
+            if let Some(carry_overlap) = flush_reason {
+                let overlap = if carry_overlap {
+                    let available_tokens = max_token_size.saturating_sub(unit.token_count);
+                    self.overlap_units(&current, overlap_token_size, available_tokens)
+                } else {
+                    Vec::new()
+                };
+                chunks.push(self.build_chunk(chunks.len(), &current));
+                current_tokens = overlap.iter().map(|u| u.token_count).sum();
+                current = overlap;
+            }
+
+            current_tokens += unit.token_count;
+            current.push(unit);
+        }
+
+        if !current.is_empty() {
+            chunks.push(self.build_chunk(chunks.len(), &current));
+        }
+
+        chunks
+    }
+
+    /// Chunk markdown without semantic breakpoints
+    pub fn chunk(
+        &self,
+        markdown: &str,
+        max_token_size: usize,
+        overlap_token_size: usize,
+    ) -> Vec<TextChunk> {
+        let units = self.split_units(markdown, max_token_size);
+        self.pack_units(units, max_token_size, overlap_token_size, None)
+    }
+
+    /// Trailing paragraph sentences of the finished chunk to repeat in the next one
+    fn overlap_units(
+        &self,
+        current: &[ChunkUnit],
+        overlap_token_size: usize,
+        available_tokens: usize,
+    ) -> Vec<ChunkUnit> {
+        let budget = overlap_token_size.min(available_tokens);
+        let mut overlap = Vec::new();
+        let mut tokens = 0;
+
+        for unit in current.iter().rev() {
+            if unit.kind != MarkdownBlockKind::Paragraph || tokens + unit.token_count > budget {
+                break;
+            }
+            tokens += unit.token_count;
+            overlap.push(unit.clone());
+        }
+
+        // Never carry the whole chunk over, or the next chunk would only repeat it
+        if overlap.len() == current.len() {
+            overlap.clear();
+        }
+
+        overlap.reverse();
+        overlap
+    }
+
+    /// Join units into a chunk, keeping sentences of a paragraph on one line
+    fn build_chunk(&self, index: usize, units: &[ChunkUnit]) -> TextChunk {
+        let mut content = String::new();
+        let mut previous_block = None;
+        for unit in units {
+            match previous_block {
+                Some(block)
+                    if block == unit.block_index && unit.kind == MarkdownBlockKind::Paragraph =>
+                {
+                    content.push(' ')
+                }
+                Some(_) => content.push_str("\n\n"),
+                None => {}
+            }
+            content.push_str(&unit.content);
+            previous_block = Some(unit.block_index);
+        }
+
+        let heading_path: Vec<&String> = units[0]
+            .heading_path
+            .iter()
+            .filter(|h| !h.is_empty())
+            .collect();
+        let mut block_types: Vec<&str> = Vec::new();
+        for unit in units {
+            if !block_types.contains(&unit.kind.as_str()) {
+                block_types.push(unit.kind.as_str());
+            }
+        }
+
+        let mut metadata = HashMap::new();
+        metadata.insert("heading_path".to_string(), serde_json::json!(heading_path));
+        metadata.insert("block_types".to_string(), serde_json::json!(block_types));
+        metadata.insert("chunking_strategy".to_string(), serde_json::json!("structure"));
+
+        let token_count = self.token_chunker.estimate_tokens(&content);
+        TextChunk {
+            id: Some(Uuid::new_v4()),
+            content_hash: self.token_chunker.calculate_content_hash(&content),
+            content,
+            token_count,
+            chunk_index: index,
+            metadata,
+        }
+    }
+
+    /// Push a multi-line block, splitting it by lines if it does not fit in one chunk.
+    /// Code fences keep their fence lines and tables their header rows in every piece.
+    fn push_block(
+        &self,
+        units: &mut Vec<ChunkUnit>,
+        lines: &[&str],
+        kind: MarkdownBlockKind,
+        heading_path: &[String],
+        block_index: usize,
+        max_token_size: usize,
+    ) {
+        let content = lines.join("\n");
+        if self.token_chunker.estimate_tokens(&content) <= max_token_size || lines.len() < 2 {
+            for piece in self.split_oversized_text(&content, max_token_size) {
+                units.push(self.make_unit(piece, kind, heading_path, block_index));
+            }
+            return;
+        }
+
+        let (prefix, body, suffix): (Vec<&str>, &[&str], Vec<&str>) = match kind {
+            MarkdownBlockKind::CodeFence => {
+                let fence = &lines[0].trim_start()[..3];
+                let has_closing = lines[lines.len() - 1].trim_start().starts_with(fence);
+                let body_end = if has_closing { lines.len() - 1 } else { lines.len() };
+                (vec![lines[0]], &lines[1..body_end], vec![fence])
+            }
+            MarkdownBlockKind::Table if lines.len() > 2 => {
+                (lines[..2].to_vec(), &lines[2..], vec![])
+            }
+            _ => (vec![], lines, vec![]),
+        };
+
+        let frame_tokens = self
+            .token_chunker
+            .estimate_tokens(&[prefix.as_slice(), suffix.as_slice()].concat().join("\n"));
+        let budget = max_token_size.saturating_sub(frame_tokens).max(1);
+
+        let mut pieces: Vec<Vec<&str>> = Vec::new();
+        let mut piece: Vec<&str> = Vec::new();
+        let mut piece_tokens = 0;
+        for &line in body {
+            let line_tokens = self.token_chunker.estimate_tokens(line);
+            // List items may continue on indented lines; only break before a new item
+            let can_break =
+                kind != MarkdownBlockKind::List || self.list_item_pattern.is_match(line);
+            if !piece.is_empty() && piece_tokens + line_tokens > budget && can_break {
+                pieces.push(std::mem::take(&mut piece));
+                piece_tokens = 0;
+            }
+            piece.push(line);
+            piece_tokens += line_tokens;
+        }
+        if !piece.is_empty() {
+            pieces.push(piece);
+        }
+
+        for piece in pieces {
+            let content = [prefix.as_slice(), piece.as_slice(), suffix.as_slice()]
+                .concat()
+                .join("\n");
+            units.push(self.make_unit(content, kind, heading_path, block_index));
+        }
+    }
+
+    /// Split text that exceeds the chunk size on word boundaries
+    fn split_oversized_text(&self, text: &str, max_token_size: usize) -> Vec<String> {
+        if self.token_chunker.estimate_tokens(text) <= max_token_size {
+            return vec![text.to_string()];
+        }
+
+        let mut pieces = Vec::new();
+        let mut current = String::new();
+        for word in text.split(' ') {
+            let candidate = if current.is_empty() {
+                word.to_string()
+            } else {
+                format!("{} {}", current, word)
+            };
+            if !current.is_empty()
+                && self.token_chunker.estimate_tokens(&candidate) > max_token_size
+            {
+                pieces.push(std::mem::replace(&mut current, word.to_string()));
+            } else {
+                current = candidate;
+            }
+        }
+        if !current.is_empty() {
+            pieces.push(current);
+        }
+        pieces
+    }
+
+    fn make_unit(
+        &self,
+        content: String,
+        kind: MarkdownBlockKind,
+        heading_path: &[String],
+        block_index: usize,
+    ) -> ChunkUnit {
+        ChunkUnit {
+            token_count: self.token_chunker.estimate_tokens(&content),
+            content,
+            kind,
+            heading_path: heading_path.to_vec(),
+            block_index,
+        }
+    }
+}
+
+/// Split a paragraph into sentences, keeping the terminating punctuation
+fn split_sentences(text: &str) -> Vec<String> {
+    let mut sentences = Vec::new();
+    let mut current = String::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        current.push(c);
+        if matches!(c, '.' | '!' | '?') {
+            match chars.peek() {
+                Some(next) if next.is_whitespace() => {
+                    let sentence = current.trim();
+                    if !sentence.is_empty() {
+                        sentences.push(sentence.to_string());
+                    }
+                    current.clear();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let rest = current.trim();
+    if !rest.is_empty() {
+        sentences.push(rest.to_string());
+    }
+    sentences
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(units: &[ChunkUnit]) -> Vec<MarkdownBlockKind> {
+        units.iter().map(|u| u.kind).collect()
+    }
+
+    #[test]
+    fn test_split_units_follows_markdown_blocks() {
+        let markdown = "# Intro\n\nFirst sentence here. Second one follows.\n\n## Setup\n\n\
+            ```rust\nfn main() {\n\n    run();\n}\n```\n\n\
+            | a | b |\n|---|---|\n| 1 | 2 |\n\n\
+            - item one\n  continued\n- item two\n";
+        let units = StructureAwareChunker::new().split_units(markdown, 100);
+
+        assert_eq!(
+            kinds(&units),
+            vec![
+                MarkdownBlockKind::Heading,
+                MarkdownBlockKind::Paragraph,
+                MarkdownBlockKind::Paragraph,
+                MarkdownBlockKind::Heading,
+                MarkdownBlockKind::CodeFence,
+                MarkdownBlockKind::Table,
+                MarkdownBlockKind::List,
+            ]
+        );
+        assert_eq!(units[1].content, "First sentence here.");
+        assert_eq!(units[1].block_index, units[2].block_index);
+        // The blank line inside the fence does not end the code block
+        assert_eq!(units[4].content, "```rust\nfn main() {\n\n    run();\n}\n```");
+        assert_eq!(units[4].heading_path, vec!["Intro", "Setup"]);
+        assert_eq!(units[6].content, "- item one\n  continued\n- item two");
+    }
+
+    #[test]
+    fn test_split_units_fills_skipped_heading_levels() {
+        let units = StructureAwareChunker::new().split_units("# Top\n\n### Deep\n\nText.", 100);
+        assert_eq!(units[2].heading_path, vec!["Top", "", "Deep"]);
+
+        let chunks = StructureAwareChunker::new().chunk("# Top\n\n### Deep\n\nText.", 100, 0);
+        assert_eq!(
+            chunks[1].metadata.get("heading_path"),
+            Some(&serde_json::json!(["Top", "Deep"]))
+        );
+    }
+
+    #[test]
+    fn test_push_block_repeats_fences_of_oversized_code() {
+        let markdown = "```python\none two three\nfour five six\nseven eight nine\nten eleven twelve\n```";
+        let units = StructureAwareChunker::new().split_units(markdown, 8);
+
+        assert!(units.len() > 1);
+        for unit in &units {
+            assert_eq!(unit.kind, MarkdownBlockKind::CodeFence);
+            assert!(unit.content.starts_with("```python\n"));
+            assert!(unit.content.ends_with("\n```"));
+            assert!(unit.token_count <= 8);
+        }
+    }
+
+    #[test]
+    fn test_push_block_repeats_header_of_oversized_table() {
+        let markdown = "| name | value |\n|---|---|\n| alpha | one |\n| beta | two |\n| gamma | three |";
+        let units = StructureAwareChunker::new().split_units(markdown, 6);
+
+        assert!(units.len() > 1);
+        for unit in &units {
+            assert_eq!(unit.kind, MarkdownBlockKind::Table);
+            assert!(unit.content.starts_with("| name | value |\n|---|---|\n"));
+        }
+    }
+
+    #[test]
+    fn test_oversized_sentence_is_split_on_words() {
+        let units = StructureAwareChunker::new()
+            .split_units("one two three four five six seven eight nine ten", 4);
+
+        assert_eq!(units.len(), 3);
+        assert!(units.iter().all(|u| u.token_count <= 4));
+        assert_eq!(units[2].content, "nine ten");
+    }
+
+    #[test]
+    fn test_pack_units_starts_chunk_at_heading_without_overlap() {
+        let chunks = StructureAwareChunker::new().chunk("# A\n\nText a.\n\n# B\n\nText b.", 100, 50);
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].content, "# A\n\nText a.");
+        assert_eq!(chunks[1].content, "# B\n\nText b.");
+        assert_eq!(chunks[1].chunk_index, 1);
+    }
+
+    #[test]
+    fn test_pack_units_overlaps_trailing_sentences() {
+        let chunks = StructureAwareChunker::new().chunk(
+            "Alpha beta gamma. Delta epsilon zeta. Eta theta iota.",
+            7,
+            3,
+        );
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].content, "Alpha beta gamma. Delta epsilon zeta.");
+        assert_eq!(chunks[1].content, "Delta epsilon zeta. Eta theta iota.");
+    }
+
+    #[test]
+    fn test_pack_units_splits_at_semantic_breakpoints() {
+        let chunker = StructureAwareChunker::new();
+        let text = "Alpha beta gamma. Delta epsilon zeta. Eta theta iota.";
+
+        let units = chunker.split_units(text, 12);
+        assert_eq!(chunker.pack_units(units.clone(), 12, 0, None).len(), 1);
+
+        let chunks = chunker.pack_units(units, 12, 0, Some(&[false, false, true][..]));
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[1].content, "Eta theta iota.");
+    }
+
+    #[test]
+    fn test_split_sentences_keeps_punctuation_and_decimals() {
+        assert_eq!(
+            split_sentences("First. Second! Third? Version 1.2 stays"),
+            vec!["First.", "Second!", "Third?", "Version 1.2 stays"]
+        );
+        assert!(split_sentences("   ").is_empty());
+    }
+}
@@ -0,0 +1,156 @@
+// Answer generation stage for RAG query results
+
+use crate::ai::core::providers::ChatMessage;
+use crate::ai::rag::types::RAGModels;
+use crate::ai::rag::{
+    RAGAnswer, RAGCitation, RAGErrorCode, RAGQueryingErrorCode, RAGResult, RAGSource,
+};
+use crate::ai::SimplifiedChatRequest;
+use crate::database::queries::files::get_files_by_ids;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+const DEFAULT_ANSWER_SYSTEM_PROMPT: &str = r#"You are a helpful assistant answering questions using only the numbered context passages below.
+Cite the passages that support each statement with their number in square brackets, e.g. [1] or [2][3].
+If the context does not contain the answer, say that you don't know instead of making one up.
+
+{content}"#;
+
+/// Generate an answer for the query from the retrieved sources using the instance's LLM.
+///
+/// `prompt_template` is the instance's post-query template; `{content}` is replaced with the
+/// numbered context and `{history}` with an empty string. `extra_context` is prepended to the
+/// numbered passages (e.g. entity and relationship descriptions from a knowledge graph).
+pub async fn generate_answer(
+    models: &RAGModels,
+    query_text: &str,
+    sources: &[RAGSource],
+    extra_context: Option<&str>,
+    prompt_template: Option<&str>,
+) -> RAGResult<RAGAnswer> {
+    let llm_model = models.llm_model.as_ref().ok_or_else(|| {
+        tracing::error!("Answer generation requested but no LLM model is configured");
+        RAGErrorCode::Querying(RAGQueryingErrorCode::LlmModelUnavailable)
+    })?;
+
+    let filenames = get_source_filenames(sources).await;
+
+    let mut context = String::new();
+    if let Some(extra_context) = extra_context.filter(|c| !c.trim().is_empty()) {
+        context.push_str(extra_context.trim());
+        context.push_str("\n\n");
+    }
+    for (index, source) in sources.iter().enumerate() {
+        let filename = filenames
+            .get(&source.document.file_id)
+            .map(|f| f.as_str())
+            .unwrap_or("unknown");
+        context.push_str(&format!(
+            "[{}] (source: {})\n{}\n\n",
+            index + 1,
+            filename,
+            source.document.content.trim()
+        ));
+    }
+    if sources.is_empty() {
+        context.push_str("No relevant passages were found.\n");
+    }
+
+    let system_prompt = prompt_template
+        .filter(|t| !t.trim().is_empty())
+        .unwrap_or(DEFAULT_ANSWER_SYSTEM_PROMPT)
+        .replace("{content}", context.trim_end())
+        .replace("{history}", "");
+
+    let response = llm_model
+        .chat(SimplifiedChatRequest {
+            messages: vec![
+                ChatMessage::text("system", &system_prompt),
+                ChatMessage::text("user", query_text),
+            ],
+            stream: false,
+            tools: None,
+            parameters: None,
+        })
+        .await
+        .map_err(|e| {
+            tracing::error!("Answer generation failed: {}", e);
+            RAGErrorCode::Querying(RAGQueryingErrorCode::LlmGenerationFailed)
+        })?;
+
+    let text = response.content.trim().to_string();
+    let citations = parse_citation_markers(&text, sources.len())
+        .into_iter()
+        .map(|marker| {
+            let document = &sources[marker - 1].document;
+            RAGCitation {
+                marker,
+                document_id: document.id,
+                file_id: document.file_id,
+                filename: filenames.get(&document.file_id).cloned(),
+                chunk_index: document.chunk_index,
+            }
+        })
+        .collect();
+
+    Ok(RAGAnswer { text, citations })
+}
+
+/// Look up file names for the sources; failures only cost the names, not the answer
+async fn get_source_filenames(sources: &[RAGSource]) -> HashMap<Uuid, String> {
+    let mut file_ids: Vec<Uuid> = sources.iter().map(|s| s.document.file_id).collect();
+    file_ids.sort();
+    file_ids.dedup();
+
+    match get_files_by_ids(file_ids).await {
+        Ok(files) => files.into_iter().map(|f| (f.id, f.filename)).collect(),
+        Err(e) => {
+            tracing::warn!("Failed to fetch file names for citations: {}", e);
+            HashMap::new()
+        }
+    }
+}
+
+/// Collect the distinct `[n]` markers in order of first appearance, ignoring out-of-range ones.
+/// Grouped markers such as `[1, 3]` are also recognized.
+fn parse_citation_markers(text: &str, source_count: usize) -> Vec<usize> {
+    let mut markers = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('[') {
+        rest = &rest[start + 1..];
+        let Some(end) = rest.find(']') else {
+            break;
+        };
+        let inner = &rest[..end];
+        let numbers: Option<Vec<usize>> = inner
+            .split(',')
+            .map(|n| n.trim().parse::<usize>().ok())
+            .collect();
+        if let Some(numbers) = numbers {
+            for number in numbers {
+                if (1..=source_count).contains(&number) && !markers.contains(&number) {
+                    markers.push(number);
+                }
+            }
+        }
+        rest = &rest[end + 1..];
+    }
+    markers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_citation_markers;
+
+    #[test]
+    fn parses_markers_in_order_of_appearance() {
+        let text = "Rust is fast [2]. It is also safe [1][2] and fun [1, 3].";
+        assert_eq!(parse_citation_markers(text, 3), vec![2, 1, 3]);
+    }
+
+    #[test]
+    fn ignores_out_of_range_and_non_numeric_markers() {
+        let text = "See [0], [4], [note] and [2].";
+        assert_eq!(parse_citation_markers(text, 3), vec![2]);
+    }
+}
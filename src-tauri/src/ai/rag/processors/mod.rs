@@ -1,6 +1,8 @@
 // RAG processors
 
 pub mod chunk;
+pub mod generation;
+pub mod rerank;
 pub mod text;
 
-pub use chunk::{ChunkSelector, TokenBasedChunker};
+pub use chunk::{ChunkSelector, StructureAwareChunker, TokenBasedChunker};
@@ -0,0 +1,234 @@
+// Rerank stage for RAG query results
+
+use crate::ai::core::providers::ChatMessage;
+use crate::ai::rag::types::RAGModels;
+use crate::ai::rag::SimpleVectorDocument;
+use crate::ai::{SimplifiedChatRequest, SimplifiedRerankRequest};
+
+/// Maximum characters of each passage sent to the LLM reranker
+const LLM_RERANK_PASSAGE_CHARS: usize = 1200;
+
+/// Result of the rerank stage
+pub struct RerankOutput {
+    pub chunks: Vec<(SimpleVectorDocument, f32)>,
+    pub confidence_score: Option<f32>,
+    pub applied: bool,
+}
+
+/// Rerank candidate chunks with the instance's reranker model, or its LLM when no
+/// reranker is configured. Reranker failures fall back to the original ranking so a
+/// query never fails because of the optional rerank stage.
+pub async fn rerank_chunks(
+    models: &RAGModels,
+    query_text: &str,
+    chunks: Vec<(SimpleVectorDocument, f32)>,
+    final_k: usize,
+    min_rerank_score: f32,
+) -> RerankOutput {
+    if chunks.is_empty() {
+        return RerankOutput {
+            chunks,
+            confidence_score: None,
+            applied: false,
+        };
+    }
+
+    let scores = if let Some(rerank_model) = &models.rerank_model {
+        score_with_reranker(rerank_model.as_ref(), query_text, &chunks).await
+    } else if let Some(llm_model) = &models.llm_model {
+        score_with_llm(llm_model.as_ref(), query_text, &chunks).await
+    } else {
+        tracing::warn!("Reranking enabled but neither a rerank model nor an LLM is configured");
+        None
+    };
+
+    let Some(scores) = scores else {
+        let mut chunks = chunks;
+        chunks.truncate(final_k);
+        return RerankOutput {
+            chunks,
+            confidence_score: None,
+            applied: false,
+        };
+    };
+
+    let original_count = chunks.len();
+    let mut reranked: Vec<(SimpleVectorDocument, f32)> = chunks
+        .into_iter()
+        .zip(scores)
+        .map(|((document, _), score)| (document, score))
+        .filter(|(_, score)| *score >= min_rerank_score)
+        .collect();
+    reranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    reranked.truncate(final_k);
+
+    tracing::debug!(
+        "Reranked {} chunks to {} (min score: {}, final k: {})",
+        original_count,
+        reranked.len(),
+        min_rerank_score,
+        final_k
+    );
+
+    let confidence_score = Some(reranked.first().map(|(_, score)| *score).unwrap_or(0.0));
+    RerankOutput {
+        chunks: reranked,
+        confidence_score,
+        applied: true,
+    }
+}
+
+/// Score chunks with a cross-encoder reranker model, normalized to 0..1
+async fn score_with_reranker(
+    model: &dyn crate::ai::AIModel,
+    query_text: &str,
+    chunks: &[(SimpleVectorDocument, f32)],
+) -> Option<Vec<f32>> {
+    let request = SimplifiedRerankRequest {
+        query: query_text.to_string(),
+        documents: chunks.iter().map(|(d, _)| d.content.clone()).collect(),
+        top_n: None,
+    };
+
+    let response = match model.rerank(request).await {
+        Ok(response) => response,
+        Err(e) => {
+            tracing::warn!("Rerank model failed, keeping original ranking: {}", e);
+            return None;
+        }
+    };
+
+    let mut scores = vec![0.0_f32; chunks.len()];
+    for result in response.results {
+        if let Some(score) = scores.get_mut(result.index) {
+            *score = result.relevance_score;
+        }
+    }
+
+    Some(normalize_reranker_scores(scores))
+}
+
+/// Some cross-encoders return raw logits; squash them into 0..1
+fn normalize_reranker_scores(mut scores: Vec<f32>) -> Vec<f32> {
+    if scores.iter().any(|s| !(0.0..=1.0).contains(s)) {
+        for score in scores.iter_mut() {
+            *score = 1.0 / (1.0 + (-*score).exp());
+        }
+    }
+    scores
+}
+
+/// Score chunks by asking the LLM for a 0-10 relevance score per passage, normalized to 0..1
+async fn score_with_llm(
+    model: &dyn crate::ai::AIModel,
+    query_text: &str,
+    chunks: &[(SimpleVectorDocument, f32)],
+) -> Option<Vec<f32>> {
+    let passages = chunks
+        .iter()
+        .enumerate()
+        .map(|(index, (document, _))| {
+            let content: String = document
+                .content
+                .chars()
+                .take(LLM_RERANK_PASSAGE_CHARS)
+                .collect();
+            format!("[{}] {}", index, content.replace('\n', " "))
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    let prompt = format!(
+        r#"Rate how relevant each passage is to the query on a scale from 0 (irrelevant) to 10 (directly answers the query).
+Return ONLY a JSON array with exactly {count} numbers, one score per passage, in passage order.
+
+Query: {query}
+
+Passages:
+{passages}"#,
+        count = chunks.len(),
+        query = query_text,
+        passages = passages,
+    );
+
+    let response = match model
+        .chat(SimplifiedChatRequest {
+            messages: vec![ChatMessage::text("user", &prompt)],
+            stream: false,
+            tools: None,
+            parameters: None,
+        })
+        .await
+    {
+        Ok(response) => response,
+        Err(e) => {
+            tracing::warn!("LLM rerank failed, keeping original ranking: {}", e);
+            return None;
+        }
+    };
+
+    let scores = parse_llm_scores(&response.content, chunks.len());
+    if scores.is_none() {
+        tracing::warn!("LLM rerank returned an unusable score list, keeping original ranking");
+    }
+    scores
+}
+
+/// Extract the JSON score array from an LLM response and normalize the 0-10 scores to 0..1.
+/// Returns None unless there is exactly one score per passage.
+fn parse_llm_scores(output: &str, expected: usize) -> Option<Vec<f32>> {
+    let start = output.find('[')?;
+    let end = output.rfind(']')?;
+    if end < start {
+        return None;
+    }
+    let scores = serde_json::from_str::<Vec<f32>>(&output[start..=end]).ok()?;
+    if scores.len() != expected {
+        return None;
+    }
+    Some(scores.into_iter().map(|s| (s / 10.0).clamp(0.0, 1.0)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_llm_scores_normalizes_to_unit_range() {
+        let scores = parse_llm_scores("[10, 5, 0, 12, -3]", 5).unwrap();
+        assert_eq!(scores, vec![1.0, 0.5, 0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_parse_llm_scores_ignores_surrounding_text() {
+        let output = "Here are the scores:\n```json\n[7, 2.5]\n```";
+        assert_eq!(parse_llm_scores(output, 2), Some(vec![0.7, 0.25]));
+    }
+
+    #[test]
+    fn test_parse_llm_scores_rejects_malformed_output() {
+        assert_eq!(parse_llm_scores("no scores here", 2), None);
+        assert_eq!(parse_llm_scores("] 3, 4 [", 2), None);
+        assert_eq!(parse_llm_scores("[3, \"high\"]", 2), None);
+        assert_eq!(parse_llm_scores("[3, 4", 2), None);
+    }
+
+    #[test]
+    fn test_parse_llm_scores_rejects_partial_lists() {
+        assert_eq!(parse_llm_scores("[3, 4]", 3), None);
+        assert_eq!(parse_llm_scores("[3, 4, 5, 6]", 3), None);
+    }
+
+    #[test]
+    fn test_normalize_reranker_scores_keeps_probabilities() {
+        assert_eq!(normalize_reranker_scores(vec![0.9, 0.1]), vec![0.9, 0.1]);
+    }
+
+    #[test]
+    fn test_normalize_reranker_scores_squashes_logits() {
+        let scores = normalize_reranker_scores(vec![4.0, 0.0, -4.0]);
+        assert!(scores[0] > 0.98);
+        assert_eq!(scores[1], 0.5);
+        assert!(scores[2] < 0.02);
+    }
+}
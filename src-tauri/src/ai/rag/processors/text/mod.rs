@@ -15,6 +15,8 @@ use validation::TextValidator;
 #[derive(Debug, Clone)]
 pub struct TextProcessingResult {
     pub content: String,
+    /// Extracted markdown with its line structure intact, for structure-aware chunking
+    pub markdown: String,
     pub metadata: HashMap<String, serde_json::Value>,
     pub validation_result: ValidationResult,
     pub quality_score: f32,
@@ -35,14 +37,16 @@ pub async fn extract_text_from_file(file_path: &str) -> RAGResult<TextProcessing
     let sanitizer = create_sanitizer();
     let validator = create_validator();
 
-    // Step 2: Normalize text content
-    content = normalizer.normalize_for_entity_extraction(&content).await?;
+    // Keep an encoding-safe copy of the markdown before normalization collapses whitespace
+    let markdown = sanitizer.sanitize_text(&content).await?;
+
+    // Step 2: Normalize the sanitized content
+    content = normalizer.normalize_for_entity_extraction(&markdown).await?;
     content = normalizer
         .normalize_case_preserving_entities(&content)
         .await?;
 
-    // Step 3: Sanitize text content
-    content = sanitizer.sanitize_text(&content).await?;
+    // Step 3: Clean text content
     content = sanitizer.clean_for_processing(&content).await?;
 
     // Step 4: Validate content and calculate quality score
@@ -99,6 +103,7 @@ pub async fn extract_text_from_file(file_path: &str) -> RAGResult<TextProcessing
 
     Ok(TextProcessingResult {
         content,
+        markdown,
         metadata,
         validation_result: combined_validation,
         quality_score,
@@ -88,9 +88,13 @@ impl RagFileStorage {
         file_path: &Path,
     ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
         let data = self.read_file_bytes(file_path).await?;
+        Ok(self.calculate_checksum_of_bytes(&data))
+    }
+
+    pub fn calculate_checksum_of_bytes(&self, data: &[u8]) -> String {
         let mut hasher = Sha256::new();
-        hasher.update(&data);
+        hasher.update(data);
         let result = hasher.finalize();
-        Ok(format!("{:x}", result))
+        format!("{:x}", result)
     }
 }
@@ -7,6 +7,7 @@ use tokio::time::{sleep, Duration};
 
 use super::processor::process_pending_files;
 use super::queries::reset_processing_files_to_pending;
+use super::reembed::process_embedding_migrations;
 
 /// Simple RAG Service for managing RAG engines
 pub struct RAGService {
@@ -61,6 +62,10 @@ impl RAGService {
                         if let Err(e) = process_pending_files().await {
                             tracing::error!("Error processing pending files: {}", e);
                         }
+                        // Start or resume embedding migrations of instances whose model changed
+                        if let Err(e) = process_embedding_migrations().await {
+                            tracing::error!("Error processing embedding migrations: {}", e);
+                        }
                     }
                 }
             }
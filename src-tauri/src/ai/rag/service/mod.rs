@@ -3,7 +3,9 @@
 pub mod core;
 pub mod processor;
 pub mod queries;
+pub mod reembed;
 
 // Re-export the main service and status types
 pub use core::{RAGService, RAGServiceStatus};
 pub use queries::reset_processing_files_to_pending;
+pub use reembed::cancel_embedding_migration;
@@ -7,7 +7,8 @@ use crate::database::get_database_pool;
 use crate::database::models::rag_instance::RAGInstanceErrorCode;
 use uuid::Uuid;
 
-/// Get unique RAG instance IDs that have pending files and are active
+/// Get unique RAG instance IDs that have pending files and are active.
+/// Instances with an embedding migration in progress are skipped until it completes.
 pub async fn get_rag_instances_with_pending_files() -> RAGResult<Vec<Uuid>> {
     let database = get_database_pool()
         .map_err(|_| RAGErrorCode::Instance(RAGInstanceErrorCode::DatabaseError))?;
@@ -18,6 +19,10 @@ pub async fn get_rag_instances_with_pending_files() -> RAGResult<Vec<Uuid>> {
         JOIN rag_instances ri ON rif.rag_instance_id = ri.id
         WHERE rif.processing_status = $1
         AND ri.is_active = true
+        AND NOT EXISTS (
+            SELECT 1 FROM rag_embedding_migrations rem
+            WHERE rem.rag_instance_id = ri.id AND rem.status IN ('pending', 'running')
+        )
         ORDER BY rif.rag_instance_id
         "#,
         ProcessingStatus::Pending.as_str()
@@ -119,6 +124,10 @@ pub async fn get_pending_files_for_instance(
                processing_error, rag_metadata, created_at, updated_at
         FROM rag_instance_files 
         WHERE processing_status = $2 AND rag_instance_id = $1
+        AND NOT EXISTS (
+            SELECT 1 FROM rag_embedding_migrations
+            WHERE rag_instance_id = $1 AND status IN ('pending', 'running')
+        )
         ORDER BY created_at ASC
         LIMIT 5
         "#,
@@ -0,0 +1,329 @@
+// Background re-embedding of RAG instances whose embedding model changed
+
+use crate::ai::core::providers::EmbeddingsInput;
+use crate::ai::model_manager::model_factory::create_ai_model;
+use crate::ai::rag::{RAGErrorCode, RAGIndexingErrorCode, RAGInstanceErrorCode, RAGResult};
+use crate::ai::SimplifiedEmbeddingsRequest;
+use crate::database::models::{RAGEmbeddingMigration, RAGEmbeddingMigrationStatus};
+use crate::database::queries::rag_embedding_migrations::{
+    cancel_active_rag_embedding_migration, clear_staged_embeddings,
+    complete_rag_embedding_migration, count_processing_files, count_staged_embeddings,
+    fail_rag_embedding_migration, get_documents_pending_reembedding,
+    get_rag_embedding_migration_status, list_active_rag_embedding_migrations,
+    list_files_pending_reembedding, start_rag_embedding_migration, store_staged_embeddings,
+    update_rag_embedding_migration_progress, RAGEmbeddingMigrationCompletion,
+};
+use crate::database::queries::rag_instances::create_rag_instance_index;
+use crate::utils::cancellation::{CancellationToken, CancellationTracker};
+use std::time::Duration;
+use uuid::Uuid;
+
+use super::processor::InstanceThreadRegistry;
+
+/// Number of chunks embedded per request while re-embedding
+const REEMBED_BATCH_SIZE: i64 = 32;
+
+/// How often a migration checks whether the instance's files finished indexing
+const PROCESSING_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long a migration waits for the instance's files to finish indexing before it fails
+const PROCESSING_WAIT_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Cancellation tokens of running migration workers, keyed by migration ID
+static MIGRATION_CANCELLATIONS: once_cell::sync::Lazy<CancellationTracker> =
+    once_cell::sync::Lazy::new(CancellationTracker::new);
+
+/// Outcome of a migration run
+enum MigrationOutcome {
+    Completed,
+    Cancelled,
+}
+
+/// Spawn a worker for every pending or running embedding migration. Migrations interrupted by a
+/// restart are resumed from the chunks that already have a staged embedding.
+pub async fn process_embedding_migrations() -> RAGResult<()> {
+    static MIGRATION_REGISTRY: tokio::sync::OnceCell<InstanceThreadRegistry> =
+        tokio::sync::OnceCell::const_new();
+    let registry = MIGRATION_REGISTRY
+        .get_or_init(|| async { InstanceThreadRegistry::new() })
+        .await;
+
+    let migrations = list_active_rag_embedding_migrations().await.map_err(|e| {
+        tracing::error!("Failed to list embedding migrations: {}", e);
+        RAGErrorCode::Instance(RAGInstanceErrorCode::DatabaseError)
+    })?;
+
+    for migration in migrations {
+        let instance_id = migration.rag_instance_id;
+        if registry.is_instance_active(instance_id).await {
+            continue;
+        }
+
+        registry.register_instance(instance_id).await;
+        tokio::spawn(async move {
+            run_embedding_migration(migration).await;
+            registry.unregister_instance(instance_id).await;
+        });
+    }
+
+    Ok(())
+}
+
+/// Cancel the active embedding migration of an instance. The instance keeps its current model
+/// and index; staged embeddings are discarded by the worker.
+pub async fn cancel_embedding_migration(instance_id: Uuid) -> Result<bool, sqlx::Error> {
+    match cancel_active_rag_embedding_migration(instance_id).await? {
+        Some(migration_id) => {
+            MIGRATION_CANCELLATIONS.cancel(migration_id).await;
+            tracing::info!(
+                "Cancelled embedding migration {} for RAG instance {}",
+                migration_id,
+                instance_id
+            );
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+async fn run_embedding_migration(migration: RAGEmbeddingMigration) {
+    let instance_id = migration.rag_instance_id;
+
+    let token = MIGRATION_CANCELLATIONS.create_token(migration.id).await;
+    let result = match wait_for_processing_files(&migration, &token).await {
+        Ok(true) => reembed_instance(&migration, &token).await,
+        Ok(false) => Ok(MigrationOutcome::Cancelled),
+        Err(e) => Err(e),
+    };
+    MIGRATION_CANCELLATIONS.remove(migration.id).await;
+
+    match result {
+        Ok(MigrationOutcome::Completed) => {
+            // Index creation reads the new model's dimension from the instance
+            if let Err(e) = create_rag_instance_index(instance_id).await {
+                tracing::warn!(
+                    "Failed to recreate index for RAG instance {} after re-embedding: {}",
+                    instance_id,
+                    e
+                );
+            }
+            tracing::info!(
+                "Embedding migration {} completed for RAG instance {}",
+                migration.id,
+                instance_id
+            );
+        }
+        Ok(MigrationOutcome::Cancelled) => {
+            if let Err(e) = clear_staged_embeddings(instance_id).await {
+                tracing::error!("Failed to clear staged embeddings for {}: {}", instance_id, e);
+            }
+            tracing::info!(
+                "Embedding migration {} for RAG instance {} stopped after cancellation",
+                migration.id,
+                instance_id
+            );
+        }
+        Err(e) => {
+            tracing::error!(
+                "Embedding migration {} for RAG instance {} failed: {}",
+                migration.id,
+                instance_id,
+                e
+            );
+            if let Err(update_err) =
+                fail_rag_embedding_migration(migration.id, instance_id, &e.to_string()).await
+            {
+                tracing::error!("Failed to mark embedding migration as failed: {}", update_err);
+            }
+            if let Err(clear_err) = clear_staged_embeddings(instance_id).await {
+                tracing::error!(
+                    "Failed to clear staged embeddings for {}: {}",
+                    instance_id,
+                    clear_err
+                );
+            }
+        }
+    }
+}
+
+/// Let in-flight file indexing finish first; new files wait until the migration is done.
+/// Returns false if the migration was cancelled while waiting.
+async fn wait_for_processing_files(
+    migration: &RAGEmbeddingMigration,
+    token: &CancellationToken,
+) -> RAGResult<bool> {
+    let instance_id = migration.rag_instance_id;
+    let started = std::time::Instant::now();
+
+    loop {
+        if is_cancelled(migration.id, token).await? {
+            return Ok(false);
+        }
+
+        let count = count_processing_files(instance_id)
+            .await
+            .map_err(database_error)?;
+        if count == 0 {
+            return Ok(true);
+        }
+
+        if started.elapsed() >= PROCESSING_WAIT_TIMEOUT {
+            tracing::error!(
+                "Gave up waiting for {} indexing files of RAG instance {} before re-embedding",
+                count,
+                instance_id
+            );
+            return Err(RAGErrorCode::Indexing(RAGIndexingErrorCode::ProcessingTimeout));
+        }
+
+        tracing::info!(
+            "Waiting for {} files of RAG instance {} to finish indexing before re-embedding",
+            count,
+            instance_id
+        );
+        tokio::time::sleep(PROCESSING_POLL_INTERVAL).await;
+    }
+}
+
+/// Re-embed every file of the instance into `embedding_next`, then swap it into the index
+async fn reembed_instance(
+    migration: &RAGEmbeddingMigration,
+    token: &CancellationToken,
+) -> RAGResult<MigrationOutcome> {
+    let instance_id = migration.rag_instance_id;
+
+    let embedding_model = create_ai_model(migration.target_model_id)
+        .await
+        .map_err(|e| {
+            tracing::error!(
+                "Failed to create embedding model {} for re-embedding: {}",
+                migration.target_model_id,
+                e
+            );
+            RAGErrorCode::Indexing(RAGIndexingErrorCode::EmbeddingModelUnavailable)
+        })?;
+
+    // A fresh migration must not pick up vectors staged by an earlier, abandoned one
+    if migration.status == RAGEmbeddingMigrationStatus::Pending {
+        clear_staged_embeddings(instance_id)
+            .await
+            .map_err(database_error)?;
+    }
+
+    let (total, staged) = count_staged_embeddings(instance_id)
+        .await
+        .map_err(database_error)?;
+    start_rag_embedding_migration(migration.id, total, staged)
+        .await
+        .map_err(database_error)?;
+
+    tracing::info!(
+        "Re-embedding RAG instance {} with model {}: {}/{} chunks already staged",
+        instance_id,
+        migration.target_model_id,
+        staged,
+        total
+    );
+
+    loop {
+        let file_ids = list_files_pending_reembedding(instance_id)
+            .await
+            .map_err(database_error)?;
+        if file_ids.is_empty() {
+            // Chunks written after they were staged have no staged embedding; the swap refuses
+            // them and they are re-embedded in another round
+            match complete_rag_embedding_migration(
+                migration.id,
+                instance_id,
+                migration.target_model_id,
+            )
+            .await
+            .map_err(database_error)?
+            {
+                RAGEmbeddingMigrationCompletion::Completed => {
+                    return Ok(MigrationOutcome::Completed)
+                }
+                RAGEmbeddingMigrationCompletion::NotRunning => {
+                    return Ok(MigrationOutcome::Cancelled)
+                }
+                RAGEmbeddingMigrationCompletion::Unstaged => continue,
+            }
+        }
+
+        for file_id in file_ids {
+            loop {
+                if is_cancelled(migration.id, token).await? {
+                    return Ok(MigrationOutcome::Cancelled);
+                }
+
+                let documents =
+                    get_documents_pending_reembedding(instance_id, file_id, REEMBED_BATCH_SIZE)
+                        .await
+                        .map_err(database_error)?;
+                if documents.is_empty() {
+                    break;
+                }
+
+                let request = SimplifiedEmbeddingsRequest {
+                    input: EmbeddingsInput::Multiple(
+                        documents.iter().map(|(_, content)| content.clone()).collect(),
+                    ),
+                    encoding_format: Some("float".to_string()),
+                    dimensions: None,
+                };
+                let response = embedding_model.embeddings(request).await.map_err(|e| {
+                    tracing::error!("Re-embedding failed for file {}: {}", file_id, e);
+                    RAGErrorCode::Indexing(RAGIndexingErrorCode::EmbeddingGenerationFailed)
+                })?;
+
+                if response.data.len() != documents.len() {
+                    tracing::error!(
+                        "Re-embedding returned {} embeddings for {} chunks of file {}",
+                        response.data.len(),
+                        documents.len(),
+                        file_id
+                    );
+                    return Err(RAGErrorCode::Indexing(
+                        RAGIndexingErrorCode::EmbeddingGenerationFailed,
+                    ));
+                }
+
+                let embeddings: Vec<(Uuid, Vec<f32>)> = documents
+                    .into_iter()
+                    .zip(response.data)
+                    .map(|((document_id, _), data)| (document_id, data.embedding))
+                    .collect();
+                store_staged_embeddings(instance_id, &embeddings)
+                    .await
+                    .map_err(database_error)?;
+
+                let (total, staged) = count_staged_embeddings(instance_id)
+                    .await
+                    .map_err(database_error)?;
+                update_rag_embedding_migration_progress(migration.id, instance_id, total, staged)
+                    .await
+                    .map_err(database_error)?;
+            }
+        }
+    }
+}
+
+/// A migration stops when its token fires or its row is no longer pending or running (cancelled
+/// before the worker registered its token, superseded by an update, or the instance was deleted)
+async fn is_cancelled(migration_id: Uuid, token: &CancellationToken) -> RAGResult<bool> {
+    if token.is_cancelled().await {
+        return Ok(true);
+    }
+    let status = get_rag_embedding_migration_status(migration_id)
+        .await
+        .map_err(database_error)?;
+    Ok(!matches!(
+        status,
+        Some(RAGEmbeddingMigrationStatus::Pending | RAGEmbeddingMigrationStatus::Running)
+    ))
+}
+
+fn database_error(e: sqlx::Error) -> RAGErrorCode {
+    tracing::error!("Database error during embedding migration: {}", e);
+    RAGErrorCode::Instance(RAGInstanceErrorCode::DatabaseError)
+}
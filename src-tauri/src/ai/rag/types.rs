@@ -12,6 +12,7 @@ use uuid::Uuid;
 pub struct RAGModels {
     pub embedding_model: Arc<dyn AIModel>,
     pub llm_model: Option<Arc<dyn AIModel>>,
+    pub rerank_model: Option<Arc<dyn AIModel>>,
 }
 
 /// Complete RAG instance information including provider and models
@@ -1,7 +1,7 @@
 // Utility functions for RAG operations
 
 use crate::ai::rag::types::{RAGInstanceInfo, RAGModels};
-use crate::database::models::rag_instance::RAGInstanceErrorCode;
+use crate::database::models::rag_instance::{RAGEngineType, RAGInstanceErrorCode};
 use crate::database::queries::{rag_instances, rag_providers};
 use uuid::Uuid;
 
@@ -41,12 +41,51 @@ pub async fn get_rag_instance_info(
         None
     };
 
+    // 5. Create rerank model if reranking is enabled with a dedicated reranker (optional)
+    let rerank_model_id = match instance.engine_type {
+        RAGEngineType::RagSimpleVector => instance
+            .engine_settings
+            .simple_vector
+            .as_ref()
+            .map(|s| s.querying())
+            .filter(|q| q.enable_rerank())
+            .and_then(|q| q.rerank_model_id),
+        RAGEngineType::RagSimpleGraph => instance
+            .engine_settings
+            .simple_graph
+            .as_ref()
+            .map(|s| s.querying())
+            .filter(|q| q.enable_rerank())
+            .and_then(|q| q.rerank_model_id),
+    };
+
+    // A reranker that cannot be created must not fail the query: reranking falls back to the
+    // LLM, or is skipped
+    let rerank_ai_model = match rerank_model_id {
+        Some(rerank_model_id) => {
+            match crate::ai::model_manager::model_factory::create_ai_model(rerank_model_id).await {
+                Ok(ai_model) => Some(ai_model),
+                Err(e) => {
+                    tracing::warn!(
+                        "Failed to create rerank model {} for RAG instance {}: {}",
+                        rerank_model_id,
+                        instance.id,
+                        e
+                    );
+                    None
+                }
+            }
+        }
+        None => None,
+    };
+
     Ok(RAGInstanceInfo {
         instance,
         provider,
         models: RAGModels {
             embedding_model: embedding_ai_model.into(),
             llm_model: llm_ai_model.map(|m| m.into()),
+            rerank_model: rerank_ai_model.map(|m| m.into()),
         },
     })
 }
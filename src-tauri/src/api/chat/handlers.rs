@@ -22,6 +22,7 @@ use crate::database::queries::chat;
 use super::helpers::send_error;
 use super::streaming::execute_message_stream_loop;
 use super::types::{ChatMessageRequest, ConnectedData, SSEChatStreamEvent};
+use super::utils::resolve_mcp_message_content;
 
 /// Send a message with AI provider integration using SSE streaming
 /// Implements main loop pattern with tool approval flow
@@ -64,6 +65,20 @@ pub async fn send_message_stream(
             }
         };
 
+        // Insert the selected MCP prompt and resources into the new message
+        let mut request = request;
+        if request.message_id.is_none()
+            && (request.mcp_prompt.is_some() || request.mcp_resources.is_some())
+        {
+            match resolve_mcp_message_content(&request, auth_user.user.id).await {
+                Ok(content) => request.content = content,
+                Err(e) => {
+                    send_error(&tx, e.to_string(), ErrorCode::SystemExternalServiceError).await;
+                    return;
+                }
+            }
+        }
+
         // Execute the main streaming loop
         let _ = execute_message_stream_loop(
             tx,
@@ -100,6 +115,18 @@ pub async fn edit_message_stream(
         let connected_event = SSEChatStreamEvent::Connected(ConnectedData {});
         let _ = tx.send(Ok(connected_event.into()));
 
+        // Insert the selected MCP prompt and resources into the edited message
+        let mut request = request;
+        if request.mcp_prompt.is_some() || request.mcp_resources.is_some() {
+            match resolve_mcp_message_content(&request, auth_user.user.id).await {
+                Ok(content) => request.content = content,
+                Err(e) => {
+                    send_error(&tx, e.to_string(), ErrorCode::SystemExternalServiceError).await;
+                    return;
+                }
+            }
+        }
+
         let edit_message = EditMessageRequest {
             content: request.content.clone(),
             file_ids: request.file_ids.clone(),
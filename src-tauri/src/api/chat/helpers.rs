@@ -59,6 +59,7 @@ pub(super) async fn generate_and_update_conversation_title(
                 messages: chat_messages,
                 stream: false,
                 tools: None, // Don't use tools for title generation
+                parameters: None,
             })
             .await
         {
@@ -0,0 +1,206 @@
+//! Replies of image generation models
+//!
+//! Models with the `image_generator` capability do not chat: the last user message is their
+//! prompt. The generated images are stored as files linked to the assistant message and sent
+//! as `generated_image` contents.
+
+use axum::response::sse::Event;
+use std::convert::Infallible;
+use uuid::Uuid;
+
+use crate::ai::core::image_generation::image_extension;
+use crate::ai::{ChatMessage, ContentPart, MessageContent, SimplifiedImageGenerationRequest};
+use crate::api::errors::ErrorCode;
+use crate::api::files::store_file;
+use crate::database::models::{Model, NewTokenUsage, SaveMessageRequest, TokenUsageSource};
+use crate::database::queries::{chat, files, token_usage};
+
+use super::helpers::send_error;
+use super::types::{
+    ChatMessageRequest, GeneratedImageData, NewAssistantMessageData, SSEChatStreamEvent,
+    StreamAIResult,
+};
+
+/// Whether the model answers with images rather than text
+pub(super) fn is_image_generator(model: &Model) -> bool {
+    model
+        .capabilities
+        .as_option()
+        .and_then(|caps| caps.image_generator)
+        .unwrap_or(false)
+}
+
+/// Generate images from the last user message and add them to the assistant message
+pub(super) async fn generate_image_response(
+    tx: &tokio::sync::mpsc::UnboundedSender<Result<Event, Infallible>>,
+    request: &ChatMessageRequest,
+    user_id: Uuid,
+    messages: &[ChatMessage],
+    last_assistant_message_id: Option<Uuid>,
+    candidate_model_ids: &[Uuid],
+    active_branch_id: Option<Uuid>,
+) -> Result<StreamAIResult, Box<dyn std::error::Error + Send + Sync>> {
+    let prompt = last_user_prompt(messages);
+    if prompt.trim().is_empty() {
+        send_error(
+            tx,
+            "Describe the image to generate".to_string(),
+            ErrorCode::ValidInvalidInput,
+        )
+        .await;
+        return Err("Empty image prompt".into());
+    }
+
+    let generated = match crate::ai::generate_images_with_failover(
+        candidate_model_ids,
+        SimplifiedImageGenerationRequest {
+            prompt,
+            n: None,
+            size: None,
+        },
+    )
+    .await
+    {
+        Ok(generated) => generated,
+        Err(e) => {
+            send_error(
+                tx,
+                format!("Error calling AI provider: {}", e),
+                ErrorCode::SystemExternalServiceError,
+            )
+            .await;
+            return Err(e);
+        }
+    };
+    let served_model_id = generated.model.model_id();
+
+    let message_id = match last_assistant_message_id {
+        Some(existing_message_id) => existing_message_id,
+        None => {
+            let assistant_message_req = SaveMessageRequest {
+                conversation_id: request.conversation_id,
+                content: String::new(),
+                role: "assistant".to_string(),
+                model_id: served_model_id,
+                file_ids: None,
+                enabled_tools: request.enabled_tools.clone(),
+                enabled_rag_ids: request.enabled_rag_ids.clone(),
+            };
+
+            match chat::save_message(assistant_message_req, user_id, active_branch_id).await {
+                Ok(assistant_message) => {
+                    let new_asst_msg_event =
+                        SSEChatStreamEvent::NewAssistantMessage(NewAssistantMessageData {
+                            message_id: assistant_message.id,
+                        });
+                    let _ = tx.send(Ok(new_asst_msg_event.into()));
+                    assistant_message.id
+                }
+                Err(e) => {
+                    send_error(
+                        tx,
+                        format!("Error creating assistant message: {}", e),
+                        ErrorCode::SystemDatabaseError,
+                    )
+                    .await;
+                    return Err(e.into());
+                }
+            }
+        }
+    };
+
+    // Count the call against the user's quotas; most image APIs report no tokens
+    if let Err(e) = token_usage::record_token_usage(NewTokenUsage {
+        source: TokenUsageSource::Chat,
+        user_id: Some(user_id),
+        message_id: Some(message_id),
+        model_id: Some(served_model_id),
+        provider_id: Some(generated.model.provider_id()),
+        usage: generated.response.usage,
+    })
+    .await
+    {
+        eprintln!("Warning: Failed to record token usage: {}", e);
+    }
+
+    for (index, image) in generated.response.images.into_iter().enumerate() {
+        let filename = format!(
+            "generated-image-{}.{}",
+            index + 1,
+            image_extension(&image.mime_type)
+        );
+
+        let file = match store_file(user_id, filename, &image.data, None).await {
+            Ok(file) => file,
+            Err(e) => {
+                send_error(
+                    tx,
+                    format!("Error saving generated image: {}", e),
+                    ErrorCode::SystemInternalError,
+                )
+                .await;
+                return Err(e);
+            }
+        };
+
+        let saved = async {
+            files::create_message_file_relationship(message_id, file.id).await?;
+            chat::append_generated_image_content_to_message(
+                message_id,
+                file.id,
+                file.filename.clone(),
+                image.mime_type.clone(),
+                image.revised_prompt.clone(),
+            )
+            .await
+        }
+        .await;
+
+        match saved {
+            Ok(content_id) => {
+                let image_event = SSEChatStreamEvent::GeneratedImage(GeneratedImageData {
+                    message_content_id: content_id,
+                    message_id,
+                    file_id: file.id,
+                    filename: file.filename,
+                    mime_type: image.mime_type,
+                    revised_prompt: image.revised_prompt,
+                });
+                let _ = tx.send(Ok(image_event.into()));
+            }
+            Err(e) => {
+                send_error(
+                    tx,
+                    format!("Error saving generated image: {}", e),
+                    ErrorCode::SystemDatabaseError,
+                )
+                .await;
+                return Err(e.into());
+            }
+        }
+    }
+
+    Ok(StreamAIResult {
+        message_id,
+        tool_call_requests: Vec::new(),
+    })
+}
+
+/// Text of the last user message, the prompt of the images
+fn last_user_prompt(messages: &[ChatMessage]) -> String {
+    let Some(message) = messages.iter().rev().find(|m| m.role == "user") else {
+        return String::new();
+    };
+
+    match &message.content {
+        MessageContent::Text(text) => text.clone(),
+        MessageContent::Multimodal(parts) => parts
+            .iter()
+            .filter_map(|part| match part {
+                ContentPart::Text(text) => Some(text.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+    }
+}
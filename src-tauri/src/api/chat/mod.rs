@@ -7,6 +7,7 @@
 //!
 //! - `types`: SSE event types and request/response structures
 //! - `helpers`: Helper functions (error handling, title generation)
+//! - `image_generation`: Replies of image generation models
 //! - `tool_handling`: Tool approval and execution logic
 //! - `streaming`: Core streaming logic for AI responses
 //! - `handlers`: Public API handlers for chat operations
@@ -18,6 +19,7 @@
 
 mod handlers;
 mod helpers;
+mod image_generation;
 mod streaming;
 mod tool_handling;
 mod types;
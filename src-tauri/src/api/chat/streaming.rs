@@ -5,37 +5,48 @@ use futures_util::StreamExt;
 use std::convert::Infallible;
 use uuid::Uuid;
 
-use crate::ai::SimplifiedChatRequest;
+use crate::ai::core::structured_output;
+use crate::ai::{ChatMessage, MessageContent, SimplifiedChatRequest};
 use crate::api::errors::ErrorCode;
-use crate::database::models::SaveMessageRequest;
+use crate::database::models::{ModelParameters, NewTokenUsage, SaveMessageRequest, TokenUsageSource};
 use crate::database::queries::{
+    assistants::get_assistant_by_id,
     chat,
+    model_groups::get_available_model_group_members,
     models::{get_model_by_id, get_provider_by_model_id},
+    token_usage,
 };
 use super::utils::{build_chat_messages, build_tool_definitions};
 
 use super::helpers::{generate_and_update_conversation_title, send_error};
+use super::image_generation::{generate_image_response, is_image_generator};
 use super::tool_handling::{check_and_handle_pending_approval, handle_tool_request};
 use super::types::{
     ChatMessageRequest, CompleteData, MaxIterationReachedData, MessageContentChunkData,
-    NewAssistantMessageData, NewMessageContentData, NewUserMessageData, SSEChatStreamEvent,
-    StreamAIResult, StreamErrorData,
+    MessageContentRetryData, NewAssistantMessageData, NewMessageContentData, NewUserMessageData,
+    SSEChatStreamEvent, StreamAIResult, StreamErrorData,
 };
 
+/// How many times a reply not matching its JSON Schema is generated again
+const STRUCTURED_OUTPUT_RETRIES: usize = 1;
+
 /// Stream AI response and save to database
 ///
 /// This function handles the interaction with the AI model, including:
 /// - Fetching conversation and model details
 /// - Building chat messages
-/// - Creating AI model instance
-/// - Streaming response chunks
+/// - Starting the stream on the first candidate model that accepts it
+/// - Streaming response chunks, with reasoning streamed as its own contents
 /// - Detecting tool use requests
+/// - Recording token usage
+/// - Checking replies against their JSON Schema, generating them again when they do not match
 /// - Saving content to database
 pub(super) async fn stream_ai_response(
     tx: tokio::sync::mpsc::UnboundedSender<Result<Event, Infallible>>,
     request: ChatMessageRequest,
     user_id: Uuid,
     last_assistant_message_id: Option<Uuid>,
+    candidate_model_ids: &[Uuid],
 ) -> Result<StreamAIResult, Box<dyn std::error::Error + Send + Sync>> {
     // IMPORTANT: Capture the conversation's active branch immediately to prevent
     // race conditions if the user switches branches during streaming
@@ -76,63 +87,55 @@ pub(super) async fn stream_ai_response(
         }
     }
 
-    // Get the model provider configuration directly from model_id
-    let provider = match get_provider_by_model_id(request.model_id).await {
-        Ok(Some(provider)) => {
-            println!("DEBUG: Found provider: {:?}", provider.name);
-            provider
-        }
-        Ok(None) => {
-            let error_event = SSEChatStreamEvent::Error(StreamErrorData {
-                error: "Model or provider not found".to_string(),
-                code: ErrorCode::ResourceModelNotFound.as_str().to_string(),
-            });
-            let _ = tx.send(Ok(error_event.into()));
-            return Err("Model or provider not found".into());
-        }
-        Err(e) => {
-            let error_event = SSEChatStreamEvent::Error(StreamErrorData {
-                error: format!("Error getting model provider: {}", e),
-                code: ErrorCode::SystemDatabaseError.as_str().to_string(),
-            });
-            let _ = tx.send(Ok(error_event.into()));
-            return Err(e.into());
-        }
-    };
+    // Keep the candidates whose model and provider exist and are enabled; the first one left
+    // is the primary model of the turn. Fail only when none is left, with the reason of the
+    // first candidate dropped.
+    let mut available_model_ids = Vec::with_capacity(candidate_model_ids.len());
+    let mut primary_model = None;
+    let mut unavailable: Option<(&str, ErrorCode)> = None;
+    for &candidate_id in candidate_model_ids {
+        let lookup = async {
+            Ok::<_, sqlx::Error>((
+                get_provider_by_model_id(candidate_id).await?,
+                get_model_by_id(candidate_id).await?,
+            ))
+        };
+        let reason = match lookup.await {
+            Ok((Some(provider), Some(model))) if provider.enabled && model.enabled => {
+                available_model_ids.push(candidate_id);
+                primary_model.get_or_insert(model);
+                continue;
+            }
+            Ok((None, _)) => ("Model or provider not found", ErrorCode::ResourceModelNotFound),
+            Ok((Some(provider), _)) if !provider.enabled => {
+                ("Provider is disabled", ErrorCode::ResourceProviderDisabled)
+            }
+            Ok((_, None)) => ("Model not found", ErrorCode::ResourceModelNotFound),
+            Ok(_) => ("Model is disabled", ErrorCode::ResourceModelNotFound),
+            Err(e) => {
+                let error_event = SSEChatStreamEvent::Error(StreamErrorData {
+                    error: format!("Error getting model: {}", e),
+                    code: ErrorCode::SystemDatabaseError.as_str().to_string(),
+                });
+                let _ = tx.send(Ok(error_event.into()));
+                return Err(e.into());
+            }
+        };
+        unavailable.get_or_insert(reason);
+    }
 
-    // Check if provider is enabled
-    if !provider.enabled {
+    let Some(model) = primary_model else {
+        let (error, code) =
+            unavailable.unwrap_or(("Model not found", ErrorCode::ResourceModelNotFound));
         let error_event = SSEChatStreamEvent::Error(StreamErrorData {
-            error: "Provider is disabled".to_string(),
-            code: ErrorCode::ResourceProviderDisabled.as_str().to_string(),
+            error: error.to_string(),
+            code: code.as_str().to_string(),
         });
         let _ = tx.send(Ok(error_event.into()));
-        return Err("Provider is disabled".into());
-    }
-
-    // Get the model to get the actual model name
-    let model = match get_model_by_id(request.model_id).await {
-        Ok(Some(model)) => {
-            println!("DEBUG: Found model: {:?}", model.name);
-            model
-        }
-        Ok(None) => {
-            let error_event = SSEChatStreamEvent::Error(StreamErrorData {
-                error: "Model not found".to_string(),
-                code: ErrorCode::ResourceModelNotFound.as_str().to_string(),
-            });
-            let _ = tx.send(Ok(error_event.into()));
-            return Err("Model not found".into());
-        }
-        Err(e) => {
-            let error_event = SSEChatStreamEvent::Error(StreamErrorData {
-                error: format!("Error getting model: {}", e),
-                code: ErrorCode::SystemDatabaseError.as_str().to_string(),
-            });
-            let _ = tx.send(Ok(error_event.into()));
-            return Err(e.into());
-        }
+        return Err(error.into());
     };
+    let candidate_model_ids = available_model_ids.as_slice();
+    request.model_id = model.id;
 
     // Build chat messages for AI provider using utility function
     let mut messages = match build_chat_messages(&request, user_id).await {
@@ -162,30 +165,6 @@ pub(super) async fn stream_ai_response(
     // Count messages excluding system messages (assistant instructions)
     let user_and_assistant_messages = messages.iter().filter(|m| m.role != "system").count();
 
-    // Create AI model instance using the new simplified API
-    let ai_model =
-        match crate::ai::model_manager::model_factory::create_ai_model(request.model_id).await {
-            Ok(model) => model,
-            Err(e) => {
-                let error_event = SSEChatStreamEvent::Error(StreamErrorData {
-                    error: format!("Error creating AI model: {}", e),
-                    code: ErrorCode::SystemInternalError.as_str().to_string(),
-                });
-                let _ = tx.send(Ok(error_event.into()));
-                return Err(e);
-            }
-        };
-
-    // Check if model supports streaming
-    if !ai_model.supports_streaming() {
-        let error_event = SSEChatStreamEvent::Error(StreamErrorData {
-            error: "Provider does not support streaming responses".to_string(),
-            code: ErrorCode::SystemInternalError.as_str().to_string(),
-        });
-        let _ = tx.send(Ok(error_event.into()));
-        return Err("Provider does not support streaming responses".into());
-    }
-
     // If there's only 1 message (the user message we just added), this is a new conversation
     // Generate title before streaming the response
     if user_and_assistant_messages == 1 {
@@ -195,6 +174,20 @@ pub(super) async fn stream_ai_response(
         let _ = generate_and_update_conversation_title(conversation_id, user_id, &model, &tx).await;
     }
 
+    // Image generation models answer with images rather than text
+    if is_image_generator(&model) {
+        return generate_image_response(
+            &tx,
+            &request,
+            user_id,
+            &messages,
+            last_assistant_message_id,
+            candidate_model_ids,
+            active_branch_id,
+        )
+        .await;
+    }
+
     // Build tool definitions from enabled_tools if provided
     let tools = if let Some(enabled_tools) = &request.enabled_tools {
         match build_tool_definitions(enabled_tools).await {
@@ -208,169 +201,304 @@ pub(super) async fn stream_ai_response(
         None
     };
 
-    // Create or get the assistant message ID BEFORE streaming
-    let message_id = if let Some(existing_message_id) = last_assistant_message_id {
-        // Resuming from previous message
-        existing_message_id
-    } else {
-        // New message - create empty message first
-        let assistant_message_req = SaveMessageRequest {
-            conversation_id: request.conversation_id,
-            content: String::new(), // Empty content initially
-            role: "assistant".to_string(),
-            model_id: request.model_id,
-            file_ids: None,
-            enabled_tools: request.enabled_tools.clone(),
-            enabled_rag_ids: request.enabled_rag_ids.clone(),
+    // Assistants that must return JSON carry the schema of their replies
+    let assistant_json_schema = match get_assistant_by_id(request.assistant_id, Some(user_id)).await {
+        Ok(Some(assistant)) => assistant
+            .parameters
+            .as_option()
+            .and_then(|p| p.json_schema.clone()),
+        _ => None,
+    };
+
+    let mut assistant_message_id = last_assistant_message_id;
+    // Reasoning blocks of the turn: their text, then their signature once closed
+    let mut thinking_blocks: Vec<(String, Option<String>)> = Vec::new();
+    let mut retries_left = STRUCTURED_OUTPUT_RETRIES;
+
+    let (message_id, full_content, tool_uses) = loop {
+        // Start streaming on the first candidate that accepts the request, falling through to the
+        // next model group member on rate limits, server errors and timeouts
+        let failover = match crate::ai::chat_stream_with_failover(
+            candidate_model_ids,
+            SimplifiedChatRequest {
+                messages: messages.clone(),
+                stream: true,
+                tools: tools.clone(),
+                parameters: assistant_json_schema.clone().map(|json_schema| ModelParameters {
+                    json_schema: Some(json_schema),
+                    ..Default::default()
+                }),
+            },
+        )
+        .await
+        {
+            Ok(failover) => failover,
+            Err(e) => {
+                let error_event = SSEChatStreamEvent::Error(StreamErrorData {
+                    error: format!("Error calling AI provider: {}", e),
+                    code: ErrorCode::SystemExternalServiceError.as_str().to_string(),
+                });
+                let _ = tx.send(Ok(error_event.into()));
+                return Err(e);
+            }
         };
+        let served_model_id = failover.model.model_id();
+        let served_provider_id = failover.model.provider_id();
+        // The schema the served model was asked to follow
+        let json_schema = assistant_json_schema
+            .clone()
+            .or_else(|| failover.model.parameters().and_then(|p| p.json_schema.clone()));
+        let mut stream = failover.stream;
+
+        // Create or get the assistant message ID BEFORE processing the stream
+        let message_id = if let Some(existing_message_id) = assistant_message_id {
+            // Resuming from previous message
+            existing_message_id
+        } else {
+            // New message - create empty message first
+            let assistant_message_req = SaveMessageRequest {
+                conversation_id: request.conversation_id,
+                content: String::new(), // Empty content initially
+                role: "assistant".to_string(),
+                model_id: served_model_id,
+                file_ids: None,
+                enabled_tools: request.enabled_tools.clone(),
+                enabled_rag_ids: request.enabled_rag_ids.clone(),
+            };
 
-        match chat::save_message(assistant_message_req, user_id, active_branch_id).await {
-            Ok(assistant_message) => {
-                let asst_msg_id = assistant_message.id;
+            match chat::save_message(assistant_message_req, user_id, active_branch_id).await {
+                Ok(assistant_message) => {
+                    let asst_msg_id = assistant_message.id;
 
-                // Send NewAssistantMessage event
-                let new_asst_msg_event =
-                    SSEChatStreamEvent::NewAssistantMessage(NewAssistantMessageData {
-                        message_id: asst_msg_id,
-                    });
-                let _ = tx.send(Ok(new_asst_msg_event.into()));
+                    // Send NewAssistantMessage event
+                    let new_asst_msg_event =
+                        SSEChatStreamEvent::NewAssistantMessage(NewAssistantMessageData {
+                            message_id: asst_msg_id,
+                        });
+                    let _ = tx.send(Ok(new_asst_msg_event.into()));
 
-                asst_msg_id
-            }
-            Err(e) => {
-                send_error(
-                    &tx,
-                    format!("Error creating assistant message: {}", e),
-                    ErrorCode::SystemDatabaseError,
-                )
-                .await;
-                return Err(e.into());
+                    assistant_message_id = Some(asst_msg_id);
+                    asst_msg_id
+                }
+                Err(e) => {
+                    send_error(
+                        &tx,
+                        format!("Error creating assistant message: {}", e),
+                        ErrorCode::SystemDatabaseError,
+                    )
+                    .await;
+                    return Err(e.into());
+                }
             }
-        }
-    };
+        };
 
-    // Call AI model with streaming
-    match ai_model
-        .chat_stream(SimplifiedChatRequest {
-            messages,
-            stream: true,
-            tools,
-        })
-        .await
-    {
-        Ok(mut stream) => {
-            let mut full_content = String::new();
-            let mut tool_use_option: Option<crate::database::models::ToolUse> = None;
-            let mut message_content_id: Option<Uuid> = None;
-
-            // Process the stream
-            while let Some(chunk_result) = stream.next().await {
-                match chunk_result {
-                    Ok(chunk) => {
-                        if let Some(content) = &chunk.content {
-                            full_content.push_str(content);
-
-                            // Create message_content_id and send NewMessageContent on first chunk
-                            if message_content_id.is_none() {
-                                let content_id = Uuid::new_v4();
-                                message_content_id = Some(content_id);
-
-                                // Send NewMessageContent event BEFORE first chunk
-                                let new_content_event =
-                                    SSEChatStreamEvent::NewMessageContent(NewMessageContentData {
-                                        message_content_id: content_id,
-                                        message_id,
-                                    });
-                                let _ = tx.send(Ok(new_content_event.into()));
-                            }
+        let mut full_content = String::new();
+        let mut tool_uses: Vec<crate::database::models::ToolUse> = Vec::new();
+        let mut message_content_id: Option<Uuid> = None;
+        let mut thinking_content_id: Option<Uuid> = None;
+        let mut usage = None;
+        let mut finished = false;
+
+        // Process the stream
+        while let Some(chunk_result) = stream.next().await {
+            match chunk_result {
+                Ok(chunk) => {
+                    if let Some(thinking) = &chunk.thinking {
+                        // A signed block is closed; further reasoning, or that of a new attempt,
+                        // starts a new one
+                        if thinking_content_id.is_none()
+                            || thinking_blocks.last().map_or(true, |(_, signature)| signature.is_some())
+                        {
+                            let content_id = Uuid::new_v4();
+                            thinking_content_id = Some(content_id);
+                            thinking_blocks.push((String::new(), None));
+
+                            let new_content_event =
+                                SSEChatStreamEvent::NewThinkingContent(NewMessageContentData {
+                                    message_content_id: content_id,
+                                    message_id,
+                                });
+                            let _ = tx.send(Ok(new_content_event.into()));
+                        }
+                        if let Some((text, _)) = thinking_blocks.last_mut() {
+                            text.push_str(thinking);
+                        }
 
-                            // Send chunk to client with content_id
-                            if let Some(content_id) = message_content_id {
-                                let chunk_event = SSEChatStreamEvent::MessageContentChunk(
-                                    MessageContentChunkData {
-                                        message_content_id: content_id,
-                                        delta: content.to_string(),
-                                    },
-                                );
-                                let _ = tx.send(Ok(chunk_event.into()));
-                            }
+                        if let Some(content_id) = thinking_content_id {
+                            let chunk_event = SSEChatStreamEvent::ThinkingContentChunk(
+                                MessageContentChunkData {
+                                    message_content_id: content_id,
+                                    delta: thinking.to_string(),
+                                },
+                            );
+                            let _ = tx.send(Ok(chunk_event.into()));
+                        }
+                    }
+
+                    if let Some(signature) = chunk.thinking_signature {
+                        if let Some((_, block_signature)) = thinking_blocks.last_mut() {
+                            *block_signature = Some(signature);
                         }
+                    }
 
-                        // Check for tool use
-                        if let Some(tool_use) = chunk.tool_use {
-                            tool_use_option = Some(tool_use);
+                    if let Some(content) = &chunk.content {
+                        full_content.push_str(content);
+
+                        // Create message_content_id and send NewMessageContent on first chunk
+                        if message_content_id.is_none() {
+                            let content_id = Uuid::new_v4();
+                            message_content_id = Some(content_id);
+
+                            // Send NewMessageContent event BEFORE first chunk
+                            let new_content_event =
+                                SSEChatStreamEvent::NewMessageContent(NewMessageContentData {
+                                    message_content_id: content_id,
+                                    message_id,
+                                });
+                            let _ = tx.send(Ok(new_content_event.into()));
                         }
 
-                        // Check if streaming is complete
-                        if chunk.finish_reason.is_some() {
-                            break;
+                        // Send chunk to client with content_id
+                        if let Some(content_id) = message_content_id {
+                            let chunk_event = SSEChatStreamEvent::MessageContentChunk(
+                                MessageContentChunkData {
+                                    message_content_id: content_id,
+                                    delta: content.to_string(),
+                                },
+                            );
+                            let _ = tx.send(Ok(chunk_event.into()));
                         }
                     }
-                    Err(e) => {
-                        let error_event = SSEChatStreamEvent::Error(StreamErrorData {
-                            error: format!("Streaming error: {}", e),
-                            code: ErrorCode::SystemStreamingError.as_str().to_string(),
-                        });
-                        let _ = tx.send(Ok(error_event.into()));
-                        return Err(e.into());
+
+                    // Collect tool uses; a turn can request several independent calls
+                    if let Some(tool_use) = chunk.tool_use {
+                        tool_uses.push(tool_use);
                     }
-                }
-            }
 
-            // Save the text content to the message
-            if !full_content.is_empty() {
-                match chat::append_text_content_to_message(message_id, full_content.clone()).await {
-                    Ok(_) => {}
-                    Err(e) => {
-                        send_error(
-                            &tx,
-                            format!("Error saving text content: {}", e),
-                            ErrorCode::SystemDatabaseError,
-                        )
-                        .await;
-                        return Err(e.into());
+                    if chunk.usage.is_some() {
+                        usage = chunk.usage;
+                    }
+                    if chunk.finish_reason.is_some() {
+                        finished = true;
                     }
+
+                    // OpenAI-compatible providers send usage in a last event after the one
+                    // carrying the finish reason, so stop early only once both arrived
+                    if finished && usage.is_some() {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    let error_event = SSEChatStreamEvent::Error(StreamErrorData {
+                        error: format!("Streaming error: {}", e),
+                        code: ErrorCode::SystemStreamingError.as_str().to_string(),
+                    });
+                    let _ = tx.send(Ok(error_event.into()));
+                    return Err(e.into());
                 }
             }
+        }
+
+        // Count the call against the user's quotas, with zero tokens if the provider reported none
+        if let Err(e) = token_usage::record_token_usage(NewTokenUsage {
+            source: TokenUsageSource::Chat,
+            user_id: Some(user_id),
+            message_id: Some(message_id),
+            model_id: Some(served_model_id),
+            provider_id: Some(served_provider_id),
+            usage,
+        })
+        .await
+        {
+            eprintln!("Warning: Failed to record token usage: {}", e);
+        }
 
-            // NOTE: Complete event is sent by the caller (send_message_stream)
-            // after the tool approval loop completes, not here
-
-            // Extract tool call request if present
-            let tool_call_request = if let Some(tool_use) = tool_use_option {
-                // Match the tool_name against enabled_tools to get server_id
-                if let Some(enabled_tools) = &request.enabled_tools {
-                    let matching_tool = enabled_tools.iter().find(|t| t.name == tool_use.name);
-                    if let Some(tool) = matching_tool {
-                        Some(super::types::ToolCallRequest {
-                            server_id: tool.server_id,
-                            tool_name: tool_use.name,
-                            arguments: tool_use.input,
-                        })
-                    } else {
-                        None
+        // Check a final reply against its schema, asking again when it does not match
+        if let Some(schema) = json_schema.as_ref().filter(|_| tool_uses.is_empty()) {
+            if let Some(error) = structured_output::reply_error(schema, &full_content) {
+                if retries_left > 0 {
+                    retries_left -= 1;
+                    if let Some(content_id) = message_content_id {
+                        let retry_event = SSEChatStreamEvent::MessageContentRetry(MessageContentRetryData {
+                            message_content_id: content_id,
+                            error: error.clone(),
+                        });
+                        let _ = tx.send(Ok(retry_event.into()));
                     }
-                } else {
-                    None
+                    messages.push(ChatMessage {
+                        role: "assistant".to_string(),
+                        content: MessageContent::Text(full_content),
+                    });
+                    messages.push(ChatMessage {
+                        role: "user".to_string(),
+                        content: MessageContent::Text(structured_output::retry_prompt(schema, &error)),
+                    });
+                    continue;
                 }
-            } else {
-                None
-            };
+                eprintln!("Warning: Reply does not match its JSON schema: {}", error);
+            }
+        }
 
-            return Ok(StreamAIResult {
-                message_id,
-                tool_call_request,
-            });
+        break (message_id, full_content, tool_uses);
+    };
+
+    // Save the reasoning before the text it led to
+    for (thinking, signature) in thinking_blocks {
+        if thinking.is_empty() {
+            continue;
         }
-        Err(e) => {
-            let error_event = SSEChatStreamEvent::Error(StreamErrorData {
-                error: format!("Error calling AI provider: {}", e),
-                code: ErrorCode::SystemExternalServiceError.as_str().to_string(),
-            });
-            let _ = tx.send(Ok(error_event.into()));
+        if let Err(e) = chat::append_thinking_content_to_message(message_id, thinking, signature).await {
+            send_error(
+                &tx,
+                format!("Error saving thinking content: {}", e),
+                ErrorCode::SystemDatabaseError,
+            )
+            .await;
             return Err(e.into());
         }
     }
+
+    // Save the text content to the message
+    if !full_content.is_empty() {
+        match chat::append_text_content_to_message(message_id, full_content.clone()).await {
+            Ok(_) => {}
+            Err(e) => {
+                send_error(
+                    &tx,
+                    format!("Error saving text content: {}", e),
+                    ErrorCode::SystemDatabaseError,
+                )
+                .await;
+                return Err(e.into());
+            }
+        }
+    }
+
+    // NOTE: Complete event is sent by the caller (send_message_stream)
+    // after the tool approval loop completes, not here
+
+    // Extract tool call requests, matching each tool_name against enabled_tools to get server_id
+    let tool_call_requests = match &request.enabled_tools {
+        Some(enabled_tools) => tool_uses
+            .into_iter()
+            .filter_map(|tool_use| {
+                enabled_tools
+                    .iter()
+                    .find(|t| t.name == tool_use.name)
+                    .map(|tool| super::types::ToolCallRequest {
+                        server_id: tool.server_id,
+                        tool_name: tool_use.name,
+                        arguments: tool_use.input,
+                    })
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+
+    Ok(StreamAIResult {
+        message_id,
+        tool_call_requests,
+    })
 }
 
 /// Execute the main message streaming loop with tool approval support
@@ -389,8 +517,8 @@ pub(super) async fn stream_ai_response(
 /// 1. Main loop (MAX_ITERATIONS times):
 ///    - Check for pending approval (if resuming)
 ///    - Create user message (if should_create_user_message and not resuming)
-///    - Stream AI response
-///    - Handle tool request (if any)
+///    - Check usage quotas, then stream AI response
+///    - Handle tool requests (if any); all calls of a turn run together in the next iteration
 /// 2. Send MaxIterationReached event (if max iterations reached)
 /// 3. Register model access
 /// 4. Send Complete event
@@ -405,6 +533,36 @@ pub(super) async fn execute_message_stream_loop(
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     const MAX_ITERATIONS: usize = 10; // Prevent infinite loops
     let mut iteration = 0;
+
+    // Resolve the models that can serve this request: the model group's members in failover
+    // order, or just the selected model
+    let mut request = request;
+    let candidate_model_ids = match request.model_group_id {
+        Some(group_id) => match get_available_model_group_members(group_id).await {
+            Ok(model_ids) if !model_ids.is_empty() => model_ids,
+            Ok(_) => {
+                send_error(
+                    &tx,
+                    "Model group not found or has no available models".to_string(),
+                    ErrorCode::ResourceModelNotFound,
+                )
+                .await;
+                return Err("Model group not found or has no available models".into());
+            }
+            Err(e) => {
+                send_error(
+                    &tx,
+                    format!("Error getting model group: {}", e),
+                    ErrorCode::SystemDatabaseError,
+                )
+                .await;
+                return Err(e.into());
+            }
+        },
+        None => vec![request.model_id],
+    };
+    request.model_id = candidate_model_ids[0];
+
     let mut last_assistant_message_id: Option<Uuid> = resume_from_message_id;
     let is_resuming = resume_from_message_id.is_some();
 
@@ -495,9 +653,35 @@ pub(super) async fn execute_message_stream_loop(
         }
 
         // ----------------------------------------
-        // 3. Stream AI response (saves message and returns result)
+        // 3. Stream AI response (saves message and returns result), unless a usage quota of
+        //    the user or one of their groups is reached
         // ----------------------------------------
-        let result = match stream_ai_response(tx.clone(), request.clone(), user_id, last_assistant_message_id).await {
+        match token_usage::find_reached_user_quota(user_id).await {
+            Ok(Some(status)) => {
+                send_error(&tx, status.reached_message(), ErrorCode::ResourceQuotaExceeded).await;
+                return Err(status.reached_message().into());
+            }
+            Ok(None) => {}
+            Err(e) => {
+                send_error(
+                    &tx,
+                    format!("Failed to check usage quotas: {}", e),
+                    ErrorCode::SystemDatabaseError,
+                )
+                .await;
+                return Err(e.into());
+            }
+        }
+
+        let result = match stream_ai_response(
+            tx.clone(),
+            request.clone(),
+            user_id,
+            last_assistant_message_id,
+            &candidate_model_ids,
+        )
+        .await
+        {
             Ok(result) => result,
             Err(e) => {
                 // Error already sent by stream_ai_response
@@ -508,15 +692,25 @@ pub(super) async fn execute_message_stream_loop(
         last_assistant_message_id = Some(result.message_id);
 
         // ----------------------------------------
-        // 4. Check if AI requests tool call
+        // 4. Check if AI requests tool calls
         // ----------------------------------------
-        if let Some(tool_request) = result.tool_call_request {
-            if !handle_tool_request(tool_request, result.message_id, request.conversation_id, &tx).await {
-                // Failed to handle tool request, error already sent
-                return Err("Failed to handle tool request".into());
+        if !result.tool_call_requests.is_empty() {
+            for tool_request in result.tool_call_requests {
+                if !handle_tool_request(
+                    tool_request,
+                    result.message_id,
+                    request.conversation_id,
+                    user_id,
+                    &tx,
+                )
+                .await
+                {
+                    // Failed to handle tool request, error already sent
+                    return Err("Failed to handle tool request".into());
+                }
             }
 
-            // Continue loop - approval will be checked in next iteration
+            // Continue loop - approvals will be checked and the calls run in next iteration
             continue;
         }
 
@@ -1,20 +1,55 @@
 //! Tool approval and execution handling
 
 use axum::response::sse::Event;
+use futures_util::future::join_all;
+use std::collections::HashMap;
 use std::convert::Infallible;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
 use uuid::Uuid;
 
+use crate::ai::mcp::client_requests::{enter_request_context, MCPRequestContext};
+use crate::ai::mcp::roots::sync_server_roots;
+use crate::ai::mcp::tool_executor::{
+    execute_mcp_tool_with_progress, MCPToolExecutionError, MCPToolExecutionResult,
+};
 use crate::api::errors::ErrorCode;
-use crate::database::models::{MessageContentData, MessageContentType};
-use crate::database::queries::chat;
+use crate::database::models::{MCPExecutionStatus, MessageContentData, MessageContentType};
+use crate::database::queries::{chat, mcp_execution_logs, mcp_tool_approvals};
+use crate::utils::cancellation::{
+    create_cancellation_token, remove_download_tracking, CancellationToken,
+};
 
 use super::helpers::send_error;
 use super::types::{
     NewMessageContentData, SSEChatStreamEvent, ToolCallData, ToolCallPendingApprovalData,
-    ToolCallRequest, ToolResultData,
+    ToolCallRequest, ToolProgressData, ToolResultData,
 };
 
-/// Check if the last message needs approval and handle it
+/// Most calls of one turn that run against the same MCP server at a time
+const MAX_CONCURRENT_CALLS_PER_SERVER: usize = 4;
+
+/// Tool call requested by the AI, read back from its pending approval
+struct PendingToolCall {
+    tool_name: String,
+    server_id: Uuid,
+    arguments: serde_json::Value,
+    approved: bool,
+    /// Approval rule that denied the call, e.g. "rule:No deletes"
+    denied_by: Option<String>,
+}
+
+/// Outcome of one tool call, saved as its ToolResult
+struct ToolCallOutcome {
+    result: serde_json::Value,
+    success: bool,
+    error_message: Option<String>,
+    cancelled: bool,
+}
+
+/// Check if the last message has tool calls waiting for approval and run them once all are
+/// decided. The calls of one turn are the trailing ToolCallPendingApproval contents of the
+/// last assistant message.
 /// Returns (needs_approval, should_continue_loop)
 pub(super) async fn check_and_handle_pending_approval(
     conversation_id: Uuid,
@@ -24,81 +59,38 @@ pub(super) async fn check_and_handle_pending_approval(
 ) -> Result<(bool, bool), Box<dyn std::error::Error + Send + Sync>> {
     let messages = chat::get_conversation_messages(conversation_id, user_id).await?;
 
-    let last_message = messages.last();
-    let needs_approval = if let Some(msg) = last_message {
-        if msg.role != "assistant" {
-            false
-        } else {
-            // Sort contents by sequence_order and check if the LAST one is ToolCallPendingApproval
-            let mut sorted_contents = msg.contents.clone();
-            sorted_contents.sort_by_key(|c| c.sequence_order);
-
-            sorted_contents
-                .last()
-                .map(|c| c.content_type == MessageContentType::ToolCallPendingApproval)
-                .unwrap_or(false)
-        }
-    } else {
-        false
-    };
-
-    if !needs_approval {
+    let Some(last_msg) = messages.last().filter(|msg| msg.role == "assistant") else {
         return Ok((false, true)); // No approval needed, continue
-    }
-
-    let last_msg = last_message.unwrap();
+    };
 
-    // Sort contents and get the last one (which should be ToolCallPendingApproval)
+    // Sort contents by sequence_order and take the ToolCallPendingApproval run at the end
     let mut sorted_contents = last_msg.contents.clone();
     sorted_contents.sort_by_key(|c| c.sequence_order);
-    let pending_content = sorted_contents.last();
+    let pending_start = sorted_contents
+        .iter()
+        .rposition(|c| c.content_type != MessageContentType::ToolCallPendingApproval)
+        .map_or(0, |index| index + 1);
+    let pending_contents = &sorted_contents[pending_start..];
+
+    if pending_contents.is_empty() {
+        return Ok((false, true)); // No approval needed, continue
+    }
 
-    if let Some(content) = pending_content {
+    let mut calls = Vec::with_capacity(pending_contents.len());
+    let mut any_undecided = false;
+    for content in pending_contents {
         // Try to parse the pending approval data
         let tool_data: Result<MessageContentData, _> = serde_json::from_value(
             serde_json::to_value(&content.content).unwrap_or_default(),
         );
 
-        if let Ok(MessageContentData::ToolCallPendingApproval {
+        let Ok(MessageContentData::ToolCallPendingApproval {
             tool_name,
             server_id,
             arguments,
             is_approved,
         }) = tool_data
-        {
-            // Check if approved - use the database value if already set, otherwise check approval
-            let is_approved = if is_approved == Some(true) {
-                true
-            } else {
-                chat::check_tool_approval(conversation_id, server_id, &tool_name).await?
-            };
-
-            if !is_approved {
-                // Not approved yet - stop and wait for approval
-                // Note: The approval event was already sent when the tool was first requested
-                // by handle_tool_request(), so we don't send it again here
-                return Ok((true, false)); // Needs approval, stop loop
-            }
-
-            // Approved! Execute tool
-            *last_assistant_message_id = Some(last_msg.id);
-
-            if let Err(e) =
-                execute_tool_and_save_result(last_msg.id, server_id, &tool_name, &arguments, tx)
-                    .await
-            {
-                send_error(
-                    tx,
-                    format!("Tool execution failed: {}", e),
-                    ErrorCode::SystemInternalError,
-                )
-                .await;
-                return Ok((true, false)); // Don't continue loop
-            }
-
-            // Continue to next iteration
-            return Ok((true, true));
-        } else {
+        else {
             send_error(
                 tx,
                 "Invalid pending approval data".to_string(),
@@ -106,10 +98,69 @@ pub(super) async fn check_and_handle_pending_approval(
             )
             .await;
             return Ok((true, false));
-        }
+        };
+
+        // Check if approved - use the database value if already set, otherwise check approvals.
+        // Approval rules are always evaluated: a denying rule wins even over a stored approval,
+        // and a user's denial stands over approving rules.
+        let (approved, denied_by) = match (
+            is_approved,
+            mcp_tool_approvals::check_tool_approval(
+                user_id,
+                conversation_id,
+                server_id,
+                &tool_name,
+                Some(&arguments),
+            )
+            .await?,
+        ) {
+            (_, Some((false, source))) => (false, Some(source)),
+            (Some(true), _) | (None, Some((true, _))) => (true, None),
+            _ => (false, None),
+        };
+        any_undecided |= !approved && denied_by.is_none() && is_approved.is_none();
+
+        calls.push(PendingToolCall {
+            tool_name,
+            server_id,
+            arguments,
+            approved,
+            denied_by,
+        });
+    }
+
+    if any_undecided {
+        // Not all calls are decided yet - stop and wait for the user
+        // Note: The approval events were already sent when the tools were first requested
+        // by handle_tool_request(), so we don't send them again here
+        return Ok((true, false)); // Needs approval, stop loop
+    }
+
+    // All decided! Execute approved tools; denied ones are reported back to the AI
+    *last_assistant_message_id = Some(last_msg.id);
+
+    if let Err(e) = execute_tool_calls(
+        last_msg.id,
+        MCPRequestContext {
+            user_id,
+            conversation_id: Some(conversation_id),
+        },
+        calls,
+        tx,
+    )
+    .await
+    {
+        send_error(
+            tx,
+            format!("Tool execution failed: {}", e),
+            ErrorCode::SystemInternalError,
+        )
+        .await;
+        return Ok((true, false)); // Don't continue loop
     }
 
-    Ok((false, true))
+    // Continue to next iteration
+    Ok((true, true))
 }
 
 /// Handle tool call request from AI by saving pending approval and sending events
@@ -118,14 +169,23 @@ pub(super) async fn handle_tool_request(
     tool_request: ToolCallRequest,
     message_id: Uuid,
     conversation_id: Uuid,
+    user_id: Uuid,
     tx: &tokio::sync::mpsc::UnboundedSender<Result<Event, Infallible>>,
 ) -> bool {
-    // Check if tool is already approved in the database
-    let is_already_approved = match chat::check_tool_approval(conversation_id, tool_request.server_id, &tool_request.tool_name).await {
-        Ok(approved) => approved,
+    // Check if tool is already approved (or denied by an approval rule)
+    let decision = match mcp_tool_approvals::check_tool_approval(
+        user_id,
+        conversation_id,
+        tool_request.server_id,
+        &tool_request.tool_name,
+        Some(&tool_request.arguments),
+    )
+    .await
+    {
+        Ok(decision) => decision.map(|(approved, _)| approved),
         Err(e) => {
             eprintln!("Warning: Failed to check tool approval: {}", e);
-            false // Proceed with normal flow on error
+            None // Proceed with normal flow on error
         }
     };
 
@@ -134,13 +194,13 @@ pub(super) async fn handle_tool_request(
         tool_name: tool_request.tool_name.clone(),
         server_id: tool_request.server_id,
         arguments: tool_request.arguments.clone(),
-        is_approved: if is_already_approved { Some(true) } else { None },
+        is_approved: decision,
     };
 
     match chat::save_pending_tool_approval_content(message_id, pending_content).await {
         Ok(message_content_id) => {
-            // Only send approval events if tool is not already approved
-            if !is_already_approved {
+            // Only send approval events if the tool call is not decided yet
+            if decision.is_none() {
                 // Send NewMessageContent event
                 let new_content_event = SSEChatStreamEvent::NewMessageContent(NewMessageContentData {
                     message_content_id,
@@ -160,8 +220,8 @@ pub(super) async fn handle_tool_request(
                 let _ = tx.send(Ok(approval_event.into()));
             }
 
-            // NOTE: ToolCall event is sent AFTER approval in execute_tool_and_save_result
-            // Tool execution happens in the next iteration via check_and_handle_pending_approval
+            // NOTE: ToolCall event is sent once every call of the turn is decided, in
+            // execute_tool_calls via check_and_handle_pending_approval in the next iteration
             true
         }
         Err(e) => {
@@ -176,22 +236,108 @@ pub(super) async fn handle_tool_request(
     }
 }
 
-/// Execute MCP tool and save result to DB (MOCK EXECUTION FOR NOW)
-async fn execute_tool_and_save_result(
+/// Run a turn's tool calls and save their results to DB
+///
+/// Every call is saved as a ToolCall first. Approved calls then run concurrently, at most
+/// MAX_CONCURRENT_CALLS_PER_SERVER at a time per server, and the results are saved in the
+/// order the AI requested the calls. Denied calls (by the user or an approval rule) get a
+/// failed result, so the AI learns they did not run.
+async fn execute_tool_calls(
     message_id: Uuid,
-    server_id: Uuid,
-    tool_name: &str,
-    arguments: &serde_json::Value,
+    context: MCPRequestContext,
+    calls: Vec<PendingToolCall>,
     tx: &tokio::sync::mpsc::UnboundedSender<Result<Event, Infallible>>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    // Generate call_id for this tool execution
-    let call_id = Uuid::new_v4().to_string();
+    // Save ToolCall contents in the requested order before anything runs
+    let mut call_ids = Vec::with_capacity(calls.len());
+    for call in &calls {
+        let call_uuid = Uuid::new_v4();
+        let tool_call_content_id = save_tool_call(message_id, call, call_uuid, tx).await?;
+        call_ids.push((call_uuid, tool_call_content_id));
+    }
+
+    let mut server_limits: HashMap<Uuid, Arc<Semaphore>> = HashMap::new();
+    let executions = calls.iter().zip(&call_ids).map(|(call, &(call_uuid, tool_call_content_id))| {
+        let server_limit = server_limits
+            .entry(call.server_id)
+            .or_insert_with(|| Arc::new(Semaphore::new(MAX_CONCURRENT_CALLS_PER_SERVER)))
+            .clone();
+
+        async move {
+            if !call.approved {
+                return ToolCallOutcome {
+                    result: serde_json::json!({ "error": "not_approved" }),
+                    success: false,
+                    error_message: Some(match &call.denied_by {
+                        Some(source) => format!("Tool execution denied by approval {}", source),
+                        None => "User denied tool execution".to_string(),
+                    }),
+                    cancelled: false,
+                };
+            }
+
+            let _permit = server_limit.acquire().await;
+            execute_tool_call(message_id, context, call, call_uuid, tool_call_content_id, tx).await
+        }
+    });
+    let outcomes = join_all(executions).await;
+
+    // Save ToolResult contents in the requested order
+    let mut cancelled = false;
+    for ((call_uuid, _), outcome) in call_ids.into_iter().zip(outcomes) {
+        cancelled |= outcome.cancelled;
+
+        let call_id = call_uuid.to_string();
+        let result_content = MessageContentData::ToolResult {
+            call_id: call_id.clone(),
+            result: outcome.result.clone(),
+            success: outcome.success,
+            error_message: outcome.error_message.clone(),
+        };
+
+        // Save to database using query function and get the content_id
+        let message_content_id = chat::save_tool_result_content(message_id, result_content).await?;
+
+        // Send NewMessageContent event
+        let new_content_event = SSEChatStreamEvent::NewMessageContent(NewMessageContentData {
+            message_content_id,
+            message_id,
+        });
+        let _ = tx.send(Ok(new_content_event.into()));
+
+        // Send ToolResult event
+        let event = SSEChatStreamEvent::ToolResult(ToolResultData {
+            message_content_id,
+            message_id,
+            call_id,
+            result: outcome.result,
+            success: outcome.success,
+            error_message: outcome.error_message,
+        });
+        let _ = tx.send(Ok(event.into()));
+    }
+
+    if cancelled {
+        return Err(Box::new(MCPToolExecutionError::Cancelled));
+    }
+
+    Ok(())
+}
+
+/// Save a ToolCall content and announce it to the client
+async fn save_tool_call(
+    message_id: Uuid,
+    call: &PendingToolCall,
+    call_uuid: Uuid,
+    tx: &tokio::sync::mpsc::UnboundedSender<Result<Event, Infallible>>,
+) -> Result<Uuid, Box<dyn std::error::Error + Send + Sync>> {
+    let call_id = call_uuid.to_string();
 
     // Save ToolCall content to database
     let tool_call_content = MessageContentData::ToolCall {
-        tool_name: tool_name.to_string(),
-        server_id,
-        arguments: arguments.clone(),
+        tool_name: call.tool_name.clone(),
+        server_id: call.server_id,
+        arguments: call.arguments.clone(),
         call_id: call_id.clone(),
     };
 
@@ -205,29 +351,105 @@ async fn execute_tool_and_save_result(
     });
     let _ = tx.send(Ok(new_content_event.into()));
 
-    // Send ToolCall event (tool is approved and being executed)
+    // Send ToolCall event
     let tool_call_event = SSEChatStreamEvent::ToolCall(ToolCallData {
         message_content_id: tool_call_content_id,
         message_id,
-        tool_name: tool_name.to_string(),
-        server_id,
-        arguments: arguments.clone(),
-        call_id: call_id.clone(),
+        tool_name: call.tool_name.clone(),
+        server_id: call.server_id,
+        arguments: call.arguments.clone(),
+        call_id,
     });
     let _ = tx.send(Ok(tool_call_event.into()));
 
-    // Execute tool via MCP
+    Ok(tool_call_content_id)
+}
+
+/// Execute one MCP tool call, forwarding its progress to the client
+async fn execute_tool_call(
+    message_id: Uuid,
+    context: MCPRequestContext,
+    call: &PendingToolCall,
+    call_uuid: Uuid,
+    tool_call_content_id: Uuid,
+    tx: &tokio::sync::mpsc::UnboundedSender<Result<Event, Infallible>>,
+) -> ToolCallOutcome {
+    let server_id = call.server_id;
+    let tool_name = call.tool_name.as_str();
+    let call_id = call_uuid.to_string();
+
+    // Requests the server sends during the call (sampling, roots) run for this user
+    let _request_context = enter_request_context(server_id, context);
+    sync_server_roots(server_id, context).await;
+
+    // Log the execution; it can be cancelled through the execution log endpoint
+    let execution_id = match mcp_execution_logs::create_execution_log(
+        context.user_id,
+        server_id,
+        context.conversation_id,
+        tool_name.to_string(),
+        Some(call.arguments.clone()),
+        Some(call_uuid),
+    )
+    .await
+    {
+        Ok(execution_id) => execution_id,
+        Err(e) => {
+            tracing::error!("Failed to create execution log for tool '{}': {}", tool_name, e);
+            return ToolCallOutcome {
+                result: serde_json::json!({ "error": "execution_failed" }),
+                success: false,
+                error_message: Some(format!("Tool execution failed: {}", e)),
+                cancelled: false,
+            };
+        }
+    };
+    let cancellation_token = create_cancellation_token(execution_id).await;
+
+    // Execute tool via MCP, forwarding progress until it completes or the user cancels
+    // (closing the chat stream or cancelling the execution)
     let start_time = std::time::Instant::now();
 
-    let execution_result = crate::ai::mcp::tool_executor::execute_mcp_tool(
+    let execution_result = execute_mcp_tool_with_progress(
         server_id,
+        Some(context.user_id),
         tool_name.to_string(),
-        arguments.clone(),
+        call.arguments.clone(),
+        call_uuid,
+        |progress| {
+            let progress_event = SSEChatStreamEvent::ToolProgress(ToolProgressData {
+                message_content_id: tool_call_content_id,
+                message_id,
+                call_id: call_id.clone(),
+                progress: progress.progress,
+                total: progress.total,
+                message: progress.message,
+            });
+            let _ = tx.send(Ok(progress_event.into()));
+        },
+        async {
+            tokio::select! {
+                _ = tx.closed() => {}
+                _ = wait_for_cancellation(&cancellation_token) => {}
+            }
+        },
     )
     .await;
 
+    remove_download_tracking(execution_id).await;
+
     let duration_ms = start_time.elapsed().as_millis() as i64;
 
+    complete_execution_log(execution_id, &execution_result, duration_ms).await;
+
+    let cancelled = matches!(
+        &execution_result,
+        Err(e) if matches!(
+            e.downcast_ref::<MCPToolExecutionError>(),
+            Some(MCPToolExecutionError::Cancelled)
+        )
+    );
+
     // Handle result
     let (result, success, error_message) = match execution_result {
         Ok(exec_result) => {
@@ -257,42 +479,66 @@ async fn execute_tool_and_save_result(
         }
     };
 
-    // Prepare result value for both database and SSE event
-    let result_value = result.clone().unwrap_or_else(|| {
-        serde_json::json!({
-            "error": "execution_failed",
-            "duration_ms": duration_ms
-        })
-    });
-
-    // Save ToolResult content to message
-    let result_content = MessageContentData::ToolResult {
-        call_id: call_id.clone(),
-        result: result_value.clone(),
+    ToolCallOutcome {
+        result: result.unwrap_or_else(|| {
+            serde_json::json!({
+                "error": "execution_failed",
+                "duration_ms": duration_ms
+            })
+        }),
         success,
-        error_message: error_message.clone(),
-    };
+        error_message,
+        cancelled,
+    }
+}
 
-    // Save to database using query function and get the content_id
-    let message_content_id = chat::save_tool_result_content(message_id, result_content).await?;
+/// Resolves once the execution's cancellation token fires
+async fn wait_for_cancellation(token: &CancellationToken) {
+    while !token.is_cancelled().await {
+        tokio::time::sleep(std::time::Duration::from_millis(250)).await;
+    }
+}
 
-    // Send NewMessageContent event
-    let new_content_event = SSEChatStreamEvent::NewMessageContent(NewMessageContentData {
-        message_content_id,
-        message_id,
-    });
-    let _ = tx.send(Ok(new_content_event.into()));
+/// Record the outcome of a tool call in its execution log
+async fn complete_execution_log(
+    execution_id: Uuid,
+    execution_result: &Result<MCPToolExecutionResult, Box<dyn std::error::Error + Send + Sync>>,
+    duration_ms: i64,
+) {
+    let (status, result, error_message, error_code) = match execution_result {
+        Ok(exec_result) if exec_result.success => (
+            MCPExecutionStatus::Completed,
+            exec_result.result.clone(),
+            None,
+            None,
+        ),
+        Ok(exec_result) => (
+            MCPExecutionStatus::Failed,
+            None,
+            exec_result.error_message.clone(),
+            exec_result.error_code.clone(),
+        ),
+        Err(e) => match e.downcast_ref::<MCPToolExecutionError>() {
+            Some(MCPToolExecutionError::Cancelled) => (
+                MCPExecutionStatus::Cancelled,
+                None,
+                Some("Cancelled by user".to_string()),
+                Some("USER_CANCELLED".to_string()),
+            ),
+            _ => (MCPExecutionStatus::Failed, None, Some(e.to_string()), None),
+        },
+    };
 
-    // Send ToolResult event
-    let event = SSEChatStreamEvent::ToolResult(ToolResultData {
-        message_content_id,
-        message_id,
-        call_id,
-        result: result_value,
-        success,
+    if let Err(e) = mcp_execution_logs::complete_execution_log(
+        execution_id,
+        status,
+        result,
         error_message,
-    });
-    let _ = tx.send(Ok(event.into()));
-
-    Ok(())
+        error_code,
+        Some(duration_ms as i32),
+    )
+    .await
+    {
+        tracing::error!("Failed to complete execution log {}: {}", execution_id, e);
+    }
 }
@@ -5,7 +5,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::database::models::{Message, MessageBranch};
-use crate::api::chat::utils::EnabledMCPTool;
+use crate::api::chat::utils::{EnabledMCPTool, SelectedMCPPrompt, SelectedMCPResource};
 
 /// Request structure for sending/editing chat messages
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -13,10 +13,13 @@ pub struct ChatMessageRequest {
     pub conversation_id: Uuid,
     pub content: String,
     pub model_id: Uuid,
+    pub model_group_id: Option<Uuid>,        // Optional model group; its members replace model_id, in failover order
     pub assistant_id: Uuid,
     pub file_ids: Option<Vec<Uuid>>,         // Optional file attachments
     pub enabled_tools: Option<Vec<EnabledMCPTool>>, // Optional MCP tools to send to AI
     pub enabled_rag_ids: Option<Vec<Uuid>>,  // Optional RAG instances to query
+    pub mcp_prompt: Option<SelectedMCPPrompt>, // Optional MCP prompt inserted before the content
+    pub mcp_resources: Option<Vec<SelectedMCPResource>>, // Optional MCP resources attached to the message
     pub message_id: Option<Uuid>,            // Optional message ID to resume from
 }
 
@@ -63,6 +66,25 @@ pub struct MessageContentChunkData {
     pub delta: String,
 }
 
+/// The streamed text did not match the JSON Schema the reply must follow; it is dropped and
+/// the reply generated again
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct MessageContentRetryData {
+    pub message_content_id: Uuid,
+    pub error: String,
+}
+
+/// Image generated for the reply, stored as a file of the message
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct GeneratedImageData {
+    pub message_content_id: Uuid,
+    pub message_id: Uuid,
+    pub file_id: Uuid,
+    pub filename: String,
+    pub mime_type: String,
+    pub revised_prompt: Option<String>,
+}
+
 // Tool-related events
 #[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct ToolCallData {
@@ -88,6 +110,17 @@ pub struct ToolCallPendingApprovalCancelData {
     pub message_content_id: Uuid,
 }
 
+/// Progress reported by the MCP server while a tool runs
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ToolProgressData {
+    pub message_content_id: Uuid,
+    pub message_id: Uuid,
+    pub call_id: String,
+    pub progress: f64,
+    pub total: Option<f64>,
+    pub message: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct ToolResultData {
     pub message_content_id: Uuid,
@@ -122,9 +155,14 @@ crate::sse_event_enum! {
         NewAssistantMessage(NewAssistantMessageData),
         NewMessageContent(NewMessageContentData),
         MessageContentChunk(MessageContentChunkData),
+        NewThinkingContent(NewMessageContentData),
+        ThinkingContentChunk(MessageContentChunkData),
+        MessageContentRetry(MessageContentRetryData),
+        GeneratedImage(GeneratedImageData),
         ToolCall(ToolCallData),
         ToolCallPendingApproval(ToolCallPendingApprovalData),
         ToolCallPendingApprovalCancel(ToolCallPendingApprovalCancelData),
+        ToolProgress(ToolProgressData),
         ToolResult(ToolResultData),
         TitleUpdated(TitleUpdatedData),
         MaxIterationReached(MaxIterationReachedData),
@@ -142,7 +180,7 @@ crate::sse_event_enum! {
 /// Result from streaming AI response
 pub(super) struct StreamAIResult {
     pub message_id: Uuid,
-    pub tool_call_request: Option<ToolCallRequest>,
+    pub tool_call_requests: Vec<ToolCallRequest>,
 }
 
 /// Tool call request extracted from AI response
@@ -52,19 +52,28 @@ pub async fn build_chat_messages(
                                 content: MessageContent::Text(text.clone()),
                             });
                         }
-                        MessageContentData::ToolCall { tool_name, server_id: _, arguments, call_id } => {
-                            // Tool calls should be sent to AI provider so tool_result has corresponding tool_use
+                        MessageContentData::Thinking { thinking, signature } => {
+                            // Only kept for providers replaying earlier reasoning
                             messages.push(ChatMessage {
                                 role: msg.role.clone(),
-                                content: MessageContent::Multimodal(vec![
-                                    ContentPart::ToolUse {
-                                        id: call_id.clone(),
-                                        name: tool_name.clone(),
-                                        input: arguments.clone(),
-                                    }
-                                ]),
+                                content: MessageContent::Multimodal(vec![ContentPart::Thinking {
+                                    text: thinking.clone(),
+                                    signature: signature.clone(),
+                                }]),
                             });
                         }
+                        MessageContentData::ToolCall { tool_name, server_id: _, arguments, call_id } => {
+                            // Tool calls should be sent to AI provider so tool_result has corresponding tool_use
+                            push_tool_part(
+                                &mut messages,
+                                &msg.role,
+                                ContentPart::ToolUse {
+                                    id: call_id.clone(),
+                                    name: tool_name.clone(),
+                                    input: arguments.clone(),
+                                },
+                            );
+                        }
                         MessageContentData::ToolResult { call_id, result, success, error_message } => {
                             // Tool results should be sent with role "tool"
                             let output = if *success {
@@ -73,15 +82,14 @@ pub async fn build_chat_messages(
                                 error_message.clone().unwrap_or_else(|| "Tool execution failed".to_string())
                             };
 
-                            messages.push(ChatMessage {
-                                role: "tool".to_string(),
-                                content: MessageContent::Multimodal(vec![
-                                    ContentPart::ToolResult {
-                                        call_id: call_id.clone(),
-                                        output,
-                                    }
-                                ]),
-                            });
+                            push_tool_part(
+                                &mut messages,
+                                "tool",
+                                ContentPart::ToolResult {
+                                    call_id: call_id.clone(),
+                                    output,
+                                },
+                            );
                         }
                         // Skip other content types (ToolCallPendingApproval, etc.)
                         // as they are internal to our system and not sent to the AI provider
@@ -99,6 +107,27 @@ pub async fn build_chat_messages(
     Ok(messages)
 }
 
+/// Add a tool use or tool result, grouping consecutive ones into a single message so the
+/// parallel tool calls of a turn (and their results) reach the provider together
+fn push_tool_part(messages: &mut Vec<ChatMessage>, role: &str, part: ContentPart) {
+    if let Some(last) = messages.last_mut().filter(|last| last.role == role) {
+        if let MessageContent::Multimodal(parts) = &mut last.content {
+            let only_tool_parts = parts.iter().all(|p| {
+                matches!(p, ContentPart::ToolUse { .. } | ContentPart::ToolResult { .. })
+            });
+            if only_tool_parts {
+                parts.push(part);
+                return;
+            }
+        }
+    }
+
+    messages.push(ChatMessage {
+        role: role.to_string(),
+        content: MessageContent::Multimodal(vec![part]),
+    });
+}
+
 /// Create a single user message (useful for simple requests like title generation)
 pub fn build_single_user_message(content: String) -> Vec<ChatMessage> {
     vec![ChatMessage {
@@ -111,12 +140,15 @@ pub fn build_single_user_message(content: String) -> Vec<ChatMessage> {
 // Tool Calling Utilities
 // ===================================================================
 
+use crate::ai::mcp::prompt_discovery::get_mcp_prompt;
+use crate::ai::mcp::resource_discovery::read_mcp_resource;
 use crate::database::models::chat::ToolDefinition;
 use crate::database::models::mcp_tool::MCPTool;
-use crate::database::queries::mcp_tools;
+use crate::database::queries::{mcp_servers, mcp_tools};
 
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Structure to track which tools are enabled for a conversation
 /// This will be used to build tool definitions from MCP tools
@@ -126,6 +158,83 @@ pub struct EnabledMCPTool {
     pub name: String,
 }
 
+/// MCP prompt selected for a message, rendered with its arguments
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SelectedMCPPrompt {
+    pub server_id: Uuid,
+    pub name: String,
+    pub arguments: Option<HashMap<String, String>>,
+}
+
+/// MCP resource attached to a message
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SelectedMCPResource {
+    pub server_id: Uuid,
+    pub uri: String,
+}
+
+/// Expand the MCP prompt and resources selected for a message into its text content.
+/// The prompt's messages go before the user's text and resource contents after it, so the
+/// saved message keeps what was sent to the model.
+pub async fn resolve_mcp_message_content(
+    request: &ChatMessageRequest,
+    user_id: Uuid,
+) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    let mut parts = Vec::new();
+
+    if let Some(prompt) = &request.mcp_prompt {
+        ensure_mcp_server_access(user_id, prompt.server_id).await?;
+        let result = get_mcp_prompt(
+            prompt.server_id,
+            Some(user_id),
+            &prompt.name,
+            prompt.arguments.clone(),
+        )
+        .await
+        .map_err(|e| format!("Failed to get MCP prompt '{}': {}", prompt.name, e))?;
+        for message in result.messages {
+            parts.push(message.text);
+        }
+    }
+
+    if !request.content.trim().is_empty() {
+        parts.push(request.content.clone());
+    }
+
+    for resource in request.mcp_resources.iter().flatten() {
+        ensure_mcp_server_access(user_id, resource.server_id).await?;
+        let contents = read_mcp_resource(resource.server_id, Some(user_id), &resource.uri)
+            .await
+            .map_err(|e| format!("Failed to read MCP resource '{}': {}", resource.uri, e))?;
+        for content in contents {
+            match content.text {
+                Some(text) => parts.push(format!(
+                    "<resource uri=\"{}\">\n{}\n</resource>",
+                    content.uri, text
+                )),
+                None => eprintln!(
+                    "Skipping binary MCP resource {} ({})",
+                    content.uri,
+                    content.mime_type.as_deref().unwrap_or("unknown type")
+                ),
+            }
+        }
+    }
+
+    Ok(parts.join("\n\n"))
+}
+
+async fn ensure_mcp_server_access(
+    user_id: Uuid,
+    server_id: Uuid,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    if mcp_servers::can_user_access_server(user_id, server_id).await? {
+        Ok(())
+    } else {
+        Err(format!("Access denied to MCP server {}", server_id).into())
+    }
+}
+
 /// Convert an MCP tool to an AI provider tool definition format
 pub fn mcp_tool_to_definition(mcp_tool: &MCPTool) -> ToolDefinition {
     ToolDefinition {
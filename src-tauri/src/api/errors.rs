@@ -44,6 +44,7 @@ pub enum ErrorCode {
     ResourceModelNotFound,
     ResourceConversationNotFound,
     ResourceProviderDisabled,
+    ResourceQuotaExceeded,
 
     // System errors (SYSTEM_xxx)
     SystemDatabaseError,
@@ -88,6 +89,7 @@ impl ErrorCode {
             ErrorCode::ResourceModelNotFound => "RESOURCE_MODEL_NOT_FOUND",
             ErrorCode::ResourceConversationNotFound => "RESOURCE_CONVERSATION_NOT_FOUND",
             ErrorCode::ResourceProviderDisabled => "RESOURCE_PROVIDER_DISABLED",
+            ErrorCode::ResourceQuotaExceeded => "RESOURCE_QUOTA_EXCEEDED",
 
             // System
             ErrorCode::SystemDatabaseError => "SYSTEM_DATABASE_ERROR",
@@ -137,6 +139,9 @@ impl ErrorCode {
                 StatusCode::CONFLICT
             }
 
+            // 429 Too Many Requests
+            ErrorCode::ResourceQuotaExceeded => StatusCode::TOO_MANY_REQUESTS,
+
             // 500 Internal Server Error
             ErrorCode::AuthTokenGenerationFailed
             | ErrorCode::AuthTokenStorageFailed
@@ -13,6 +13,7 @@ use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::{
+    ai::mcp::roots::project_roots_changed,
     api::{
         errors::{ApiResult, AppError, ErrorCode},
         middleware::AuthenticatedUser,
@@ -74,7 +75,6 @@ pub async fn upload_file(
 ) -> ApiResult<Json<UploadFileResponse>> {
     let mut file_data = None;
     let mut filename = String::new();
-    let mut file_size = 0u64;
 
     // Extract multipart data
     while let Some(field) = multipart.next_field().await.map_err(|_| {
@@ -94,7 +94,6 @@ pub async fn upload_file(
                         AppError::new(ErrorCode::ValidInvalidInput, "Failed to read file data"),
                     )
                 })?;
-                file_size = data.len() as u64;
                 file_data = Some(data);
             }
             _ => continue,
@@ -118,7 +117,7 @@ pub async fn upload_file(
         ));
     }
 
-    match process_file_upload(user.user_id, filename, file_data, file_size, None).await {
+    match process_file_upload(user.user_id, filename, file_data, None).await {
         Ok(response) => Ok((StatusCode::OK, response)),
         Err(status) => Err((status, AppError::internal_error("Failed to upload file"))),
     }
@@ -133,7 +132,6 @@ pub async fn upload_project_file(
 ) -> ApiResult<Json<UploadFileResponse>> {
     let mut file_data = None;
     let mut filename = String::new();
-    let mut file_size = 0u64;
 
     // Extract multipart data
     while let Some(field) = multipart.next_field().await.map_err(|_| {
@@ -153,7 +151,6 @@ pub async fn upload_project_file(
                         AppError::new(ErrorCode::ValidInvalidInput, "Failed to read file data"),
                     )
                 })?;
-                file_size = data.len() as u64;
                 file_data = Some(data);
             }
             _ => continue,
@@ -181,12 +178,14 @@ pub async fn upload_project_file(
         user.user_id,
         filename,
         file_data,
-        file_size,
         Some(project_id),
     )
     .await
     {
-        Ok(response) => Ok((StatusCode::OK, response)),
+        Ok(response) => {
+            project_roots_changed(project_id).await;
+            Ok((StatusCode::OK, response))
+        }
         Err(status) => Err((
             status,
             AppError::internal_error("Failed to upload file to project"),
@@ -198,38 +197,44 @@ async fn process_file_upload(
     user_id: Uuid,
     filename: String,
     file_data: bytes::Bytes,
-    file_size: u64,
     project_id: Option<Uuid>,
 ) -> Result<Json<UploadFileResponse>, StatusCode> {
+    let file = store_file(user_id, filename, &file_data, project_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(UploadFileResponse { file }))
+}
+
+/// Save a file, extract its text and thumbnails, and record it for the user. Used for uploads
+/// and for files produced by models, such as generated images.
+pub(crate) async fn store_file(
+    user_id: Uuid,
+    filename: String,
+    file_data: &[u8],
+    project_id: Option<Uuid>,
+) -> Result<File, Box<dyn std::error::Error + Send + Sync>> {
     let file_id = Uuid::new_v4();
     let extension = extract_extension(&filename);
     let mime_type = get_mime_type_from_extension(&extension);
 
     // Save original file
     let file_path = FILE_STORAGE.get_original_path(file_id, &extension);
-    FILE_STORAGE
-        .save_file_bytes(&file_path, &file_data)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    FILE_STORAGE.save_file_bytes(&file_path, file_data).await?;
 
     // Calculate checksum
-    let checksum = FILE_STORAGE
-        .calculate_checksum(&file_path)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let checksum = FILE_STORAGE.calculate_checksum(&file_path).await?;
 
     // Process file content
     let processing_result = PROCESSING_MANAGER
         .process_file(&file_path, &mime_type)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .await?;
 
     // Save processed content
     if let Some(ref text_content) = processing_result.text_content {
         FILE_STORAGE
             .save_text_content(file_id, text_content)
-            .await
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            .await?;
     }
 
     // Create file record
@@ -237,7 +242,7 @@ async fn process_file_upload(
         id: file_id,
         user_id,
         filename,
-        file_size: file_size as i64,
+        file_size: file_data.len() as i64,
         mime_type,
         checksum: Some(checksum),
         project_id,
@@ -246,11 +251,9 @@ async fn process_file_upload(
         processing_metadata: processing_result.metadata,
     };
 
-    let file = files::create_file(file_create_data)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let file = files::create_file(file_create_data).await?;
 
-    Ok(Json(UploadFileResponse { file }))
+    Ok(file)
 }
 
 // Get file metadata
@@ -466,6 +469,10 @@ pub async fn delete_file(
             )
         })?;
 
+    if let Some(project_id) = file_db.project_id {
+        project_roots_changed(project_id).await;
+    }
+
     Ok((
         StatusCode::OK,
         Json(FileOperationSuccessResponse { success: true }),
@@ -0,0 +1,154 @@
+use axum::{debug_handler, extract::Path, http::StatusCode, Extension, Json};
+use uuid::Uuid;
+
+use crate::api::{
+    errors::{ApiResult, AppError, ErrorCode},
+    middleware::AuthenticatedUser,
+};
+use crate::database::{
+    models::{
+        ApprovalRuleDryRunRequest, ApprovalRuleEvaluation, MCPToolApprovalRule,
+        MCPToolApprovalRuleRequest,
+    },
+    queries::mcp_tool_approval_rules,
+};
+
+/// List approval rules in evaluation order
+#[debug_handler]
+pub async fn list_approval_rules(
+    Extension(_auth_user): Extension<AuthenticatedUser>,
+) -> ApiResult<Json<Vec<MCPToolApprovalRule>>> {
+    match mcp_tool_approval_rules::list_approval_rules().await {
+        Ok(rules) => Ok((StatusCode::OK, Json(rules))),
+        Err(e) => {
+            tracing::error!("Failed to list approval rules: {}", e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, AppError::internal_error("Database error")))
+        }
+    }
+}
+
+/// Get an approval rule
+#[debug_handler]
+pub async fn get_approval_rule(
+    Extension(_auth_user): Extension<AuthenticatedUser>,
+    Path(rule_id): Path<Uuid>,
+) -> ApiResult<Json<MCPToolApprovalRule>> {
+    match mcp_tool_approval_rules::get_approval_rule_by_id(rule_id).await {
+        Ok(Some(rule)) => Ok((StatusCode::OK, Json(rule))),
+        Ok(None) => Err((StatusCode::NOT_FOUND, AppError::not_found("Approval rule"))),
+        Err(e) => {
+            tracing::error!("Failed to get approval rule {}: {}", rule_id, e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, AppError::internal_error("Database error")))
+        }
+    }
+}
+
+/// Create an approval rule
+#[debug_handler]
+pub async fn create_approval_rule(
+    Extension(_auth_user): Extension<AuthenticatedUser>,
+    Json(request): Json<MCPToolApprovalRuleRequest>,
+) -> ApiResult<Json<MCPToolApprovalRule>> {
+    validate_rule_request(&request)?;
+
+    match mcp_tool_approval_rules::create_approval_rule(request).await {
+        Ok(rule) => Ok((StatusCode::CREATED, Json(rule))),
+        Err(e) => Err(approval_rule_write_error(e)),
+    }
+}
+
+/// Replace an approval rule
+#[debug_handler]
+pub async fn update_approval_rule(
+    Extension(_auth_user): Extension<AuthenticatedUser>,
+    Path(rule_id): Path<Uuid>,
+    Json(request): Json<MCPToolApprovalRuleRequest>,
+) -> ApiResult<Json<MCPToolApprovalRule>> {
+    validate_rule_request(&request)?;
+
+    match mcp_tool_approval_rules::update_approval_rule(rule_id, request).await {
+        Ok(Some(rule)) => Ok((StatusCode::OK, Json(rule))),
+        Ok(None) => Err((StatusCode::NOT_FOUND, AppError::not_found("Approval rule"))),
+        Err(e) => Err(approval_rule_write_error(e)),
+    }
+}
+
+/// Delete an approval rule
+#[debug_handler]
+pub async fn delete_approval_rule(
+    Extension(_auth_user): Extension<AuthenticatedUser>,
+    Path(rule_id): Path<Uuid>,
+) -> ApiResult<StatusCode> {
+    match mcp_tool_approval_rules::delete_approval_rule(rule_id).await {
+        Ok(true) => Ok((StatusCode::NO_CONTENT, StatusCode::NO_CONTENT)),
+        Ok(false) => Err((StatusCode::NOT_FOUND, AppError::not_found("Approval rule"))),
+        Err(e) => {
+            tracing::error!("Failed to delete approval rule {}: {}", rule_id, e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, AppError::internal_error("Database error")))
+        }
+    }
+}
+
+/// Evaluate the approval rules for a tool call without running it, explaining which rule
+/// matched and why the others did not
+#[debug_handler]
+pub async fn dry_run_approval_rules(
+    Extension(auth_user): Extension<AuthenticatedUser>,
+    Json(request): Json<ApprovalRuleDryRunRequest>,
+) -> ApiResult<Json<ApprovalRuleEvaluation>> {
+    match mcp_tool_approval_rules::evaluate_approval_rules_for_user(
+        request.user_id.unwrap_or(auth_user.user_id),
+        request.server_id,
+        &request.tool_name,
+        request.arguments.as_ref(),
+    )
+    .await
+    {
+        Ok(evaluation) => Ok((StatusCode::OK, Json(evaluation))),
+        Err(e) => {
+            tracing::error!("Failed to evaluate approval rules: {}", e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, AppError::internal_error("Database error")))
+        }
+    }
+}
+
+fn validate_rule_request(request: &MCPToolApprovalRuleRequest) -> Result<(), (StatusCode, AppError)> {
+    let invalid = |message: String| {
+        (
+            StatusCode::BAD_REQUEST,
+            AppError::new(ErrorCode::ValidInvalidInput, message),
+        )
+    };
+
+    if request.name.trim().is_empty() {
+        return Err(invalid("Approval rule name is required".to_string()));
+    }
+    if request
+        .tool_name_pattern
+        .as_ref()
+        .map_or(false, |pattern| pattern.trim().is_empty())
+    {
+        return Err(invalid("Tool name pattern cannot be empty".to_string()));
+    }
+    for condition in &request.conditions {
+        condition.validate().map_err(invalid)?;
+    }
+
+    Ok(())
+}
+
+fn approval_rule_write_error(e: sqlx::Error) -> (StatusCode, AppError) {
+    match &e {
+        sqlx::Error::Database(db_error) if db_error.is_foreign_key_violation() => (
+            StatusCode::BAD_REQUEST,
+            AppError::new(
+                ErrorCode::ValidInvalidInput,
+                "Unknown server or group in approval rule",
+            ),
+        ),
+        _ => {
+            tracing::error!("Failed to save approval rule: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, AppError::internal_error("Database error"))
+        }
+    }
+}
@@ -13,18 +13,19 @@ use crate::api::{
     middleware::AuthenticatedUser,
 };
 use crate::database::{
+    models::ApprovalRuleAction,
     models::mcp_tool::{
         CreateConversationApprovalRequest, ToolApprovalResponse, ListConversationApprovalsQuery,
         UpdateToolApprovalRequest,
     },
-    queries::{mcp_tool_approvals, mcp_servers},
+    queries::{mcp_tool_approval_rules, mcp_tool_approvals, mcp_servers},
 };
 
 // Response types
 #[derive(Debug, Serialize, JsonSchema)]
 pub struct ApprovalCheckResponse {
     pub approved: bool,
-    pub source: Option<String>, // "global" or "conversation"
+    pub source: Option<String>, // "rule:<name>", "global" or "conversation"
     pub expires_at: Option<String>,
 }
 
@@ -80,10 +81,56 @@ pub async fn create_conversation_approval(
         return Err((StatusCode::FORBIDDEN, AppError::forbidden("Access denied to MCP server")));
     }
 
+    let pool = crate::database::queries::get_database_pool().map_err(|e| {
+        tracing::error!("Failed to get database pool: {}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, AppError::internal_error("Database error"))
+    })?;
+
+    // Approval rules set by admins cannot be overridden by approving the call
+    if request.approved {
+        let arguments = match request.approval_message_content_id {
+            Some(content_id) => sqlx::query_scalar!(
+                r#"
+                SELECT content->'arguments' AS "arguments: serde_json::Value"
+                FROM message_contents
+                WHERE id = $1 AND content_type = 'tool_call_pending_approval'
+                "#,
+                content_id
+            )
+            .fetch_optional(pool.as_ref())
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to get pending approval content: {}", e);
+                (StatusCode::INTERNAL_SERVER_ERROR, AppError::internal_error("Database error"))
+            })?
+            .flatten(),
+            None => None,
+        };
+
+        let evaluation = mcp_tool_approval_rules::evaluate_approval_rules_for_user(
+            auth_user.user_id,
+            request.server_id,
+            &request.tool_name,
+            arguments.as_ref(),
+        )
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to evaluate approval rules: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, AppError::internal_error("Database error"))
+        })?;
+
+        if let (Some(ApprovalRuleAction::Deny), Some(rule)) =
+            (evaluation.action, evaluation.matched_rule)
+        {
+            return Err((
+                StatusCode::FORBIDDEN,
+                AppError::forbidden(&format!("Denied by approval rule '{}'", rule.name)),
+            ));
+        }
+    }
+
     // Update message content if approval_message_content_id is provided
     if let Some(content_id) = request.approval_message_content_id {
-        use crate::database::queries;
-
         // Update the message content to set is_approved field
         sqlx::query!(
             r#"
@@ -95,10 +142,7 @@ pub async fn create_conversation_approval(
             content_id,
             serde_json::json!(request.approved)
         )
-        .execute(queries::get_database_pool().map_err(|e| {
-            tracing::error!("Failed to get database pool: {}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, AppError::internal_error("Database error"))
-        })?.as_ref())
+        .execute(pool.as_ref())
         .await
         .map_err(|e| {
             tracing::error!("Failed to update message content: {}", e);
@@ -213,6 +257,7 @@ pub async fn check_conversation_approval(
         conversation_id,
         query.server_id,
         &query.tool_name,
+        None,
     )
     .await
     .map_err(|e| {
@@ -15,8 +15,10 @@ use crate::api::{
 };
 use crate::database::{
     models::mcp_tool::{ExecuteToolRequest, MCPExecutionLog, MCPExecutionStatus, ToolExecutionResponse},
-    queries::{mcp_execution_logs, mcp_servers, mcp_tools, mcp_tool_approvals},
+    models::ApprovalRuleAction,
+    queries::{mcp_execution_logs, mcp_servers, mcp_tools, mcp_tool_approval_rules, mcp_tool_approvals},
 };
+use crate::utils::cancellation::cancel_download;
 
 // Request/Response types
 #[derive(Debug, Deserialize, JsonSchema)]
@@ -79,6 +81,7 @@ pub async fn execute_tool(
             conversation_id,
             tool.server_id,
             &tool.tool_name,
+            Some(&request.parameters),
         )
         .await
         .map_err(|e| {
@@ -86,6 +89,11 @@ pub async fn execute_tool(
             StatusCode::INTERNAL_SERVER_ERROR
         })?;
 
+        // Only approval rules deny a call, and their denial cannot be overridden
+        if let Some((false, source)) = &approval_check {
+            return Ok(Json(denied_by_rule_response(source)));
+        }
+
         // If not approved and doesn't require special approval, check if tool typically needs approval
         let requires_approval = should_require_approval(&tool.tool_name);
         let is_approved = approval_check.as_ref().map_or(false, |(approved, _)| *approved);
@@ -109,9 +117,31 @@ pub async fn execute_tool(
             );
         }
     } else {
-        // No conversation_id provided, check if tool generally requires approval
+        // No conversation_id provided: approval rules decide, otherwise check if tool
+        // generally requires approval
+        let evaluation = mcp_tool_approval_rules::evaluate_approval_rules_for_user(
+            auth_user.user_id,
+            tool.server_id,
+            &tool.tool_name,
+            Some(&request.parameters),
+        )
+        .await
+        .map_err(|e| {
+            eprintln!("Failed to evaluate approval rules: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+        if let (Some(ApprovalRuleAction::Deny), Some(rule)) =
+            (evaluation.action, &evaluation.matched_rule)
+        {
+            return Ok(Json(denied_by_rule_response(&format!("rule:{}", rule.name))));
+        }
+
         let requires_approval = should_require_approval(&tool.tool_name);
-        if requires_approval && !request.auto_approve.unwrap_or(false) {
+        if requires_approval
+            && evaluation.action != Some(ApprovalRuleAction::Approve)
+            && !request.auto_approve.unwrap_or(false)
+        {
             // For tools without conversation context, require explicit auto_approve flag
             return Ok(Json(ToolExecutionResponse {
                 execution_id: Uuid::new_v4(), // Placeholder ID
@@ -299,8 +329,11 @@ pub async fn cancel_execution(
     // Check if execution can be cancelled
     match log.status {
         MCPExecutionStatus::Pending | MCPExecutionStatus::Running => {
-            // TODO: Implement actual cancellation logic
-            // This would involve sending a cancellation request to the MCP server
+            // A tool call in progress sends `notifications/cancelled` to its server and
+            // records the cancellation itself
+            if cancel_download(execution_id).await {
+                return Ok((StatusCode::OK, Json(serde_json::json!({"message": "Execution cancelled successfully"}))));
+            }
 
             // Update execution log to cancelled
             let cancel_reason = request.reason.unwrap_or_else(|| "Cancelled by user".to_string());
@@ -412,4 +445,14 @@ fn should_require_approval(tool_name: &str) -> bool {
         // By default, don't require approval for read-only or safe operations
         _ => false,
     }
-}
\ No newline at end of file
+}
+/// Response for a call an approval rule denied
+fn denied_by_rule_response(source: &str) -> ToolExecutionResponse {
+    ToolExecutionResponse {
+        execution_id: Uuid::new_v4(), // Placeholder ID
+        status: MCPExecutionStatus::Failed,
+        result: None,
+        error_message: Some(format!("Tool execution denied by approval {}", source)),
+        duration_ms: Some(0),
+    }
+}
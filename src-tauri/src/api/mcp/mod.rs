@@ -1,5 +1,9 @@
+pub mod approval_rules;
 pub mod approvals;
 pub mod execution;
 pub mod logs;
+pub mod oauth;
+pub mod prompts;
+pub mod resources;
 pub mod servers;
 pub mod tools;
\ No newline at end of file
@@ -0,0 +1,152 @@
+use axum::{
+    debug_handler,
+    extract::{Path, Query},
+    http::StatusCode,
+    response::Html,
+    Extension, Json,
+};
+use uuid::Uuid;
+
+use crate::ai::mcp::oauth::{self, MCPOAuthError};
+use crate::api::{
+    errors::{ApiResult, AppError, ErrorCode},
+    middleware::AuthenticatedUser,
+    permissions::{check_permission, Permission},
+};
+use crate::database::{
+    models::{MCPOAuthCallbackQuery, MCPOAuthStatus, StartMCPOAuthRequest, StartMCPOAuthResponse},
+    queries::mcp_servers,
+};
+
+/// Users authorize the servers they can use; admins also the system servers they manage
+async fn check_authorization_access(
+    auth_user: &AuthenticatedUser,
+    server_id: Uuid,
+) -> Result<(), (StatusCode, AppError)> {
+    let server = match mcp_servers::get_mcp_server_by_id(server_id).await {
+        Ok(Some(server)) => server,
+        Ok(None) => return Err((StatusCode::NOT_FOUND, AppError::not_found("MCP Server"))),
+        Err(e) => {
+            tracing::error!("Failed to get server: {}", e);
+            return Err((StatusCode::INTERNAL_SERVER_ERROR, AppError::internal_error("Database error")));
+        }
+    };
+
+    if server.is_system
+        && check_permission(&auth_user.user, Permission::McpAdminServersRead.as_str())
+    {
+        return Ok(());
+    }
+
+    match mcp_servers::can_user_access_server(auth_user.user_id, server_id).await {
+        Ok(true) => Ok(()),
+        Ok(false) => Err((StatusCode::FORBIDDEN, AppError::forbidden("Access denied to MCP server"))),
+        Err(e) => {
+            tracing::error!("Failed to check server access: {}", e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, AppError::internal_error("Database error")))
+        }
+    }
+}
+
+fn oauth_error(e: MCPOAuthError) -> (StatusCode, AppError) {
+    match e {
+        MCPOAuthError::ServerNotFound => (StatusCode::NOT_FOUND, AppError::not_found("MCP Server")),
+        MCPOAuthError::UnsupportedTransport
+        | MCPOAuthError::MissingServerUrl
+        | MCPOAuthError::InvalidRedirectUri(_) => (
+            StatusCode::BAD_REQUEST,
+            AppError::new(ErrorCode::ValidInvalidInput, e.to_string()),
+        ),
+        MCPOAuthError::Database(e) => {
+            tracing::error!("Database error during MCP authorization: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, AppError::internal_error("Database error"))
+        }
+        e => {
+            tracing::error!("MCP authorization failed: {}", e);
+            (
+                StatusCode::BAD_GATEWAY,
+                AppError::new(ErrorCode::SystemExternalServiceError, e.to_string()),
+            )
+        }
+    }
+}
+
+/// Start authorizing the current user for a server
+#[debug_handler]
+pub async fn start_server_authorization(
+    Extension(auth_user): Extension<AuthenticatedUser>,
+    Path(server_id): Path<Uuid>,
+    Json(request): Json<StartMCPOAuthRequest>,
+) -> ApiResult<Json<StartMCPOAuthResponse>> {
+    check_authorization_access(&auth_user, server_id).await?;
+
+    match oauth::start_authorization(auth_user.user_id, server_id, request.redirect_uri).await {
+        Ok(response) => Ok((StatusCode::OK, Json(response))),
+        Err(e) => Err(oauth_error(e)),
+    }
+}
+
+/// Whether the current user has authorized a server
+#[debug_handler]
+pub async fn get_server_authorization(
+    Extension(auth_user): Extension<AuthenticatedUser>,
+    Path(server_id): Path<Uuid>,
+) -> ApiResult<Json<MCPOAuthStatus>> {
+    check_authorization_access(&auth_user, server_id).await?;
+
+    match oauth::authorization_status(auth_user.user_id, server_id).await {
+        Ok(status) => Ok((StatusCode::OK, Json(status))),
+        Err(e) => Err(oauth_error(e)),
+    }
+}
+
+/// Forget the current user's token for a server
+#[debug_handler]
+pub async fn revoke_server_authorization(
+    Extension(auth_user): Extension<AuthenticatedUser>,
+    Path(server_id): Path<Uuid>,
+) -> ApiResult<StatusCode> {
+    check_authorization_access(&auth_user, server_id).await?;
+
+    match oauth::revoke_authorization(auth_user.user_id, server_id).await {
+        Ok(_) => Ok((StatusCode::NO_CONTENT, StatusCode::NO_CONTENT)),
+        Err(e) => Err(oauth_error(e)),
+    }
+}
+
+/// Redirect endpoint of the authorization servers. Shows the outcome and tells the window
+/// that opened the authorization page, if any.
+pub async fn oauth_callback(Query(query): Query<MCPOAuthCallbackQuery>) -> Html<String> {
+    let (success, server_id, message) = match oauth::complete_authorization(query).await {
+        Ok(server_id) => (
+            true,
+            Some(server_id),
+            "Authorization complete. You can close this window.".to_string(),
+        ),
+        Err(e) => {
+            tracing::warn!("MCP authorization callback failed: {}", e);
+            (false, None, format!("Authorization failed: {}", e))
+        }
+    };
+
+    let event = serde_json::json!({
+        "type": "mcp-oauth",
+        "success": success,
+        "server_id": server_id,
+    });
+    Html(format!(
+        "<!DOCTYPE html><html><head><title>MCP authorization</title></head><body>\
+         <p>{}</p>\
+         <script>if (window.opener) {{ window.opener.postMessage({}, '*'); window.close(); }}</script>\
+         </body></html>",
+        escape_html(&message),
+        event
+    ))
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
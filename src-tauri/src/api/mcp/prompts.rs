@@ -0,0 +1,81 @@
+use axum::{debug_handler, extract::Path, http::StatusCode, Extension, Json};
+use uuid::Uuid;
+
+use super::servers::check_server_access;
+use crate::ai::mcp::prompt_discovery::{
+    discover_and_cache_prompts, get_mcp_prompt, should_rediscover_prompts,
+};
+use crate::api::{
+    errors::{ApiResult, AppError, ErrorCode},
+    middleware::AuthenticatedUser,
+    permissions::Permission,
+};
+use crate::database::{
+    models::mcp_prompt::{GetMCPPromptRequest, MCPPrompt, MCPPromptResult},
+    queries::mcp_prompts,
+};
+
+/// Get prompts for a specific server (with auto-discovery and caching)
+#[debug_handler]
+pub async fn get_server_prompts(
+    Extension(auth_user): Extension<AuthenticatedUser>,
+    Path(server_id): Path<Uuid>,
+) -> ApiResult<Json<Vec<MCPPrompt>>> {
+    check_server_access(
+        &auth_user,
+        server_id,
+        Permission::McpAdminServersRead.as_str(),
+        "read",
+    )
+    .await?;
+
+    let should_discover = should_rediscover_prompts(server_id).await.map_err(|e| {
+        tracing::error!("Failed to check if prompts should be rediscovered: {}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, AppError::internal_error("Database error"))
+    })?;
+
+    if should_discover {
+        // Servers without prompt support answer with an error; serve the cache instead
+        if let Err(e) = discover_and_cache_prompts(server_id, Some(auth_user.user_id)).await {
+            tracing::warn!("Prompt discovery failed for server {}: {}", server_id, e);
+        }
+    }
+
+    match mcp_prompts::get_cached_prompts_for_server(server_id).await {
+        Ok(prompts) => Ok((StatusCode::OK, Json(prompts))),
+        Err(e) => {
+            tracing::error!("Failed to get server prompts: {}", e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, AppError::internal_error("Database error")))
+        }
+    }
+}
+
+/// Get a prompt's messages from a server, rendered with the given arguments
+#[debug_handler]
+pub async fn get_server_prompt(
+    Extension(auth_user): Extension<AuthenticatedUser>,
+    Path((server_id, prompt_name)): Path<(Uuid, String)>,
+    Json(request): Json<GetMCPPromptRequest>,
+) -> ApiResult<Json<MCPPromptResult>> {
+    check_server_access(
+        &auth_user,
+        server_id,
+        Permission::McpAdminServersRead.as_str(),
+        "read",
+    )
+    .await?;
+
+    match get_mcp_prompt(server_id, Some(auth_user.user_id), &prompt_name, request.arguments).await {
+        Ok(prompt) => Ok((StatusCode::OK, Json(prompt))),
+        Err(e) => {
+            tracing::error!("Failed to get prompt '{}' from server {}: {}", prompt_name, server_id, e);
+            Err((
+                StatusCode::BAD_GATEWAY,
+                AppError::new(
+                    ErrorCode::SystemExternalServiceError,
+                    format!("Failed to get prompt: {}", e),
+                ),
+            ))
+        }
+    }
+}
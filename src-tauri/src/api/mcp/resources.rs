@@ -0,0 +1,86 @@
+use axum::{
+    debug_handler,
+    extract::{Path, Query},
+    http::StatusCode,
+    Extension, Json,
+};
+use uuid::Uuid;
+
+use super::servers::check_server_access;
+use crate::ai::mcp::resource_discovery::{
+    discover_and_cache_resources, read_mcp_resource, should_rediscover_resources,
+};
+use crate::api::{
+    errors::{ApiResult, AppError, ErrorCode},
+    middleware::AuthenticatedUser,
+    permissions::Permission,
+};
+use crate::database::{
+    models::mcp_resource::{MCPResource, MCPResourceContent, ReadMCPResourceQuery},
+    queries::mcp_resources,
+};
+
+/// Get resources for a specific server (with auto-discovery and caching)
+#[debug_handler]
+pub async fn get_server_resources(
+    Extension(auth_user): Extension<AuthenticatedUser>,
+    Path(server_id): Path<Uuid>,
+) -> ApiResult<Json<Vec<MCPResource>>> {
+    check_server_access(
+        &auth_user,
+        server_id,
+        Permission::McpAdminServersRead.as_str(),
+        "read",
+    )
+    .await?;
+
+    let should_discover = should_rediscover_resources(server_id).await.map_err(|e| {
+        tracing::error!("Failed to check if resources should be rediscovered: {}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, AppError::internal_error("Database error"))
+    })?;
+
+    if should_discover {
+        // Servers without resource support answer with an error; serve the cache instead
+        if let Err(e) = discover_and_cache_resources(server_id, Some(auth_user.user_id)).await {
+            tracing::warn!("Resource discovery failed for server {}: {}", server_id, e);
+        }
+    }
+
+    match mcp_resources::get_cached_resources_for_server(server_id).await {
+        Ok(resources) => Ok((StatusCode::OK, Json(resources))),
+        Err(e) => {
+            tracing::error!("Failed to get server resources: {}", e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, AppError::internal_error("Database error")))
+        }
+    }
+}
+
+/// Read the contents of a resource from a server
+#[debug_handler]
+pub async fn read_server_resource(
+    Extension(auth_user): Extension<AuthenticatedUser>,
+    Path(server_id): Path<Uuid>,
+    Query(query): Query<ReadMCPResourceQuery>,
+) -> ApiResult<Json<Vec<MCPResourceContent>>> {
+    check_server_access(
+        &auth_user,
+        server_id,
+        Permission::McpAdminServersRead.as_str(),
+        "read",
+    )
+    .await?;
+
+    match read_mcp_resource(server_id, Some(auth_user.user_id), &query.uri).await {
+        Ok(contents) => Ok((StatusCode::OK, Json(contents))),
+        Err(e) => {
+            tracing::error!("Failed to read resource '{}' from server {}: {}", query.uri, server_id, e);
+            Err((
+                StatusCode::BAD_GATEWAY,
+                AppError::new(
+                    ErrorCode::SystemExternalServiceError,
+                    format!("Failed to read resource: {}", e),
+                ),
+            ))
+        }
+    }
+}
@@ -16,12 +16,13 @@ use crate::api::{
 };
 use crate::database::{
     models::mcp_server::{MCPServer, CreateMCPServerRequest, CreateSystemMCPServerRequest, UpdateMCPServerRequest, MCPTransportType},
+    models::mcp_server_config::{ImportMCPServersResponse, MCPServerImportIssue, MCPServersConfig},
     queries::{mcp_servers, user_group_mcp_servers},
 };
 use crate::ai::mcp::{start_mcp_server, stop_mcp_server};
 
 // Helper function to check server access permissions
-async fn check_server_access(
+pub(super) async fn check_server_access(
     auth_user: &AuthenticatedUser,
     server_id: Uuid,
     required_admin_permission: &str,
@@ -311,7 +312,90 @@ pub async fn stop_server(
     }
 }
 
+// Import / Export in the `mcpServers` config format
 
+// Validate every entry of an imported config before creating any server
+fn validate_import(
+    config: &MCPServersConfig,
+    allow_stdio: bool,
+) -> Result<Vec<(String, CreateMCPServerRequest)>, (StatusCode, AppError)> {
+    if config.mcp_servers.is_empty() {
+        return Err((StatusCode::BAD_REQUEST, AppError::new(ErrorCode::ValidMissingRequiredField, "No servers found under mcpServers")));
+    }
+
+    let mut requests: Vec<(String, CreateMCPServerRequest)> = Vec::new();
+    let mut problems = Vec::new();
+
+    for (key, entry) in &config.mcp_servers {
+        match entry.to_create_request(key) {
+            Ok(request) => {
+                if matches!(request.transport_type, MCPTransportType::Stdio) && !allow_stdio {
+                    problems.push(format!("{}: stdio transport is only available for desktop applications", key));
+                } else if let Some((other, _)) = requests.iter().find(|(_, r)| r.name == request.name) {
+                    problems.push(format!("{}: same server name as {}", key, other));
+                } else {
+                    requests.push((key.clone(), request));
+                }
+            }
+            Err(e) => problems.push(format!("{}: {}", key, e)),
+        }
+    }
+
+    if !problems.is_empty() {
+        return Err((StatusCode::BAD_REQUEST, AppError::new(ErrorCode::ValidInvalidInput, format!("Invalid MCP server config: {}", problems.join("; ")))));
+    }
+
+    Ok(requests)
+}
+
+fn import_issue(name: String, e: sqlx::Error) -> MCPServerImportIssue {
+    let error = match e {
+        sqlx::Error::Database(db_err) if db_err.constraint().is_some() => "Server with this name already exists".to_string(),
+        e => {
+            tracing::error!("Failed to import MCP server {}: {}", name, e);
+            "Database error".to_string()
+        }
+    };
+    MCPServerImportIssue { name, error }
+}
+
+/// Import user MCP servers from an `mcpServers` config
+#[debug_handler]
+pub async fn import_user_servers(
+    Extension(auth_user): Extension<AuthenticatedUser>,
+    Json(config): Json<MCPServersConfig>,
+) -> ApiResult<Json<ImportMCPServersResponse>> {
+    let requests = validate_import(&config, is_desktop_app())?;
+
+    let mut response = ImportMCPServersResponse { created: Vec::new(), skipped: Vec::new() };
+    for (key, request) in requests {
+        match mcp_servers::create_user_mcp_server(auth_user.user_id, request).await {
+            Ok(server) => response.created.push(server),
+            Err(e) => response.skipped.push(import_issue(key, e)),
+        }
+    }
+
+    Ok((StatusCode::OK, Json(response)))
+}
+
+/// Export the user's own MCP servers as an `mcpServers` config
+#[debug_handler]
+pub async fn export_user_servers(
+    Extension(auth_user): Extension<AuthenticatedUser>,
+) -> ApiResult<Json<MCPServersConfig>> {
+    match mcp_servers::list_user_accessible_mcp_servers(auth_user.user_id).await {
+        Ok(servers) => {
+            let own_servers: Vec<MCPServer> = servers.into_iter()
+                .filter(|s| !s.is_system && s.user_id == Some(auth_user.user_id))
+                .collect();
+            Ok((StatusCode::OK, Json(MCPServersConfig::from_servers(&own_servers))))
+        }
+        Err(e) => {
+            tracing::error!("Failed to load user MCP servers: {}", e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, AppError::internal_error("Database error")))
+        }
+    }
+}
 
 // Admin MCP Server Operations
 
@@ -377,6 +461,39 @@ pub async fn create_system_server(
     }
 }
 
+/// Import system MCP servers from an `mcpServers` config (admin only)
+#[debug_handler]
+pub async fn import_system_servers(
+    Extension(_auth_user): Extension<AuthenticatedUser>,
+    Json(config): Json<MCPServersConfig>,
+) -> ApiResult<Json<ImportMCPServersResponse>> {
+    let requests = validate_import(&config, true)?;
+
+    let mut response = ImportMCPServersResponse { created: Vec::new(), skipped: Vec::new() };
+    for (key, request) in requests {
+        match mcp_servers::create_system_mcp_server(request.into()).await {
+            Ok(server) => response.created.push(server),
+            Err(e) => response.skipped.push(import_issue(key, e)),
+        }
+    }
+
+    Ok((StatusCode::OK, Json(response)))
+}
+
+/// Export system MCP servers as an `mcpServers` config (admin only)
+#[debug_handler]
+pub async fn export_system_servers(
+    Extension(_auth_user): Extension<AuthenticatedUser>,
+) -> ApiResult<Json<MCPServersConfig>> {
+    match mcp_servers::list_system_mcp_servers().await {
+        Ok(servers) => Ok((StatusCode::OK, Json(MCPServersConfig::from_servers(&servers)))),
+        Err(e) => {
+            tracing::error!("Failed to load system MCP servers: {}", e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, AppError::internal_error("Database error")))
+        }
+    }
+}
+
 // Group Assignment Operations
 
 /// Get MCP servers assigned to group (admin only)
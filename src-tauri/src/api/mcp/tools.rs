@@ -138,7 +138,7 @@ pub async fn get_server_tools(
 
     if should_discover {
         // Attempt discovery, but don't fail if it doesn't work
-        if let Err(e) = discover_and_cache_tools_http(server_id).await {
+        if let Err(e) = discover_and_cache_tools_http(server_id, Some(auth_user.user_id)).await {
             tracing::warn!("Tool discovery failed for server {}: {}", server_id, e);
 
             // Check if we have any cached tools to fall back to
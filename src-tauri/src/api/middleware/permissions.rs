@@ -293,3 +293,7 @@ permission_middleware!(
     mcp_admin_servers_edit_middleware,
     Permission::McpAdminServersEdit
 );
+permission_middleware!(
+    mcp_admin_servers_delete_middleware,
+    Permission::McpAdminServersDelete
+);
@@ -15,6 +15,7 @@ pub mod hub;
 pub mod macros;
 pub mod mcp;
 pub mod middleware;
+pub mod model_groups;
 pub mod model_uploads;
 pub mod models;
 pub mod permissions;
@@ -23,6 +24,7 @@ pub mod providers;
 pub mod rag;
 pub mod repositories;
 pub mod types;
+pub mod usage;
 pub mod user;
 pub mod user_groups;
 pub mod user_settings;
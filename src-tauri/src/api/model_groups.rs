@@ -0,0 +1,158 @@
+use axum::{debug_handler, extract::Path, http::StatusCode, Extension, Json};
+use uuid::Uuid;
+
+use crate::api::errors::{ApiResult, AppError, ErrorCode};
+use crate::api::middleware::AuthenticatedUser;
+use crate::database::{
+    models::{CreateModelGroupRequest, ModelGroup, UpdateModelGroupRequest},
+    queries::model_groups,
+};
+
+/// List all model groups
+#[debug_handler]
+pub async fn list_model_groups(
+    Extension(_auth_user): Extension<AuthenticatedUser>,
+) -> ApiResult<Json<Vec<ModelGroup>>> {
+    match model_groups::list_model_groups().await {
+        Ok(groups) => Ok((StatusCode::OK, Json(groups))),
+        Err(e) => {
+            eprintln!("Failed to list model groups: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                AppError::internal_error("Database operation failed"),
+            ))
+        }
+    }
+}
+
+/// Get a model group
+#[debug_handler]
+pub async fn get_model_group(
+    Extension(_auth_user): Extension<AuthenticatedUser>,
+    Path(group_id): Path<Uuid>,
+) -> ApiResult<Json<ModelGroup>> {
+    match model_groups::get_model_group_by_id(group_id).await {
+        Ok(Some(group)) => Ok((StatusCode::OK, Json(group))),
+        Ok(None) => Err((StatusCode::NOT_FOUND, AppError::not_found("Model group"))),
+        Err(e) => {
+            eprintln!("Failed to get model group {}: {}", group_id, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                AppError::internal_error("Database operation failed"),
+            ))
+        }
+    }
+}
+
+/// Create a model group
+#[debug_handler]
+pub async fn create_model_group(
+    Extension(_auth_user): Extension<AuthenticatedUser>,
+    Json(request): Json<CreateModelGroupRequest>,
+) -> ApiResult<Json<ModelGroup>> {
+    if request.name.trim().is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            AppError::new(ErrorCode::ValidInvalidInput, "Model group name is required"),
+        ));
+    }
+    if request.model_ids.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            AppError::new(
+                ErrorCode::ValidInvalidInput,
+                "Model group needs at least one model",
+            ),
+        ));
+    }
+
+    match model_groups::create_model_group(request).await {
+        Ok(group) => {
+            reload_proxy_model_groups().await;
+            Ok((StatusCode::OK, Json(group)))
+        }
+        Err(e) => Err(model_group_write_error(e)),
+    }
+}
+
+/// Update a model group
+#[debug_handler]
+pub async fn update_model_group(
+    Extension(_auth_user): Extension<AuthenticatedUser>,
+    Path(group_id): Path<Uuid>,
+    Json(request): Json<UpdateModelGroupRequest>,
+) -> ApiResult<Json<ModelGroup>> {
+    if request.name.as_ref().map_or(false, |name| name.trim().is_empty()) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            AppError::new(ErrorCode::ValidInvalidInput, "Model group name is required"),
+        ));
+    }
+    if request.model_ids.as_ref().map_or(false, |ids| ids.is_empty()) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            AppError::new(
+                ErrorCode::ValidInvalidInput,
+                "Model group needs at least one model",
+            ),
+        ));
+    }
+
+    match model_groups::update_model_group(group_id, request).await {
+        Ok(Some(group)) => {
+            reload_proxy_model_groups().await;
+            Ok((StatusCode::OK, Json(group)))
+        }
+        Ok(None) => Err((StatusCode::NOT_FOUND, AppError::not_found("Model group"))),
+        Err(e) => Err(model_group_write_error(e)),
+    }
+}
+
+/// Delete a model group
+#[debug_handler]
+pub async fn delete_model_group(
+    Extension(_auth_user): Extension<AuthenticatedUser>,
+    Path(group_id): Path<Uuid>,
+) -> ApiResult<StatusCode> {
+    match model_groups::delete_model_group(group_id).await {
+        Ok(true) => {
+            reload_proxy_model_groups().await;
+            Ok((StatusCode::NO_CONTENT, StatusCode::NO_CONTENT))
+        }
+        Ok(false) => Err((StatusCode::NOT_FOUND, AppError::not_found("Model group"))),
+        Err(e) => {
+            eprintln!("Failed to delete model group {}: {}", group_id, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                AppError::internal_error("Database operation failed"),
+            ))
+        }
+    }
+}
+
+fn model_group_write_error(e: sqlx::Error) -> (StatusCode, AppError) {
+    match &e {
+        sqlx::Error::Database(db_error) if db_error.is_unique_violation() => (
+            StatusCode::CONFLICT,
+            AppError::conflict("A model group with this name already exists"),
+        ),
+        sqlx::Error::Database(db_error) if db_error.is_foreign_key_violation() => (
+            StatusCode::BAD_REQUEST,
+            AppError::new(ErrorCode::ValidInvalidInput, "Unknown model in model group"),
+        ),
+        _ => {
+            eprintln!("Failed to save model group: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                AppError::internal_error("Database operation failed"),
+            )
+        }
+    }
+}
+
+/// The API proxy resolves model group names from its registry; refresh it when it is running
+async fn reload_proxy_model_groups() {
+    if let Err(e) = crate::ai::api_proxy_server::reload_proxy_models().await {
+        tracing::debug!("API proxy registry not reloaded: {}", e);
+    }
+}
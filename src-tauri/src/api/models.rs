@@ -1,10 +1,11 @@
 use axum::{debug_handler, extract::Path, http::StatusCode, Extension, Json};
 use uuid::Uuid;
 
+use crate::api::engines::EngineType;
 use crate::api::errors::{ApiResult, AppError, ErrorCode};
 use crate::api::middleware::AuthenticatedUser;
 use crate::database::{
-    models::{CreateModelRequest, Model, UpdateModelRequest},
+    models::{CreateModelRequest, FileFormat, Model, UpdateModelRequest},
     queries::{models, providers, user_group_providers},
 };
 
@@ -31,6 +32,89 @@ pub async fn create_model(
     }
 }
 
+/// Add the models a provider serves that are not in its model list yet, returning the new ones
+#[debug_handler]
+pub async fn sync_provider_models(
+    Extension(_auth_user): Extension<AuthenticatedUser>,
+    Path(provider_id): Path<Uuid>,
+) -> ApiResult<Json<Vec<Model>>> {
+    let provider = match providers::get_provider_by_id(provider_id).await {
+        Ok(Some(provider)) => provider,
+        Ok(None) => return Err((StatusCode::NOT_FOUND, AppError::provider_not_found())),
+        Err(e) => {
+            eprintln!("Failed to get provider {}: {}", provider_id, e);
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                AppError::internal_error("Database operation failed"),
+            ));
+        }
+    };
+
+    let remote_models = match crate::ai::create_ai_provider_with_model_id(&provider, None).await {
+        Ok(ai_provider) => ai_provider.list_models().await,
+        Err(e) => Err(e),
+    };
+    let remote_models = match remote_models {
+        Ok(remote_models) => remote_models,
+        Err(e) => {
+            eprintln!("Failed to list models of provider {}: {}", provider_id, e);
+            return Err((
+                StatusCode::BAD_GATEWAY,
+                AppError::new(
+                    ErrorCode::SystemExternalServiceError,
+                    format!("Failed to list provider models: {}", e),
+                ),
+            ));
+        }
+    };
+
+    let existing_names: std::collections::HashSet<String> =
+        match models::get_models_by_provider_id(provider_id).await {
+            Ok(existing) => existing.into_iter().map(|m| m.name).collect(),
+            Err(e) => {
+                eprintln!("Failed to get models for provider {}: {}", provider_id, e);
+                return Err((
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    AppError::internal_error("Database operation failed"),
+                ));
+            }
+        };
+
+    let mut created = Vec::new();
+    for remote_model in remote_models {
+        if existing_names.contains(&remote_model.name) {
+            continue;
+        }
+
+        let request = CreateModelRequest {
+            provider_id,
+            name: remote_model.name,
+            display_name: remote_model.display_name,
+            description: None,
+            enabled: Some(true),
+            capabilities: Some(remote_model.capabilities),
+            parameters: None,
+            engine_type: EngineType::None,
+            engine_settings: None,
+            file_format: FileFormat::Safetensors,
+            source: None,
+        };
+
+        match models::create_model(provider_id, request).await {
+            Ok(model) => created.push(model),
+            Err(e) => {
+                eprintln!("Failed to add synced model for provider {}: {}", provider_id, e);
+                return Err((
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    AppError::internal_error("Database operation failed"),
+                ));
+            }
+        }
+    }
+
+    Ok((StatusCode::OK, Json(created)))
+}
+
 #[debug_handler]
 pub async fn update_model(
     Extension(_auth_user): Extension<AuthenticatedUser>,
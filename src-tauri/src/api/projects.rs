@@ -9,6 +9,7 @@ use serde::Deserialize;
 use uuid::Uuid;
 
 use crate::{
+    ai::mcp::roots::project_roots_changed,
     api::{
         errors::{ApiResult, AppError},
         middleware::AuthenticatedUser,
@@ -196,7 +197,10 @@ pub async fn delete_project(
     })?;
 
     match projects::delete_project(&pool, project_id, user.user_id).await {
-        Ok(true) => Ok((StatusCode::NO_CONTENT, StatusCode::NO_CONTENT)),
+        Ok(true) => {
+            project_roots_changed(project_id).await;
+            Ok((StatusCode::NO_CONTENT, StatusCode::NO_CONTENT))
+        }
         Ok(false) => Err((StatusCode::NOT_FOUND, AppError::not_found("Project"))),
         Err(e) => {
             eprintln!("Failed to delete project: {:?}", e);
@@ -123,6 +123,7 @@ pub async fn create_provider(
         "groq",
         "gemini",
         "mistral",
+        "ollama",
         "custom",
     ];
     if !valid_types.contains(&request.provider_type.as_str()) {
@@ -139,7 +140,10 @@ pub async fn create_provider(
     if let Some(true) = request.enabled {
         if request.provider_type != "local" {
             // Check API key
-            if request.api_key.is_none() || request.api_key.as_ref().unwrap().trim().is_empty() {
+            if requires_api_key(&request.provider_type)
+                && (request.api_key.is_none()
+                    || request.api_key.as_ref().unwrap().trim().is_empty())
+            {
                 eprintln!("Cannot create enabled provider: API key is required");
                 return Err((
                     StatusCode::BAD_REQUEST,
@@ -195,6 +199,11 @@ fn is_valid_url(url: &str) -> bool {
     reqwest::Url::parse(url).is_ok()
 }
 
+/// Ollama servers run without authentication by default, so the API key is optional for them
+fn requires_api_key(provider_type: &str) -> bool {
+    provider_type != "ollama"
+}
+
 #[debug_handler]
 pub async fn update_provider(
     Extension(_auth_user): Extension<AuthenticatedUser>,
@@ -213,7 +222,9 @@ pub async fn update_provider(
                         .api_key
                         .as_ref()
                         .or(current_provider.api_key.as_ref());
-                    if api_key.is_none() || api_key.unwrap().trim().is_empty() {
+                    if requires_api_key(current_provider.provider_type.as_str())
+                        && (api_key.is_none() || api_key.unwrap().trim().is_empty())
+                    {
                         eprintln!(
                             "Cannot enable provider {}: API key is required",
                             provider_id
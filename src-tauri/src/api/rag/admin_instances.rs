@@ -6,6 +6,7 @@ use axum::{
 };
 use uuid::Uuid;
 
+use crate::ai::rag::service::cancel_embedding_migration;
 use crate::api::types::PaginationQuery;
 use crate::api::{
     errors::{ApiResult, AppError},
@@ -121,6 +122,11 @@ pub async fn delete_system_rag_instance_handler(
 
     match existing {
         Some(instance) if instance.is_system => {
+            // Stop the embedding migration worker before its rows are removed
+            cancel_embedding_migration(instance_id)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, AppError::from(e)))?;
+
             let success = delete_rag_instance(instance_id)
                 .await
                 .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, AppError::from(e)))?;
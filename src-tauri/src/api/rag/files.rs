@@ -6,6 +6,7 @@ use axum::{
 };
 use uuid::Uuid;
 
+use crate::ai::rag::engines::simple_graph::indexing::remove_file_from_graph;
 use crate::api::{errors::ErrorCode, files::FileOperationSuccessResponse};
 use crate::api::{
     errors::{ApiResult, AppError},
@@ -13,13 +14,16 @@ use crate::api::{
 };
 use crate::database::{
     models::{
-        file::{FileCreateData, UploadFileResponse},
-        RAGInstanceFilesListResponse, RAGInstanceFilesQuery,
+        file::{File, FileCreateData, UploadFileResponse},
+        RAGInstanceFile, RAGInstanceFilesListResponse, RAGInstanceFilesQuery,
+        UpdateRAGInstanceFileTagsRequest,
     },
     queries::{
         files,
         rag_instance_files::{
-            add_file_to_rag_instance, list_rag_instance_files, remove_file_from_rag_instance,
+            add_file_to_rag_instance, find_rag_instance_file_by_filename,
+            list_rag_instance_files, remove_file_from_rag_instance,
+            reset_rag_instance_file_to_pending, update_rag_instance_file_tags,
         },
         rag_instances::validate_rag_instance_access,
     },
@@ -90,6 +94,7 @@ pub async fn upload_rag_file_handler(
     let mut file_data = None;
     let mut filename = String::new();
     let mut file_size = 0u64;
+    let mut tags: Option<Vec<String>> = None;
 
     while let Some(field) = multipart.next_field().await.map_err(|_| {
         (
@@ -110,6 +115,15 @@ pub async fn upload_rag_file_handler(
                 file_size = data.len() as u64;
                 file_data = Some(data);
             }
+            "tags" => {
+                let value = field.text().await.map_err(|_| {
+                    (
+                        StatusCode::BAD_REQUEST,
+                        AppError::new(ErrorCode::ValidInvalidInput, "Failed to read tags"),
+                    )
+                })?;
+                tags = Some(parse_tags_field(&value));
+            }
             _ => continue,
         }
     }
@@ -138,6 +152,7 @@ pub async fn upload_rag_file_handler(
         filename,
         file_data,
         file_size,
+        tags,
     )
     .await
     {
@@ -156,7 +171,24 @@ async fn process_rag_file_upload(
     filename: String,
     file_data: bytes::Bytes,
     file_size: u64,
+    tags: Option<Vec<String>>,
 ) -> Result<Json<UploadFileResponse>, StatusCode> {
+    // Re-uploading a file with the same name replaces its content in place, so processing
+    // can diff the new chunks against the documents already indexed for it
+    if let Some(existing) = find_rag_instance_file_by_filename(instance_id, user_id, &filename)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    {
+        let file_id = existing.id;
+        let response = replace_rag_file_content(instance_id, existing, file_data, file_size).await?;
+        if let Some(tags) = tags {
+            update_rag_instance_file_tags(instance_id, file_id, &tags)
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        }
+        return Ok(response);
+    }
+
     let file_id = Uuid::new_v4();
     let extension = extract_extension(&filename);
     let mime_type = get_mime_type_from_extension(&extension);
@@ -196,6 +228,44 @@ async fn process_rag_file_upload(
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
+    if let Some(tags) = tags.filter(|t| !t.is_empty()) {
+        update_rag_instance_file_tags(instance_id, file_id, &tags)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    }
+
+    Ok(Json(UploadFileResponse { file }))
+}
+
+/// Replace the content of a file already in the RAG instance and queue it for re-indexing
+async fn replace_rag_file_content(
+    instance_id: Uuid,
+    existing: File,
+    file_data: bytes::Bytes,
+    file_size: u64,
+) -> Result<Json<UploadFileResponse>, StatusCode> {
+    let extension = extract_extension(&existing.filename);
+    let mime_type = get_mime_type_from_extension(&extension);
+
+    // Identical content needs neither a write nor re-indexing
+    let checksum = RAG_FILE_STORAGE.calculate_checksum_of_bytes(&file_data);
+    if existing.checksum.as_deref() == Some(checksum.as_str()) {
+        return Ok(Json(UploadFileResponse { file: existing }));
+    }
+
+    RAG_FILE_STORAGE
+        .save_rag_file(instance_id, existing.id, &extension, &file_data)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let file = files::update_file_content(existing.id, file_size as i64, mime_type, Some(checksum))
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    reset_rag_instance_file_to_pending(instance_id, existing.id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
     Ok(Json(UploadFileResponse { file }))
 }
 
@@ -224,6 +294,16 @@ pub async fn delete_rag_file_handler(
         })?
         .ok_or((StatusCode::NOT_FOUND, AppError::not_found("File")))?;
 
+    // Drop what the file contributed to a knowledge graph while the file rows still exist
+    remove_file_from_graph(instance_id, file_id)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                AppError::internal_error("Failed to remove file from knowledge graph"),
+            )
+        })?;
+
     // Remove from rag_instance_files table
     let removed = remove_file_from_rag_instance(instance_id, file_id)
         .await
@@ -268,3 +348,45 @@ pub async fn delete_rag_file_handler(
         Json(FileOperationSuccessResponse { success: true }),
     ))
 }
+
+/// Replace the tags of a file in a RAG instance
+#[debug_handler]
+pub async fn update_rag_file_tags_handler(
+    Extension(auth_user): Extension<AuthenticatedUser>,
+    Path((instance_id, file_id)): Path<(Uuid, Uuid)>,
+    Json(request): Json<UpdateRAGInstanceFileTagsRequest>,
+) -> ApiResult<Json<RAGInstanceFile>> {
+    let has_access = validate_rag_instance_access(auth_user.user.id, instance_id, false)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, AppError::from(e)))?;
+    if !has_access {
+        return Err((StatusCode::FORBIDDEN, AppError::forbidden("Access denied")));
+    }
+
+    let tags = normalize_tags(request.tags);
+    let rag_file = update_rag_instance_file_tags(instance_id, file_id, &tags)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, AppError::from(e)))?
+        .ok_or((StatusCode::NOT_FOUND, AppError::not_found("RAG file")))?;
+
+    Ok((StatusCode::OK, Json(rag_file)))
+}
+
+/// Parse the `tags` upload field, either a JSON array or a comma-separated list
+fn parse_tags_field(value: &str) -> Vec<String> {
+    let tags = serde_json::from_str::<Vec<String>>(value)
+        .unwrap_or_else(|_| value.split(',').map(|t| t.to_string()).collect());
+    normalize_tags(tags)
+}
+
+/// Trim tags and drop empty and duplicate ones, keeping their order
+fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags {
+        let tag = tag.trim();
+        if !tag.is_empty() && !normalized.iter().any(|t| t == tag) {
+            normalized.push(tag.to_string());
+        }
+    }
+    normalized
+}
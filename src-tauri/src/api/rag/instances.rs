@@ -16,16 +16,18 @@ use crate::api::{
     middleware::auth::AuthenticatedUser,
 };
 use crate::ai::rag::{
-    engines::{simple_vector::RAGSimpleVectorEngine, traits::RAGEngine},
-    QueryMode, RAGQuery, RAGSource,
+    engines::RAGEngineFactory,
+    service::cancel_embedding_migration,
+    RAGAnswer, RAGQuery, RAGQueryFilter, RAGSource,
 };
 use crate::database::{
     models::{
-        file::File, CreateRAGInstanceRequest, RAGInstance, RAGInstanceListResponse, RAGProvider,
-        UpdateRAGInstanceRequest, RAGInstanceErrorCode,
+        file::File, CreateRAGInstanceRequest, RAGEmbeddingMigration, RAGInstance,
+        RAGInstanceListResponse, RAGProvider, UpdateRAGInstanceRequest, RAGInstanceErrorCode,
     },
     queries::{
         files::get_files_by_ids,
+        rag_embedding_migrations::get_latest_rag_embedding_migration,
         rag_instances::{
             create_user_rag_instance, delete_rag_instance, get_rag_instance,
             get_instance_file_processing_details, get_rag_instance_status_with_stats,
@@ -88,6 +90,8 @@ pub struct SSERAGInstanceStatusUpdateData {
     pub failed_files: i64,
     pub processing_files: i64,
     pub current_files_processing: Vec<RAGFileProcessingStatus>,
+    /// Latest embedding migration, reporting re-embedding progress after a model change
+    pub embedding_migration: Option<RAGEmbeddingMigration>,
     pub updated_at: String,
 }
 
@@ -103,6 +107,11 @@ pub struct RAGInstanceListQuery {
 pub struct RAGQueryRequest {
     /// The query text to search for
     pub query: String,
+    /// Generate an answer with citations from the retrieved chunks using the instance's LLM
+    #[serde(default)]
+    pub generate_answer: bool,
+    /// Only search files matching the filter
+    pub filter: Option<RAGQueryFilter>,
 }
 
 #[derive(Debug, Serialize, JsonSchema)]
@@ -115,6 +124,8 @@ pub struct RAGQueryResponse {
     pub token_usage: RAGTokenUsage,
     /// Processing metadata
     pub metadata: RAGQueryMetadata,
+    /// Generated answer with citations, when requested
+    pub answer: Option<RAGAnswer>,
 }
 
 #[derive(Debug, Serialize, JsonSchema)]
@@ -280,6 +291,14 @@ pub async fn delete_rag_instance_handler(
         ));
     }
 
+    // Stop the embedding migration worker before its rows are removed
+    cancel_embedding_migration(instance_id).await.map_err(|e| {
+        (
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+            AppError::from(e),
+        )
+    })?;
+
     let success = delete_rag_instance(instance_id).await.map_err(|e| {
         (
             axum::http::StatusCode::INTERNAL_SERVER_ERROR,
@@ -385,6 +404,33 @@ pub async fn list_creatable_rag_providers_handler(
     Ok((axum::http::StatusCode::OK, Json(providers)))
 }
 
+/// Cancel the running embedding migration of a RAG instance, keeping its current model
+#[debug_handler]
+pub async fn cancel_rag_embedding_migration_handler(
+    Extension(auth_user): Extension<AuthenticatedUser>,
+    Path(instance_id): Path<Uuid>,
+) -> ApiResult<StatusCode> {
+    let has_access = validate_rag_instance_access(auth_user.user.id, instance_id, true)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, AppError::from(e)))?;
+    if !has_access {
+        return Err((StatusCode::FORBIDDEN, AppError::forbidden("Access denied")));
+    }
+
+    let cancelled = cancel_embedding_migration(instance_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, AppError::from(e)))?;
+
+    if cancelled {
+        Ok((StatusCode::NO_CONTENT, StatusCode::NO_CONTENT))
+    } else {
+        Err((
+            StatusCode::NOT_FOUND,
+            AppError::not_found("Embedding migration"),
+        ))
+    }
+}
+
 /// Subscribe to RAG instance status stream via SSE
 #[debug_handler]
 pub async fn subscribe_rag_instance_status(
@@ -500,6 +546,10 @@ async fn get_rag_instance_status_update(
         Vec::new()
     };
 
+    let embedding_migration = get_latest_rag_embedding_migration(instance_id)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?;
+
     Ok(Some(SSERAGInstanceStatusUpdateData {
         instance_id: instance.id.to_string(),
         name: instance.name,
@@ -511,6 +561,7 @@ async fn get_rag_instance_status_update(
         failed_files: instance.failed_files,
         processing_files: instance.processing_files,
         current_files_processing: current_files,
+        embedding_migration,
         updated_at: instance.updated_at.to_rfc3339(),
     }))
 }
@@ -533,20 +584,22 @@ pub async fn query_rag_instance_handler(
     }
 
     // Get RAG instance details
-    let _instance = get_rag_instance(instance_id, auth_user.user.id)
+    let instance = get_rag_instance(instance_id, auth_user.user.id)
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, AppError::from(e)))?
         .ok_or((StatusCode::NOT_FOUND, AppError::not_found("RAG instance")))?;
 
-    // Create RAG engine
-    let engine = RAGSimpleVectorEngine::new(instance_id)
+    // Create RAG engine for the instance's engine type
+    let engine = RAGEngineFactory::create_engine(instance_id)
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, AppError::internal_error(&format!("Failed to create RAG engine: {}", e))))?;
 
-    // Create RAG query (no context needed anymore)
+    // Create RAG query using the instance's configured query mode
     let rag_query = RAGQuery {
         text: request.query,
-        mode: QueryMode::Bypass, // Always use Bypass mode for this testing endpoint
+        mode: instance.get_query_mode(),
+        generate_answer: request.generate_answer,
+        filter: request.filter,
     };
 
     // Execute query
@@ -559,6 +612,7 @@ pub async fn query_rag_instance_handler(
 
     // The results are already in RAGSource format from the RAG engine
     let results = rag_response.sources;
+    let answer = rag_response.answer;
 
     // Extract unique file IDs from results and fetch file information
     let unique_file_ids: Vec<Uuid> = results
@@ -594,9 +648,8 @@ pub async fn query_rag_instance_handler(
         chunks_retrieved: rag_response.metadata.get("chunks_retrieved")
             .and_then(|v| v.as_u64()).unwrap_or(0) as usize,
         chunks_filtered: results.len(), // Number of results we're returning
-        rerank_applied: engine.rag_instance().instance.engine_settings.simple_vector
-            .as_ref()
-            .map_or(false, |s| s.querying.as_ref().map_or(false, |q| q.enable_rerank())),
+        rerank_applied: rag_response.metadata.get("rerank_applied")
+            .and_then(|v| v.as_bool()).unwrap_or(false),
     };
 
     let response = RAGQueryResponse {
@@ -604,6 +657,7 @@ pub async fn query_rag_instance_handler(
         files,
         token_usage,
         metadata,
+        answer,
     };
 
     Ok((StatusCode::OK, Json(response)))
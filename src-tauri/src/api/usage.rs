@@ -0,0 +1,163 @@
+use axum::{
+    debug_handler,
+    extract::{Path, Query},
+    http::StatusCode,
+    Extension, Json,
+};
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::api::{
+    errors::{ApiResult, AppError, ErrorCode},
+    middleware::AuthenticatedUser,
+};
+use crate::database::{
+    models::{
+        UsagePeriod, UsageQuota, UsageQuotaRequest, UsageReport, UsageReportQuery,
+        UserUsageResponse,
+    },
+    queries::token_usage,
+};
+
+/// Get the current user's usage today and this month, with the quotas that apply to them
+#[debug_handler]
+pub async fn get_my_usage(
+    Extension(auth_user): Extension<AuthenticatedUser>,
+) -> ApiResult<Json<UserUsageResponse>> {
+    let now = Utc::now();
+    let user_id = auth_user.user_id;
+
+    let result = async {
+        Ok::<_, sqlx::Error>(UserUsageResponse {
+            today: token_usage::get_user_usage_totals(user_id, UsagePeriod::Daily.start(now))
+                .await?,
+            this_month: token_usage::get_user_usage_totals(
+                user_id,
+                UsagePeriod::Monthly.start(now),
+            )
+            .await?,
+            quotas: token_usage::get_user_quota_statuses(user_id).await?,
+        })
+    }
+    .await;
+
+    match result {
+        Ok(usage) => Ok((StatusCode::OK, Json(usage))),
+        Err(e) => {
+            tracing::error!("Failed to get usage of user {}: {}", user_id, e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, AppError::internal_error("Database error")))
+        }
+    }
+}
+
+/// Get the usage of every user and group, and of the API proxy server, over the current
+/// day or month
+#[debug_handler]
+pub async fn get_usage_report(
+    Extension(_auth_user): Extension<AuthenticatedUser>,
+    Query(params): Query<UsageReportQuery>,
+) -> ApiResult<Json<UsageReport>> {
+    let period = params.period.unwrap_or(UsagePeriod::Monthly);
+    let period_start = period.start(Utc::now());
+
+    let result = async {
+        Ok::<_, sqlx::Error>(UsageReport {
+            period,
+            period_start,
+            users: token_usage::list_user_usage(period_start).await?,
+            groups: token_usage::list_group_usage(period_start).await?,
+            api_proxy: token_usage::get_api_proxy_usage_totals(period_start).await?,
+        })
+    }
+    .await;
+
+    match result {
+        Ok(report) => Ok((StatusCode::OK, Json(report))),
+        Err(e) => {
+            tracing::error!("Failed to get usage report: {}", e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, AppError::internal_error("Database error")))
+        }
+    }
+}
+
+/// List usage quotas
+#[debug_handler]
+pub async fn list_usage_quotas(
+    Extension(_auth_user): Extension<AuthenticatedUser>,
+) -> ApiResult<Json<Vec<UsageQuota>>> {
+    match token_usage::list_usage_quotas().await {
+        Ok(quotas) => Ok((StatusCode::OK, Json(quotas))),
+        Err(e) => {
+            tracing::error!("Failed to list usage quotas: {}", e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, AppError::internal_error("Database error")))
+        }
+    }
+}
+
+/// Create a usage quota
+#[debug_handler]
+pub async fn create_usage_quota(
+    Extension(_auth_user): Extension<AuthenticatedUser>,
+    Json(request): Json<UsageQuotaRequest>,
+) -> ApiResult<Json<UsageQuota>> {
+    validate_quota_request(&request)?;
+
+    match token_usage::create_usage_quota(request).await {
+        Ok(quota) => Ok((StatusCode::CREATED, Json(quota))),
+        Err(e) => Err(usage_quota_write_error(e)),
+    }
+}
+
+/// Replace a usage quota
+#[debug_handler]
+pub async fn update_usage_quota(
+    Extension(_auth_user): Extension<AuthenticatedUser>,
+    Path(quota_id): Path<Uuid>,
+    Json(request): Json<UsageQuotaRequest>,
+) -> ApiResult<Json<UsageQuota>> {
+    validate_quota_request(&request)?;
+
+    match token_usage::update_usage_quota(quota_id, request).await {
+        Ok(Some(quota)) => Ok((StatusCode::OK, Json(quota))),
+        Ok(None) => Err((StatusCode::NOT_FOUND, AppError::not_found("Usage quota"))),
+        Err(e) => Err(usage_quota_write_error(e)),
+    }
+}
+
+/// Delete a usage quota
+#[debug_handler]
+pub async fn delete_usage_quota(
+    Extension(_auth_user): Extension<AuthenticatedUser>,
+    Path(quota_id): Path<Uuid>,
+) -> ApiResult<StatusCode> {
+    match token_usage::delete_usage_quota(quota_id).await {
+        Ok(true) => Ok((StatusCode::NO_CONTENT, StatusCode::NO_CONTENT)),
+        Ok(false) => Err((StatusCode::NOT_FOUND, AppError::not_found("Usage quota"))),
+        Err(e) => {
+            tracing::error!("Failed to delete usage quota {}: {}", quota_id, e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, AppError::internal_error("Database error")))
+        }
+    }
+}
+
+fn validate_quota_request(request: &UsageQuotaRequest) -> Result<(), (StatusCode, AppError)> {
+    request.validate().map_err(|message| {
+        (
+            StatusCode::BAD_REQUEST,
+            AppError::new(ErrorCode::ValidInvalidInput, message),
+        )
+    })
+}
+
+fn usage_quota_write_error(e: sqlx::Error) -> (StatusCode, AppError) {
+    match &e {
+        sqlx::Error::Database(db_error) if db_error.is_foreign_key_violation() => (
+            StatusCode::BAD_REQUEST,
+            AppError::new(ErrorCode::ValidInvalidInput, "Unknown user or group in usage quota"),
+        ),
+        _ => {
+            tracing::error!("Failed to save usage quota: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, AppError::internal_error("Database error"))
+        }
+    }
+}
@@ -31,6 +31,8 @@ make_transparent!(
 pub enum MessageContentType {
     #[serde(rename = "text")]
     Text,
+    #[serde(rename = "thinking")]
+    Thinking,
     #[serde(rename = "tool_call")]
     ToolCall,
     #[serde(rename = "tool_call_pending_approval")]
@@ -41,6 +43,8 @@ pub enum MessageContentType {
     ToolResult,
     #[serde(rename = "file_attachment")]
     FileAttachment,
+    #[serde(rename = "generated_image")]
+    GeneratedImage,
     #[serde(rename = "error")]
     Error,
 }
@@ -52,6 +56,15 @@ pub enum MessageContentData {
     #[serde(rename = "text")]
     Text { text: String },
 
+    /// Reasoning produced by the model before its answer
+    #[serde(rename = "thinking")]
+    Thinking {
+        thinking: String,
+        /// Provider signature of the reasoning, required to send it back (Anthropic)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        signature: Option<String>,
+    },
+
     #[serde(rename = "tool_call")]
     ToolCall {
         tool_name: String,
@@ -90,6 +103,17 @@ pub enum MessageContentData {
         file_type: Option<String>,
     },
 
+    /// Image produced by an image generation model, stored as a file of the message
+    #[serde(rename = "generated_image")]
+    GeneratedImage {
+        file_id: Uuid,
+        filename: String,
+        mime_type: String,
+        /// Prompt the provider actually used, when it rewrote the one it was given
+        #[serde(skip_serializing_if = "Option::is_none")]
+        revised_prompt: Option<String>,
+    },
+
     #[serde(rename = "error")]
     Error {
         error_type: String,
@@ -102,11 +126,13 @@ impl MessageContentType {
     pub fn as_str(&self) -> &'static str {
         match self {
             MessageContentType::Text => "text",
+            MessageContentType::Thinking => "thinking",
             MessageContentType::ToolCall => "tool_call",
             MessageContentType::ToolCallPendingApproval => "tool_call_pending_approval",
             MessageContentType::ToolCallPendingApprovalCancel => "tool_call_pending_approval_cancel",
             MessageContentType::ToolResult => "tool_result",
             MessageContentType::FileAttachment => "file_attachment",
+            MessageContentType::GeneratedImage => "generated_image",
             MessageContentType::Error => "error",
         }
     }
@@ -114,11 +140,13 @@ impl MessageContentType {
     pub fn from_str(s: &str) -> Option<Self> {
         match s {
             "text" => Some(MessageContentType::Text),
+            "thinking" => Some(MessageContentType::Thinking),
             "tool_call" => Some(MessageContentType::ToolCall),
             "tool_call_pending_approval" => Some(MessageContentType::ToolCallPendingApproval),
             "tool_call_pending_approval_cancel" => Some(MessageContentType::ToolCallPendingApprovalCancel),
             "tool_result" => Some(MessageContentType::ToolResult),
             "file_attachment" => Some(MessageContentType::FileAttachment),
+            "generated_image" => Some(MessageContentType::GeneratedImage),
             "error" => Some(MessageContentType::Error),
             _ => None,
         }
@@ -168,6 +196,16 @@ impl From<MessageContentRow> for MessageContentItem {
                     .to_string();
                 MessageContentData::Text { text }
             }
+            MessageContentType::Thinking => {
+                let thinking = row.content.get("thinking")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("")
+                    .to_string();
+                let signature = row.content.get("signature")
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_string());
+                MessageContentData::Thinking { thinking, signature }
+            }
             MessageContentType::ToolCall => {
                 let tool_name = row.content.get("tool_name")
                     .and_then(|v| v.as_str())
@@ -243,6 +281,24 @@ impl From<MessageContentRow> for MessageContentItem {
                     .map(|s| s.to_string());
                 MessageContentData::FileAttachment { file_id, filename, file_type }
             }
+            MessageContentType::GeneratedImage => {
+                let file_id = row.content.get("file_id")
+                    .and_then(|v| v.as_str())
+                    .and_then(|s| Uuid::parse_str(s).ok())
+                    .unwrap_or_default();
+                let filename = row.content.get("filename")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("")
+                    .to_string();
+                let mime_type = row.content.get("mime_type")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("image/png")
+                    .to_string();
+                let revised_prompt = row.content.get("revised_prompt")
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_string());
+                MessageContentData::GeneratedImage { file_id, filename, mime_type, revised_prompt }
+            }
             MessageContentType::Error => {
                 let error_type = row.content.get("error_type")
                     .and_then(|v| v.as_str())
@@ -482,6 +538,11 @@ pub enum ContentPart {
         call_id: String,
         output: String,
     },
+    /// Reasoning of a previous assistant turn, only sent to providers replaying it
+    Thinking {
+        text: String,
+        signature: Option<String>,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -542,6 +603,7 @@ pub struct ChatRequest {
     pub stream: bool,
     pub parameters: Option<crate::database::models::model::ModelParameters>,
     pub tools: Option<Vec<ToolDefinition>>, // Tool definitions sent to AI
+    pub json_schema: Option<serde_json::Value>, // JSON Schema the reply must match
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -550,9 +612,10 @@ pub struct AIProviderChatResponse {
     pub finish_reason: Option<String>,
     pub usage: Option<Usage>,
     pub tool_use: Option<ToolUse>, // Tool request from AI (not executed yet)
+    pub thinking: Option<String>,  // Reasoning returned apart from the content
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Usage {
     pub prompt_tokens: Option<u32>,
     pub completion_tokens: Option<u32>,
@@ -564,4 +627,7 @@ pub struct StreamingChunk {
     pub content: Option<String>,
     pub finish_reason: Option<String>,
     pub tool_use: Option<ToolUse>, // Tool request from AI (not executed yet)
+    pub usage: Option<Usage>,      // Token usage, sent with the last chunks of a stream
+    pub thinking: Option<String>,  // Reasoning delta, kept apart from the content
+    pub thinking_signature: Option<String>, // Signature closing a reasoning block (Anthropic)
 }
@@ -0,0 +1,104 @@
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+// Client registered with the authorization server of an MCP server
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct MCPOAuthClient {
+    pub id: Uuid,
+    pub server_id: Uuid,
+    pub issuer: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    pub registration_endpoint: Option<String>,
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    pub redirect_uri: String,
+    // Canonical URL of the MCP server, sent as the resource indicator (RFC 8707)
+    pub resource: String,
+    pub scope: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// Client to save after registering with an authorization server
+#[derive(Debug, Clone)]
+pub struct NewMCPOAuthClient {
+    pub server_id: Uuid,
+    pub issuer: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    pub registration_endpoint: Option<String>,
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    pub redirect_uri: String,
+    pub resource: String,
+    pub scope: Option<String>,
+}
+
+// Token a user obtained for an MCP server; never sent to the frontend
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct MCPOAuthToken {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub server_id: Uuid,
+    pub oauth_client_id: Uuid,
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+    pub token_type: String,
+    pub scope: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// Token granted by a token endpoint, to save for a user
+#[derive(Debug, Clone)]
+pub struct MCPOAuthTokenGrant {
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+    pub token_type: String,
+    pub scope: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl MCPOAuthToken {
+    /// Whether the token expires within `margin_seconds`
+    pub fn expires_within(&self, margin_seconds: i64) -> bool {
+        self.expires_at.map_or(false, |expires_at| {
+            expires_at - chrono::Duration::seconds(margin_seconds) <= Utc::now()
+        })
+    }
+}
+
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+pub struct StartMCPOAuthRequest {
+    /// Where the authorization server sends the user back; defaults to this server's
+    /// `/api/mcp/oauth/callback` on localhost
+    pub redirect_uri: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct StartMCPOAuthResponse {
+    /// Page to open in the browser to authorize access to the server
+    pub authorization_url: String,
+    pub state: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct MCPOAuthCallbackQuery {
+    pub code: Option<String>,
+    pub state: Option<String>,
+    pub error: Option<String>,
+    pub error_description: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct MCPOAuthStatus {
+    pub server_id: Uuid,
+    pub authorized: bool,
+    pub scope: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub refreshable: bool,
+}
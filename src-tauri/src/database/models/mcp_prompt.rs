@@ -0,0 +1,33 @@
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MCPPrompt {
+    pub id: Uuid,
+    pub server_id: Uuid,
+    pub prompt_name: String,
+    pub prompt_description: Option<String>,
+    pub arguments: serde_json::Value, // [{ name, description, required }]
+    pub discovered_at: DateTime<Utc>,
+}
+
+// Request/Response types for getting a prompt
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct GetMCPPromptRequest {
+    pub arguments: Option<HashMap<String, String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MCPPromptMessage {
+    pub role: String,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MCPPromptResult {
+    pub description: Option<String>,
+    pub messages: Vec<MCPPromptMessage>,
+}
@@ -0,0 +1,29 @@
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MCPResource {
+    pub id: Uuid,
+    pub server_id: Uuid,
+    pub uri: String,
+    pub resource_name: String,
+    pub resource_description: Option<String>,
+    pub mime_type: Option<String>,
+    pub discovered_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ReadMCPResourceQuery {
+    pub uri: String,
+}
+
+// One entry of a resource read: text resources fill `text`, binary resources fill `blob`
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MCPResourceContent {
+    pub uri: String,
+    pub mime_type: Option<String>,
+    pub text: Option<String>,
+    pub blob: Option<String>, // base64
+}
@@ -115,6 +115,8 @@ pub struct MCPServer {
     pub environment_variables: serde_json::Value,
     pub url: Option<String>,
     pub headers: serde_json::Value,
+    // Sandbox profile of stdio servers, see MCPSandboxProfile
+    pub sandbox: serde_json::Value,
     pub timeout_seconds: Option<i32>,
 
     // Status
@@ -137,6 +139,31 @@ pub struct MCPServer {
     pub updated_at: DateTime<Utc>,
 }
 
+impl MCPServer {
+    /// The sandbox profile of the server, when sandboxing is enabled. A profile that cannot be
+    /// read is an error rather than no sandbox, so the server never starts unsandboxed by mistake.
+    pub fn sandbox_profile(&self) -> Result<Option<MCPSandboxProfile>, serde_json::Error> {
+        let profile = serde_json::from_value::<MCPSandboxProfile>(self.sandbox.clone())?;
+        Ok(Some(profile).filter(|profile| profile.enabled))
+    }
+}
+
+/// Isolation of a stdio server's process (Linux only). The server sees a read-only
+/// filesystem except its scratch directory and `writable_paths`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(default)]
+pub struct MCPSandboxProfile {
+    pub enabled: bool,
+    /// Run the server without network access
+    pub deny_network: bool,
+    /// Host paths the server may write to besides its scratch directory
+    pub writable_paths: Vec<String>,
+    /// Address space limit of the server process, in megabytes
+    pub memory_limit_mb: Option<u64>,
+    /// CPU time limit of the server process, in seconds
+    pub cpu_time_limit_seconds: Option<u64>,
+}
+
 // Request/Response types
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct CreateMCPServerRequest {
@@ -151,6 +178,7 @@ pub struct CreateMCPServerRequest {
     pub environment_variables: Option<serde_json::Value>,
     pub url: Option<String>,
     pub headers: Option<serde_json::Value>,
+    pub sandbox: Option<MCPSandboxProfile>,
     pub timeout_seconds: Option<i32>,
 
     pub max_restart_attempts: Option<i32>,
@@ -170,6 +198,7 @@ pub struct CreateSystemMCPServerRequest {
     pub environment_variables: Option<serde_json::Value>,
     pub url: Option<String>,
     pub headers: Option<serde_json::Value>,
+    pub sandbox: Option<MCPSandboxProfile>,
     pub timeout_seconds: Option<i32>,
 
     pub max_restart_attempts: Option<i32>,
@@ -188,6 +217,7 @@ pub struct UpdateMCPServerRequest {
     pub environment_variables: Option<serde_json::Value>,
     pub url: Option<String>,
     pub headers: Option<serde_json::Value>,
+    pub sandbox: Option<MCPSandboxProfile>,
     pub timeout_seconds: Option<i32>,
 
     pub max_restart_attempts: Option<i32>,
@@ -0,0 +1,263 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::mcp_server::{
+    CreateMCPServerRequest, CreateSystemMCPServerRequest, MCPServer, MCPTransportType,
+};
+
+/// MCP servers in the `mcpServers` config format shared by Claude Desktop, Cursor and
+/// others. VS Code's `servers` key is accepted on import.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct MCPServersConfig {
+    #[serde(rename = "mcpServers", alias = "servers")]
+    pub mcp_servers: BTreeMap<String, MCPServerConfigEntry>,
+}
+
+/// A server of the `mcpServers` format, keyed by its name. Local servers give `command`,
+/// `args` and `env`; remote servers give `url` and `headers`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct MCPServerConfigEntry {
+    /// `stdio`, `http` (also `streamable-http`) or `sse`; inferred when missing
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub transport_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub disabled: bool,
+}
+
+impl MCPServerConfigEntry {
+    /// The transport of the entry, from its `type` or else from whether it has a command
+    /// or a URL
+    pub fn resolve_transport_type(&self) -> Result<MCPTransportType, String> {
+        if let Some(transport_type) = &self.transport_type {
+            return match transport_type.to_lowercase().as_str() {
+                "stdio" => Ok(MCPTransportType::Stdio),
+                "http" | "streamable-http" | "streamablehttp" => Ok(MCPTransportType::Http),
+                "sse" => Ok(MCPTransportType::Sse),
+                other => Err(format!("unknown transport type '{}'", other)),
+            };
+        }
+
+        match (&self.command, &self.url) {
+            (Some(_), None) => Ok(MCPTransportType::Stdio),
+            (None, Some(url)) if url.trim_end_matches('/').ends_with("/sse") => {
+                Ok(MCPTransportType::Sse)
+            }
+            (None, Some(_)) => Ok(MCPTransportType::Http),
+            (Some(_), Some(_)) => Err("give either a command or a url, not both".to_string()),
+            (None, None) => Err("a command or a url is required".to_string()),
+        }
+    }
+
+    /// Map the entry named `key` to the server it creates, checking it is complete
+    pub fn to_create_request(&self, key: &str) -> Result<CreateMCPServerRequest, String> {
+        let name = sanitize_server_name(key)?;
+        let transport_type = self.resolve_transport_type()?;
+
+        match transport_type {
+            MCPTransportType::Stdio => {
+                if self.command.as_deref().map_or(true, |c| c.trim().is_empty()) {
+                    return Err("stdio servers need a command".to_string());
+                }
+                if self.url.is_some() {
+                    return Err("stdio servers do not take a url".to_string());
+                }
+            }
+            MCPTransportType::Http | MCPTransportType::Sse => {
+                let url = self
+                    .url
+                    .as_deref()
+                    .ok_or_else(|| "remote servers need a url".to_string())?;
+                let parsed = url::Url::parse(url).map_err(|e| format!("invalid url: {}", e))?;
+                if !matches!(parsed.scheme(), "http" | "https") {
+                    return Err("url must use http or https".to_string());
+                }
+                if self.command.is_some() {
+                    return Err("remote servers do not take a command".to_string());
+                }
+            }
+        }
+
+        Ok(CreateMCPServerRequest {
+            name,
+            display_name: key.to_string(),
+            description: self.description.clone(),
+            transport_type,
+            command: self.command.clone(),
+            args: Some(serde_json::json!(self.args)),
+            environment_variables: Some(serde_json::json!(self.env)),
+            url: self.url.clone(),
+            headers: Some(serde_json::json!(self.headers)),
+            sandbox: None,
+            timeout_seconds: None,
+            max_restart_attempts: None,
+            enabled: Some(!self.disabled),
+        })
+    }
+
+    /// The entry describing an existing server
+    pub fn from_server(server: &MCPServer) -> Self {
+        let string_map = |value: &serde_json::Value| -> BTreeMap<String, String> {
+            value
+                .as_object()
+                .map(|object| {
+                    object
+                        .iter()
+                        .filter_map(|(k, v)| v.as_str().map(|v| (k.clone(), v.to_string())))
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+
+        match server.transport_type {
+            MCPTransportType::Stdio => Self {
+                command: server.command.clone(),
+                args: serde_json::from_value(server.args.clone()).unwrap_or_default(),
+                env: string_map(&server.environment_variables),
+                description: server.description.clone(),
+                disabled: !server.enabled,
+                ..Default::default()
+            },
+            MCPTransportType::Http | MCPTransportType::Sse => Self {
+                transport_type: Some(
+                    match server.transport_type {
+                        MCPTransportType::Sse => "sse",
+                        _ => "http",
+                    }
+                    .to_string(),
+                ),
+                url: server.url.clone(),
+                headers: string_map(&server.headers),
+                description: server.description.clone(),
+                disabled: !server.enabled,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl MCPServersConfig {
+    /// The config listing existing servers, keyed by their names
+    pub fn from_servers(servers: &[MCPServer]) -> Self {
+        Self {
+            mcp_servers: servers
+                .iter()
+                .map(|server| (server.name.clone(), MCPServerConfigEntry::from_server(server)))
+                .collect(),
+        }
+    }
+}
+
+impl From<CreateMCPServerRequest> for CreateSystemMCPServerRequest {
+    fn from(request: CreateMCPServerRequest) -> Self {
+        Self {
+            name: request.name,
+            display_name: request.display_name,
+            description: request.description,
+            transport_type: request.transport_type,
+            command: request.command,
+            args: request.args,
+            environment_variables: request.environment_variables,
+            url: request.url,
+            headers: request.headers,
+            sandbox: request.sandbox,
+            timeout_seconds: request.timeout_seconds,
+            max_restart_attempts: request.max_restart_attempts,
+            enabled: request.enabled,
+        }
+    }
+}
+
+/// Server names only take letters, digits, `_` and `-`; other characters of a config key
+/// become `-`
+fn sanitize_server_name(key: &str) -> Result<String, String> {
+    let name: String = key
+        .trim()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' || c == '-' { c } else { '-' })
+        .collect();
+    let name = name.trim_matches('-');
+
+    if name.is_empty() {
+        return Err("the server name must contain letters or digits".to_string());
+    }
+    Ok(name.to_string())
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct MCPServerImportIssue {
+    /// Key of the entry in `mcpServers`
+    pub name: String,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ImportMCPServersResponse {
+    pub created: Vec<MCPServer>,
+    /// Valid entries that were not created, e.g. because a server has the same name
+    pub skipped: Vec<MCPServerImportIssue>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_local_and_remote_entries() {
+        let config: MCPServersConfig = serde_json::from_value(serde_json::json!({
+            "mcpServers": {
+                "filesystem": {
+                    "command": "npx",
+                    "args": ["-y", "@modelcontextprotocol/server-filesystem", "/tmp"],
+                    "env": { "DEBUG": "1" }
+                },
+                "remote.events": { "url": "https://example.com/mcp/sse" },
+                "api": { "type": "streamable-http", "url": "https://example.com/mcp", "disabled": true }
+            }
+        }))
+        .unwrap();
+
+        let filesystem = config.mcp_servers["filesystem"].to_create_request("filesystem").unwrap();
+        assert_eq!(filesystem.transport_type, MCPTransportType::Stdio);
+        assert_eq!(filesystem.args, Some(serde_json::json!(["-y", "@modelcontextprotocol/server-filesystem", "/tmp"])));
+
+        let events = config.mcp_servers["remote.events"].to_create_request("remote.events").unwrap();
+        assert_eq!(events.name, "remote-events");
+        assert_eq!(events.transport_type, MCPTransportType::Sse);
+
+        let api = config.mcp_servers["api"].to_create_request("api").unwrap();
+        assert_eq!(api.transport_type, MCPTransportType::Http);
+        assert_eq!(api.enabled, Some(false));
+    }
+
+    #[test]
+    fn rejects_incomplete_entries() {
+        let entry = MCPServerConfigEntry::default();
+        assert!(entry.to_create_request("empty").is_err());
+
+        let entry = MCPServerConfigEntry {
+            transport_type: Some("http".to_string()),
+            url: Some("ftp://example.com".to_string()),
+            ..Default::default()
+        };
+        assert!(entry.to_create_request("ftp").is_err());
+
+        let entry = MCPServerConfigEntry {
+            command: Some("node".to_string()),
+            ..Default::default()
+        };
+        assert!(entry.to_create_request("!!!").is_err());
+    }
+}
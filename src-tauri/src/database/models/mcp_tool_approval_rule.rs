@@ -0,0 +1,526 @@
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::Row;
+use uuid::Uuid;
+
+/// What an approval rule does with the tool calls it matches
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ApprovalRuleAction {
+    Approve,
+    Deny,
+}
+
+impl ApprovalRuleAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApprovalRuleAction::Approve => "approve",
+            ApprovalRuleAction::Deny => "deny",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "approve" => Some(ApprovalRuleAction::Approve),
+            "deny" => Some(ApprovalRuleAction::Deny),
+            _ => None,
+        }
+    }
+}
+
+/// Comparison applied to the argument found at a condition's path. `contains` looks for a
+/// substring of a string or an element of an array, `matches` takes a glob pattern (`*` and
+/// `?`) and `path_under` checks a file path lies inside a directory after resolving `.` and
+/// `..`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalRuleOperator {
+    Equals,
+    NotEquals,
+    StartsWith,
+    EndsWith,
+    Contains,
+    Matches,
+    PathUnder,
+    Exists,
+    NotExists,
+}
+
+/// Condition over the arguments of a tool call
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ApprovalRuleCondition {
+    /// JSON path into the arguments, e.g. `$.path`, `$.options.recursive` or `$.files[0]`
+    pub path: String,
+    pub operator: ApprovalRuleOperator,
+    /// Value to compare with; not used by `exists` and `not_exists`
+    pub value: Option<Value>,
+}
+
+/// Admin-defined rule that approves or denies MCP tool calls before user approvals are
+/// consulted. Enabled rules are evaluated by descending priority (deny first on ties) and the
+/// first matching rule decides.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MCPToolApprovalRule {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    /// Server the rule applies to; any server when not set
+    pub server_id: Option<Uuid>,
+    /// Glob pattern (`*` and `?`) matched against the tool name
+    pub tool_name_pattern: String,
+    /// User group the rule applies to; any user when not set
+    pub group_id: Option<Uuid>,
+    /// Conditions over the call arguments, all of which must hold
+    pub conditions: Vec<ApprovalRuleCondition>,
+    pub action: ApprovalRuleAction,
+    pub priority: i32,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl sqlx::FromRow<'_, sqlx::postgres::PgRow> for MCPToolApprovalRule {
+    fn from_row(row: &sqlx::postgres::PgRow) -> Result<Self, sqlx::Error> {
+        let action: String = row.try_get("action")?;
+        let conditions: sqlx::types::Json<Vec<ApprovalRuleCondition>> =
+            row.try_get("conditions")?;
+
+        Ok(Self {
+            id: row.try_get("id")?,
+            name: row.try_get("name")?,
+            description: row.try_get("description")?,
+            server_id: row.try_get("server_id")?,
+            tool_name_pattern: row.try_get("tool_name_pattern")?,
+            group_id: row.try_get("group_id")?,
+            conditions: conditions.0,
+            action: ApprovalRuleAction::from_str(&action).ok_or_else(|| {
+                sqlx::Error::Decode(format!("Invalid approval rule action '{}'", action).into())
+            })?,
+            priority: row.try_get("priority")?,
+            enabled: row.try_get("enabled")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+}
+
+/// Create or replace an approval rule (updates replace the whole rule)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MCPToolApprovalRuleRequest {
+    pub name: String,
+    pub description: Option<String>,
+    pub server_id: Option<Uuid>,
+    /// Defaults to `*` (every tool)
+    pub tool_name_pattern: Option<String>,
+    pub group_id: Option<Uuid>,
+    #[serde(default)]
+    pub conditions: Vec<ApprovalRuleCondition>,
+    pub action: ApprovalRuleAction,
+    pub priority: Option<i32>,
+    pub enabled: Option<bool>,
+}
+
+/// Tool call to evaluate the approval rules against, without running it
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ApprovalRuleDryRunRequest {
+    pub server_id: Uuid,
+    pub tool_name: String,
+    pub arguments: Option<Value>,
+    /// User whose groups are matched; defaults to the caller
+    pub user_id: Option<Uuid>,
+}
+
+/// How one rule was judged during an evaluation
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ApprovalRuleTrace {
+    pub rule_id: Uuid,
+    pub rule_name: String,
+    pub matched: bool,
+    pub reason: String,
+}
+
+/// Result of evaluating the approval rules for a tool call. `trace` lists the rules in
+/// evaluation order up to (and including) the one that matched.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ApprovalRuleEvaluation {
+    /// Decision of the matching rule; no rule matched when not set
+    pub action: Option<ApprovalRuleAction>,
+    pub matched_rule: Option<MCPToolApprovalRule>,
+    pub trace: Vec<ApprovalRuleTrace>,
+}
+
+impl ApprovalRuleCondition {
+    /// Check the path parses and the value suits the operator
+    pub fn validate(&self) -> Result<(), String> {
+        parse_json_path(&self.path)?;
+
+        match self.operator {
+            ApprovalRuleOperator::Exists | ApprovalRuleOperator::NotExists => Ok(()),
+            ApprovalRuleOperator::Equals
+            | ApprovalRuleOperator::NotEquals
+            | ApprovalRuleOperator::Contains => match self.value {
+                Some(_) => Ok(()),
+                None => Err(format!("Condition on '{}' needs a value", self.path)),
+            },
+            ApprovalRuleOperator::StartsWith
+            | ApprovalRuleOperator::EndsWith
+            | ApprovalRuleOperator::Matches
+            | ApprovalRuleOperator::PathUnder => match self.value {
+                Some(Value::String(_)) => Ok(()),
+                _ => Err(format!("Condition on '{}' needs a string value", self.path)),
+            },
+        }
+    }
+
+    /// Whether the condition holds for the given call arguments
+    pub fn holds(&self, arguments: &Value) -> bool {
+        let Ok(segments) = parse_json_path(&self.path) else {
+            return false;
+        };
+        let actual = resolve_json_path(arguments, &segments).filter(|v| !v.is_null());
+        let expected = self.value.as_ref();
+
+        match self.operator {
+            ApprovalRuleOperator::Exists => actual.is_some(),
+            ApprovalRuleOperator::NotExists => actual.is_none(),
+            ApprovalRuleOperator::Equals => actual.is_some() && actual == expected,
+            ApprovalRuleOperator::NotEquals => actual != expected,
+            ApprovalRuleOperator::Contains => match (actual, expected) {
+                (Some(Value::String(actual)), Some(Value::String(expected))) => {
+                    actual.contains(expected.as_str())
+                }
+                (Some(Value::Array(items)), Some(expected)) => items.contains(expected),
+                _ => false,
+            },
+            ApprovalRuleOperator::StartsWith => {
+                compare_strings(actual, expected, |a, e| a.starts_with(e))
+            }
+            ApprovalRuleOperator::EndsWith => {
+                compare_strings(actual, expected, |a, e| a.ends_with(e))
+            }
+            ApprovalRuleOperator::Matches => compare_strings(actual, expected, glob_match),
+            ApprovalRuleOperator::PathUnder => compare_strings(actual, expected, path_under),
+        }
+    }
+
+    fn describe(&self) -> String {
+        match &self.value {
+            Some(value) => format!("{} {:?} {}", self.path, self.operator, value),
+            None => format!("{} {:?}", self.path, self.operator),
+        }
+    }
+}
+
+impl MCPToolApprovalRule {
+    /// Why the rule does not apply to a call, or None when it matches. Rules with argument
+    /// conditions never match when the arguments are not known.
+    pub fn mismatch_reason(
+        &self,
+        server_id: Uuid,
+        tool_name: &str,
+        group_ids: &[Uuid],
+        arguments: Option<&Value>,
+    ) -> Option<String> {
+        if let Some(rule_server_id) = self.server_id {
+            if rule_server_id != server_id {
+                return Some("Applies to another server".to_string());
+            }
+        }
+
+        if !glob_match(tool_name, &self.tool_name_pattern) {
+            return Some(format!(
+                "Tool name does not match '{}'",
+                self.tool_name_pattern
+            ));
+        }
+
+        if let Some(group_id) = self.group_id {
+            if !group_ids.contains(&group_id) {
+                return Some("User is not in the rule's group".to_string());
+            }
+        }
+
+        if self.conditions.is_empty() {
+            return None;
+        }
+
+        let Some(arguments) = arguments else {
+            return Some("Arguments are not known".to_string());
+        };
+
+        self.conditions
+            .iter()
+            .find(|condition| !condition.holds(arguments))
+            .map(|condition| format!("Condition failed: {}", condition.describe()))
+    }
+}
+
+/// Evaluate rules, already in evaluation order, against a call. The first matching enabled
+/// rule decides.
+pub fn evaluate_approval_rules(
+    rules: Vec<MCPToolApprovalRule>,
+    server_id: Uuid,
+    tool_name: &str,
+    group_ids: &[Uuid],
+    arguments: Option<&Value>,
+) -> ApprovalRuleEvaluation {
+    let mut trace = Vec::new();
+
+    for rule in rules.into_iter().filter(|rule| rule.enabled) {
+        match rule.mismatch_reason(server_id, tool_name, group_ids, arguments) {
+            Some(reason) => trace.push(ApprovalRuleTrace {
+                rule_id: rule.id,
+                rule_name: rule.name.clone(),
+                matched: false,
+                reason,
+            }),
+            None => {
+                trace.push(ApprovalRuleTrace {
+                    rule_id: rule.id,
+                    rule_name: rule.name.clone(),
+                    matched: true,
+                    reason: format!("Matched; the call is {}d", rule.action.as_str()),
+                });
+                return ApprovalRuleEvaluation {
+                    action: Some(rule.action),
+                    matched_rule: Some(rule),
+                    trace,
+                };
+            }
+        }
+    }
+
+    ApprovalRuleEvaluation {
+        action: None,
+        matched_rule: None,
+        trace,
+    }
+}
+
+enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+/// Parse `$.a.b[0]`-style paths; the leading `$` is optional
+fn parse_json_path(path: &str) -> Result<Vec<PathSegment>, String> {
+    let path = path.trim();
+    let rest = path.strip_prefix('$').unwrap_or(path);
+    let invalid = || format!("Invalid JSON path '{}'", path);
+
+    let mut segments = Vec::new();
+    let mut chars = rest.chars().peekable();
+    // A path without `$` starts with a key, e.g. `options.recursive`
+    let mut expect_key = !rest.is_empty() && !rest.starts_with(['.', '[']);
+
+    loop {
+        if expect_key {
+            let mut key = String::new();
+            while let Some(&c) = chars.peek() {
+                if c == '.' || c == '[' {
+                    break;
+                }
+                key.push(c);
+                chars.next();
+            }
+            if key.is_empty() {
+                return Err(invalid());
+            }
+            segments.push(PathSegment::Key(key));
+            expect_key = false;
+            continue;
+        }
+
+        match chars.next() {
+            None => break,
+            Some('.') => expect_key = true,
+            Some('[') => {
+                let mut inner = String::new();
+                loop {
+                    match chars.next() {
+                        Some(']') => break,
+                        Some(c) => inner.push(c),
+                        None => return Err(invalid()),
+                    }
+                }
+                let inner = inner.trim();
+                let quoted = inner
+                    .strip_prefix('\'')
+                    .and_then(|s| s.strip_suffix('\''))
+                    .or_else(|| inner.strip_prefix('"').and_then(|s| s.strip_suffix('"')));
+                match quoted {
+                    Some(key) => segments.push(PathSegment::Key(key.to_string())),
+                    None => segments.push(PathSegment::Index(
+                        inner.parse().map_err(|_| invalid())?,
+                    )),
+                }
+            }
+            Some(_) => return Err(invalid()),
+        }
+    }
+
+    Ok(segments)
+}
+
+fn resolve_json_path<'a>(value: &'a Value, segments: &[PathSegment]) -> Option<&'a Value> {
+    segments.iter().try_fold(value, |current, segment| match segment {
+        PathSegment::Key(key) => current.get(key),
+        PathSegment::Index(index) => current.get(index),
+    })
+}
+
+fn compare_strings(
+    actual: Option<&Value>,
+    expected: Option<&Value>,
+    compare: impl Fn(&str, &str) -> bool,
+) -> bool {
+    match (actual, expected) {
+        (Some(Value::String(actual)), Some(Value::String(expected))) => compare(actual, expected),
+        _ => false,
+    }
+}
+
+/// Match text against a glob pattern where `*` is any run of characters and `?` one character
+fn glob_match(text: &str, pattern: &str) -> bool {
+    let text: Vec<char> = text.chars().collect();
+    let pattern: Vec<char> = pattern.chars().collect();
+    let (mut t, mut p) = (0, 0);
+    // Position after the last `*` and the text position it is currently matched up to
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            t += 1;
+            p += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            p += 1;
+            backtrack = Some((p, t));
+        } else if let Some((star_p, star_t)) = backtrack {
+            p = star_p;
+            t = star_t + 1;
+            backtrack = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Whether `path` lies inside `directory`, comparing components after resolving `.` and `..`
+/// so `/data/../etc` is not inside `/data`
+fn path_under(path: &str, directory: &str) -> bool {
+    match (normalize_path(path), normalize_path(directory)) {
+        (Some((path_absolute, path)), Some((dir_absolute, dir))) => {
+            path_absolute == dir_absolute && path.starts_with(&dir)
+        }
+        _ => false,
+    }
+}
+
+/// Split a path into components with `.` and `..` resolved; None when it climbs above its start
+fn normalize_path(path: &str) -> Option<(bool, Vec<&str>)> {
+    let absolute = path.starts_with(['/', '\\']);
+    let mut components = Vec::new();
+    for component in path.split(['/', '\\']) {
+        match component {
+            "" | "." => {}
+            ".." => {
+                components.pop()?;
+            }
+            component => components.push(component),
+        }
+    }
+    Some((absolute, components))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn rule(tool_name_pattern: &str, conditions: Vec<ApprovalRuleCondition>) -> MCPToolApprovalRule {
+        MCPToolApprovalRule {
+            id: Uuid::new_v4(),
+            name: tool_name_pattern.to_string(),
+            description: None,
+            server_id: None,
+            tool_name_pattern: tool_name_pattern.to_string(),
+            group_id: None,
+            conditions,
+            action: ApprovalRuleAction::Approve,
+            priority: 0,
+            enabled: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn condition(path: &str, operator: ApprovalRuleOperator, value: Value) -> ApprovalRuleCondition {
+        ApprovalRuleCondition {
+            path: path.to_string(),
+            operator,
+            value: Some(value),
+        }
+    }
+
+    #[test]
+    fn glob_matches_tool_names() {
+        assert!(glob_match("delete_file", "delete_*"));
+        assert!(glob_match("read_file", "read_?ile"));
+        assert!(glob_match("anything", "*"));
+        assert!(!glob_match("undelete_file", "delete_*"));
+        assert!(!glob_match("read_files", "read_file"));
+    }
+
+    #[test]
+    fn path_under_resolves_parent_components() {
+        assert!(path_under("/data/project/a.txt", "/data/project"));
+        assert!(path_under("/data/project/./sub/../a.txt", "/data/project/"));
+        assert!(!path_under("/data/project/../secrets", "/data/project"));
+        assert!(!path_under("/data/projects/a.txt", "/data/project"));
+        assert!(!path_under("../data/project/a.txt", "/data/project"));
+    }
+
+    #[test]
+    fn conditions_follow_json_paths() {
+        let arguments = json!({ "path": "/data/a.txt", "options": { "recursive": true }, "files": ["x"] });
+
+        assert!(condition("$.path", ApprovalRuleOperator::PathUnder, json!("/data")).holds(&arguments));
+        assert!(condition("options.recursive", ApprovalRuleOperator::Equals, json!(true)).holds(&arguments));
+        assert!(condition("$.files[0]", ApprovalRuleOperator::Equals, json!("x")).holds(&arguments));
+        assert!(condition("$['files']", ApprovalRuleOperator::Contains, json!("x")).holds(&arguments));
+        assert!(!condition("$.missing", ApprovalRuleOperator::Equals, json!(null)).holds(&arguments));
+        assert!(parse_json_path("$..path").is_err());
+    }
+
+    #[test]
+    fn first_matching_rule_decides() {
+        let mut deny = rule("delete_*", Vec::new());
+        deny.action = ApprovalRuleAction::Deny;
+        let read = rule(
+            "read_file",
+            vec![condition("$.path", ApprovalRuleOperator::PathUnder, json!("/data"))],
+        );
+        let rules = vec![deny, read];
+        let server_id = Uuid::new_v4();
+
+        let evaluation = evaluate_approval_rules(
+            rules.clone(),
+            server_id,
+            "read_file",
+            &[],
+            Some(&json!({ "path": "/data/a.txt" })),
+        );
+        assert_eq!(evaluation.action, Some(ApprovalRuleAction::Approve));
+        assert_eq!(evaluation.trace.len(), 2);
+
+        let evaluation = evaluate_approval_rules(rules.clone(), server_id, "read_file", &[], None);
+        assert_eq!(evaluation.action, None);
+
+        let evaluation = evaluate_approval_rules(rules, server_id, "delete_file", &[], None);
+        assert_eq!(evaluation.action, Some(ApprovalRuleAction::Deny));
+    }
+}
@@ -4,9 +4,15 @@ pub mod chat;
 pub mod config;
 pub mod download_instance;
 pub mod file;
+pub mod mcp_oauth;
+pub mod mcp_prompt;
+pub mod mcp_resource;
 pub mod mcp_server;
+pub mod mcp_server_config;
 pub mod mcp_tool;
+pub mod mcp_tool_approval_rule;
 pub mod model;
+pub mod model_group;
 pub mod project;
 pub mod provider;
 pub mod proxy;
@@ -14,6 +20,7 @@ pub mod rag_instance;
 pub mod rag_provider;
 pub mod rag_repository;
 pub mod repository;
+pub mod token_usage;
 pub mod user;
 pub mod user_group_mcp_server;
 
@@ -23,9 +30,15 @@ pub use chat::*;
 pub use config::*;
 pub use download_instance::*;
 pub use file::*;
+pub use mcp_oauth::*;
+pub use mcp_prompt::*;
+pub use mcp_resource::*;
 pub use mcp_server::*;
+pub use mcp_server_config::*;
 pub use mcp_tool::*;
+pub use mcp_tool_approval_rule::*;
 pub use model::*;
+pub use model_group::*;
 pub use project::*;
 pub use provider::*;
 pub use proxy::*;
@@ -33,5 +46,6 @@ pub use rag_instance::*;
 pub use rag_provider::*;
 pub use rag_repository::*;
 pub use repository::*;
+pub use token_usage::*;
 pub use user::*;
 pub use user_group_mcp_server::*;
@@ -79,6 +79,8 @@ pub enum MistralRsCommand {
     Lora,
     /// TOML configuration-based models
     Toml,
+    /// Diffusion models generating images from text (FLUX)
+    Diffusion,
 }
 
 impl MistralRsCommand {
@@ -91,6 +93,7 @@ impl MistralRsCommand {
             MistralRsCommand::XLora => "x-lora",
             MistralRsCommand::Lora => "lora",
             MistralRsCommand::Toml => "toml",
+            MistralRsCommand::Diffusion => "diffusion",
         }
     }
 
@@ -103,6 +106,7 @@ impl MistralRsCommand {
             "x-lora" => Some(MistralRsCommand::XLora),
             "lora" => Some(MistralRsCommand::Lora),
             "toml" => Some(MistralRsCommand::Toml),
+            "diffusion" => Some(MistralRsCommand::Diffusion),
             _ => None,
         }
     }
@@ -168,6 +172,8 @@ pub struct ModelCapabilities {
     pub text_embedding: Option<bool>,
     /// Image generation capability - can generate images from text descriptions
     pub image_generator: Option<bool>,
+    /// Rerank capability - can score documents against a query (cross-encoder)
+    pub rerank: Option<bool>,
 }
 
 impl_json_option_from!(ModelCapabilities);
@@ -211,6 +217,14 @@ pub struct ModelParameters {
     pub seed: Option<i32>,
     /// Stop sequences to terminate generation
     pub stop: Option<Vec<String>>,
+
+    // Reasoning
+    /// Token budget for extended thinking on providers that take one; unset leaves it off
+    pub thinking_budget: Option<i32>,
+
+    // Output format
+    /// JSON Schema the reply must match; unset leaves the reply free-form
+    pub json_schema: Option<serde_json::Value>,
 }
 impl_json_option_from!(ModelParameters);
 
@@ -234,6 +248,8 @@ impl ModelParameters {
             frequency_penalty: Some(0.0),
             seed: None,
             stop: None,
+            thinking_budget: None,
+            json_schema: None,
         }
     }
 
@@ -251,6 +267,30 @@ impl ModelParameters {
             frequency_penalty: Some(0.1),
             seed: None,
             stop: None,
+            thinking_budget: None,
+            json_schema: None,
+        }
+    }
+
+    /// These parameters with each field set in `overrides` replaced by the override
+    pub fn with_overrides(&self, overrides: &ModelParameters) -> Self {
+        Self {
+            max_tokens: overrides.max_tokens.or(self.max_tokens),
+            temperature: overrides.temperature.or(self.temperature),
+            top_k: overrides.top_k.or(self.top_k),
+            top_p: overrides.top_p.or(self.top_p),
+            min_p: overrides.min_p.or(self.min_p),
+            repeat_last_n: overrides.repeat_last_n.or(self.repeat_last_n),
+            repeat_penalty: overrides.repeat_penalty.or(self.repeat_penalty),
+            presence_penalty: overrides.presence_penalty.or(self.presence_penalty),
+            frequency_penalty: overrides.frequency_penalty.or(self.frequency_penalty),
+            seed: overrides.seed.or(self.seed),
+            stop: overrides.stop.clone().or_else(|| self.stop.clone()),
+            thinking_budget: overrides.thinking_budget.or(self.thinking_budget),
+            json_schema: overrides
+                .json_schema
+                .clone()
+                .or_else(|| self.json_schema.clone()),
         }
     }
 
@@ -292,6 +332,21 @@ impl ModelParameters {
             }
         }
 
+        if let Some(thinking_budget) = self.thinking_budget {
+            if thinking_budget < 1 {
+                return Err("thinking_budget must be greater than 0".to_string());
+            }
+        }
+
+        if let Some(json_schema) = &self.json_schema {
+            if !json_schema.is_object() {
+                return Err("json_schema must be a JSON Schema object".to_string());
+            }
+            if let Err(e) = jsonschema::validator_for(json_schema) {
+                return Err(format!("json_schema is not a valid JSON Schema: {}", e));
+            }
+        }
+
         if let Some(stop) = &self.stop {
             if stop.len() > 4 {
                 return Err("stop sequences cannot exceed 4 items".to_string());
@@ -320,7 +375,7 @@ pub struct MistralRsSettings {
     pub model_id_name: Option<String>,
     /// Path to tokenizer.json file
     pub tokenizer_json: Option<String>,
-    /// Model architecture (for plain models)
+    /// Model architecture (for plain and diffusion models)
     pub arch: Option<String>,
 
     // Quantization and weights
@@ -0,0 +1,36 @@
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A named logical model served by an ordered list of concrete models. Requests go to the first
+/// member and fall through to the next one on rate limits, server errors and timeouts.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ModelGroup {
+    pub id: Uuid,
+    /// Name used to address the group, e.g. in the `model` field of proxy requests
+    pub name: String,
+    pub description: Option<String>,
+    pub enabled: bool,
+    /// Member models in failover order
+    pub model_ids: Vec<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CreateModelGroupRequest {
+    pub name: String,
+    pub description: Option<String>,
+    pub enabled: Option<bool>,
+    pub model_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct UpdateModelGroupRequest {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub enabled: Option<bool>,
+    /// Replaces the member list, in failover order
+    pub model_ids: Option<Vec<Uuid>>,
+}
@@ -18,6 +18,7 @@ pub enum ProviderType {
     Mistral,
     DeepSeek,
     Huggingface,
+    Ollama,
     Custom,
 }
 
@@ -32,6 +33,7 @@ impl ProviderType {
             "mistral" => Some(ProviderType::Mistral),
             "deepseek" => Some(ProviderType::DeepSeek),
             "huggingface" => Some(ProviderType::Huggingface),
+            "ollama" => Some(ProviderType::Ollama),
             "custom" => Some(ProviderType::Custom),
             _ => Some(ProviderType::Custom), // fallback to custom for unknown types
         }
@@ -47,6 +49,7 @@ impl ProviderType {
             ProviderType::Mistral => "mistral",
             ProviderType::DeepSeek => "deepseek",
             ProviderType::Huggingface => "huggingface",
+            ProviderType::Ollama => "ollama",
             ProviderType::Custom => "custom",
         }
     }
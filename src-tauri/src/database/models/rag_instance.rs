@@ -267,6 +267,61 @@ impl RAGProcessingStatus {
 // Implement string to enum conversion for RAGProcessingStatus
 impl_string_to_enum!(RAGProcessingStatus);
 
+/// Background job re-embedding an instance's chunks after its embedding model changed
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RAGEmbeddingMigration {
+    pub id: Uuid,
+    pub rag_instance_id: Uuid,
+    /// Model the current index was built with
+    pub source_model_id: Option<Uuid>,
+    /// Model the instance switches to once the migration completes
+    pub target_model_id: Uuid,
+    pub status: RAGEmbeddingMigrationStatus,
+    pub total_documents: i64,
+    pub processed_documents: i64,
+    pub error_message: Option<String>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+pub enum RAGEmbeddingMigrationStatus {
+    Pending,
+    Running,
+    Completed,
+    Cancelled,
+    Failed,
+}
+
+impl RAGEmbeddingMigrationStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RAGEmbeddingMigrationStatus::Pending => "pending",
+            RAGEmbeddingMigrationStatus::Running => "running",
+            RAGEmbeddingMigrationStatus::Completed => "completed",
+            RAGEmbeddingMigrationStatus::Cancelled => "cancelled",
+            RAGEmbeddingMigrationStatus::Failed => "failed",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "pending" => Some(RAGEmbeddingMigrationStatus::Pending),
+            "running" => Some(RAGEmbeddingMigrationStatus::Running),
+            "completed" => Some(RAGEmbeddingMigrationStatus::Completed),
+            "cancelled" => Some(RAGEmbeddingMigrationStatus::Cancelled),
+            "failed" => Some(RAGEmbeddingMigrationStatus::Failed),
+            _ => None,
+        }
+    }
+}
+
+// Implement string to enum conversion for RAGEmbeddingMigrationStatus
+impl_string_to_enum!(RAGEmbeddingMigrationStatus);
+
 
 
 
@@ -278,6 +333,13 @@ pub struct RAGInstanceFilesQuery {
     pub search: Option<String>,
 }
 
+/// Replace the user-defined tags of a file in a RAG instance
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct UpdateRAGInstanceFileTagsRequest {
+    /// Tags stored in the file's `rag_metadata.tags`, usable in query filters
+    pub tags: Vec<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct RAGInstanceFilesListResponse {
     pub files: Vec<RAGInstanceFile>,
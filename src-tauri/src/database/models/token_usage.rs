@@ -0,0 +1,351 @@
+use chrono::{DateTime, Datelike, Duration, TimeZone, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sqlx::Row;
+use uuid::Uuid;
+
+use super::chat::Usage;
+
+/// Where a model call came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenUsageSource {
+    Chat,
+    ApiProxy,
+}
+
+impl TokenUsageSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TokenUsageSource::Chat => "chat",
+            TokenUsageSource::ApiProxy => "api_proxy",
+        }
+    }
+}
+
+/// Token usage of one model call, to be recorded
+#[derive(Debug, Clone)]
+pub struct NewTokenUsage {
+    pub source: TokenUsageSource,
+    pub user_id: Option<Uuid>,
+    pub message_id: Option<Uuid>,
+    pub model_id: Option<Uuid>,
+    pub provider_id: Option<Uuid>,
+    /// Counts reported by the provider; the call is still counted as a request without them
+    pub usage: Option<Usage>,
+}
+
+/// What a quota limits: one user, the combined usage of a group's members, or all requests
+/// to the API proxy server
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum UsageQuotaScope {
+    User,
+    Group,
+    ApiProxy,
+}
+
+impl UsageQuotaScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UsageQuotaScope::User => "user",
+            UsageQuotaScope::Group => "group",
+            UsageQuotaScope::ApiProxy => "api_proxy",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "user" => Some(UsageQuotaScope::User),
+            "group" => Some(UsageQuotaScope::Group),
+            "api_proxy" => Some(UsageQuotaScope::ApiProxy),
+            _ => None,
+        }
+    }
+}
+
+/// Calendar period a quota applies to, in UTC
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum UsagePeriod {
+    Daily,
+    Monthly,
+}
+
+impl UsagePeriod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UsagePeriod::Daily => "daily",
+            UsagePeriod::Monthly => "monthly",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "daily" => Some(UsagePeriod::Daily),
+            "monthly" => Some(UsagePeriod::Monthly),
+            _ => None,
+        }
+    }
+
+    /// Start of the period containing `now`
+    pub fn start(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        let day = now.date_naive();
+        let start = match self {
+            UsagePeriod::Daily => day,
+            UsagePeriod::Monthly => day.with_day(1).unwrap_or(day),
+        };
+        Utc.from_utc_datetime(&start.and_hms_opt(0, 0, 0).unwrap_or_default())
+    }
+
+    /// Start of the period following the one containing `now`
+    pub fn next_start(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        let start = self.start(now);
+        match self {
+            UsagePeriod::Daily => start + Duration::days(1),
+            UsagePeriod::Monthly => {
+                let (year, month) = if start.month() == 12 {
+                    (start.year() + 1, 1)
+                } else {
+                    (start.year(), start.month() + 1)
+                };
+                Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0)
+                    .single()
+                    .unwrap_or(start + Duration::days(31))
+            }
+        }
+    }
+}
+
+/// Limit on the tokens and/or requests of a user, a group or the API proxy over a period
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct UsageQuota {
+    pub id: Uuid,
+    pub scope: UsageQuotaScope,
+    /// User limited by a `user` quota
+    pub user_id: Option<Uuid>,
+    /// Group whose members share a `group` quota
+    pub group_id: Option<Uuid>,
+    pub period: UsagePeriod,
+    pub max_tokens: Option<i64>,
+    pub max_requests: Option<i32>,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl sqlx::FromRow<'_, sqlx::postgres::PgRow> for UsageQuota {
+    fn from_row(row: &sqlx::postgres::PgRow) -> Result<Self, sqlx::Error> {
+        let scope: String = row.try_get("scope")?;
+        let period: String = row.try_get("period")?;
+
+        Ok(Self {
+            id: row.try_get("id")?,
+            scope: UsageQuotaScope::from_str(&scope).ok_or_else(|| {
+                sqlx::Error::Decode(format!("Invalid usage quota scope '{}'", scope).into())
+            })?,
+            user_id: row.try_get("user_id")?,
+            group_id: row.try_get("group_id")?,
+            period: UsagePeriod::from_str(&period).ok_or_else(|| {
+                sqlx::Error::Decode(format!("Invalid usage quota period '{}'", period).into())
+            })?,
+            max_tokens: row.try_get("max_tokens")?,
+            max_requests: row.try_get("max_requests")?,
+            enabled: row.try_get("enabled")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+}
+
+impl UsageQuota {
+    /// Whether the usage already reaches one of the quota's limits, so no further request fits
+    pub fn is_reached_by(&self, usage: &UsageTotals) -> bool {
+        self.max_tokens.map_or(false, |max| usage.total_tokens >= max)
+            || self
+                .max_requests
+                .map_or(false, |max| usage.requests >= i64::from(max))
+    }
+}
+
+/// Create or replace a usage quota (updates replace the whole quota)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct UsageQuotaRequest {
+    pub scope: UsageQuotaScope,
+    pub user_id: Option<Uuid>,
+    pub group_id: Option<Uuid>,
+    pub period: UsagePeriod,
+    pub max_tokens: Option<i64>,
+    pub max_requests: Option<i32>,
+    pub enabled: Option<bool>,
+}
+
+impl UsageQuotaRequest {
+    /// Check the quota limits something and targets what its scope needs
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_tokens.is_none() && self.max_requests.is_none() {
+            return Err("A quota needs max_tokens, max_requests or both".to_string());
+        }
+        if self.max_tokens.map_or(false, |max| max < 0)
+            || self.max_requests.map_or(false, |max| max < 0)
+        {
+            return Err("Quota limits cannot be negative".to_string());
+        }
+
+        match (self.scope, self.user_id, self.group_id) {
+            (UsageQuotaScope::User, Some(_), None)
+            | (UsageQuotaScope::Group, None, Some(_))
+            | (UsageQuotaScope::ApiProxy, None, None) => Ok(()),
+            (UsageQuotaScope::User, _, _) => {
+                Err("A user quota needs a user_id and no group_id".to_string())
+            }
+            (UsageQuotaScope::Group, _, _) => {
+                Err("A group quota needs a group_id and no user_id".to_string())
+            }
+            (UsageQuotaScope::ApiProxy, _, _) => {
+                Err("An api_proxy quota takes neither user_id nor group_id".to_string())
+            }
+        }
+    }
+}
+
+/// Requests and tokens counted over a period
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct UsageTotals {
+    pub requests: i64,
+    pub prompt_tokens: i64,
+    pub completion_tokens: i64,
+    pub total_tokens: i64,
+}
+
+/// A quota with the usage counted against it in the current period
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct UsageQuotaStatus {
+    pub quota: UsageQuota,
+    pub usage: UsageTotals,
+    pub period_start: DateTime<Utc>,
+    pub resets_at: DateTime<Utc>,
+    /// Whether a limit is reached, blocking further requests until `resets_at`
+    pub reached: bool,
+}
+
+impl UsageQuotaStatus {
+    /// Message shown to the user when the quota blocks a request
+    pub fn reached_message(&self) -> String {
+        let subject = match self.quota.scope {
+            UsageQuotaScope::User => "Your",
+            UsageQuotaScope::Group => "Your group's",
+            UsageQuotaScope::ApiProxy => "The API proxy's",
+        };
+        let limit = match (self.quota.max_tokens, self.quota.max_requests) {
+            (Some(max), _) if self.usage.total_tokens >= max => {
+                format!("token quota of {} tokens", max)
+            }
+            (_, Some(max)) => format!("request quota of {} requests", max),
+            (Some(max), None) => format!("token quota of {} tokens", max),
+            (None, None) => "quota".to_string(),
+        };
+
+        format!(
+            "{} {} {} has been reached. It resets at {}.",
+            subject,
+            self.quota.period.as_str(),
+            limit,
+            self.resets_at.format("%Y-%m-%d %H:%M UTC")
+        )
+    }
+}
+
+/// Usage of the current user today and this month, with the quotas that apply to them
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct UserUsageResponse {
+    pub today: UsageTotals,
+    pub this_month: UsageTotals,
+    pub quotas: Vec<UsageQuotaStatus>,
+}
+
+/// Usage of one user over a period
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct UserUsageSummary {
+    pub user_id: Uuid,
+    pub username: String,
+    pub usage: UsageTotals,
+}
+
+/// Combined usage of a group's members over a period
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GroupUsageSummary {
+    pub group_id: Uuid,
+    pub name: String,
+    pub usage: UsageTotals,
+}
+
+/// Usage of every user and group over a period, and of the API proxy server
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct UsageReport {
+    pub period: UsagePeriod,
+    pub period_start: DateTime<Utc>,
+    pub users: Vec<UserUsageSummary>,
+    pub groups: Vec<GroupUsageSummary>,
+    pub api_proxy: UsageTotals,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct UsageReportQuery {
+    /// Defaults to `monthly`
+    pub period: Option<UsagePeriod>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn periods_follow_the_utc_calendar() {
+        let now = Utc.with_ymd_and_hms(2026, 12, 17, 15, 30, 0).unwrap();
+
+        assert_eq!(
+            UsagePeriod::Daily.start(now),
+            Utc.with_ymd_and_hms(2026, 12, 17, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            UsagePeriod::Monthly.start(now),
+            Utc.with_ymd_and_hms(2026, 12, 1, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            UsagePeriod::Monthly.next_start(now),
+            Utc.with_ymd_and_hms(2027, 1, 1, 0, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn quota_is_reached_by_either_limit() {
+        let now = Utc::now();
+        let quota = UsageQuota {
+            id: Uuid::new_v4(),
+            scope: UsageQuotaScope::User,
+            user_id: Some(Uuid::new_v4()),
+            group_id: None,
+            period: UsagePeriod::Daily,
+            max_tokens: Some(1000),
+            max_requests: Some(10),
+            enabled: true,
+            created_at: now,
+            updated_at: now,
+        };
+
+        let mut usage = UsageTotals {
+            requests: 9,
+            total_tokens: 999,
+            ..Default::default()
+        };
+        assert!(!quota.is_reached_by(&usage));
+
+        usage.total_tokens = 1000;
+        assert!(quota.is_reached_by(&usage));
+
+        usage.total_tokens = 0;
+        usage.requests = 10;
+        assert!(quota.is_reached_by(&usage));
+    }
+}
@@ -1290,34 +1290,6 @@ pub async fn switch_conversation_branch(
 // MCP Tool Approval Query Functions
 // ============================================
 
-/// Check if a tool is approved for this conversation
-pub async fn check_tool_approval(
-    conversation_id: Uuid,
-    server_id: Uuid,
-    tool_name: &str,
-) -> Result<bool, Error> {
-    let pool = get_database_pool().map_err(|e| Error::Configuration(e.into()))?;
-
-    let approval = sqlx::query!(
-        r#"
-        SELECT approved, expires_at
-        FROM mcp_tool_approvals
-        WHERE conversation_id = $1
-          AND server_id = $2
-          AND tool_name = $3
-          AND approved = true
-          AND (expires_at IS NULL OR expires_at > NOW())
-        "#,
-        conversation_id,
-        server_id,
-        tool_name
-    )
-    .fetch_optional(pool.as_ref())
-    .await?;
-
-    Ok(approval.is_some())
-}
-
 /// Save tool result content to message_contents table
 pub async fn save_tool_result_content(
     message_id: Uuid,
@@ -1487,6 +1459,98 @@ pub async fn append_text_content_to_message(
     Ok(())
 }
 
+/// Append the reasoning of a model to an existing message
+pub async fn append_thinking_content_to_message(
+    message_id: Uuid,
+    thinking: String,
+    signature: Option<String>,
+) -> Result<(), Error> {
+    let pool = get_database_pool().map_err(|e| Error::Configuration(e.into()))?;
+
+    // Get current max sequence order for this message
+    let max_seq = sqlx::query!(
+        "SELECT COALESCE(MAX(sequence_order), -1) as max_seq FROM message_contents WHERE message_id = $1",
+        message_id
+    )
+    .fetch_one(pool.as_ref())
+    .await?
+    .max_seq
+    .unwrap_or(-1);
+
+    let content_id = Uuid::new_v4();
+    let sequence = (max_seq + 1) as i32;
+    let content_type = MessageContentType::Thinking.as_str();
+    let content_data = MessageContentData::Thinking { thinking, signature };
+    let content_json = serde_json::to_value(&content_data)
+        .map_err(|e| Error::Decode(Box::new(e)))?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO message_contents (id, message_id, content_type, content, sequence_order)
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+        content_id,
+        message_id,
+        content_type,
+        content_json,
+        sequence
+    )
+    .execute(pool.as_ref())
+    .await?;
+
+    Ok(())
+}
+
+/// Append a generated image, already stored as a file of the message, to an existing message.
+/// Returns the id of the new content.
+pub async fn append_generated_image_content_to_message(
+    message_id: Uuid,
+    file_id: Uuid,
+    filename: String,
+    mime_type: String,
+    revised_prompt: Option<String>,
+) -> Result<Uuid, Error> {
+    let pool = get_database_pool().map_err(|e| Error::Configuration(e.into()))?;
+
+    // Get current max sequence order for this message
+    let max_seq = sqlx::query!(
+        "SELECT COALESCE(MAX(sequence_order), -1) as max_seq FROM message_contents WHERE message_id = $1",
+        message_id
+    )
+    .fetch_one(pool.as_ref())
+    .await?
+    .max_seq
+    .unwrap_or(-1);
+
+    let content_id = Uuid::new_v4();
+    let sequence = (max_seq + 1) as i32;
+    let content_type = MessageContentType::GeneratedImage.as_str();
+    let content_data = MessageContentData::GeneratedImage {
+        file_id,
+        filename,
+        mime_type,
+        revised_prompt,
+    };
+    let content_json = serde_json::to_value(&content_data)
+        .map_err(|e| Error::Decode(Box::new(e)))?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO message_contents (id, message_id, content_type, content, sequence_order)
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+        content_id,
+        message_id,
+        content_type,
+        content_json,
+        sequence
+    )
+    .execute(pool.as_ref())
+    .await?;
+
+    Ok(content_id)
+}
+
 /// Cancel all pending tool approvals for a conversation
 /// Sets is_approved to false for all ToolCallPendingApproval contents where is_approved is NULL
 /// Returns the message_content_ids that were updated
@@ -130,6 +130,24 @@ pub async fn get_files_by_project(
     Ok((files, total))
 }
 
+/// All files of a project, oldest first
+pub async fn get_all_files_by_project(project_id: Uuid) -> Result<Vec<File>, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    sqlx::query_as!(
+        File,
+        r#"
+        SELECT * FROM files
+        WHERE project_id = $1
+        ORDER BY created_at ASC
+        "#,
+        project_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
 pub async fn get_files_by_message(
     message_id: Uuid,
     user_id: Uuid,
@@ -154,6 +172,35 @@ pub async fn get_files_by_message(
     Ok(files)
 }
 
+/// Update size, type and checksum after a file's content was replaced in place
+pub async fn update_file_content(
+    file_id: Uuid,
+    file_size: i64,
+    mime_type: Option<String>,
+    checksum: Option<String>,
+) -> Result<File, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    let file = sqlx::query_as!(
+        File,
+        r#"
+        UPDATE files
+        SET file_size = $2, mime_type = $3, checksum = $4, updated_at = NOW()
+        WHERE id = $1
+        RETURNING *
+        "#,
+        file_id,
+        file_size,
+        mime_type,
+        checksum
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(file)
+}
+
 pub async fn delete_file(file_id: Uuid, user_id: Uuid) -> Result<bool, sqlx::Error> {
     let pool = get_database_pool()?;
     let pool = pool.as_ref();
@@ -178,6 +225,27 @@ pub async fn delete_file(file_id: Uuid, user_id: Uuid) -> Result<bool, sqlx::Err
 
 // Message-file relationship functions
 
+pub async fn create_message_file_relationship(
+    message_id: Uuid,
+    file_id: Uuid,
+) -> Result<(), sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    sqlx::query!(
+        r#"
+        INSERT INTO messages_files (message_id, file_id, created_at)
+        VALUES ($1, $2, NOW())
+        "#,
+        message_id,
+        file_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
 pub async fn delete_message_file_relationship(
     message_id: Uuid,
     file_id: Uuid,
@@ -0,0 +1,140 @@
+use uuid::Uuid;
+
+use crate::database::{
+    get_database_pool,
+    models::{MCPOAuthClient, MCPOAuthToken, MCPOAuthTokenGrant, NewMCPOAuthClient},
+};
+
+/// Get the client registered for a server and redirect URI
+pub async fn get_oauth_client(
+    server_id: Uuid,
+    redirect_uri: &str,
+) -> Result<Option<MCPOAuthClient>, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    sqlx::query_as::<_, MCPOAuthClient>(
+        "SELECT * FROM mcp_oauth_clients WHERE server_id = $1 AND redirect_uri = $2",
+    )
+    .bind(server_id)
+    .bind(redirect_uri)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Get a registered client by ID
+pub async fn get_oauth_client_by_id(id: Uuid) -> Result<Option<MCPOAuthClient>, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    sqlx::query_as::<_, MCPOAuthClient>("SELECT * FROM mcp_oauth_clients WHERE id = $1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+}
+
+/// Save the client registered for a server and redirect URI, replacing any previous one
+pub async fn save_oauth_client(client: &NewMCPOAuthClient) -> Result<MCPOAuthClient, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    sqlx::query_as::<_, MCPOAuthClient>(
+        r#"INSERT INTO mcp_oauth_clients (
+               server_id, issuer, authorization_endpoint, token_endpoint,
+               registration_endpoint, client_id, client_secret, redirect_uri, resource, scope
+           )
+           VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+           ON CONFLICT (server_id, redirect_uri) DO UPDATE SET
+               issuer = EXCLUDED.issuer,
+               authorization_endpoint = EXCLUDED.authorization_endpoint,
+               token_endpoint = EXCLUDED.token_endpoint,
+               registration_endpoint = EXCLUDED.registration_endpoint,
+               client_id = EXCLUDED.client_id,
+               client_secret = EXCLUDED.client_secret,
+               resource = EXCLUDED.resource,
+               scope = EXCLUDED.scope,
+               updated_at = NOW()
+           RETURNING *"#,
+    )
+    .bind(client.server_id)
+    .bind(&client.issuer)
+    .bind(&client.authorization_endpoint)
+    .bind(&client.token_endpoint)
+    .bind(client.registration_endpoint.as_deref())
+    .bind(&client.client_id)
+    .bind(client.client_secret.as_deref())
+    .bind(&client.redirect_uri)
+    .bind(&client.resource)
+    .bind(client.scope.as_deref())
+    .fetch_one(pool)
+    .await
+}
+
+/// Get the token of a user for a server
+pub async fn get_oauth_token(
+    user_id: Uuid,
+    server_id: Uuid,
+) -> Result<Option<MCPOAuthToken>, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    sqlx::query_as::<_, MCPOAuthToken>(
+        "SELECT * FROM mcp_oauth_tokens WHERE user_id = $1 AND server_id = $2",
+    )
+    .bind(user_id)
+    .bind(server_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Save the token of a user for a server, replacing any previous one
+pub async fn save_oauth_token(
+    user_id: Uuid,
+    server_id: Uuid,
+    oauth_client_id: Uuid,
+    grant: &MCPOAuthTokenGrant,
+) -> Result<MCPOAuthToken, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    sqlx::query_as::<_, MCPOAuthToken>(
+        r#"INSERT INTO mcp_oauth_tokens (
+               user_id, server_id, oauth_client_id, access_token, refresh_token,
+               token_type, scope, expires_at
+           )
+           VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+           ON CONFLICT (user_id, server_id) DO UPDATE SET
+               oauth_client_id = EXCLUDED.oauth_client_id,
+               access_token = EXCLUDED.access_token,
+               refresh_token = EXCLUDED.refresh_token,
+               token_type = EXCLUDED.token_type,
+               scope = EXCLUDED.scope,
+               expires_at = EXCLUDED.expires_at,
+               updated_at = NOW()
+           RETURNING *"#,
+    )
+    .bind(user_id)
+    .bind(server_id)
+    .bind(oauth_client_id)
+    .bind(&grant.access_token)
+    .bind(grant.refresh_token.as_deref())
+    .bind(&grant.token_type)
+    .bind(grant.scope.as_deref())
+    .bind(grant.expires_at)
+    .fetch_one(pool)
+    .await
+}
+
+/// Delete the token of a user for a server
+pub async fn delete_oauth_token(user_id: Uuid, server_id: Uuid) -> Result<bool, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    let result = sqlx::query("DELETE FROM mcp_oauth_tokens WHERE user_id = $1 AND server_id = $2")
+        .bind(user_id)
+        .bind(server_id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
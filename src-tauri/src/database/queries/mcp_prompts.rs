@@ -0,0 +1,97 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::database::{get_database_pool, models::MCPPrompt};
+
+/// Cache discovered prompts for a server and record the discovery time
+pub async fn cache_discovered_prompts(
+    server_id: Uuid,
+    prompts: Vec<(String, Option<String>, serde_json::Value)>, // (name, description, arguments)
+) -> Result<Vec<MCPPrompt>, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    // Start a transaction to clear old prompts and insert new ones
+    let mut tx = pool.begin().await?;
+
+    sqlx::query!("DELETE FROM mcp_prompts_cache WHERE server_id = $1", server_id)
+        .execute(&mut *tx)
+        .await?;
+
+    let mut cached_prompts = Vec::new();
+
+    for (prompt_name, prompt_description, arguments) in prompts {
+        let prompt = sqlx::query_as!(
+            MCPPrompt,
+            r#"
+            INSERT INTO mcp_prompts_cache (
+                server_id, prompt_name, prompt_description, arguments
+            )
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (server_id, prompt_name) DO UPDATE SET
+                prompt_description = EXCLUDED.prompt_description,
+                arguments = EXCLUDED.arguments
+            RETURNING
+                id, server_id, prompt_name, prompt_description, arguments, discovered_at
+            "#,
+            server_id,
+            prompt_name,
+            prompt_description,
+            arguments
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        cached_prompts.push(prompt);
+    }
+
+    sqlx::query!(
+        "UPDATE mcp_servers SET prompts_discovered_at = NOW(), updated_at = NOW() WHERE id = $1",
+        server_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(cached_prompts)
+}
+
+/// Get all cached prompts for a server
+pub async fn get_cached_prompts_for_server(
+    server_id: Uuid,
+) -> Result<Vec<MCPPrompt>, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    let prompts = sqlx::query_as!(
+        MCPPrompt,
+        r#"
+        SELECT id, server_id, prompt_name, prompt_description, arguments, discovered_at
+        FROM mcp_prompts_cache
+        WHERE server_id = $1
+        ORDER BY prompt_name ASC
+        "#,
+        server_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(prompts)
+}
+
+/// When prompts were last discovered for a server, None if never
+pub async fn get_prompts_discovered_at(
+    server_id: Uuid,
+) -> Result<Option<DateTime<Utc>>, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    let discovered_at = sqlx::query_scalar!(
+        "SELECT prompts_discovered_at FROM mcp_servers WHERE id = $1",
+        server_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(discovered_at.flatten())
+}
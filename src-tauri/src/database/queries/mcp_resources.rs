@@ -0,0 +1,102 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::database::{get_database_pool, models::MCPResource};
+
+/// Cache discovered resources for a server and record the discovery time
+pub async fn cache_discovered_resources(
+    server_id: Uuid,
+    resources: Vec<(String, String, Option<String>, Option<String>)>, // (uri, name, description, mime type)
+) -> Result<Vec<MCPResource>, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    // Start a transaction to clear old resources and insert new ones
+    let mut tx = pool.begin().await?;
+
+    sqlx::query!("DELETE FROM mcp_resources_cache WHERE server_id = $1", server_id)
+        .execute(&mut *tx)
+        .await?;
+
+    let mut cached_resources = Vec::new();
+
+    for (uri, resource_name, resource_description, mime_type) in resources {
+        let resource = sqlx::query_as!(
+            MCPResource,
+            r#"
+            INSERT INTO mcp_resources_cache (
+                server_id, uri, resource_name, resource_description, mime_type
+            )
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (server_id, uri) DO UPDATE SET
+                resource_name = EXCLUDED.resource_name,
+                resource_description = EXCLUDED.resource_description,
+                mime_type = EXCLUDED.mime_type
+            RETURNING
+                id, server_id, uri, resource_name, resource_description, mime_type,
+                discovered_at
+            "#,
+            server_id,
+            uri,
+            resource_name,
+            resource_description,
+            mime_type
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        cached_resources.push(resource);
+    }
+
+    sqlx::query!(
+        "UPDATE mcp_servers SET resources_discovered_at = NOW(), updated_at = NOW() WHERE id = $1",
+        server_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(cached_resources)
+}
+
+/// Get all cached resources for a server
+pub async fn get_cached_resources_for_server(
+    server_id: Uuid,
+) -> Result<Vec<MCPResource>, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    let resources = sqlx::query_as!(
+        MCPResource,
+        r#"
+        SELECT
+            id, server_id, uri, resource_name, resource_description, mime_type,
+            discovered_at
+        FROM mcp_resources_cache
+        WHERE server_id = $1
+        ORDER BY resource_name ASC
+        "#,
+        server_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(resources)
+}
+
+/// When resources were last discovered for a server, None if never
+pub async fn get_resources_discovered_at(
+    server_id: Uuid,
+) -> Result<Option<DateTime<Utc>>, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    let discovered_at = sqlx::query_scalar!(
+        "SELECT resources_discovered_at FROM mcp_servers WHERE id = $1",
+        server_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(discovered_at.flatten())
+}
@@ -4,10 +4,14 @@ use crate::database::{
     get_database_pool,
     models::{
         CreateMCPServerRequest, CreateSystemMCPServerRequest, MCPServer, MCPServerStatus,
-        MCPTransportType, UpdateMCPServerRequest,
+        MCPSandboxProfile, MCPTransportType, UpdateMCPServerRequest,
     },
 };
 
+fn sandbox_value(sandbox: Option<MCPSandboxProfile>) -> Option<serde_json::Value> {
+    sandbox.and_then(|sandbox| serde_json::to_value(sandbox).ok())
+}
+
 /// Create a new user MCP server
 pub async fn create_user_mcp_server(
     user_id: Uuid,
@@ -22,14 +26,14 @@ pub async fn create_user_mcp_server(
         INSERT INTO mcp_servers (
             user_id, name, display_name, description,
             transport_type, command, args, environment_variables,
-            url, headers, timeout_seconds, max_restart_attempts, enabled, is_system
+            url, headers, timeout_seconds, max_restart_attempts, enabled, is_system, sandbox
         )
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, false)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, false, $14)
         RETURNING
             id, user_id, name, display_name, description,
             enabled, is_system,
             transport_type as "transport_type: MCPTransportType",
-            command, args, environment_variables, url, headers, timeout_seconds,
+            command, args, environment_variables, url, headers, sandbox, timeout_seconds,
             status as "status: MCPServerStatus",
             is_active, last_health_check, restart_count, last_restart_at,
             max_restart_attempts, process_id, port,
@@ -48,7 +52,8 @@ pub async fn create_user_mcp_server(
         request.headers.unwrap_or(serde_json::json!({})),
         request.timeout_seconds,
         request.max_restart_attempts,
-        request.enabled.unwrap_or(true)
+        request.enabled.unwrap_or(true),
+        sandbox_value(request.sandbox).unwrap_or(serde_json::json!({}))
     )
     .fetch_one(pool)
     .await?;
@@ -69,14 +74,14 @@ pub async fn create_system_mcp_server(
         INSERT INTO mcp_servers (
             name, display_name, description,
             transport_type, command, args, environment_variables,
-            url, headers, timeout_seconds, max_restart_attempts, enabled, is_system
+            url, headers, timeout_seconds, max_restart_attempts, enabled, is_system, sandbox
         )
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, true)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, true, $13)
         RETURNING
             id, user_id, name, display_name, description,
             enabled, is_system,
             transport_type as "transport_type: MCPTransportType",
-            command, args, environment_variables, url, headers, timeout_seconds,
+            command, args, environment_variables, url, headers, sandbox, timeout_seconds,
             status as "status: MCPServerStatus",
             is_active, last_health_check, restart_count, last_restart_at,
             max_restart_attempts, process_id, port,
@@ -94,7 +99,8 @@ pub async fn create_system_mcp_server(
         request.headers.unwrap_or(serde_json::json!({})),
         request.timeout_seconds,
         request.max_restart_attempts,
-        request.enabled.unwrap_or(true)
+        request.enabled.unwrap_or(true),
+        sandbox_value(request.sandbox).unwrap_or(serde_json::json!({}))
     )
     .fetch_one(pool)
     .await?;
@@ -114,7 +120,7 @@ pub async fn get_mcp_server_by_id(server_id: Uuid) -> Result<Option<MCPServer>,
             id, user_id, name, display_name, description,
             enabled, is_system,
             transport_type as "transport_type: MCPTransportType",
-            command, args, environment_variables, url, headers, timeout_seconds,
+            command, args, environment_variables, url, headers, sandbox, timeout_seconds,
             status as "status: MCPServerStatus",
             is_active, last_health_check, restart_count, last_restart_at,
             max_restart_attempts, process_id, port,
@@ -143,7 +149,7 @@ pub async fn list_user_accessible_mcp_servers(user_id: Uuid) -> Result<Vec<MCPSe
             s.id, s.user_id, s.name, s.display_name, s.description,
             s.enabled, s.is_system,
             s.transport_type as "transport_type: MCPTransportType",
-            s.command, s.args, s.environment_variables, s.url, s.headers, s.timeout_seconds,
+            s.command, s.args, s.environment_variables, s.url, s.headers, s.sandbox, s.timeout_seconds,
             s.status as "status: MCPServerStatus",
             s.is_active, s.last_health_check, s.restart_count, s.last_restart_at,
             s.max_restart_attempts, s.process_id, s.port,
@@ -182,7 +188,7 @@ pub async fn list_system_mcp_servers() -> Result<Vec<MCPServer>, sqlx::Error> {
             id, user_id, name, display_name, description,
             enabled, is_system,
             transport_type as "transport_type: MCPTransportType",
-            command, args, environment_variables, url, headers, timeout_seconds,
+            command, args, environment_variables, url, headers, sandbox, timeout_seconds,
             status as "status: MCPServerStatus",
             is_active, last_health_check, restart_count, last_restart_at,
             max_restart_attempts, process_id, port,
@@ -221,13 +227,14 @@ pub async fn update_mcp_server(
             headers = COALESCE($9, headers),
             timeout_seconds = COALESCE($10, timeout_seconds),
             max_restart_attempts = COALESCE($11, max_restart_attempts),
+            sandbox = COALESCE($12, sandbox),
             updated_at = NOW()
         WHERE id = $1
         RETURNING
             id, user_id, name, display_name, description,
             enabled, is_system,
             transport_type as "transport_type: MCPTransportType",
-            command, args, environment_variables, url, headers, timeout_seconds,
+            command, args, environment_variables, url, headers, sandbox, timeout_seconds,
             status as "status: MCPServerStatus",
             is_active, last_health_check, restart_count, last_restart_at,
             max_restart_attempts, process_id, port,
@@ -244,7 +251,8 @@ pub async fn update_mcp_server(
         request.url,
         request.headers,
         request.timeout_seconds,
-        request.max_restart_attempts
+        request.max_restart_attempts,
+        sandbox_value(request.sandbox)
     )
     .fetch_one(pool)
     .await?;
@@ -392,7 +400,7 @@ pub async fn get_all_enabled_mcp_servers() -> Result<Vec<MCPServer>, sqlx::Error
             id, user_id, name, display_name, description,
             enabled, is_system,
             transport_type as "transport_type: MCPTransportType",
-            command, args, environment_variables, url, headers, timeout_seconds,
+            command, args, environment_variables, url, headers, sandbox, timeout_seconds,
             status as "status: MCPServerStatus",
             is_active, last_health_check, restart_count, last_restart_at,
             max_restart_attempts, process_id, port,
@@ -0,0 +1,150 @@
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::database::{
+    get_database_pool,
+    models::{
+        evaluate_approval_rules, ApprovalRuleEvaluation, MCPToolApprovalRule,
+        MCPToolApprovalRuleRequest,
+    },
+    queries::user_groups,
+};
+
+// Evaluation order: highest priority first, deny before approve on ties, then oldest first
+const RULE_ORDER: &str = "priority DESC, (action = 'deny') DESC, created_at ASC";
+
+/// List all approval rules in evaluation order
+pub async fn list_approval_rules() -> Result<Vec<MCPToolApprovalRule>, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    sqlx::query_as::<_, MCPToolApprovalRule>(&format!(
+        "SELECT * FROM mcp_tool_approval_rules ORDER BY {}",
+        RULE_ORDER
+    ))
+    .fetch_all(pool)
+    .await
+}
+
+/// Get an approval rule by ID
+pub async fn get_approval_rule_by_id(
+    rule_id: Uuid,
+) -> Result<Option<MCPToolApprovalRule>, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    sqlx::query_as::<_, MCPToolApprovalRule>("SELECT * FROM mcp_tool_approval_rules WHERE id = $1")
+        .bind(rule_id)
+        .fetch_optional(pool)
+        .await
+}
+
+/// Create an approval rule
+pub async fn create_approval_rule(
+    request: MCPToolApprovalRuleRequest,
+) -> Result<MCPToolApprovalRule, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    sqlx::query_as::<_, MCPToolApprovalRule>(
+        r#"INSERT INTO mcp_tool_approval_rules (
+               name, description, server_id, tool_name_pattern, group_id,
+               conditions, action, priority, enabled
+           )
+           VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+           RETURNING *"#,
+    )
+    .bind(request.name.trim())
+    .bind(request.description.as_deref())
+    .bind(request.server_id)
+    .bind(request.tool_name_pattern.as_deref().map(str::trim).unwrap_or("*"))
+    .bind(request.group_id)
+    .bind(sqlx::types::Json(&request.conditions))
+    .bind(request.action.as_str())
+    .bind(request.priority.unwrap_or(0))
+    .bind(request.enabled.unwrap_or(true))
+    .fetch_one(pool)
+    .await
+}
+
+/// Replace an approval rule
+pub async fn update_approval_rule(
+    rule_id: Uuid,
+    request: MCPToolApprovalRuleRequest,
+) -> Result<Option<MCPToolApprovalRule>, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    sqlx::query_as::<_, MCPToolApprovalRule>(
+        r#"UPDATE mcp_tool_approval_rules
+           SET name = $2,
+               description = $3,
+               server_id = $4,
+               tool_name_pattern = $5,
+               group_id = $6,
+               conditions = $7,
+               action = $8,
+               priority = $9,
+               enabled = $10,
+               updated_at = NOW()
+           WHERE id = $1
+           RETURNING *"#,
+    )
+    .bind(rule_id)
+    .bind(request.name.trim())
+    .bind(request.description.as_deref())
+    .bind(request.server_id)
+    .bind(request.tool_name_pattern.as_deref().map(str::trim).unwrap_or("*"))
+    .bind(request.group_id)
+    .bind(sqlx::types::Json(&request.conditions))
+    .bind(request.action.as_str())
+    .bind(request.priority.unwrap_or(0))
+    .bind(request.enabled.unwrap_or(true))
+    .fetch_optional(pool)
+    .await
+}
+
+/// Delete an approval rule
+pub async fn delete_approval_rule(rule_id: Uuid) -> Result<bool, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    let result = sqlx::query!("DELETE FROM mcp_tool_approval_rules WHERE id = $1", rule_id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Evaluate the enabled approval rules for a call made by a user. Rules with argument
+/// conditions are skipped when the arguments are not known.
+pub async fn evaluate_approval_rules_for_user(
+    user_id: Uuid,
+    server_id: Uuid,
+    tool_name: &str,
+    arguments: Option<&Value>,
+) -> Result<ApprovalRuleEvaluation, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    let rules = sqlx::query_as::<_, MCPToolApprovalRule>(&format!(
+        "SELECT * FROM mcp_tool_approval_rules WHERE enabled = true ORDER BY {}",
+        RULE_ORDER
+    ))
+    .fetch_all(pool)
+    .await?;
+
+    if rules.is_empty() {
+        return Ok(evaluate_approval_rules(rules, server_id, tool_name, &[], arguments));
+    }
+
+    let group_ids: Vec<Uuid> = user_groups::get_user_groups(user_id)
+        .await?
+        .into_iter()
+        .map(|group| group.id)
+        .collect();
+
+    Ok(evaluate_approval_rules(
+        rules, server_id, tool_name, &group_ids, arguments,
+    ))
+}
@@ -7,6 +7,8 @@ use crate::database::{
         MCPToolApproval, ToolApprovalResponse, CreateConversationApprovalRequest,
         SetToolGlobalApprovalRequest, ListConversationApprovalsQuery, UpdateToolApprovalRequest,
     },
+    models::ApprovalRuleAction,
+    queries::mcp_tool_approval_rules,
 };
 
 /// Create or update a global tool approval (auto_approve setting)
@@ -170,17 +172,32 @@ pub async fn create_conversation_tool_approval(
     .await
 }
 
-/// Check if tool is approved (checks both global and conversation-specific)
+/// Check if tool is approved (checks approval rules, then global and conversation-specific
+/// approvals). Only approval rules deny a call; `arguments` are matched against rule
+/// conditions when known.
 pub async fn check_tool_approval(
     user_id: Uuid,
     conversation_id: Uuid,
     server_id: Uuid,
     tool_name: &str,
+    arguments: Option<&serde_json::Value>,
 ) -> Result<Option<(bool, String)>, sqlx::Error> { // (approved, source)
     let pool = get_database_pool()?;
     let pool = pool.as_ref();
 
-    // First check for global auto_approve
+    // Admin-defined rules take precedence over user approvals
+    let evaluation = mcp_tool_approval_rules::evaluate_approval_rules_for_user(
+        user_id, server_id, tool_name, arguments,
+    )
+    .await?;
+    if let (Some(action), Some(rule)) = (evaluation.action, evaluation.matched_rule) {
+        return Ok(Some((
+            action == ApprovalRuleAction::Approve,
+            format!("rule:{}", rule.name),
+        )));
+    }
+
+    // Then check for global auto_approve
     let global_result = sqlx::query!(
         r#"
         SELECT auto_approve, expires_at
@@ -6,17 +6,24 @@ pub mod configuration;
 pub mod download_instances;
 pub mod files;
 pub mod mcp_execution_logs;
+pub mod mcp_oauth;
+pub mod mcp_prompts;
+pub mod mcp_resources;
 pub mod mcp_servers;
+pub mod mcp_tool_approval_rules;
 pub mod mcp_tool_approvals;
 pub mod mcp_tools;
+pub mod model_groups;
 pub mod models;
 pub mod projects;
 pub mod providers;
+pub mod rag_embedding_migrations;
 pub mod rag_instance_files;
 pub mod rag_instances;
 pub mod rag_providers;
 pub mod rag_repositories;
 pub mod repositories;
+pub mod token_usage;
 pub mod user_group_mcp_servers;
 pub mod user_group_providers;
 pub mod user_group_rag_providers;
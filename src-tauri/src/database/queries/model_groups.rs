@@ -0,0 +1,202 @@
+use uuid::Uuid;
+
+use crate::database::{
+    get_database_pool,
+    models::{CreateModelGroupRequest, ModelGroup, UpdateModelGroupRequest},
+};
+
+/// List all model groups
+pub async fn list_model_groups() -> Result<Vec<ModelGroup>, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    let groups = sqlx::query_as!(
+        ModelGroup,
+        r#"SELECT g.id, g.name, g.description, g.enabled,
+                  ARRAY(
+                      SELECT gm.model_id FROM model_group_members gm
+                      WHERE gm.model_group_id = g.id
+                      ORDER BY gm.position
+                  ) AS "model_ids!",
+                  g.created_at, g.updated_at
+           FROM model_groups g
+           ORDER BY g.name ASC"#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(groups)
+}
+
+/// List enabled model groups
+pub async fn list_enabled_model_groups() -> Result<Vec<ModelGroup>, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    let groups = sqlx::query_as!(
+        ModelGroup,
+        r#"SELECT g.id, g.name, g.description, g.enabled,
+                  ARRAY(
+                      SELECT gm.model_id FROM model_group_members gm
+                      WHERE gm.model_group_id = g.id
+                      ORDER BY gm.position
+                  ) AS "model_ids!",
+                  g.created_at, g.updated_at
+           FROM model_groups g
+           WHERE g.enabled = true
+           ORDER BY g.name ASC"#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(groups)
+}
+
+/// Get a model group by ID
+pub async fn get_model_group_by_id(group_id: Uuid) -> Result<Option<ModelGroup>, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    let group = sqlx::query_as!(
+        ModelGroup,
+        r#"SELECT g.id, g.name, g.description, g.enabled,
+                  ARRAY(
+                      SELECT gm.model_id FROM model_group_members gm
+                      WHERE gm.model_group_id = g.id
+                      ORDER BY gm.position
+                  ) AS "model_ids!",
+                  g.created_at, g.updated_at
+           FROM model_groups g
+           WHERE g.id = $1"#,
+        group_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(group)
+}
+
+/// Member models of an enabled group that can currently serve requests (model and provider
+/// enabled), in failover order
+pub async fn get_available_model_group_members(group_id: Uuid) -> Result<Vec<Uuid>, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    let model_ids = sqlx::query_scalar!(
+        r#"SELECT gm.model_id
+           FROM model_group_members gm
+           JOIN model_groups g ON g.id = gm.model_group_id
+           JOIN models m ON m.id = gm.model_id
+           JOIN providers p ON p.id = m.provider_id
+           WHERE gm.model_group_id = $1 AND g.enabled = true
+             AND m.enabled = true AND p.enabled = true
+           ORDER BY gm.position"#,
+        group_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(model_ids)
+}
+
+/// Create a model group with its members
+pub async fn create_model_group(request: CreateModelGroupRequest) -> Result<ModelGroup, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+    let mut tx = pool.begin().await?;
+
+    let group_id = sqlx::query_scalar!(
+        r#"INSERT INTO model_groups (name, description, enabled)
+           VALUES ($1, $2, $3)
+           RETURNING id"#,
+        request.name.trim(),
+        request.description.as_deref(),
+        request.enabled.unwrap_or(true)
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    replace_model_group_members(&mut tx, group_id, &request.model_ids).await?;
+
+    tx.commit().await?;
+
+    get_model_group_by_id(group_id)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)
+}
+
+/// Update a model group. A provided member list replaces the existing one.
+pub async fn update_model_group(
+    group_id: Uuid,
+    request: UpdateModelGroupRequest,
+) -> Result<Option<ModelGroup>, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+    let mut tx = pool.begin().await?;
+
+    let updated = sqlx::query!(
+        r#"UPDATE model_groups
+           SET name = COALESCE($2, name),
+               description = COALESCE($3, description),
+               enabled = COALESCE($4, enabled),
+               updated_at = NOW()
+           WHERE id = $1"#,
+        group_id,
+        request.name.as_deref().map(str::trim),
+        request.description.as_deref(),
+        request.enabled
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    if updated.rows_affected() == 0 {
+        return Ok(None);
+    }
+
+    if let Some(model_ids) = &request.model_ids {
+        replace_model_group_members(&mut tx, group_id, model_ids).await?;
+    }
+
+    tx.commit().await?;
+
+    get_model_group_by_id(group_id).await
+}
+
+/// Delete a model group
+pub async fn delete_model_group(group_id: Uuid) -> Result<bool, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    let result = sqlx::query!("DELETE FROM model_groups WHERE id = $1", group_id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+async fn replace_model_group_members(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    group_id: Uuid,
+    model_ids: &[Uuid],
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "DELETE FROM model_group_members WHERE model_group_id = $1",
+        group_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    // Positions follow the order of the list; repeated models keep their first position
+    sqlx::query!(
+        r#"INSERT INTO model_group_members (model_group_id, model_id, position)
+           SELECT $1, member.model_id, MIN(member.position)::INTEGER
+           FROM UNNEST($2::uuid[]) WITH ORDINALITY AS member(model_id, position)
+           GROUP BY member.model_id"#,
+        group_id,
+        model_ids
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,506 @@
+use crate::database::{
+    get_database_pool,
+    models::{RAGEmbeddingMigration, RAGEmbeddingMigrationStatus, RAGProcessingStatus},
+};
+use pgvector::HalfVector;
+use uuid::Uuid;
+
+/// Create a pending embedding migration for a RAG instance within a transaction
+pub async fn create_rag_embedding_migration_tx(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    instance_id: Uuid,
+    source_model_id: Option<Uuid>,
+    target_model_id: Uuid,
+) -> Result<RAGEmbeddingMigration, sqlx::Error> {
+    let migration = sqlx::query_as!(
+        RAGEmbeddingMigration,
+        r#"INSERT INTO rag_embedding_migrations (rag_instance_id, source_model_id, target_model_id, status)
+         VALUES ($1, $2, $3, $4)
+         RETURNING id, rag_instance_id, source_model_id, target_model_id, status,
+                   total_documents, processed_documents, error_message,
+                   started_at, completed_at, created_at, updated_at"#,
+        instance_id,
+        source_model_id,
+        target_model_id,
+        RAGEmbeddingMigrationStatus::Pending.as_str()
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(migration)
+}
+
+/// Get the most recent embedding migration of a RAG instance, whatever its status
+pub async fn get_latest_rag_embedding_migration(
+    instance_id: Uuid,
+) -> Result<Option<RAGEmbeddingMigration>, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    let migration = sqlx::query_as!(
+        RAGEmbeddingMigration,
+        r#"SELECT id, rag_instance_id, source_model_id, target_model_id, status,
+                  total_documents, processed_documents, error_message,
+                  started_at, completed_at, created_at, updated_at
+         FROM rag_embedding_migrations
+         WHERE rag_instance_id = $1
+         ORDER BY created_at DESC
+         LIMIT 1"#,
+        instance_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(migration)
+}
+
+/// Get the pending or running embedding migration of a RAG instance within a transaction
+pub async fn get_active_rag_embedding_migration_tx(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    instance_id: Uuid,
+) -> Result<Option<RAGEmbeddingMigration>, sqlx::Error> {
+    let migration = sqlx::query_as!(
+        RAGEmbeddingMigration,
+        r#"SELECT id, rag_instance_id, source_model_id, target_model_id, status,
+                  total_documents, processed_documents, error_message,
+                  started_at, completed_at, created_at, updated_at
+         FROM rag_embedding_migrations
+         WHERE rag_instance_id = $1 AND status IN ($2, $3)"#,
+        instance_id,
+        RAGEmbeddingMigrationStatus::Pending.as_str(),
+        RAGEmbeddingMigrationStatus::Running.as_str()
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(migration)
+}
+
+/// List pending and running embedding migrations, including ones interrupted by a restart
+pub async fn list_active_rag_embedding_migrations() -> Result<Vec<RAGEmbeddingMigration>, sqlx::Error>
+{
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    let migrations = sqlx::query_as!(
+        RAGEmbeddingMigration,
+        r#"SELECT id, rag_instance_id, source_model_id, target_model_id, status,
+                  total_documents, processed_documents, error_message,
+                  started_at, completed_at, created_at, updated_at
+         FROM rag_embedding_migrations
+         WHERE status IN ($1, $2)
+         ORDER BY created_at ASC"#,
+        RAGEmbeddingMigrationStatus::Pending.as_str(),
+        RAGEmbeddingMigrationStatus::Running.as_str()
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(migrations)
+}
+
+/// Mark the active embedding migration of a RAG instance as cancelled.
+/// Returns the cancelled migration ID so its worker can be signalled.
+pub async fn cancel_active_rag_embedding_migration(
+    instance_id: Uuid,
+) -> Result<Option<Uuid>, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    let mut tx = pool.begin().await?;
+    let migration_id = cancel_active_rag_embedding_migration_tx(&mut tx, instance_id).await?;
+    tx.commit().await?;
+
+    Ok(migration_id)
+}
+
+/// Mark the active embedding migration of a RAG instance as cancelled within a transaction
+pub async fn cancel_active_rag_embedding_migration_tx(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    instance_id: Uuid,
+) -> Result<Option<Uuid>, sqlx::Error> {
+    let migration_id = sqlx::query_scalar!(
+        r#"UPDATE rag_embedding_migrations
+         SET status = $1, completed_at = NOW()
+         WHERE rag_instance_id = $2 AND status IN ($3, $4)
+         RETURNING id"#,
+        RAGEmbeddingMigrationStatus::Cancelled.as_str(),
+        instance_id,
+        RAGEmbeddingMigrationStatus::Pending.as_str(),
+        RAGEmbeddingMigrationStatus::Running.as_str()
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    if migration_id.is_some() {
+        sqlx::query!(
+            "UPDATE rag_instances SET updated_at = NOW() WHERE id = $1",
+            instance_id
+        )
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    Ok(migration_id)
+}
+
+/// Mark an embedding migration as running with the number of chunks to re-embed
+pub async fn start_rag_embedding_migration(
+    migration_id: Uuid,
+    total_documents: i64,
+    processed_documents: i64,
+) -> Result<(), sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    sqlx::query!(
+        r#"UPDATE rag_embedding_migrations
+         SET status = $1, total_documents = $2, processed_documents = $3,
+             started_at = COALESCE(started_at, NOW())
+         WHERE id = $4 AND status IN ($5, $1)"#,
+        RAGEmbeddingMigrationStatus::Running.as_str(),
+        total_documents,
+        processed_documents,
+        migration_id,
+        RAGEmbeddingMigrationStatus::Pending.as_str()
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Record embedding migration progress; the instance is touched so status streams pick it up
+pub async fn update_rag_embedding_migration_progress(
+    migration_id: Uuid,
+    instance_id: Uuid,
+    total_documents: i64,
+    processed_documents: i64,
+) -> Result<(), sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    sqlx::query!(
+        r#"UPDATE rag_embedding_migrations
+         SET total_documents = $1, processed_documents = $2
+         WHERE id = $3"#,
+        total_documents,
+        processed_documents,
+        migration_id
+    )
+    .execute(pool)
+    .await?;
+
+    touch_rag_instance(instance_id).await
+}
+
+/// Mark an embedding migration as failed
+pub async fn fail_rag_embedding_migration(
+    migration_id: Uuid,
+    instance_id: Uuid,
+    error_message: &str,
+) -> Result<(), sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    sqlx::query!(
+        r#"UPDATE rag_embedding_migrations
+         SET status = $1, error_message = $2, completed_at = NOW()
+         WHERE id = $3"#,
+        RAGEmbeddingMigrationStatus::Failed.as_str(),
+        error_message,
+        migration_id
+    )
+    .execute(pool)
+    .await?;
+
+    touch_rag_instance(instance_id).await
+}
+
+/// Get the status of an embedding migration
+pub async fn get_rag_embedding_migration_status(
+    migration_id: Uuid,
+) -> Result<Option<RAGEmbeddingMigrationStatus>, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    let status = sqlx::query_scalar!(
+        "SELECT status FROM rag_embedding_migrations WHERE id = $1",
+        migration_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(status.and_then(|s| RAGEmbeddingMigrationStatus::from_str(&s)))
+}
+
+/// Count the instance's chunks and how many of them already have a staged embedding
+pub async fn count_staged_embeddings(instance_id: Uuid) -> Result<(i64, i64), sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    let row = sqlx::query!(
+        r#"SELECT COUNT(*) as "total!", COUNT(embedding_next) as "staged!"
+         FROM simple_vector_documents
+         WHERE rag_instance_id = $1"#,
+        instance_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok((row.total, row.staged))
+}
+
+/// Count the instance's chunks within a transaction
+pub async fn count_vector_documents_tx(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    instance_id: Uuid,
+) -> Result<i64, sqlx::Error> {
+    let count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM simple_vector_documents WHERE rag_instance_id = $1"#,
+        instance_id
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(count)
+}
+
+/// Count files of the instance that are currently being indexed
+pub async fn count_processing_files(instance_id: Uuid) -> Result<i64, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    let count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM rag_instance_files
+         WHERE rag_instance_id = $1 AND processing_status = $2"#,
+        instance_id,
+        RAGProcessingStatus::Processing.as_str()
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(count)
+}
+
+/// List files of the instance that have chunks without a staged embedding
+pub async fn list_files_pending_reembedding(instance_id: Uuid) -> Result<Vec<Uuid>, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    let file_ids = sqlx::query_scalar!(
+        r#"SELECT DISTINCT file_id FROM simple_vector_documents
+         WHERE rag_instance_id = $1 AND embedding_next IS NULL
+         ORDER BY file_id"#,
+        instance_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(file_ids)
+}
+
+/// Get a file's chunks that still need a staged embedding, in chunk order
+pub async fn get_documents_pending_reembedding(
+    instance_id: Uuid,
+    file_id: Uuid,
+    limit: i64,
+) -> Result<Vec<(Uuid, String)>, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    let rows = sqlx::query!(
+        r#"SELECT id, content FROM simple_vector_documents
+         WHERE rag_instance_id = $1 AND file_id = $2 AND embedding_next IS NULL
+         ORDER BY chunk_index
+         LIMIT $3"#,
+        instance_id,
+        file_id,
+        limit
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(|row| (row.id, row.content)).collect())
+}
+
+/// Store staged embeddings for a batch of chunks
+pub async fn store_staged_embeddings(
+    instance_id: Uuid,
+    embeddings: &[(Uuid, Vec<f32>)],
+) -> Result<(), sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    let mut tx = pool.begin().await?;
+    for (document_id, embedding) in embeddings {
+        let embedding = HalfVector::from_f32_slice(embedding);
+        sqlx::query!(
+            r#"UPDATE simple_vector_documents SET embedding_next = $1
+             WHERE rag_instance_id = $2 AND id = $3"#,
+            embedding as HalfVector,
+            instance_id,
+            document_id
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+
+    Ok(())
+}
+
+/// Discard embeddings staged by an unfinished migration
+pub async fn clear_staged_embeddings(instance_id: Uuid) -> Result<(), sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    sqlx::query!(
+        r#"UPDATE simple_vector_documents SET embedding_next = NULL
+         WHERE rag_instance_id = $1 AND embedding_next IS NOT NULL"#,
+        instance_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Result of completing an embedding migration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RAGEmbeddingMigrationCompletion {
+    /// Staged embeddings were swapped into the index
+    Completed,
+    /// The migration is no longer running (e.g. it was cancelled meanwhile)
+    NotRunning,
+    /// Some chunks have no staged embedding yet; they have to be re-embedded first
+    Unstaged,
+}
+
+impl RAGEmbeddingMigrationCompletion {
+    /// Decide the completion from the rows the status update matched and the chunks that still
+    /// lack a staged embedding. Unstaged chunks would lose their embedding in the swap.
+    pub fn decide(completed_rows: u64, unstaged_documents: i64) -> Self {
+        if completed_rows == 0 {
+            Self::NotRunning
+        } else if unstaged_documents > 0 {
+            Self::Unstaged
+        } else {
+            Self::Completed
+        }
+    }
+}
+
+/// Swap staged embeddings into the index and switch the instance to the target model in one
+/// transaction. The HNSW index is dimension-specific, so it is dropped here and has to be
+/// recreated with `create_rag_instance_index` afterwards. Nothing is changed unless the
+/// migration is still running and every chunk of the instance has a staged embedding.
+pub async fn complete_rag_embedding_migration(
+    migration_id: Uuid,
+    instance_id: Uuid,
+    target_model_id: Uuid,
+) -> Result<RAGEmbeddingMigrationCompletion, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    let instance_id_str = instance_id.to_string().replace("-", "_");
+    let index_name = format!("idx_simple_vector_docs_embedding_{}", instance_id_str);
+
+    let mut tx = pool.begin().await?;
+
+    // Completing first locks the migration row, so a concurrent cancel either wins or waits
+    let completed = sqlx::query!(
+        r#"UPDATE rag_embedding_migrations
+         SET status = $1, processed_documents = total_documents, completed_at = NOW()
+         WHERE id = $2 AND status = $3"#,
+        RAGEmbeddingMigrationStatus::Completed.as_str(),
+        migration_id,
+        RAGEmbeddingMigrationStatus::Running.as_str()
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    // Chunks written since they were staged (re-indexed or new files) have to be embedded again
+    let unstaged = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM simple_vector_documents
+         WHERE rag_instance_id = $1 AND embedding_next IS NULL"#,
+        instance_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let completion = RAGEmbeddingMigrationCompletion::decide(completed.rows_affected(), unstaged);
+    if completion != RAGEmbeddingMigrationCompletion::Completed {
+        tx.rollback().await?;
+        return Ok(completion);
+    }
+
+    sqlx::query(&format!("DROP INDEX IF EXISTS {}", index_name))
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query!(
+        r#"UPDATE simple_vector_documents
+         SET embedding = embedding_next, embedding_next = NULL, updated_at = NOW()
+         WHERE rag_instance_id = $1 AND embedding_next IS NOT NULL"#,
+        instance_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        "UPDATE rag_instances SET embedding_model_id = $1, updated_at = NOW() WHERE id = $2",
+        target_model_id,
+        instance_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(RAGEmbeddingMigrationCompletion::Completed)
+}
+
+/// Bump the instance's updated_at so status streams emit a new update
+async fn touch_rag_instance(instance_id: Uuid) -> Result<(), sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    sqlx::query!(
+        "UPDATE rag_instances SET updated_at = NOW() WHERE id = $1",
+        instance_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn completion_requires_running_migration() {
+        assert_eq!(
+            RAGEmbeddingMigrationCompletion::decide(0, 0),
+            RAGEmbeddingMigrationCompletion::NotRunning
+        );
+        assert_eq!(
+            RAGEmbeddingMigrationCompletion::decide(0, 5),
+            RAGEmbeddingMigrationCompletion::NotRunning
+        );
+    }
+
+    #[test]
+    fn completion_waits_for_unstaged_chunks() {
+        assert_eq!(
+            RAGEmbeddingMigrationCompletion::decide(1, 3),
+            RAGEmbeddingMigrationCompletion::Unstaged
+        );
+        assert_eq!(
+            RAGEmbeddingMigrationCompletion::decide(1, 0),
+            RAGEmbeddingMigrationCompletion::Completed
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use crate::database::{
     get_database_pool,
-    models::{RAGInstanceFile, RAGInstanceFilesListResponse, RAGProcessingStatus},
+    models::{file::File, RAGInstanceFile, RAGInstanceFilesListResponse, RAGProcessingStatus},
 };
 use uuid::Uuid;
 
@@ -217,6 +217,101 @@ pub async fn add_file_to_rag_instance(
     Ok(rag_file)
 }
 
+/// Find the user's file with the given name in a RAG instance
+pub async fn find_rag_instance_file_by_filename(
+    instance_id: Uuid,
+    user_id: Uuid,
+    filename: &str,
+) -> Result<Option<File>, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    let file = sqlx::query_as!(
+        File,
+        r#"SELECT f.id, f.user_id, f.filename, f.file_size, f.mime_type, f.checksum,
+                 f.project_id, f.thumbnail_count, f.page_count, f.processing_metadata,
+                 f.created_at, f.updated_at
+         FROM files f
+         JOIN rag_instance_files rif ON rif.file_id = f.id
+         WHERE rif.rag_instance_id = $1 AND f.user_id = $2 AND f.filename = $3
+         ORDER BY f.created_at DESC
+         LIMIT 1"#,
+        instance_id,
+        user_id,
+        filename
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(file)
+}
+
+/// Mark a file of a RAG instance as pending so it is processed again
+pub async fn reset_rag_instance_file_to_pending(
+    instance_id: Uuid,
+    file_id: Uuid,
+) -> Result<(), sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    sqlx::query!(
+        "UPDATE rag_instance_files
+         SET processing_status = $3, processing_error = NULL, processed_at = NULL,
+             updated_at = NOW()
+         WHERE rag_instance_id = $1 AND file_id = $2",
+        instance_id,
+        file_id,
+        RAGProcessingStatus::Pending.as_str()
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Replace the user-defined tags of a file in a RAG instance, keeping other metadata
+pub async fn update_rag_instance_file_tags(
+    instance_id: Uuid,
+    file_id: Uuid,
+    tags: &[String],
+) -> Result<Option<RAGInstanceFile>, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    let updated = sqlx::query!(
+        "UPDATE rag_instance_files
+         SET rag_metadata = COALESCE(rag_metadata, '{}'::jsonb) || jsonb_build_object('tags', $3::jsonb),
+             updated_at = NOW()
+         WHERE rag_instance_id = $1 AND file_id = $2",
+        instance_id,
+        file_id,
+        serde_json::json!(tags)
+    )
+    .execute(pool)
+    .await?;
+
+    if updated.rows_affected() == 0 {
+        return Ok(None);
+    }
+
+    let rag_file = sqlx::query_as!(
+        RAGInstanceFile,
+        r#"SELECT rif.id, rif.rag_instance_id, rif.file_id, f.filename, 
+                 rif.processing_status, 
+                 rif.processed_at, rif.processing_error, rif.rag_metadata, 
+                 rif.created_at, rif.updated_at
+         FROM rag_instance_files rif
+         JOIN files f ON rif.file_id = f.id
+         WHERE rif.rag_instance_id = $1 AND rif.file_id = $2"#,
+        instance_id,
+        file_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(rag_file)
+}
+
 /// Remove file from RAG instance
 pub async fn remove_file_from_rag_instance(
     instance_id: Uuid,
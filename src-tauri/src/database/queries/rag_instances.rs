@@ -1,12 +1,18 @@
 use crate::database::{
     get_database_pool,
     models::{
-        CreateRAGInstanceRequest, CreateSystemRAGInstanceRequest, RAGInstance,
+        CreateRAGInstanceRequest, CreateSystemRAGInstanceRequest, RAGEmbeddingMigration, RAGInstance,
         RAGInstanceListResponse, UpdateRAGInstanceRequest, RAGInstanceErrorCode,
         RAGProcessingStatus,
         rag_instance,
     },
-    queries::user_group_rag_providers::can_user_create_rag_instance,
+    queries::{
+        rag_embedding_migrations::{
+            cancel_active_rag_embedding_migration_tx, count_vector_documents_tx,
+            create_rag_embedding_migration_tx, get_active_rag_embedding_migration_tx,
+        },
+        user_group_rag_providers::can_user_create_rag_instance,
+    },
 };
 use uuid::Uuid;
 
@@ -320,17 +326,36 @@ pub async fn list_system_rag_instances(
     })
 }
 
-/// Update RAG instance with ownership validation
-pub async fn update_rag_instance(
-    instance_id: Uuid,
-    request: UpdateRAGInstanceRequest,
-) -> Result<Option<RAGInstance>, sqlx::Error> {
-    let pool = get_database_pool()?;
-    let pool = pool.as_ref();
+/// What an update of an instance changes
+#[derive(Debug, PartialEq)]
+pub struct RAGInstanceUpdatePlan {
+    /// Embedding model of the instance before the update
+    pub current_model_id: Option<Uuid>,
+    /// Whether the instance's files must be indexed again
+    pub should_reset_state: bool,
+    /// Model the existing chunks are re-embedded with in the background instead
+    pub embedding_migration_target: Option<Uuid>,
+    /// Model stored on the instance right away
+    pub embedding_model_update: Option<Uuid>,
+    /// Whether the active embedding migration is superseded and must be cancelled
+    pub cancels_active_migration: bool,
+}
 
-    // Get current instance to check for changes that require state reset
-    let current_instance = get_rag_instance_by_id(instance_id).await?;
-    let should_reset_state = if let Some(current) = &current_instance {
+/// Plan an update of an instance from its current state, its active embedding migration and
+/// the number of chunks it has indexed.
+///
+/// Choosing yet another model or resetting supersedes a migration in progress. Re-sending the
+/// current model does not, so saving unrelated settings keeps the migration running; it is
+/// reverted through the cancel endpoint instead.
+pub fn plan_rag_instance_update(
+    current: Option<&RAGInstance>,
+    active_migration: Option<&RAGEmbeddingMigration>,
+    request: &UpdateRAGInstanceRequest,
+    document_count: i64,
+) -> RAGInstanceUpdatePlan {
+    let current_model_id = current.and_then(|i| i.embedding_model_id);
+    let mut embedding_migration_target: Option<Uuid> = None;
+    let should_reset_state = if let Some(current) = current {
         // Check if engine type is changing
         let engine_type_changed = request.engine_type.as_ref()
             .map(|new_type| new_type != &current.engine_type)
@@ -355,11 +380,92 @@ pub async fn update_rag_instance(
             .map(|new_id| Some(new_id) != current.llm_model_id)
             .unwrap_or(false);
 
-        engine_type_changed || engine_settings_changed || embedding_model_changed || llm_model_changed
+        let other_changes = engine_type_changed || engine_settings_changed || llm_model_changed;
+
+        // A changed embedding model alone re-embeds the existing chunks in the background
+        // instead of re-indexing every file; the current index keeps serving queries meanwhile
+        if embedding_model_changed
+            && !other_changes
+            && current.embedding_model_id.is_some()
+            && document_count > 0
+        {
+            embedding_migration_target = request.embedding_model_id;
+        }
+
+        other_changes || (embedding_model_changed && embedding_migration_target.is_none())
     } else {
         false // Instance doesn't exist, no need to reset
     };
 
+    let cancels_active_migration = active_migration
+        .map(|migration| {
+            should_reset_state
+                || request
+                    .embedding_model_id
+                    .map(|id| id != migration.target_model_id && Some(id) != current_model_id)
+                    .unwrap_or(false)
+        })
+        .unwrap_or(false);
+
+    // A migration that is not superseded already moves the instance to the requested model;
+    // the current model is kept until it swaps the index
+    let keeps_active_migration = active_migration.is_some() && !cancels_active_migration;
+    let embedding_migration_target = embedding_migration_target.filter(|_| !keeps_active_migration);
+    let embedding_model_update = request
+        .embedding_model_id
+        .filter(|_| !keeps_active_migration && embedding_migration_target.is_none());
+
+    RAGInstanceUpdatePlan {
+        current_model_id,
+        should_reset_state,
+        embedding_migration_target,
+        embedding_model_update,
+        cancels_active_migration,
+    }
+}
+
+/// Update RAG instance with ownership validation. The update is planned and stored in one
+/// transaction, cancelling an embedding migration it supersedes; the migration's worker stops
+/// once it sees the cancelled status.
+pub async fn update_rag_instance(
+    instance_id: Uuid,
+    request: UpdateRAGInstanceRequest,
+) -> Result<Option<RAGInstance>, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    let mut tx = pool.begin().await?;
+
+    // Lock the instance so concurrent updates are planned against each other's result
+    let current_instance = sqlx::query_as!(
+        RAGInstance,
+        r#"SELECT id, provider_id, user_id, project_id, name, display_name, description,
+                  enabled, is_active, is_system, status, error_code,
+                  engine_type,
+                  engine_settings,
+                  embedding_model_id, llm_model_id, age_graph_name, parameters,
+                  created_at, updated_at
+         FROM rag_instances
+         WHERE id = $1
+         FOR UPDATE"#,
+        instance_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+    let active_migration = get_active_rag_embedding_migration_tx(&mut tx, instance_id).await?;
+    let document_count = count_vector_documents_tx(&mut tx, instance_id).await?;
+
+    let plan = plan_rag_instance_update(
+        current_instance.as_ref(),
+        active_migration.as_ref(),
+        &request,
+        document_count,
+    );
+
+    if plan.cancels_active_migration {
+        cancel_active_rag_embedding_migration_tx(&mut tx, instance_id).await?;
+    }
+
     // Handle engine settings update
     let engine_settings_update = if let Some(settings) = &request.engine_settings {
         Some(serde_json::to_value(settings).unwrap_or_else(|_| serde_json::json!({})))
@@ -374,7 +480,7 @@ pub async fn update_rag_instance(
             name,
             instance_id
         )
-          .execute(pool)
+          .execute(&mut *tx)
           .await?;
     }
 
@@ -384,7 +490,7 @@ pub async fn update_rag_instance(
             display_name,
             instance_id
         )
-          .execute(pool)
+          .execute(&mut *tx)
           .await?;
     }
 
@@ -394,7 +500,7 @@ pub async fn update_rag_instance(
             description,
             instance_id
         )
-        .execute(pool)
+        .execute(&mut *tx)
         .await?;
     }
 
@@ -404,7 +510,7 @@ pub async fn update_rag_instance(
             enabled,
             instance_id
         )
-        .execute(pool)
+        .execute(&mut *tx)
         .await?;
     }
 
@@ -414,7 +520,7 @@ pub async fn update_rag_instance(
             is_active,
             instance_id
         )
-        .execute(pool)
+        .execute(&mut *tx)
         .await?;
     }
 
@@ -425,17 +531,31 @@ pub async fn update_rag_instance(
             engine_type_str,
             instance_id
         )
-        .execute(pool)
+        .execute(&mut *tx)
         .await?;
     }
 
-    if let Some(embedding_model_id) = request.embedding_model_id {
+    if let Some(target_model_id) = plan.embedding_migration_target {
+        // The instance switches to the new model when the migration swaps the index
+        create_rag_embedding_migration_tx(
+            &mut tx,
+            instance_id,
+            plan.current_model_id,
+            target_model_id,
+        )
+        .await?;
+        tracing::info!(
+            "Scheduled embedding migration for RAG instance {} to model {}",
+            instance_id,
+            target_model_id
+        );
+    } else if let Some(embedding_model_id) = plan.embedding_model_update {
         sqlx::query!(
             "UPDATE rag_instances SET embedding_model_id = $1, updated_at = NOW() WHERE id = $2",
             embedding_model_id,
             instance_id
         )
-        .execute(pool)
+        .execute(&mut *tx)
         .await?;
     }
 
@@ -445,7 +565,7 @@ pub async fn update_rag_instance(
             llm_model_id,
             instance_id
         )
-        .execute(pool)
+        .execute(&mut *tx)
         .await?;
     }
 
@@ -455,7 +575,7 @@ pub async fn update_rag_instance(
             parameters,
             instance_id
         )
-        .execute(pool)
+        .execute(&mut *tx)
         .await?;
     }
 
@@ -466,7 +586,7 @@ pub async fn update_rag_instance(
             error_code_str,
             instance_id
         )
-        .execute(pool)
+        .execute(&mut *tx)
         .await?;
     }
 
@@ -476,12 +596,14 @@ pub async fn update_rag_instance(
             engine_settings,
             instance_id
         )
-        .execute(pool)
+        .execute(&mut *tx)
         .await?;
     }
 
+    tx.commit().await?;
+
     // Reset instance state if configuration changes require it
-    if should_reset_state {
+    if plan.should_reset_state {
         if let Err(e) = reset_rag_instance_state(instance_id).await {
             tracing::warn!("Failed to reset state for updated RAG instance {}: {}", instance_id, e);
         } else {
@@ -518,6 +640,11 @@ pub async fn delete_rag_instance(instance_id: Uuid) -> Result<bool, sqlx::Error>
         tracing::warn!("Failed to drop partition for RAG instance {} during deletion: {}", instance_id, e);
     }
 
+    // Drop the knowledge graph (AGE graphs are not removed by CASCADE)
+    if let Err(e) = drop_graph_for_instance(instance_id).await {
+        tracing::warn!("Failed to drop AGE graph for RAG instance {} during deletion: {}", instance_id, e);
+    }
+
     // Delete RAG instance (CASCADE will automatically delete associated files and rag_instance_files)
     let result = sqlx::query!("DELETE FROM rag_instances WHERE id = $1", instance_id)
         .execute(pool)
@@ -869,6 +996,48 @@ async fn drop_partition_for_instance(instance_id: Uuid) -> Result<(), sqlx::Erro
 }
 
 
+/// Drop the AGE graph and clear knowledge graph tables for a specific RAG instance
+async fn drop_graph_for_instance(instance_id: Uuid) -> Result<(), sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    let graph_name = sqlx::query_scalar!(
+        "SELECT age_graph_name FROM rag_instances WHERE id = $1",
+        instance_id
+    )
+    .fetch_optional(pool)
+    .await?
+    .flatten();
+
+    if let Some(graph_name) = graph_name {
+        sqlx::query!("SELECT drop_age_graph($1)", graph_name)
+            .fetch_one(pool)
+            .await?;
+
+        sqlx::query!("UPDATE rag_instances SET age_graph_name = NULL WHERE id = $1", instance_id)
+            .execute(pool)
+            .await?;
+
+        tracing::info!("Dropped AGE graph {} for RAG instance {}", graph_name, instance_id);
+    }
+
+    sqlx::query!("DELETE FROM age_graphs WHERE rag_instance_id = $1", instance_id)
+        .execute(pool)
+        .await?;
+    sqlx::query!("DELETE FROM simple_graph_chunks WHERE rag_instance_id = $1", instance_id)
+        .execute(pool)
+        .await?;
+    sqlx::query!("DELETE FROM simple_graph_relationships WHERE rag_instance_id = $1", instance_id)
+        .execute(pool)
+        .await?;
+    sqlx::query!("DELETE FROM simple_graph_entities WHERE rag_instance_id = $1", instance_id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+
 /// Create HNSW index for a RAG instance on its specific partition
 pub async fn create_rag_instance_index(instance_id: Uuid) -> Result<(), sqlx::Error> {
     let pool = get_database_pool()?;
@@ -1009,6 +1178,11 @@ pub async fn reset_rag_instance_state(instance_id: Uuid) -> Result<(), sqlx::Err
         tracing::warn!("Failed to create partition for RAG instance {} during reset: {}", instance_id, e);
         return Err(e);
     }
+
+    // Drop the knowledge graph; the graph engine recreates it on initialization
+    if let Err(e) = drop_graph_for_instance(instance_id).await {
+        tracing::warn!("Failed to drop AGE graph for RAG instance {} during reset: {}", instance_id, e);
+    }
     
     // Create fresh index if embedding model is configured
     if instance.embedding_model_id.is_some() {
@@ -1021,3 +1195,172 @@ pub async fn reset_rag_instance_state(instance_id: Uuid) -> Result<(), sqlx::Err
     
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::models::{RAGEmbeddingMigrationStatus, RAGEngineSettings, RAGEngineType};
+    use crate::database::types::EnumOption;
+
+    fn instance(embedding_model_id: Uuid) -> RAGInstance {
+        RAGInstance {
+            id: Uuid::new_v4(),
+            provider_id: Uuid::new_v4(),
+            user_id: None,
+            project_id: None,
+            name: "docs".to_string(),
+            display_name: "Docs".to_string(),
+            description: None,
+            enabled: true,
+            is_active: true,
+            is_system: false,
+            status: EnumOption(None),
+            error_code: EnumOption(None),
+            engine_type: RAGEngineType::RagSimpleVector,
+            engine_settings: RAGEngineSettings::default(),
+            embedding_model_id: Some(embedding_model_id),
+            llm_model_id: None,
+            age_graph_name: None,
+            parameters: serde_json::json!({}),
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    fn migration(instance: &RAGInstance, target_model_id: Uuid) -> RAGEmbeddingMigration {
+        RAGEmbeddingMigration {
+            id: Uuid::new_v4(),
+            rag_instance_id: instance.id,
+            source_model_id: instance.embedding_model_id,
+            target_model_id,
+            status: RAGEmbeddingMigrationStatus::Running,
+            total_documents: 10,
+            processed_documents: 4,
+            error_message: None,
+            started_at: None,
+            completed_at: None,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    fn request(embedding_model_id: Option<Uuid>) -> UpdateRAGInstanceRequest {
+        UpdateRAGInstanceRequest {
+            name: None,
+            display_name: None,
+            description: None,
+            enabled: None,
+            is_active: None,
+            engine_type: None,
+            embedding_model_id,
+            llm_model_id: None,
+            parameters: None,
+            engine_settings: None,
+            error_code: None,
+        }
+    }
+
+    #[test]
+    fn model_change_with_documents_schedules_migration() {
+        let current = instance(Uuid::new_v4());
+        let target = Uuid::new_v4();
+
+        let plan = plan_rag_instance_update(Some(&current), None, &request(Some(target)), 12);
+
+        assert!(!plan.should_reset_state);
+        assert_eq!(plan.embedding_migration_target, Some(target));
+        assert_eq!(plan.embedding_model_update, None);
+        assert_eq!(plan.current_model_id, current.embedding_model_id);
+    }
+
+    #[test]
+    fn model_change_without_documents_resets() {
+        let current = instance(Uuid::new_v4());
+        let target = Uuid::new_v4();
+
+        let plan = plan_rag_instance_update(Some(&current), None, &request(Some(target)), 0);
+
+        assert!(plan.should_reset_state);
+        assert_eq!(plan.embedding_migration_target, None);
+        assert_eq!(plan.embedding_model_update, Some(target));
+    }
+
+    #[test]
+    fn model_change_with_other_changes_resets() {
+        let current = instance(Uuid::new_v4());
+        let target = Uuid::new_v4();
+        let mut update = request(Some(target));
+        update.engine_type = Some(RAGEngineType::RagSimpleGraph);
+
+        let plan = plan_rag_instance_update(Some(&current), None, &update, 12);
+
+        assert!(plan.should_reset_state);
+        assert_eq!(plan.embedding_migration_target, None);
+        assert_eq!(plan.embedding_model_update, Some(target));
+    }
+
+    #[test]
+    fn resending_migration_target_keeps_migration() {
+        let current = instance(Uuid::new_v4());
+        let target = Uuid::new_v4();
+        let active = migration(&current, target);
+
+        let plan =
+            plan_rag_instance_update(Some(&current), Some(&active), &request(Some(target)), 12);
+
+        assert!(!plan.cancels_active_migration);
+        assert_eq!(plan.embedding_migration_target, None);
+        assert_eq!(plan.embedding_model_update, None);
+    }
+
+    #[test]
+    fn resending_current_model_keeps_migration() {
+        let current = instance(Uuid::new_v4());
+        let active = migration(&current, Uuid::new_v4());
+
+        let plan = plan_rag_instance_update(
+            Some(&current),
+            Some(&active),
+            &request(current.embedding_model_id),
+            12,
+        );
+
+        assert!(!plan.cancels_active_migration);
+        assert_eq!(plan.embedding_model_update, None);
+    }
+
+    #[test]
+    fn another_model_supersedes_migration() {
+        let current = instance(Uuid::new_v4());
+        let active = migration(&current, Uuid::new_v4());
+        let other = Uuid::new_v4();
+
+        let plan =
+            plan_rag_instance_update(Some(&current), Some(&active), &request(Some(other)), 12);
+
+        assert!(plan.cancels_active_migration);
+        assert_eq!(plan.embedding_migration_target, Some(other));
+    }
+
+    #[test]
+    fn reset_supersedes_migration() {
+        let current = instance(Uuid::new_v4());
+        let active = migration(&current, Uuid::new_v4());
+        let mut update = request(None);
+        update.llm_model_id = Some(Uuid::new_v4());
+
+        let plan = plan_rag_instance_update(Some(&current), Some(&active), &update, 12);
+
+        assert!(plan.should_reset_state);
+        assert!(plan.cancels_active_migration);
+    }
+
+    #[test]
+    fn missing_instance_changes_nothing() {
+        let plan = plan_rag_instance_update(None, None, &request(Some(Uuid::new_v4())), 0);
+
+        assert!(!plan.should_reset_state);
+        assert!(!plan.cancels_active_migration);
+        assert_eq!(plan.embedding_migration_target, None);
+    }
+}
@@ -0,0 +1,346 @@
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+use uuid::Uuid;
+
+use crate::database::{
+    get_database_pool,
+    models::{
+        GroupUsageSummary, NewTokenUsage, TokenUsageSource, UsageQuota, UsageQuotaRequest,
+        UsageQuotaScope, UsageQuotaStatus, UsageTotals, UserUsageSummary,
+    },
+};
+
+const TOTALS_COLUMNS: &str = r#"COUNT(tu.id) AS requests,
+       COALESCE(SUM(tu.prompt_tokens), 0)::BIGINT AS prompt_tokens,
+       COALESCE(SUM(tu.completion_tokens), 0)::BIGINT AS completion_tokens,
+       COALESCE(SUM(tu.total_tokens), 0)::BIGINT AS total_tokens"#;
+
+fn totals_from_row(row: &sqlx::postgres::PgRow) -> Result<UsageTotals, sqlx::Error> {
+    Ok(UsageTotals {
+        requests: row.try_get("requests")?,
+        prompt_tokens: row.try_get("prompt_tokens")?,
+        completion_tokens: row.try_get("completion_tokens")?,
+        total_tokens: row.try_get("total_tokens")?,
+    })
+}
+
+fn to_i32(count: Option<u32>) -> i32 {
+    count.map_or(0, |c| i32::try_from(c).unwrap_or(i32::MAX))
+}
+
+/// Record the token usage of one model call
+pub async fn record_token_usage(entry: NewTokenUsage) -> Result<(), sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    let usage = entry.usage.unwrap_or_default();
+    let prompt_tokens = to_i32(usage.prompt_tokens);
+    let completion_tokens = to_i32(usage.completion_tokens);
+    let total_tokens = usage
+        .total_tokens
+        .map(|t| to_i32(Some(t)))
+        .unwrap_or_else(|| prompt_tokens.saturating_add(completion_tokens));
+
+    sqlx::query(
+        r#"INSERT INTO token_usage (
+               source, user_id, message_id, model_id, provider_id,
+               prompt_tokens, completion_tokens, total_tokens
+           )
+           VALUES ($1, $2, $3, $4, $5, $6, $7, $8)"#,
+    )
+    .bind(entry.source.as_str())
+    .bind(entry.user_id)
+    .bind(entry.message_id)
+    .bind(entry.model_id)
+    .bind(entry.provider_id)
+    .bind(prompt_tokens)
+    .bind(completion_tokens)
+    .bind(total_tokens)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Usage of a user since the given time
+pub async fn get_user_usage_totals(
+    user_id: Uuid,
+    since: DateTime<Utc>,
+) -> Result<UsageTotals, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    let row = sqlx::query(&format!(
+        "SELECT {} FROM token_usage tu WHERE tu.user_id = $1 AND tu.created_at >= $2",
+        TOTALS_COLUMNS
+    ))
+    .bind(user_id)
+    .bind(since)
+    .fetch_one(pool)
+    .await?;
+
+    totals_from_row(&row)
+}
+
+/// Combined usage of a group's current members since the given time
+pub async fn get_group_usage_totals(
+    group_id: Uuid,
+    since: DateTime<Utc>,
+) -> Result<UsageTotals, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    let row = sqlx::query(&format!(
+        r#"SELECT {} FROM token_usage tu
+           JOIN user_group_memberships ugm ON ugm.user_id = tu.user_id
+           WHERE ugm.group_id = $1 AND tu.created_at >= $2"#,
+        TOTALS_COLUMNS
+    ))
+    .bind(group_id)
+    .bind(since)
+    .fetch_one(pool)
+    .await?;
+
+    totals_from_row(&row)
+}
+
+/// Usage of the API proxy server since the given time
+pub async fn get_api_proxy_usage_totals(
+    since: DateTime<Utc>,
+) -> Result<UsageTotals, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    let row = sqlx::query(&format!(
+        "SELECT {} FROM token_usage tu WHERE tu.source = $1 AND tu.created_at >= $2",
+        TOTALS_COLUMNS
+    ))
+    .bind(TokenUsageSource::ApiProxy.as_str())
+    .bind(since)
+    .fetch_one(pool)
+    .await?;
+
+    totals_from_row(&row)
+}
+
+/// Usage of every user with usage since the given time, heaviest first
+pub async fn list_user_usage(since: DateTime<Utc>) -> Result<Vec<UserUsageSummary>, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    let rows = sqlx::query(&format!(
+        r#"SELECT u.id AS user_id, u.username, {} FROM token_usage tu
+           JOIN users u ON u.id = tu.user_id
+           WHERE tu.created_at >= $1
+           GROUP BY u.id, u.username
+           ORDER BY total_tokens DESC, requests DESC"#,
+        TOTALS_COLUMNS
+    ))
+    .bind(since)
+    .fetch_all(pool)
+    .await?;
+
+    rows.iter()
+        .map(|row| {
+            Ok(UserUsageSummary {
+                user_id: row.try_get("user_id")?,
+                username: row.try_get("username")?,
+                usage: totals_from_row(row)?,
+            })
+        })
+        .collect()
+}
+
+/// Combined usage of every group's current members since the given time, heaviest first
+pub async fn list_group_usage(
+    since: DateTime<Utc>,
+) -> Result<Vec<GroupUsageSummary>, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    let rows = sqlx::query(&format!(
+        r#"SELECT ug.id AS group_id, ug.name, {} FROM user_groups ug
+           LEFT JOIN user_group_memberships ugm ON ugm.group_id = ug.id
+           LEFT JOIN token_usage tu ON tu.user_id = ugm.user_id AND tu.created_at >= $1
+           GROUP BY ug.id, ug.name
+           ORDER BY total_tokens DESC, requests DESC, ug.name ASC"#,
+        TOTALS_COLUMNS
+    ))
+    .bind(since)
+    .fetch_all(pool)
+    .await?;
+
+    rows.iter()
+        .map(|row| {
+            Ok(GroupUsageSummary {
+                group_id: row.try_get("group_id")?,
+                name: row.try_get("name")?,
+                usage: totals_from_row(row)?,
+            })
+        })
+        .collect()
+}
+
+/// List all usage quotas
+pub async fn list_usage_quotas() -> Result<Vec<UsageQuota>, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    sqlx::query_as::<_, UsageQuota>("SELECT * FROM usage_quotas ORDER BY scope, created_at ASC")
+        .fetch_all(pool)
+        .await
+}
+
+/// Create a usage quota
+pub async fn create_usage_quota(request: UsageQuotaRequest) -> Result<UsageQuota, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    sqlx::query_as::<_, UsageQuota>(
+        r#"INSERT INTO usage_quotas (
+               scope, user_id, group_id, period, max_tokens, max_requests, enabled
+           )
+           VALUES ($1, $2, $3, $4, $5, $6, $7)
+           RETURNING *"#,
+    )
+    .bind(request.scope.as_str())
+    .bind(request.user_id)
+    .bind(request.group_id)
+    .bind(request.period.as_str())
+    .bind(request.max_tokens)
+    .bind(request.max_requests)
+    .bind(request.enabled.unwrap_or(true))
+    .fetch_one(pool)
+    .await
+}
+
+/// Replace a usage quota
+pub async fn update_usage_quota(
+    quota_id: Uuid,
+    request: UsageQuotaRequest,
+) -> Result<Option<UsageQuota>, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    sqlx::query_as::<_, UsageQuota>(
+        r#"UPDATE usage_quotas
+           SET scope = $2,
+               user_id = $3,
+               group_id = $4,
+               period = $5,
+               max_tokens = $6,
+               max_requests = $7,
+               enabled = $8
+           WHERE id = $1
+           RETURNING *"#,
+    )
+    .bind(quota_id)
+    .bind(request.scope.as_str())
+    .bind(request.user_id)
+    .bind(request.group_id)
+    .bind(request.period.as_str())
+    .bind(request.max_tokens)
+    .bind(request.max_requests)
+    .bind(request.enabled.unwrap_or(true))
+    .fetch_optional(pool)
+    .await
+}
+
+/// Delete a usage quota
+pub async fn delete_usage_quota(quota_id: Uuid) -> Result<bool, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    let result = sqlx::query("DELETE FROM usage_quotas WHERE id = $1")
+        .bind(quota_id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Count a quota's usage in its current period
+async fn get_quota_status(quota: UsageQuota) -> Result<UsageQuotaStatus, sqlx::Error> {
+    let now = Utc::now();
+    let period_start = quota.period.start(now);
+
+    let usage = match quota.scope {
+        UsageQuotaScope::User => match quota.user_id {
+            Some(user_id) => get_user_usage_totals(user_id, period_start).await?,
+            None => UsageTotals::default(),
+        },
+        UsageQuotaScope::Group => match quota.group_id {
+            Some(group_id) => get_group_usage_totals(group_id, period_start).await?,
+            None => UsageTotals::default(),
+        },
+        UsageQuotaScope::ApiProxy => get_api_proxy_usage_totals(period_start).await?,
+    };
+
+    Ok(UsageQuotaStatus {
+        reached: quota.is_reached_by(&usage),
+        resets_at: quota.period.next_start(now),
+        period_start,
+        usage,
+        quota,
+    })
+}
+
+/// Enabled quotas applying to a user: their own and those of their active groups, with
+/// their current usage
+pub async fn get_user_quota_statuses(user_id: Uuid) -> Result<Vec<UsageQuotaStatus>, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    let quotas = sqlx::query_as::<_, UsageQuota>(
+        r#"SELECT q.* FROM usage_quotas q
+           WHERE q.enabled = true
+             AND (
+               (q.scope = 'user' AND q.user_id = $1)
+               OR (q.scope = 'group' AND q.group_id IN (
+                   SELECT ugm.group_id FROM user_group_memberships ugm
+                   JOIN user_groups ug ON ug.id = ugm.group_id
+                   WHERE ugm.user_id = $1 AND ug.is_active = true
+               ))
+             )
+           ORDER BY q.scope DESC, q.created_at ASC"#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    let mut statuses = Vec::with_capacity(quotas.len());
+    for quota in quotas {
+        statuses.push(get_quota_status(quota).await?);
+    }
+    Ok(statuses)
+}
+
+/// First quota blocking further requests from a user, if any
+pub async fn find_reached_user_quota(
+    user_id: Uuid,
+) -> Result<Option<UsageQuotaStatus>, sqlx::Error> {
+    Ok(get_user_quota_statuses(user_id)
+        .await?
+        .into_iter()
+        .find(|status| status.reached))
+}
+
+/// First quota blocking further requests to the API proxy server, if any
+pub async fn find_reached_api_proxy_quota() -> Result<Option<UsageQuotaStatus>, sqlx::Error> {
+    let pool = get_database_pool()?;
+    let pool = pool.as_ref();
+
+    let quotas = sqlx::query_as::<_, UsageQuota>(
+        "SELECT * FROM usage_quotas WHERE enabled = true AND scope = 'api_proxy' ORDER BY created_at ASC",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    for quota in quotas {
+        let status = get_quota_status(quota).await?;
+        if status.reached {
+            return Ok(Some(status));
+        }
+    }
+    Ok(None)
+}
@@ -1,9 +1,11 @@
-use crate::api::mcp::{servers, tools, execution};
+use crate::api::mcp::{approval_rules, servers, tools, execution};
 use crate::database::models::mcp_server::MCPServer;
+use crate::database::models::mcp_server_config::{ImportMCPServersResponse, MCPServersConfig};
+use crate::database::models::{ApprovalRuleEvaluation, MCPToolApprovalRule};
 use crate::database::models::user_group_mcp_server::GroupServerAssignmentResponse;
 use servers::GroupAssignmentResponse;
 use aide::axum::{
-    routing::{delete_with, get_with, post_with},
+    routing::{delete_with, get_with, post_with, put_with},
     ApiRouter,
 };
 use axum::{middleware, Json};
@@ -37,6 +39,30 @@ pub fn admin_mcp_routes() -> ApiRouter {
                 crate::api::middleware::permissions::mcp_admin_servers_create_middleware,
             )),
         )
+        .api_route(
+            "/system-servers/import",
+            post_with(servers::import_system_servers, |op| {
+                op.description("Import system MCP servers from an mcpServers config (admin only)")
+                    .id("AdminMcp.importSystemServers")
+                    .tag("mcp-admin")
+                    .response::<200, Json<ImportMCPServersResponse>>()
+            })
+            .layer(middleware::from_fn(
+                crate::api::middleware::permissions::mcp_admin_servers_create_middleware,
+            )),
+        )
+        .api_route(
+            "/system-servers/export",
+            get_with(servers::export_system_servers, |op| {
+                op.description("Export system MCP servers as an mcpServers config (admin only)")
+                    .id("AdminMcp.exportSystemServers")
+                    .tag("mcp-admin")
+                    .response::<200, Json<MCPServersConfig>>()
+            })
+            .layer(middleware::from_fn(
+                crate::api::middleware::permissions::mcp_admin_servers_read_middleware,
+            )),
+        )
         // Group assignment (admin only)
         .api_route(
             "/groups/{group_id}/servers",
@@ -138,4 +164,77 @@ pub fn admin_mcp_routes() -> ApiRouter {
                 crate::api::middleware::permissions::mcp_admin_servers_read_middleware,
             )),
         )
+        // Tool approval rules (admin only)
+        .api_route(
+            "/approval-rules",
+            get_with(approval_rules::list_approval_rules, |op| {
+                op.description("List MCP tool approval rules in evaluation order (admin only)")
+                    .id("AdminMcp.listApprovalRules")
+                    .tag("mcp-admin")
+                    .response::<200, Json<Vec<MCPToolApprovalRule>>>()
+            })
+            .layer(middleware::from_fn(
+                crate::api::middleware::permissions::mcp_admin_servers_read_middleware,
+            )),
+        )
+        .api_route(
+            "/approval-rules",
+            post_with(approval_rules::create_approval_rule, |op| {
+                op.description("Create MCP tool approval rule (admin only)")
+                    .id("AdminMcp.createApprovalRule")
+                    .tag("mcp-admin")
+                    .response::<201, Json<MCPToolApprovalRule>>()
+            })
+            .layer(middleware::from_fn(
+                crate::api::middleware::permissions::mcp_admin_servers_create_middleware,
+            )),
+        )
+        .api_route(
+            "/approval-rules/dry-run",
+            post_with(approval_rules::dry_run_approval_rules, |op| {
+                op.description("Explain which approval rule decides a tool call, without running it (admin only)")
+                    .id("AdminMcp.dryRunApprovalRules")
+                    .tag("mcp-admin")
+                    .response::<200, Json<ApprovalRuleEvaluation>>()
+            })
+            .layer(middleware::from_fn(
+                crate::api::middleware::permissions::mcp_admin_servers_read_middleware,
+            )),
+        )
+        .api_route(
+            "/approval-rules/{rule_id}",
+            get_with(approval_rules::get_approval_rule, |op| {
+                op.description("Get MCP tool approval rule (admin only)")
+                    .id("AdminMcp.getApprovalRule")
+                    .tag("mcp-admin")
+                    .response::<200, Json<MCPToolApprovalRule>>()
+            })
+            .layer(middleware::from_fn(
+                crate::api::middleware::permissions::mcp_admin_servers_read_middleware,
+            )),
+        )
+        .api_route(
+            "/approval-rules/{rule_id}",
+            put_with(approval_rules::update_approval_rule, |op| {
+                op.description("Replace MCP tool approval rule (admin only)")
+                    .id("AdminMcp.updateApprovalRule")
+                    .tag("mcp-admin")
+                    .response::<200, Json<MCPToolApprovalRule>>()
+            })
+            .layer(middleware::from_fn(
+                crate::api::middleware::permissions::mcp_admin_servers_edit_middleware,
+            )),
+        )
+        .api_route(
+            "/approval-rules/{rule_id}",
+            delete_with(approval_rules::delete_approval_rule, |op| {
+                op.description("Delete MCP tool approval rule (admin only)")
+                    .id("AdminMcp.deleteApprovalRule")
+                    .tag("mcp-admin")
+                    .response::<204, ()>()
+            })
+            .layer(middleware::from_fn(
+                crate::api::middleware::permissions::mcp_admin_servers_delete_middleware,
+            )),
+        )
 }
\ No newline at end of file
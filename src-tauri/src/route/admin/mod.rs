@@ -6,10 +6,12 @@ pub mod engines;
 pub mod groups;
 pub mod hardware;
 pub mod mcp;
+pub mod model_groups;
 pub mod models;
 pub mod providers;
 pub mod rag;
 pub mod repositories;
+pub mod usage;
 pub mod users;
 
 use aide::axum::ApiRouter;
@@ -20,9 +22,11 @@ pub fn admin_routes() -> ApiRouter {
         ApiRouter::new()
             .merge(users::admin_user_routes())
             .merge(groups::admin_group_routes())
+            .merge(usage::admin_usage_routes())
             .merge(config::admin_config_routes())
             .merge(providers::admin_provider_routes())
             .merge(models::admin_model_routes())
+            .merge(model_groups::admin_model_group_routes())
             .merge(repositories::admin_repository_routes())
             .nest("/rag", rag::admin_rag_routes())
             .merge(assistants::admin_assistant_routes())
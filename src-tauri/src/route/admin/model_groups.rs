@@ -0,0 +1,72 @@
+use crate::api;
+use crate::database::models::ModelGroup;
+use aide::axum::{
+    routing::{delete_with, get_with, post_with, put_with},
+    ApiRouter,
+};
+use axum::{middleware, Json};
+
+pub fn admin_model_group_routes() -> ApiRouter {
+    ApiRouter::new()
+        // Model groups: logical models with failover across providers
+        .api_route(
+            "/model-groups",
+            get_with(api::model_groups::list_model_groups, |op| {
+                op.description("List all model groups")
+                    .id("Admin.listModelGroups")
+                    .tag("admin")
+                    .response::<200, Json<Vec<ModelGroup>>>()
+            })
+            .layer(middleware::from_fn(
+                api::middleware::providers_read_middleware,
+            )),
+        )
+        .api_route(
+            "/model-groups",
+            post_with(api::model_groups::create_model_group, |op| {
+                op.description("Create a new model group")
+                    .id("Admin.createModelGroup")
+                    .tag("admin")
+                    .response::<200, Json<ModelGroup>>()
+            })
+            .layer(middleware::from_fn(
+                api::middleware::providers_create_middleware,
+            )),
+        )
+        .api_route(
+            "/model-groups/{group_id}",
+            get_with(api::model_groups::get_model_group, |op| {
+                op.description("Get a specific model group")
+                    .id("Admin.getModelGroup")
+                    .tag("admin")
+                    .response::<200, Json<ModelGroup>>()
+            })
+            .layer(middleware::from_fn(
+                api::middleware::providers_read_middleware,
+            )),
+        )
+        .api_route(
+            "/model-groups/{group_id}",
+            put_with(api::model_groups::update_model_group, |op| {
+                op.description("Update a model group")
+                    .id("Admin.updateModelGroup")
+                    .tag("admin")
+                    .response::<200, Json<ModelGroup>>()
+            })
+            .layer(middleware::from_fn(
+                api::middleware::providers_edit_middleware,
+            )),
+        )
+        .api_route(
+            "/model-groups/{group_id}",
+            delete_with(api::model_groups::delete_model_group, |op| {
+                op.description("Delete a model group")
+                    .id("Admin.deleteModelGroup")
+                    .tag("admin")
+                    .response::<204, ()>()
+            })
+            .layer(middleware::from_fn(
+                api::middleware::providers_delete_middleware,
+            )),
+        )
+}
@@ -21,6 +21,18 @@ pub fn admin_model_routes() -> ApiRouter {
                 api::middleware::models_create_middleware,
             )),
         )
+        .api_route(
+            "/providers/{provider_id}/models/sync",
+            post_with(api::models::sync_provider_models, |op| {
+                op.description("Add the models served by a provider that are not listed yet")
+                    .id("Admin.syncProviderModels")
+                    .tag("admin")
+                    .response::<200, Json<Vec<Model>>>()
+            })
+            .layer(middleware::from_fn(
+                api::middleware::models_create_middleware,
+            )),
+        )
         .api_route(
             "/models/{model_id}",
             get_with(api::models::get_model, |op| {
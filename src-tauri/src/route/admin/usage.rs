@@ -0,0 +1,63 @@
+use crate::api;
+use crate::database::models::{UsageQuota, UsageReport};
+use aide::axum::{
+    routing::{delete_with, get_with, post_with, put_with},
+    ApiRouter,
+};
+use axum::{middleware, Json};
+
+pub fn admin_usage_routes() -> ApiRouter {
+    ApiRouter::new()
+        // Token usage reports
+        .api_route(
+            "/usage",
+            get_with(api::usage::get_usage_report, |op| {
+                op.description("Get token usage per user and group for the current day or month")
+                    .id("Admin.getUsageReport")
+                    .tag("admin")
+                    .response::<200, Json<UsageReport>>()
+            })
+            .layer(middleware::from_fn(api::middleware::groups_read_middleware)),
+        )
+        // Usage quota management
+        .api_route(
+            "/usage-quotas",
+            get_with(api::usage::list_usage_quotas, |op| {
+                op.description("List usage quotas")
+                    .id("Admin.listUsageQuotas")
+                    .tag("admin")
+                    .response::<200, Json<Vec<UsageQuota>>>()
+            })
+            .layer(middleware::from_fn(api::middleware::groups_read_middleware)),
+        )
+        .api_route(
+            "/usage-quotas",
+            post_with(api::usage::create_usage_quota, |op| {
+                op.description("Create a usage quota")
+                    .id("Admin.createUsageQuota")
+                    .tag("admin")
+                    .response::<201, Json<UsageQuota>>()
+            })
+            .layer(middleware::from_fn(api::middleware::groups_edit_middleware)),
+        )
+        .api_route(
+            "/usage-quotas/{quota_id}",
+            put_with(api::usage::update_usage_quota, |op| {
+                op.description("Replace a usage quota")
+                    .id("Admin.updateUsageQuota")
+                    .tag("admin")
+                    .response::<200, Json<UsageQuota>>()
+            })
+            .layer(middleware::from_fn(api::middleware::groups_edit_middleware)),
+        )
+        .api_route(
+            "/usage-quotas/{quota_id}",
+            delete_with(api::usage::delete_usage_quota, |op| {
+                op.description("Delete a usage quota")
+                    .id("Admin.deleteUsageQuota")
+                    .tag("admin")
+                    .response::<204, ()>()
+            })
+            .layer(middleware::from_fn(api::middleware::groups_edit_middleware)),
+        )
+}
@@ -1,18 +1,35 @@
-use crate::api::mcp::{approvals, servers, tools, execution, logs};
+use crate::api::mcp::{approvals, servers, tools, execution, logs, oauth, prompts, resources};
+use crate::database::models::mcp_oauth::{MCPOAuthStatus, StartMCPOAuthResponse};
 use crate::database::models::mcp_server::MCPServer;
+use crate::database::models::mcp_server_config::{ImportMCPServersResponse, MCPServersConfig};
+use crate::database::models::mcp_prompt::{MCPPrompt, MCPPromptResult};
+use crate::database::models::mcp_resource::{MCPResource, MCPResourceContent};
 use crate::database::models::mcp_tool::{MCPTool, MCPToolWithServer, ToolExecutionResponse};
 use servers::ServerActionResponse;
 use aide::axum::{
     routing::{delete_with, get_with, post_with, put_with},
     ApiRouter,
 };
-use axum::{middleware, Json};
+use axum::{middleware, response::Html, Json};
 
 pub fn mcp_routes() -> ApiRouter {
     ApiRouter::new()
         .nest("/mcp", all_mcp_routes())
 }
 
+/// MCP routes reached without authentication (OAuth redirects from authorization servers)
+pub fn mcp_public_routes() -> ApiRouter {
+    ApiRouter::new().api_route(
+        "/mcp/oauth/callback",
+        get_with(oauth::oauth_callback, |op| {
+            op.description("Redirect endpoint completing the OAuth authorization of an MCP server")
+                .id("Mcp.oauthCallback")
+                .tag("mcp")
+                .response::<200, Html<String>>()
+        }),
+    )
+}
+
 /// All MCP routes (user + admin operations)
 fn all_mcp_routes() -> ApiRouter {
     ApiRouter::new()
@@ -41,6 +58,30 @@ fn all_mcp_routes() -> ApiRouter {
                 crate::api::middleware::permissions::mcp_servers_create_middleware,
             )),
         )
+        .api_route(
+            "/servers/import",
+            post_with(servers::import_user_servers, |op| {
+                op.description("Import user MCP servers from an mcpServers config")
+                    .id("Mcp.importServers")
+                    .tag("mcp")
+                    .response::<200, Json<ImportMCPServersResponse>>()
+            })
+            .layer(middleware::from_fn(
+                crate::api::middleware::permissions::mcp_servers_create_middleware,
+            )),
+        )
+        .api_route(
+            "/servers/export",
+            get_with(servers::export_user_servers, |op| {
+                op.description("Export user's own MCP servers as an mcpServers config")
+                    .id("Mcp.exportServers")
+                    .tag("mcp")
+                    .response::<200, Json<MCPServersConfig>>()
+            })
+            .layer(middleware::from_fn(
+                crate::api::middleware::permissions::mcp_servers_read_middleware,
+            )),
+        )
         .api_route(
             "/servers/{id}",
             get_with(servers::get_server, |op| {
@@ -102,6 +143,43 @@ fn all_mcp_routes() -> ApiRouter {
                 crate::api::middleware::permissions::mcp_servers_edit_middleware,
             )),
         )
+        // Per-user OAuth authorization of remote servers
+        .api_route(
+            "/servers/{id}/oauth",
+            get_with(oauth::get_server_authorization, |op| {
+                op.description("Get whether the current user has authorized an MCP server")
+                    .id("Mcp.getServerAuthorization")
+                    .tag("mcp")
+                    .response::<200, Json<MCPOAuthStatus>>()
+            })
+            .layer(middleware::from_fn(
+                crate::api::middleware::permissions::mcp_servers_read_middleware,
+            )),
+        )
+        .api_route(
+            "/servers/{id}/oauth",
+            delete_with(oauth::revoke_server_authorization, |op| {
+                op.description("Forget the current user's OAuth token for an MCP server")
+                    .id("Mcp.revokeServerAuthorization")
+                    .tag("mcp")
+                    .response::<204, ()>()
+            })
+            .layer(middleware::from_fn(
+                crate::api::middleware::permissions::mcp_tools_execute_middleware,
+            )),
+        )
+        .api_route(
+            "/servers/{id}/oauth/authorize",
+            post_with(oauth::start_server_authorization, |op| {
+                op.description("Start the OAuth authorization of an MCP server for the current user")
+                    .id("Mcp.startServerAuthorization")
+                    .tag("mcp")
+                    .response::<200, Json<StartMCPOAuthResponse>>()
+            })
+            .layer(middleware::from_fn(
+                crate::api::middleware::permissions::mcp_tools_execute_middleware,
+            )),
+        )
         // Tool management
         .api_route(
             "/tools",
@@ -127,6 +205,55 @@ fn all_mcp_routes() -> ApiRouter {
                 crate::api::middleware::permissions::mcp_tools_read_middleware,
             )),
         )
+        // Server prompts and resources
+        .api_route(
+            "/servers/{id}/prompts",
+            get_with(prompts::get_server_prompts, |op| {
+                op.description("Get prompts for specific server")
+                    .id("Mcp.getServerPrompts")
+                    .tag("mcp")
+                    .response::<200, Json<Vec<MCPPrompt>>>()
+            })
+            .layer(middleware::from_fn(
+                crate::api::middleware::permissions::mcp_tools_read_middleware,
+            )),
+        )
+        .api_route(
+            "/servers/{id}/prompts/{prompt_name}",
+            post_with(prompts::get_server_prompt, |op| {
+                op.description("Get a prompt's messages with the given arguments")
+                    .id("Mcp.getServerPrompt")
+                    .tag("mcp")
+                    .response::<200, Json<MCPPromptResult>>()
+            })
+            .layer(middleware::from_fn(
+                crate::api::middleware::permissions::mcp_tools_read_middleware,
+            )),
+        )
+        .api_route(
+            "/servers/{id}/resources",
+            get_with(resources::get_server_resources, |op| {
+                op.description("Get resources for specific server")
+                    .id("Mcp.getServerResources")
+                    .tag("mcp")
+                    .response::<200, Json<Vec<MCPResource>>>()
+            })
+            .layer(middleware::from_fn(
+                crate::api::middleware::permissions::mcp_tools_read_middleware,
+            )),
+        )
+        .api_route(
+            "/servers/{id}/resources/read",
+            get_with(resources::read_server_resource, |op| {
+                op.description("Read the contents of a server resource")
+                    .id("Mcp.readServerResource")
+                    .tag("mcp")
+                    .response::<200, Json<Vec<MCPResourceContent>>>()
+            })
+            .layer(middleware::from_fn(
+                crate::api::middleware::permissions::mcp_tools_read_middleware,
+            )),
+        )
         // Tool execution
         .api_route(
             "/tools/find",
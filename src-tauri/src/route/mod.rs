@@ -30,6 +30,7 @@ pub fn create_rest_router_internal() -> (OpenApi, Router) {
         .merge(auth::auth_routes())
         .merge(config::config_routes())
         .merge(utils::utils_routes())
+        .merge(mcp::mcp_public_routes())
         // Protected API routes requiring authentication
         .merge(
             ApiRouter::new()
@@ -2,8 +2,8 @@ use crate::api::files::FileOperationSuccessResponse;
 use crate::api::rag::{files, instances};
 use crate::api::rag::instances::{RAGQueryResponse, SSERAGStatusEvent};
 use crate::database::models::{
-    file::UploadFileResponse, RAGInstance, RAGInstanceFilesListResponse, RAGInstanceListResponse,
-    RAGProvider,
+    file::UploadFileResponse, RAGInstance, RAGInstanceFile, RAGInstanceFilesListResponse,
+    RAGInstanceListResponse, RAGProvider,
 };
 use aide::axum::{
     routing::{delete_with, get_with, post_with, put_with},
@@ -114,6 +114,19 @@ fn user_rag_routes() -> ApiRouter {
                 crate::api::middleware::permissions::rag_instances_read_middleware,
             )),
         )
+        // Cancel re-embedding after an embedding model change
+        .api_route(
+            "/instances/{instance_id}/embedding-migration",
+            delete_with(instances::cancel_rag_embedding_migration_handler, |op| {
+                op.description("Cancel the embedding migration of a RAG instance")
+                    .id("Rag.cancelEmbeddingMigration")
+                    .tag("rag")
+                    .response::<204, ()>()
+            })
+            .layer(middleware::from_fn(
+                crate::api::middleware::permissions::rag_instances_edit_middleware,
+            )),
+        )
         // .api_route(
         //     "/instances/{instance_id}/status",
         //     get_with(instances::get_instance_processing_status, |op| {
@@ -156,6 +169,18 @@ fn user_rag_routes() -> ApiRouter {
                 crate::api::middleware::files_delete_middleware,
             )),
         )
+        .api_route(
+            "/instances/{instance_id}/files/{file_id}/tags",
+            put_with(files::update_rag_file_tags_handler, |op| {
+                op.description("Replace the tags of a file in RAG instance")
+                    .id("Rag.updateInstanceFileTags")
+                    .tag("rag")
+                    .response::<200, Json<RAGInstanceFile>>()
+            })
+            .layer(middleware::from_fn(
+                crate::api::middleware::permissions::rag_instances_edit_middleware,
+            )),
+        )
         // Query RAG instance for testing purposes
         .api_route(
             "/instances/{instance_id}/query",
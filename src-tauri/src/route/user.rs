@@ -2,7 +2,7 @@ use crate::api;
 use crate::api::user_settings::UserSettingsDeletionResponse;
 use crate::database::models::{
     Assistant, AssistantListResponse, Model, ProviderListResponse, UserSetting,
-    UserSettingsResponse,
+    UserSettingsResponse, UserUsageResponse,
 };
 use aide::axum::{
     routing::{delete_with, get_with, post_with, put_with},
@@ -21,6 +21,15 @@ pub fn user_routes() -> ApiRouter {
                     .response::<200, Json<String>>()
             }),
         )
+        .api_route(
+            "/user/usage",
+            get_with(api::usage::get_my_usage, |op| {
+                op.description("Get the current user's token usage and quotas")
+                    .id("User.getUsage")
+                    .tag("user")
+                    .response::<200, Json<UserUsageResponse>>()
+            }),
+        )
         // User settings routes
         .api_route(
             "/user/settings",
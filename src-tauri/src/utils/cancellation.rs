@@ -36,7 +36,7 @@ impl CancellationToken {
     }
 }
 
-/// Global cancellation tracker for managing download cancellations
+/// Cancellation tracker for operations keyed by ID, such as downloads
 pub struct CancellationTracker {
     cancellation_senders: RwLock<HashMap<Uuid, oneshot::Sender<()>>>,
 }
@@ -58,10 +58,10 @@ impl CancellationTracker {
         CancellationToken::new(download_id, receiver)
     }
 
-    /// Cancel a download by its ID
-    pub async fn cancel_download(&self, download_id: Uuid) -> bool {
+    /// Cancel an operation by its ID
+    pub async fn cancel(&self, id: Uuid) -> bool {
         let mut senders = self.cancellation_senders.write().await;
-        if let Some(sender) = senders.remove(&download_id) {
+        if let Some(sender) = senders.remove(&id) {
             // Send cancellation signal
             let _ = sender.send(());
             true
@@ -70,10 +70,20 @@ impl CancellationTracker {
         }
     }
 
+    /// Remove an operation from tracking (cleanup when it completes)
+    pub async fn remove(&self, id: Uuid) {
+        let mut senders = self.cancellation_senders.write().await;
+        senders.remove(&id);
+    }
+
+    /// Cancel a download by its ID
+    pub async fn cancel_download(&self, download_id: Uuid) -> bool {
+        self.cancel(download_id).await
+    }
+
     /// Remove a download from tracking (cleanup when download completes)
     pub async fn remove_download(&self, download_id: Uuid) {
-        let mut senders = self.cancellation_senders.write().await;
-        senders.remove(&download_id);
+        self.remove(download_id).await
     }
 }
 
@@ -125,8 +125,11 @@ export const ChatInput = function ChatInput({
     }> = []
 
     providers.forEach(provider => {
+      // Image generators reply to the last message with images
       const providerModels = (modelsByProvider[provider.id] || []).filter(
-        model => model.capabilities?.chat === true,
+        model =>
+          model.capabilities?.chat === true ||
+          model.capabilities?.image_generator === true,
       )
 
       if (providerModels.length > 0) {
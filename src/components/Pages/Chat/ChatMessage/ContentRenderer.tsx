@@ -1,11 +1,13 @@
 import { memo } from 'react'
 import { MessageContentItem } from '../../../../types'
 import { TextContent } from './TextContent'
+import { ThinkingContent } from './ThinkingContent'
 import { ToolCallContent } from './ToolCallContent'
 import { ToolCallPendingApprovalContent } from './ToolCallPendingApprovalContent'
 import { ToolCallPendingApprovalCancelContent } from './ToolCallPendingApprovalCancelContent'
 import { ToolResultContent } from './ToolResultContent'
 import { FileAttachmentContent } from './FileAttachmentContent'
+import { GeneratedImageContent } from './GeneratedImageContent'
 import { ErrorContent } from './ErrorContent'
 import { UnknownContent } from './UnknownContent'
 
@@ -22,6 +24,9 @@ export const ContentRenderer = memo(function ContentRenderer({
     case 'text':
       return <TextContent content={content} isUser={isUser} />
 
+    case 'thinking':
+      return <ThinkingContent content={content} />
+
     case 'tool_call':
       return <ToolCallContent content={content} />
 
@@ -37,6 +42,9 @@ export const ContentRenderer = memo(function ContentRenderer({
     case 'file_attachment':
       return <FileAttachmentContent content={content} />
 
+    case 'generated_image':
+      return <GeneratedImageContent content={content} />
+
     case 'error':
       return <ErrorContent content={content} />
 
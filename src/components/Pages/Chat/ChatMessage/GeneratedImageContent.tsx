@@ -0,0 +1,66 @@
+import { memo, useEffect, useState } from 'react'
+import { Image, Spin, theme, Typography } from 'antd'
+import {
+  MessageContentItem,
+  MessageContentDataGeneratedImage,
+} from '../../../../types'
+import { getFileImageUrl } from '../../../../store'
+
+interface GeneratedImageContentProps {
+  content: MessageContentItem
+}
+
+export const GeneratedImageContent = memo(function GeneratedImageContent({
+  content,
+}: GeneratedImageContentProps) {
+  const { token } = theme.useToken()
+  const imageData = content.content as MessageContentDataGeneratedImage
+  const [imageUrl, setImageUrl] = useState<string | null>(null)
+  const [failed, setFailed] = useState(false)
+
+  useEffect(() => {
+    let url: string | null = null
+    let cancelled = false
+
+    getFileImageUrl(imageData.file_id)
+      .then(objectUrl => {
+        url = objectUrl
+        if (cancelled) {
+          window.URL.revokeObjectURL(objectUrl)
+        } else {
+          setImageUrl(objectUrl)
+        }
+      })
+      .catch(() => {
+        if (!cancelled) setFailed(true)
+      })
+
+    return () => {
+      cancelled = true
+      if (url) window.URL.revokeObjectURL(url)
+    }
+  }, [imageData.file_id])
+
+  return (
+    <div className="flex flex-col gap-2">
+      {imageUrl ? (
+        <Image
+          src={imageUrl}
+          alt={imageData.revised_prompt || imageData.filename}
+          style={{ maxWidth: 512, borderRadius: token.borderRadiusLG }}
+        />
+      ) : failed ? (
+        <Typography.Text type="secondary">
+          Image unavailable: {imageData.filename}
+        </Typography.Text>
+      ) : (
+        <Spin size="small" />
+      )}
+      {imageData.revised_prompt && (
+        <Typography.Text type="secondary" style={{ fontSize: '12px' }}>
+          {imageData.revised_prompt}
+        </Typography.Text>
+      )}
+    </div>
+  )
+})
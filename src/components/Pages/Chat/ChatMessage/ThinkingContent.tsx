@@ -0,0 +1,56 @@
+import { memo, useState } from 'react'
+import { theme, Typography } from 'antd'
+import { BulbOutlined, DownOutlined, RightOutlined } from '@ant-design/icons'
+import {
+  MessageContentItem,
+  MessageContentDataThinking,
+} from '../../../../types'
+import { DivScrollY } from '../../../common/DivScrollY.tsx'
+
+interface ThinkingContentProps {
+  content: MessageContentItem
+}
+
+export const ThinkingContent = memo(function ThinkingContent({
+  content,
+}: ThinkingContentProps) {
+  const { token } = theme.useToken()
+  const thinkingData = content.content as MessageContentDataThinking
+  const [isCollapsed, setIsCollapsed] = useState(true)
+
+  if (!thinkingData.thinking) {
+    return null
+  }
+
+  return (
+    <div
+      className="rounded-lg p-3"
+      style={{
+        border: `1px solid ${token.colorBorderSecondary}`,
+        backgroundColor: token.colorFillQuaternary,
+      }}
+    >
+      <div
+        className="flex items-center gap-2 cursor-pointer"
+        onClick={() => setIsCollapsed(!isCollapsed)}
+      >
+        {isCollapsed ? <RightOutlined /> : <DownOutlined />}
+        <Typography.Text type="secondary">
+          <BulbOutlined /> Thinking
+        </Typography.Text>
+      </div>
+      {!isCollapsed && (
+        <DivScrollY
+          className="mt-2 max-h-80"
+          style={{
+            whiteSpace: 'pre-wrap',
+            color: token.colorTextSecondary,
+            fontSize: '13px',
+          }}
+        >
+          {thinkingData.thinking.trim()}
+        </DivScrollY>
+      )}
+    </div>
+  )
+})
@@ -1,11 +1,12 @@
 import { memo, useState } from 'react'
-import { theme, Typography } from 'antd'
+import { Progress, theme, Typography } from 'antd'
 import { DownOutlined, RightOutlined, ToolOutlined } from '@ant-design/icons'
 import {
   MessageContentDataToolCall,
   MessageContentItem,
 } from '../../../../types'
 import { DivScrollY } from '../../../common/DivScrollY.tsx'
+import { useChatStore } from '../../../../store'
 
 interface ToolCallContentProps {
   content: MessageContentItem
@@ -17,6 +18,8 @@ export const ToolCallContent = memo(function ToolCallContent({
   const { token } = theme.useToken()
   const toolCallData = content.content as MessageContentDataToolCall
   const [isCollapsed, setIsCollapsed] = useState(true)
+  const { toolProgress } = useChatStore()
+  const progress = toolProgress[toolCallData.call_id]
 
   return (
     <div
@@ -35,6 +38,26 @@ export const ToolCallContent = memo(function ToolCallContent({
           <ToolOutlined /> Tool Call: {toolCallData.tool_name}
         </Typography.Text>
       </div>
+      {progress && (
+        <div className="mt-2">
+          {progress.total ? (
+            <Progress
+              percent={Math.round((progress.progress / progress.total) * 100)}
+              size="small"
+              status="active"
+            />
+          ) : (
+            <Typography.Text type="secondary">
+              Progress: {progress.progress}
+            </Typography.Text>
+          )}
+          {progress.message && (
+            <Typography.Text type="secondary" className="block">
+              {progress.message}
+            </Typography.Text>
+          )}
+        </div>
+      )}
       {!isCollapsed && (
         <div className="mt-2">
           <Typography.Text type="secondary">Arguments:</Typography.Text>
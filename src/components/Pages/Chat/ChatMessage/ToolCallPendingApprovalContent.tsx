@@ -104,6 +104,17 @@ export const ToolCallPendingApprovalContent = memo(
 
         // Hide the component
         setIsHidden(true)
+
+        // Resume the chat - the other tool calls of this turn run once all are decided
+        if (conversation.model_id && conversation.assistant_id) {
+          await sendMessage({
+            content: '',
+            model_id: conversation.model_id,
+            assistant_id: conversation.assistant_id,
+            file_ids: [],
+            message_id: content.message_id,
+          })
+        }
       } catch (error) {
         console.error('Failed to deny tool:', error)
         message.error('Failed to deny tool')
@@ -1,9 +1,11 @@
 export { ChatMessage } from './ChatMessage'
 export { TextContent } from './TextContent'
+export { ThinkingContent } from './ThinkingContent'
 export { ToolCallContent } from './ToolCallContent'
 export { ToolCallPendingApprovalContent } from './ToolCallPendingApprovalContent'
 export { ToolCallPendingApprovalCancelContent } from './ToolCallPendingApprovalCancelContent'
 export { ToolResultContent } from './ToolResultContent'
 export { FileAttachmentContent } from './FileAttachmentContent'
+export { GeneratedImageContent } from './GeneratedImageContent'
 export { ErrorContent } from './ErrorContent'
 export { UnknownContent } from './UnknownContent'
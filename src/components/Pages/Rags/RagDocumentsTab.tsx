@@ -4,6 +4,7 @@ import {
   UploadOutlined,
   SearchOutlined,
   DeleteOutlined,
+  TagsOutlined,
 } from '@ant-design/icons'
 import {
   App,
@@ -19,7 +20,9 @@ import {
   Pagination,
   Checkbox,
   Badge,
+  Modal,
   Select,
+  Tag,
 } from 'antd'
 import React, { useEffect, useRef, useState } from 'react'
 import { useParams } from 'react-router-dom'
@@ -32,8 +35,10 @@ import {
   searchFiles,
   changePage,
   changePageSize,
+  updateRAGFileTags,
 } from '../../../store/ragInstance'
 import { Permission } from '../../../types'
+import type { RAGInstanceFile } from '../../../types/api'
 import { PermissionGuard } from '../../Auth/PermissionGuard.tsx'
 import { debounce } from '../../../utils/debounce.ts'
 import { DivScrollY } from '../../common/DivScrollY.tsx'
@@ -49,6 +54,11 @@ export const RagDocumentsTab: React.FC = () => {
   // Local state for search
   const [localSearchQuery, setLocalSearchQuery] = useState('')
 
+  // Local state for tag editing
+  const [tagEditFile, setTagEditFile] = useState<RAGInstanceFile | null>(null)
+  const [editingTags, setEditingTags] = useState<string[]>([])
+  const [savingTags, setSavingTags] = useState(false)
+
   // RAG instance store
   const {
     ragInstance,
@@ -149,6 +159,29 @@ export const RagDocumentsTab: React.FC = () => {
     })
   }
 
+  const getFileTags = (file: RAGInstanceFile): string[] =>
+    Array.isArray(file.rag_metadata?.tags) ? file.rag_metadata.tags : []
+
+  const handleEditTags = (file: RAGInstanceFile) => {
+    setTagEditFile(file)
+    setEditingTags(getFileTags(file))
+  }
+
+  const handleSaveTags = async () => {
+    if (!ragInstanceId || !tagEditFile) return
+
+    try {
+      setSavingTags(true)
+      await updateRAGFileTags(ragInstanceId, tagEditFile.file_id, editingTags)
+      setTagEditFile(null)
+    } catch (error) {
+      console.error('Failed to update file tags:', error)
+      message.error('Failed to update file tags')
+    } finally {
+      setSavingTags(false)
+    }
+  }
+
   const handleFileUpload = debounce(async (files: globalThis.File[]) => {
     if (!ragInstance || !ragInstanceId) return
 
@@ -377,6 +410,22 @@ export const RagDocumentsTab: React.FC = () => {
                     }
                   />
                   <Text className="flex-1 truncate">{file.filename}</Text>
+                  {getFileTags(file).map(tag => (
+                    <Tag key={tag} className="!m-0">
+                      {tag}
+                    </Tag>
+                  ))}
+                  <PermissionGuard
+                    permissions={[Permission.RagInstancesEdit]}
+                    type="disabled"
+                  >
+                    <Button
+                      type="text"
+                      size="small"
+                      icon={<TagsOutlined />}
+                      onClick={() => handleEditTags(file)}
+                    />
+                  </PermissionGuard>
                   <Badge
                     status={getProcessingStatusType(file.processing_status)}
                     text={getProcessingStatusText(file.processing_status)}
@@ -438,6 +487,25 @@ export const RagDocumentsTab: React.FC = () => {
           style={{ display: 'none' }}
           onChange={handleFileInputChange}
         />
+
+        {/* Tag editor */}
+        <Modal
+          title={`Tags for ${tagEditFile?.filename ?? ''}`}
+          open={!!tagEditFile}
+          onOk={handleSaveTags}
+          onCancel={() => setTagEditFile(null)}
+          confirmLoading={savingTags}
+          okText="Save"
+        >
+          <Select
+            mode="tags"
+            className="w-full"
+            placeholder="Add tags"
+            tokenSeparators={[',']}
+            value={editingTags}
+            onChange={setEditingTags}
+          />
+        </Modal>
       </div>
     </Card>
   )
@@ -4,6 +4,7 @@ import { useParams } from 'react-router-dom'
 import {
   subscribeToRAGStatus,
   disconnectRAGStatus,
+  cancelRAGEmbeddingMigration,
   Stores,
 } from '../../../store'
 import { DivScrollY } from '../../common/DivScrollY'
@@ -143,6 +144,61 @@ export const RagInstanceStatus: React.FC = () => {
     )
   }
 
+  const renderEmbeddingMigration = () => {
+    const migration = currentStatus?.embedding_migration
+    if (!migration) return null
+
+    const isActive =
+      migration.status === 'pending' || migration.status === 'running'
+    // Finished migrations stay visible only when they need attention
+    if (!isActive && migration.status !== 'failed') return null
+
+    const percent =
+      migration.total_documents > 0
+        ? (migration.processed_documents / migration.total_documents) * 100
+        : 0
+
+    const handleCancel = async () => {
+      try {
+        await cancelRAGEmbeddingMigration(ragInstanceId)
+        message.success('Re-embedding cancelled')
+      } catch (error) {
+        console.error('Failed to cancel re-embedding:', error)
+        message.error('Failed to cancel re-embedding')
+      }
+    }
+
+    return (
+      <Card
+        title="Re-embedding"
+        extra={
+          isActive && (
+            <Button size="small" danger onClick={handleCancel}>
+              Cancel
+            </Button>
+          )
+        }
+      >
+        <div className="space-y-2">
+          <Text type="secondary">
+            {isActive
+              ? 'Chunks are being re-embedded with the new embedding model. Queries use the previous model until this finishes.'
+              : `Re-embedding failed: ${migration.error_message ?? 'unknown error'}`}
+          </Text>
+          <Progress
+            percent={Number(percent.toFixed(1))}
+            status={isActive ? 'active' : 'exception'}
+            size="small"
+          />
+          <Text type="secondary" style={{ fontSize: '11px' }}>
+            {migration.processed_documents} / {migration.total_documents}{' '}
+            chunks
+          </Text>
+        </div>
+      </Card>
+    )
+  }
+
   const renderCurrentFilesProcessing = () => {
     if (!currentStatus?.current_files_processing?.length) return null
 
@@ -197,6 +253,7 @@ export const RagInstanceStatus: React.FC = () => {
       {currentStatus && (
         <div className={'w-full flex flex-col gap-3'}>
           {renderInstanceSummary()}
+          {renderEmbeddingMigration()}
           {renderCurrentFilesProcessing()}
         </div>
       )}
@@ -2,13 +2,16 @@ import {
   App,
   Button,
   Card,
+  DatePicker,
   Divider,
   Flex,
   Form,
   Input,
   Result,
+  Select,
   Space,
   Statistic,
+  Switch,
   Tabs,
   Tag,
   Typography,
@@ -29,17 +32,36 @@ import {
   BarChartOutlined,
   DownloadOutlined,
 } from '@ant-design/icons'
+import type { Dayjs } from 'dayjs'
 import type {
-  RAGQueryRequest,
+  RAGQueryFilter,
   RAGSource,
   File as ApiFile,
 } from '../../../types/api'
 
 const { TextArea } = Input
 const { Text, Paragraph } = Typography
+const { RangePicker } = DatePicker
 
-interface QueryFormData extends RAGQueryRequest {
+interface QueryFormData {
   query: string
+  generate_answer?: boolean
+  tags?: string[]
+  file_types?: string[]
+  uploaded_range?: [Dayjs | null, Dayjs | null] | null
+}
+
+// Build the query filter from the form, leaving out criteria that are not set
+const buildQueryFilter = (
+  values: QueryFormData,
+): RAGQueryFilter | undefined => {
+  const filter: RAGQueryFilter = {}
+  if (values.tags?.length) filter.tags = values.tags
+  if (values.file_types?.length) filter.file_types = values.file_types
+  const [from, to] = values.uploaded_range ?? []
+  if (from) filter.uploaded_after = from.startOf('day').toISOString()
+  if (to) filter.uploaded_before = to.add(1, 'day').startOf('day').toISOString()
+  return Object.keys(filter).length > 0 ? filter : undefined
 }
 
 export const RagQueryTab: React.FC = () => {
@@ -58,7 +80,11 @@ export const RagQueryTab: React.FC = () => {
     }
 
     try {
-      await queryRAGInstance(ragInstanceId, values)
+      await queryRAGInstance(ragInstanceId, {
+        query: values.query,
+        generate_answer: values.generate_answer,
+        filter: buildQueryFilter(values),
+      })
       message.success('Query completed successfully')
     } catch (error) {
       console.error('Query failed:', error)
@@ -221,6 +247,32 @@ export const RagQueryTab: React.FC = () => {
     )
   }
 
+  // Generated answer with its citation list
+  const AnswerCard: React.FC = () => {
+    const answer = queryResults?.answer
+    if (!answer) return null
+
+    return (
+      <Card size="small" title="Answer" className="mb-4">
+        <Paragraph className="whitespace-pre-wrap">{answer.text}</Paragraph>
+        {answer.citations.length > 0 && (
+          <Space direction="vertical" size="small" className="w-full">
+            {answer.citations.map(citation => (
+              <Text
+                key={`${citation.marker}-${citation.document_id}`}
+                type="secondary"
+                className="text-xs"
+              >
+                [{citation.marker}] {citation.filename ?? 'Unknown'} (chunk{' '}
+                {citation.chunk_index})
+              </Text>
+            ))}
+          </Space>
+        )}
+      </Card>
+    )
+  }
+
   // Source card component
   const SourceCard: React.FC<{ source: RAGSource; index: number }> = ({
     source,
@@ -384,6 +436,7 @@ export const RagQueryTab: React.FC = () => {
     return (
       <div>
         <ResultsOverview />
+        <AnswerCard />
         <Tabs items={tabItems} defaultActiveKey="chunks" />
       </div>
     )
@@ -409,6 +462,55 @@ export const RagQueryTab: React.FC = () => {
             />
           </Form.Item>
 
+          <Form.Item
+            name="generate_answer"
+            label="Generate Answer"
+            tooltip="Synthesize an answer with citations from the retrieved chunks using the instance's LLM model"
+            valuePropName="checked"
+          >
+            <Switch disabled={querying} />
+          </Form.Item>
+
+          <Flex gap="middle" wrap>
+            <Form.Item
+              name="tags"
+              label="Tags"
+              tooltip="Only search files carrying all of these tags"
+              className="flex-1 min-w-48"
+            >
+              <Select
+                mode="tags"
+                placeholder="Any tags"
+                tokenSeparators={[',']}
+                disabled={querying}
+              />
+            </Form.Item>
+            <Form.Item
+              name="file_types"
+              label="File Types"
+              tooltip="File extensions (e.g. pdf, md) or MIME types"
+              className="flex-1 min-w-48"
+            >
+              <Select
+                mode="tags"
+                placeholder="Any type"
+                tokenSeparators={[',']}
+                disabled={querying}
+              />
+            </Form.Item>
+            <Form.Item
+              name="uploaded_range"
+              label="Uploaded"
+              className="flex-1 min-w-48"
+            >
+              <RangePicker
+                allowEmpty={[true, true]}
+                className="w-full"
+                disabled={querying}
+              />
+            </Form.Item>
+          </Flex>
+
           {/* Submit buttons */}
           <Form.Item>
             <Space>
@@ -106,6 +106,44 @@ export const RagSimpleVectorEngineSettings: React.FC = () => {
                 className="w-full"
               />
             </Form.Item>
+
+            <Form.Item
+              label="Chunking Strategy"
+              name={getFieldName('indexing', 'chunking_strategy')}
+              tooltip="Token-based splitting, or structure-aware splitting that keeps Markdown headings, code blocks, tables and lists intact (default: token)"
+            >
+              <Select
+                placeholder="Select strategy"
+                allowClear
+                options={[
+                  { value: 'token', label: 'Token-based' },
+                  { value: 'structure', label: 'Structure-aware (Markdown)' },
+                ]}
+              />
+            </Form.Item>
+
+            <Form.Item
+              label="Semantic Split"
+              name={getFieldName('indexing', 'enable_semantic_split')}
+              tooltip="With structure-aware chunking, split where adjacent sentences have low embedding similarity (default: false)"
+              valuePropName="checked"
+            >
+              <Switch />
+            </Form.Item>
+
+            <Form.Item
+              label="Semantic Split Threshold"
+              name={getFieldName('indexing', 'semantic_split_threshold')}
+              tooltip="Similarity between adjacent sentences below which a split is preferred (default: 0.75)"
+            >
+              <InputNumber
+                placeholder="0.75"
+                min={0}
+                max={1}
+                step={0.05}
+                className="w-full"
+              />
+            </Form.Item>
           </div>
         </Panel>
 
@@ -197,6 +235,38 @@ export const RagSimpleVectorEngineSettings: React.FC = () => {
               />
             </Form.Item>
 
+            <Divider orientation="left" orientationMargin="0">
+              <Text type="secondary">Retrieval</Text>
+            </Divider>
+
+            <Form.Item
+              label="Retrieval Mode"
+              name={getFieldName('querying', 'retrieval_mode')}
+              tooltip="Vector-only search, or hybrid search fusing full-text and vector results (default: vector)"
+            >
+              <Select
+                placeholder="Select mode"
+                allowClear
+                options={[
+                  { value: 'vector', label: 'Vector Only' },
+                  { value: 'hybrid', label: 'Hybrid (Full-text + Vector)' },
+                ]}
+              />
+            </Form.Item>
+
+            <Form.Item
+              label="RRF Constant (k)"
+              name={getFieldName('querying', 'rrf_k')}
+              tooltip="Reciprocal rank fusion constant used in hybrid mode; higher values flatten rank differences (default: 60)"
+            >
+              <InputNumber
+                placeholder="60"
+                min={1}
+                max={1000}
+                className="w-full"
+              />
+            </Form.Item>
+
             <Divider orientation="left" orientationMargin="0">
               <Text type="secondary">Prompt Templates</Text>
             </Divider>
@@ -249,6 +319,45 @@ export const RagSimpleVectorEngineSettings: React.FC = () => {
                 className="w-full"
               />
             </Form.Item>
+
+            <Form.Item
+              label="Rerank Model"
+              name={getFieldName('querying', 'rerank_model_id')}
+              tooltip="Cross-encoder model used to rerank results; the LLM model scores results when empty"
+            >
+              <Select
+                placeholder="Select rerank model"
+                allowClear
+                showSearch
+                filterOption={(input, option) => {
+                  if (!option) return false
+                  if ('options' in option && Array.isArray(option.options)) {
+                    // This is a group option - search in children
+                    return option.options.some((child: any) =>
+                      child?.label?.toLowerCase().includes(input.toLowerCase()),
+                    )
+                  }
+                  // This is a regular option
+                  return (option.label ?? '')
+                    .toLowerCase()
+                    .includes(input.toLowerCase())
+                }}
+                options={getAvailableModels('rerank')}
+              />
+            </Form.Item>
+
+            <Form.Item
+              label="Rerank Top K"
+              name={getFieldName('querying', 'rerank_top_k')}
+              tooltip="Number of chunks kept after reranking (default: chunk top k)"
+            >
+              <InputNumber
+                placeholder="20"
+                min={1}
+                max={100}
+                className="w-full"
+              />
+            </Form.Item>
           </div>
         </Panel>
       </Collapse>
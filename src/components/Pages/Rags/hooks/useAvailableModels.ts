@@ -5,7 +5,7 @@ export const useAvailableModels = () => {
   const { providers, modelsByProvider } = Stores.Providers
 
   // Get available models grouped by provider, filtered by capability
-  const getAvailableModels = (capability?: 'text_embedding' | 'chat') => {
+  const getAvailableModels = (capability?: 'text_embedding' | 'chat' | 'rerank') => {
     const options: Array<{
       label: string
       options: Array<{
@@ -7,12 +7,15 @@ import { Permission } from '../../../../../types'
 import {
   loadSystemServers,
   clearAdminMCPErrors,
+  importSystemServers,
+  exportSystemServers,
 } from '../../../../../store/admin/mcpServers.ts'
 import { openMCPServerDrawer } from '../../../../../store/ui/mcpDrawers'
 import { hasPermission } from '../../../../../permissions/utils'
 import { MCPServerCard } from '../MCPServerCard'
 import { useMainContentMinSize } from '../../../../hooks/useWindowMinSize'
 import { MCPServerDrawer } from '../MCPServerDrawer'
+import { MCPServersImportExport } from '../MCPServersImportExport'
 
 const { Text } = Typography
 
@@ -122,6 +125,11 @@ export function SystemServersTab() {
       >
         Add Server
       </Button>
+      <MCPServersImportExport
+        onImport={importSystemServers}
+        onExport={exportSystemServers}
+        exportFilename="mcp-system-servers.json"
+      />
     </>
   )
 
@@ -1,15 +1,21 @@
-import { useState } from 'react'
+import { useEffect, useState } from 'react'
 import { App, Button, Card, Tag, Typography, Tooltip, Switch, Flex } from 'antd'
 import {
   EditOutlined,
+  KeyOutlined,
   ToolOutlined,
   FileTextOutlined,
   PlayCircleOutlined,
   StopOutlined,
 } from '@ant-design/icons'
-import type { MCPServer } from '../../../../types/api'
+import { openUrl } from '@tauri-apps/plugin-opener'
+import { isTauriView } from '../../../../api/core'
+import type { MCPOAuthStatus, MCPServer } from '../../../../types/api'
 import {
+  getMCPServerAuthorization,
+  revokeMCPServerAuthorization,
   startMCPServer,
+  startMCPServerAuthorization,
   stopMCPServer,
   updateMCPServer,
 } from '../../../../store/mcp'
@@ -33,6 +39,43 @@ export function MCPServerCard({
   const [showLogs, setShowLogs] = useState(false)
   const [operationLoading, setOperationLoading] = useState<string | null>(null)
   const [enableLoading, setEnableLoading] = useState(false)
+  const [authorization, setAuthorization] = useState<MCPOAuthStatus | null>(
+    null,
+  )
+
+  // Remote servers may need the user to sign in with their authorization server
+  const isRemote = server.transport_type !== 'stdio'
+
+  const loadAuthorization = async () => {
+    try {
+      setAuthorization(await getMCPServerAuthorization(server.id))
+    } catch {
+      setAuthorization(null)
+    }
+  }
+
+  useEffect(() => {
+    if (!isRemote) return
+    loadAuthorization()
+
+    // The callback page notifies its opener; in the desktop app the browser is
+    // external, so refresh when the user comes back instead
+    const handleMessage = (event: MessageEvent) => {
+      if (
+        event.data?.type === 'mcp-oauth' &&
+        event.data?.server_id === server.id
+      ) {
+        loadAuthorization()
+      }
+    }
+    const handleFocus = () => loadAuthorization()
+    window.addEventListener('message', handleMessage)
+    window.addEventListener('focus', handleFocus)
+    return () => {
+      window.removeEventListener('message', handleMessage)
+      window.removeEventListener('focus', handleFocus)
+    }
+  }, [server.id, isRemote])
 
   const handleEdit = () => {
     if (server.is_system) {
@@ -96,6 +139,38 @@ export function MCPServerCard({
     }
   }
 
+  const handleAuthorize = async () => {
+    setOperationLoading('authorize')
+    try {
+      const { authorization_url } = await startMCPServerAuthorization(
+        server.id,
+      )
+      if (isTauriView) {
+        await openUrl(authorization_url)
+      } else {
+        window.open(authorization_url, 'mcp-oauth', 'width=600,height=700')
+      }
+    } catch (error) {
+      console.error('Failed to authorize server:', error)
+      message.error('Failed to start authorization')
+    } finally {
+      setOperationLoading(null)
+    }
+  }
+
+  const handleRevoke = async () => {
+    setOperationLoading('revoke')
+    try {
+      await revokeMCPServerAuthorization(server.id)
+      await loadAuthorization()
+      message.success('Authorization revoked')
+    } catch (error) {
+      message.error('Failed to revoke authorization')
+    } finally {
+      setOperationLoading(null)
+    }
+  }
+
   return (
     <>
       <Card>
@@ -111,6 +186,9 @@ export function MCPServerCard({
                     <Tag color="blue">System</Tag>
                   )}
                   {server.status === 'error' && <Tag color="red">Error</Tag>}
+                  {authorization?.authorized && (
+                    <Tag color="green">Authorized</Tag>
+                  )}
                   {/* Show enabled/disabled status tag for all system servers */}
                   {(server.tool_count ?? 0) > 0 && (
                     <Tag color="cyan">{server.tool_count} tools</Tag>
@@ -168,6 +246,31 @@ export function MCPServerCard({
                     </Button>
                   </>
                 )}
+                {isRemote &&
+                  authorization &&
+                  (authorization.authorized ? (
+                    <Button
+                      icon={<KeyOutlined />}
+                      onClick={e => {
+                        e.stopPropagation()
+                        handleRevoke()
+                      }}
+                      loading={operationLoading === 'revoke'}
+                    >
+                      Revoke
+                    </Button>
+                  ) : (
+                    <Button
+                      icon={<KeyOutlined />}
+                      onClick={e => {
+                        e.stopPropagation()
+                        handleAuthorize()
+                      }}
+                      loading={operationLoading === 'authorize'}
+                    >
+                      Authorize
+                    </Button>
+                  ))}
                 <Button
                   type={showTools ? 'primary' : 'default'}
                   icon={<ToolOutlined />}
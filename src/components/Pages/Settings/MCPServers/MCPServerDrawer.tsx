@@ -3,6 +3,7 @@ import {
   Form,
   Typography,
   Input,
+  InputNumber,
   Select,
   Switch,
   Card,
@@ -23,6 +24,7 @@ import type {
   CreateMCPServerRequest,
   UpdateMCPServerRequest,
   CreateSystemMCPServerRequest,
+  MCPSandboxProfile,
   UpdateMCPServerRequest as UpdateSystemMCPServerRequest,
 } from '../../../../types/api'
 
@@ -106,6 +108,7 @@ export function MCPServerDrawer() {
           ? JSON.stringify(editingServer.environment_variables, null, 2)
           : '',
         enabled: editingServer.enabled,
+        sandbox: (editingServer.sandbox as MCPSandboxProfile) || {},
       }
       form.setFieldsValue(formValues)
     }
@@ -128,6 +131,19 @@ export function MCPServerDrawer() {
         }
       }
 
+      // The sandbox only applies to stdio servers
+      const sandbox: MCPSandboxProfile | undefined =
+        values.transport_type === 'stdio'
+          ? {
+              enabled: values.sandbox?.enabled ?? false,
+              deny_network: values.sandbox?.deny_network ?? false,
+              writable_paths: values.sandbox?.writable_paths ?? [],
+              memory_limit_mb: values.sandbox?.memory_limit_mb ?? undefined,
+              cpu_time_limit_seconds:
+                values.sandbox?.cpu_time_limit_seconds ?? undefined,
+            }
+          : undefined
+
       if (mode === 'create' || mode === 'clone') {
         // Create new user server
         const createRequest: CreateMCPServerRequest = {
@@ -140,6 +156,7 @@ export function MCPServerDrawer() {
           args: values.args ? values.args.split(' ').filter(Boolean) : [],
           environment_variables: environmentVariables,
           enabled: values.enabled ?? true,
+          sandbox,
         }
         await createMCPServer(createRequest)
       } else if (mode === 'edit') {
@@ -153,6 +170,7 @@ export function MCPServerDrawer() {
           args: values.args ? values.args.split(' ').filter(Boolean) : [],
           environment_variables: environmentVariables,
           enabled: values.enabled ?? true,
+          sandbox,
         }
         await updateMCPServer(editingServer.id, updateRequest)
       } else if (mode === 'create-system') {
@@ -167,6 +185,7 @@ export function MCPServerDrawer() {
           args: values.args ? values.args.split(' ').filter(Boolean) : [],
           environment_variables: environmentVariables,
           enabled: values.enabled ?? true,
+          sandbox,
         }
         await createSystemServer(createRequest)
       } else if (mode === 'edit-system') {
@@ -180,6 +199,7 @@ export function MCPServerDrawer() {
           args: values.args ? values.args.split(' ').filter(Boolean) : [],
           environment_variables: environmentVariables,
           enabled: values.enabled ?? true,
+          sandbox,
         }
         await updateMCPServer(editingServer.id, updateRequest)
       }
@@ -443,6 +463,54 @@ export function MCPServerDrawer() {
                 to the child process.
               </Text>
             </Card>
+
+            {/* Sandbox */}
+            {transportType === 'stdio' && (
+              <Card title="Sandbox">
+                <Form.Item
+                  name={['sandbox', 'enabled']}
+                  label="Run in sandbox"
+                  valuePropName="checked"
+                  tooltip="Linux only, requires bubblewrap (bwrap). The server sees a read-only filesystem except its own scratch directory."
+                >
+                  <Switch />
+                </Form.Item>
+                <Form.Item
+                  name={['sandbox', 'deny_network']}
+                  label="Deny network access"
+                  valuePropName="checked"
+                >
+                  <Switch />
+                </Form.Item>
+                <Form.Item
+                  name={['sandbox', 'writable_paths']}
+                  label="Writable paths"
+                  tooltip="Absolute host paths the server may write to"
+                >
+                  <Select mode="tags" placeholder="/home/user/projects" />
+                </Form.Item>
+                <Flex className="gap-3">
+                  <Form.Item
+                    name={['sandbox', 'memory_limit_mb']}
+                    label="Memory limit (MB)"
+                    className="flex-1"
+                  >
+                    <InputNumber min={1} className="w-full" />
+                  </Form.Item>
+                  <Form.Item
+                    name={['sandbox', 'cpu_time_limit_seconds']}
+                    label="CPU time limit (seconds)"
+                    className="flex-1"
+                  >
+                    <InputNumber min={1} className="w-full" />
+                  </Form.Item>
+                </Flex>
+                <Text type="secondary" className="text-xs">
+                  Blocked writes, network access and exceeded limits are
+                  reported as sandbox violations in the server logs.
+                </Text>
+              </Card>
+            )}
           </Flex>
         </Form>
       </div>
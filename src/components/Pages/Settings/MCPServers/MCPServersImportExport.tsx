@@ -0,0 +1,147 @@
+import { useState } from 'react'
+import { Alert, App, Button, Input, Modal, Typography } from 'antd'
+import { DownloadOutlined, UploadOutlined } from '@ant-design/icons'
+import type {
+  ImportMCPServersResponse,
+  MCPServersConfig,
+} from '../../../../types/api'
+
+const { Text } = Typography
+const { TextArea } = Input
+
+const PLACEHOLDER = `{
+  "mcpServers": {
+    "filesystem": {
+      "command": "npx",
+      "args": ["-y", "@modelcontextprotocol/server-filesystem", "/tmp"]
+    },
+    "remote": {
+      "url": "https://example.com/mcp"
+    }
+  }
+}`
+
+interface MCPServersImportExportProps {
+  onImport: (config: MCPServersConfig) => Promise<ImportMCPServersResponse>
+  onExport: () => Promise<MCPServersConfig>
+  exportFilename?: string
+}
+
+// Import and export servers in the mcpServers format used by Claude Desktop,
+// VS Code, Cursor and other MCP clients
+export function MCPServersImportExport({
+  onImport,
+  onExport,
+  exportFilename = 'mcp-servers.json',
+}: MCPServersImportExportProps) {
+  const { message } = App.useApp()
+  const [open, setOpen] = useState(false)
+  const [config, setConfig] = useState('')
+  const [importing, setImporting] = useState(false)
+  const [exporting, setExporting] = useState(false)
+  const [error, setError] = useState<string | null>(null)
+
+  const handleClose = () => {
+    setOpen(false)
+    setConfig('')
+    setError(null)
+  }
+
+  const handleImport = async () => {
+    let parsed: MCPServersConfig
+    try {
+      parsed = JSON.parse(config)
+    } catch {
+      setError('The config is not valid JSON')
+      return
+    }
+
+    setImporting(true)
+    setError(null)
+    try {
+      const result = await onImport(parsed)
+      if (result.skipped.length > 0) {
+        message.warning(
+          `Imported ${result.created.length} server(s), skipped ${result.skipped
+            .map(issue => `${issue.name} (${issue.error})`)
+            .join(', ')}`,
+        )
+      } else {
+        message.success(`Imported ${result.created.length} server(s)`)
+      }
+      handleClose()
+    } catch (error) {
+      setError(error instanceof Error ? error.message : 'Import failed')
+    } finally {
+      setImporting(false)
+    }
+  }
+
+  const handleExport = async () => {
+    setExporting(true)
+    try {
+      const exported = await onExport()
+      const blob = new Blob([JSON.stringify(exported, null, 2)], {
+        type: 'application/json',
+      })
+      const link = document.createElement('a')
+      link.href = URL.createObjectURL(blob)
+      link.download = exportFilename
+      document.body.appendChild(link)
+      link.click()
+      document.body.removeChild(link)
+      URL.revokeObjectURL(link.href)
+    } catch (error) {
+      console.error('Failed to export MCP servers:', error)
+      message.error('Failed to export MCP servers')
+    } finally {
+      setExporting(false)
+    }
+  }
+
+  return (
+    <>
+      <Button
+        icon={<UploadOutlined />}
+        onClick={() => setOpen(true)}
+        className="flex-1"
+      >
+        Import
+      </Button>
+      <Button
+        icon={<DownloadOutlined />}
+        onClick={handleExport}
+        loading={exporting}
+        className="flex-1"
+      >
+        Export
+      </Button>
+
+      <Modal
+        title="Import MCP Servers"
+        open={open}
+        onCancel={handleClose}
+        onOk={handleImport}
+        okText="Import"
+        okButtonProps={{ disabled: !config.trim(), loading: importing }}
+        width={640}
+      >
+        <div className="flex flex-col gap-3">
+          <Text type="secondary">
+            Paste an mcpServers config from Claude Desktop, VS Code, Cursor or
+            another MCP client. Servers whose name is already taken are
+            skipped.
+          </Text>
+          <TextArea
+            rows={14}
+            value={config}
+            onChange={e => setConfig(e.target.value)}
+            placeholder={PLACEHOLDER}
+            className="font-mono"
+          />
+          {error && <Alert type="error" message={error} showIcon />}
+        </div>
+      </Modal>
+    </>
+  )
+}
@@ -5,11 +5,17 @@ import { VscFilter } from 'react-icons/vsc'
 import { SettingsPageContainer } from '../common/SettingsPageContainer'
 import { MCPServerCard } from './MCPServerCard'
 import { MCPServerDrawer } from './MCPServerDrawer'
+import { MCPServersImportExport } from './MCPServersImportExport'
 import { PermissionGuard } from '../../../Auth/PermissionGuard'
 import { Permission } from '../../../../types'
 import { Stores } from '../../../../store'
 import { openMCPServerDrawer } from '../../../../store/ui/mcpDrawers'
-import { clearMCPError, loadMCPServers } from '../../../../store/mcp'
+import {
+  clearMCPError,
+  exportMCPServers,
+  importMCPServers,
+  loadMCPServers,
+} from '../../../../store/mcp'
 import { useMainContentMinSize } from '../../../hooks/useWindowMinSize'
 
 const { Text } = Typography
@@ -108,6 +114,10 @@ export function MCPServersSettings() {
         >
           Add Server
         </Button>
+        <MCPServersImportExport
+          onImport={importMCPServers}
+          onExport={exportMCPServers}
+        />
       </PermissionGuard>
     </>
   )
@@ -124,6 +124,23 @@ export function ModelCapabilitiesSection() {
             <Switch />
           </Form.Item>
         </div>
+
+        <div
+          style={{
+            display: 'flex',
+            justifyContent: 'space-between',
+            alignItems: 'center',
+          }}
+        >
+          <span>{t('providers.rerank')}</span>
+          <Form.Item
+            name={['capabilities', 'rerank']}
+            valuePropName="checked"
+            style={{ marginBottom: 0 }}
+          >
+            <Switch />
+          </Form.Item>
+        </div>
       </Flex>
     </Card>
   )
@@ -2,6 +2,7 @@ import {
   DeleteOutlined,
   EditOutlined,
   PlusOutlined,
+  SyncOutlined,
   UploadOutlined,
 } from '@ant-design/icons'
 import {
@@ -30,6 +31,7 @@ import {
   startModelExecution,
   stopModelExecution,
   Stores,
+  syncModelsFromProvider,
   updateModelProvider,
 } from '../../../../../store'
 import { Model } from '../../../../../types'
@@ -155,6 +157,23 @@ export function ModelsSection() {
     }
   }
 
+  const handleSyncModels = async () => {
+    if (!currentProvider) return
+    try {
+      const newModels = await syncModelsFromProvider(currentProvider.id)
+      message.success(
+        newModels.length > 0
+          ? `Added ${newModels.length} model(s) from ${currentProvider.name}`
+          : 'All models are already listed',
+      )
+    } catch (error) {
+      console.error('Failed to sync models:', error)
+      message.error(
+        error instanceof Error ? error.message : 'Failed to sync models',
+      )
+    }
+  }
+
   const handleEditModel = (modelId: string) => {
     if (!currentProvider) return
     if (currentProvider.type === 'local') {
@@ -253,6 +272,24 @@ export function ModelsSection() {
       )
     }
 
+    if (currentProvider.type === 'ollama') {
+      return (
+        <Flex className="gap-1">
+          <Button
+            type="text"
+            icon={<SyncOutlined />}
+            onClick={handleSyncModels}
+            title="Sync models from server"
+          />
+          <Button
+            type="text"
+            icon={<PlusOutlined />}
+            onClick={handleAddModel}
+          />
+        </Flex>
+      )
+    }
+
     return (
       <Button type="text" icon={<PlusOutlined />} onClick={handleAddModel} />
     )
@@ -53,7 +53,12 @@ export function ProviderHeader() {
     const providerModels = provider.id === providerId ? models : []
     if (providerModels.length === 0) return false
     if (provider.type === 'local') return true
-    if (!provider.api_key || provider.api_key.trim() === '') return false
+    // Ollama servers don't require an API key by default
+    if (
+      provider.type !== 'ollama' &&
+      (!provider.api_key || provider.api_key.trim() === '')
+    )
+      return false
     if (!provider.base_url || provider.base_url.trim() === '') return false
     try {
       new globalThis.URL(provider.base_url)
@@ -69,7 +74,10 @@ export function ProviderHeader() {
     if (providerModels.length === 0)
       return 'No models available. Add at least one model first.'
     if (provider.type === 'local') return null
-    if (!provider.api_key || provider.api_key.trim() === '')
+    if (
+      provider.type !== 'ollama' &&
+      (!provider.api_key || provider.api_key.trim() === '')
+    )
       return 'API key is required'
     if (!provider.base_url || provider.base_url.trim() === '')
       return 'Base URL is required'
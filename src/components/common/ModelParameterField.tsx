@@ -69,7 +69,14 @@ export interface ParameterFieldConfig {
   label: string
   help?: string
   placeholder?: string
-  type: 'number' | 'text' | 'password' | 'textarea' | 'select' | 'string-array'
+  type:
+    | 'number'
+    | 'text'
+    | 'password'
+    | 'textarea'
+    | 'select'
+    | 'string-array'
+    | 'json'
   min?: number
   max?: number
   step?: number
@@ -95,6 +102,17 @@ export function ModelParameterField({
 }: ModelParameterFieldProps) {
   const fieldRules = [
     ...(required ? [{ required: true, message: `${label} is required` }] : []),
+    ...(type === 'json'
+      ? [
+          {
+            // Text that did not parse is left as a string by normalizeJson
+            validator: (_: any, value: any) =>
+              typeof value === 'string'
+                ? Promise.reject(new Error(`${label} must be valid JSON`))
+                : Promise.resolve(),
+          },
+        ]
+      : []),
     ...rules,
   ]
 
@@ -128,15 +146,46 @@ export function ModelParameterField({
         return (
           <StringArrayInput placeholder={placeholder} style={commonStyle} />
         )
+      case 'json':
+        return <TextArea placeholder={placeholder} rows={6} />
       case 'text':
       default:
         return <Input placeholder={placeholder} style={commonStyle} />
     }
   }
 
+  // JSON fields hold the parsed value and are edited as text
+  const jsonProps =
+    type === 'json'
+      ? {
+          getValueProps: (value: any) => ({
+            value:
+              value === undefined || value === null || typeof value === 'string'
+                ? value
+                : JSON.stringify(value, null, 2),
+          }),
+          normalize: normalizeJson,
+        }
+      : {}
+
   return (
-    <Form.Item name={name} label={label} help={help} rules={fieldRules}>
+    <Form.Item
+      name={name}
+      label={label}
+      help={help}
+      rules={fieldRules}
+      {...jsonProps}
+    >
       {renderInput()}
     </Form.Item>
   )
 }
+
+function normalizeJson(text: string) {
+  if (!text?.trim()) return undefined
+  try {
+    return JSON.parse(text)
+  } catch {
+    return text
+  }
+}
@@ -3,7 +3,14 @@ export interface ParameterFieldConfig {
   label: string
   help?: string
   placeholder?: string
-  type: 'number' | 'text' | 'password' | 'textarea' | 'select' | 'string-array'
+  type:
+    | 'number'
+    | 'text'
+    | 'password'
+    | 'textarea'
+    | 'select'
+    | 'string-array'
+    | 'json'
   min?: number
   max?: number
   step?: number
@@ -82,6 +89,22 @@ export const MODEL_PARAMETERS: ParameterFieldConfig[] = [
     placeholder: 'Enter stop sequence',
     help: 'Stop generation when any of these sequences are encountered (max 4 sequences)',
   },
+  {
+    name: ['parameters', 'thinking_budget'],
+    label: 'Thinking Budget',
+    type: 'number',
+    min: 1,
+    step: 1024,
+    placeholder: 'Leave empty to disable',
+    help: 'Token budget for extended thinking (Anthropic, Gemini; turns thinking on for Ollama). Reasoning is shown apart from the answer.',
+  },
+  {
+    name: ['parameters', 'json_schema'],
+    label: 'JSON Schema',
+    type: 'json',
+    placeholder: '{ "type": "object", "properties": { ... } }',
+    help: 'Constrain replies to JSON matching this schema. Replies that do not match are generated again once.',
+  },
 ]
 
 export const BASIC_MODEL_FIELDS: ParameterFieldConfig[] = [
@@ -2,7 +2,7 @@ import { RiAnthropicFill, RiGeminiFill, RiOpenaiFill } from 'react-icons/ri'
 import { SiHuggingface } from 'react-icons/si'
 import { FaServer, FaWrench } from 'react-icons/fa'
 import { BsFillLightningChargeFill } from 'react-icons/bs'
-import { DeepSeek, Mistral } from '@lobehub/icons'
+import { DeepSeek, Mistral, Ollama } from '@lobehub/icons'
 import type { IconType } from 'react-icons'
 import { ProviderType } from '../types'
 
@@ -40,6 +40,7 @@ export const SUPPORTED_PROVIDERS: ProviderOption[] = [
   { value: 'mistral', label: 'Mistral' },
   { value: 'deepseek', label: 'DeepSeek' },
   { value: 'huggingface', label: 'Hugging Face' },
+  { value: 'ollama', label: 'Ollama' },
   { value: 'custom', label: 'Custom' },
 ]
 
@@ -65,6 +66,9 @@ export const PROVIDER_DEFAULTS: Record<ProviderType, ProviderDefaults> = {
   huggingface: {
     base_url: 'https://api-inference.huggingface.co/v1',
   },
+  ollama: {
+    base_url: 'http://localhost:11434',
+  },
   local: {
     settings: {
       device: 'cpu',
@@ -93,6 +97,7 @@ export const PROVIDER_ICONS: Record<ProviderType, IconType> = {
   mistral: Mistral,
   deepseek: DeepSeek,
   huggingface: SiHuggingface,
+  ollama: Ollama,
   custom: FaWrench,
 }
 
@@ -10,6 +10,8 @@ import type {
   GroupServerAssignmentResponse,
   GroupAssignmentResponse,
   AssignServersRequest,
+  MCPServersConfig,
+  ImportMCPServersResponse,
 } from '../../types/api'
 
 // Enable Map and Set support in Immer
@@ -201,6 +203,32 @@ export const createSystemServer = async (
   }
 }
 
+export const importSystemServers = async (
+  config: MCPServersConfig,
+): Promise<ImportMCPServersResponse> => {
+  try {
+    const result = await ApiClient.AdminMcp.importSystemServers(config)
+
+    useAdminMCPServersStore.setState(draft => {
+      draft.systemServers.push(...result.created)
+    })
+
+    return result
+  } catch (error) {
+    console.error('Failed to import system servers:', error)
+    throw error
+  }
+}
+
+export const exportSystemServers = async (): Promise<MCPServersConfig> => {
+  try {
+    return await ApiClient.AdminMcp.exportSystemServers()
+  } catch (error) {
+    console.error('Failed to export system servers:', error)
+    throw error
+  }
+}
+
 // Execution logs management (admin view of all executions)
 export const loadAllExecutionLogs = async (
   page?: number,
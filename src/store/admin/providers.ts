@@ -281,6 +281,47 @@ export const addNewModelToProvider = async (
   }
 }
 
+export const syncModelsFromProvider = async (
+  providerId: string,
+): Promise<Model[]> => {
+  const state = useAdminProvidersStore.getState()
+  if (state.modelsLoading[providerId]) {
+    return []
+  }
+
+  try {
+    useAdminProvidersStore.setState(state => ({
+      modelsLoading: { ...state.modelsLoading, [providerId]: true },
+      modelError: { ...state.modelError, [providerId]: '' },
+    }))
+
+    const newModels = await ApiClient.Admin.syncProviderModels({
+      provider_id: providerId,
+    })
+
+    useAdminProvidersStore.setState(state => ({
+      providers: state.providers.map(p =>
+        p.id === providerId
+          ? { ...p, models: [...p.models, ...newModels] }
+          : p,
+      ),
+      modelsLoading: { ...state.modelsLoading, [providerId]: false },
+    }))
+
+    return newModels
+  } catch (error) {
+    useAdminProvidersStore.setState(state => ({
+      modelError: {
+        ...state.modelError,
+        [providerId]:
+          error instanceof Error ? error.message : 'Failed to sync models',
+      },
+      modelsLoading: { ...state.modelsLoading, [providerId]: false },
+    }))
+    throw error
+  }
+}
+
 // Legacy compatibility
 export const addNewModel = async (
   providerId: string,
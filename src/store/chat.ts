@@ -6,6 +6,7 @@ import {
   Message,
   MessageContentDataText,
   ChatMessageRequest,
+  ToolProgressData,
 } from '../types'
 import { useConversationsStore } from './conversations.ts'
 import { getFile } from './files.ts'
@@ -89,6 +90,7 @@ export interface ChatState {
 
   // Stream state
   isStreaming: boolean
+  toolProgress: Record<string, ToolProgressData> // Latest progress by tool call_id
 
   // Store management
   destroy: () => void
@@ -145,6 +147,7 @@ export const createChatStore = (conversation: string | Conversation) => {
         loadingBranches: false,
         error: null,
         isStreaming: false,
+        toolProgress: {},
 
         destroy: () => {
           // Clean up cached messages and debounce timers for this conversation
@@ -600,6 +603,7 @@ export const createChatStore = (conversation: string | Conversation) => {
             loadingBranches: false,
             error: null,
             isStreaming: false,
+            toolProgress: {},
           })
         },
       }),
@@ -3,6 +3,8 @@ import {
   Message,
   MessageContentItem,
   MessageContentDataText,
+  MessageContentDataThinking,
+  MessageContentDataGeneratedImage,
   MessageContentDataToolCall,
   MessageContentDataToolCallPendingApproval,
   MessageContentDataToolResult,
@@ -10,12 +12,15 @@ import {
   ConnectedData,
   CompleteData,
   MessageContentChunkData,
+  MessageContentRetryData,
+  GeneratedImageData,
   NewUserMessageData,
   NewAssistantMessageData,
   NewMessageContentData,
   ToolCallData,
   ToolCallPendingApprovalData,
   ToolCallPendingApprovalCancelData,
+  ToolProgressData,
   ToolResultData,
   TitleUpdatedData,
 } from '../../types'
@@ -245,6 +250,121 @@ export const createSSEHandlers = (context: SSEHandlerContext) => {
       })
     },
 
+    newThinkingContent: (data: NewMessageContentData) => {
+      set(state => {
+        const targetMessageId = getTargetMessageId()
+        if (!targetMessageId) return {}
+
+        const updatedMessages = state.messages.map((msg: Message) => {
+          if (msg.id === targetMessageId) {
+            // Create new thinking content item
+            const newContent: MessageContentItem = {
+              id: data.message_content_id,
+              message_id: data.message_id,
+              content_type: 'thinking',
+              content: { thinking: '' } as MessageContentDataThinking,
+              sequence_order: msg.contents.length,
+              created_at: new Date().toISOString(),
+              updated_at: new Date().toISOString(),
+            }
+            return {
+              ...msg,
+              contents: [...msg.contents, newContent],
+            }
+          }
+          return msg
+        })
+
+        return { messages: updatedMessages }
+      })
+    },
+
+    thinkingContentChunk: (data: MessageContentChunkData) => {
+      if (!data.delta) return
+
+      set(state => {
+        const targetMessageId = getTargetMessageId()
+        if (!targetMessageId) return {}
+
+        const updatedMessages = state.messages.map((msg: Message) => {
+          if (msg.id !== targetMessageId) return msg
+
+          const updatedContents = msg.contents.map(c => {
+            if (
+              c.id === data.message_content_id &&
+              c.content_type === 'thinking'
+            ) {
+              return {
+                ...c,
+                content: {
+                  ...c.content,
+                  thinking:
+                    (c.content as MessageContentDataThinking).thinking +
+                    data.delta,
+                },
+              }
+            }
+            return c
+          })
+          return { ...msg, contents: updatedContents }
+        })
+
+        return { messages: updatedMessages }
+      })
+    },
+
+    messageContentRetry: (data: MessageContentRetryData) => {
+      // The reply did not match its JSON Schema and is being generated again
+      set(state => {
+        const targetMessageId = getTargetMessageId()
+        if (!targetMessageId) return {}
+
+        const updatedMessages = state.messages.map((msg: Message) => {
+          if (msg.id !== targetMessageId) return msg
+          return {
+            ...msg,
+            contents: msg.contents.filter(
+              c => c.id !== data.message_content_id,
+            ),
+          }
+        })
+
+        return { messages: updatedMessages }
+      })
+    },
+
+    generatedImage: (data: GeneratedImageData) => {
+      set(state => {
+        const targetMessageId = getTargetMessageId()
+        if (!targetMessageId) return {}
+
+        const updatedMessages = state.messages.map((msg: Message) => {
+          if (msg.id !== targetMessageId) return msg
+
+          const newContent: MessageContentItem = {
+            id: data.message_content_id,
+            message_id: data.message_id,
+            content_type: 'generated_image',
+            content: {
+              file_id: data.file_id,
+              filename: data.filename,
+              mime_type: data.mime_type,
+              revised_prompt: data.revised_prompt,
+            } as MessageContentDataGeneratedImage,
+            sequence_order: msg.contents.length,
+            created_at: new Date().toISOString(),
+            updated_at: new Date().toISOString(),
+          }
+          return {
+            ...msg,
+            contents: [...msg.contents, newContent],
+          }
+        })
+
+        return { messages: updatedMessages }
+      })
+    },
+
     // ============================================
     // Tool Events
     // ============================================
@@ -353,11 +473,20 @@ export const createSSEHandlers = (context: SSEHandlerContext) => {
       })
     },
 
+    toolProgress: (data: ToolProgressData) => {
+      set(state => ({
+        toolProgress: { ...state.toolProgress, [data.call_id]: data },
+      }))
+    },
+
     toolResult: (data: ToolResultData) => {
       set(state => {
         const targetMessageId = getTargetMessageId()
         if (!targetMessageId) return {}
 
+        const { [data.call_id]: _finished, ...toolProgress } =
+          state.toolProgress
+
         const updatedMessages = state.messages.map((msg: Message) => {
           if (msg.id === targetMessageId) {
             const newContent: MessageContentItem = {
@@ -382,7 +511,7 @@ export const createSSEHandlers = (context: SSEHandlerContext) => {
           return msg
         })
 
-        return { messages: updatedMessages }
+        return { messages: updatedMessages, toolProgress }
       })
     },
 
@@ -52,6 +52,17 @@ export const getFileThumbnails = async (
   return thumbnails
 }
 
+// Get an object URL for the full image of a file
+export const getFileImageUrl = async (fileId: string): Promise<string> => {
+  try {
+    const blob = await ApiClient.Files.downloadFile({ file_id: fileId })
+    return window.URL.createObjectURL(blob)
+  } catch (error) {
+    console.error('Failed to fetch image:', error)
+    throw error
+  }
+}
+
 // Get file content for text files
 export const getFileContent = async (fileId: string): Promise<string> => {
   try {
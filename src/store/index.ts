@@ -109,6 +109,8 @@ export {
 export {
   clearAdminMCPErrors,
   createSystemServer,
+  importSystemServers,
+  exportSystemServers,
   loadSystemServers,
   loadAllExecutionLogs,
   loadGroupAssignments,
@@ -240,6 +242,7 @@ export {
 export {
   getFile,
   getFileContent,
+  getFileImageUrl,
   getFileThumbnail,
   getFileThumbnails,
   uploadFile,
@@ -276,6 +279,7 @@ export {
   loadModelsForProvider,
   startModelExecution,
   stopModelExecution,
+  syncModelsFromProvider,
   updateExistingModel,
   getModelsForProvider,
   getCurrentProvider,
@@ -339,6 +343,7 @@ export {
   subscribeToRAGStatus,
   disconnectRAGStatus,
   clearRAGStatusError,
+  cancelRAGEmbeddingMigration,
   useRAGStatusStore,
 } from './ragStatus'
 
@@ -406,10 +411,15 @@ export {
   createMCPServer,
   deleteMCPServer,
   discoverServerTools,
+  exportMCPServers,
   findToolByName,
   getActiveServers,
   getEnabledServers,
   getMCPServer,
+  getMCPServerAuthorization,
+  getServerPrompt,
+  getServerPrompts,
+  getServerResources,
   getServerTools,
   getServersByType,
   getSystemServers,
@@ -419,11 +429,15 @@ export {
   getToolsByServerType,
   getUserAssignedServers,
   getUserServers,
+  importMCPServers,
   loadMCPServers,
   loadMCPTools,
+  readServerResource,
+  revokeMCPServerAuthorization,
   searchServers,
   searchTools,
   startMCPServer,
+  startMCPServerAuthorization,
   stopMCPServer,
   updateMCPServer,
   useMCPStore,
@@ -11,7 +11,16 @@ import type {
   UpdateMCPServerRequest,
   ServerActionResponse,
   SetToolGlobalApprovalRequest,
+  MCPPrompt,
+  MCPPromptResult,
+  MCPResource,
+  MCPResourceContent,
+  MCPOAuthStatus,
+  StartMCPOAuthResponse,
+  MCPServersConfig,
+  ImportMCPServersResponse,
 } from '../types/api'
+import { getBaseUrl } from '../api/core'
 import { useAdminMCPServersStore } from './admin/mcpServers'
 
 // Enable Map and Set support in Immer
@@ -436,6 +445,121 @@ export const getServerTools = async (
   }
 }
 
+export const getServerPrompts = async (
+  serverId: string,
+): Promise<MCPPrompt[]> => {
+  try {
+    return await ApiClient.Mcp.getServerPrompts({ id: serverId })
+  } catch (error) {
+    console.error('Failed to get server prompts:', error)
+    throw error
+  }
+}
+
+export const getServerPrompt = async (
+  serverId: string,
+  promptName: string,
+  args?: { [key: string]: string },
+): Promise<MCPPromptResult> => {
+  try {
+    return await ApiClient.Mcp.getServerPrompt({
+      id: serverId,
+      prompt_name: promptName,
+      arguments: args,
+    })
+  } catch (error) {
+    console.error('Failed to get server prompt:', error)
+    throw error
+  }
+}
+
+export const getServerResources = async (
+  serverId: string,
+): Promise<MCPResource[]> => {
+  try {
+    return await ApiClient.Mcp.getServerResources({ id: serverId })
+  } catch (error) {
+    console.error('Failed to get server resources:', error)
+    throw error
+  }
+}
+
+export const readServerResource = async (
+  serverId: string,
+  uri: string,
+): Promise<MCPResourceContent[]> => {
+  try {
+    return await ApiClient.Mcp.readServerResource({ id: serverId, uri })
+  } catch (error) {
+    console.error('Failed to read server resource:', error)
+    throw error
+  }
+}
+
+export const importMCPServers = async (
+  config: MCPServersConfig,
+): Promise<ImportMCPServersResponse> => {
+  try {
+    const result = await ApiClient.Mcp.importServers(config)
+
+    useMCPStore.setState(draft => {
+      draft.servers.push(...result.created)
+    })
+
+    return result
+  } catch (error) {
+    console.error('MCP server import failed:', error)
+    throw error
+  }
+}
+
+export const exportMCPServers = async (): Promise<MCPServersConfig> => {
+  try {
+    return await ApiClient.Mcp.exportServers()
+  } catch (error) {
+    console.error('MCP server export failed:', error)
+    throw error
+  }
+}
+
+export const getMCPServerAuthorization = async (
+  serverId: string,
+): Promise<MCPOAuthStatus> => {
+  try {
+    return await ApiClient.Mcp.getServerAuthorization({ id: serverId })
+  } catch (error) {
+    console.error('Failed to get server authorization:', error)
+    throw error
+  }
+}
+
+// The authorization server redirects back to this app's API, which stores the token
+export const startMCPServerAuthorization = async (
+  serverId: string,
+): Promise<StartMCPOAuthResponse> => {
+  try {
+    const baseUrl = await getBaseUrl()
+    return await ApiClient.Mcp.startServerAuthorization({
+      id: serverId,
+      redirect_uri: `${baseUrl}/api/mcp/oauth/callback`,
+    })
+  } catch (error) {
+    console.error('Failed to start server authorization:', error)
+    throw error
+  }
+}
+
+export const revokeMCPServerAuthorization = async (
+  serverId: string,
+): Promise<void> => {
+  try {
+    await ApiClient.Mcp.revokeServerAuthorization({ id: serverId })
+  } catch (error) {
+    console.error('Failed to revoke server authorization:', error)
+    throw error
+  }
+}
+
 export const getUserAssignedServers = async (): Promise<string[]> => {
   try {
     const serverIds = await ApiClient.Mcp.getUserAssignedServers()
@@ -534,6 +534,29 @@ export const bulkDeleteFiles = async (
   }
 }
 
+export const updateRAGFileTags = async (
+  instanceId: string,
+  fileId: string,
+  tags: string[],
+): Promise<RAGInstanceFile | undefined> => {
+  const store = RAGInstanceStoreMap.get(instanceId)
+  if (!store) return
+
+  const updated = await ApiClient.Rag.updateInstanceFileTags({
+    instance_id: instanceId,
+    file_id: fileId,
+    tags,
+  })
+
+  store.__setState({
+    files: store.__state.files.map((file: RAGInstanceFile) =>
+      file.file_id === fileId ? { ...file, ...updated } : file,
+    ),
+  })
+
+  return updated
+}
+
 export const searchFiles = async (instanceId: string, query: string) => {
   const store = RAGInstanceStoreMap.get(instanceId)
   if (!store) return
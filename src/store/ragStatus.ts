@@ -147,3 +147,9 @@ export const clearRAGStatusError = (): void => {
     sseError: null,
   })
 }
+
+export const cancelRAGEmbeddingMigration = async (
+  instanceId: string,
+): Promise<void> => {
+  await ApiClient.Rag.cancelEmbeddingMigration({ instance_id: instanceId })
+}
@@ -51,6 +51,36 @@ export interface ApprovalCheckResponse {
   source?: string
 }
 
+export type ApprovalRuleAction = 'approve' | 'deny'
+
+export interface ApprovalRuleCondition {
+  operator: ApprovalRuleOperator
+  path: string
+  value?: any
+}
+
+export interface ApprovalRuleDryRunRequest {
+  arguments?: any
+  server_id: string
+  tool_name: string
+  user_id?: string
+}
+
+export interface ApprovalRuleEvaluation {
+  action?: ApprovalRuleAction
+  matched_rule?: MCPToolApprovalRule
+  trace: ApprovalRuleTrace[]
+}
+
+export type ApprovalRuleOperator = 'equals' | 'not_equals' | 'starts_with' | 'ends_with' | 'contains' | 'matches' | 'path_under' | 'exists' | 'not_exists'
+
+export interface ApprovalRuleTrace {
+  matched: boolean
+  reason: string
+  rule_id: string
+  rule_name: string
+}
+
 export interface AssignServersRequest {
   server_ids: string[]
 }
@@ -119,7 +149,10 @@ export interface ChatMessageRequest {
   enabled_rag_ids?: string[]
   enabled_tools?: EnabledMCPTool[]
   file_ids?: string[]
+  mcp_prompt?: SelectedMCPPrompt
+  mcp_resources?: SelectedMCPResource[]
   message_id?: string
+  model_group_id?: string
   model_id: string
 }
 
@@ -207,11 +240,19 @@ export interface CreateMCPServerRequest {
   headers?: any
   max_restart_attempts?: number
   name: string
+  sandbox?: MCPSandboxProfile
   timeout_seconds?: number
   transport_type: MCPTransportType
   url?: string
 }
 
+export interface CreateModelGroupRequest {
+  description?: string
+  enabled?: boolean
+  model_ids: string[]
+  name: string
+}
+
 export interface CreateModelRequest {
   description?: string
   capabilities?: ModelCapabilities
@@ -291,6 +332,7 @@ export interface CreateSystemMCPServerRequest {
   headers?: any
   max_restart_attempts?: number
   name: string
+  sandbox?: MCPSandboxProfile
   timeout_seconds?: number
   transport_type: MCPTransportType
   url?: string
@@ -535,6 +577,19 @@ export interface GPUUsage {
   utilization_percentage?: number
 }
 
+export interface GeneratedImageData {
+  file_id: string
+  filename: string
+  message_content_id: string
+  message_id: string
+  mime_type: string
+  revised_prompt?: string | null
+}
+
+export interface GetMCPPromptRequest {
+  arguments?: { [key: string]: string }
+}
+
 export interface GroupAssignmentResponse {
   group_id: string
   server_ids: string[]
@@ -554,6 +609,12 @@ export interface GroupServerAssignmentResponse {
   server_assignments: GroupServerAssignment[]
 }
 
+export interface GroupUsageSummary {
+  group_id: string
+  name: string
+  usage: UsageTotals
+}
+
 export interface HardwareInfo {
   cpu: CPUInfo
   gpu_devices: GPUDevice[]
@@ -624,6 +685,11 @@ export interface HubVersionResponse {
   hub_version: string
 }
 
+export interface ImportMCPServersResponse {
+  created: MCPServer[]
+  skipped: MCPServerImportIssue[]
+}
+
 export interface InitResponse {
   allow_registration: boolean
   is_desktop: boolean
@@ -768,6 +834,65 @@ export interface MCPLogError {
 
 export type MCPLogType = 'Exec' | 'In' | 'Out' | 'Err'
 
+export interface MCPOAuthCallbackQuery {
+  code?: string
+  error?: string
+  error_description?: string
+  state?: string
+}
+
+export interface MCPOAuthStatus {
+  authorized: boolean
+  expires_at?: string
+  refreshable: boolean
+  scope?: string
+  server_id: string
+}
+
+export interface MCPPrompt {
+  arguments: any
+  discovered_at: string
+  id: string
+  prompt_description?: string
+  prompt_name: string
+  server_id: string
+}
+
+export interface MCPPromptMessage {
+  role: string
+  text: string
+}
+
+export interface MCPPromptResult {
+  description?: string
+  messages: MCPPromptMessage[]
+}
+
+export interface MCPResource {
+  discovered_at: string
+  id: string
+  mime_type?: string
+  resource_description?: string
+  resource_name: string
+  server_id: string
+  uri: string
+}
+
+export interface MCPResourceContent {
+  blob?: string
+  mime_type?: string
+  text?: string
+  uri: string
+}
+
+export interface MCPSandboxProfile {
+  cpu_time_limit_seconds?: number
+  deny_network?: boolean
+  enabled?: boolean
+  memory_limit_mb?: number
+  writable_paths?: string[]
+}
+
 export interface MCPServer {
   description?: string
   args: any
@@ -787,6 +912,7 @@ export interface MCPServer {
   port?: number
   process_id?: number
   restart_count: number
+  sandbox: any
   status: MCPServerStatus
   timeout_seconds?: number
   tool_count?: number
@@ -797,8 +923,28 @@ export interface MCPServer {
   user_id?: string
 }
 
+export interface MCPServerConfigEntry {
+  args?: string[]
+  command?: string
+  description?: string
+  disabled?: boolean
+  env?: { [key: string]: string }
+  headers?: { [key: string]: string }
+  type?: string
+  url?: string
+}
+
+export interface MCPServerImportIssue {
+  error: string
+  name: string
+}
+
 export type MCPServerStatus = 'stopped' | 'starting' | 'running' | 'error' | 'restarting'
 
+export interface MCPServersConfig {
+  mcpServers: { [key: string]: MCPServerConfigEntry }
+}
+
 export interface MCPTool {
   discovered_at: string
   id: string
@@ -810,6 +956,33 @@ export interface MCPTool {
   usage_count: number
 }
 
+export interface MCPToolApprovalRule {
+  action: ApprovalRuleAction
+  conditions: ApprovalRuleCondition[]
+  created_at: string
+  description?: string
+  enabled: boolean
+  group_id?: string
+  id: string
+  name: string
+  priority: number
+  server_id?: string
+  tool_name_pattern: string
+  updated_at: string
+}
+
+export interface MCPToolApprovalRuleRequest {
+  action: ApprovalRuleAction
+  conditions?: ApprovalRuleCondition[]
+  description?: string
+  enabled?: boolean
+  group_id?: string
+  name: string
+  priority?: number
+  server_id?: string
+  tool_name_pattern?: string
+}
+
 export interface MCPToolWithApproval {
   approval_expires_at?: string
   approval_source?: string
@@ -889,6 +1062,10 @@ export interface MessageContentChunkData {
 export interface MessageContentDataText {
   text: string
 }
+export interface MessageContentDataThinking {
+  signature?: string | null
+  thinking: string
+}
 export interface MessageContentDataToolCall {
   arguments: any
   call_id: string
@@ -916,13 +1093,19 @@ export interface MessageContentDataFileAttachment {
   file_type?: string | null
   filename: string
 }
+export interface MessageContentDataGeneratedImage {
+  file_id: string
+  filename: string
+  mime_type: string
+  revised_prompt?: string | null
+}
 export interface MessageContentDataError {
   details?: any
   error_type: string
   message: string
 }
 
-export type MessageContentData = MessageContentDataText | MessageContentDataToolCall | MessageContentDataToolCallPendingApproval | MessageContentDataToolCallPendingApprovalCancel | MessageContentDataToolResult | MessageContentDataFileAttachment | MessageContentDataError
+export type MessageContentData = MessageContentDataText | MessageContentDataThinking | MessageContentDataToolCall | MessageContentDataToolCallPendingApproval | MessageContentDataToolCallPendingApprovalCancel | MessageContentDataToolResult | MessageContentDataFileAttachment | MessageContentDataGeneratedImage | MessageContentDataError
 
 export interface MessageContentItem {
   content: MessageContentData
@@ -934,7 +1117,12 @@ export interface MessageContentItem {
   updated_at: string
 }
 
-export type MessageContentType = 'text' | 'tool_call' | 'tool_call_pending_approval' | 'tool_call_pending_approval_cancel' | 'tool_result' | 'file_attachment' | 'error'
+export interface MessageContentRetryData {
+  error: string
+  message_content_id: string
+}
+
+export type MessageContentType = 'text' | 'thinking' | 'tool_call' | 'tool_call_pending_approval' | 'tool_call_pending_approval_cancel' | 'tool_result' | 'file_attachment' | 'generated_image' | 'error'
 
 export type MessageFiles = File[]
 
@@ -943,7 +1131,7 @@ export interface MessageMetadataStruct {
   enabled_tools?: EnabledMCPTool[]
 }
 
-export type MistralRsCommand = string | string | string | string | string | string | string
+export type MistralRsCommand = string | string | string | string | string | string | string | string
 
 export interface MistralRsSettings {
   arch?: string
@@ -1015,6 +1203,7 @@ export interface ModelCapabilities {
   chat?: boolean
   code_interpreter?: boolean
   image_generator?: boolean
+  rerank?: boolean
   text_embedding?: boolean
   tools?: boolean
   vision?: boolean
@@ -1025,8 +1214,19 @@ export interface ModelEngineSettings {
   mistralrs?: MistralRsSettings
 }
 
+export interface ModelGroup {
+  created_at: string
+  description?: string
+  enabled: boolean
+  id: string
+  model_ids: string[]
+  name: string
+  updated_at: string
+}
+
 export interface ModelParameters {
   frequency_penalty?: number
+  json_schema?: any
   max_tokens?: number
   min_p?: number
   presence_penalty?: number
@@ -1035,6 +1235,7 @@ export interface ModelParameters {
   seed?: number
   stop?: string[]
   temperature?: number
+  thinking_budget?: number
   top_k?: number
   top_p?: number
 }
@@ -1263,7 +1464,7 @@ export interface ProviderListResponse {
   total: number
 }
 
-export type ProviderType = 'local' | 'openai' | 'anthropic' | 'groq' | 'gemini' | 'mistral' | 'deepseek' | 'huggingface' | 'custom'
+export type ProviderType = 'local' | 'openai' | 'anthropic' | 'groq' | 'gemini' | 'mistral' | 'deepseek' | 'huggingface' | 'ollama' | 'custom'
 
 export interface ProxySettings {
   enabled?: boolean
@@ -1283,8 +1484,40 @@ export interface ProxySettingsResponse {
   username: string
 }
 
+export interface RAGAnswer {
+  citations: RAGCitation[]
+  text: string
+}
+
 export type RAGChunkSelectionMethod = 'weight' | 'vector'
 
+export type RAGChunkingStrategy = 'token' | 'structure'
+
+export interface RAGCitation {
+  chunk_index: number
+  document_id: string
+  file_id: string
+  filename?: string
+  marker: number
+}
+
+export interface RAGEmbeddingMigration {
+  completed_at?: string
+  created_at: string
+  error_message?: string
+  id: string
+  processed_documents: number
+  rag_instance_id: string
+  source_model_id?: string
+  started_at?: string
+  status: RAGEmbeddingMigrationStatus
+  target_model_id: string
+  total_documents: number
+  updated_at: string
+}
+
+export type RAGEmbeddingMigrationStatus = 'pending' | 'running' | 'completed' | 'cancelled' | 'failed'
+
 export interface RAGEngineSettings {
   simple_graph?: RAGSimpleGraphEngineSettings
   simple_vector?: RAGSimpleVectorEngineSettings
@@ -1395,6 +1628,14 @@ export interface RAGProviderListResponse {
 
 export type RAGProviderType = 'local' | 'lightrag' | 'ragstack' | 'chroma' | 'weaviate' | 'pinecone' | 'custom'
 
+export interface RAGQueryFilter {
+  file_ids?: string[]
+  file_types?: string[]
+  tags?: string[]
+  uploaded_after?: string
+  uploaded_before?: string
+}
+
 export interface RAGQueryMetadata {
   chunks_filtered: number
   chunks_retrieved: number
@@ -1403,10 +1644,13 @@ export interface RAGQueryMetadata {
 }
 
 export interface RAGQueryRequest {
+  filter?: RAGQueryFilter
+  generate_answer?: boolean
   query: string
 }
 
 export interface RAGQueryResponse {
+  answer?: RAGAnswer
   files: File[]
   metadata: RAGQueryMetadata
   results: RAGSource[]
@@ -1439,6 +1683,8 @@ export interface RAGRepositoryListResponse {
   total: number
 }
 
+export type RAGRetrievalMode = 'vector' | 'hybrid'
+
 export interface RAGSimpleGraphEngineSettings {
   indexing?: RAGSimpleGraphIndexingSettings
   querying?: RAGSimpleGraphQueryingSettings
@@ -1447,11 +1693,14 @@ export interface RAGSimpleGraphEngineSettings {
 export interface RAGSimpleGraphIndexingSettings {
   chunk_overlap_token_size?: number
   chunk_token_size?: number
+  chunking_strategy?: RAGChunkingStrategy
+  enable_semantic_split?: boolean
   entity_extract_max_gleaning?: number
   entity_types?: string[]
   extraction_language?: string
   force_llm_summary_on_merge?: number
   max_graph_nodes?: number
+  semantic_split_threshold?: number
   summary_max_tokens?: number
 }
 
@@ -1470,6 +1719,8 @@ export interface RAGSimpleGraphQueryingSettings {
   prompt_template_pre_query?: string
   query_mode?: RAGSimpleGraphQueryMode
   related_chunk_number?: number
+  rerank_model_id?: string
+  rerank_top_k?: number
   top_k?: number
 }
 
@@ -1481,8 +1732,11 @@ export interface RAGSimpleVectorEngineSettings {
 export interface RAGSimpleVectorIndexingSettings {
   chunk_overlap_token_size?: number
   chunk_token_size?: number
+  chunking_strategy?: RAGChunkingStrategy
   embedding_batch_size?: number
+  enable_semantic_split?: boolean
   max_parallel_insert?: number
+  semantic_split_threshold?: number
 }
 
 export interface RAGSimpleVectorQueryingSettings {
@@ -1494,6 +1748,10 @@ export interface RAGSimpleVectorQueryingSettings {
   prompt_template_post_query?: string
   prompt_template_pre_query?: string
   related_chunk_number?: number
+  rerank_model_id?: string
+  rerank_top_k?: number
+  retrieval_mode?: RAGRetrievalMode
+  rrf_k?: number
   similarity_threshold?: number
   top_k?: number
 }
@@ -1551,9 +1809,14 @@ export type SSEChatStreamEvent = {
   newAssistantMessage: NewAssistantMessageData
   newMessageContent: NewMessageContentData
   messageContentChunk: MessageContentChunkData
+  newThinkingContent: NewMessageContentData
+  thinkingContentChunk: MessageContentChunkData
+  messageContentRetry: MessageContentRetryData
+  generatedImage: GeneratedImageData
   toolCall: ToolCallData
   toolCallPendingApproval: ToolCallPendingApprovalData
   toolCallPendingApprovalCancel: ToolCallPendingApprovalCancelData
+  toolProgress: ToolProgressData
   toolResult: ToolResultData
   titleUpdated: TitleUpdatedData
   maxIterationReached: MaxIterationReachedData
@@ -1615,6 +1878,7 @@ export interface SSERAGInstanceStatusErrorData {
 
 export interface SSERAGInstanceStatusUpdateData {
   current_files_processing: RAGFileProcessingStatus[]
+  embedding_migration?: RAGEmbeddingMigration
   enabled: boolean
   error_code?: RAGInstanceErrorCode
   failed_files: number
@@ -1640,6 +1904,17 @@ export interface SearchQuery {
   q: string
 }
 
+export interface SelectedMCPPrompt {
+  arguments?: { [key: string]: string }
+  name: string
+  server_id: string
+}
+
+export interface SelectedMCPResource {
+  server_id: string
+  uri: string
+}
+
 export interface ServerActionResponse {
   message: string
   success: boolean
@@ -1674,6 +1949,15 @@ export interface SourceInfo {
   id?: string
 }
 
+export interface StartMCPOAuthRequest {
+  redirect_uri?: string
+}
+
+export interface StartMCPOAuthResponse {
+  authorization_url: string
+  state: string
+}
+
 export interface StreamErrorData {
   code: string
   error: string
@@ -1760,6 +2044,15 @@ export interface ToolExecutionResponse {
   status: MCPExecutionStatus
 }
 
+export interface ToolProgressData {
+  call_id: string
+  message?: string
+  message_content_id: string
+  message_id: string
+  progress: number
+  total?: number
+}
+
 export interface ToolResultData {
   call_id: string
   error_message?: string
@@ -1804,10 +2097,18 @@ export interface UpdateMCPServerRequest {
   environment_variables?: any
   headers?: any
   max_restart_attempts?: number
+  sandbox?: MCPSandboxProfile
   timeout_seconds?: number
   url?: string
 }
 
+export interface UpdateModelGroupRequest {
+  description?: string
+  enabled?: boolean
+  model_ids?: string[]
+  name?: string
+}
+
 export interface UpdateModelRequest {
   description?: string
   capabilities?: ModelCapabilities
@@ -1851,6 +2152,10 @@ export interface UpdateProxySettingsRequest {
   username: string
 }
 
+export interface UpdateRAGInstanceFileTagsRequest {
+  tags: string[]
+}
+
 export interface UpdateRAGInstanceRequest {
   description?: string
   display_name?: string
@@ -1935,6 +2240,56 @@ export interface UploadFileResponse {
   file: File
 }
 
+export type UsagePeriod = 'daily' | 'monthly'
+
+export interface UsageQuota {
+  created_at: string
+  enabled: boolean
+  group_id?: string
+  id: string
+  max_requests?: number
+  max_tokens?: number
+  period: UsagePeriod
+  scope: UsageQuotaScope
+  updated_at: string
+  user_id?: string
+}
+
+export interface UsageQuotaRequest {
+  enabled?: boolean
+  group_id?: string
+  max_requests?: number
+  max_tokens?: number
+  period: UsagePeriod
+  scope: UsageQuotaScope
+  user_id?: string
+}
+
+export type UsageQuotaScope = 'user' | 'group' | 'api_proxy'
+
+export interface UsageQuotaStatus {
+  period_start: string
+  quota: UsageQuota
+  reached: boolean
+  resets_at: string
+  usage: UsageTotals
+}
+
+export interface UsageReport {
+  api_proxy: UsageTotals
+  groups: GroupUsageSummary[]
+  period: UsagePeriod
+  period_start: string
+  users: UserUsageSummary[]
+}
+
+export interface UsageTotals {
+  completion_tokens: number
+  prompt_tokens: number
+  requests: number
+  total_tokens: number
+}
+
 export interface User {
   created_at: string
   emails: UserEmail[]
@@ -2035,21 +2390,25 @@ export const ApiEndpoints = {
   'Admin.cancelDownload': 'POST /api/admin/downloads/{download_id}/cancel',
   'Admin.createAssistant': 'POST /api/admin/assistants',
   'Admin.createGroup': 'POST /api/admin/groups',
+  'Admin.createModelGroup': 'POST /api/admin/model-groups',
   'Admin.createProvider': 'POST /api/admin/providers',
   'Admin.createRAGRepository': 'POST /api/admin/rag/repositories',
   'Admin.createRagProvider': 'POST /api/admin/rag/providers',
   'Admin.createRepository': 'POST /api/admin/repositories',
   'Admin.createSystemRagInstance': 'POST /api/admin/rag/providers/{provider_id}/instances',
+  'Admin.createUsageQuota': 'POST /api/admin/usage-quotas',
   'Admin.createUser': 'POST /api/admin/users',
   'Admin.deleteAssistant': 'DELETE /api/admin/assistants/{assistant_id}',
   'Admin.deleteDownload': 'DELETE /api/admin/downloads/{download_id}',
   'Admin.deleteGroup': 'DELETE /api/admin/groups/{group_id}',
   'Admin.deleteModel': 'DELETE /api/admin/models/{model_id}',
+  'Admin.deleteModelGroup': 'DELETE /api/admin/model-groups/{group_id}',
   'Admin.deleteProvider': 'DELETE /api/admin/providers/{provider_id}',
   'Admin.deleteRAGRepository': 'DELETE /api/admin/rag/repositories/{repository_id}',
   'Admin.deleteRagProvider': 'DELETE /api/admin/rag/providers/{provider_id}',
   'Admin.deleteRepository': 'DELETE /api/admin/repositories/{repository_id}',
   'Admin.deleteSystemRagInstance': 'DELETE /api/admin/rag/instances/{instance_id}',
+  'Admin.deleteUsageQuota': 'DELETE /api/admin/usage-quotas/{quota_id}',
   'Admin.deleteUser': 'DELETE /api/admin/users/{user_id}',
   'Admin.disableModel': 'POST /api/admin/models/{model_id}/disable',
   'Admin.downloadFromRepository': 'POST /api/admin/models/initiate-repository-download',
@@ -2067,6 +2426,7 @@ export const ApiEndpoints = {
   'Admin.getGroupRagProviders': 'GET /api/admin/groups/{group_id}/rag_providers',
   'Admin.getHardwareInfo': 'GET /api/admin/hardware',
   'Admin.getModel': 'GET /api/admin/models/{model_id}',
+  'Admin.getModelGroup': 'GET /api/admin/model-groups/{group_id}',
   'Admin.getNgrokSettings': 'GET /api/admin/config/ngrok',
   'Admin.getNgrokStatus': 'GET /api/admin/config/ngrok/status',
   'Admin.getProvider': 'GET /api/admin/providers/{provider_id}',
@@ -2076,6 +2436,7 @@ export const ApiEndpoints = {
   'Admin.getRagProvider': 'GET /api/admin/rag/providers/{provider_id}',
   'Admin.getRepository': 'GET /api/admin/repositories/{repository_id}',
   'Admin.getSystemRagInstance': 'GET /api/admin/rag/instances/{instance_id}',
+  'Admin.getUsageReport': 'GET /api/admin/usage',
   'Admin.getUser': 'GET /api/admin/users/{user_id}',
   'Admin.getUserRegistrationStatus': 'GET /api/admin/config/user-registration',
   'Admin.listAllDownloads': 'GET /api/admin/downloads',
@@ -2084,12 +2445,14 @@ export const ApiEndpoints = {
   'Admin.listAssistants': 'GET /api/admin/assistants',
   'Admin.listEngines': 'GET /api/admin/engines',
   'Admin.listGroups': 'GET /api/admin/groups',
+  'Admin.listModelGroups': 'GET /api/admin/model-groups',
   'Admin.listProviderModels': 'GET /api/admin/providers/{provider_id}/models',
   'Admin.listProviders': 'GET /api/admin/providers',
   'Admin.listRAGRepositories': 'GET /api/admin/rag/repositories',
   'Admin.listRagProviders': 'GET /api/admin/rag/providers',
   'Admin.listRepositories': 'GET /api/admin/repositories',
   'Admin.listSystemRagInstances': 'GET /api/admin/rag/providers/{provider_id}/instances',
+  'Admin.listUsageQuotas': 'GET /api/admin/usage-quotas',
   'Admin.listUsers': 'GET /api/admin/users',
   'Admin.reloadApiProxyServerModels': 'POST /api/admin/api-proxy-server/reload/models',
   'Admin.reloadApiProxyServerTrustedHosts': 'POST /api/admin/api-proxy-server/reload/trusted-hosts',
@@ -2106,6 +2469,7 @@ export const ApiEndpoints = {
   'Admin.subscribeApiProxyServerLogs': 'GET /api/admin/api-proxy-server/logs/stream',
   'Admin.subscribeDownloadProgress': 'GET /api/admin/downloads/subscribe',
   'Admin.subscribeHardwareUsage': 'GET /api/admin/hardware/usage-stream',
+  'Admin.syncProviderModels': 'POST /api/admin/providers/{provider_id}/models/sync',
   'Admin.testRAGRepositoryConnection': 'POST /api/admin/rag/repositories/{repository_id}/test-connection',
   'Admin.testRagProvider': 'POST /api/admin/rag/providers/{provider_id}/test',
   'Admin.testRepositoryConnection': 'POST /api/admin/repositories/test',
@@ -2117,6 +2481,7 @@ export const ApiEndpoints = {
   'Admin.updateDefaultLanguage': 'PUT /api/admin/config/default-language',
   'Admin.updateGroup': 'PUT /api/admin/groups/{group_id}',
   'Admin.updateModel': 'PUT /api/admin/models/{model_id}',
+  'Admin.updateModelGroup': 'PUT /api/admin/model-groups/{group_id}',
   'Admin.updateNgrokSettings': 'PUT /api/admin/config/ngrok',
   'Admin.updateProvider': 'PUT /api/admin/providers/{provider_id}',
   'Admin.updateProxySettings': 'PUT /api/admin/config/proxy',
@@ -2124,19 +2489,28 @@ export const ApiEndpoints = {
   'Admin.updateRagProvider': 'PUT /api/admin/rag/providers/{provider_id}',
   'Admin.updateRepository': 'PUT /api/admin/repositories/{repository_id}',
   'Admin.updateSystemRagInstance': 'PUT /api/admin/rag/instances/{instance_id}',
+  'Admin.updateUsageQuota': 'PUT /api/admin/usage-quotas/{quota_id}',
   'Admin.updateUser': 'PUT /api/admin/users/{user_id}',
   'Admin.updateUserRegistrationStatus': 'PUT /api/admin/config/user-registration',
   'Admin.uploadAndCommitModel': 'POST /api/admin/uploaded-models/upload-and-commit',
   'AdminMcp.assignServersToGroup': 'POST /api/admin/mcp/groups/{group_id}/servers',
+  'AdminMcp.createApprovalRule': 'POST /api/admin/mcp/approval-rules',
   'AdminMcp.createSystemServer': 'POST /api/admin/mcp/system-servers',
+  'AdminMcp.deleteApprovalRule': 'DELETE /api/admin/mcp/approval-rules/{rule_id}',
+  'AdminMcp.dryRunApprovalRules': 'POST /api/admin/mcp/approval-rules/dry-run',
+  'AdminMcp.exportSystemServers': 'GET /api/admin/mcp/system-servers/export',
+  'AdminMcp.getApprovalRule': 'GET /api/admin/mcp/approval-rules/{rule_id}',
   'AdminMcp.getExecutionStatistics': 'GET /api/admin/mcp/execution/statistics',
   'AdminMcp.getGroupServers': 'GET /api/admin/mcp/groups/{group_id}/servers',
   'AdminMcp.getServerAccessGroups': 'GET /api/admin/mcp/servers/{server_id}/groups',
   'AdminMcp.getToolStatistics': 'GET /api/admin/mcp/tools/statistics',
+  'AdminMcp.importSystemServers': 'POST /api/admin/mcp/system-servers/import',
   'AdminMcp.listAllExecutionLogs': 'GET /api/admin/mcp/execution/logs',
   'AdminMcp.listAllGroupAssignments': 'GET /api/admin/mcp/assignments',
+  'AdminMcp.listApprovalRules': 'GET /api/admin/mcp/approval-rules',
   'AdminMcp.listSystemServers': 'GET /api/admin/mcp/system-servers',
   'AdminMcp.removeServerFromGroup': 'DELETE /api/admin/mcp/groups/{group_id}/servers/{server_id}',
+  'AdminMcp.updateApprovalRule': 'PUT /api/admin/mcp/approval-rules/{rule_id}',
   'Assistants.createAssistant': 'POST /api/assistants',
   'Assistants.deleteAssistant': 'DELETE /api/assistants/{assistant_id}',
   'Assistants.getAssistant': 'GET /api/assistants/{assistant_id}',
@@ -2185,20 +2559,30 @@ export const ApiEndpoints = {
   'Mcp.deleteConversationApproval': 'DELETE /api/mcp/approvals/conversations/{conversation_id}/tool',
   'Mcp.deleteServer': 'DELETE /api/mcp/servers/{id}',
   'Mcp.executeTool': 'POST /api/mcp/tools/execute',
+  'Mcp.exportServers': 'GET /api/mcp/servers/export',
   'Mcp.findTool': 'GET /api/mcp/tools/find',
   'Mcp.getExecutionLog': 'GET /api/mcp/execution/logs/{id}',
   'Mcp.getGlobalToolApproval': 'GET /api/mcp/servers/{server_id}/tools/{tool_name}/global-approval',
   'Mcp.getServer': 'GET /api/mcp/servers/{id}',
+  'Mcp.getServerAuthorization': 'GET /api/mcp/servers/{id}/oauth',
+  'Mcp.getServerPrompt': 'POST /api/mcp/servers/{id}/prompts/{prompt_name}',
+  'Mcp.getServerPrompts': 'GET /api/mcp/servers/{id}/prompts',
+  'Mcp.getServerResources': 'GET /api/mcp/servers/{id}/resources',
   'Mcp.getServerTools': 'GET /api/mcp/servers/{id}/tools',
   'Mcp.getUserAssignedServers': 'GET /api/mcp/user/assigned-servers',
+  'Mcp.importServers': 'POST /api/mcp/servers/import',
   'Mcp.listConversationApprovals': 'GET /api/mcp/approvals/conversations/{conversation_id}',
   'Mcp.listExecutionLogs': 'GET /api/mcp/execution/logs',
   'Mcp.listServers': 'GET /api/mcp/servers',
   'Mcp.listThreadExecutionLogs': 'GET /api/mcp/threads/{thread_id}/execution/logs',
   'Mcp.listTools': 'GET /api/mcp/tools',
+  'Mcp.oauthCallback': 'GET /api/mcp/oauth/callback',
+  'Mcp.readServerResource': 'GET /api/mcp/servers/{id}/resources/read',
   'Mcp.removeToolGlobalApproval': 'DELETE /api/mcp/servers/{server_id}/tools/{tool_name}/approve',
+  'Mcp.revokeServerAuthorization': 'DELETE /api/mcp/servers/{id}/oauth',
   'Mcp.setToolGlobalApproval': 'POST /api/mcp/servers/{server_id}/tools/{tool_name}/approve',
   'Mcp.startServer': 'POST /api/mcp/servers/{id}/start',
+  'Mcp.startServerAuthorization': 'POST /api/mcp/servers/{id}/oauth/authorize',
   'Mcp.stopServer': 'POST /api/mcp/servers/{id}/stop',
   'Mcp.streamServerLogs': 'GET /api/mcp/servers/{server_id}/logs/stream',
   'Mcp.updateServer': 'PUT /api/mcp/servers/{id}',
@@ -2210,6 +2594,7 @@ export const ApiEndpoints = {
   'Projects.listProjects': 'GET /api/projects',
   'Projects.updateProject': 'PUT /api/projects/{project_id}',
   'Providers.listEnabledProviders': 'GET /api/providers',
+  'Rag.cancelEmbeddingMigration': 'DELETE /api/rag/instances/{instance_id}/embedding-migration',
   'Rag.createInstance': 'POST /api/rag/providers/{provider_id}/instances',
   'Rag.deleteInstance': 'DELETE /api/rag/instances/{instance_id}',
   'Rag.deleteInstanceFile': 'DELETE /api/rag/instances/{instance_id}/files/{file_id}',
@@ -2221,7 +2606,9 @@ export const ApiEndpoints = {
   'Rag.subscribeInstanceStatus': 'GET /api/rag/instances/{instance_id}/status/stream',
   'Rag.toggleInstanceActivate': 'PUT /api/rag/instances/{instance_id}/toggle-activate',
   'Rag.updateInstance': 'PUT /api/rag/instances/{instance_id}',
+  'Rag.updateInstanceFileTags': 'PUT /api/rag/instances/{instance_id}/files/{file_id}/tags',
   'Rag.uploadInstanceFile': 'POST /api/rag/instances/{instance_id}/files',
+  'User.getUsage': 'GET /api/user/usage',
   'User.greet': 'POST /api/user/greet',
   'User.updateAccountPassword': 'PUT /api/admin/config/user/password',
   'UserSettings.deleteAllUserSettings': 'DELETE /api/user/settings/all',
@@ -2241,21 +2628,25 @@ export type ApiEndpointParameters = {
   'Admin.cancelDownload': { download_id: string }
   'Admin.createAssistant': CreateAssistantRequest
   'Admin.createGroup': CreateUserGroupRequest
+  'Admin.createModelGroup': CreateModelGroupRequest
   'Admin.createProvider': CreateProviderRequest
   'Admin.createRAGRepository': CreateRAGRepositoryRequest
   'Admin.createRagProvider': CreateRAGProviderRequest
   'Admin.createRepository': CreateRepositoryRequest
   'Admin.createSystemRagInstance': { provider_id: string } & CreateSystemRAGInstanceRequest
+  'Admin.createUsageQuota': UsageQuotaRequest
   'Admin.createUser': CreateUserRequest
   'Admin.deleteAssistant': { assistant_id: string }
   'Admin.deleteDownload': { download_id: string }
   'Admin.deleteGroup': { group_id: string }
   'Admin.deleteModel': { model_id: string }
+  'Admin.deleteModelGroup': { group_id: string }
   'Admin.deleteProvider': { provider_id: string }
   'Admin.deleteRAGRepository': { repository_id: string }
   'Admin.deleteRagProvider': { provider_id: string }
   'Admin.deleteRepository': { repository_id: string }
   'Admin.deleteSystemRagInstance': { instance_id: string }
+  'Admin.deleteUsageQuota': { quota_id: string }
   'Admin.deleteUser': { user_id: string }
   'Admin.disableModel': { model_id: string }
   'Admin.downloadFromRepository': DownloadFromRepositoryRequest
@@ -2273,6 +2664,7 @@ export type ApiEndpointParameters = {
   'Admin.getGroupRagProviders': { group_id: string } & PaginationQuery
   'Admin.getHardwareInfo': void
   'Admin.getModel': { model_id: string }
+  'Admin.getModelGroup': { group_id: string }
   'Admin.getNgrokSettings': void
   'Admin.getNgrokStatus': void
   'Admin.getProvider': { provider_id: string }
@@ -2282,6 +2674,7 @@ export type ApiEndpointParameters = {
   'Admin.getRagProvider': { provider_id: string }
   'Admin.getRepository': { repository_id: string }
   'Admin.getSystemRagInstance': { instance_id: string }
+  'Admin.getUsageReport': { period?: UsagePeriod }
   'Admin.getUser': { user_id: string }
   'Admin.getUserRegistrationStatus': void
   'Admin.listAllDownloads': { page?: number; per_page?: number; status?: string }
@@ -2290,12 +2683,14 @@ export type ApiEndpointParameters = {
   'Admin.listAssistants': PaginationQuery
   'Admin.listEngines': void
   'Admin.listGroups': PaginationQuery
+  'Admin.listModelGroups': void
   'Admin.listProviderModels': { provider_id: string }
   'Admin.listProviders': PaginationQuery
   'Admin.listRAGRepositories': PaginationQuery
   'Admin.listRagProviders': PaginationQuery
   'Admin.listRepositories': PaginationQuery
   'Admin.listSystemRagInstances': { provider_id: string } & PaginationQuery
+  'Admin.listUsageQuotas': void
   'Admin.listUsers': PaginationQuery
   'Admin.reloadApiProxyServerModels': void
   'Admin.reloadApiProxyServerTrustedHosts': void
@@ -2312,6 +2707,7 @@ export type ApiEndpointParameters = {
   'Admin.subscribeApiProxyServerLogs': void
   'Admin.subscribeDownloadProgress': void
   'Admin.subscribeHardwareUsage': void
+  'Admin.syncProviderModels': { provider_id: string }
   'Admin.testRAGRepositoryConnection': { repository_id: string }
   'Admin.testRagProvider': { provider_id: string }
   'Admin.testRepositoryConnection': TestRepositoryConnectionRequest
@@ -2323,6 +2719,7 @@ export type ApiEndpointParameters = {
   'Admin.updateDefaultLanguage': UpdateDefaultLanguageRequest
   'Admin.updateGroup': { group_id: string } & UpdateUserGroupRequest
   'Admin.updateModel': { model_id: string } & UpdateModelRequest
+  'Admin.updateModelGroup': { group_id: string } & UpdateModelGroupRequest
   'Admin.updateNgrokSettings': UpdateNgrokSettingsRequest
   'Admin.updateProvider': { provider_id: string } & UpdateProviderRequest
   'Admin.updateProxySettings': UpdateProxySettingsRequest
@@ -2330,19 +2727,28 @@ export type ApiEndpointParameters = {
   'Admin.updateRagProvider': { provider_id: string } & UpdateRAGProviderRequest
   'Admin.updateRepository': { repository_id: string } & UpdateRepositoryRequest
   'Admin.updateSystemRagInstance': { instance_id: string } & UpdateRAGInstanceRequest
+  'Admin.updateUsageQuota': { quota_id: string } & UsageQuotaRequest
   'Admin.updateUser': { user_id: string } & UpdateUserRequest
   'Admin.updateUserRegistrationStatus': UpdateUserRegistrationRequest
   'Admin.uploadAndCommitModel': FormData
   'AdminMcp.assignServersToGroup': { group_id: string } & AssignServersRequest
+  'AdminMcp.createApprovalRule': MCPToolApprovalRuleRequest
   'AdminMcp.createSystemServer': CreateSystemMCPServerRequest
+  'AdminMcp.deleteApprovalRule': { rule_id: string }
+  'AdminMcp.dryRunApprovalRules': ApprovalRuleDryRunRequest
+  'AdminMcp.exportSystemServers': void
+  'AdminMcp.getApprovalRule': { rule_id: string }
   'AdminMcp.getExecutionStatistics': void
   'AdminMcp.getGroupServers': { group_id: string }
   'AdminMcp.getServerAccessGroups': { server_id: string }
   'AdminMcp.getToolStatistics': void
+  'AdminMcp.importSystemServers': MCPServersConfig
   'AdminMcp.listAllExecutionLogs': { page?: number; per_page?: number; server_id?: string; status?: string; thread_id?: string }
   'AdminMcp.listAllGroupAssignments': void
+  'AdminMcp.listApprovalRules': void
   'AdminMcp.listSystemServers': { enabled?: boolean; page?: number; per_page?: number }
   'AdminMcp.removeServerFromGroup': { group_id: string; server_id: string }
+  'AdminMcp.updateApprovalRule': { rule_id: string } & MCPToolApprovalRuleRequest
   'Assistants.createAssistant': CreateAssistantRequest
   'Assistants.deleteAssistant': { assistant_id: string }
   'Assistants.getAssistant': { assistant_id: string }
@@ -2391,20 +2797,30 @@ export type ApiEndpointParameters = {
   'Mcp.deleteConversationApproval': { conversation_id: string; server_id: string; tool_name: string }
   'Mcp.deleteServer': { id: string }
   'Mcp.executeTool': ExecuteToolRequest
+  'Mcp.exportServers': void
   'Mcp.findTool': { server_id?: string }
   'Mcp.getExecutionLog': { id: string }
   'Mcp.getGlobalToolApproval': { server_id: string; tool_name: string }
   'Mcp.getServer': { id: string }
+  'Mcp.getServerAuthorization': { id: string }
+  'Mcp.getServerPrompt': { id: string; prompt_name: string } & GetMCPPromptRequest
+  'Mcp.getServerPrompts': { id: string }
+  'Mcp.getServerResources': { id: string }
   'Mcp.getServerTools': { id: string }
   'Mcp.getUserAssignedServers': void
+  'Mcp.importServers': MCPServersConfig
   'Mcp.listConversationApprovals': { conversation_id: string; approved?: boolean; include_expired?: boolean; page?: number; per_page?: number; server_id?: string; tool_name?: string }
   'Mcp.listExecutionLogs': { page?: number; per_page?: number; server_id?: string; status?: string; thread_id?: string }
   'Mcp.listServers': { page?: number; per_page?: number; status?: string }
   'Mcp.listThreadExecutionLogs': { thread_id: string }
   'Mcp.listTools': { page?: number; per_page?: number; search?: string; server_id?: string }
+  'Mcp.oauthCallback': MCPOAuthCallbackQuery
+  'Mcp.readServerResource': { id: string; uri: string }
   'Mcp.removeToolGlobalApproval': { server_id: string; tool_name: string }
+  'Mcp.revokeServerAuthorization': { id: string }
   'Mcp.setToolGlobalApproval': { server_id: string; tool_name: string } & SetToolGlobalApprovalRequest
   'Mcp.startServer': { id: string }
+  'Mcp.startServerAuthorization': { id: string } & StartMCPOAuthRequest
   'Mcp.stopServer': { id: string }
   'Mcp.streamServerLogs': { server_id: string }
   'Mcp.updateServer': { id: string } & UpdateMCPServerRequest
@@ -2416,6 +2832,7 @@ export type ApiEndpointParameters = {
   'Projects.listProjects': { page?: number; per_page?: number; search?: string }
   'Projects.updateProject': { project_id: string } & UpdateProjectRequest
   'Providers.listEnabledProviders': PaginationQuery
+  'Rag.cancelEmbeddingMigration': { instance_id: string }
   'Rag.createInstance': { provider_id: string } & CreateRAGInstanceRequest
   'Rag.deleteInstance': { instance_id: string }
   'Rag.deleteInstanceFile': { instance_id: string; file_id: string }
@@ -2427,7 +2844,9 @@ export type ApiEndpointParameters = {
   'Rag.subscribeInstanceStatus': { instance_id: string; include_files?: boolean }
   'Rag.toggleInstanceActivate': { instance_id: string }
   'Rag.updateInstance': { instance_id: string } & UpdateRAGInstanceRequest
+  'Rag.updateInstanceFileTags': { instance_id: string; file_id: string } & UpdateRAGInstanceFileTagsRequest
   'Rag.uploadInstanceFile': { instance_id: string } & FormData
+  'User.getUsage': void
   'User.greet': UserHello
   'User.updateAccountPassword': UpdateUserPasswordRequest
   'UserSettings.deleteAllUserSettings': void
@@ -2447,21 +2866,25 @@ export type ApiEndpointResponses = {
   'Admin.cancelDownload': void
   'Admin.createAssistant': Assistant
   'Admin.createGroup': UserGroup
+  'Admin.createModelGroup': ModelGroup
   'Admin.createProvider': Provider
   'Admin.createRAGRepository': RAGRepository
   'Admin.createRagProvider': RAGProvider
   'Admin.createRepository': Repository
   'Admin.createSystemRagInstance': RAGInstance
+  'Admin.createUsageQuota': UsageQuota
   'Admin.createUser': User
   'Admin.deleteAssistant': void
   'Admin.deleteDownload': void
   'Admin.deleteGroup': void
   'Admin.deleteModel': void
+  'Admin.deleteModelGroup': void
   'Admin.deleteProvider': void
   'Admin.deleteRAGRepository': void
   'Admin.deleteRagProvider': void
   'Admin.deleteRepository': void
   'Admin.deleteSystemRagInstance': void
+  'Admin.deleteUsageQuota': void
   'Admin.deleteUser': void
   'Admin.disableModel': void
   'Admin.downloadFromRepository': DownloadInstance
@@ -2479,6 +2902,7 @@ export type ApiEndpointResponses = {
   'Admin.getGroupRagProviders': RAGProviderListResponse
   'Admin.getHardwareInfo': HardwareInfoResponse
   'Admin.getModel': Model
+  'Admin.getModelGroup': ModelGroup
   'Admin.getNgrokSettings': NgrokSettingsResponse
   'Admin.getNgrokStatus': NgrokStatusResponse
   'Admin.getProvider': Provider
@@ -2488,6 +2912,7 @@ export type ApiEndpointResponses = {
   'Admin.getRagProvider': RAGProvider
   'Admin.getRepository': Repository
   'Admin.getSystemRagInstance': RAGInstance
+  'Admin.getUsageReport': UsageReport
   'Admin.getUser': User
   'Admin.getUserRegistrationStatus': UserRegistrationStatusResponse
   'Admin.listAllDownloads': DownloadInstanceListResponse
@@ -2496,12 +2921,14 @@ export type ApiEndpointResponses = {
   'Admin.listAssistants': AssistantListResponse
   'Admin.listEngines': EngineInfo[]
   'Admin.listGroups': UserGroupListResponse
+  'Admin.listModelGroups': ModelGroup[]
   'Admin.listProviderModels': Model[]
   'Admin.listProviders': ProviderListResponse
   'Admin.listRAGRepositories': RAGRepositoryListResponse
   'Admin.listRagProviders': RAGProviderListResponse
   'Admin.listRepositories': RepositoryListResponse
   'Admin.listSystemRagInstances': RAGInstanceListResponse
+  'Admin.listUsageQuotas': UsageQuota[]
   'Admin.listUsers': UserListResponse
   'Admin.reloadApiProxyServerModels': void
   'Admin.reloadApiProxyServerTrustedHosts': void
@@ -2518,6 +2945,7 @@ export type ApiEndpointResponses = {
   'Admin.subscribeApiProxyServerLogs': SSEProxyLogsEvent
   'Admin.subscribeDownloadProgress': SSEDownloadProgressEvent
   'Admin.subscribeHardwareUsage': SSEHardwareUsageEvent
+  'Admin.syncProviderModels': Model[]
   'Admin.testRAGRepositoryConnection': RAGRepositoryConnectionTestResponse
   'Admin.testRagProvider': void
   'Admin.testRepositoryConnection': TestRepositoryConnectionResponse
@@ -2529,6 +2957,7 @@ export type ApiEndpointResponses = {
   'Admin.updateDefaultLanguage': DefaultLanguageResponse
   'Admin.updateGroup': UserGroup
   'Admin.updateModel': Model
+  'Admin.updateModelGroup': ModelGroup
   'Admin.updateNgrokSettings': NgrokSettingsResponse
   'Admin.updateProvider': Provider
   'Admin.updateProxySettings': ProxySettingsResponse
@@ -2536,19 +2965,28 @@ export type ApiEndpointResponses = {
   'Admin.updateRagProvider': RAGProvider
   'Admin.updateRepository': Repository
   'Admin.updateSystemRagInstance': RAGInstance
+  'Admin.updateUsageQuota': UsageQuota
   'Admin.updateUser': User
   'Admin.updateUserRegistrationStatus': UserRegistrationStatusResponse
   'Admin.uploadAndCommitModel': Model
   'AdminMcp.assignServersToGroup': GroupAssignmentResponse
+  'AdminMcp.createApprovalRule': MCPToolApprovalRule
   'AdminMcp.createSystemServer': MCPServer
+  'AdminMcp.deleteApprovalRule': void
+  'AdminMcp.dryRunApprovalRules': ApprovalRuleEvaluation
+  'AdminMcp.exportSystemServers': MCPServersConfig
+  'AdminMcp.getApprovalRule': MCPToolApprovalRule
   'AdminMcp.getExecutionStatistics': any
   'AdminMcp.getGroupServers': string[]
   'AdminMcp.getServerAccessGroups': string[]
   'AdminMcp.getToolStatistics': any[][]
+  'AdminMcp.importSystemServers': ImportMCPServersResponse
   'AdminMcp.listAllExecutionLogs': ListExecutionLogsResponse
   'AdminMcp.listAllGroupAssignments': GroupServerAssignmentResponse[]
+  'AdminMcp.listApprovalRules': MCPToolApprovalRule[]
   'AdminMcp.listSystemServers': ListServersResponse
   'AdminMcp.removeServerFromGroup': void
+  'AdminMcp.updateApprovalRule': MCPToolApprovalRule
   'Assistants.createAssistant': Assistant
   'Assistants.deleteAssistant': void
   'Assistants.getAssistant': Assistant
@@ -2597,20 +3035,30 @@ export type ApiEndpointResponses = {
   'Mcp.deleteConversationApproval': SimpleResponse
   'Mcp.deleteServer': void
   'Mcp.executeTool': ToolExecutionResponse
+  'Mcp.exportServers': MCPServersConfig
   'Mcp.findTool': MCPToolWithServer | null
   'Mcp.getExecutionLog': MCPExecutionLog
   'Mcp.getGlobalToolApproval': ToolApprovalResponse
   'Mcp.getServer': MCPServer
+  'Mcp.getServerAuthorization': MCPOAuthStatus
+  'Mcp.getServerPrompt': MCPPromptResult
+  'Mcp.getServerPrompts': MCPPrompt[]
+  'Mcp.getServerResources': MCPResource[]
   'Mcp.getServerTools': MCPTool[]
   'Mcp.getUserAssignedServers': string[]
+  'Mcp.importServers': ImportMCPServersResponse
   'Mcp.listConversationApprovals': ToolApprovalResponse[]
   'Mcp.listExecutionLogs': ListExecutionLogsResponse
   'Mcp.listServers': ListServersResponse
   'Mcp.listThreadExecutionLogs': MCPExecutionLog[]
   'Mcp.listTools': ListToolsResponse
+  'Mcp.oauthCallback': string
+  'Mcp.readServerResource': MCPResourceContent[]
   'Mcp.removeToolGlobalApproval': any
+  'Mcp.revokeServerAuthorization': void
   'Mcp.setToolGlobalApproval': any
   'Mcp.startServer': ServerActionResponse
+  'Mcp.startServerAuthorization': StartMCPOAuthResponse
   'Mcp.stopServer': ServerActionResponse
   'Mcp.streamServerLogs': SSEMCPLogEvent
   'Mcp.updateServer': MCPServer
@@ -2622,6 +3070,7 @@ export type ApiEndpointResponses = {
   'Projects.listProjects': ProjectListResponse
   'Projects.updateProject': Project
   'Providers.listEnabledProviders': ProviderListResponse
+  'Rag.cancelEmbeddingMigration': void
   'Rag.createInstance': RAGInstance
   'Rag.deleteInstance': void
   'Rag.deleteInstanceFile': FileOperationSuccessResponse
@@ -2633,7 +3082,9 @@ export type ApiEndpointResponses = {
   'Rag.subscribeInstanceStatus': SSERAGStatusEvent
   'Rag.toggleInstanceActivate': RAGInstance
   'Rag.updateInstance': RAGInstance
+  'Rag.updateInstanceFileTags': RAGInstanceFile
   'Rag.uploadInstanceFile': UploadFileResponse
+  'User.getUsage': UserUsageResponse
   'User.greet': string
   'User.updateAccountPassword': void
   'UserSettings.deleteAllUserSettings': UserSettingsDeletionResponse
@@ -2690,3 +3141,15 @@ type ValidateResponsesComplete = {
 // Type-safe validation - these will cause a TypeScript error if any endpoint is missing
 // from Parameters or Responses. They are used for compile-time validation only.
 export type { ValidateParametersComplete, ValidateResponsesComplete }
+
+export interface UserUsageResponse {
+  quotas: UsageQuotaStatus[]
+  this_month: UsageTotals
+  today: UsageTotals
+}
+
+export interface UserUsageSummary {
+  usage: UsageTotals
+  user_id: string
+  username: string
+}